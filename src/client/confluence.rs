@@ -0,0 +1,336 @@
+//! Confluence Cloud REST v2: pages, blog posts and spaces
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::fmt;
+
+use super::models::{Listing, Page, Space};
+use super::rest;
+use crate::config::Config;
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+
+/// [`Confluence::update_page`] was refused with 409: the page is no longer at
+/// the version before the one sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionConflict {
+    pub page_id: String,
+    /// The version the update would have created
+    pub version: u64,
+}
+
+impl fmt::Display for VersionConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Failed to update page: page {} changed before version {} was saved",
+            self.page_id, self.version
+        )
+    }
+}
+
+impl std::error::Error for VersionConflict {}
+
+/// Largest `id` list (and `limit`) of the v2 list endpoints
+const MAX_IDS_PER_LIST: usize = 250;
+
+#[derive(Deserialize)]
+struct Results<T> {
+    #[serde(default = "Vec::new")]
+    results: Vec<T>,
+}
+
+/// `query` arguments below are extra query parameters such as
+/// `body-format=storage`; `tools::confluence::field_filtering::apply_v2_filtering`
+/// builds the ones the tools send
+pub struct Confluence<'a> {
+    config: &'a Config,
+    http: reqwest::Client,
+}
+
+impl<'a> Confluence<'a> {
+    pub fn new(config: &'a Config) -> Self {
+        Self::with_client(config, create_atlassian_client(config))
+    }
+
+    /// Shares an existing HTTP client (and its connection pool)
+    pub fn with_client(config: &'a Config, http: reqwest::Client) -> Self {
+        Self { config, http }
+    }
+
+    pub fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!(
+            "{}/wiki/api/v2/{}",
+            self.config.get_atlassian_base_url(),
+            path
+        )
+    }
+
+    fn get<Q: Serialize + ?Sized>(&self, url: &str, query: &Q) -> reqwest::RequestBuilder {
+        self.http
+            .get(url)
+            .header("Authorization", create_auth_header(self.config))
+            .header("Accept", "application/json")
+            .query(query)
+    }
+
+    fn send_json(
+        &self,
+        request: reqwest::RequestBuilder,
+        query: &[(String, String)],
+        body: &Value,
+    ) -> reqwest::RequestBuilder {
+        request
+            .header("Authorization", create_auth_header(self.config))
+            .header("Content-Type", "application/json")
+            .query(query)
+            .json(body)
+    }
+
+    pub async fn get_page(&self, page_id: &str, query: &[(String, String)]) -> Result<Page> {
+        let url = self.url(&format!("pages/{}", page_id));
+        let page = rest::get_json(&self.http, self.config, &url, query, "page").await?;
+        Ok(serde_json::from_value(page)?)
+    }
+
+    /// [`get_page`](Self::get_page) from the site, never the response
+    /// cache; for reads a write depends on
+    pub async fn get_current_page(
+        &self,
+        page_id: &str,
+        query: &[(String, String)],
+    ) -> Result<Page> {
+        let url = self.url(&format!("pages/{}", page_id));
+        let page = rest::get_current_json(&self.http, self.config, &url, query, "page").await?;
+        Ok(serde_json::from_value(page)?)
+    }
+
+    /// Pages by ID, in any order; IDs that are missing or not visible are
+    /// left out
+    pub async fn get_pages(&self, ids: &[&str], body_format: &str) -> Result<Vec<Page>> {
+        self.list_by_id("pages", ids, body_format).await
+    }
+
+    /// Blog posts by ID, like [`get_pages`](Self::get_pages)
+    pub async fn get_blog_posts(&self, ids: &[&str], body_format: &str) -> Result<Vec<Page>> {
+        self.list_by_id("blogposts", ids, body_format).await
+    }
+
+    async fn list_by_id(
+        &self,
+        collection: &str,
+        ids: &[&str],
+        body_format: &str,
+    ) -> Result<Vec<Page>> {
+        let mut found = Vec::with_capacity(ids.len());
+        for ids in ids.chunks(MAX_IDS_PER_LIST) {
+            let query = [
+                ("id", ids.join(",")),
+                ("body-format", body_format.to_string()),
+                ("limit", ids.len().to_string()),
+            ];
+            let response = self.get(&self.url(collection), &query).send().await?;
+            if !response.status().is_success() {
+                anyhow::bail!("Failed to get {}: {}", collection, response.status());
+            }
+            found.extend(response.json::<Results<Page>>().await?.results);
+        }
+        Ok(found)
+    }
+
+    /// The page (or, with `version`, that past version) with its version
+    /// number and storage body, as an edit starts from
+    pub async fn get_page_storage(&self, page_id: &str, version: Option<u64>) -> Result<Page> {
+        let mut query = vec![
+            ("include-version", "true".to_string()),
+            ("body-format", "storage".to_string()),
+        ];
+        if let Some(version) = version {
+            query.push(("version", version.to_string()));
+        }
+
+        // Edits start from here, so this is never a cached response
+        let url = self.url(&format!("pages/{}", page_id));
+        let page = rest::get_current_json(&self.http, self.config, &url, &query, "page for update")
+            .await?;
+        Ok(serde_json::from_value(page)?)
+    }
+
+    /// One page of direct child pages; `query` may carry `cursor` and `limit`
+    pub async fn get_page_children(
+        &self,
+        page_id: &str,
+        query: &[(String, String)],
+    ) -> Result<Listing<Page>> {
+        #[derive(Deserialize, Default)]
+        struct Links {
+            next: Option<String>,
+        }
+        #[derive(Deserialize)]
+        struct Children {
+            #[serde(default)]
+            results: Vec<Page>,
+            #[serde(default, rename = "_links")]
+            links: Links,
+        }
+
+        let response = self
+            .get(&self.url(&format!("pages/{}/children", page_id)), query)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to get child pages: {}", response.status());
+        }
+        let children = response.json::<Children>().await?;
+        Ok(Listing {
+            results: children.results,
+            next: children.links.next,
+        })
+    }
+
+    /// The v2 space ID for a space key
+    pub async fn space_id(&self, space_key: &str) -> Result<String> {
+        self.get_spaces_by_key(&[space_key])
+            .await?
+            .into_iter()
+            .next()
+            .map(|space| space.id)
+            .ok_or_else(|| anyhow::anyhow!("Space '{}' not found", space_key))
+    }
+
+    /// Spaces by key, in any order; unknown keys are left out
+    pub async fn get_spaces_by_key(&self, keys: &[&str]) -> Result<Vec<Space>> {
+        let mut found = Vec::with_capacity(keys.len());
+        for keys in keys.chunks(MAX_IDS_PER_LIST) {
+            let query = [("keys", keys.join(",")), ("limit", keys.len().to_string())];
+            let response = self.get(&self.url("spaces"), &query).send().await?;
+            if !response.status().is_success() {
+                anyhow::bail!("Failed to get spaces: {}", response.status());
+            }
+            found.extend(response.json::<Results<Space>>().await?.results);
+        }
+        Ok(found)
+    }
+
+    /// The current page with exactly this title in the space, if any
+    pub async fn find_page_by_title(&self, space_id: &str, title: &str) -> Result<Option<Page>> {
+        let response = self
+            .get(
+                &self.url("pages"),
+                &[
+                    ("space-id", space_id),
+                    ("title", title),
+                    ("status", "current"),
+                    ("limit", "1"),
+                ],
+            )
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to look up page by title: {}", response.status());
+        }
+        Ok(response
+            .json::<Results<Page>>()
+            .await?
+            .results
+            .into_iter()
+            .next())
+    }
+
+    /// A page with a storage-format body; at the space root without `parent_id`
+    pub async fn create_page(
+        &self,
+        space_id: &str,
+        title: &str,
+        storage: &str,
+        parent_id: Option<&str>,
+        query: &[(String, String)],
+    ) -> Result<Page> {
+        let body = create_page_body(space_id, title, storage, parent_id);
+        let response = self
+            .send_json(self.http.post(self.url("pages")), query, &body)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to create page: {}", error);
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Saves `storage` as `version` (the current version + 1); a 409 fails
+    /// with [`VersionConflict`], so callers can re-read and retry
+    pub async fn update_page(
+        &self,
+        page_id: &str,
+        title: &str,
+        storage: &str,
+        version: u64,
+        query: &[(String, String)],
+    ) -> Result<Page> {
+        let body = json!({
+            "id": page_id,
+            "title": title,
+            "body": {
+                "representation": "storage",
+                "value": storage
+            },
+            "version": {
+                "number": version
+            }
+        });
+
+        let url = self.url(&format!("pages/{}", page_id));
+        let response = self
+            .send_json(self.http.put(url), query, &body)
+            .send()
+            .await?;
+        if response.status() == reqwest::StatusCode::CONFLICT {
+            return Err(VersionConflict {
+                page_id: page_id.to_string(),
+                version,
+            }
+            .into());
+        }
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to update page: {}", error);
+        }
+        Ok(response.json().await?)
+    }
+}
+
+/// v2 create body; `parentId` omitted for a space-root page
+fn create_page_body(space_id: &str, title: &str, content: &str, parent_id: Option<&str>) -> Value {
+    let mut body = json!({
+        "spaceId": space_id,
+        "title": title,
+        "body": {
+            "representation": "storage",
+            "value": content
+        }
+    });
+    if let Some(parent_id) = parent_id {
+        body["parentId"] = json!(parent_id);
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_page_body_parent_id() {
+        let body = create_page_body("space123", "Child", "<p>x</p>", Some("98765"));
+        assert_eq!(body["parentId"], "98765");
+        assert_eq!(body["spaceId"], "space123");
+
+        let root = create_page_body("space123", "Root", "<p>x</p>", None);
+        assert!(root.get("parentId").is_none());
+    }
+}
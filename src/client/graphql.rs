@@ -0,0 +1,363 @@
+//! Atlassian GraphQL gateway: `/gateway/api/graphql` on the site
+//!
+//! Compass and Teams data is only served here. Requests reuse the site's
+//! HTTP client and Basic credentials, and the cloud ID most queries take is
+//! resolved once per process ([`fetch_cloud_id`]).
+//!
+//! Documents are sent as automatic persisted queries: the first request
+//! carries the document with its SHA-256, later ones only the hash. A
+//! gateway that no longer knows a hash answers `PersistedQueryNotFound` and
+//! gets the document again; one that rejects the extension is remembered
+//! and sent plain documents from then on.
+//!
+//! The gateway limits each request's query cost, so [`Gateway::query_each`]
+//! runs one selection per item as aliased fields of as few requests as fit
+//! a cost budget, and reports errors per item.
+
+use anyhow::Result;
+use serde_json::{Map, Value, json};
+use std::sync::Mutex;
+
+use crate::config::Config;
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header, fetch_cloud_id};
+use crate::utils::sha256;
+
+/// Estimated cost points per request [`Gateway::query_each`] stays under
+pub const DEFAULT_COST_BUDGET: u32 = 1000;
+
+/// `(site, document hash)` the gateway has been sent the document for
+static REGISTERED: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+/// Sites whose gateway rejected the persisted query extension
+static UNSUPPORTED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// One selection run per item by [`Gateway::query_each`]
+#[derive(Debug, Clone, Copy)]
+pub struct Batched<'d> {
+    /// `(name, type)` of the variables one item sets, e.g. `("id", "ID!")`
+    pub variables: &'d [(&'d str, &'d str)],
+    /// One root field with its selection, using the variables above
+    pub selection: &'d str,
+    /// Estimated cost points of one item
+    pub cost: u32,
+}
+
+/// Gateway calls for one configuration
+pub struct Gateway<'a> {
+    config: &'a Config,
+    http: reqwest::Client,
+    auth: String,
+}
+
+impl<'a> Gateway<'a> {
+    pub fn new(config: &'a Config) -> Self {
+        Self::with_client(config, create_atlassian_client(config))
+    }
+
+    /// Shares an existing HTTP client (and its connection pool)
+    pub fn with_client(config: &'a Config, http: reqwest::Client) -> Self {
+        Self {
+            auth: create_auth_header(config),
+            config,
+            http,
+        }
+    }
+
+    /// The site's cloud ID, the `cloudId`/`siteId` argument of most queries
+    pub async fn cloud_id(&self) -> Result<String> {
+        fetch_cloud_id(&self.http, self.config).await
+    }
+
+    async fn post(&self, body: &Value) -> Result<(reqwest::StatusCode, Value)> {
+        let response = self
+            .http
+            .post(format!(
+                "{}/gateway/api/graphql",
+                self.config.get_atlassian_base_url()
+            ))
+            .header("Authorization", &self.auth)
+            .header("Content-Type", "application/json")
+            .header("X-ExperimentalApi", "compass-beta")
+            .json(body)
+            .send()
+            .await?;
+        let status = response.status();
+        let body = if status.is_success() {
+            response.json().await?
+        } else {
+            Value::Null
+        };
+        Ok((status, body))
+    }
+
+    /// The full response of `document` with `variables`, sent as a
+    /// persisted query where the gateway supports it
+    async fn execute(&self, document: &str, variables: &Value) -> Result<Value> {
+        let site = self.config.get_atlassian_base_url().to_string();
+        let hash = sha256::hex_digest(document.as_bytes());
+        let plain = json!({"query": document, "variables": variables});
+        if UNSUPPORTED
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(&site)
+        {
+            return checked(self.post(&plain).await?);
+        }
+
+        let extensions = json!({"persistedQuery": {"version": 1, "sha256Hash": hash}});
+        let registered = REGISTERED
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .any(|(s, h)| *s == site && *h == hash);
+        let mut reply = if registered {
+            self.post(&json!({"variables": variables, "extensions": extensions}))
+                .await?
+        } else {
+            (reqwest::StatusCode::NOT_FOUND, Value::Null)
+        };
+        if !registered || persisted_error(&reply.1) == Some(PersistedError::NotFound) {
+            reply = self
+                .post(&json!({"query": document, "variables": variables, "extensions": extensions}))
+                .await?;
+        }
+
+        let rejected = reply.0 == reqwest::StatusCode::BAD_REQUEST
+            || persisted_error(&reply.1) == Some(PersistedError::NotSupported);
+        if rejected {
+            let plain_reply = self.post(&plain).await?;
+            if plain_reply.0.is_success() {
+                UNSUPPORTED
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push(site);
+            }
+            return checked(plain_reply);
+        }
+        if reply.0.is_success() && !registered {
+            REGISTERED
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push((site, hash));
+        }
+        checked(reply)
+    }
+
+    /// The `data` of `document`; fails on errors only when there is no data
+    /// at all, as fields may fail on their own
+    pub async fn query(&self, document: &str, variables: Value) -> Result<Value> {
+        let body = self.execute(document, &variables).await?;
+        if body["data"].is_null() {
+            anyhow::bail!(
+                "GraphQL error: {}",
+                body["errors"][0]["message"]
+                    .as_str()
+                    .unwrap_or("no data returned")
+            );
+        }
+        Ok(body["data"].clone())
+    }
+
+    /// Runs `batched` once per item of variable values, as few requests as
+    /// keep under `cost_budget`; each result is the item's root field
+    pub async fn query_each(
+        &self,
+        batched: &Batched<'_>,
+        items: &[Map<String, Value>],
+        cost_budget: u32,
+    ) -> Result<Vec<Result<Value>>> {
+        let per_request = (cost_budget / batched.cost.max(1)).max(1) as usize;
+        let mut results = Vec::with_capacity(items.len());
+        for chunk in items.chunks(per_request) {
+            let (document, variables) = batch_document(batched, chunk);
+            let body = self.execute(&document, &variables).await?;
+            results.extend(batch_results(&body, chunk.len()));
+        }
+        Ok(results)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum PersistedError {
+    NotFound,
+    NotSupported,
+}
+
+fn persisted_error(body: &Value) -> Option<PersistedError> {
+    body["errors"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find_map(|error| {
+            let code = error["extensions"]["code"].as_str().unwrap_or_default();
+            match error["message"].as_str().unwrap_or_default() {
+                "PersistedQueryNotFound" => Some(PersistedError::NotFound),
+                "PersistedQueryNotSupported" => Some(PersistedError::NotSupported),
+                _ if code == "PERSISTED_QUERY_NOT_FOUND" => Some(PersistedError::NotFound),
+                _ if code == "PERSISTED_QUERY_NOT_SUPPORTED" => Some(PersistedError::NotSupported),
+                _ => None,
+            }
+        })
+}
+
+fn checked((status, body): (reqwest::StatusCode, Value)) -> Result<Value> {
+    if !status.is_success() {
+        anyhow::bail!("GraphQL request failed: {}", status);
+    }
+    Ok(body)
+}
+
+/// `selection` with each `$name` of `names` renamed to `$name<suffix>`
+fn rename_variables(selection: &str, names: &[&str], suffix: usize) -> String {
+    let mut out = String::with_capacity(selection.len() + 8);
+    let mut rest = selection;
+    while let Some(start) = rest.find('$') {
+        out.push_str(&rest[..=start]);
+        rest = &rest[start + 1..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        out.push_str(&rest[..len]);
+        if names.contains(&&rest[..len]) {
+            out.push_str(&suffix.to_string());
+        }
+        rest = &rest[len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// One document with an `item<i>:` aliased copy of the selection per item
+fn batch_document(batched: &Batched<'_>, items: &[Map<String, Value>]) -> (String, Value) {
+    let names: Vec<&str> = batched.variables.iter().map(|(name, _)| *name).collect();
+    let mut declarations = Vec::new();
+    let mut fields = Vec::new();
+    let mut variables = Map::new();
+    for (i, item) in items.iter().enumerate() {
+        for (name, kind) in batched.variables {
+            declarations.push(format!("${}{}: {}", name, i, kind));
+            variables.insert(
+                format!("{}{}", name, i),
+                item.get(*name).cloned().unwrap_or(Value::Null),
+            );
+        }
+        fields.push(format!(
+            "item{}: {}",
+            i,
+            rename_variables(batched.selection.trim(), &names, i)
+        ));
+    }
+    let declarations = if declarations.is_empty() {
+        String::new()
+    } else {
+        format!("({})", declarations.join(", "))
+    };
+    (
+        format!("query batch{} {{\n{}\n}}", declarations, fields.join("\n")),
+        Value::Object(variables),
+    )
+}
+
+/// Per item its aliased field, or the first error whose path starts there
+fn batch_results(body: &Value, count: usize) -> Vec<Result<Value>> {
+    (0..count)
+        .map(|i| {
+            let alias = format!("item{}", i);
+            let error = body["errors"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .find(|error| error["path"][0] == alias.as_str());
+            match (&body["data"][&alias], error) {
+                (Value::Null, Some(error)) => Err(anyhow::anyhow!(
+                    "GraphQL error: {}",
+                    error["message"].as_str().unwrap_or("unknown error")
+                )),
+                (Value::Null, None) if body["data"].is_null() => Err(anyhow::anyhow!(
+                    "GraphQL error: {}",
+                    body["errors"][0]["message"]
+                        .as_str()
+                        .unwrap_or("no data returned")
+                )),
+                (value, _) => Ok(value.clone()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCORE: Batched<'static> = Batched {
+        variables: &[("id", "ID!"), ("scorecardId", "ID!")],
+        selection: "compass { component(id: $id) { ... on CompassComponent { scorecardScore(query: {scorecardId: $scorecardId}) { totalScore } } } }",
+        cost: 10,
+    };
+
+    #[test]
+    fn test_rename_variables_whole_names_only() {
+        assert_eq!(
+            rename_variables("f(id: $id, ids: $ids, other: $idx)", &["id", "ids"], 3),
+            "f(id: $id3, ids: $ids3, other: $idx)"
+        );
+    }
+
+    #[test]
+    fn test_batch_document_aliases_items() {
+        let items: Vec<Map<String, Value>> = ["s-1", "s-2"]
+            .iter()
+            .map(|scorecard| {
+                json!({"id": "ari:c", "scorecardId": scorecard})
+                    .as_object()
+                    .cloned()
+                    .unwrap()
+            })
+            .collect();
+        let (document, variables) = batch_document(&SCORE, &items);
+        assert!(document.starts_with(
+            "query batch($id0: ID!, $scorecardId0: ID!, $id1: ID!, $scorecardId1: ID!) {"
+        ));
+        assert!(document.contains("item1: compass { component(id: $id1)"));
+        assert!(document.contains("{scorecardId: $scorecardId1}"));
+        assert_eq!(variables["scorecardId1"], "s-2");
+        assert_eq!(variables["id0"], "ari:c");
+    }
+
+    #[test]
+    fn test_batch_results_per_item_errors() {
+        let body = json!({
+            "data": {"item0": {"ok": 1}, "item1": null},
+            "errors": [{"message": "Scorecard not found", "path": ["item1", "component"]}]
+        });
+        let results = batch_results(&body, 2);
+        assert_eq!(results[0].as_ref().unwrap()["ok"], 1);
+        assert!(
+            results[1]
+                .as_ref()
+                .unwrap_err()
+                .to_string()
+                .contains("Scorecard not found")
+        );
+
+        let failed = batch_results(&json!({"errors": [{"message": "Rate limited"}]}), 1);
+        assert!(
+            failed[0]
+                .as_ref()
+                .unwrap_err()
+                .to_string()
+                .contains("Rate limited")
+        );
+    }
+
+    #[test]
+    fn test_persisted_error_codes() {
+        let not_found = json!({"errors": [{"message": "PersistedQueryNotFound"}]});
+        let not_supported = json!({"errors": [{"message": "x", "extensions": {"code": "PERSISTED_QUERY_NOT_SUPPORTED"}}]});
+        assert_eq!(persisted_error(&not_found), Some(PersistedError::NotFound));
+        assert_eq!(
+            persisted_error(&not_supported),
+            Some(PersistedError::NotSupported)
+        );
+        assert_eq!(persisted_error(&json!({"data": {}})), None);
+    }
+}
@@ -0,0 +1,340 @@
+//! Jira Cloud REST v3: issues, search, comments and transitions
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use super::models::{ChangelogPage, Comment, CommentPage, Issue, IssueRef, Transition};
+use super::rest;
+use crate::config::Config;
+use crate::tools::jira::field_filtering;
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+
+/// One page of `/rest/api/3/search/jql`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPage {
+    #[serde(default)]
+    pub issues: Vec<Issue>,
+    pub next_page_token: Option<String>,
+    pub is_last: Option<bool>,
+    pub total: Option<u64>,
+}
+
+/// Jira calls for one configuration
+pub struct Jira<'a> {
+    config: &'a Config,
+    http: reqwest::Client,
+}
+
+impl<'a> Jira<'a> {
+    pub fn new(config: &'a Config) -> Self {
+        Self::with_client(config, create_atlassian_client(config))
+    }
+
+    /// Shares an existing HTTP client (and its connection pool)
+    pub fn with_client(config: &'a Config, http: reqwest::Client) -> Self {
+        Self { config, http }
+    }
+
+    pub fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!(
+            "{}/rest/api/3/{}",
+            self.config.get_atlassian_base_url(),
+            path
+        )
+    }
+
+    fn get(&self, url: &str) -> reqwest::RequestBuilder {
+        self.http
+            .get(url)
+            .header("Authorization", create_auth_header(self.config))
+            .header("Accept", "application/json")
+    }
+
+    fn send_json(&self, request: reqwest::RequestBuilder, body: &Value) -> reqwest::RequestBuilder {
+        request
+            .header("Authorization", create_auth_header(self.config))
+            .header("Content-Type", "application/json")
+            .json(body)
+    }
+
+    /// The issue with the essential fields, without rendered HTML
+    pub async fn get_issue(&self, issue_key: &str) -> Result<Issue> {
+        self.fetch_issue(issue_key, false, true).await
+    }
+
+    /// [`get_issue`](Self::get_issue) with the `changelog` (in `extra`)
+    pub async fn get_issue_with_changelog(&self, issue_key: &str) -> Result<Issue> {
+        self.fetch_issue(issue_key, true, true).await
+    }
+
+    /// [`get_issue`](Self::get_issue) from the site, never the response
+    /// cache; for reads a write depends on
+    pub async fn get_current_issue(&self, issue_key: &str) -> Result<Issue> {
+        self.fetch_issue(issue_key, false, false).await
+    }
+
+    async fn fetch_issue(&self, issue_key: &str, changelog: bool, cached: bool) -> Result<Issue> {
+        let mut url = field_filtering::apply_field_filtering_to_url(
+            &self.url(&format!("issue/{}", issue_key)),
+        );
+        if changelog {
+            // One `expand` is honoured; the changelog one does not render fields either
+            url = url.replace("expand=-renderedFields", "expand=changelog");
+        }
+
+        let no_query: &[(&str, &str)] = &[];
+        let issue = if cached {
+            rest::get_json(&self.http, self.config, &url, no_query, "issue").await?
+        } else {
+            rest::get_current_json(&self.http, self.config, &url, no_query, "issue").await?
+        };
+        Ok(serde_json::from_value(issue)?)
+    }
+
+    /// One page of results; `next_page_token` continues a previous page
+    pub async fn search(
+        &self,
+        jql: &str,
+        fields: &[String],
+        max_results: u64,
+        next_page_token: Option<&str>,
+    ) -> Result<SearchPage> {
+        let mut query_params = vec![
+            ("jql".to_string(), jql.to_string()),
+            ("maxResults".to_string(), max_results.to_string()),
+            ("fields".to_string(), fields.join(",")),
+        ];
+        if let Some(token) = next_page_token {
+            query_params.push(("nextPageToken".to_string(), token.to_string()));
+        }
+
+        let response = self
+            .get(&self.url("search/jql"))
+            .query(&query_params)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Search failed: {}", error);
+        }
+        Ok(response.json().await?)
+    }
+
+    /// `description` is ADF (or null)
+    pub async fn create_issue(
+        &self,
+        project_key: &str,
+        summary: &str,
+        issue_type: &str,
+        description: Value,
+    ) -> Result<IssueRef> {
+        let url = field_filtering::apply_field_filtering_to_url(&self.url("issue"));
+        let body = json!({
+            "fields": {
+                "project": {
+                    "key": project_key
+                },
+                "summary": summary,
+                "issuetype": {
+                    "name": issue_type
+                },
+                "description": description
+            }
+        });
+
+        let response = self.send_json(self.http.post(&url), &body).send().await?;
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to create issue: {}", error);
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Sets `fields` as given (ADF for rich text, `YYYY-MM-DD` dates)
+    pub async fn update_issue(&self, issue_key: &str, fields: Map<String, Value>) -> Result<()> {
+        let url = self.url(&format!("issue/{}", issue_key));
+        let response = self
+            .send_json(self.http.put(&url), &json!({ "fields": fields }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error = response.text().await.unwrap_or_default();
+            anyhow::bail!("Failed to update issue ({}): {}", status, error);
+        }
+        Ok(())
+    }
+
+    pub async fn get_comment(&self, issue_key: &str, comment_id: &str) -> Result<Comment> {
+        let url = self.url(&format!("issue/{}/comment/{}", issue_key, comment_id));
+        let response = self.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            anyhow::bail!("Comment {} not found on {}", comment_id, issue_key);
+        }
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to get comment: {}", response.status());
+        }
+        Ok(response.json().await?)
+    }
+
+    /// One page of the issue's comments, oldest first unless `newest_first`
+    pub async fn get_comments(
+        &self,
+        issue_key: &str,
+        start_at: usize,
+        max_results: usize,
+        newest_first: bool,
+    ) -> Result<CommentPage> {
+        let url = self.url(&format!("issue/{}/comment", issue_key));
+        let order = if newest_first { "-created" } else { "created" };
+        let response = self
+            .get(&url)
+            .query(&[
+                ("orderBy", order.to_string()),
+                ("startAt", start_at.to_string()),
+                ("maxResults", max_results.to_string()),
+            ])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to get comments: {}", response.status());
+        }
+        Ok(response.json().await?)
+    }
+
+    /// One page of the issue's changelog, oldest entries first
+    pub async fn get_changelog(
+        &self,
+        issue_key: &str,
+        start_at: usize,
+        max_results: usize,
+    ) -> Result<ChangelogPage> {
+        let url = self.url(&format!("issue/{}/changelog", issue_key));
+        let response = self
+            .get(&url)
+            .query(&[
+                ("startAt", start_at.to_string()),
+                ("maxResults", max_results.to_string()),
+            ])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to get changelog: {}", response.status());
+        }
+        Ok(response.json().await?)
+    }
+
+    /// `body` is ADF; `visibility` is `{"type": "role"|"group", "value": name}`
+    pub async fn add_comment(
+        &self,
+        issue_key: &str,
+        body: Value,
+        visibility: Option<Value>,
+    ) -> Result<Comment> {
+        let url = field_filtering::apply_field_filtering_to_url(
+            &self.url(&format!("issue/{}/comment", issue_key)),
+        );
+        let response = self
+            .send_json(self.http.post(&url), &comment_body(body, visibility))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to add comment: {}", response.status());
+        }
+        Ok(response.json().await?)
+    }
+
+    pub async fn update_comment(
+        &self,
+        issue_key: &str,
+        comment_id: &str,
+        body: Value,
+        visibility: Option<Value>,
+    ) -> Result<Comment> {
+        let url = field_filtering::apply_field_filtering_to_url(
+            &self.url(&format!("issue/{}/comment/{}", issue_key, comment_id)),
+        );
+        let response = self
+            .send_json(self.http.put(&url), &comment_body(body, visibility))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to update comment: {}", error);
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Transitions available from the issue's current status
+    pub async fn get_transitions(&self, issue_key: &str) -> Result<Vec<Transition>> {
+        #[derive(Deserialize)]
+        struct Transitions {
+            #[serde(default)]
+            transitions: Vec<Transition>,
+        }
+
+        let url = field_filtering::apply_field_filtering_to_url(
+            &self.url(&format!("issue/{}/transitions", issue_key)),
+        );
+        let response = self.get(&url).send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to get transitions: {}", response.status());
+        }
+        Ok(response.json::<Transitions>().await?.transitions)
+    }
+
+    pub async fn transition_issue(&self, issue_key: &str, transition_id: &str) -> Result<()> {
+        let url = self.url(&format!("issue/{}/transitions", issue_key));
+        let body = json!({
+            "transition": {
+                "id": transition_id
+            }
+        });
+
+        let response = self.send_json(self.http.post(&url), &body).send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to transition issue: {}", response.status());
+        }
+        Ok(())
+    }
+}
+
+fn comment_body(body: Value, visibility: Option<Value>) -> Value {
+    let mut comment = json!({ "body": body });
+    if let Some(visibility) = visibility {
+        comment["visibility"] = visibility;
+    }
+    comment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_page_from_response() {
+        let page: SearchPage = serde_json::from_value(json!({
+            "issues": [{"id": "1", "key": "A-1", "fields": {}}],
+            "nextPageToken": "abc",
+            "isLast": false
+        }))
+        .unwrap();
+        assert_eq!(page.issues[0].key, "A-1");
+        assert_eq!(page.next_page_token.as_deref(), Some("abc"));
+        assert_eq!(page.is_last, Some(false));
+        assert_eq!(page.total, None);
+    }
+}
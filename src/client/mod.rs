@@ -0,0 +1,32 @@
+//! Typed Jira and Confluence calls, usable without the MCP server
+//!
+//! The core issue and page tools are thin adapters over these: they parse
+//! arguments, call e.g. [`Jira::get_issue`], and add what only an MCP client
+//! wants (Markdown bodies, mention lookups, tables, `applied_filters`).
+//! Another Rust program can make the same calls with its own [`Config`], or
+//! through `AtlassianToolkit`:
+//!
+//! ```no_run
+//! # async fn demo() -> anyhow::Result<()> {
+//! let config = mcp_atlassian::config::Config::load(None)?;
+//! let jira = mcp_atlassian::client::Jira::new(&config);
+//! let issue = jira.get_issue("PROJ-123").await?;
+//! println!("{}: {:?}", issue.key, issue.fields.summary);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Requests go to the configured site as given: `JIRA_PROJECTS_FILTER`,
+//! `CONFLUENCE_SPACES_FILTER`, `READ_ONLY_MODE` and field optimization are tool
+//! policies and are not applied here. Errors read like the tools' errors.
+//!
+//! [`Config`]: crate::config::Config
+
+pub mod confluence;
+pub mod graphql;
+pub mod jira;
+pub mod models;
+pub mod rest;
+
+pub use confluence::Confluence;
+pub use jira::Jira;
@@ -0,0 +1,355 @@
+//! Typed Jira issues and Confluence pages
+//!
+//! The fields tools read are typed; everything else lands in a
+//! `#[serde(flatten)] extra` map, so a response serializes back with all of
+//! its fields. Optional fields are omitted when absent or `null` (Jira sends
+//! `"assignee": null` for unassigned issues), which keeps tool output short.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// `{"name": ..., ...}`: issue types, priorities, statuses
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Named {
+    #[serde(default)]
+    pub name: String,
+    /// `id`, `statusCategory`, ...
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A Jira or Confluence user reference
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// `emailAddress`, `active`, `timeZone`, ...
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// An issue as `/rest/api/3/issue/{key}` and search return it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Issue {
+    #[serde(default)]
+    pub id: String,
+    pub key: String,
+    #[serde(default)]
+    pub fields: IssueFields,
+    /// `self`, `expand`, `changelog`, ...
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The system fields tools work with; custom fields are in `extra`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IssueFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<Named>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuetype: Option<Named>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Named>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<User>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reporter: Option<User>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+    /// ADF, or Markdown text after `markdown: true`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<CommentPage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duedate: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The `comment` field (the first comments) or a `/comment` page, with
+/// paging counts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommentPage {
+    #[serde(default)]
+    pub comments: Vec<Comment>,
+    /// `total`, `maxResults`, `startAt`
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl CommentPage {
+    /// Comments on the issue, across all pages
+    pub fn total(&self) -> Option<usize> {
+        self.extra
+            .get("total")
+            .and_then(Value::as_u64)
+            .map(|n| n as usize)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<User>,
+    /// ADF, or Markdown text after `markdown: true`
+    #[serde(default)]
+    pub body: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
+    /// `{"type": "role"|"group", "value": name}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Value>,
+    /// `updateAuthor`, `jsdPublic`, ...
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transition {
+    pub id: String,
+    pub name: String,
+    /// The status the transition leads to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Named>,
+    /// `hasScreen`, `isConditional`, ...
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// One changelog entry: who changed which fields when
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<User>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(default)]
+    pub items: Vec<HistoryItem>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryItem {
+    #[serde(default)]
+    pub field: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_string: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_string: Option<String>,
+    /// `fieldtype`, `fieldId`, `from`, `to`
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A `/changelog` page, oldest entries first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogPage {
+    #[serde(default)]
+    pub values: Vec<History>,
+    #[serde(default)]
+    pub total: usize,
+    /// `startAt`, `maxResults`, `isLast`
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A created issue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueRef {
+    pub id: String,
+    pub key: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A page as `/wiki/api/v2/pages` returns it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page {
+    pub id: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub space_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<PageVersion>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<PageBody>,
+    /// `authorId`, `createdAt`, `_links`, ...
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Page {
+    /// `version.number`, when it was included
+    pub fn version_number(&self) -> Option<u64> {
+        self.version.as_ref().map(|version| version.number)
+    }
+
+    /// The storage-format body, when it was included
+    pub fn storage(&self) -> Option<&str> {
+        self.body
+            .as_ref()?
+            .storage
+            .as_ref()
+            .map(|b| b.value.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PageVersion {
+    #[serde(default)]
+    pub number: u64,
+    /// `createdAt`, `message`, `authorId`, ...
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The requested body representations
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PageBody {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BodyValue>,
+    /// `atlas_doc_format`, `view`, ...
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BodyValue {
+    #[serde(default)]
+    pub value: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// One page of a v2 list and the link to the next one
+#[derive(Debug, Clone, Default)]
+pub struct Listing<T> {
+    pub results: Vec<T>,
+    /// `_links.next`: a relative URL carrying the `cursor` parameter
+    pub next: Option<String>,
+}
+
+impl<T> Listing<T> {
+    /// The `cursor` parameter of `next`, when there is a next page
+    pub fn next_cursor(&self) -> Option<String> {
+        let next = self.next.as_deref()?;
+        let url = reqwest::Url::parse("https://localhost/")
+            .ok()?
+            .join(next)
+            .ok()?;
+        url.query_pairs()
+            .find(|(name, value)| name == "cursor" && !value.is_empty())
+            .map(|(_, value)| value.into_owned())
+    }
+}
+
+/// A space as `/wiki/api/v2/spaces` returns it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Space {
+    pub id: String,
+    pub key: String,
+    #[serde(default)]
+    pub name: String,
+    /// `type`, `status`, `homepageId`, ...
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_issue_round_trip_keeps_unknown_fields() {
+        let raw = json!({
+            "id": "10001",
+            "key": "PROJ-1",
+            "self": "https://test.atlassian.net/rest/api/3/issue/10001",
+            "fields": {
+                "summary": "Crash",
+                "status": {"name": "To Do", "statusCategory": {"key": "new"}},
+                "assignee": {"accountId": "abc", "displayName": "Jane", "active": true},
+                "labels": [],
+                "customfield_10010": 3,
+                "comment": {"comments": [{"id": "1", "body": "hi", "jsdPublic": true}], "total": 1}
+            },
+            "changelog": {"histories": []}
+        });
+        let issue: Issue = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(issue.fields.summary.as_deref(), Some("Crash"));
+        assert_eq!(issue.fields.status.as_ref().unwrap().name, "To Do");
+        let assignee = issue.fields.assignee.as_ref().unwrap();
+        assert_eq!(assignee.display_name.as_deref(), Some("Jane"));
+        assert_eq!(issue.fields.extra["customfield_10010"], 3);
+        assert!(issue.extra.contains_key("changelog"));
+        assert_eq!(serde_json::to_value(&issue).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_null_fields_are_omitted() {
+        let issue: Issue = serde_json::from_value(json!({
+            "key": "PROJ-2",
+            "fields": {"summary": "x", "assignee": null, "description": null}
+        }))
+        .unwrap();
+        assert!(issue.fields.assignee.is_none());
+        assert_eq!(
+            serde_json::to_value(&issue.fields).unwrap(),
+            json!({"summary": "x"})
+        );
+    }
+
+    #[test]
+    fn test_page_accessors() {
+        let raw = json!({
+            "id": "42",
+            "title": "Runbook",
+            "spaceId": "7",
+            "version": {"number": 3, "message": ""},
+            "body": {"storage": {"value": "<p>Hi</p>", "representation": "storage"}},
+            "_links": {"webui": "/spaces/ENG/pages/42"}
+        });
+        let page: Page = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(page.version_number(), Some(3));
+        assert_eq!(page.storage(), Some("<p>Hi</p>"));
+        assert_eq!(page.space_id.as_deref(), Some("7"));
+        assert_eq!(serde_json::to_value(&page).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_listing_next_cursor() {
+        let listing = Listing::<Page> {
+            results: vec![],
+            next: Some("/wiki/api/v2/pages/42/children?limit=2&cursor=abc%3D".to_string()),
+        };
+        assert_eq!(listing.next_cursor().as_deref(), Some("abc="));
+        assert_eq!(Listing::<Value>::default().next_cursor(), None);
+    }
+}
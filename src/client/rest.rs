@@ -0,0 +1,110 @@
+//! JSON GET requests shared by the typed clients and the tools that call
+//! endpoints they do not cover
+//!
+//! With `ATLASSIAN_CACHE_DIR` and `ATLASSIAN_CACHE_TTL_SECS` set, responses
+//! are kept in the disk cache's `responses` namespace, keyed by the full URL
+//! and the account, and reused for that many seconds. Every write call
+//! through `call_tool` empties the namespace, so the server's own changes
+//! show at once; changes made elsewhere show once an entry expires. Reads a
+//! write depends on (versions, current values, policy checks) use
+//! [`get_current_json`], which always asks the site.
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::Config;
+use crate::utils::disk_cache::DiskCache;
+use crate::utils::http_utils::create_auth_header;
+use crate::utils::sha256;
+
+const NAMESPACE: &str = "responses";
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn response_cache(config: &Config) -> Option<DiskCache> {
+    if config.cache_ttl_secs == 0 {
+        return None;
+    }
+    DiskCache::from_config(config, NAMESPACE)
+}
+
+/// `url` with `query` as JSON, from the response cache when enabled; a
+/// failure status reads "Failed to get `what`: `status`", like the typed
+/// calls' errors
+pub async fn get_json<Q: Serialize + ?Sized>(
+    http: &reqwest::Client,
+    config: &Config,
+    url: &str,
+    query: &Q,
+    what: &str,
+) -> Result<Value> {
+    fetch(http, config, url, query, what, true).await
+}
+
+/// [`get_json`] from the site itself, never a cached response
+pub async fn get_current_json<Q: Serialize + ?Sized>(
+    http: &reqwest::Client,
+    config: &Config,
+    url: &str,
+    query: &Q,
+    what: &str,
+) -> Result<Value> {
+    fetch(http, config, url, query, what, false).await
+}
+
+async fn fetch<Q: Serialize + ?Sized>(
+    http: &reqwest::Client,
+    config: &Config,
+    url: &str,
+    query: &Q,
+    what: &str,
+    reuse: bool,
+) -> Result<Value> {
+    let request = http
+        .get(url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .query(query)
+        .build()?;
+
+    let cache = response_cache(config);
+    // Accounts can see different things, so each has its own entries
+    let identity = format!("{} {}", config.atlassian_email, request.url());
+    let key = format!("{}.json", sha256::hex_digest(identity.as_bytes()));
+    if let Some(entry) = cache
+        .as_ref()
+        .filter(|_| reuse)
+        .and_then(|cache| cache.get(&key))
+    {
+        let entry: Value = serde_json::from_str(&entry).unwrap_or_default();
+        let fetched = entry["fetched"].as_u64().unwrap_or(0);
+        if now_secs().saturating_sub(fetched) < config.cache_ttl_secs {
+            crate::mcp::stats::record_cache_hit();
+            return Ok(entry["body"].clone());
+        }
+    }
+
+    let response = http.execute(request).await?;
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get {}: {}", what, response.status());
+    }
+    let body: Value = response.json().await?;
+    if let Some(cache) = &cache {
+        let entry = json!({ "fetched": now_secs(), "body": body });
+        cache.put(&key, &entry.to_string());
+    }
+    Ok(body)
+}
+
+/// Drops the configured site's cached responses
+pub fn forget_responses(config: &Config) {
+    if let Some(cache) = response_cache(config) {
+        cache.remove_where(|_| true);
+    }
+}
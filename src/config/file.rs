@@ -0,0 +1,325 @@
+//! Config file support
+//!
+//! A TOML file (path via `--config` or `MCP_ATLASSIAN_CONFIG`) supplies the
+//! same settings as the environment, keyed by the lowercased variable name:
+//!
+//! ```toml
+//! profile = "prod"
+//! atlassian_email = "me@example.com"
+//! enabled_tools = ["jira_*", "confluence_get_page"]
+//!
+//! [profiles.prod]
+//! atlassian_domain = "prod.atlassian.net"
+//! jira_projects_filter = ["OPS", "ENG"]
+//!
+//! [profiles.staging]
+//! atlassian_domain = "staging.atlassian.net"
+//! ```
+//!
+//! The selected profile (`ATLASSIAN_PROFILE`, else the top-level `profile`)
+//! overrides top-level keys, and environment variables override both. Arrays
+//! become comma-separated values, matching the environment format. Only the
+//! TOML subset needed for this is parsed: tables, strings, integers,
+//! booleans and arrays of those.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+#[derive(Debug, Default)]
+pub struct ConfigFile {
+    values: HashMap<String, String>,
+}
+
+impl ConfigFile {
+    pub fn load(path: &Path, profile: Option<&str>) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        Self::parse(&text, profile)
+            .with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    /// Flattens the top level and the selected profile into variable values
+    pub fn parse(text: &str, profile: Option<&str>) -> Result<Self> {
+        let entries = parse_toml(text)?;
+
+        let mut top = HashMap::new();
+        let mut profiles: HashMap<String, HashMap<String, String>> = HashMap::new();
+        for (table, key, value) in entries {
+            match table.as_str() {
+                "" => {
+                    top.insert(key, value);
+                }
+                t => match t.strip_prefix("profiles.") {
+                    Some(name) if !name.is_empty() && !name.contains('.') => {
+                        profiles
+                            .entry(name.to_string())
+                            .or_default()
+                            .insert(key, value);
+                    }
+                    _ => anyhow::bail!("Unknown table [{}]: expected [profiles.<name>]", t),
+                },
+            }
+        }
+
+        let selected = profile
+            .map(str::to_string)
+            .or_else(|| top.remove("profile"));
+        top.remove("profile");
+
+        if let Some(name) = selected {
+            let Some(overrides) = profiles.remove(&name) else {
+                let available: BTreeSet<&String> = profiles.keys().collect();
+                anyhow::bail!(
+                    "Profile '{}' not found; available profiles: {}",
+                    name,
+                    available
+                        .into_iter()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            };
+            top.extend(overrides);
+        }
+
+        Ok(Self { values: top })
+    }
+
+    /// Value for an environment variable name, e.g. `ATLASSIAN_DOMAIN`
+    pub fn get(&self, var: &str) -> Option<&str> {
+        self.values.get(&var.to_lowercase()).map(String::as_str)
+    }
+}
+
+/// `(table, key, value)` for every assignment
+fn parse_toml(text: &str) -> Result<Vec<(String, String, String)>> {
+    let mut entries = Vec::new();
+    let mut table = String::new();
+    let mut lines = text.lines().enumerate();
+
+    while let Some((index, line)) = lines.next() {
+        let line_no = index + 1;
+        let line = strip_comment(line);
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name
+                .strip_suffix(']')
+                .ok_or_else(|| anyhow::anyhow!("line {}: unterminated table header", line_no))?;
+            table = name.trim().to_string();
+            continue;
+        }
+
+        let (key, raw) = line
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("line {}: expected key = value", line_no))?;
+        let key = key.trim().trim_matches('"').to_string();
+        let mut raw = raw.trim().to_string();
+
+        // Multi-line arrays continue until the closing bracket
+        if raw.starts_with('[') {
+            while !array_closed(&raw) {
+                let (_, next) = lines
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("line {}: unterminated array", line_no))?;
+                raw.push(' ');
+                raw.push_str(strip_comment(next).trim());
+            }
+        }
+
+        let value = parse_value(&raw).map_err(|e| anyhow::anyhow!("line {}: {}", line_no, e))?;
+        entries.push((table.clone(), key, value));
+    }
+    Ok(entries)
+}
+
+/// Drops a `#` comment that is not inside a string
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..i],
+            _ => {}
+        }
+        escaped = false;
+    }
+    line
+}
+
+fn array_closed(raw: &str) -> bool {
+    let mut depth = 0i32;
+    let mut quote = None;
+    for c in raw.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[') => depth += 1,
+            (None, ']') => depth -= 1,
+            _ => {}
+        }
+    }
+    depth <= 0
+}
+
+/// Scalar as its string form; arrays as comma-separated scalars
+fn parse_value(raw: &str) -> Result<String> {
+    if let Some(inner) = raw.strip_prefix('[') {
+        let inner = inner
+            .trim_end()
+            .strip_suffix(']')
+            .ok_or_else(|| anyhow::anyhow!("unterminated array"))?;
+        let items = split_array(inner)?
+            .iter()
+            .map(|item| parse_scalar(item))
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(items.join(","));
+    }
+    parse_scalar(raw)
+}
+
+fn split_array(inner: &str) -> Result<Vec<String>> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut escaped = false;
+    for c in inner.chars() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                current.push(c);
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => {
+                quote = None;
+                current.push(c);
+            }
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                current.push(c);
+            }
+            (None, ',') => items.push(std::mem::take(&mut current)),
+            (None, '[' | ']') => anyhow::bail!("nested arrays are not supported"),
+            _ => current.push(c),
+        }
+        escaped = false;
+    }
+    items.push(current);
+    Ok(items
+        .into_iter()
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect())
+}
+
+fn parse_scalar(raw: &str) -> Result<String> {
+    if let Some(inner) = raw.strip_prefix('"') {
+        let inner = inner
+            .strip_suffix('"')
+            .ok_or_else(|| anyhow::anyhow!("unterminated string"))?;
+        let mut out = String::with_capacity(inner.len());
+        let mut chars = inner.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some('"') => out.push('"'),
+                Some('\\') => out.push('\\'),
+                other => anyhow::bail!("unsupported escape \\{}", other.unwrap_or(' ')),
+            }
+        }
+        return Ok(out);
+    }
+    if let Some(inner) = raw.strip_prefix('\'') {
+        return inner
+            .strip_suffix('\'')
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("unterminated string"));
+    }
+    if raw == "true" || raw == "false" || raw.parse::<i64>().is_ok() {
+        return Ok(raw.to_string());
+    }
+    anyhow::bail!("unsupported value '{}'", raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = r#"
+# Shared settings
+profile = "prod"
+atlassian_email = "me@example.com"   # inline comment
+request_timeout_ms = 45000
+read_only_mode = true
+enabled_tools = [
+    "jira_*",
+    "confluence_get_page", # trailing comma and comment
+]
+
+[profiles.prod]
+atlassian_domain = "prod.atlassian.net"
+jira_projects_filter = ["OPS", "ENG"]
+
+[profiles.staging]
+atlassian_domain = 'staging.atlassian.net'
+atlassian_email = "qa#1@example.com"
+"#;
+
+    #[test]
+    fn test_parse_selects_default_profile() {
+        let file = ConfigFile::parse(FILE, None).unwrap();
+        assert_eq!(file.get("ATLASSIAN_DOMAIN"), Some("prod.atlassian.net"));
+        assert_eq!(file.get("ATLASSIAN_EMAIL"), Some("me@example.com"));
+        assert_eq!(file.get("REQUEST_TIMEOUT_MS"), Some("45000"));
+        assert_eq!(file.get("READ_ONLY_MODE"), Some("true"));
+        assert_eq!(file.get("JIRA_PROJECTS_FILTER"), Some("OPS,ENG"));
+        assert_eq!(
+            file.get("ENABLED_TOOLS"),
+            Some("jira_*,confluence_get_page")
+        );
+        assert_eq!(file.get("PROFILE"), None);
+    }
+
+    #[test]
+    fn test_profile_overrides_top_level() {
+        let file = ConfigFile::parse(FILE, Some("staging")).unwrap();
+        assert_eq!(file.get("ATLASSIAN_DOMAIN"), Some("staging.atlassian.net"));
+        assert_eq!(file.get("ATLASSIAN_EMAIL"), Some("qa#1@example.com"));
+        assert_eq!(file.get("JIRA_PROJECTS_FILTER"), None);
+    }
+
+    #[test]
+    fn test_unknown_profile_lists_available() {
+        let err = ConfigFile::parse(FILE, Some("dev"))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "Profile 'dev' not found; available profiles: prod, staging"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(ConfigFile::parse("[jira]\nx = 1", None).is_err());
+        assert!(ConfigFile::parse("x = \"open", None).is_err());
+        assert!(ConfigFile::parse("x = 1.5", None).is_err());
+        assert!(ConfigFile::parse("x = [\"a\"", None).is_err());
+        assert!(ConfigFile::parse("just text", None).is_err());
+    }
+}
@@ -1,8 +1,20 @@
+pub mod file;
+pub mod secrets;
+
 use anyhow::{Context, Result};
 use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::mcp::i18n::Locale;
+use crate::tools::schema::Verbosity;
+use file::ConfigFile;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct Config {
     // Atlassian API Configuration
     pub atlassian_domain: String,
@@ -19,35 +31,387 @@ pub struct Config {
     // Jira Search Field Configuration
     pub jira_search_default_fields: Option<Vec<String>>,
     pub jira_search_custom_fields: Vec<String>,
+    // Name this site's project keys and statuses in jira_search's description (JIRA_SCHEMA_EXAMPLES)
+    pub jira_schema_examples: bool,
 
     // Response Optimization Configuration
     pub response_exclude_fields: Option<Vec<String>>,
+    pub response_metrics: bool,
+
+    // Access mode: hide and reject tools that modify Atlassian data
+    pub read_only: bool,
+    // Projects and spaces (keys or v2 IDs) write tools may change; empty = any
+    // (WRITE_ALLOWED_PROJECTS, WRITE_ALLOWED_SPACES)
+    pub write_allowed_projects: Vec<String>,
+    pub write_allowed_spaces: Vec<String>,
+    // Hold deletes, bulk edits and transitions to done for a confirm_token
+    // round trip (CONFIRM_DESTRUCTIVE_WRITES)
+    pub confirm_destructive_writes: bool,
+
+    // Jira Webhook Receiver (push notifications)
+    pub jira_webhook_listen_addr: Option<String>,
+    pub jira_webhook_public_url: Option<String>,
+    pub jira_webhook_secret: Option<String>,
+
+    // Bitbucket Cloud (tools registered only when a workspace is set)
+    pub bitbucket_workspace: Option<String>,
+    pub bitbucket_username: Option<String>,
+    pub bitbucket_app_password: Option<String>,
+
+    // Trello (requires the `trello` feature; key/token auth)
+    pub trello_api_key: Option<String>,
+    pub trello_token: Option<String>,
+
+    // Jira Service Management (tools registered only when JSM_ENABLED=true)
+    pub jsm_enabled: bool,
+
+    // Compass (tools registered only when COMPASS_ENABLED=true)
+    pub compass_enabled: bool,
+
+    // Organization ID for the Teams API (looked up from the site when unset)
+    pub atlassian_org_id: Option<String>,
+
+    // Incidents (create_incident registered only when a project is set)
+    pub incident_project: Option<String>,
+    pub incident_postmortem_parent: Option<String>,
+    pub incident_postmortem_template: Option<String>,
+
+    // Issue templates: `<name>.md` files that override the built-ins
+    pub jira_templates_dir: Option<String>,
+
+    // Default parent page id for publish_meeting_notes
+    pub confluence_meeting_notes_parent: Option<String>,
+
+    // Audit trail of write operations: a JSONL file path, or "stderr"
+    pub audit_log: Option<String>,
+    // Rotate the audit file to `<path>.1` past this size (0 disables rotation)
+    pub audit_log_max_bytes: u64,
+
+    // Tool allowlist; entries may end in `*` (empty = all tools)
+    pub enabled_tools: Vec<String>,
+    // Prepended to every tool name clients see, ending in `_` (e.g. `prod_`)
+    pub tool_name_prefix: Option<String>,
+    // Language of tool descriptions and error hints (ATLASSIAN_LOCALE: en, ko, ja)
+    pub locale: Locale,
+    // How much guidance tool schemas carry (TOOL_SCHEMA_VERBOSITY: minimal, standard, detailed)
+    pub tool_schema_verbosity: Verbosity,
+
+    // Probe Jira and Confluence at startup (`Config::verify`)
+    pub verify_credentials: bool,
+
+    // Network: explicit proxy (HTTPS_PROXY / NO_PROXY), extra CA roots, and
+    // an escape hatch that disables certificate verification
+    pub https_proxy: Option<String>,
+    pub no_proxy: Option<String>,
+    pub ca_cert_path: Option<String>,
+    pub insecure_skip_verify: bool,
+
+    // Per-tool call deadlines, e.g. `jira_search=90000` (TOOL_TIMEOUTS_MS)
+    pub tool_timeouts_ms: HashMap<String, u64>,
+
+    // Deadline of the tool call this config was cloned for; every client
+    // created from it times out at the deadline instead of `request_timeout_ms`
+    #[serde(skip)]
+    pub(crate) deadline: Option<Instant>,
+
+    // Config file this was loaded from (`--config` or MCP_ATLASSIAN_CONFIG)
+    pub config_file: Option<String>,
+    // Poll the config file and `.env` for changes every N seconds (0 = off)
+    pub config_reload_secs: u64,
+
+    // Longest stdin message accepted; longer lines are skipped and answered
+    // with an error (MCP_MAX_MESSAGE_BYTES)
+    pub max_message_bytes: usize,
+    // Keep serving notifications this many seconds after stdin closes, for
+    // clients that half-close it (STDIN_KEEPALIVE_SECS, 0 = exit at EOF)
+    pub stdin_keepalive_secs: u64,
+    // IANA name or UTC offset results and date inputs are read in
+    // (ATLASSIAN_TIMEZONE, e.g. Europe/Berlin or +09:00; unset = as the API answers)
+    pub timezone: Option<String>,
+
+    // Log the session statistics every N seconds (SESSION_STATS_LOG_SECS, 0 = at shutdown only)
+    pub session_stats_log_secs: u64,
+
+    // Consecutive 5xx/timeout failures that open a host's circuit (CIRCUIT_BREAKER_THRESHOLD, 0 = off)
+    pub circuit_breaker_threshold: u32,
+    // How long an open circuit rejects calls (CIRCUIT_BREAKER_COOLDOWN_SECS)
+    pub circuit_breaker_cooldown_secs: u64,
+    // Recent writes atlassian_undo_last can revert (UNDO_JOURNAL_SIZE, 0 = off)
+    pub undo_journal_size: usize,
+
+    // Where tool results come from (ATLASSIAN_MODE: live, record, replay, mock)
+    pub mode: Mode,
+    // Recorded tool results for record/replay (ATLASSIAN_FIXTURES_DIR)
+    pub fixtures_dir: String,
+
+    // Responses kept across restarts: cloud IDs, page text by version, GET
+    // responses (ATLASSIAN_CACHE_DIR; unset = in-process only)
+    pub cache_dir: Option<String>,
+    // Least recently used entries are removed past this size (0 = no limit)
+    pub cache_max_bytes: u64,
+    // How long GET responses are reused (ATLASSIAN_CACHE_TTL_SECS; 0 = not cached)
+    pub cache_ttl_secs: u64,
 
     // Cached normalized base URL (not deserialized, computed at init)
     #[serde(skip)]
     pub(crate) base_url: String,
 }
 
+/// `ATLASSIAN_MODE`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    /// Call Atlassian
+    #[default]
+    Live,
+    /// Call Atlassian and save every tool result under `ATLASSIAN_FIXTURES_DIR`
+    Record,
+    /// Answer from the saved results without network access
+    Replay,
+    /// Serve every tool from the built-in mock site; no account needed
+    Mock,
+}
+
+impl Mode {
+    fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "live" => Ok(Self::Live),
+            "record" => Ok(Self::Record),
+            "replay" => Ok(Self::Replay),
+            "mock" => Ok(Self::Mock),
+            other => anyhow::bail!(
+                "Invalid ATLASSIAN_MODE '{}': expected live, record, replay or mock",
+                other
+            ),
+        }
+    }
+}
+
+/// Upper bound for a tool call deadline (`TOOL_TIMEOUTS_MS`, `timeout_ms` argument)
+pub const MAX_CALL_TIMEOUT_MS: u64 = 600_000;
+
+/// Variables set before `.env` was loaded; everything else in the process
+/// environment came from `.env` and may be stale after a reload
+static PROCESS_ENV: OnceLock<HashSet<String>> = OnceLock::new();
+
+/// The `.env` file found at startup
+static DOTENV_PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+fn process_env() -> &'static HashSet<String> {
+    PROCESS_ENV.get_or_init(|| {
+        env::vars_os()
+            .filter_map(|(k, _)| k.into_string().ok())
+            .collect()
+    })
+}
+
+/// Process environment first, then `.env` (re-read on every load), then
+/// the config file
+struct Source {
+    dotenv: HashMap<String, String>,
+    file: Option<ConfigFile>,
+    keychain_service: Option<String>,
+}
+
+impl Source {
+    fn new(config_path: Option<&str>) -> Result<(Self, Option<String>)> {
+        let dotenv = DOTENV_PATH
+            .get()
+            .cloned()
+            .flatten()
+            .and_then(|path| dotenvy::from_path_iter(path).ok())
+            .map(|iter| iter.filter_map(|item| item.ok()).collect())
+            .unwrap_or_default();
+        let mut source = Self {
+            dotenv,
+            file: None,
+            keychain_service: None,
+        };
+
+        let config_path = config_path
+            .map(str::to_string)
+            .or_else(|| source.var("MCP_ATLASSIAN_CONFIG").ok())
+            .filter(|p| !p.trim().is_empty());
+        if let Some(path) = &config_path {
+            let profile = source.var("ATLASSIAN_PROFILE").ok();
+            tracing::info!("Loading config file {}", path);
+            source.file = Some(ConfigFile::load(Path::new(path), profile.as_deref())?);
+        }
+        source.keychain_service = source
+            .var("ATLASSIAN_KEYCHAIN_SERVICE")
+            .ok()
+            .filter(|s| !s.trim().is_empty());
+        Ok((source, config_path))
+    }
+
+    fn var(&self, name: &str) -> Result<String, env::VarError> {
+        if process_env().contains(name) {
+            return env::var(name);
+        }
+        self.dotenv
+            .get(name)
+            .map(String::as_str)
+            .or_else(|| self.file.as_ref().and_then(|f| f.get(name)))
+            .map(str::to_string)
+            .ok_or(env::VarError::NotPresent)
+    }
+
+    /// A secret from the variable, `<name>_FILE`, or the keychain, in that order
+    fn secret(&self, name: &str) -> Result<Option<String>> {
+        if let Ok(value) = self.var(name) {
+            return Ok(Some(value));
+        }
+        if let Ok(path) = self.var(&format!("{}_FILE", name)) {
+            return secrets::read_secret_file(Path::new(path.trim())).map(Some);
+        }
+        match &self.keychain_service {
+            Some(service) => secrets::keychain_lookup(service, name),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Secrets are shown as `[REDACTED]`
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn redacted(secret: &Option<String>) -> Option<&'static str> {
+            secret.as_ref().map(|_| "[REDACTED]")
+        }
+        f.debug_struct("Config")
+            .field("atlassian_domain", &self.atlassian_domain)
+            .field("atlassian_email", &self.atlassian_email)
+            .field("atlassian_api_token", &"[REDACTED]")
+            .field("request_timeout_ms", &self.request_timeout_ms)
+            .field("jira_projects_filter", &self.jira_projects_filter)
+            .field("confluence_spaces_filter", &self.confluence_spaces_filter)
+            .field(
+                "jira_search_default_fields",
+                &self.jira_search_default_fields,
+            )
+            .field("jira_search_custom_fields", &self.jira_search_custom_fields)
+            .field("jira_schema_examples", &self.jira_schema_examples)
+            .field("response_exclude_fields", &self.response_exclude_fields)
+            .field("response_metrics", &self.response_metrics)
+            .field("read_only", &self.read_only)
+            .field("write_allowed_projects", &self.write_allowed_projects)
+            .field("write_allowed_spaces", &self.write_allowed_spaces)
+            .field(
+                "confirm_destructive_writes",
+                &self.confirm_destructive_writes,
+            )
+            .field("jira_webhook_listen_addr", &self.jira_webhook_listen_addr)
+            .field("jira_webhook_public_url", &self.jira_webhook_public_url)
+            .field("jira_webhook_secret", &redacted(&self.jira_webhook_secret))
+            .field("bitbucket_workspace", &self.bitbucket_workspace)
+            .field("bitbucket_username", &self.bitbucket_username)
+            .field(
+                "bitbucket_app_password",
+                &redacted(&self.bitbucket_app_password),
+            )
+            .field("trello_api_key", &redacted(&self.trello_api_key))
+            .field("trello_token", &redacted(&self.trello_token))
+            .field("jsm_enabled", &self.jsm_enabled)
+            .field("compass_enabled", &self.compass_enabled)
+            .field("atlassian_org_id", &self.atlassian_org_id)
+            .field("incident_project", &self.incident_project)
+            .field(
+                "incident_postmortem_parent",
+                &self.incident_postmortem_parent,
+            )
+            .field(
+                "incident_postmortem_template",
+                &self.incident_postmortem_template,
+            )
+            .field("jira_templates_dir", &self.jira_templates_dir)
+            .field(
+                "confluence_meeting_notes_parent",
+                &self.confluence_meeting_notes_parent,
+            )
+            .field("audit_log", &self.audit_log)
+            .field("audit_log_max_bytes", &self.audit_log_max_bytes)
+            .field("enabled_tools", &self.enabled_tools)
+            .field("tool_name_prefix", &self.tool_name_prefix)
+            .field("locale", &self.locale)
+            .field("tool_schema_verbosity", &self.tool_schema_verbosity)
+            .field("verify_credentials", &self.verify_credentials)
+            .field("https_proxy", &self.https_proxy)
+            .field("no_proxy", &self.no_proxy)
+            .field("ca_cert_path", &self.ca_cert_path)
+            .field("insecure_skip_verify", &self.insecure_skip_verify)
+            .field("tool_timeouts_ms", &self.tool_timeouts_ms)
+            .field("deadline", &self.deadline)
+            .field("config_file", &self.config_file)
+            .field("config_reload_secs", &self.config_reload_secs)
+            .field("max_message_bytes", &self.max_message_bytes)
+            .field("stdin_keepalive_secs", &self.stdin_keepalive_secs)
+            .field("session_stats_log_secs", &self.session_stats_log_secs)
+            .field("circuit_breaker_threshold", &self.circuit_breaker_threshold)
+            .field(
+                "circuit_breaker_cooldown_secs",
+                &self.circuit_breaker_cooldown_secs,
+            )
+            .field("undo_journal_size", &self.undo_journal_size)
+            .field("mode", &self.mode)
+            .field("fixtures_dir", &self.fixtures_dir)
+            .field("cache_dir", &self.cache_dir)
+            .field("cache_max_bytes", &self.cache_max_bytes)
+            .field("cache_ttl_secs", &self.cache_ttl_secs)
+            .field("timezone", &self.timezone)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Parses `name=ms` pairs separated by commas
+fn parse_tool_timeouts(value: &str) -> Result<HashMap<String, u64>> {
+    value
+        .split(',')
+        .filter(|s| !s.trim().is_empty())
+        .map(|pair| {
+            let (name, ms) = pair
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("Invalid TOOL_TIMEOUTS_MS entry '{}'", pair))?;
+            let ms = ms
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid TOOL_TIMEOUTS_MS value for {}", name.trim()))?;
+            Ok((name.trim().to_string(), ms))
+        })
+        .collect()
+}
+
 impl Config {
-    pub fn from_env() -> Result<Self> {
-        // Load .env file if it exists
-        dotenvy::dotenv().ok();
+    /// Loads the environment, layered over the config file at `config_path`
+    /// (or `MCP_ATLASSIAN_CONFIG`) when one is given
+    pub fn load(config_path: Option<&str>) -> Result<Self> {
+        // Load .env file if it exists; remember what was set before it
+        process_env();
+        DOTENV_PATH.get_or_init(|| dotenvy::dotenv().ok());
+
+        let (source, config_file) = Source::new(config_path)?;
 
-        let domain = env::var("ATLASSIAN_DOMAIN")
+        let mode = Mode::parse(&source.var("ATLASSIAN_MODE").unwrap_or_default())?;
+        // The mock site needs no account: placeholders fill unset credentials
+        let placeholder = |value: &str| (mode == Mode::Mock).then(|| value.to_string());
+
+        let domain = source
+            .var("ATLASSIAN_DOMAIN")
+            .ok()
+            .or_else(|| placeholder("mock.atlassian.net"))
             .context("ATLASSIAN_DOMAIN environment variable not set")?;
 
         tracing::debug!("Loaded ATLASSIAN_DOMAIN: {}", domain);
 
         // Parse Jira search field configuration
         let jira_search_default_fields: Option<Vec<String>> =
-            env::var("JIRA_SEARCH_DEFAULT_FIELDS").ok().map(|s| {
+            source.var("JIRA_SEARCH_DEFAULT_FIELDS").ok().map(|s| {
                 s.split(',')
                     .filter(|s| !s.trim().is_empty())
                     .map(|s| s.trim().to_string())
                     .collect()
             });
 
-        let jira_search_custom_fields: Vec<String> = env::var("JIRA_SEARCH_CUSTOM_FIELDS")
+        let jira_search_custom_fields: Vec<String> = source
+            .var("JIRA_SEARCH_CUSTOM_FIELDS")
             .unwrap_or_default()
             .split(',')
             .filter(|s| !s.trim().is_empty())
@@ -70,7 +434,7 @@ impl Config {
 
         // Parse response optimization configuration
         let response_exclude_fields: Option<Vec<String>> =
-            env::var("RESPONSE_EXCLUDE_FIELDS").ok().map(|s| {
+            source.var("RESPONSE_EXCLUDE_FIELDS").ok().map(|s| {
                 s.split(',')
                     .filter(|s| !s.trim().is_empty())
                     .map(|s| s.trim().to_string())
@@ -84,6 +448,23 @@ impl Config {
             );
         }
 
+        // Attach per-response optimization metrics as `_meta.optimization`
+        let response_metrics = source
+            .var("RESPONSE_METRICS")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse::<bool>()
+            .unwrap_or(false);
+
+        let read_only = source
+            .var("READ_ONLY_MODE")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse::<bool>()
+            .unwrap_or(false);
+
+        if read_only {
+            tracing::info!("READ_ONLY_MODE enabled: write tools are disabled");
+        }
+
         // Normalize base URL once at initialization
         let base_url = if domain.starts_with("https://") {
             domain.clone()
@@ -93,25 +474,35 @@ impl Config {
             format!("https://{}", domain)
         };
 
-        Ok(Self {
+        let config = Self {
             atlassian_domain: domain,
-            atlassian_email: env::var("ATLASSIAN_EMAIL")
+            atlassian_email: source
+                .var("ATLASSIAN_EMAIL")
+                .ok()
+                .or_else(|| placeholder("mock@example.com"))
                 .context("ATLASSIAN_EMAIL environment variable not set")?,
-            atlassian_api_token: env::var("ATLASSIAN_API_TOKEN")
-                .context("ATLASSIAN_API_TOKEN environment variable not set")?,
+            atlassian_api_token: source
+                .secret("ATLASSIAN_API_TOKEN")?
+                .or_else(|| placeholder("mock-token"))
+                .context(
+                    "ATLASSIAN_API_TOKEN not set (checked ATLASSIAN_API_TOKEN, ATLASSIAN_API_TOKEN_FILE and the keychain)",
+                )?,
 
-            request_timeout_ms: env::var("REQUEST_TIMEOUT_MS")
+            request_timeout_ms: source
+                .var("REQUEST_TIMEOUT_MS")
                 .unwrap_or_else(|_| "30000".to_string())
                 .parse()
                 .context("Invalid REQUEST_TIMEOUT_MS")?,
 
-            jira_projects_filter: env::var("JIRA_PROJECTS_FILTER")
+            jira_projects_filter: source
+                .var("JIRA_PROJECTS_FILTER")
                 .unwrap_or_default()
                 .split(',')
                 .filter(|s| !s.is_empty())
                 .map(|s| s.trim().to_string())
                 .collect(),
-            confluence_spaces_filter: env::var("CONFLUENCE_SPACES_FILTER")
+            confluence_spaces_filter: source
+                .var("CONFLUENCE_SPACES_FILTER")
                 .unwrap_or_default()
                 .split(',')
                 .filter(|s| !s.is_empty())
@@ -120,9 +511,236 @@ impl Config {
 
             jira_search_default_fields,
             jira_search_custom_fields,
+            jira_schema_examples: source
+                .var("JIRA_SCHEMA_EXAMPLES")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse::<bool>()
+                .unwrap_or(false),
             response_exclude_fields,
+            response_metrics,
+            read_only,
+            write_allowed_projects: source
+                .var("WRITE_ALLOWED_PROJECTS")
+                .unwrap_or_default()
+                .split(',')
+                .filter(|s| !s.trim().is_empty())
+                .map(|s| s.trim().to_string())
+                .collect(),
+            write_allowed_spaces: source
+                .var("WRITE_ALLOWED_SPACES")
+                .unwrap_or_default()
+                .split(',')
+                .filter(|s| !s.trim().is_empty())
+                .map(|s| s.trim().to_string())
+                .collect(),
+            confirm_destructive_writes: source
+                .var("CONFIRM_DESTRUCTIVE_WRITES")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse::<bool>()
+                .unwrap_or(false),
+            jira_webhook_listen_addr: source
+                .var("JIRA_WEBHOOK_LISTEN_ADDR")
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+            jira_webhook_public_url: source
+                .var("JIRA_WEBHOOK_PUBLIC_URL")
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+            jira_webhook_secret: source
+                .secret("JIRA_WEBHOOK_SECRET")?
+                .filter(|s| !s.is_empty()),
+            bitbucket_workspace: source
+                .var("BITBUCKET_WORKSPACE")
+                .ok()
+                .filter(|s| !s.trim().is_empty())
+                .map(|s| s.trim().to_string()),
+            bitbucket_username: source
+                .var("BITBUCKET_USERNAME")
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+            bitbucket_app_password: source
+                .secret("BITBUCKET_APP_PASSWORD")?
+                .filter(|s| !s.is_empty()),
+            trello_api_key: source
+                .var("TRELLO_API_KEY")
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+            trello_token: source
+                .secret("TRELLO_TOKEN")?
+                .filter(|s| !s.trim().is_empty()),
+            jsm_enabled: source
+                .var("JSM_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse::<bool>()
+                .unwrap_or(false),
+            compass_enabled: source
+                .var("COMPASS_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse::<bool>()
+                .unwrap_or(false),
+            atlassian_org_id: source
+                .var("ATLASSIAN_ORG_ID")
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            incident_project: source
+                .var("INCIDENT_PROJECT")
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            incident_postmortem_parent: source
+                .var("INCIDENT_POSTMORTEM_PARENT")
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            incident_postmortem_template: source
+                .var("INCIDENT_POSTMORTEM_TEMPLATE")
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            jira_templates_dir: source
+                .var("JIRA_TEMPLATES_DIR")
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+            confluence_meeting_notes_parent: source
+                .var("CONFLUENCE_MEETING_NOTES_PARENT")
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            audit_log: source
+                .var("AUDIT_LOG")
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+            audit_log_max_bytes: source
+                .var("AUDIT_LOG_MAX_BYTES")
+                .unwrap_or_else(|_| "10485760".to_string())
+                .parse()
+                .context("Invalid AUDIT_LOG_MAX_BYTES")?,
+            enabled_tools: source
+                .var("ENABLED_TOOLS")
+                .unwrap_or_default()
+                .split(',')
+                .filter(|s| !s.trim().is_empty())
+                .map(|s| s.trim().to_string())
+                .collect(),
+            tool_name_prefix: source
+                .var("TOOL_NAME_PREFIX")
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .map(|s| if s.ends_with('_') { s } else { s + "_" }),
+            locale: Locale::parse(&source.var("ATLASSIAN_LOCALE").unwrap_or_default())?,
+            tool_schema_verbosity: Verbosity::parse(
+                &source.var("TOOL_SCHEMA_VERBOSITY").unwrap_or_default(),
+            )?,
+            verify_credentials: source
+                .var("VERIFY_CREDENTIALS")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse::<bool>()
+                .unwrap_or(false),
+            https_proxy: source
+                .var("HTTPS_PROXY")
+                .or_else(|_| source.var("https_proxy"))
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+            no_proxy: source
+                .var("NO_PROXY")
+                .or_else(|_| source.var("no_proxy"))
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+            ca_cert_path: source
+                .var("ATLASSIAN_CA_CERT_PATH")
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+            insecure_skip_verify: source
+                .var("ATLASSIAN_INSECURE_SKIP_VERIFY")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse::<bool>()
+                .unwrap_or(false),
+            tool_timeouts_ms: parse_tool_timeouts(
+                &source.var("TOOL_TIMEOUTS_MS").unwrap_or_default(),
+            )?,
+            deadline: None,
+            config_file,
+            config_reload_secs: source
+                .var("CONFIG_RELOAD_SECS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("Invalid CONFIG_RELOAD_SECS")?,
+            max_message_bytes: match source.var("MCP_MAX_MESSAGE_BYTES") {
+                Ok(value) => value.trim().parse().context("Invalid MCP_MAX_MESSAGE_BYTES")?,
+                Err(_) => crate::mcp::stdio::DEFAULT_MAX_MESSAGE_BYTES,
+            },
+            stdin_keepalive_secs: source
+                .var("STDIN_KEEPALIVE_SECS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("Invalid STDIN_KEEPALIVE_SECS")?,
+            timezone: source
+                .var("ATLASSIAN_TIMEZONE")
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            session_stats_log_secs: source
+                .var("SESSION_STATS_LOG_SECS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("Invalid SESSION_STATS_LOG_SECS")?,
+            circuit_breaker_threshold: source
+                .var("CIRCUIT_BREAKER_THRESHOLD")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .context("Invalid CIRCUIT_BREAKER_THRESHOLD")?,
+            circuit_breaker_cooldown_secs: source
+                .var("CIRCUIT_BREAKER_COOLDOWN_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .context("Invalid CIRCUIT_BREAKER_COOLDOWN_SECS")?,
+            undo_journal_size: source
+                .var("UNDO_JOURNAL_SIZE")
+                .unwrap_or_else(|_| "20".to_string())
+                .parse()
+                .context("Invalid UNDO_JOURNAL_SIZE")?,
+            mode,
+            fixtures_dir: source
+                .var("ATLASSIAN_FIXTURES_DIR")
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(|| "fixtures".to_string()),
+            cache_dir: source
+                .var("ATLASSIAN_CACHE_DIR")
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            cache_max_bytes: source
+                .var("ATLASSIAN_CACHE_MAX_BYTES")
+                .unwrap_or_else(|_| "104857600".to_string())
+                .parse()
+                .context("Invalid ATLASSIAN_CACHE_MAX_BYTES")?,
+            cache_ttl_secs: source
+                .var("ATLASSIAN_CACHE_TTL_SECS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("Invalid ATLASSIAN_CACHE_TTL_SECS")?,
             base_url,
-        })
+        };
+
+        // Keep every credential out of log output
+        for secret in [
+            Some(&config.atlassian_api_token),
+            config.jira_webhook_secret.as_ref(),
+            config.bitbucket_app_password.as_ref(),
+            config.trello_api_key.as_ref(),
+            config.trello_token.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            crate::utils::logging::register_secret(secret);
+        }
+
+        Ok(config)
     }
 
     pub fn validate(&self) -> Result<()> {
@@ -155,9 +773,182 @@ impl Config {
             anyhow::bail!("Request timeout must be between 100ms and 60000ms");
         }
 
+        if let Some(ref timezone) = self.timezone {
+            crate::utils::time::Timezone::parse(timezone).context("Invalid ATLASSIAN_TIMEZONE")?;
+        }
+
+        if self.max_message_bytes == 0 {
+            anyhow::bail!("MCP_MAX_MESSAGE_BYTES must be greater than 0");
+        }
+
+        if self.circuit_breaker_threshold > 0 && self.circuit_breaker_cooldown_secs == 0 {
+            anyhow::bail!("CIRCUIT_BREAKER_COOLDOWN_SECS must be greater than 0");
+        }
+
+        if let Some(ref addr) = self.jira_webhook_listen_addr
+            && addr.parse::<std::net::SocketAddr>().is_err()
+        {
+            anyhow::bail!(
+                "Invalid JIRA_WEBHOOK_LISTEN_ADDR (expected host:port, e.g. 127.0.0.1:8787)"
+            );
+        }
+
+        if let Some(ref url) = self.jira_webhook_public_url {
+            if self.jira_webhook_listen_addr.is_none() {
+                anyhow::bail!("JIRA_WEBHOOK_PUBLIC_URL requires JIRA_WEBHOOK_LISTEN_ADDR");
+            }
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                anyhow::bail!("JIRA_WEBHOOK_PUBLIC_URL must be an http(s) URL");
+            }
+        }
+
+        if let Some(ref prefix) = self.tool_name_prefix
+            && !prefix
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            anyhow::bail!("TOOL_NAME_PREFIX may only contain letters, digits, '_' and '-'");
+        }
+
+        if self.bitbucket_username.is_some() != self.bitbucket_app_password.is_some() {
+            anyhow::bail!("BITBUCKET_USERNAME and BITBUCKET_APP_PASSWORD must be set together");
+        }
+
+        if self.trello_api_key.is_some() != self.trello_token.is_some() {
+            anyhow::bail!("TRELLO_API_KEY and TRELLO_TOKEN must be set together");
+        }
+
+        if let Some((name, ms)) = self
+            .tool_timeouts_ms
+            .iter()
+            .find(|(_, ms)| !(100..=MAX_CALL_TIMEOUT_MS).contains(*ms))
+        {
+            anyhow::bail!(
+                "TOOL_TIMEOUTS_MS for {} must be between 100ms and {}ms (got {})",
+                name,
+                MAX_CALL_TIMEOUT_MS,
+                ms
+            );
+        }
+
+        if let Some(ref proxy) = self.https_proxy {
+            let url = reqwest::Url::parse(proxy).map_err(|_| {
+                anyhow::anyhow!("Invalid HTTPS_PROXY (expected http(s)://host:port)")
+            })?;
+            if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+                anyhow::bail!("Invalid HTTPS_PROXY (expected http(s)://host:port)");
+            }
+        }
+
+        if let Some(ref path) = self.ca_cert_path {
+            crate::utils::http_utils::load_ca_certificates(path)
+                .context("Invalid ATLASSIAN_CA_CERT_PATH")?;
+        }
+
+        if self.insecure_skip_verify {
+            tracing::warn!(
+                "ATLASSIAN_INSECURE_SKIP_VERIFY is enabled: TLS certificates are not verified"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Startup credential check: probes Jira and Confluence, logs a
+    /// remediation hint for each problem, and fails only when neither
+    /// product is usable with these credentials
+    pub async fn verify(&self) -> Result<()> {
+        use crate::tools::health::{CONFLUENCE_SPACES_PATH, JIRA_MYSELF_PATH, diagnose, probe};
+
+        let client = crate::utils::http_utils::create_atlassian_client(self);
+        let jira = probe(&client, self, JIRA_MYSELF_PATH).await;
+        let confluence = probe(&client, self, CONFLUENCE_SPACES_PATH).await;
+        let hints = diagnose(self, &jira, &confluence);
+
+        if !jira.is_ok() && !confluence.is_ok() {
+            anyhow::bail!("Credential verification failed: {}", hints.join("; "));
+        }
+        for hint in &hints {
+            tracing::warn!("{}", hint);
+        }
+        tracing::info!(
+            jira = jira.is_ok(),
+            confluence = confluence.is_ok(),
+            "Credentials verified"
+        );
         Ok(())
     }
 
+    /// Re-reads the environment, `.env` and the config file this was loaded from
+    pub fn reload(&self) -> Result<Self> {
+        let mut config = Self::load(self.config_file.as_deref())?;
+        config.validate()?;
+        // The mock site keeps running; only a restart leaves or enters mock mode
+        if config.mode == Mode::Mock && self.mode == Mode::Mock {
+            config.base_url = self.base_url.clone();
+        }
+        Ok(config)
+    }
+
+    /// Files whose changes trigger a reload
+    pub fn watched_files(&self) -> Vec<PathBuf> {
+        DOTENV_PATH
+            .get()
+            .cloned()
+            .flatten()
+            .into_iter()
+            .chain(self.config_file.as_ref().map(PathBuf::from))
+            .collect()
+    }
+
+    /// A copy for one tool call that must finish within `timeout`
+    pub fn with_deadline(&self, timeout: Duration) -> Self {
+        let mut config = self.clone();
+        config.deadline = Some(Instant::now() + timeout);
+        config
+    }
+
+    /// Per-request timeout: the time left before the call deadline, if any
+    pub fn request_timeout(&self) -> Duration {
+        match self.deadline {
+            Some(deadline) => deadline
+                .saturating_duration_since(Instant::now())
+                .max(Duration::from_millis(1)),
+            None => Duration::from_millis(self.request_timeout_ms),
+        }
+    }
+
+    /// `name` as clients see it, with `TOOL_NAME_PREFIX`
+    pub fn exposed_tool_name(&self, name: &str) -> String {
+        format!("{}{}", self.tool_name_prefix.as_deref().unwrap_or(""), name)
+    }
+
+    /// The registered name behind a name clients use; `None` when it lacks
+    /// `TOOL_NAME_PREFIX`
+    pub fn registered_tool_name<'a>(&self, name: &'a str) -> Option<&'a str> {
+        match &self.tool_name_prefix {
+            Some(prefix) => name.strip_prefix(prefix.as_str()),
+            None => Some(name),
+        }
+    }
+
+    /// Whether `ENABLED_TOOLS` allows `name`
+    pub fn tool_enabled(&self, name: &str) -> bool {
+        self.enabled_tools.is_empty()
+            || self
+                .enabled_tools
+                .iter()
+                .any(|pattern| match pattern.strip_suffix('*') {
+                    Some(prefix) => name.starts_with(prefix),
+                    None => pattern == name,
+                })
+    }
+
+    /// Trello tools are available only with the `trello` feature and both credentials
+    pub fn trello_enabled(&self) -> bool {
+        cfg!(feature = "trello") && self.trello_api_key.is_some() && self.trello_token.is_some()
+    }
+
     /// Returns the normalized Atlassian base URL.
     /// This is a zero-cost operation as the URL is pre-computed during initialization.
     #[inline]
@@ -168,41 +959,19 @@ impl Config {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use crate::test_support::ConfigBuilder;
 
     // T009: Valid configuration tests
     #[test]
     fn test_config_validation() {
-        let config = Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token123".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        };
+        let config = ConfigBuilder::new().build();
 
         assert!(config.validate().is_ok());
     }
 
     #[test]
     fn test_domain_normalization_adds_https() {
-        let config = Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        };
+        let config = ConfigBuilder::new().build();
 
         let url = config.get_atlassian_base_url();
         assert!(url.starts_with("https://"));
@@ -211,18 +980,9 @@ mod tests {
 
     #[test]
     fn test_domain_normalization_converts_http_to_https() {
-        let config = Config {
-            atlassian_domain: "http://test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        };
+        let config = ConfigBuilder::new()
+            .with(|config| config.atlassian_domain = "http://test.atlassian.net".to_string())
+            .build();
 
         let url = config.get_atlassian_base_url();
         assert!(url.starts_with("https://"));
@@ -232,72 +992,37 @@ mod tests {
     // T010: Invalid configuration tests
     #[test]
     fn test_invalid_domain() {
-        let config = Config {
-            atlassian_domain: "invalid-domain".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token123".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://invalid-domain".to_string(),
-        };
+        let config = ConfigBuilder::new()
+            .base_url("https://invalid-domain")
+            .with(|config| config.atlassian_domain = "invalid-domain".to_string())
+            .build();
 
         assert!(config.validate().is_err());
     }
 
     #[test]
     fn test_invalid_email_missing_at_symbol() {
-        let config = Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: "invalid-email".to_string(),
-            atlassian_api_token: "token".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        };
+        let config = ConfigBuilder::new()
+            .with(|config| config.atlassian_email = "invalid-email".to_string())
+            .build();
 
         assert!(config.validate().is_err());
     }
 
     #[test]
     fn test_empty_api_token_fails() {
-        let config = Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        };
+        let config = ConfigBuilder::new()
+            .with(|config| config.atlassian_api_token = "".to_string())
+            .build();
 
         assert!(config.validate().is_err());
     }
 
     #[test]
     fn test_invalid_timeout_too_low() {
-        let config = Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token".to_string(),
-            request_timeout_ms: 50,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        };
+        let config = ConfigBuilder::new()
+            .with(|config| config.request_timeout_ms = 50)
+            .build();
 
         assert!(config.validate().is_err());
     }
@@ -305,18 +1030,9 @@ mod tests {
     // T024: Additional configuration tests for coverage
     #[test]
     fn test_url_normalization_preserves_https() {
-        let config = Config {
-            atlassian_domain: "https://test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        };
+        let config = ConfigBuilder::new()
+            .with(|config| config.atlassian_domain = "https://test.atlassian.net".to_string())
+            .build();
 
         let url = config.get_atlassian_base_url();
         assert_eq!(url, "https://test.atlassian.net");
@@ -325,36 +1041,18 @@ mod tests {
 
     #[test]
     fn test_request_timeout_upper_bound() {
-        let config = Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token".to_string(),
-            request_timeout_ms: 60001, // Above max
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        };
+        let config = ConfigBuilder::new()
+            .with(|config| config.request_timeout_ms = 60001)
+            .build();
 
         assert!(config.validate().is_err());
     }
 
     #[test]
     fn test_project_filter_with_values() {
-        let config = Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec!["PROJ1".to_string(), "PROJ2".to_string()],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        };
+        let config = ConfigBuilder::new()
+            .jira_projects_filter(&["PROJ1", "PROJ2"])
+            .build();
 
         assert!(config.validate().is_ok());
         assert_eq!(config.jira_projects_filter.len(), 2);
@@ -363,18 +1061,9 @@ mod tests {
 
     #[test]
     fn test_space_filter_with_values() {
-        let config = Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec!["SPACE1".to_string(), "SPACE2".to_string()],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        };
+        let config = ConfigBuilder::new()
+            .confluence_spaces_filter(&["SPACE1", "SPACE2"])
+            .build();
 
         assert!(config.validate().is_ok());
         assert_eq!(config.confluence_spaces_filter.len(), 2);
@@ -383,21 +1072,14 @@ mod tests {
 
     #[test]
     fn test_jira_search_custom_fields_configuration() {
-        let config = Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![
-                "customfield_10015".to_string(),
-                "customfield_10016".to_string(),
-            ],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        };
+        let config = ConfigBuilder::new()
+            .with(|config| {
+                config.jira_search_custom_fields = vec![
+                    "customfield_10015".to_string(),
+                    "customfield_10016".to_string(),
+                ]
+            })
+            .build();
 
         assert!(config.validate().is_ok());
         assert_eq!(config.jira_search_custom_fields.len(), 2);
@@ -410,22 +1092,15 @@ mod tests {
 
     #[test]
     fn test_jira_search_default_fields_override() {
-        let config = Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: Some(vec![
-                "key".to_string(),
-                "summary".to_string(),
-                "status".to_string(),
-            ]),
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        };
+        let config = ConfigBuilder::new()
+            .with(|config| {
+                config.jira_search_default_fields = Some(vec![
+                    "key".to_string(),
+                    "summary".to_string(),
+                    "status".to_string(),
+                ])
+            })
+            .build();
 
         assert!(config.validate().is_ok());
         assert!(config.jira_search_default_fields.is_some());
@@ -434,19 +1109,57 @@ mod tests {
 
     #[test]
     fn test_domain_with_https_validates() {
-        let config = Config {
-            atlassian_domain: "https://test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        };
+        let config = ConfigBuilder::new()
+            .with(|config| config.atlassian_domain = "https://test.atlassian.net".to_string())
+            .build();
+
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_webhook_listen_addr_validation() {
+        let mut config = ConfigBuilder::new()
+            .with(|config| {
+                config.jira_webhook_listen_addr = Some("127.0.0.1:8787".to_string());
+                config.jira_webhook_public_url = Some("https://hooks.example.com".to_string());
+            })
+            .build();
+        assert!(config.validate().is_ok());
+
+        config.jira_webhook_listen_addr = Some("not-an-address".to_string());
+        assert!(config.validate().is_err());
+
+        // Public URL without a local listener is a misconfiguration
+        config.jira_webhook_listen_addr = None;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_bitbucket_credentials_must_be_paired() {
+        let mut config = ConfigBuilder::new()
+            .with(|config| {
+                config.bitbucket_workspace = Some("acme".to_string());
+                config.bitbucket_username = Some("bbuser".to_string());
+            })
+            .build();
+        assert!(config.validate().is_err());
+
+        config.bitbucket_app_password = Some("apppass".to_string());
+        assert!(config.validate().is_ok());
+    }
 
+    #[test]
+    fn test_network_options_validation() {
+        let mut config = ConfigBuilder::new()
+            .with(|config| config.https_proxy = Some("http://proxy.corp:3128".to_string()))
+            .build();
         assert!(config.validate().is_ok());
+
+        config.https_proxy = Some("proxy.corp:3128".to_string());
+        assert!(config.validate().is_err());
+
+        config.https_proxy = None;
+        config.ca_cert_path = Some("/nonexistent/ca.pem".to_string());
+        assert!(config.validate().is_err());
     }
 }
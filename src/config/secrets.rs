@@ -0,0 +1,71 @@
+//! Secret sources beyond plain environment variables
+//!
+//! For each secret (`ATLASSIAN_API_TOKEN`, `JIRA_WEBHOOK_SECRET`,
+//! `BITBUCKET_APP_PASSWORD`, `TRELLO_TOKEN`) the lookup order is the variable
+//! itself, then `<NAME>_FILE` (Docker secrets style), then the OS keychain
+//! when `ATLASSIAN_KEYCHAIN_SERVICE` is set. Keychain entries are stored
+//! under that service with the variable name as the account:
+//!
+//! - macOS: `security add-generic-password -s mcp-atlassian -a ATLASSIAN_API_TOKEN -w`
+//! - Linux: `secret-tool store --label=mcp-atlassian service mcp-atlassian account ATLASSIAN_API_TOKEN`
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// Reads a secret file, dropping the trailing newline editors and `echo` add
+pub fn read_secret_file(path: &Path) -> Result<String> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read secret file {}", path.display()))?;
+    let secret = content.trim_end_matches(['\r', '\n']).to_string();
+    if secret.is_empty() {
+        anyhow::bail!("Secret file {} is empty", path.display());
+    }
+    Ok(secret)
+}
+
+/// Looks up `account` under `service` in the platform keychain; `None` when
+/// there is no such entry
+pub fn keychain_lookup(service: &str, account: &str) -> Result<Option<String>> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut c = Command::new("security");
+        c.args(["find-generic-password", "-s", service, "-a", account, "-w"]);
+        c
+    } else if cfg!(target_os = "linux") {
+        let mut c = Command::new("secret-tool");
+        c.args(["lookup", "service", service, "account", account]);
+        c
+    } else {
+        anyhow::bail!("ATLASSIAN_KEYCHAIN_SERVICE is not supported on this platform");
+    };
+
+    let output = command
+        .output()
+        .context("Failed to run the keychain tool (security / secret-tool)")?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    let secret = String::from_utf8(output.stdout)
+        .context("Keychain entry is not valid UTF-8")?
+        .trim_end_matches(['\r', '\n'])
+        .to_string();
+    Ok(Some(secret).filter(|s| !s.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_secret_file_trims_newline() {
+        let path = std::env::temp_dir().join(format!("mcp-secret-{}", std::process::id()));
+        std::fs::write(&path, "ATATT-secret \n").unwrap();
+        assert_eq!(read_secret_file(&path).unwrap(), "ATATT-secret ");
+
+        std::fs::write(&path, "\n").unwrap();
+        assert!(read_secret_file(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(read_secret_file(&path).is_err());
+    }
+}
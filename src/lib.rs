@@ -1,7 +1,16 @@
 // Library entry point for mcp-atlassian
-// Exposes internal modules for testing
+// Exposes internal modules for testing, and `AtlassianToolkit` (see `client`)
+// for use as a library without the MCP server
 
+pub mod client;
 pub mod config;
 pub mod mcp;
+pub mod mock;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
+pub mod toolkit;
 pub mod tools;
 pub mod utils;
+pub mod webhook;
+
+pub use toolkit::AtlassianToolkit;
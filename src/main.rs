@@ -1,20 +1,66 @@
+mod client;
 mod config;
 mod mcp;
+mod mock;
+// For unit tests; most of the harness only serves the library's users
+#[cfg(test)]
+#[allow(dead_code)]
+mod test_support;
 mod tools;
 mod utils;
+mod webhook;
 
 use anyhow::Result;
 use tokio::signal;
 
+/// Value of `--config <path>` or `--config=<path>`
+fn config_arg() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            return args.next();
+        }
+        if let Some(path) = arg.strip_prefix("--config=") {
+            return Some(path.to_string());
+        }
+    }
+    None
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logging to stderr
     utils::logging::init_logging();
 
-    // Load configuration
-    let config = config::Config::from_env()?;
+    // Load configuration (`--config <path>` layers a TOML file under the environment)
+    let config_path = config_arg();
+    let mut config = config::Config::load(config_path.as_deref())?;
     config.validate()?;
 
+    // Mock mode: every request goes to the in-process sample site
+    let _mock = if config.mode == config::Mode::Mock {
+        let mock = mock::MockAtlassianServer::start().await?;
+        config.base_url = mock.base_url();
+        tracing::info!("Mock mode: serving sample data from {}", config.base_url);
+        Some(mock)
+    } else {
+        None
+    };
+
+    // `--check`: run the credential self-test, print the report and exit
+    if std::env::args().skip(1).any(|arg| arg == "--check") {
+        let report = tools::health::run_health_check(&config, true).await;
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        std::process::exit(if report["healthy"] == true { 0 } else { 1 });
+    }
+
+    // Opt-in startup probe with remediation hints (VERIFY_CREDENTIALS);
+    // replay and mock never call Atlassian, so placeholder credentials are fine
+    if config.verify_credentials && matches!(config.mode, config::Mode::Live | config::Mode::Record)
+    {
+        config.verify().await?;
+    }
+
     utils::logging::log_startup(&config);
 
     // Create and run MCP server
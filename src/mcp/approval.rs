@@ -0,0 +1,234 @@
+//! Two-step confirmation for destructive writes (`CONFIRM_DESTRUCTIVE_WRITES`)
+//!
+//! Deletes, bulk edits and transitions into a done status do not run on the
+//! first call: it changes nothing and answers with what the call would do
+//! and a `confirm_token`. The change runs when the same tool is called again
+//! with the same arguments plus that token. Tokens are single-use, expire
+//! after [`TOKEN_TTL`] and are bound to the tool and its arguments, so a
+//! token issued for one change cannot confirm another. Unlike MCP
+//! elicitation this needs nothing from the client, and the assistant is
+//! expected to ask the user before it sends the token back.
+
+use anyhow::Result;
+use serde_json::{Value, json};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::client::Jira;
+use crate::config::Config;
+
+use super::notifications::PROGRESS_TOKEN_ARG;
+
+/// Argument carrying the token; removed before the handler sees the call
+pub const CONFIRM_TOKEN_ARG: &str = "confirm_token";
+/// How long an issued token stays valid
+pub const TOKEN_TTL: Duration = Duration::from_secs(300);
+/// Unredeemed tokens kept; the oldest is dropped beyond this
+const MAX_PENDING: usize = 100;
+
+/// Canonical JSON of the arguments that identify a call: everything but
+/// the progress token, which differs on every call
+fn identifying_args(args: &Value) -> String {
+    let mut args = args.clone();
+    if let Some(map) = args.as_object_mut() {
+        map.remove(PROGRESS_TOKEN_ARG);
+    }
+    args.to_string()
+}
+
+struct Pending {
+    token: String,
+    tool: String,
+    /// Canonical JSON of the arguments the token was issued for
+    args: String,
+    expires: Instant,
+}
+
+pub struct Approvals {
+    pending: Mutex<Vec<Pending>>,
+}
+
+/// The process-wide tokens used by `call_tool`; like the circuit breakers
+/// they survive config reloads
+pub static APPROVALS: Approvals = Approvals::new();
+
+static ISSUED: AtomicU64 = AtomicU64::new(0);
+
+impl Default for Approvals {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Approvals {
+    pub const fn new() -> Self {
+        Self {
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// A new token for calling `tool` with `args`
+    pub fn issue(&self, tool: &str, args: &Value, now: Instant) -> String {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(ISSUED.fetch_add(1, Ordering::Relaxed));
+        hasher.write(tool.as_bytes());
+        let token = format!("{:016x}", hasher.finish());
+
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.retain(|p| p.expires > now);
+        if pending.len() >= MAX_PENDING {
+            pending.remove(0);
+        }
+        pending.push(Pending {
+            token: token.clone(),
+            tool: tool.to_string(),
+            args: identifying_args(args),
+            expires: now + TOKEN_TTL,
+        });
+        token
+    }
+
+    /// Consumes `token` when it was issued for `tool` with exactly `args`
+    /// and has not expired
+    pub fn redeem(&self, token: &str, tool: &str, args: &Value, now: Instant) -> Result<()> {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.retain(|p| p.expires > now);
+        let Some(index) = pending.iter().position(|p| p.token == token) else {
+            anyhow::bail!(
+                "confirm_token is unknown or expired; call {} again without it for a new one",
+                tool
+            );
+        };
+        let args = identifying_args(args);
+        if pending[index].tool != tool || pending[index].args != args {
+            anyhow::bail!(
+                "confirm_token was issued for a different call; call {} again without it for a new one",
+                tool
+            );
+        }
+        pending.remove(index);
+        Ok(())
+    }
+}
+
+/// Tools that can need confirmation, and so accept `confirm_token`
+pub fn may_need_confirmation(tool: &str) -> bool {
+    matches!(
+        tool,
+        "jira_delete_issue_property"
+            | "jira_bulk_update"
+            | "jira_bulk_transition"
+            | "jira_transition_issue"
+    )
+}
+
+/// What the call would do when it needs confirmation; `None` runs it as is.
+/// Previews (`dry_run`, `jira_bulk_update` without `confirm`) never do.
+pub async fn destructive_change(
+    tool: &str,
+    args: &Value,
+    config: &Config,
+) -> Result<Option<String>> {
+    let str_arg = |name: &str| args[name].as_str().unwrap_or_default();
+    Ok(match tool {
+        "jira_delete_issue_property" => Some(format!(
+            "deletes property '{}' of {}",
+            str_arg("property_key"),
+            str_arg("issue_key")
+        )),
+        "jira_bulk_update" if !args["confirm"].is_null() => {
+            Some(format!("updates every issue matching '{}'", str_arg("jql")))
+        }
+        "jira_bulk_transition" if !args["dry_run"].as_bool().unwrap_or(false) => Some(format!(
+            "applies '{}' to every issue matching '{}'",
+            str_arg("transition"),
+            str_arg("jql")
+        )),
+        "jira_transition_issue" => {
+            let issue_key = str_arg("issue_key");
+            let transitions = Jira::new(config).get_transitions(issue_key).await?;
+            transitions
+                .iter()
+                .find(|t| t.id == str_arg("transition_id"))
+                .and_then(|t| t.to.as_ref())
+                .filter(|to| to.extra["statusCategory"]["key"] == "done")
+                .map(|to| format!("moves {} to {}", issue_key, to.name))
+        }
+        _ => None,
+    })
+}
+
+/// The result of a call held for confirmation; `tool` as the client names it
+pub fn confirmation_request(tool: &str, change: &str, token: &str) -> Value {
+    json!({
+        "success": false,
+        "confirmation_required": true,
+        "change": change,
+        "confirm_token": token,
+        "expires_in_secs": TOKEN_TTL.as_secs(),
+        "message": format!(
+            "Nothing changed yet: this call {}. Ask the user to confirm, then call {} again with the same arguments and confirm_token",
+            change, tool
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_are_single_use_and_bound_to_the_call() {
+        let approvals = Approvals::new();
+        let now = Instant::now();
+        let args = json!({"issue_key": "PROJ-1", "property_key": "p"});
+        let token = approvals.issue("jira_delete_issue_property", &args, now);
+
+        let other = json!({"issue_key": "PROJ-2", "property_key": "p"});
+        let error = approvals
+            .redeem(&token, "jira_delete_issue_property", &other, now)
+            .unwrap_err();
+        assert!(error.to_string().contains("different call"));
+
+        assert!(
+            approvals
+                .redeem(&token, "jira_delete_issue_property", &args, now)
+                .is_ok()
+        );
+        let error = approvals
+            .redeem(&token, "jira_delete_issue_property", &args, now)
+            .unwrap_err();
+        assert!(error.to_string().contains("unknown or expired"));
+    }
+
+    #[test]
+    fn test_tokens_ignore_the_progress_token() {
+        let approvals = Approvals::new();
+        let now = Instant::now();
+        let first = json!({"jql": "project = PROJ", "confirm": 3, PROGRESS_TOKEN_ARG: 1});
+        let token = approvals.issue("jira_bulk_update", &first, now);
+        let second = json!({"jql": "project = PROJ", "confirm": 3, PROGRESS_TOKEN_ARG: 2});
+        assert!(
+            approvals
+                .redeem(&token, "jira_bulk_update", &second, now)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_tokens_expire() {
+        let approvals = Approvals::new();
+        let now = Instant::now();
+        let args = json!({"jql": "project = PROJ"});
+        let token = approvals.issue("jira_bulk_transition", &args, now);
+        let later = now + TOKEN_TTL + Duration::from_secs(1);
+        assert!(
+            approvals
+                .redeem(&token, "jira_bulk_transition", &args, later)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,288 @@
+//! Audit trail of write operations
+//!
+//! Each write tool call becomes one JSON record: timestamp, tool, redacted
+//! arguments, target, outcome and latency. Records are appended to a JSONL
+//! file (rotated to `<path>.1` past `AUDIT_LOG_MAX_BYTES`) or emitted as
+//! tracing events with target `audit` when `AUDIT_LOG=stderr`. Failing to
+//! write a record is logged and never fails the tool call.
+
+use serde_json::{Map, Value, json};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config::Config;
+
+/// Argument keys whose values are never written
+const SECRET_KEYS: &[&str] = &["token", "password", "secret", "api_key", "authorization"];
+
+/// Longer string arguments (bodies, descriptions) are reduced to their length
+const MAX_ARG_CHARS: usize = 200;
+
+/// Argument keys identifying what a write touched, in preference order
+const TARGET_KEYS: &[&str] = &[
+    "issue_key",
+    "page_id",
+    "comment_id",
+    "task_id",
+    "project_key",
+    "space_key",
+    "card_id",
+    "pr_id",
+];
+
+enum Sink {
+    Stderr,
+    File {
+        path: PathBuf,
+        max_bytes: u64,
+        file: Mutex<Option<File>>,
+    },
+}
+
+pub struct AuditLog {
+    sink: Sink,
+}
+
+impl AuditLog {
+    /// `None` when `AUDIT_LOG` is unset
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let target = config.audit_log.as_deref()?.trim();
+        let sink = if target.eq_ignore_ascii_case("stderr") {
+            Sink::Stderr
+        } else {
+            Sink::File {
+                path: PathBuf::from(target),
+                max_bytes: config.audit_log_max_bytes,
+                file: Mutex::new(None),
+            }
+        };
+        Some(Self { sink })
+    }
+
+    /// Records one finished write call
+    pub fn record(
+        &self,
+        tool: &str,
+        args: &Value,
+        outcome: &anyhow::Result<Value>,
+        latency: Duration,
+    ) {
+        let entry = audit_entry(SystemTime::now(), tool, args, outcome, latency);
+        match &self.sink {
+            Sink::Stderr => tracing::info!(
+                target: "audit",
+                tool,
+                target_ref = %entry["target"],
+                result = %entry["result"],
+                latency_ms = latency.as_millis() as u64,
+                record = %entry,
+                "Write operation"
+            ),
+            Sink::File {
+                path,
+                max_bytes,
+                file,
+            } => {
+                let mut guard = file.lock().unwrap_or_else(|e| e.into_inner());
+                if let Err(e) = append_line(path, *max_bytes, &mut guard, &entry.to_string()) {
+                    tracing::warn!(path = %path.display(), error = %e, "Failed to write audit record");
+                }
+            }
+        }
+    }
+}
+
+/// Appends `line`, first rotating the file when it would exceed `max_bytes`
+fn append_line(
+    path: &PathBuf,
+    max_bytes: u64,
+    file: &mut Option<File>,
+    line: &str,
+) -> std::io::Result<()> {
+    if max_bytes > 0 {
+        let len = match file {
+            Some(f) => f.metadata()?.len(),
+            None => std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+        };
+        if len > 0 && len + line.len() as u64 + 1 > max_bytes {
+            *file = None;
+            let mut rotated = path.clone().into_os_string();
+            rotated.push(".1");
+            std::fs::rename(path, rotated)?;
+        }
+    }
+
+    if file.is_none() {
+        *file = Some(OpenOptions::new().create(true).append(true).open(path)?);
+    }
+    let f = file.as_mut().expect("audit file opened above");
+    writeln!(f, "{}", line)?;
+    f.flush()
+}
+
+/// Builds the record for one call
+pub fn audit_entry(
+    now: SystemTime,
+    tool: &str,
+    args: &Value,
+    outcome: &anyhow::Result<Value>,
+    latency: Duration,
+) -> Value {
+    let result = match outcome {
+        Ok(_) => json!({ "status": "ok" }),
+        Err(e) => json!({ "status": "error", "error": truncate(&e.to_string()) }),
+    };
+    json!({
+        "ts": rfc3339(now),
+        "tool": tool,
+        "target": audit_target(args),
+        "args": redact(args),
+        "result": result,
+        "latency_ms": latency.as_millis() as u64
+    })
+}
+
+/// `key=value` for the first identifying argument, or `null`
+pub fn audit_target(args: &Value) -> Value {
+    TARGET_KEYS
+        .iter()
+        .find_map(|key| match &args[*key] {
+            Value::String(s) => Some(json!(format!("{}={}", key, s))),
+            Value::Number(n) => Some(json!(format!("{}={}", key, n))),
+            _ => None,
+        })
+        .unwrap_or(Value::Null)
+}
+
+/// Masks credential-like keys and reduces long strings to their length
+pub fn redact(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| {
+                    let lower = k.to_lowercase();
+                    let v = if SECRET_KEYS.iter().any(|s| lower.contains(s)) {
+                        json!("[REDACTED]")
+                    } else {
+                        redact(v)
+                    };
+                    (k.clone(), v)
+                })
+                .collect::<Map<_, _>>(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact).collect()),
+        Value::String(s) if s.chars().count() > MAX_ARG_CHARS => {
+            json!(format!("[{} chars]", s.chars().count()))
+        }
+        other => other.clone(),
+    }
+}
+
+fn truncate(s: &str) -> String {
+    match s.char_indices().nth(MAX_ARG_CHARS) {
+        Some((at, _)) => format!("{}...", &s[..at]),
+        None => s.to_string(),
+    }
+}
+
+/// UTC timestamp such as `2024-01-02T03:04:05.678Z`
+pub fn rfc3339(time: SystemTime) -> String {
+    let since = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since.as_secs();
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60,
+        since.subsec_millis()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc3339() {
+        assert_eq!(rfc3339(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        let t = UNIX_EPOCH + Duration::from_millis(1_709_210_096_789);
+        assert_eq!(rfc3339(t), "2024-02-29T12:34:56.789Z");
+    }
+
+    #[test]
+    fn test_redact() {
+        let args = json!({
+            "issue_key": "PROJ-1",
+            "api_token": "abc",
+            "fields": {"Password": "x", "summary": "short"},
+            "body": "y".repeat(500)
+        });
+        let redacted = redact(&args);
+        assert_eq!(redacted["issue_key"], "PROJ-1");
+        assert_eq!(redacted["api_token"], "[REDACTED]");
+        assert_eq!(redacted["fields"]["Password"], "[REDACTED]");
+        assert_eq!(redacted["fields"]["summary"], "short");
+        assert_eq!(redacted["body"], "[500 chars]");
+    }
+
+    #[test]
+    fn test_audit_entry() {
+        let args = json!({"page_id": "123", "content": "<p>x</p>"});
+        let err: anyhow::Result<Value> = Err(anyhow::anyhow!("Failed to update page: 403"));
+        let entry = audit_entry(
+            UNIX_EPOCH,
+            "confluence_update_page",
+            &args,
+            &err,
+            Duration::from_millis(42),
+        );
+        assert_eq!(entry["tool"], "confluence_update_page");
+        assert_eq!(entry["target"], "page_id=123");
+        assert_eq!(entry["result"]["status"], "error");
+        assert_eq!(entry["result"]["error"], "Failed to update page: 403");
+        assert_eq!(entry["latency_ms"], 42);
+
+        assert_eq!(audit_target(&json!({"summary": "x"})), Value::Null);
+    }
+
+    #[test]
+    fn test_file_sink_appends_and_rotates() {
+        let dir = std::env::temp_dir().join(format!("mcp-audit-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("audit.jsonl");
+        let rotated = dir.join("audit.jsonl.1");
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&rotated);
+
+        let mut file = None;
+        append_line(&path, 20, &mut file, "first record").unwrap();
+        append_line(&path, 20, &mut file, "second record").unwrap();
+        append_line(&path, 20, &mut file, "third").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&rotated).unwrap(), "first record\n");
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "second record\nthird\n"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,278 @@
+//! Remediation hints for common Atlassian errors
+//!
+//! Jira and Confluence error texts say what failed, rarely what to do next,
+//! and the assistant tends to retry the same call. A failed tool call's
+//! message is matched against [`RULES`] and the first rule whose patterns all
+//! occur (case-insensitively) appends its hint. To cover another error, add
+//! a row: the patterns are plain substrings of the text the tool reports,
+//! and the hint is given in every [`Locale`].
+
+use super::i18n::{Locale, Text};
+
+/// Which authentication problem a failed call ran into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthFailure {
+    /// 401: the credentials were rejected (invalid, expired or revoked token)
+    Unauthorized,
+    /// 403: the credentials are fine, the account lacks permission
+    Forbidden,
+}
+
+impl AuthFailure {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Unauthorized => "unauthorized",
+            Self::Forbidden => "forbidden",
+        }
+    }
+}
+
+/// Classifies an error message by the status or body text Atlassian sends
+pub fn auth_failure(message: &str) -> Option<AuthFailure> {
+    let message = message.to_lowercase();
+    if message.contains("401 unauthorized") || message.contains("client must be authenticated") {
+        Some(AuthFailure::Unauthorized)
+    } else if message.contains("403 forbidden") {
+        Some(AuthFailure::Forbidden)
+    } else {
+        None
+    }
+}
+
+struct Rule {
+    /// Substrings that must all occur, lowercase
+    patterns: &'static [&'static str],
+    hint: Text,
+}
+
+const UNAUTHORIZED_HINT: Text = Text {
+    en: "The API token was rejected (invalid, expired or revoked). Create a new one at id.atlassian.com and set ATLASSIAN_API_TOKEN in .env or the config file; the next call that gets a 401 picks it up without a restart.",
+    ko: "API 토큰이 거부되었습니다(잘못되었거나 만료 또는 폐기됨). id.atlassian.com에서 새 토큰을 만들어 .env 또는 설정 파일의 ATLASSIAN_API_TOKEN에 설정하세요. 다음에 401을 받는 호출이 재시작 없이 이를 반영합니다.",
+    ja: "API トークンが拒否されました(無効、期限切れ、または失効)。id.atlassian.com で新しいトークンを作成し、.env または設定ファイルの ATLASSIAN_API_TOKEN に設定してください。次に 401 を受けた呼び出しで再起動なしに反映されます。",
+};
+
+const RULES: &[Rule] = &[
+    Rule {
+        patterns: &["401 unauthorized"],
+        hint: UNAUTHORIZED_HINT,
+    },
+    Rule {
+        patterns: &["client must be authenticated"],
+        hint: UNAUTHORIZED_HINT,
+    },
+    Rule {
+        patterns: &["cannot be set", "appropriate screen"],
+        hint: Text {
+            en: "The field is not on this project's create/edit screen for the issue type, or the field ID is wrong (custom field IDs differ per site). Leave the field out or ask a Jira admin to add it to the screen.",
+            ko: "필드가 이 프로젝트의 해당 이슈 유형 생성/편집 화면에 없거나 필드 ID가 잘못되었습니다(사용자 정의 필드 ID는 사이트마다 다름). 필드를 빼거나 Jira 관리자에게 화면에 추가해 달라고 요청하세요.",
+            ja: "フィールドがこのプロジェクトの課題タイプの作成/編集画面にないか、フィールド ID が誤っています(カスタムフィールド ID はサイトごとに異なります)。フィールドを外すか、Jira 管理者に画面への追加を依頼してください。",
+        },
+    },
+    Rule {
+        patterns: &["valid issue type"],
+        hint: Text {
+            en: "Use an issue type this project offers, by its exact name (e.g. 'Task', 'Bug'); sub-task types also need a parent.",
+            ko: "이 프로젝트에서 제공하는 이슈 유형을 정확한 이름으로 사용하세요(예: 'Task', 'Bug'). 하위 작업 유형에는 상위 이슈도 필요합니다.",
+            ja: "このプロジェクトで使える課題タイプを正確な名前で指定してください(例: 'Task'、'Bug')。サブタスクタイプには親課題も必要です。",
+        },
+    },
+    Rule {
+        patterns: &["transition", "not valid"],
+        hint: Text {
+            en: "Transition IDs depend on the workflow and the current status: list the valid ones with jira_get_transitions.",
+            ko: "전환 ID는 워크플로와 현재 상태에 따라 다릅니다. jira_get_transitions로 유효한 전환을 확인하세요.",
+            ja: "トランジション ID はワークフローと現在のステータスによって異なります。jira_get_transitions で有効なものを確認してください。",
+        },
+    },
+    Rule {
+        patterns: &["does not exist for the field"],
+        hint: Text {
+            en: "A JQL value is unknown: check the spelling of the project key, status or user, and quote values that contain spaces.",
+            ko: "JQL 값을 알 수 없습니다. 프로젝트 키, 상태, 사용자의 철자를 확인하고 공백이 있는 값은 따옴표로 감싸세요.",
+            ja: "JQL の値が不明です。プロジェクトキー、ステータス、ユーザーの綴りを確認し、空白を含む値は引用符で囲んでください。",
+        },
+    },
+    Rule {
+        patterns: &["does not exist or this field cannot be viewed"],
+        hint: Text {
+            en: "A JQL field name is unknown: use the field's display name in quotes or cf[<id>] for custom fields.",
+            ko: "JQL 필드 이름을 알 수 없습니다. 필드 표시 이름을 따옴표로 감싸거나 사용자 정의 필드는 cf[<id>]를 사용하세요.",
+            ja: "JQL のフィールド名が不明です。フィールドの表示名を引用符で囲むか、カスタムフィールドには cf[<id>] を使ってください。",
+        },
+    },
+    Rule {
+        patterns: &["error in the jql query"],
+        hint: Text {
+            en: "Fix the JQL syntax: values with spaces or reserved words need double quotes, and ORDER BY comes last.",
+            ko: "JQL 구문을 고치세요. 공백이나 예약어가 있는 값은 큰따옴표가 필요하고 ORDER BY는 맨 끝에 옵니다.",
+            ja: "JQL の構文を修正してください。空白や予約語を含む値には二重引用符が必要で、ORDER BY は最後に置きます。",
+        },
+    },
+    Rule {
+        patterns: &["parse cql"],
+        hint: Text {
+            en: "Fix the CQL syntax, e.g. type = page AND space = \"KEY\" AND text ~ \"term\"; values need double quotes.",
+            ko: "CQL 구문을 고치세요. 예: type = page AND space = \"KEY\" AND text ~ \"term\"; 값에는 큰따옴표가 필요합니다.",
+            ja: "CQL の構文を修正してください。例: type = page AND space = \"KEY\" AND text ~ \"term\"。値には二重引用符が必要です。",
+        },
+    },
+    Rule {
+        patterns: &["does not exist or you do not have permission"],
+        hint: Text {
+            en: "Either the key is wrong or the API token's user cannot browse it: check the key and that user's project permissions.",
+            ko: "키가 잘못되었거나 API 토큰 사용자가 볼 수 없습니다. 키와 해당 사용자의 프로젝트 권한을 확인하세요.",
+            ja: "キーが誤っているか、API トークンのユーザーに閲覧権限がありません。キーとそのユーザーのプロジェクト権限を確認してください。",
+        },
+    },
+    Rule {
+        patterns: &["gadget"],
+        hint: Text {
+            en: "Dashboard gadgets are only visible to users the dashboard is shared with; check the dashboard's sharing and the gadget's filter permissions.",
+            ko: "대시보드 가젯은 대시보드가 공유된 사용자에게만 보입니다. 대시보드 공유 설정과 가젯 필터 권한을 확인하세요.",
+            ja: "ダッシュボードのガジェットは、ダッシュボードが共有されたユーザーにのみ表示されます。ダッシュボードの共有設定とガジェットのフィルター権限を確認してください。",
+        },
+    },
+    Rule {
+        patterns: &["already exists", "title"],
+        hint: Text {
+            en: "Page titles are unique per space: pick another title, or find the existing page with confluence_get_page_by_title and update it.",
+            ko: "페이지 제목은 스페이스 안에서 고유해야 합니다. 다른 제목을 쓰거나 confluence_get_page_by_title로 기존 페이지를 찾아 수정하세요.",
+            ja: "ページタイトルはスペース内で一意です。別のタイトルにするか、confluence_get_page_by_title で既存のページを見つけて更新してください。",
+        },
+    },
+    Rule {
+        patterns: &["version must be incremented"],
+        hint: Text {
+            en: "The page changed since it was read: fetch it again with confluence_get_page and retry with the new version.",
+            ko: "읽은 뒤 페이지가 바뀌었습니다. confluence_get_page로 다시 가져와 새 버전으로 재시도하세요.",
+            ja: "読み取り後にページが変更されました。confluence_get_page で再取得し、新しいバージョンで再試行してください。",
+        },
+    },
+    Rule {
+        patterns: &["403 forbidden"],
+        hint: Text {
+            en: "The account lacks permission for this action (project role or space permission); jira_get_permission_scheme shows who holds which Jira permission.",
+            ko: "계정에 이 작업 권한(프로젝트 역할 또는 스페이스 권한)이 없습니다. jira_get_permission_scheme으로 누가 어떤 Jira 권한을 가졌는지 볼 수 있습니다.",
+            ja: "アカウントにこの操作の権限(プロジェクトロールまたはスペース権限)がありません。jira_get_permission_scheme で誰がどの Jira 権限を持つか確認できます。",
+        },
+    },
+    Rule {
+        patterns: &["429 too many requests"],
+        hint: Text {
+            en: "Atlassian is rate limiting this account: wait before retrying and prefer smaller limits or fewer parallel calls.",
+            ko: "Atlassian이 이 계정의 요청 속도를 제한하고 있습니다. 잠시 기다린 뒤 재시도하고, 더 작은 limit이나 더 적은 병렬 호출을 사용하세요.",
+            ja: "Atlassian がこのアカウントのリクエストを制限しています。時間をおいて再試行し、limit を小さくするか並列呼び出しを減らしてください。",
+        },
+    },
+];
+
+const HINT_LABEL: Text = Text {
+    en: "Hint",
+    ko: "힌트",
+    ja: "ヒント",
+};
+
+/// The hint for an error message, if a rule matches
+pub fn hint_for(message: &str, locale: Locale) -> Option<&'static str> {
+    let message = message.to_lowercase();
+    RULES
+        .iter()
+        .find(|rule| rule.patterns.iter().all(|p| message.contains(p)))
+        .map(|rule| rule.hint.get(locale))
+}
+
+/// `message` followed by its hint, if any
+pub fn with_hint(message: &str, locale: Locale) -> String {
+    match hint_for(message, locale) {
+        Some(hint) => format!("{}\n{}: {}", message, HINT_LABEL.get(locale), hint),
+        None => message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_are_lowercase() {
+        for rule in RULES {
+            for pattern in rule.patterns {
+                assert_eq!(*pattern, pattern.to_lowercase());
+            }
+        }
+    }
+
+    #[test]
+    fn test_known_errors_get_hints() {
+        let message = r#"Failed to create issue: {"errorMessages":[],"errors":{"customfield_10010":"Field 'customfield_10010' cannot be set. It is not on the appropriate screen, or unknown."}}"#;
+        assert!(
+            with_hint(message, Locale::En).contains("\nHint: The field is not on this project's")
+        );
+        assert!(
+            hint_for(
+                r#"{"errors":{"issuetype":"Specify a valid issue type"}}"#,
+                Locale::En
+            )
+            .unwrap()
+            .contains("exact name")
+        );
+        assert!(
+            hint_for(
+                "Failed to transition issue: Transition id '31' is not valid for this issue.",
+                Locale::En
+            )
+            .unwrap()
+            .contains("jira_get_transitions")
+        );
+        assert!(
+            hint_for(
+                "Search failed: Error in the JQL Query: Expecting operator",
+                Locale::En
+            )
+            .unwrap()
+            .starts_with("Fix the JQL")
+        );
+    }
+
+    #[test]
+    fn test_hints_follow_the_locale() {
+        let message = "Failed to transition issue: Transition id '31' is not valid for this issue.";
+        let ko = with_hint(message, Locale::Ko);
+        assert!(ko.starts_with(message));
+        assert!(ko.contains("\n힌트: 전환 ID는"));
+        assert!(with_hint(message, Locale::Ja).contains("\nヒント: トランジション ID"));
+        for rule in RULES {
+            assert!(!rule.hint.ko.is_empty() && !rule.hint.ja.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_auth_failures_are_told_apart() {
+        assert_eq!(
+            auth_failure("Failed to get issue: 401 Unauthorized"),
+            Some(AuthFailure::Unauthorized)
+        );
+        assert_eq!(
+            auth_failure(
+                "Failed to create issue: Client must be authenticated to access this resource."
+            ),
+            Some(AuthFailure::Unauthorized)
+        );
+        assert_eq!(
+            auth_failure("Failed to update page: 403 Forbidden"),
+            Some(AuthFailure::Forbidden)
+        );
+        assert_eq!(auth_failure("Failed to get issue: 404 Not Found"), None);
+        assert!(
+            hint_for("Failed to get issue: 401 Unauthorized", Locale::En)
+                .unwrap()
+                .contains("ATLASSIAN_API_TOKEN")
+        );
+    }
+
+    #[test]
+    fn test_unknown_errors_are_unchanged() {
+        assert_eq!(hint_for("connection reset by peer", Locale::En), None);
+        assert_eq!(with_hint("timeout", Locale::Ko), "timeout");
+    }
+}
@@ -0,0 +1,179 @@
+//! Record/replay of tool results (`ATLASSIAN_MODE=record|replay`)
+//!
+//! In `record` mode every handler result (or error) is saved as one JSON file
+//! under `ATLASSIAN_FIXTURES_DIR`, named `<tool>-<hash>.json` after the tool
+//! and its arguments. In `replay` mode handlers do not run: the saved result
+//! is served instead, so demos, downstream agent CI and prompt work get the
+//! same answers without network access. Fixtures hold the handler output
+//! before response optimization and timestamp localization, which still run
+//! on replay, so field filters can be tuned against recorded data.
+//!
+//! Fixtures contain whatever Atlassian returned; review them before sharing.
+
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, Mode};
+
+use super::notifications::PROGRESS_TOKEN_ARG;
+
+pub struct Fixtures {
+    dir: PathBuf,
+    mode: Mode,
+}
+
+/// 64-bit FNV-1a; unlike `DefaultHasher` it is stable across Rust releases,
+/// so fixture names stay valid
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// The arguments that identify a call: everything but the progress token
+fn identifying_args(args: &Value) -> Value {
+    let mut args = args.clone();
+    if let Some(map) = args.as_object_mut() {
+        map.remove(PROGRESS_TOKEN_ARG);
+    }
+    args
+}
+
+/// File name of a call's fixture; object keys serialize sorted, so equal
+/// arguments always give the same name
+pub fn fixture_name(tool: &str, args: &Value) -> String {
+    let args = identifying_args(args).to_string();
+    format!("{}-{:016x}.json", tool, fnv1a(args.as_bytes()))
+}
+
+impl Fixtures {
+    pub fn from_config(config: &Config) -> Option<Self> {
+        match config.mode {
+            mode @ (Mode::Record | Mode::Replay) => Some(Self {
+                dir: PathBuf::from(&config.fixtures_dir),
+                mode,
+            }),
+            Mode::Live | Mode::Mock => None,
+        }
+    }
+
+    pub fn replaying(&self) -> bool {
+        self.mode == Mode::Replay
+    }
+
+    fn path(&self, tool: &str, args: &Value) -> PathBuf {
+        self.dir.join(fixture_name(tool, args))
+    }
+
+    /// The recorded outcome of a call
+    pub fn replay(&self, tool: &str, args: &Value) -> Result<Value> {
+        let path = self.path(tool, args);
+        let text = std::fs::read_to_string(&path).with_context(|| {
+            format!(
+                "No recorded result for {} with these arguments ({}); record it with ATLASSIAN_MODE=record",
+                tool,
+                path.display()
+            )
+        })?;
+        let fixture: Value = serde_json::from_str(&text)
+            .with_context(|| format!("Invalid fixture {}", path.display()))?;
+        match fixture["error"].as_str() {
+            Some(error) => Err(anyhow::anyhow!("{}", error)),
+            None => Ok(fixture["result"].clone()),
+        }
+    }
+
+    /// Saves a call's outcome; failures are logged and never fail the call
+    pub fn record(&self, tool: &str, args: &Value, outcome: &Result<Value>) {
+        let path = self.path(tool, args);
+        let mut fixture = json!({
+            "tool": tool,
+            "arguments": identifying_args(args)
+        });
+        match outcome {
+            Ok(result) => fixture["result"] = result.clone(),
+            Err(e) => fixture["error"] = json!(e.to_string()),
+        }
+        if let Err(e) = write_fixture(&path, &fixture) {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to record fixture");
+        }
+    }
+}
+
+fn write_fixture(path: &Path, fixture: &Value) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(fixture)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixtures(mode: Mode) -> Fixtures {
+        Fixtures {
+            dir: std::env::temp_dir().join(format!("mcp-fixtures-{}", std::process::id())),
+            mode,
+        }
+    }
+
+    #[test]
+    fn test_fixture_names_ignore_progress_token() {
+        let name = fixture_name("jira_get_issue", &json!({"issue_key": "A-1"}));
+        assert!(name.starts_with("jira_get_issue-"));
+        assert_eq!(
+            name,
+            fixture_name(
+                "jira_get_issue",
+                &json!({"issue_key": "A-1", PROGRESS_TOKEN_ARG: 7})
+            )
+        );
+        assert_ne!(
+            name,
+            fixture_name("jira_get_issue", &json!({"issue_key": "A-2"}))
+        );
+    }
+
+    #[test]
+    fn test_record_then_replay() {
+        let recorder = fixtures(Mode::Record);
+        let args = json!({"jql": "project = A", "limit": 5});
+        recorder.record(
+            "jira_search",
+            &args,
+            &Ok(json!({"issues": [{"key": "A-1"}]})),
+        );
+        recorder.record(
+            "jira_get_issue",
+            &json!({"issue_key": "A-9"}),
+            &Err(anyhow::anyhow!("Failed to get issue: 404 Not Found")),
+        );
+
+        let player = fixtures(Mode::Replay);
+        assert!(player.replaying());
+        assert_eq!(
+            player.replay("jira_search", &args).unwrap()["issues"][0]["key"],
+            "A-1"
+        );
+        assert_eq!(
+            player
+                .replay("jira_get_issue", &json!({"issue_key": "A-9"}))
+                .unwrap_err()
+                .to_string(),
+            "Failed to get issue: 404 Not Found"
+        );
+        let missing = player
+            .replay("jira_search", &json!({"jql": "project = B"}))
+            .unwrap_err();
+        assert!(
+            missing
+                .to_string()
+                .contains("No recorded result for jira_search")
+        );
+
+        std::fs::remove_dir_all(&recorder.dir).unwrap();
+    }
+}
@@ -2,96 +2,368 @@ use anyhow::Result;
 use serde_json::{Value, json};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::config::Config;
+use crate::client::rest;
+use crate::config::{Config, MAX_CALL_TIMEOUT_MS, Mode};
 use crate::tools::ToolHandler;
+use crate::tools::args::InvalidArgs;
+use crate::tools::confluence::{action_items, meeting_notes};
 use crate::tools::response_optimizer::ResponseOptimizer;
-use crate::tools::{confluence, jira};
+use crate::tools::schema::ToolSchema;
+use crate::tools::{
+    bitbucket, compass, confluence, health, incident, jira, jsm, session_stats, teams, undo,
+};
+use crate::utils::circuit_breaker::{BREAKERS, is_upstream_failure};
+use crate::utils::issue_keys::normalize_issue_key;
+use crate::utils::logging::redact_secrets;
+use crate::utils::time;
 
+use super::approval::{self, APPROVALS, CONFIRM_TOKEN_ARG};
+use super::audit::AuditLog;
+use super::fixtures::Fixtures;
+use super::i18n;
+use super::notifications::Notifier;
+use super::output_schemas;
+use super::stats::SessionStats;
 use super::types::{CallToolResult, Property, Tool as McpTool, ToolContent, ToolInputSchema};
 
+/// Tools that modify Atlassian data; hidden and rejected in `READ_ONLY_MODE`
+pub fn is_write_operation(name: &str) -> bool {
+    matches!(
+        name,
+        "jira_create_issue"
+            | "jira_update_issue"
+            | "jira_create_issue_from_template"
+            | "jira_add_comment"
+            | "jira_update_comment"
+            | "jira_transition_issue"
+            | "jira_bulk_transition"
+            | "jira_bulk_update"
+            | "jira_import_csv"
+            | "jira_set_issue_property"
+            | "jira_delete_issue_property"
+            | "jira_notify"
+            | "jira_add_user_to_role"
+            | "confluence_add_comment"
+            | "confluence_set_page_restrictions"
+            | "confluence_create_page"
+            | "confluence_create_folder"
+            | "confluence_update_page"
+            | "confluence_append_to_page"
+            | "confluence_publish_meeting_notes"
+            | "confluence_replace_section"
+            | "confluence_update_task_status"
+            | "bitbucket_create_pr_comment"
+            | "create_incident"
+            | "trello_move_card"
+            | "atlassian_undo_last"
+    )
+}
+
+/// Whether this call writes: a write tool, or
+/// `confluence_extract_action_items` asked to create issues
+pub fn is_write_call(name: &str, args: &Value) -> bool {
+    is_write_operation(name)
+        || (name == "confluence_extract_action_items"
+            && args["create_issues"].as_bool().unwrap_or(false))
+}
+
+/// Write tools that send a single write request, so a call failing with
+/// 401 changed nothing
+fn is_single_request_write(name: &str) -> bool {
+    matches!(
+        name,
+        "jira_create_issue"
+            | "jira_update_issue"
+            | "jira_add_comment"
+            | "jira_update_comment"
+            | "jira_set_issue_property"
+            | "jira_add_user_to_role"
+            | "confluence_add_comment"
+            | "confluence_create_folder"
+            | "confluence_update_page"
+            | "confluence_update_task_status"
+            | "bitbucket_create_pr_comment"
+            | "trello_move_card"
+    )
+}
+
+/// Whether a call that failed with 401 may run again as a whole: reads and
+/// single-request writes. Composite writes may have made part of their
+/// changes before the 401, and a `confirm_token` was consumed by the first
+/// run.
+pub fn is_retryable_after_unauthorized(name: &str, args: &Value) -> bool {
+    let confirmed = args.get(CONFIRM_TOKEN_ARG).is_some_and(|t| !t.is_null());
+    !confirmed && (!is_write_call(name, args) || is_single_request_write(name))
+}
+
+/// Built-in call deadlines for tools that routinely outlast `REQUEST_TIMEOUT_MS`
+fn default_tool_timeout_ms(name: &str) -> Option<u64> {
+    match name {
+        "jira_search" | "confluence_search" => Some(60_000),
+        "jira_export_search" => Some(120_000),
+        "confluence_export_page" | "jira_read_attachment_text" => Some(120_000),
+        "jira_bulk_transition" | "jira_bulk_update" | "jira_import_csv" => Some(300_000),
+        _ => None,
+    }
+}
+
+/// Deadline for one call: the `timeout_ms` argument (removed from `args`),
+/// then `TOOL_TIMEOUTS_MS`, then the built-in default; `None` leaves each
+/// request on `REQUEST_TIMEOUT_MS`
+fn call_timeout(name: &str, args: &mut Value, config: &Config) -> Result<Option<Duration>> {
+    let requested = match args.as_object_mut().and_then(|a| a.remove("timeout_ms")) {
+        None | Some(Value::Null) => None,
+        Some(v) => Some(
+            v.as_u64()
+                .filter(|ms| (100..=MAX_CALL_TIMEOUT_MS).contains(ms))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Invalid timeout_ms: expected 100-{} milliseconds",
+                        MAX_CALL_TIMEOUT_MS
+                    )
+                })?,
+        ),
+    };
+    Ok(requested
+        .or_else(|| config.tool_timeouts_ms.get(name).copied())
+        .or_else(|| default_tool_timeout_ms(name).map(|ms| ms.max(config.request_timeout_ms)))
+        .map(Duration::from_millis))
+}
+
+/// The host a tool talks to, for its circuit breaker; `None` for tools that
+/// answer locally
+fn upstream_host(tool: &str, config: &Config) -> Option<String> {
+    if tool == "atlassian_session_stats" {
+        None
+    } else if tool.starts_with("bitbucket_") {
+        Some("api.bitbucket.org".to_string())
+    } else if tool.starts_with("trello_") {
+        Some("api.trello.com".to_string())
+    } else {
+        Some(config.atlassian_domain.clone())
+    }
+}
+
+/// Arguments holding one Jira issue key, normalized before the handler runs
+const ISSUE_KEY_ARGS: &[&str] = &["issue_key", "epic_key", "exclude_key"];
+
+/// Rewrites issue key arguments to their canonical form (e.g. a pasted
+/// browse link to `PROJ-1`), so handlers never send a URL to the API
+fn normalize_issue_keys(args: &mut Value) -> Result<(), InvalidArgs> {
+    let mut errors = InvalidArgs::default();
+    let Some(args) = args.as_object_mut() else {
+        return Ok(());
+    };
+    for field in ISSUE_KEY_ARGS {
+        let Some(Value::String(input)) = args.get_mut(*field) else {
+            continue;
+        };
+        match normalize_issue_key(input) {
+            Ok(key) => *input = key,
+            Err(reason) => errors.invalid.push((field.to_string(), reason)),
+        }
+    }
+    if errors.invalid.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 pub struct RequestHandler {
     tools: HashMap<String, Arc<dyn ToolHandler>>,
     config: Arc<Config>,
     optimizer: Arc<ResponseOptimizer>,
+    audit: Option<AuditLog>,
+    fixtures: Option<Fixtures>,
+    stats: Arc<SessionStats>,
+    watcher: Arc<jira::watch::QueryWatcher>,
 }
 
 impl RequestHandler {
-    pub async fn new(config: Arc<Config>) -> Result<Self> {
-        let mut tools: HashMap<String, Arc<dyn ToolHandler>> = HashMap::new();
+    pub async fn new(config: Arc<Config>, notifier: Notifier) -> Result<Self> {
+        // Polling watches share one task manager and emit via the server notifier
+        let watcher = Arc::new(jira::watch::QueryWatcher::new(notifier.clone()));
+        Self::build(config, notifier, Arc::new(SessionStats::new()), watcher).await
+    }
 
-        // Register Jira tools
-        tools.insert(
-            "jira_get_issue".to_string(),
+    /// Builds the handler for a reloaded config; it keeps counting into
+    /// `previous`'s stats and keeps its JQL watches running
+    pub async fn replacing(
+        config: Arc<Config>,
+        notifier: Notifier,
+        previous: &RequestHandler,
+    ) -> Result<Self> {
+        let watcher = previous.watcher.clone();
+        Self::build(config, notifier, previous.stats(), watcher).await
+    }
+
+    async fn build(
+        config: Arc<Config>,
+        notifier: Notifier,
+        stats: Arc<SessionStats>,
+        watcher: Arc<jira::watch::QueryWatcher>,
+    ) -> Result<Self> {
+        let mut handlers: Vec<Arc<dyn ToolHandler>> = vec![
+            Arc::new(health::HealthCheckHandler),
+            Arc::new(session_stats::SessionStatsHandler::new(stats.clone())),
+            Arc::new(undo::UndoLastHandler),
+            // Jira
             Arc::new(jira::GetIssueHandler),
-        );
-        tools.insert("jira_search".to_string(), Arc::new(jira::SearchHandler));
-        tools.insert(
-            "jira_create_issue".to_string(),
+            Arc::new(jira::SearchHandler),
+            Arc::new(jira::ExportSearchHandler),
             Arc::new(jira::CreateIssueHandler),
-        );
-        tools.insert(
-            "jira_update_issue".to_string(),
+            Arc::new(jira::templates::CreateIssueFromTemplateHandler),
             Arc::new(jira::UpdateIssueHandler),
-        );
-        tools.insert(
-            "jira_add_comment".to_string(),
             Arc::new(jira::AddCommentHandler),
-        );
-        tools.insert(
-            "jira_update_comment".to_string(),
+            Arc::new(jira::comments::GetCommentHandler),
+            Arc::new(jira::comments::GetCommentsHandler),
+            Arc::new(jira::delta::IssueDeltaHandler),
+            Arc::new(jira::standup::StandupDigestHandler),
+            Arc::new(jira::workload::WorkloadByAssigneeHandler),
+            Arc::new(teams::GetTeamsHandler),
+            Arc::new(teams::GetTeamMembersHandler),
             Arc::new(jira::UpdateCommentHandler),
-        );
-        tools.insert(
-            "jira_transition_issue".to_string(),
             Arc::new(jira::TransitionIssueHandler),
-        );
-        tools.insert(
-            "jira_get_transitions".to_string(),
             Arc::new(jira::GetTransitionsHandler),
-        );
-
-        // Register Confluence tools
-        tools.insert(
-            "confluence_search".to_string(),
+            Arc::new(jira::bulk::BulkTransitionHandler),
+            Arc::new(jira::properties::GetIssuePropertiesHandler),
+            Arc::new(jira::properties::SetIssuePropertyHandler),
+            Arc::new(jira::properties::DeleteIssuePropertyHandler),
+            Arc::new(jira::attachments::ReadAttachmentTextHandler),
+            Arc::new(jira::notify::NotifyHandler),
+            Arc::new(jira::report::IssueReportHandler),
+            Arc::new(jira::similar::FindSimilarIssuesHandler),
+            Arc::new(jira::hierarchy::GetSubtasksHandler),
+            Arc::new(jira::hierarchy::GetIssueHierarchyHandler),
+            Arc::new(jira::board::GetBoardSnapshotHandler),
+            Arc::new(jira::roles::GetProjectRolesHandler),
+            Arc::new(jira::roles::AddUserToRoleHandler),
+            Arc::new(jira::automation::ListAutomationRulesHandler),
+            Arc::new(jira::automation::GetAutomationRuleHandler),
+            Arc::new(jira::schemes::GetProjectWorkflowHandler),
+            Arc::new(jira::schemes::GetPermissionSchemeHandler),
+            Arc::new(jira::schemes::GetStatusesHandler),
+            Arc::new(jira::activity::RecentActivityHandler),
+            Arc::new(jira::favourites::GetFavouritesHandler),
+            Arc::new(jira::time_tracking::TimeTrackingSummaryHandler),
+            Arc::new(jira::import::ImportCsvHandler),
+            Arc::new(jira::bulk::BulkUpdateHandler::new(notifier)),
+            Arc::new(jira::watch::WatchQueryHandler::new(watcher.clone())),
+            Arc::new(jira::watch::UnwatchQueryHandler::new(watcher.clone())),
+            // Confluence
             Arc::new(confluence::SearchHandler),
-        );
-        tools.insert(
-            "confluence_get_page".to_string(),
             Arc::new(confluence::GetPageHandler),
-        );
-        tools.insert(
-            "confluence_get_page_children".to_string(),
+            Arc::new(confluence::GetPageByTitleHandler),
+            Arc::new(confluence::chunks::GetPageChunkHandler),
+            Arc::new(confluence::edit::GetPageSectionHandler),
             Arc::new(confluence::GetPageChildrenHandler),
-        );
-        tools.insert(
-            "confluence_get_comments".to_string(),
             Arc::new(confluence::GetCommentsHandler),
-        );
-        tools.insert(
-            "confluence_create_page".to_string(),
+            Arc::new(confluence::comments::AddCommentHandler),
+            Arc::new(confluence::restrictions::GetPageRestrictionsHandler),
+            Arc::new(confluence::restrictions::SetPageRestrictionsHandler),
+            Arc::new(confluence::activity::RecentlyViewedHandler),
+            Arc::new(confluence::favourites::GetFavouritesHandler),
+            Arc::new(confluence::analytics::GetPageViewsHandler),
+            Arc::new(confluence::folders::ListFoldersHandler),
+            Arc::new(confluence::folders::GetFolderHandler),
+            Arc::new(confluence::folders::CreateFolderHandler),
+            Arc::new(confluence::whiteboards::GetWhiteboardHandler),
             Arc::new(confluence::CreatePageHandler),
-        );
-        tools.insert(
-            "confluence_update_page".to_string(),
             Arc::new(confluence::UpdatePageHandler),
-        );
+            Arc::new(confluence::action_items::ExtractActionItemsHandler),
+            Arc::new(confluence::meeting_notes::PublishMeetingNotesHandler),
+            Arc::new(confluence::edit::AppendToPageHandler),
+            Arc::new(confluence::edit::ReplaceSectionHandler),
+            Arc::new(confluence::GetTasksHandler),
+            Arc::new(confluence::UpdateTaskStatusHandler),
+            Arc::new(confluence::export::ExportPageHandler),
+        ];
+
+        // Register Bitbucket tools (opt-in via BITBUCKET_WORKSPACE)
+        if config.bitbucket_workspace.is_some() {
+            handlers.push(Arc::new(bitbucket::ListPullRequestsHandler));
+            handlers.push(Arc::new(bitbucket::GetPullRequestHandler));
+            handlers.push(Arc::new(bitbucket::CreatePullRequestCommentHandler));
+            handlers.push(Arc::new(bitbucket::GetPipelinesHandler));
+        }
+
+        // Register Jira Service Management tools (opt-in via JSM_ENABLED)
+        if config.jsm_enabled {
+            handlers.push(Arc::new(jsm::SlaBreachesHandler));
+        }
+
+        // Register Compass tools (opt-in via COMPASS_ENABLED)
+        if config.compass_enabled {
+            handlers.push(Arc::new(compass::ListComponentsHandler));
+            handlers.push(Arc::new(compass::GetComponentHandler));
+        }
+
+        // Register the incident tool (opt-in via INCIDENT_PROJECT)
+        if config.incident_project.is_some() {
+            handlers.push(Arc::new(incident::CreateIncidentHandler));
+        }
+
+        // Register Trello tools (`trello` feature + TRELLO_API_KEY/TRELLO_TOKEN)
+        #[cfg(feature = "trello")]
+        if config.trello_enabled() {
+            use crate::tools::trello;
+            handlers.push(Arc::new(trello::ListBoardsHandler));
+            handlers.push(Arc::new(trello::GetListsHandler));
+            handlers.push(Arc::new(trello::GetCardsHandler));
+            handlers.push(Arc::new(trello::MoveCardHandler));
+        }
+
+        // Each handler declares its own name and schema
+        let mut tools: HashMap<String, Arc<dyn ToolHandler>> = handlers
+            .into_iter()
+            .map(|handler| (handler.name().to_string(), handler))
+            .collect();
+
+        if config.read_only {
+            tools.retain(|name, _| !is_write_operation(name));
+        }
+        if config.undo_journal_size == 0 {
+            tools.remove("atlassian_undo_last");
+        }
+        tools.retain(|name, _| config.tool_enabled(name));
+
+        // Site-specific JQL examples for jira_search, looked up once per site
+        if config.jira_schema_examples
+            && config.mode != Mode::Replay
+            && tools.contains_key("jira_search")
+        {
+            jira::examples::discover(&config).await;
+        }
 
         // Create response optimizer for field removal
         let optimizer = Arc::new(ResponseOptimizer::from_config(&config));
 
+        let audit = AuditLog::from_config(&config);
+        let fixtures = Fixtures::from_config(&config);
+
         Ok(Self {
             tools,
             config,
             optimizer,
+            audit,
+            fixtures,
+            stats,
+            watcher,
         })
     }
 
+    pub fn stats(&self) -> Arc<SessionStats> {
+        self.stats.clone()
+    }
+
     pub async fn list_tools(&self) -> Vec<McpTool> {
         let mut tool_list = Vec::new();
 
-        for name in self.tools.keys() {
-            tool_list.push(self.tool_to_mcp_tool(name, &self.config));
+        for (name, tool) in &self.tools {
+            tool_list.push(Self::tool_to_mcp_tool(name, tool.as_ref(), &self.config));
         }
 
         tool_list
@@ -103,13 +375,176 @@ impl RequestHandler {
         arguments: Value,
         config: &Config,
     ) -> Result<CallToolResult> {
-        let tool = self
-            .tools
-            .get(name)
+        // Clients see the names with TOOL_NAME_PREFIX; handlers are keyed without it
+        let name = config
+            .registered_tool_name(name)
             .ok_or_else(|| anyhow::anyhow!("Tool not found: {}", name))?;
+        let mut api_time = Duration::ZERO;
+        let result = self
+            .run_tool(name, arguments, config, &mut api_time)
+            .await
+            .and_then(|result| self.finish(name, result, config));
+        if self.tools.contains_key(name) {
+            let bytes = result.as_ref().map_or(0, |result| {
+                result
+                    .content
+                    .iter()
+                    .map(|content| match content {
+                        ToolContent::Text { text } => text.len(),
+                        ToolContent::Image { data, .. } => data.len(),
+                    })
+                    .sum()
+            });
+            self.stats.record(name, api_time, result.is_ok(), bytes);
+        }
+        result
+    }
 
-        let mut result = tool.execute(arguments, config).await?;
+    /// `call_tool` without the session counters and response shaping;
+    /// `api_time` is the time spent in the handler
+    async fn run_tool(
+        &self,
+        name: &str,
+        mut arguments: Value,
+        config: &Config,
+        api_time: &mut Duration,
+    ) -> Result<Value> {
+        let tool = self.tools.get(name).ok_or_else(|| {
+            if config.read_only && is_write_operation(name) {
+                anyhow::anyhow!(
+                    "Tool {} is disabled in read-only mode (READ_ONLY_MODE)",
+                    name
+                )
+            } else if !config.tool_enabled(name) {
+                anyhow::anyhow!("Tool {} is not enabled (ENABLED_TOOLS)", name)
+            } else {
+                anyhow::anyhow!("Tool not found: {}", name)
+            }
+        })?;
+        if config.read_only && is_write_call(name, &arguments) {
+            anyhow::bail!(
+                "{} with create_issues is disabled in read-only mode (READ_ONLY_MODE)",
+                name
+            );
+        }
 
+        // Every client the handler creates inherits the call deadline
+        let timeout = call_timeout(name, &mut arguments, config)?;
+        let confirm_token = arguments
+            .as_object_mut()
+            .and_then(|a| a.remove(CONFIRM_TOKEN_ARG))
+            .filter(|t| !t.is_null());
+        tool.schema(config).validate(&arguments)?;
+        normalize_issue_keys(&mut arguments)?;
+        let call_config = timeout.map(|t| config.with_deadline(t));
+        let exec_config = call_config.as_ref().unwrap_or(config);
+        // Replayed calls reach neither Atlassian nor the audit log
+        if let Some(fixtures) = self.fixtures.as_ref().filter(|f| f.replaying()) {
+            return fixtures.replay(name, &arguments);
+        }
+        // Held calls change nothing, so they are neither recorded nor audited
+        if config.confirm_destructive_writes
+            && let Some(change) =
+                approval::destructive_change(name, &arguments, exec_config).await?
+        {
+            match confirm_token.as_ref().map(|t| t.as_str()) {
+                Some(Some(token)) => APPROVALS.redeem(token, name, &arguments, Instant::now())?,
+                Some(None) => anyhow::bail!("Invalid confirm_token: expected a string"),
+                None => {
+                    let token = APPROVALS.issue(name, &arguments, Instant::now());
+                    let exposed = config.exposed_tool_name(name);
+                    return Ok(approval::confirmation_request(&exposed, &change, &token));
+                }
+            }
+        }
+        let recording = self.fixtures.as_ref().map(|f| (f, arguments.clone()));
+        let writes = is_write_call(name, &arguments);
+        let audit = self
+            .audit
+            .as_ref()
+            .filter(|_| writes)
+            .map(|audit| (audit, arguments.clone(), Instant::now()));
+
+        let breaker = upstream_host(name, config).filter(|_| config.circuit_breaker_threshold > 0);
+        if let Some(host) = &breaker {
+            BREAKERS.check(host, Instant::now())?;
+        }
+
+        let undo_state = undo::capture(name, &arguments, exec_config).await;
+        let handler_started = Instant::now();
+        let execution = async {
+            let creates_issues = writes && !is_write_operation(name);
+            let mut result = tool.execute(arguments, exec_config).await?;
+            // Each item's issue is one jira_create_issue call, audited and
+            // policy-checked like any other
+            if creates_issues {
+                action_items::create_issues(&mut result, exec_config, |issue| {
+                    Box::pin(async move {
+                        let mut api_time = Duration::ZERO;
+                        self.run_tool("jira_create_issue", issue, exec_config, &mut api_time)
+                            .await
+                    })
+                })
+                .await;
+            }
+            // Likewise each issue's link to published notes is one jira_add_comment call
+            if name == "confluence_publish_meeting_notes" {
+                meeting_notes::comment_issues(&mut result, |comment| {
+                    Box::pin(async move {
+                        let mut api_time = Duration::ZERO;
+                        self.run_tool("jira_add_comment", comment, exec_config, &mut api_time)
+                            .await
+                    })
+                })
+                .await;
+            }
+            Ok(result)
+        };
+        let outcome = match timeout {
+            Some(t) => tokio::time::timeout(t, execution)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(anyhow::anyhow!(
+                        "Tool {} timed out after {} ms",
+                        name,
+                        t.as_millis()
+                    ))
+                }),
+            None => execution.await,
+        };
+        *api_time = handler_started.elapsed();
+        if let Some((fixtures, recorded_args)) = recording {
+            fixtures.record(name, &recorded_args, &outcome);
+        }
+        if let Some(host) = &breaker {
+            let failure = outcome
+                .as_ref()
+                .err()
+                .filter(|e| is_upstream_failure(e))
+                .map(|e| redact_secrets(&e.to_string()));
+            BREAKERS.record(
+                host,
+                failure,
+                config.circuit_breaker_threshold,
+                Duration::from_secs(config.circuit_breaker_cooldown_secs),
+                Instant::now(),
+            );
+        }
+        // Cached GET responses may show what this call changed
+        if writes {
+            rest::forget_responses(config);
+        }
+        if let Some((audit, audited_args, started)) = audit {
+            audit.record(name, &audited_args, &outcome, started.elapsed());
+        }
+        if let (Some(change), Ok(result)) = (undo_state, &outcome) {
+            undo::record(name, change, result, config);
+        }
+        outcome
+    }
+
+    /// Optimizes and localizes a handler result and wraps it as tool content
+    fn finish(&self, name: &str, mut result: Value, config: &Config) -> Result<CallToolResult> {
         // Apply response optimization for GET operations only
         // CREATE/UPDATE operations already return minimal responses (Phase 3)
         let is_get_operation = matches!(
@@ -117,16 +552,66 @@ impl RequestHandler {
             "jira_get_issue"
                 | "jira_search"
                 | "jira_get_transitions"
+                | "jira_get_comment"
+                | "jira_get_comments"
+                | "jira_issue_delta"
+                | "jira_standup_digest"
+                | "jira_workload_by_assignee"
+                | "atlassian_get_teams"
+                | "atlassian_get_team_members"
+                | "jsm_sla_breaches"
+                | "compass_list_components"
+                | "compass_get_component"
+                | "jira_get_issue_properties"
+                | "jira_get_project_roles"
+                | "jira_list_automation_rules"
+                | "jira_get_automation_rule"
+                | "jira_get_project_workflow"
+                | "jira_get_permission_scheme"
+                | "jira_get_statuses"
+                | "jira_recent_activity"
+                | "jira_get_favourites"
+                | "jira_time_tracking_summary"
+                | "jira_find_similar_issues"
+                | "jira_issue_report"
+                | "jira_get_subtasks"
+                | "jira_get_issue_hierarchy"
+                | "jira_get_board_snapshot"
                 | "confluence_search"
                 | "confluence_get_page"
+                | "confluence_get_page_by_title"
                 | "confluence_get_page_children"
                 | "confluence_get_comments"
+                | "confluence_get_page_restrictions"
+                | "confluence_recently_viewed"
+                | "confluence_get_favourites"
+                | "confluence_get_page_views"
+                | "confluence_list_folders"
+                | "confluence_get_folder"
+                | "confluence_get_whiteboard"
+                | "confluence_get_tasks"
+                | "bitbucket_list_prs"
+                | "bitbucket_get_pr"
+                | "bitbucket_get_pipelines"
+                | "trello_list_boards"
+                | "trello_get_lists"
+                | "trello_get_cards"
         );
 
+        let mut optimization = None;
         if is_get_operation {
             match self.optimizer.optimize(&mut result) {
-                Ok(()) => {
-                    tracing::debug!(tool = name, "Response optimization applied successfully");
+                Ok(stats) => {
+                    tracing::debug!(
+                        tool = name,
+                        fields_removed = stats.fields_removed,
+                        empty_strings_removed = stats.empty_strings_removed,
+                        bytes_before = stats.bytes_before,
+                        bytes_after = stats.bytes_after,
+                        estimated_tokens_saved = stats.estimated_tokens_saved(),
+                        "Response optimization applied successfully"
+                    );
+                    optimization = Some(stats);
                 }
                 Err(e) => {
                     tracing::warn!(
@@ -138,6 +623,15 @@ impl RequestHandler {
             }
         }
 
+        // Timestamps in the user's zone, when one is configured
+        if config.timezone.is_some() {
+            let zone = time::user_timezone(config)?;
+            time::localize_timestamps(&mut result, &zone);
+        }
+
+        // Object results also travel as JSON; the server drops it for older clients
+        let structured_content = result.is_object().then(|| result.clone());
+
         // Convert result to tool content
         let content = if let Some(text) = result.as_str() {
             vec![ToolContent::Text {
@@ -149,314 +643,58 @@ impl RequestHandler {
             }]
         };
 
-        Ok(CallToolResult { content })
-    }
+        // Attach optimization metrics only when explicitly requested
+        let meta = if config.response_metrics {
+            optimization.map(|stats| json!({ "optimization": stats.to_meta() }))
+        } else {
+            None
+        };
 
-    fn create_string_prop(description: &str, _required: bool) -> Property {
-        Property {
-            property_type: json!("string"),
-            description: Some(description.to_string()),
-            default: None,
-            enum_values: None,
-        }
+        Ok(CallToolResult {
+            content,
+            structured_content,
+            meta,
+        })
     }
 
-    fn create_number_prop(description: &str, default: i32) -> Property {
-        Property {
-            property_type: json!("number"),
-            description: Some(description.to_string()),
-            default: Some(Value::Number(default.into())),
-            enum_values: None,
+    fn tool_to_mcp_tool(name: &str, tool: &dyn ToolHandler, config: &Config) -> McpTool {
+        let mut schema = tool.schema(config);
+        if let Some(localized) = i18n::tool_description(config.locale, name) {
+            schema.description = localized.to_string();
         }
-    }
 
-    fn create_union_prop(description: &str, types: Vec<&str>) -> Property {
-        Property {
-            property_type: json!(types),
-            description: Some(description.to_string()),
-            default: None,
-            enum_values: None,
+        // Server-wide arguments every tool accepts
+        schema.properties.insert(
+            "timeout_ms".to_string(),
+            Property::new("number").with_description(
+                "Deadline for the whole call in milliseconds (overrides the tool default)"
+                    .to_string(),
+            ),
+        );
+        if config.confirm_destructive_writes && approval::may_need_confirmation(name) {
+            schema.properties.insert(
+                CONFIRM_TOKEN_ARG.to_string(),
+                Property::new("string").with_description(
+                    "Token from a previous call held for confirmation; send it only after the user agreed"
+                        .to_string(),
+                ),
+            );
         }
-    }
-
-    fn tool_to_mcp_tool(&self, name: &str, config: &Config) -> McpTool {
-        let (description, properties, required) = match name {
-            // Jira tools
-            "jira_get_issue" => {
-                let mut props = HashMap::new();
-                props.insert(
-                    "issue_key".to_string(),
-                    Self::create_string_prop(
-                        "Issue key (e.g., 'PROJECT-123'). Case-sensitive.",
-                        true,
-                    ),
-                );
-                (
-                    "Get Jira issue by key",
-                    props,
-                    vec!["issue_key".to_string()],
-                )
-            }
-            "jira_search" => {
-                // Resolve the actual fields that will be used
-                let resolved_fields = jira::field_filtering::resolve_search_fields(None, config);
-                let fields_count = resolved_fields.len();
-                let fields_list = resolved_fields.join(", ");
-
-                let mut props = HashMap::new();
-                props.insert("jql".to_string(), Self::create_string_prop("JQL query. Must include search condition before ORDER BY (e.g., 'project = KEY ORDER BY created DESC'). ORDER BY only works with orderable fields (dates, versions).", true));
-                props.insert(
-                    "limit".to_string(),
-                    Self::create_number_prop("Maximum results (default: 20)", 20),
-                );
-                props.insert("fields".to_string(), Property {
-                    property_type: json!("array"),
-                    description: Some(format!(
-                        "Optional: Array of field names to return. If not specified, returns {} default fields: {}\n\n\
-                        To minimize tokens, specify only the fields you need (e.g., [\"key\",\"summary\",\"status\",\"assignee\"]).",
-                        fields_count, fields_list
-                    )),
-                    default: None,
-                    enum_values: None,
-                });
-                (
-                    "Search Jira issues using JQL",
-                    props,
-                    vec!["jql".to_string()],
-                )
-            }
-            "jira_create_issue" => {
-                let mut props = HashMap::new();
-                props.insert(
-                    "project_key".to_string(),
-                    Self::create_string_prop("Project key", true),
-                );
-                props.insert(
-                    "summary".to_string(),
-                    Self::create_string_prop("Issue summary", true),
-                );
-                props.insert(
-                    "issue_type".to_string(),
-                    Self::create_string_prop(
-                        "Issue type name (e.g., 'Task', 'Bug', 'Story').",
-                        true,
-                    ),
-                );
-                props.insert(
-                    "description".to_string(),
-                    Self::create_union_prop(
-                        "Issue description - accepts plain text (string, auto-converted to ADF) or ADF object",
-                        vec!["string", "object"],
-                    ),
-                );
-                (
-                    "Create Jira issue",
-                    props,
-                    vec![
-                        "project_key".to_string(),
-                        "summary".to_string(),
-                        "issue_type".to_string(),
-                    ],
-                )
-            }
-            "jira_update_issue" => {
-                let mut props = HashMap::new();
-                props.insert(
-                    "issue_key".to_string(),
-                    Self::create_string_prop("Issue key", true),
-                );
-                props.insert("fields".to_string(), Property {
-                    property_type: json!("object"),
-                    description: Some("Fields to update as JSON object (e.g., {\"summary\": \"New title\"}). Custom fields use 'customfield_*' format. The 'description' field accepts plain text (auto-converted to ADF) or ADF object.".to_string()),
-                    default: None,
-                    enum_values: None,
-                });
-                (
-                    "Update Jira issue",
-                    props,
-                    vec!["issue_key".to_string(), "fields".to_string()],
-                )
-            }
-            "jira_add_comment" => {
-                let mut props = HashMap::new();
-                props.insert(
-                    "issue_key".to_string(),
-                    Self::create_string_prop("Issue key", true),
-                );
-                props.insert(
-                    "comment".to_string(),
-                    Self::create_union_prop(
-                        "Comment text - accepts plain text (string, auto-converted to ADF) or ADF object",
-                        vec!["string", "object"],
-                    ),
-                );
-                (
-                    "Add comment to Jira issue",
-                    props,
-                    vec!["issue_key".to_string(), "comment".to_string()],
-                )
-            }
-            "jira_update_comment" => {
-                let mut props = HashMap::new();
-                props.insert(
-                    "issue_key".to_string(),
-                    Self::create_string_prop("Issue key (e.g., 'PROJ-123')", true),
-                );
-                props.insert(
-                    "comment_id".to_string(),
-                    Self::create_string_prop(
-                        "Comment ID to update (obtained from comment object's 'id' field)",
-                        true,
-                    ),
-                );
-                props.insert(
-                    "body".to_string(),
-                    Self::create_union_prop(
-                        "Comment body - accepts plain text (string, auto-converted to ADF) or ADF object",
-                        vec!["string", "object"],
-                    ),
-                );
-                (
-                    "Update an existing comment on a Jira issue with rich text formatting (ADF)",
-                    props,
-                    vec![
-                        "issue_key".to_string(),
-                        "comment_id".to_string(),
-                        "body".to_string(),
-                    ],
-                )
-            }
-            "jira_transition_issue" => {
-                let mut props = HashMap::new();
-                props.insert(
-                    "issue_key".to_string(),
-                    Self::create_string_prop("Issue key", true),
-                );
-                props.insert("transition_id".to_string(), Self::create_string_prop("Transition ID. Get available transition IDs using jira_get_transitions for the issue's current status.", true));
-                (
-                    "Transition Jira issue status",
-                    props,
-                    vec!["issue_key".to_string(), "transition_id".to_string()],
-                )
-            }
-            "jira_get_transitions" => {
-                let mut props = HashMap::new();
-                props.insert(
-                    "issue_key".to_string(),
-                    Self::create_string_prop("Issue key", true),
-                );
-                (
-                    "Get Jira issue transitions",
-                    props,
-                    vec!["issue_key".to_string()],
-                )
-            }
-            // Confluence tools
-            "confluence_search" => {
-                let mut props = HashMap::new();
-                props.insert("query".to_string(), Self::create_string_prop("CQL query. Format: field operator value (e.g., 'type=page AND space=\"SPACE\"'). Use text ~ \"keyword\" for text search.", true));
-                props.insert(
-                    "limit".to_string(),
-                    Self::create_number_prop("Max results", 10),
-                );
-                (
-                    "Search Confluence using CQL",
-                    props,
-                    vec!["query".to_string()],
-                )
-            }
-            "confluence_get_page" => {
-                let mut props = HashMap::new();
-                props.insert(
-                    "page_id".to_string(),
-                    Self::create_string_prop("Page ID", true),
-                );
-                (
-                    "Get Confluence page by ID",
-                    props,
-                    vec!["page_id".to_string()],
-                )
-            }
-            "confluence_get_page_children" => {
-                let mut props = HashMap::new();
-                props.insert(
-                    "page_id".to_string(),
-                    Self::create_string_prop("Page ID", true),
-                );
-                ("Get page child pages", props, vec!["page_id".to_string()])
-            }
-            "confluence_get_comments" => {
-                let mut props = HashMap::new();
-                props.insert(
-                    "page_id".to_string(),
-                    Self::create_string_prop("Page ID", true),
-                );
-                ("Get page comments", props, vec!["page_id".to_string()])
-            }
-            "confluence_create_page" => {
-                let mut props = HashMap::new();
-                props.insert(
-                    "space_key".to_string(),
-                    Self::create_string_prop("Space key", true),
-                );
-                props.insert(
-                    "title".to_string(),
-                    Self::create_string_prop("Page title", true),
-                );
-                props.insert(
-                    "content".to_string(),
-                    Self::create_string_prop("Page content in HTML storage format.", true),
-                );
-                props.insert(
-                    "parent_id".to_string(),
-                    Self::create_string_prop("Parent page ID", false),
-                );
-                (
-                    "Create Confluence page",
-                    props,
-                    vec![
-                        "space_key".to_string(),
-                        "title".to_string(),
-                        "content".to_string(),
-                    ],
-                )
-            }
-            "confluence_update_page" => {
-                let mut props = HashMap::new();
-                props.insert(
-                    "page_id".to_string(),
-                    Self::create_string_prop("Page ID", true),
-                );
-                props.insert(
-                    "title".to_string(),
-                    Self::create_string_prop("Page title", true),
-                );
-                props.insert(
-                    "content".to_string(),
-                    Self::create_string_prop("Page content in HTML storage format", true),
-                );
-                props.insert("version_number".to_string(), Self::create_number_prop("Version number (optional). Current version is automatically retrieved and incremented.", 1));
-                (
-                    "Update Confluence page",
-                    props,
-                    vec![
-                        "page_id".to_string(),
-                        "title".to_string(),
-                        "content".to_string(),
-                    ],
-                )
-            }
-            _ => ("Unknown tool", HashMap::new(), vec![]),
-        };
+        let ToolSchema {
+            description,
+            properties,
+            required,
+        } = schema.with_verbosity(config.tool_schema_verbosity);
 
         McpTool {
-            name: name.to_string(),
-            description: description.to_string(),
+            name: config.exposed_tool_name(name),
+            description,
             input_schema: ToolInputSchema {
                 schema_type: "object".to_string(),
                 properties,
                 required,
             },
+            output_schema: output_schemas::output_schema(name),
         }
     }
 }
@@ -464,79 +702,422 @@ impl RequestHandler {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::ConfigBuilder;
+    use crate::tools::schema::Verbosity;
     use serde_json::json;
 
     fn create_test_config() -> Config {
-        Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "test-token".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        }
+        ConfigBuilder::new().build()
     }
 
     #[tokio::test]
     async fn test_request_handler_creation() {
         let config = Arc::new(create_test_config());
-        let handler = RequestHandler::new(config).await;
+        let handler = RequestHandler::new(config, Notifier::channel().0).await;
         assert!(handler.is_ok());
     }
 
     #[tokio::test]
-    async fn test_list_tools_returns_14_tools() {
+    async fn test_list_tools_returns_71_tools() {
         let config = Arc::new(create_test_config());
-        let handler = RequestHandler::new(config).await.unwrap();
+        let handler = RequestHandler::new(config, Notifier::channel().0)
+            .await
+            .unwrap();
         let tools = handler.list_tools().await;
-        assert_eq!(tools.len(), 14);
+        assert_eq!(tools.len(), 71);
+        assert!(tools.iter().any(|t| t.name == "atlassian_health_check"));
     }
 
     #[tokio::test]
     async fn test_list_tools_has_jira_tools() {
         let config = Arc::new(create_test_config());
-        let handler = RequestHandler::new(config).await.unwrap();
+        let handler = RequestHandler::new(config, Notifier::channel().0)
+            .await
+            .unwrap();
         let tools = handler.list_tools().await;
 
         let jira_tools: Vec<_> = tools
             .iter()
             .filter(|t| t.name.starts_with("jira_"))
             .collect();
-        assert_eq!(jira_tools.len(), 8);
+        assert_eq!(jira_tools.len(), 40);
 
         // Verify specific Jira tools exist
         assert!(tools.iter().any(|t| t.name == "jira_get_issue"));
         assert!(tools.iter().any(|t| t.name == "jira_search"));
         assert!(tools.iter().any(|t| t.name == "jira_create_issue"));
         assert!(tools.iter().any(|t| t.name == "jira_update_comment"));
+        assert!(tools.iter().any(|t| t.name == "jira_watch_query"));
+        assert!(tools.iter().any(|t| t.name == "jira_unwatch_query"));
+        assert!(tools.iter().any(|t| t.name == "jira_get_issue_hierarchy"));
+    }
+
+    #[tokio::test]
+    async fn test_bitbucket_tools_require_workspace() {
+        let mut config = create_test_config();
+        let handler = RequestHandler::new(Arc::new(config.clone()), Notifier::channel().0)
+            .await
+            .unwrap();
+        let tools = handler.list_tools().await;
+        assert!(!tools.iter().any(|t| t.name.starts_with("bitbucket_")));
+
+        config.bitbucket_workspace = Some("acme".to_string());
+        let handler = RequestHandler::new(Arc::new(config), Notifier::channel().0)
+            .await
+            .unwrap();
+        let tools = handler.list_tools().await;
+        let bitbucket_tools: Vec<_> = tools
+            .iter()
+            .filter(|t| t.name.starts_with("bitbucket_"))
+            .collect();
+        assert_eq!(bitbucket_tools.len(), 4);
+        assert!(tools.iter().any(|t| t.name == "bitbucket_get_pipelines"));
+    }
+
+    #[tokio::test]
+    async fn test_jsm_tools_require_opt_in() {
+        let mut config = create_test_config();
+        let handler = RequestHandler::new(Arc::new(config.clone()), Notifier::channel().0)
+            .await
+            .unwrap();
+        let tools = handler.list_tools().await;
+        assert!(!tools.iter().any(|t| t.name.starts_with("jsm_")));
+
+        config.jsm_enabled = true;
+        let handler = RequestHandler::new(Arc::new(config), Notifier::channel().0)
+            .await
+            .unwrap();
+        let tools = handler.list_tools().await;
+        assert!(tools.iter().any(|t| t.name == "jsm_sla_breaches"));
+    }
+
+    #[tokio::test]
+    async fn test_compass_tools_require_opt_in() {
+        let mut config = create_test_config();
+        let handler = RequestHandler::new(Arc::new(config.clone()), Notifier::channel().0)
+            .await
+            .unwrap();
+        let tools = handler.list_tools().await;
+        assert!(!tools.iter().any(|t| t.name.starts_with("compass_")));
+
+        config.compass_enabled = true;
+        let handler = RequestHandler::new(Arc::new(config), Notifier::channel().0)
+            .await
+            .unwrap();
+        let tools = handler.list_tools().await;
+        let compass_tools: Vec<_> = tools
+            .iter()
+            .filter(|t| t.name.starts_with("compass_"))
+            .collect();
+        assert_eq!(compass_tools.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_incident_tool_requires_project() {
+        let mut config = create_test_config();
+        let handler = RequestHandler::new(Arc::new(config.clone()), Notifier::channel().0)
+            .await
+            .unwrap();
+        let tools = handler.list_tools().await;
+        assert!(!tools.iter().any(|t| t.name == "create_incident"));
+
+        config.incident_project = Some("OPS".to_string());
+        let handler = RequestHandler::new(Arc::new(config), Notifier::channel().0)
+            .await
+            .unwrap();
+        let tools = handler.list_tools().await;
+        assert!(tools.iter().any(|t| t.name == "create_incident"));
+        assert!(is_write_operation("create_incident"));
+    }
+
+    #[cfg(feature = "trello")]
+    #[tokio::test]
+    async fn test_trello_tools_require_credentials() {
+        let mut config = create_test_config();
+        config.trello_api_key = Some("key".to_string());
+        config.trello_token = Some("token".to_string());
+        let handler = RequestHandler::new(Arc::new(config), Notifier::channel().0)
+            .await
+            .unwrap();
+        let tools = handler.list_tools().await;
+        let trello_tools: Vec<_> = tools
+            .iter()
+            .filter(|t| t.name.starts_with("trello_"))
+            .collect();
+        assert_eq!(trello_tools.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_replay_mode_serves_recorded_results() {
+        let mut config = create_test_config();
+        config.fixtures_dir = std::env::temp_dir()
+            .join(format!("mcp-replay-{}", std::process::id()))
+            .to_string_lossy()
+            .into_owned();
+        config.mode = crate::config::Mode::Record;
+        let args = json!({"issue_key": "PROJ-1"});
+        Fixtures::from_config(&config).unwrap().record(
+            "jira_get_issue",
+            &args,
+            &Ok(json!({"key": "PROJ-1", "fields": {"summary": "Recorded"}})),
+        );
+
+        config.mode = crate::config::Mode::Replay;
+        let config = Arc::new(config);
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+        let result = handler
+            .call_tool("jira_get_issue", json!({"issue_key": "proj-1"}), &config)
+            .await
+            .unwrap();
+        assert_eq!(
+            result.structured_content.unwrap()["fields"]["summary"],
+            "Recorded"
+        );
+        assert!(
+            handler
+                .call_tool("jira_get_issue", json!({"issue_key": "PROJ-2"}), &config)
+                .await
+                .is_err()
+        );
+
+        std::fs::remove_dir_all(&config.fixtures_dir).unwrap();
+    }
+
+    #[test]
+    fn test_only_reads_and_single_request_writes_retry_after_401() {
+        assert!(is_retryable_after_unauthorized("jira_search", &json!({})));
+        assert!(is_retryable_after_unauthorized(
+            "jira_update_issue",
+            &json!({"issue_key": "PROJ-1"})
+        ));
+        assert!(!is_retryable_after_unauthorized(
+            "jira_bulk_update",
+            &json!({"jql": "project = PROJ"})
+        ));
+        assert!(!is_retryable_after_unauthorized(
+            "confluence_extract_action_items",
+            &json!({"page_id": "1", "create_issues": true})
+        ));
+        assert!(!is_retryable_after_unauthorized(
+            "jira_delete_issue_property",
+            &json!({"issue_key": "PROJ-1", "confirm_token": "abc"})
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_mode_hides_write_tools() {
+        let mut config = create_test_config();
+        config.read_only = true;
+        let config = Arc::new(config);
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+        let tools = handler.list_tools().await;
+
+        assert!(tools.iter().all(|t| !is_write_operation(&t.name)));
+        assert!(tools.iter().any(|t| t.name == "jira_get_project_roles"));
+        assert!(!tools.iter().any(|t| t.name == "jira_add_user_to_role"));
+
+        let result = handler
+            .call_tool("jira_create_issue", json!({}), &config)
+            .await;
+        assert!(result.unwrap_err().to_string().contains("read-only mode"));
+    }
+
+    #[test]
+    fn test_call_timeout_resolution() {
+        let mut config = create_test_config();
+        config
+            .tool_timeouts_ms
+            .insert("jira_get_issue".to_string(), 5000);
+
+        let mut args = json!({"issue_key": "PROJ-1", "timeout_ms": 2500});
+        assert_eq!(
+            call_timeout("jira_get_issue", &mut args, &config).unwrap(),
+            Some(Duration::from_millis(2500))
+        );
+        assert!(args.get("timeout_ms").is_none());
+
+        let mut args = json!({});
+        assert_eq!(
+            call_timeout("jira_get_issue", &mut args, &config).unwrap(),
+            Some(Duration::from_millis(5000))
+        );
+        assert_eq!(
+            call_timeout("jira_search", &mut args, &config).unwrap(),
+            Some(Duration::from_millis(60_000))
+        );
+        assert_eq!(
+            call_timeout("jira_add_comment", &mut args, &config).unwrap(),
+            None
+        );
+
+        let mut args = json!({"timeout_ms": 10});
+        assert!(call_timeout("jira_get_issue", &mut args, &config).is_err());
+    }
+
+    struct SlowHandler;
+
+    #[async_trait::async_trait]
+    impl ToolHandler for SlowHandler {
+        fn name(&self) -> &'static str {
+            "slow_tool"
+        }
+
+        fn schema(&self, _config: &Config) -> ToolSchema {
+            ToolSchema::new("Sleeps past any deadline", HashMap::new(), vec![])
+        }
+
+        async fn execute(&self, _args: Value, config: &Config) -> Result<Value> {
+            assert!(config.request_timeout() <= Duration::from_millis(200));
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(json!({"success": true}))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_call_deadline_is_enforced() {
+        let config = Arc::new(create_test_config());
+        let mut handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+        handler
+            .tools
+            .insert("slow_tool".to_string(), Arc::new(SlowHandler));
+
+        let err = handler
+            .call_tool("slow_tool", json!({"timeout_ms": 200}), &config)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Tool slow_tool timed out after 200 ms");
+    }
+
+    #[tokio::test]
+    async fn test_enabled_tools_allowlist() {
+        let mut config = create_test_config();
+        config.enabled_tools = vec!["confluence_*".to_string(), "jira_get_issue".to_string()];
+        let config = Arc::new(config);
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+        let tools = handler.list_tools().await;
+
+        assert_eq!(tools.len(), 27);
+        assert!(tools.iter().any(|t| t.name == "jira_get_issue"));
+        assert!(!tools.iter().any(|t| t.name == "jira_search"));
+
+        let result = handler.call_tool("jira_search", json!({}), &config).await;
+        assert!(result.unwrap_err().to_string().contains("ENABLED_TOOLS"));
+    }
+
+    #[tokio::test]
+    async fn test_localized_tool_descriptions() {
+        let mut config = create_test_config();
+        config.locale = i18n::Locale::Ja;
+        let handler = RequestHandler::new(Arc::new(config), Notifier::channel().0)
+            .await
+            .unwrap();
+        let tools = handler.list_tools().await;
+        let search = tools.iter().find(|t| t.name == "jira_search").unwrap();
+        assert_eq!(search.description, "JQL で Jira 課題を検索");
+        // Every tool has a translation; untranslated ones would stay English
+        for tool in &tools {
+            assert!(
+                i18n::tool_description(i18n::Locale::Ko, &tool.name).is_some(),
+                "{} has no translated description",
+                tool.name
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_schema_verbosity_tiers() {
+        let mut sizes = Vec::new();
+        for verbosity in [Verbosity::Minimal, Verbosity::Standard, Verbosity::Detailed] {
+            let mut config = create_test_config();
+            config.tool_schema_verbosity = verbosity;
+            let handler = RequestHandler::new(Arc::new(config), Notifier::channel().0)
+                .await
+                .unwrap();
+            let tools = handler.list_tools().await;
+            let search = tools.iter().find(|t| t.name == "jira_search").unwrap();
+            let fields = search.input_schema.properties["fields"]
+                .description
+                .clone()
+                .unwrap();
+            assert_eq!(
+                fields.contains("issuetype"),
+                verbosity != Verbosity::Minimal,
+                "{:?}: {}",
+                verbosity,
+                fields
+            );
+            sizes.push(serde_json::to_string(&tools).unwrap().len());
+        }
+        assert!(sizes[0] < sizes[1] && sizes[1] < sizes[2], "{:?}", sizes);
+    }
+
+    #[tokio::test]
+    async fn test_tool_name_prefix() {
+        let mut config = create_test_config();
+        config.tool_name_prefix = Some("prod_".to_string());
+        let config = Arc::new(config);
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+        let tools = handler.list_tools().await;
+        assert!(tools.iter().all(|t| t.name.starts_with("prod_")));
+        let get_issue = tools
+            .iter()
+            .find(|t| t.name == "prod_jira_get_issue")
+            .unwrap();
+        assert!(get_issue.output_schema.is_some());
+
+        let unprefixed = handler
+            .call_tool("jira_get_issue", json!({}), &config)
+            .await;
+        assert!(
+            unprefixed
+                .unwrap_err()
+                .to_string()
+                .contains("Tool not found: jira_get_issue")
+        );
+        // The prefixed name reaches the handler (and its argument validation)
+        let prefixed = handler
+            .call_tool("prod_jira_get_issue", json!({}), &config)
+            .await;
+        assert!(prefixed.unwrap_err().to_string().contains("issue_key"));
     }
 
     #[tokio::test]
     async fn test_list_tools_has_confluence_tools() {
         let config = Arc::new(create_test_config());
-        let handler = RequestHandler::new(config).await.unwrap();
+        let handler = RequestHandler::new(config, Notifier::channel().0)
+            .await
+            .unwrap();
         let tools = handler.list_tools().await;
 
         let confluence_tools: Vec<_> = tools
             .iter()
             .filter(|t| t.name.starts_with("confluence_"))
             .collect();
-        assert_eq!(confluence_tools.len(), 6);
+        assert_eq!(confluence_tools.len(), 26);
 
         // Verify specific Confluence tools exist
         assert!(tools.iter().any(|t| t.name == "confluence_search"));
         assert!(tools.iter().any(|t| t.name == "confluence_get_page"));
         assert!(tools.iter().any(|t| t.name == "confluence_create_page"));
+        assert!(tools.iter().any(|t| t.name == "confluence_get_tasks"));
     }
 
     #[tokio::test]
     async fn test_tool_schema_structure() {
         let config = Arc::new(create_test_config());
-        let handler = RequestHandler::new(config).await.unwrap();
+        let handler = RequestHandler::new(config, Notifier::channel().0)
+            .await
+            .unwrap();
         let tools = handler.list_tools().await;
 
         for tool in tools {
@@ -567,7 +1148,9 @@ mod tests {
     #[tokio::test]
     async fn test_jira_search_schema_includes_fields_description() {
         let config = Arc::new(create_test_config());
-        let handler = RequestHandler::new(config).await.unwrap();
+        let handler = RequestHandler::new(config, Notifier::channel().0)
+            .await
+            .unwrap();
         let tools = handler.list_tools().await;
 
         let jira_search = tools.iter().find(|t| t.name == "jira_search").unwrap();
@@ -585,12 +1168,19 @@ mod tests {
         assert!(fields_prop.description.is_some());
         let desc = fields_prop.description.as_ref().unwrap();
         assert!(desc.contains("17 default fields")); // Based on DEFAULT_SEARCH_FIELDS count
+
+        // Verify format parameter advertises table output
+        let format_prop = jira_search.input_schema.properties.get("format").unwrap();
+        let formats = format_prop.enum_values.as_ref().unwrap();
+        assert!(formats.contains(&json!("table")));
     }
 
     #[tokio::test]
     async fn test_jira_get_issue_schema() {
         let config = Arc::new(create_test_config());
-        let handler = RequestHandler::new(config).await.unwrap();
+        let handler = RequestHandler::new(config, Notifier::channel().0)
+            .await
+            .unwrap();
         let tools = handler.list_tools().await;
 
         let tool = tools.iter().find(|t| t.name == "jira_get_issue").unwrap();
@@ -607,7 +1197,9 @@ mod tests {
     #[tokio::test]
     async fn test_confluence_create_page_schema() {
         let config = Arc::new(create_test_config());
-        let handler = RequestHandler::new(config).await.unwrap();
+        let handler = RequestHandler::new(config, Notifier::channel().0)
+            .await
+            .unwrap();
         let tools = handler.list_tools().await;
 
         let tool = tools
@@ -632,20 +1224,66 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_create_string_prop() {
-        let prop = RequestHandler::create_string_prop("Test description", true);
-        assert_eq!(prop.property_type, "string");
-        assert_eq!(prop.description, Some("Test description".to_string()));
-        assert!(prop.default.is_none());
-        assert!(prop.enum_values.is_none());
+    async fn test_call_tool_validates_arguments_against_schema() {
+        let config = Arc::new(create_test_config());
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+
+        let error = handler
+            .call_tool("jira_get_issue", json!({"issue_key": 123}), &config)
+            .await
+            .unwrap_err();
+        let invalid = error
+            .downcast_ref::<crate::tools::args::InvalidArgs>()
+            .expect("schema violations are InvalidArgs");
+        assert_eq!(invalid.invalid[0].0, "issue_key");
+
+        let error = handler
+            .call_tool("jira_transition_issue", json!({}), &config)
+            .await
+            .unwrap_err();
+        let invalid = error
+            .downcast_ref::<crate::tools::args::InvalidArgs>()
+            .unwrap();
+        assert_eq!(invalid.missing, vec!["issue_key", "transition_id"]);
+
+        let error = handler
+            .call_tool("jira_get_issue", json!({"issue_key": "not a key"}), &config)
+            .await
+            .unwrap_err();
+        let invalid = error
+            .downcast_ref::<crate::tools::args::InvalidArgs>()
+            .unwrap();
+        assert_eq!(invalid.invalid[0].0, "issue_key");
+    }
+
+    #[test]
+    fn test_normalize_issue_keys_rewrites_links() {
+        let mut args = json!({"issue_key": "https://x.atlassian.net/browse/proj-7", "jql": "x"});
+        normalize_issue_keys(&mut args).unwrap();
+        assert_eq!(args, json!({"issue_key": "PROJ-7", "jql": "x"}));
     }
 
     #[tokio::test]
-    async fn test_create_number_prop() {
-        let prop = RequestHandler::create_number_prop("Test number", 42);
-        assert_eq!(prop.property_type, "number");
-        assert_eq!(prop.description, Some("Test number".to_string()));
-        assert_eq!(prop.default, Some(json!(42)));
-        assert!(prop.enum_values.is_none());
+    async fn test_session_stats_count_calls_and_errors() {
+        let config = Arc::new(create_test_config());
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+
+        let _ = handler
+            .call_tool("jira_get_issue", json!({}), &config)
+            .await;
+        let _ = handler.call_tool("no_such_tool", json!({}), &config).await;
+        let result = handler
+            .call_tool("atlassian_session_stats", json!({}), &config)
+            .await
+            .unwrap();
+        let stats = result.structured_content.unwrap();
+        assert_eq!(stats["tool_calls"], 1);
+        assert_eq!(stats["errors"], 1);
+        assert_eq!(stats["by_tool"][0]["tool"], "jira_get_issue");
+        assert_eq!(handler.stats().snapshot()["tool_calls"], 2);
     }
 }
@@ -0,0 +1,514 @@
+//! Tool descriptions and error hints in the conversation's language
+//!
+//! Assistants pick tools more reliably when the descriptions match the
+//! language the user writes in. `ATLASSIAN_LOCALE` selects one of
+//! [`Locale`]; `tools/list` then sends the description from
+//! [`TOOL_DESCRIPTIONS`] and failed calls get their hint (see
+//! `error_hints`) in that language. Argument descriptions, results and
+//! Atlassian's own error texts stay as they are. A tool missing from the
+//! table keeps its English description, so add a row with every new tool.
+
+use anyhow::Result;
+use serde::Deserialize;
+
+/// `ATLASSIAN_LOCALE`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Ko,
+    Ja,
+}
+
+impl Locale {
+    /// `en`, `ko` or `ja`; a region or encoding (`ko-KR`, `ja_JP.UTF-8`) is ignored
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim().to_lowercase();
+        let language = value.split(['-', '_', '.']).next().unwrap_or_default();
+        match language {
+            "" | "en" => Ok(Self::En),
+            "ko" => Ok(Self::Ko),
+            "ja" => Ok(Self::Ja),
+            _ => anyhow::bail!(
+                "Invalid ATLASSIAN_LOCALE '{}': expected en, ko or ja",
+                value
+            ),
+        }
+    }
+}
+
+/// A message in every [`Locale`]
+pub struct Text {
+    pub en: &'static str,
+    pub ko: &'static str,
+    pub ja: &'static str,
+}
+
+impl Text {
+    pub fn get(&self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::En => self.en,
+            Locale::Ko => self.ko,
+            Locale::Ja => self.ja,
+        }
+    }
+}
+
+/// The description `tools/list` shows for `tool`; `None` keeps the
+/// handler's English one
+pub fn tool_description(locale: Locale, tool: &str) -> Option<&'static str> {
+    let (_, ko, ja) = TOOL_DESCRIPTIONS
+        .iter()
+        .find(|(name, _, _)| *name == tool)?;
+    match locale {
+        Locale::En => None,
+        Locale::Ko => Some(ko),
+        Locale::Ja => Some(ja),
+    }
+}
+
+/// Tool name, Korean and Japanese description
+const TOOL_DESCRIPTIONS: &[(&str, &str, &str)] = &[
+    // Jira
+    (
+        "jira_get_issue",
+        "키로 Jira 이슈 조회",
+        "キーで Jira 課題を取得",
+    ),
+    (
+        "jira_search",
+        "JQL로 Jira 이슈 검색",
+        "JQL で Jira 課題を検索",
+    ),
+    (
+        "jira_export_search",
+        "JQL 검색 결과를 CSV 텍스트로 내보내기, 한도까지 모든 결과를 페이지 단위로 조회",
+        "JQL の検索結果を CSV テキストでエクスポート(上限まで全ページを取得)",
+    ),
+    ("jira_create_issue", "Jira 이슈 생성", "Jira 課題を作成"),
+    ("jira_update_issue", "Jira 이슈 수정", "Jira 課題を更新"),
+    (
+        "jira_add_comment",
+        "Jira 이슈에 댓글 추가",
+        "Jira 課題にコメントを追加",
+    ),
+    (
+        "jira_update_comment",
+        "Jira 이슈의 기존 댓글을 서식 있는 텍스트(ADF)로 수정",
+        "Jira 課題の既存コメントをリッチテキスト(ADF)で更新",
+    ),
+    (
+        "jira_transition_issue",
+        "Jira 이슈 상태 전환",
+        "Jira 課題のステータスをトランジション",
+    ),
+    (
+        "jira_get_transitions",
+        "Jira 이슈의 가능한 전환 조회",
+        "Jira 課題のトランジションを取得",
+    ),
+    (
+        "jira_get_comment",
+        "Jira 댓글 하나를 작성자, 시각, 공개 범위와 함께 조회",
+        "Jira のコメント 1 件を作成者、日時、公開範囲付きで取得",
+    ),
+    (
+        "jira_get_comments",
+        "Jira 이슈의 댓글 목록, 최신순, 선택적으로 특정 시각 이후만",
+        "Jira 課題のコメント一覧(新しい順)、指定時刻以降のみに絞り込み可能",
+    ),
+    (
+        "jira_issue_delta",
+        "특정 시각 이후 Jira 이슈의 변경 사항: 새 댓글, 변경 이력, 필드의 최종 변경",
+        "指定時刻以降の Jira 課題の変更点: 新しいコメント、変更履歴、フィールドの正味の変更",
+    ),
+    (
+        "jira_issue_report",
+        "이슈 하나에 대한 Markdown 보고서: 필드, 설명, 연결된 이슈, 첨부 파일, 최근 댓글, 주요 변경 이력",
+        "課題 1 件の Markdown レポート: フィールド、説明、リンクされた課題、添付ファイル、最近のコメント、主な変更履歴",
+    ),
+    (
+        "jira_recent_activity",
+        "최근에 보거나 수정한 이슈, 최신순, 어떻게 관여했는지 표시",
+        "最近閲覧・更新した課題を新しい順に、関わり方の印付きで表示",
+    ),
+    (
+        "jira_read_attachment_text",
+        "텍스트 첨부 파일(txt, log, csv, json, md, xml, yaml) 읽기; 내용 크기 제한 있음",
+        "テキスト添付ファイル(txt、log、csv、json、md、xml、yaml)を読み取り、内容はサイズ上限あり",
+    ),
+    (
+        "jira_list_automation_rules",
+        "Jira 자동화 규칙 목록과 상태 및 범위",
+        "Jira オートメーションルールの一覧(状態と範囲付き)",
+    ),
+    (
+        "jira_get_automation_rule",
+        "Jira 자동화 규칙 조회: 트리거, 단계, 전체 정의",
+        "Jira オートメーションルールを取得: トリガー、ステップ、完全な定義",
+    ),
+    (
+        "jira_get_board_snapshot",
+        "보드의 열과 열별 이슈 스냅샷(스크럼 보드는 진행 중 스프린트만), 스탠드업 요약용",
+        "ボードの列と各列の課題のスナップショット(スクラムボードは進行中のスプリントのみ)、スタンドアップの要約向け",
+    ),
+    (
+        "jira_bulk_transition",
+        "JQL 쿼리에 맞는 모든 이슈를 전환(4개씩 동시); 이슈별 결과와 오류를 보고하며 이미 대상 상태인 이슈는 건너뜀",
+        "JQL クエリに一致するすべての課題をトランジション(4 件ずつ並行)、課題ごとの結果とエラーを報告し、既に目的のステータスの課題はスキップ",
+    ),
+    (
+        "jira_bulk_update",
+        "JQL 쿼리에 맞는 모든 이슈에 fields/update 페이로드 적용. 먼저 confirm 없이 호출해 미리 본 뒤, confirm에 일치 건수를 넣어 다시 호출",
+        "JQL クエリに一致するすべての課題に fields/update ペイロードを適用。まず confirm なしで呼び出してプレビューし、次に confirm に一致件数を指定して実行",
+    ),
+    (
+        "jira_get_favourites",
+        "즐겨찾기한 Jira 필터와 이를 쓰는 보드; '내가 자주 쓰는 보드'의 기본값으로 적합",
+        "お気に入りの Jira フィルターとそれを使うボード、「いつものボード」の既定値に最適",
+    ),
+    (
+        "jira_get_subtasks",
+        "직계 하위 항목(하위 작업 또는 에픽의 이슈)을 상태 및 진행률 집계와 함께 나열",
+        "直下の子(サブタスク、またはエピックの課題)をステータスと進捗の集計付きで一覧表示",
+    ),
+    (
+        "jira_get_issue_hierarchy",
+        "이슈 아래의 에픽 -> 스토리 -> 하위 작업 트리를 JQL 쿼리 두 번으로 조회, 상태와 수준별 진행률 집계 포함",
+        "課題配下のエピック -> ストーリー -> サブタスクのツリーを 2 回の JQL クエリで取得(ステータスと階層ごとの進捗集計付き)",
+    ),
+    (
+        "jira_import_csv",
+        "CSV 행으로 이슈 일괄 생성; 행별 결과와 Markdown 결과 표 반환",
+        "CSV の行から課題を一括作成し、行ごとの結果と Markdown の結果表を返す",
+    ),
+    (
+        "jira_notify",
+        "Jira 이슈에 대한 알림 이메일 보내기(수신자 최소 1명 필요)",
+        "Jira 課題について通知メールを送信(受信者が 1 人以上必要)",
+    ),
+    (
+        "jira_get_issue_properties",
+        "Jira 이슈 엔터티 속성 조회(필드가 아닌 앱 메타데이터)",
+        "Jira 課題のエンティティプロパティを取得(フィールドではなくアプリのメタデータ)",
+    ),
+    (
+        "jira_set_issue_property",
+        "Jira 이슈 엔터티 속성 생성 또는 교체",
+        "Jira 課題のエンティティプロパティを作成または置換",
+    ),
+    (
+        "jira_delete_issue_property",
+        "Jira 이슈 엔터티 속성 삭제",
+        "Jira 課題のエンティティプロパティを削除",
+    ),
+    (
+        "jira_get_project_roles",
+        "Jira 프로젝트 역할 목록 또는 한 역할의 구성원",
+        "Jira プロジェクトロールの一覧、または 1 つのロールのメンバー",
+    ),
+    (
+        "jira_add_user_to_role",
+        "Jira 프로젝트 역할에 사용자 추가",
+        "Jira プロジェクトロールにユーザーを追加",
+    ),
+    (
+        "jira_get_project_workflow",
+        "프로젝트 워크플로 조회: 상태, 조건·검증기가 있는 전환, 선택적으로 Mermaid 다이어그램",
+        "プロジェクトのワークフローを取得: ステータス、条件とバリデーター付きのトランジション、必要なら Mermaid 図",
+    ),
+    (
+        "jira_get_permission_scheme",
+        "프로젝트 권한 스킴 조회: 권한별 보유자(역할, 그룹, 사용자)",
+        "プロジェクトの権限スキームを取得: 権限ごとの保持者(ロール、グループ、ユーザー)",
+    ),
+    (
+        "jira_get_statuses",
+        "Jira 상태 목록(id, 이름, 카테고리), 사이트 전체 또는 프로젝트 이슈 유형별; 전환 전에 표현을 실제 상태 이름에 맞출 때 사용",
+        "Jira ステータスの一覧(id、名前、カテゴリ)、サイト全体またはプロジェクトの課題タイプごと。トランジション前に表現を実際のステータス名に対応付けるのに使用",
+    ),
+    (
+        "jira_find_similar_issues",
+        "작성 중인 이슈의 중복 후보 찾기: 요약/텍스트 검색을 로컬 유사도 점수(0-1)로 순위화",
+        "下書きの課題の重複候補を検索: 要約/テキスト検索をローカルのあいまい一致スコア(0-1)で順位付け",
+    ),
+    (
+        "jira_standup_digest",
+        "보드, 프로젝트, 팀의 스탠드업 요약: 기간 내 완료·시작된 이슈, 현재 막힌 이슈, 기한 초과 이슈",
+        "ボード、プロジェクト、チームのスタンドアップ要約: 期間内に完了・開始した課題、現在ブロック中の課題、期限切れの課題",
+    ),
+    (
+        "jira_create_issue_from_template",
+        "설명 템플릿(bug, story, incident, 사용자 정의)으로 Jira 이슈 생성",
+        "説明テンプレート(bug、story、incident、カスタム)から Jira 課題を作成",
+    ),
+    (
+        "jira_time_tracking_summary",
+        "에픽 또는 JQL 집합의 최초 추정, 소요 시간, 잔여 시간 합계(전체 및 담당자별)",
+        "エピックまたは JQL の対象全体の初期見積もり、消費時間、残り時間の合計(全体と担当者ごと)",
+    ),
+    (
+        "jira_watch_query",
+        "JQL 쿼리 감시; 새로 생기거나 수정된 이슈를 jira://issue/KEY에 대한 notifications/resources/updated로 전송",
+        "JQL クエリを監視し、新規・更新された課題を jira://issue/KEY の notifications/resources/updated として送信",
+    ),
+    ("jira_unwatch_query", "JQL 감시 중지", "JQL の監視を停止"),
+    (
+        "jira_workload_by_assignee",
+        "보드, 프로젝트, 팀의 담당자별 미해결 이슈 수와 추정치, 업무량 파악용",
+        "ボード、プロジェクト、チームの担当者ごとの未解決課題数と見積もり(キャパシティの確認向け)",
+    ),
+    // Confluence
+    (
+        "confluence_search",
+        "CQL로 Confluence 검색",
+        "CQL で Confluence を検索",
+    ),
+    (
+        "confluence_get_page",
+        "ID 또는 링크로 Confluence 페이지 조회",
+        "ID またはリンクで Confluence ページを取得",
+    ),
+    (
+        "confluence_get_page_chunk",
+        "큰 Confluence 페이지 본문을 청크 단위로 읽기, next_offset부터 이어서",
+        "大きな Confluence ページの本文を分割して読み取り、next_offset から続きを取得",
+    ),
+    (
+        "confluence_get_page_section",
+        "Confluence 페이지에서 제목 아래의 내용을 같은 수준 이상의 다음 제목 전까지 조회",
+        "Confluence ページの見出しの下の内容を、同じかより上位の次の見出しまで取得",
+    ),
+    (
+        "confluence_get_page_by_title",
+        "스페이스 안에서 정확한 제목으로 Confluence 페이지 찾기",
+        "スペース内で完全一致するタイトルの Confluence ページを検索",
+    ),
+    (
+        "confluence_get_page_children",
+        "페이지의 하위 페이지 조회",
+        "ページの子ページを取得",
+    ),
+    (
+        "confluence_get_comments",
+        "페이지 댓글을 스레드로 조회, 답글은 답글 아래에 중첩",
+        "ページのコメントをスレッドとして取得(返信は返信の下に入れ子)",
+    ),
+    (
+        "confluence_add_comment",
+        "페이지에 @멘션을 포함한 하단 댓글 추가",
+        "ページに @メンション付きのフッターコメントを追加",
+    ),
+    (
+        "confluence_create_page",
+        "Confluence 페이지 생성",
+        "Confluence ページを作成",
+    ),
+    (
+        "confluence_update_page",
+        "Confluence 페이지 수정",
+        "Confluence ページを更新",
+    ),
+    (
+        "confluence_append_to_page",
+        "Confluence 페이지 본문의 나머지는 그대로 두고 내용을 끝이나 앞에 추가",
+        "Confluence ページの本文を保ったまま、末尾または先頭に内容を追加",
+    ),
+    (
+        "confluence_replace_section",
+        "제목 아래의 내용을 같은 수준 이상의 다음 제목 전까지 교체",
+        "見出しの下の内容を、同じかより上位の次の見出しまで置き換え",
+    ),
+    (
+        "confluence_export_page",
+        "Confluence 페이지를 PDF 또는 Word로 내보내기",
+        "Confluence ページを PDF または Word でエクスポート",
+    ),
+    (
+        "confluence_get_tasks",
+        "Confluence 작업(액션 아이템) 조회",
+        "Confluence のタスク(アクションアイテム)を取得",
+    ),
+    (
+        "confluence_update_task_status",
+        "Confluence 작업을 완료 처리하거나 다시 열기",
+        "Confluence のタスクを完了にする、または再オープン",
+    ),
+    (
+        "confluence_extract_action_items",
+        "페이지의 액션 아이템(작업 목록)을 담당자·기한과 함께 나열하고, 선택적으로 항목마다 Jira 이슈 생성",
+        "ページのアクションアイテム(タスクリスト)を担当者と期限付きで一覧表示し、必要なら各項目の Jira 課題を作成",
+    ),
+    (
+        "confluence_recently_viewed",
+        "최근에 보거나 편집한 페이지와 블로그 게시물",
+        "最近閲覧・編集したページとブログ投稿",
+    ),
+    (
+        "confluence_get_page_views",
+        "페이지별 조회수와 고유 조회자 수, 적게 읽힌 순(Confluence 분석)",
+        "ページごとの閲覧数とユニーク閲覧者数、閲覧の少ない順(Confluence アナリティクス)",
+    ),
+    (
+        "confluence_get_favourites",
+        "저장한(별표) Confluence 페이지와 별표한 스페이스",
+        "保存済み(スター付き)の Confluence ページとスター付きスペース",
+    ),
+    (
+        "confluence_list_folders",
+        "스페이스의 폴더 목록",
+        "スペース内のフォルダー一覧",
+    ),
+    (
+        "confluence_get_folder",
+        "폴더와 그 직계 하위 항목(페이지, 폴더, 화이트보드, 데이터베이스, 임베드) 조회",
+        "フォルダーとその直下の項目(ページ、フォルダー、ホワイトボード、データベース、埋め込み)を取得",
+    ),
+    (
+        "confluence_create_folder",
+        "폴더 생성; confluence_create_page의 parent_id로 페이지를 넣을 수 있음",
+        "フォルダーを作成し、confluence_create_page の parent_id でページを入れられる",
+    ),
+    (
+        "confluence_publish_meeting_notes",
+        "Markdown 회의록을 레이블이 붙은 Confluence 페이지로 게시하고 언급된 Jira 이슈에서 링크",
+        "Markdown の議事録をラベル付きの Confluence ページとして公開し、言及された Jira 課題からリンク",
+    ),
+    (
+        "confluence_get_page_restrictions",
+        "페이지를 읽고 수정할 수 있는 사용자와 그룹(작업별) 조회",
+        "ページを閲覧・編集できるユーザーとグループ(操作ごと)を取得",
+    ),
+    (
+        "confluence_set_page_restrictions",
+        "사용자와 그룹 단위로 페이지 읽기·수정 권한 제한",
+        "ユーザーとグループ単位でページの閲覧・編集を制限",
+    ),
+    (
+        "confluence_get_whiteboard",
+        "화이트보드의 제목, 위치, 작성 정보 조회(내용은 API에서 제공되지 않음)",
+        "ホワイトボードのタイトル、場所、作成者情報を取得(内容は API で公開されていない)",
+    ),
+    // Atlassian-wide
+    (
+        "atlassian_health_check",
+        "자격 증명과 연결 확인: Jira 사용자와 권한, Confluence 접근, 설정된 필터",
+        "認証情報と接続を確認: Jira ユーザーと権限、Confluence へのアクセス、設定済みフィルター",
+    ),
+    (
+        "atlassian_session_stats",
+        "세션 카운터: 도구 호출, 오류, Atlassian API 시간, 캐시 적중, 반환된 추정 토큰 수, 도구별(무거운 순)",
+        "セッションのカウンター: ツール呼び出し、エラー、Atlassian API 時間、キャッシュヒット、返した推定トークン数(ツールごと、重い順)",
+    ),
+    (
+        "atlassian_undo_last",
+        "이 서버로 한 가장 최근의 이슈 수정, 전환 또는 페이지 편집을 되돌리기",
+        "このサーバーで行った直近の課題更新、トランジション、またはページ編集を元に戻す",
+    ),
+    (
+        "atlassian_get_teams",
+        "조직의 Atlassian 팀 목록(ID, 이름, 설명), 선택적으로 이름으로 필터",
+        "組織の Atlassian チーム一覧(ID、名前、説明)、名前で絞り込み可能",
+    ),
+    (
+        "atlassian_get_team_members",
+        "Atlassian 팀 구성원과 Jira 계정 ID, 선택적으로 담당자 선정을 위한 구성원별 미해결 업무량",
+        "Atlassian チームのメンバーと Jira アカウント ID、担当者選び向けに各メンバーの未解決の作業量も取得可能",
+    ),
+    // Opt-in
+    (
+        "bitbucket_list_prs",
+        "Bitbucket 풀 리퀘스트 목록 조회",
+        "Bitbucket のプルリクエスト一覧を取得",
+    ),
+    (
+        "bitbucket_get_pr",
+        "Bitbucket 풀 리퀘스트 조회",
+        "Bitbucket のプルリクエストを取得",
+    ),
+    (
+        "bitbucket_create_pr_comment",
+        "Bitbucket 풀 리퀘스트에 댓글 추가",
+        "Bitbucket のプルリクエストにコメントを追加",
+    ),
+    (
+        "bitbucket_get_pipelines",
+        "최근 Bitbucket Pipelines 실행 목록",
+        "最近の Bitbucket Pipelines の実行一覧",
+    ),
+    (
+        "jsm_sla_breaches",
+        "SLA를 위반했거나 N시간 안에 만료되는 열린 서비스 요청, 긴급한 순, 에스컬레이션용",
+        "SLA 違反中または N 時間以内に期限を迎える未解決のサービスリクエスト(緊急度順、エスカレーション向け)",
+    ),
+    (
+        "compass_list_components",
+        "Compass 컴포넌트 카탈로그(서비스, 라이브러리 등)를 유형 및 소유 팀 ID와 함께 검색",
+        "Compass コンポーネントカタログ(サービス、ライブラリなど)を種類と担当チーム ID 付きで検索",
+    ),
+    (
+        "compass_get_component",
+        "Compass 컴포넌트의 소유 팀, 링크, 레이블, 스코어카드 점수 조회('서비스 X의 담당자는?')",
+        "Compass コンポーネントの担当チーム、リンク、ラベル、スコアカードのスコアを取得(「サービス X の担当は?」)",
+    ),
+    (
+        "create_incident",
+        "인시던트 개시: 템플릿으로 Jira 인시던트 이슈와 포스트모템 Confluence 페이지를 만들어 서로 연결",
+        "インシデントを開始: テンプレートから Jira のインシデント課題とポストモーテムの Confluence ページを作成し、相互にリンク",
+    ),
+    (
+        "trello_list_boards",
+        "인증된 구성원의 Trello 보드 목록",
+        "認証済みメンバーの Trello ボード一覧",
+    ),
+    (
+        "trello_get_lists",
+        "Trello 보드의 열린 리스트 조회",
+        "Trello ボードの開いているリストを取得",
+    ),
+    (
+        "trello_get_cards",
+        "Trello 리스트 또는 보드의 카드 조회",
+        "Trello のリストまたはボードのカードを取得",
+    ),
+    (
+        "trello_move_card",
+        "Trello 카드를 다른 리스트로 이동",
+        "Trello カードを別のリストに移動",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_locale() {
+        assert_eq!(Locale::parse("").unwrap(), Locale::En);
+        assert_eq!(Locale::parse("KO").unwrap(), Locale::Ko);
+        assert_eq!(Locale::parse("ja_JP.UTF-8").unwrap(), Locale::Ja);
+        assert_eq!(Locale::parse("ko-KR").unwrap(), Locale::Ko);
+        assert!(
+            Locale::parse("fr")
+                .unwrap_err()
+                .to_string()
+                .contains("expected en, ko or ja")
+        );
+    }
+
+    #[test]
+    fn test_tool_descriptions() {
+        assert_eq!(tool_description(Locale::En, "jira_search"), None);
+        assert_eq!(
+            tool_description(Locale::Ko, "jira_search"),
+            Some("JQL로 Jira 이슈 검색")
+        );
+        assert_eq!(tool_description(Locale::Ja, "unknown_tool"), None);
+
+        let mut names: Vec<_> = TOOL_DESCRIPTIONS.iter().map(|(name, _, _)| *name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), TOOL_DESCRIPTIONS.len());
+    }
+}
@@ -1,3 +1,15 @@
+pub mod approval;
+pub mod audit;
+pub mod error_hints;
+pub mod fixtures;
 pub mod handlers;
+pub mod i18n;
+pub mod notifications;
+pub mod output_schemas;
+pub mod prompts;
+pub mod reload;
 pub mod server;
+pub mod stats;
+pub mod stdio;
+pub mod subscriptions;
 pub mod types;
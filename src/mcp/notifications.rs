@@ -0,0 +1,94 @@
+//! Server-initiated notifications
+//!
+//! Background subsystems (webhook receiver, watchers) push JSON-RPC notifications
+//! through a `Notifier`; the stdio server forwards them to the client.
+
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use super::types::JsonRpcNotification;
+
+/// Notification method for changed resources (MCP `resources/subscribe`)
+pub const RESOURCE_UPDATED: &str = "notifications/resources/updated";
+
+/// Notification method for a changed `tools/list` (config reload)
+pub const TOOLS_LIST_CHANGED: &str = "notifications/tools/list_changed";
+
+/// Notification method for long-running call progress (MCP `_meta.progressToken`)
+pub const PROGRESS: &str = "notifications/progress";
+
+/// Argument key under which `tools/call` passes the request's progress token
+pub const PROGRESS_TOKEN_ARG: &str = "_progress_token";
+
+/// Cloneable handle for emitting notifications to the connected client
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    tx: mpsc::UnboundedSender<JsonRpcNotification>,
+}
+
+impl Notifier {
+    /// Creates a notifier and the receiver the server drains
+    pub fn channel() -> (Self, mpsc::UnboundedReceiver<JsonRpcNotification>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Self { tx }, rx)
+    }
+
+    /// Queues a notification. Returns `false` if the server is no longer forwarding.
+    pub fn notify(&self, method: &str, params: Option<Value>) -> bool {
+        self.tx
+            .send(JsonRpcNotification::new(method, params))
+            .is_ok()
+    }
+
+    /// Queues `notifications/progress` for `token`
+    pub fn progress(&self, token: &Value, progress: usize, total: usize, message: &str) -> bool {
+        self.notify(
+            PROGRESS,
+            Some(serde_json::json!({
+                "progressToken": token,
+                "progress": progress,
+                "total": total,
+                "message": message
+            })),
+        )
+    }
+
+    /// Queues `notifications/resources/updated` for `uri`
+    pub fn resource_updated(&self, uri: &str) -> bool {
+        self.notify(RESOURCE_UPDATED, Some(serde_json::json!({ "uri": uri })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_resource_updated_is_delivered() {
+        let (notifier, mut rx) = Notifier::channel();
+        assert!(notifier.resource_updated("jira://issue/PROJ-1"));
+
+        let notification = rx.recv().await.unwrap();
+        assert_eq!(notification.method, RESOURCE_UPDATED);
+        assert_eq!(notification.params.unwrap()["uri"], "jira://issue/PROJ-1");
+    }
+
+    #[tokio::test]
+    async fn test_progress_carries_token() {
+        let (notifier, mut rx) = Notifier::channel();
+        notifier.progress(&serde_json::json!("tok-1"), 20, 45, "20 of 45 issues");
+
+        let notification = rx.recv().await.unwrap();
+        assert_eq!(notification.method, PROGRESS);
+        let params = notification.params.unwrap();
+        assert_eq!(params["progressToken"], "tok-1");
+        assert_eq!(params["total"], 45);
+    }
+
+    #[test]
+    fn test_notify_after_receiver_dropped() {
+        let (notifier, rx) = Notifier::channel();
+        drop(rx);
+        assert!(!notifier.notify("notifications/message", None));
+    }
+}
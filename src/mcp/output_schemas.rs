@@ -0,0 +1,88 @@
+//! Tool output schemas (`outputSchema`, protocol 2025-06-18)
+//!
+//! Declared only for tools whose result is always a JSON object: a tool with
+//! an `outputSchema` must answer with matching `structuredContent`, so tools
+//! that can return Markdown, CSV or Mermaid text stay undeclared (their object
+//! results still carry `structuredContent`). Response optimization drops
+//! empty and null fields, so only `success` is required.
+
+use serde_json::{Value, json};
+
+fn object(properties: Value) -> Value {
+    let mut properties = properties;
+    properties["success"] = json!({"type": "boolean"});
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": ["success"]
+    })
+}
+
+fn array_of_objects(description: &str) -> Value {
+    json!({"type": "array", "items": {"type": "object"}, "description": description})
+}
+
+/// JSON Schema of the tool's result, if declared
+pub fn output_schema(tool: &str) -> Option<Value> {
+    let schema = match tool {
+        "jira_get_issue" => object(json!({
+            "issue": {"type": "object", "description": "Issue as returned by the Jira REST API"},
+            "activity": {"type": "object", "description": "Latest comments and changelog entries (include_activity)"}
+        })),
+        "jira_create_issue" => object(json!({
+            "key": {"type": "string"},
+            "id": {"type": "string"}
+        })),
+        "jira_transition_issue" => object(json!({
+            "message": {"type": "string"}
+        })),
+        "jira_get_transitions" => object(json!({
+            "transitions": array_of_objects("Available transitions with their target status")
+        })),
+        "jira_time_tracking_summary" => object(json!({
+            "scope": {"type": "string"},
+            "truncated": {"type": "boolean"},
+            "totals": {"type": "object"},
+            "by_assignee": array_of_objects("Totals per assignee")
+        })),
+        "confluence_search" => object(json!({
+            "results": array_of_objects("CQL search results"),
+            "total": {"type": "integer"},
+            "applied_filters": {"type": "object", "description": "The CQL as run when it was rewritten (space filter)"}
+        })),
+        "confluence_get_page" => object(json!({
+            "page": {"type": "object", "description": "Page as returned by the Confluence REST API"}
+        })),
+        "confluence_create_page" => object(json!({
+            "page_id": {"type": "string"},
+            "title": {"type": "string"}
+        })),
+        "confluence_get_page_views" => object(json!({
+            "total": {"type": "integer"},
+            "pages": array_of_objects("Pages with view and viewer counts, least read first")
+        })),
+        _ => return None,
+    };
+    Some(schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_schema_requires_success() {
+        let schema = output_schema("jira_create_issue").unwrap();
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], json!(["success"]));
+        assert_eq!(schema["properties"]["key"]["type"], "string");
+        assert_eq!(schema["properties"]["success"]["type"], "boolean");
+    }
+
+    #[test]
+    fn test_text_returning_tools_have_no_schema() {
+        // `format: csv|table` returns text, which cannot match an object schema
+        assert!(output_schema("jira_search").is_none());
+        assert!(output_schema("jira_get_project_workflow").is_none());
+    }
+}
@@ -0,0 +1,130 @@
+//! MCP prompts
+//!
+//! Prompts are user-selected message templates (`prompts/list`,
+//! `prompts/get`). Each one frames a workflow around the server's tools;
+//! arguments are substituted into the message text.
+
+use serde_json::{Value, json};
+
+struct PromptArgument {
+    name: &'static str,
+    description: &'static str,
+    required: bool,
+    /// Substituted when an optional argument is omitted
+    default: &'static str,
+}
+
+struct Prompt {
+    name: &'static str,
+    description: &'static str,
+    arguments: &'static [PromptArgument],
+    /// `{name}` placeholders are replaced by the argument values, and
+    /// `{prefix}` by `TOOL_NAME_PREFIX` in front of tool names
+    template: &'static str,
+}
+
+const PROMPTS: &[Prompt] = &[Prompt {
+    name: "meeting_notes",
+    description: "Tidy raw meeting notes and publish them to Confluence with confluence_publish_meeting_notes",
+    arguments: &[
+        PromptArgument {
+            name: "notes",
+            description: "Raw notes or transcript",
+            required: true,
+            default: "",
+        },
+        PromptArgument {
+            name: "title",
+            description: "Page title (default: \"Meeting notes\" and today's date)",
+            required: false,
+            default: "Meeting notes <today's date>",
+        },
+    ],
+    template: "Turn the raw notes below into meeting notes in Markdown with these sections: \
+## Attendees, ## Agenda, ## Decisions and ## Action items. Write each action item as a \
+task (`- [ ] owner: what, by when`). Keep Jira issue keys (e.g. PROJ-123) exactly as written \
+so the issues get a link to the page. Then call {prefix}confluence_publish_meeting_notes with the title \
+\"{title}\" and the Markdown as `notes`, and reply with the page URL and the issues that \
+were commented on.\n\nRaw notes:\n\n{notes}",
+}];
+
+/// `prompts/list` entries
+pub fn list() -> Value {
+    let prompts: Vec<Value> = PROMPTS
+        .iter()
+        .map(|prompt| {
+            json!({
+                "name": prompt.name,
+                "description": prompt.description,
+                "arguments": prompt.arguments.iter().map(|arg| json!({
+                    "name": arg.name,
+                    "description": arg.description,
+                    "required": arg.required
+                })).collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    json!({ "prompts": prompts })
+}
+
+/// `prompts/get` result, or an error message for unknown prompts and
+/// missing required arguments; `tool_prefix` is `TOOL_NAME_PREFIX`
+pub fn get(name: &str, arguments: &Value, tool_prefix: &str) -> Result<Value, String> {
+    let prompt = PROMPTS
+        .iter()
+        .find(|prompt| prompt.name == name)
+        .ok_or_else(|| format!("Unknown prompt: {}", name))?;
+
+    let mut text = prompt.template.to_string();
+    for arg in prompt.arguments {
+        let value = arguments[arg.name]
+            .as_str()
+            .filter(|v| !v.trim().is_empty());
+        let value = match value {
+            Some(value) => value,
+            None if arg.required => {
+                return Err(format!("Missing required argument: {}", arg.name));
+            }
+            None => arg.default,
+        };
+        text = text.replace(&format!("{{{}}}", arg.name), value);
+    }
+    text = text.replace("{prefix}", tool_prefix);
+
+    Ok(json!({
+        "description": prompt.description,
+        "messages": [{
+            "role": "user",
+            "content": { "type": "text", "text": text }
+        }]
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_prompts() {
+        let listed = list();
+        assert_eq!(listed["prompts"][0]["name"], "meeting_notes");
+        assert_eq!(listed["prompts"][0]["arguments"][0]["required"], true);
+    }
+
+    #[test]
+    fn test_get_prompt_fills_arguments() {
+        let result = get("meeting_notes", &json!({"notes": "Bob: ship PROJ-1"}), "").unwrap();
+        let text = result["messages"][0]["content"]["text"].as_str().unwrap();
+        assert!(text.ends_with("Raw notes:\n\nBob: ship PROJ-1"));
+        assert!(text.contains("\"Meeting notes <today's date>\""));
+
+        assert!(text.contains("call confluence_publish_meeting_notes"));
+
+        let prefixed = get("meeting_notes", &json!({"notes": "x"}), "prod_").unwrap();
+        let text = prefixed["messages"][0]["content"]["text"].as_str().unwrap();
+        assert!(text.contains("call prod_confluence_publish_meeting_notes"));
+
+        assert!(get("meeting_notes", &json!({}), "").is_err());
+        assert!(get("nope", &json!({}), "").is_err());
+    }
+}
@@ -0,0 +1,201 @@
+//! Config hot-reload
+//!
+//! With `CONFIG_RELOAD_SECS` set, the server polls the modification times of
+//! `.env` and the config file. On a change it reloads `Config`, rebuilds the
+//! `RequestHandler` (tool registry, filters, exclude fields, audit log) and
+//! swaps both in; in-flight calls finish on the previous pair. Session stats
+//! and JQL watches carry over to the new handler. When the set of registered
+//! tools changed, clients get `notifications/tools/list_changed`.
+//! The webhook listener is bound once at startup and needs a restart.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::config::Config;
+
+use super::handlers::RequestHandler;
+use super::notifications::{Notifier, TOOLS_LIST_CHANGED};
+
+/// Config and the handler built from it, swapped as a unit
+pub struct Live {
+    pub config: Arc<Config>,
+    pub handler: Arc<RequestHandler>,
+}
+
+pub type SharedLive = Arc<RwLock<Arc<Live>>>;
+
+/// Modification time of each file; `None` for a missing file
+pub fn file_stamps(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+pub async fn tool_names(handler: &RequestHandler) -> BTreeSet<String> {
+    handler
+        .list_tools()
+        .await
+        .into_iter()
+        .map(|t| t.name)
+        .collect()
+}
+
+/// Builds the next `Live` from a reloaded config
+async fn rebuild(current: &Live, config: Config, notifier: &Notifier) -> anyhow::Result<Live> {
+    let config = Arc::new(config);
+    if config.jira_webhook_listen_addr != current.config.jira_webhook_listen_addr {
+        warn!("JIRA_WEBHOOK_LISTEN_ADDR changed; restart the server to apply it");
+    }
+
+    let handler = Arc::new(
+        RequestHandler::replacing(config.clone(), notifier.clone(), &current.handler).await?,
+    );
+    if tool_names(&handler).await != tool_names(&current.handler).await {
+        notifier.notify(TOOLS_LIST_CHANGED, None);
+    }
+    Ok(Live { config, handler })
+}
+
+/// Polls the watched files until the task is aborted
+pub async fn watch(live: SharedLive, notifier: Notifier) {
+    let (interval, mut stamps) = {
+        let current = live.read().await;
+        (
+            Duration::from_secs(current.config.config_reload_secs.max(1)),
+            file_stamps(&current.config.watched_files()),
+        )
+    };
+
+    loop {
+        tokio::time::sleep(interval).await;
+        let current = live.read().await.clone();
+        let now = file_stamps(&current.config.watched_files());
+        if now == stamps {
+            continue;
+        }
+        stamps = now;
+
+        let next = match current.config.reload() {
+            Ok(config) => rebuild(&current, config, &notifier).await,
+            Err(e) => Err(e),
+        };
+        match next {
+            Ok(next) => {
+                info!("Configuration reloaded");
+                *live.write().await = Arc::new(next);
+            }
+            Err(e) => warn!(error = %e, "Config reload failed; keeping the previous configuration"),
+        }
+    }
+}
+
+fn same_credentials(a: &Config, b: &Config) -> bool {
+    a.atlassian_email == b.atlassian_email && a.atlassian_api_token == b.atlassian_api_token
+}
+
+/// After a 401 on `failed`, re-reads the configuration in case the API token
+/// was rotated in `.env` or the config file. Swaps in and returns the new
+/// `Live` when the credentials differ, `None` when they are unchanged.
+pub async fn refresh_credentials(
+    live: &SharedLive,
+    notifier: &Notifier,
+    failed: &Live,
+) -> Option<Arc<Live>> {
+    let current = live.read().await.clone();
+    // The watcher or a concurrent call got there first
+    if !same_credentials(&current.config, &failed.config) {
+        return Some(current);
+    }
+
+    let config = match current.config.reload() {
+        Ok(config) if !same_credentials(&config, &failed.config) => config,
+        Ok(_) => return None,
+        Err(e) => {
+            warn!(error = %e, "Config reload after 401 failed");
+            return None;
+        }
+    };
+    match rebuild(&current, config, notifier).await {
+        Ok(next) => {
+            info!("Credentials changed; configuration reloaded");
+            let next = Arc::new(next);
+            *live.write().await = next.clone();
+            Some(next)
+        }
+        Err(e) => {
+            warn!(error = %e, "Config reload after 401 failed");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ConfigBuilder;
+    use serde_json::json;
+
+    #[test]
+    fn test_file_stamps_detect_changes() {
+        let path = std::env::temp_dir().join(format!("mcp-reload-{}.toml", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let paths = vec![path.clone()];
+
+        let missing = file_stamps(&paths);
+        assert_eq!(missing, vec![None]);
+
+        std::fs::write(&path, "read_only_mode = true").unwrap();
+        let created = file_stamps(&paths);
+        assert!(created[0].is_some());
+        assert_ne!(created, missing);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_keeps_jql_watches() {
+        let mock = crate::mock::MockAtlassianServer::start().await.unwrap();
+        let config = Arc::new(ConfigBuilder::mock(&mock).build());
+        let notifier = Notifier::channel().0;
+        let handler = RequestHandler::new(config.clone(), notifier.clone())
+            .await
+            .unwrap();
+        let started = handler
+            .call_tool(
+                "jira_watch_query",
+                json!({"jql": "project = DEMO"}),
+                &config,
+            )
+            .await
+            .unwrap()
+            .structured_content
+            .unwrap();
+
+        let current = Live {
+            config,
+            handler: Arc::new(handler),
+        };
+        let next = rebuild(&current, (*current.config).clone(), &notifier)
+            .await
+            .unwrap();
+        drop(current);
+
+        let stopped = next
+            .handler
+            .call_tool(
+                "jira_unwatch_query",
+                json!({"watch_id": started["watch_id"]}),
+                &next.config,
+            )
+            .await
+            .unwrap()
+            .structured_content
+            .unwrap();
+        assert_eq!(stopped["jql"], "project = DEMO");
+    }
+}
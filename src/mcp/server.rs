@@ -2,119 +2,270 @@ use anyhow::Result;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::io::{AsyncWriteExt, BufReader, Stdout};
+use tokio::sync::{Mutex, RwLock, mpsc};
 use tracing::{debug, error, info, warn};
 
 use crate::config::Config;
-
-use super::handlers::RequestHandler;
+use crate::tools::args::InvalidArgs;
+use crate::tools::{ToolHandler, jira};
+
+use super::error_hints::{self, AuthFailure};
+use super::handlers::{RequestHandler, is_retryable_after_unauthorized};
+use super::notifications::{Notifier, PROGRESS_TOKEN_ARG};
+use super::prompts;
+use super::reload::{self, Live, SharedLive};
+use super::stdio::{Frame, FrameReader};
+use super::subscriptions::{ResourceSubscriptions, parse_issue_uri};
 use super::types::*;
 
 pub struct McpServer {
-    config: Arc<Config>,
-    handler: Arc<RequestHandler>,
+    live: SharedLive,
     initialized: Arc<RwLock<bool>>,
+    /// Version agreed on in `initialize`; newer result fields depend on it
+    protocol_version: Arc<RwLock<Option<&'static str>>>,
+    subscriptions: Arc<ResourceSubscriptions>,
+    notifier: Notifier,
+    notifications: std::sync::Mutex<Option<mpsc::UnboundedReceiver<JsonRpcNotification>>>,
+}
+
+/// Writes one newline-delimited JSON-RPC message to the shared stdout
+async fn write_message<T: serde::Serialize>(stdout: &Mutex<Stdout>, message: &T) -> Result<()> {
+    let message_str = serde_json::to_string(message)?;
+    debug!("Sending: {}", message_str);
+
+    let mut stdout = stdout.lock().await;
+    stdout.write_all(message_str.as_bytes()).await?;
+    stdout.write_all(b"\n").await?;
+    stdout.flush().await?;
+    Ok(())
 }
 
 impl McpServer {
     pub async fn new(config: Config) -> Result<Self> {
         let config = Arc::new(config);
-        let handler = RequestHandler::new(config.clone()).await?;
+        let (notifier, notifications) = Notifier::channel();
+        let handler = RequestHandler::new(config.clone(), notifier.clone()).await?;
 
         Ok(Self {
-            config,
-            handler: Arc::new(handler),
+            live: Arc::new(RwLock::new(Arc::new(Live {
+                config,
+                handler: Arc::new(handler),
+            }))),
             initialized: Arc::new(RwLock::new(false)),
+            protocol_version: Arc::new(RwLock::new(None)),
+            subscriptions: Arc::new(ResourceSubscriptions::new()),
+            notifier,
+            notifications: std::sync::Mutex::new(Some(notifications)),
         })
     }
 
+    /// Protocol version negotiated with the client, once initialized
+    pub async fn protocol_version(&self) -> Option<&'static str> {
+        *self.protocol_version.read().await
+    }
+
+    /// Whether the client negotiated `structuredContent`/`outputSchema`
+    async fn structured_content(&self) -> bool {
+        self.protocol_version()
+            .await
+            .is_some_and(supports_structured_content)
+    }
+
+    /// Current config and handler; replaced when the config is reloaded
+    async fn live(&self) -> Arc<Live> {
+        self.live.read().await.clone()
+    }
+
     pub async fn run(&self) -> Result<()> {
         info!("Starting MCP server for Atlassian");
+        let config = self.live().await.config.clone();
 
         let stdin = tokio::io::stdin();
-        let stdout = tokio::io::stdout();
-        let mut reader = BufReader::new(stdin);
-        let mut stdout = stdout;
+        let stdout = Arc::new(Mutex::new(tokio::io::stdout()));
+
+        // Forward server-initiated notifications to the client
+        let forwarder = self
+            .notifications
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .map(|mut rx| {
+                let stdout = stdout.clone();
+                tokio::spawn(async move {
+                    while let Some(notification) = rx.recv().await {
+                        if let Err(e) = write_message(&stdout, &notification).await {
+                            error!("Failed to send notification: {}", e);
+                        }
+                    }
+                })
+            });
+
+        let webhook =
+            crate::webhook::start(&config, self.subscriptions.clone(), self.notifier.clone())
+                .await?;
+
+        let reloader = (config.config_reload_secs > 0)
+            .then(|| tokio::spawn(reload::watch(self.live.clone(), self.notifier.clone())));
+
+        let stats_logger = (config.session_stats_log_secs > 0).then(|| {
+            let stats = self.live.clone();
+            let period = Duration::from_secs(config.session_stats_log_secs);
+            tokio::spawn(async move {
+                let mut interval =
+                    tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+                loop {
+                    interval.tick().await;
+                    stats.read().await.handler.stats().log_summary();
+                }
+            })
+        });
 
-        let mut buffer = String::new();
-        let mut empty_reads = 0;
+        let mut frames = FrameReader::new(BufReader::new(stdin), config.max_message_bytes);
 
         loop {
-            buffer.clear();
-
-            // Read a line from stdin
-            match reader.read_line(&mut buffer).await {
-                Ok(0) => {
-                    empty_reads += 1;
-
-                    // Give it a few chances before exiting
-                    if empty_reads > 3 {
-                        info!("Client disconnected (EOF)");
-                        break;
-                    }
-                    // Small delay before retrying
-                    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            let input = match frames.next_frame().await {
+                Ok(Frame::Line(line)) => line,
+                Ok(Frame::Oversized { bytes }) => {
+                    warn!(
+                        "Skipped a {} byte message (limit {})",
+                        bytes, config.max_message_bytes
+                    );
+                    let error = JsonRpcError::message_too_large(bytes, config.max_message_bytes);
+                    write_message(&stdout, &JsonRpcResponse::error(None, error)).await?;
                     continue;
                 }
-                Ok(_) => {
-                    empty_reads = 0; // Reset counter on successful read
-                    let trimmed = buffer.trim();
-                    if trimmed.is_empty() {
-                        continue;
-                    }
-
-                    debug!("Received: {}", trimmed);
-
-                    // Process the request
-                    match self.process_request(trimmed).await {
-                        Ok(Some(response)) => {
-                            let response_str = serde_json::to_string(&response)?;
-                            debug!("Sending response: {}", response_str);
-
-                            stdout.write_all(response_str.as_bytes()).await?;
-                            stdout.write_all(b"\n").await?;
-                            stdout.flush().await?;
-                        }
-                        Ok(None) => {
-                            debug!("Notification received, no response sent");
-                        }
-                        Err(e) => {
-                            error!("Error processing request: {}", e);
-
-                            // Send error response
-                            let error_response = JsonRpcResponse::error(
-                                None,
-                                JsonRpcError::internal_error(e.to_string()),
-                            );
-
-                            let response_str = serde_json::to_string(&error_response)?;
-                            stdout.write_all(response_str.as_bytes()).await?;
-                            stdout.write_all(b"\n").await?;
-                            stdout.flush().await?;
-                        }
-                    }
+                Ok(Frame::InvalidUtf8) => {
+                    warn!("Skipped a message that is not valid UTF-8");
+                    let error = JsonRpcError::parse_error();
+                    write_message(&stdout, &JsonRpcResponse::error(None, error)).await?;
+                    continue;
+                }
+                Ok(Frame::Eof) => {
+                    info!("Client disconnected (EOF)");
+                    break;
                 }
                 Err(e) => {
                     error!("Error reading from stdin: {}", e);
                     break;
                 }
+            };
+            let trimmed = input.trim();
+            if trimmed.is_empty() {
+                continue;
             }
+
+            debug!("Received: {}", trimmed);
+
+            // Process the request
+            match self.process_request(trimmed).await {
+                Ok(Some(response)) => {
+                    write_message(&stdout, &response).await?;
+                }
+                Ok(None) => {
+                    debug!("Notification received, no response sent");
+                }
+                Err(e) => {
+                    error!("Error processing request: {}", e);
+
+                    // Send error response
+                    let error_response =
+                        JsonRpcResponse::error(None, JsonRpcError::internal_error(e.to_string()));
+
+                    write_message(&stdout, &error_response).await?;
+                }
+            }
+        }
+
+        // Watches and webhooks may still have notifications for a client
+        // that only closed its end of stdin
+        if config.stdin_keepalive_secs > 0 {
+            info!(
+                "Keeping notifications alive for {}s after EOF",
+                config.stdin_keepalive_secs
+            );
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(config.stdin_keepalive_secs)) => {}
+                _ = tokio::signal::ctrl_c() => {}
+            }
+        }
+
+        if let Some(task) = webhook {
+            task.abort();
+        }
+        if let Some(task) = reloader {
+            task.abort();
+        }
+        if let Some(task) = forwarder {
+            task.abort();
+        }
+        if let Some(task) = stats_logger {
+            task.abort();
         }
 
+        self.live().await.handler.stats().log_summary();
+
         info!("MCP server shutting down");
         Ok(())
     }
 
-    async fn process_request(&self, input: &str) -> Result<Option<JsonRpcResponse>> {
-        // Parse JSON-RPC request
-        let request: JsonRpcRequest = match serde_json::from_str(input) {
-            Ok(req) => req,
+    /// Answers one line: a request, a notification or a JSON-RPC batch.
+    /// Batch members run in order, so a batched `initialize` takes effect
+    /// before the `tools/list` after it.
+    async fn process_request(&self, input: &str) -> Result<Option<JsonRpcReply>> {
+        let message: Value = match serde_json::from_str(input) {
+            Ok(message) => message,
             Err(e) => {
                 warn!("Failed to parse request: {}", e);
-                return Ok(Some(JsonRpcResponse::error(
+                return Ok(Some(JsonRpcReply::Single(JsonRpcResponse::error(
                     None,
                     JsonRpcError::parse_error(),
+                ))));
+            }
+        };
+
+        let Value::Array(batch) = message else {
+            return Ok(self
+                .process_message(message)
+                .await?
+                .map(JsonRpcReply::Single));
+        };
+        if batch.is_empty() {
+            return Ok(Some(JsonRpcReply::Single(JsonRpcResponse::error(
+                None,
+                JsonRpcError::invalid_request(),
+            ))));
+        }
+
+        debug!("Processing batch of {} messages", batch.len());
+        let mut responses = Vec::new();
+        for message in batch {
+            let id = message.get("id").cloned();
+            match self.process_message(message).await {
+                Ok(Some(response)) => responses.push(response),
+                Ok(None) => {}
+                Err(e) => {
+                    error!("Error processing batched request: {}", e);
+                    responses.push(JsonRpcResponse::error(
+                        id,
+                        JsonRpcError::internal_error(e.to_string()),
+                    ));
+                }
+            }
+        }
+        // A batch of notifications only is answered with nothing at all
+        Ok((!responses.is_empty()).then_some(JsonRpcReply::Batch(responses)))
+    }
+
+    async fn process_message(&self, message: Value) -> Result<Option<JsonRpcResponse>> {
+        let request: JsonRpcRequest = match serde_json::from_value(message) {
+            Ok(req) => req,
+            Err(e) => {
+                warn!("Invalid request: {}", e);
+                return Ok(Some(JsonRpcResponse::error(
+                    None,
+                    JsonRpcError::invalid_request(),
                 )));
             }
         };
@@ -134,7 +285,14 @@ impl McpServer {
             "tools/list" => self.handle_list_tools(request).await.map(Some),
             "tools/call" => self.handle_call_tool(request).await.map(Some),
             "prompts/list" => self.handle_list_prompts(request).await.map(Some),
+            "prompts/get" => self.handle_get_prompt(request).await.map(Some),
             "resources/list" => self.handle_list_resources(request).await.map(Some),
+            "resources/templates/list" => {
+                self.handle_list_resource_templates(request).await.map(Some)
+            }
+            "resources/read" => self.handle_read_resource(request).await.map(Some),
+            "resources/subscribe" => self.handle_subscribe(request, true).await.map(Some),
+            "resources/unsubscribe" => self.handle_subscribe(request, false).await.map(Some),
             _ => {
                 warn!("Unknown method: {}", request.method);
                 Ok(Some(JsonRpcResponse::error(
@@ -148,28 +306,40 @@ impl McpServer {
     async fn handle_initialize(&self, request: JsonRpcRequest) -> Result<JsonRpcResponse> {
         debug!("Handling initialize request");
 
-        // Parse initialize params (optional for flexibility)
-        let protocol_version = if let Some(params) = request.params {
-            if let Ok(init_req) = serde_json::from_value::<InitializeRequest>(params) {
-                // Support both protocol versions
-                if init_req.protocol_version.starts_with("2025") {
-                    PROTOCOL_VERSION_2025.to_string()
-                } else {
-                    PROTOCOL_VERSION.to_string()
-                }
-            } else {
-                PROTOCOL_VERSION_2025.to_string()
+        // Params are optional for flexibility; without a version we offer our latest
+        let requested = request
+            .params
+            .and_then(|params| serde_json::from_value::<InitializeRequest>(params).ok())
+            .map(|init_req| init_req.protocol_version);
+        let protocol_version = match requested.as_deref().map(negotiate_protocol_version) {
+            None => PROTOCOL_VERSION_2025,
+            Some(Ok(version)) => version,
+            Some(Err(e)) => {
+                warn!("Rejecting initialize: {}", e.message);
+                return Ok(JsonRpcResponse::error(request.id, e));
             }
-        } else {
-            PROTOCOL_VERSION_2025.to_string()
         };
+        *self.protocol_version.write().await = Some(protocol_version);
 
-        // Create initialize result with empty tools object (like mcp-slack)
+        let config = self.live().await.config.clone();
+
+        // Create initialize result with empty tools object (like mcp-slack),
+        // advertising `listChanged` when the config is hot-reloaded
+        let mut tools = HashMap::new();
+        if config.config_reload_secs > 0 {
+            tools.insert("listChanged".to_string(), Value::Bool(true));
+        }
         let result = InitializeResult {
-            protocol_version,
+            protocol_version: protocol_version.to_string(),
             capabilities: ServerCapabilities {
-                tools: HashMap::new(), // Empty tools object
+                tools,
                 experimental: HashMap::new(),
+                // Subscriptions are only useful when webhook events can arrive
+                resources: config
+                    .jira_webhook_listen_addr
+                    .as_ref()
+                    .map(|_| HashMap::from([("subscribe".to_string(), Value::Bool(true))])),
+                prompts: Some(HashMap::new()),
             },
             server_info: ServerInfo {
                 name: "mcp-atlassian".to_string(),
@@ -210,7 +380,12 @@ impl McpServer {
             ));
         }
 
-        let tools = self.handler.list_tools().await;
+        let mut tools = self.live().await.handler.list_tools().await;
+        if !self.structured_content().await {
+            for tool in &mut tools {
+                tool.output_schema = None;
+            }
+        }
         let result = ListToolsResult { tools };
 
         Ok(JsonRpcResponse::success(
@@ -244,35 +419,101 @@ impl McpServer {
 
         debug!("Executing tool: {}", params.name);
 
+        // Long-running tools report progress against the client's token
+        let mut arguments = params.arguments;
+        let progress_token = params
+            .meta
+            .as_ref()
+            .map(|meta| &meta["progressToken"])
+            .filter(|token| !token.is_null());
+        if let (Some(token), Some(args)) = (progress_token, arguments.as_object_mut()) {
+            args.insert(PROGRESS_TOKEN_ARG.to_string(), token.clone());
+        }
+
         // Execute tool
-        match self
+        let live = self.live().await;
+        let mut outcome = live
             .handler
-            .call_tool(&params.name, params.arguments, &self.config)
-            .await
+            .call_tool(&params.name, arguments.clone(), &live.config)
+            .await;
+        // The request that got the 401 changed nothing, so reads and
+        // single-request writes are retried once the credentials on disk
+        // have changed
+        let retryable = live
+            .config
+            .registered_tool_name(&params.name)
+            .is_some_and(|name| is_retryable_after_unauthorized(name, &arguments));
+        if let Err(e) = &outcome
+            && retryable
+            && error_hints::auth_failure(&e.to_string()) == Some(AuthFailure::Unauthorized)
+            && let Some(next) = reload::refresh_credentials(&self.live, &self.notifier, &live).await
         {
-            Ok(result) => Ok(JsonRpcResponse::success(
-                request.id,
-                serde_json::to_value(result)?,
-            )),
-            Err(e) => {
-                error!("Tool execution failed: {}", e);
-                Ok(JsonRpcResponse::error(
+            info!("Retrying {} with the reloaded credentials", params.name);
+            outcome = next
+                .handler
+                .call_tool(&params.name, arguments, &next.config)
+                .await;
+        }
+        match outcome {
+            Ok(mut result) => {
+                if !self.structured_content().await {
+                    result.structured_content = None;
+                }
+                Ok(JsonRpcResponse::success(
                     request.id,
-                    JsonRpcError::internal_error(e.to_string()),
+                    serde_json::to_value(result)?,
                 ))
             }
+            Err(e) => {
+                if let Some(invalid) = e.downcast_ref::<InvalidArgs>() {
+                    warn!("Invalid arguments for {}: {}", params.name, invalid);
+                    return Ok(JsonRpcResponse::error(
+                        request.id,
+                        JsonRpcError::invalid_arguments(invalid),
+                    ));
+                }
+                error!("Tool execution failed: {}", e);
+                let message = e.to_string();
+                let mut error = JsonRpcError::internal_error(error_hints::with_hint(
+                    &message,
+                    live.config.locale,
+                ));
+                error.data = error_hints::auth_failure(&message)
+                    .map(|failure| serde_json::json!({"auth_error": failure.as_str()}));
+                Ok(JsonRpcResponse::error(request.id, error))
+            }
         }
     }
 
     async fn handle_list_prompts(&self, request: JsonRpcRequest) -> Result<JsonRpcResponse> {
         debug!("Handling prompts/list request");
 
-        // We don't have prompts, return empty list
-        let result = serde_json::json!({
-            "prompts": []
-        });
+        Ok(JsonRpcResponse::success(request.id, prompts::list()))
+    }
 
-        Ok(JsonRpcResponse::success(request.id, result))
+    async fn handle_get_prompt(&self, request: JsonRpcRequest) -> Result<JsonRpcResponse> {
+        debug!("Handling prompts/get request");
+
+        let params = request.params.unwrap_or(Value::Null);
+        let Some(name) = params["name"].as_str() else {
+            return Ok(JsonRpcResponse::error(
+                request.id,
+                JsonRpcError::invalid_params("Missing prompt name".to_string()),
+            ));
+        };
+
+        let tool_prefix = self.live().await.config.tool_name_prefix.clone();
+        match prompts::get(
+            name,
+            &params["arguments"],
+            tool_prefix.as_deref().unwrap_or(""),
+        ) {
+            Ok(result) => Ok(JsonRpcResponse::success(request.id, result)),
+            Err(message) => Ok(JsonRpcResponse::error(
+                request.id,
+                JsonRpcError::invalid_params(message),
+            )),
+        }
     }
 
     async fn handle_list_resources(&self, request: JsonRpcRequest) -> Result<JsonRpcResponse> {
@@ -285,4 +526,155 @@ impl McpServer {
 
         Ok(JsonRpcResponse::success(request.id, result))
     }
+
+    async fn handle_list_resource_templates(
+        &self,
+        request: JsonRpcRequest,
+    ) -> Result<JsonRpcResponse> {
+        debug!("Handling resources/templates/list request");
+
+        let result = serde_json::json!({
+            "resourceTemplates": [
+                {
+                    "uriTemplate": "jira://issue/{issueKey}",
+                    "name": "Jira issue",
+                    "mimeType": "application/json"
+                },
+                {
+                    "uriTemplate": "jira://project/{projectKey}",
+                    "name": "Jira project (subscribe only)",
+                    "mimeType": "application/json"
+                }
+            ]
+        });
+
+        Ok(JsonRpcResponse::success(request.id, result))
+    }
+
+    async fn handle_read_resource(&self, request: JsonRpcRequest) -> Result<JsonRpcResponse> {
+        debug!("Handling resources/read request");
+
+        let uri = request
+            .params
+            .as_ref()
+            .and_then(|p| p["uri"].as_str())
+            .map(String::from);
+        let Some(uri) = uri else {
+            return Ok(JsonRpcResponse::error(
+                request.id,
+                JsonRpcError::invalid_params("Missing uri".to_string()),
+            ));
+        };
+        let Some(issue_key) = parse_issue_uri(&uri) else {
+            return Ok(JsonRpcResponse::error(
+                request.id,
+                JsonRpcError::invalid_params(format!("Unsupported resource: {}", uri)),
+            ));
+        };
+
+        let args = serde_json::json!({ "issue_key": issue_key });
+        let config = self.live().await.config.clone();
+        match jira::GetIssueHandler.execute(args, &config).await {
+            Ok(issue) => {
+                let result = serde_json::json!({
+                    "contents": [{
+                        "uri": uri,
+                        "mimeType": "application/json",
+                        "text": serde_json::to_string(&issue)?
+                    }]
+                });
+                Ok(JsonRpcResponse::success(request.id, result))
+            }
+            Err(e) => Ok(JsonRpcResponse::error(
+                request.id,
+                JsonRpcError::internal_error(e.to_string()),
+            )),
+        }
+    }
+
+    async fn handle_subscribe(
+        &self,
+        request: JsonRpcRequest,
+        subscribe: bool,
+    ) -> Result<JsonRpcResponse> {
+        debug!("Handling resources/(un)subscribe request");
+
+        let Some(uri) = request.params.as_ref().and_then(|p| p["uri"].as_str()) else {
+            return Ok(JsonRpcResponse::error(
+                request.id,
+                JsonRpcError::invalid_params("Missing uri".to_string()),
+            ));
+        };
+
+        if subscribe {
+            self.subscriptions.subscribe(uri);
+        } else {
+            self.subscriptions.unsubscribe(uri);
+        }
+        debug!("{} active resource subscriptions", self.subscriptions.len());
+
+        Ok(JsonRpcResponse::success(request.id, serde_json::json!({})))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ConfigBuilder;
+    use serde_json::json;
+
+    fn create_test_config() -> Config {
+        ConfigBuilder::new().build()
+    }
+
+    async fn reply(server: &McpServer, input: Value) -> Option<Value> {
+        let reply = server.process_request(&input.to_string()).await.unwrap();
+        reply.map(|reply| serde_json::to_value(reply).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_batch_runs_in_order_and_skips_notifications() {
+        let server = McpServer::new(create_test_config()).await.unwrap();
+        let batch = json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "initialize",
+                "params": {"protocolVersion": "2025-06-18", "capabilities": {},
+                    "clientInfo": {"name": "test", "version": "1"}}},
+            {"jsonrpc": "2.0", "method": "notifications/initialized"},
+            {"jsonrpc": "2.0", "id": 2, "method": "tools/list"},
+            {"jsonrpc": "2.0", "id": 3, "method": "nope"},
+            42
+        ]);
+        let responses = reply(&server, batch).await.unwrap();
+        let responses = responses.as_array().unwrap();
+        assert_eq!(responses.len(), 4);
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[0]["result"]["protocolVersion"], "2025-06-18");
+        assert_eq!(responses[1]["id"], 2);
+        assert!(responses[1]["result"]["tools"].is_array());
+        assert_eq!(responses[2]["error"]["code"], error_codes::METHOD_NOT_FOUND);
+        assert_eq!(responses[3]["error"]["code"], error_codes::INVALID_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_single_and_degenerate_batches() {
+        let server = McpServer::new(create_test_config()).await.unwrap();
+        let single = reply(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 7, "method": "prompts/list"}),
+        )
+        .await
+        .unwrap();
+        assert_eq!(single["id"], 7);
+        assert!(single.is_object());
+
+        let empty = reply(&server, json!([])).await.unwrap();
+        assert_eq!(empty["error"]["code"], error_codes::INVALID_REQUEST);
+
+        let notifications = json!([{"jsonrpc": "2.0", "method": "notifications/initialized"}]);
+        assert!(reply(&server, notifications).await.is_none());
+
+        let garbage = server.process_request("[{").await.unwrap().unwrap();
+        let garbage = serde_json::to_value(garbage).unwrap();
+        assert_eq!(garbage["error"]["code"], error_codes::PARSE_ERROR);
+    }
 }
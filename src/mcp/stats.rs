@@ -0,0 +1,159 @@
+//! Session statistics
+//!
+//! One stdio session is one process, so the counters live for the whole
+//! process and survive config reloads (the rebuilt `RequestHandler` keeps the
+//! same [`SessionStats`]). They back the `atlassian_session_stats` tool, the
+//! optional periodic summary (`SESSION_STATS_LOG_SECS`) and the summary
+//! logged at shutdown, so operators can see which tools return the most
+//! tokens when tuning field filters.
+//!
+//! "API time" is the time spent inside tool handlers, which is almost all
+//! Atlassian round trips; tokens are estimated at ~4 bytes per token like the
+//! response optimizer's savings.
+
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Hits of in-process caches (cloud IDs, ...), recorded where they are served
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+
+pub fn record_cache_hit() {
+    CACHE_HITS.fetch_add(1, Ordering::Relaxed);
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ToolStats {
+    calls: u64,
+    errors: u64,
+    api_time_ms: u64,
+    bytes_returned: u64,
+}
+
+fn estimated_tokens(bytes: u64) -> u64 {
+    bytes / 4
+}
+
+pub struct SessionStats {
+    started: Instant,
+    cache_hits_at_start: u64,
+    tools: Mutex<BTreeMap<String, ToolStats>>,
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionStats {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            cache_hits_at_start: CACHE_HITS.load(Ordering::Relaxed),
+            tools: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Counts one call; `bytes_returned` is the size of the content sent back
+    pub fn record(&self, tool: &str, api_time: Duration, ok: bool, bytes_returned: usize) {
+        let mut tools = self.tools.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = tools.entry(tool.to_string()).or_default();
+        stats.calls += 1;
+        stats.errors += u64::from(!ok);
+        stats.api_time_ms += api_time.as_millis() as u64;
+        stats.bytes_returned += bytes_returned as u64;
+    }
+
+    /// Totals and per-tool rows, the most token-heavy tools first
+    pub fn snapshot(&self) -> Value {
+        let tools = self.tools.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        let total = tools
+            .values()
+            .fold(ToolStats::default(), |total, stats| ToolStats {
+                calls: total.calls + stats.calls,
+                errors: total.errors + stats.errors,
+                api_time_ms: total.api_time_ms + stats.api_time_ms,
+                bytes_returned: total.bytes_returned + stats.bytes_returned,
+            });
+
+        let mut by_tool: Vec<(String, ToolStats)> = tools.into_iter().collect();
+        by_tool.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.bytes_returned));
+        let by_tool: Vec<Value> = by_tool
+            .into_iter()
+            .map(|(tool, stats)| {
+                json!({
+                    "tool": tool,
+                    "calls": stats.calls,
+                    "errors": stats.errors,
+                    "api_time_ms": stats.api_time_ms,
+                    "avg_time_ms": stats.api_time_ms / stats.calls.max(1),
+                    "estimated_tokens": estimated_tokens(stats.bytes_returned)
+                })
+            })
+            .collect();
+
+        json!({
+            "uptime_secs": self.started.elapsed().as_secs(),
+            "tool_calls": total.calls,
+            "errors": total.errors,
+            "api_time_ms": total.api_time_ms,
+            "cache_hits": CACHE_HITS.load(Ordering::Relaxed) - self.cache_hits_at_start,
+            "estimated_tokens_returned": estimated_tokens(total.bytes_returned),
+            "by_tool": by_tool
+        })
+    }
+
+    /// One `info` line with the totals and the heaviest tools
+    pub fn log_summary(&self) {
+        let snapshot = self.snapshot();
+        let heaviest: Vec<String> = snapshot["by_tool"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .take(3)
+            .map(|row| {
+                format!(
+                    "{}={}",
+                    row["tool"].as_str().unwrap_or_default(),
+                    row["estimated_tokens"]
+                )
+            })
+            .collect();
+        info!(
+            tool_calls = snapshot["tool_calls"].as_u64(),
+            errors = snapshot["errors"].as_u64(),
+            api_time_ms = snapshot["api_time_ms"].as_u64(),
+            cache_hits = snapshot["cache_hits"].as_u64(),
+            estimated_tokens_returned = snapshot["estimated_tokens_returned"].as_u64(),
+            heaviest_tools = %heaviest.join(", "),
+            "Session statistics"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_totals_and_orders_by_tokens() {
+        let stats = SessionStats::new();
+        stats.record("jira_get_issue", Duration::from_millis(100), true, 400);
+        stats.record("jira_search", Duration::from_millis(300), true, 8000);
+        stats.record("jira_search", Duration::from_millis(100), false, 40);
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot["tool_calls"], 3);
+        assert_eq!(snapshot["errors"], 1);
+        assert_eq!(snapshot["api_time_ms"], 500);
+        assert_eq!(snapshot["estimated_tokens_returned"], 2110);
+        assert_eq!(snapshot["by_tool"][0]["tool"], "jira_search");
+        assert_eq!(snapshot["by_tool"][0]["calls"], 2);
+        assert_eq!(snapshot["by_tool"][0]["avg_time_ms"], 200);
+        assert_eq!(snapshot["by_tool"][1]["estimated_tokens"], 100);
+    }
+}
@@ -0,0 +1,133 @@
+//! Newline-delimited frames from stdin
+//!
+//! `read_line` into a `String` grows without bound on a message with no
+//! newline and fails the whole read on one invalid UTF-8 byte. [`FrameReader`]
+//! accumulates partial reads itself, stops buffering past `max_bytes` (the
+//! rest of that line is skipped, not kept), and hands undecodable lines back
+//! as [`Frame::InvalidUtf8`] so the server can answer them and keep reading.
+
+use std::io;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+/// Default `MCP_MAX_MESSAGE_BYTES`
+pub const DEFAULT_MAX_MESSAGE_BYTES: usize = 16 * 1024 * 1024;
+
+/// One line from the client, without its line terminator
+#[derive(Debug, PartialEq, Eq)]
+pub enum Frame {
+    Line(String),
+    /// A line longer than the limit; `bytes` is its full length
+    Oversized {
+        bytes: usize,
+    },
+    InvalidUtf8,
+    /// The client closed stdin
+    Eof,
+}
+
+pub struct FrameReader<R> {
+    reader: R,
+    max_bytes: usize,
+    buffer: Vec<u8>,
+}
+
+impl<R: AsyncBufRead + Unpin> FrameReader<R> {
+    pub fn new(reader: R, max_bytes: usize) -> Self {
+        Self {
+            reader,
+            max_bytes,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Reads up to the next newline. A last line without one still counts;
+    /// `Eof` only comes once nothing is left.
+    pub async fn next_frame(&mut self) -> io::Result<Frame> {
+        self.buffer.clear();
+        let mut length = 0;
+
+        loop {
+            let available = self.reader.fill_buf().await?;
+            if available.is_empty() {
+                if length == 0 {
+                    return Ok(Frame::Eof);
+                }
+                return Ok(self.finish(length));
+            }
+
+            let (chunk, consumed, complete) = match available.iter().position(|&b| b == b'\n') {
+                Some(end) => (&available[..end], end + 1, true),
+                None => (available, available.len(), false),
+            };
+            length += chunk.len();
+            if length <= self.max_bytes {
+                self.buffer.extend_from_slice(chunk);
+            } else {
+                // Skip the rest of this line rather than buffering it
+                self.buffer = Vec::new();
+            }
+            self.reader.consume(consumed);
+
+            if complete {
+                return Ok(self.finish(length));
+            }
+        }
+    }
+
+    fn finish(&mut self, length: usize) -> Frame {
+        if length > self.max_bytes {
+            return Frame::Oversized { bytes: length };
+        }
+        let bytes = std::mem::take(&mut self.buffer);
+        match String::from_utf8(bytes) {
+            Ok(line) => Frame::Line(line),
+            Err(_) => Frame::InvalidUtf8,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::BufReader;
+
+    async fn frames(input: &[u8], max_bytes: usize) -> Vec<Frame> {
+        // A tiny buffer forces lines to arrive over several reads
+        let mut reader = FrameReader::new(BufReader::with_capacity(3, input), max_bytes);
+        let mut frames = Vec::new();
+        loop {
+            let frame = reader.next_frame().await.unwrap();
+            let eof = frame == Frame::Eof;
+            frames.push(frame);
+            if eof {
+                return frames;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lines_across_partial_reads() {
+        assert_eq!(
+            frames(b"{\"id\":1}\r\n\n{\"id\":2}", 64).await,
+            vec![
+                Frame::Line("{\"id\":1}\r".to_string()),
+                Frame::Line(String::new()),
+                Frame::Line("{\"id\":2}".to_string()),
+                Frame::Eof
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oversized_and_invalid_lines_do_not_stop_reading() {
+        assert_eq!(
+            frames(b"0123456789abcdef\n\xff\xfe\nok\n", 8).await,
+            vec![
+                Frame::Oversized { bytes: 16 },
+                Frame::InvalidUtf8,
+                Frame::Line("ok".to_string()),
+                Frame::Eof
+            ]
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Resource subscriptions (`resources/subscribe` / `resources/unsubscribe`)
+//!
+//! Jira resources are addressed as `jira://issue/{KEY}` and `jira://project/{KEY}`.
+//! A project subscription receives updates for every issue in that project.
+
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+pub const ISSUE_URI_PREFIX: &str = "jira://issue/";
+pub const PROJECT_URI_PREFIX: &str = "jira://project/";
+
+/// Builds the resource URI for an issue
+pub fn issue_uri(issue_key: &str) -> String {
+    format!("{}{}", ISSUE_URI_PREFIX, issue_key)
+}
+
+/// Builds the resource URI for a project
+pub fn project_uri(project_key: &str) -> String {
+    format!("{}{}", PROJECT_URI_PREFIX, project_key)
+}
+
+/// Extracts the issue key from a `jira://issue/{KEY}` URI
+pub fn parse_issue_uri(uri: &str) -> Option<&str> {
+    uri.strip_prefix(ISSUE_URI_PREFIX).filter(|k| !k.is_empty())
+}
+
+/// Set of resource URIs the client has subscribed to
+#[derive(Debug, Default)]
+pub struct ResourceSubscriptions {
+    uris: RwLock<HashSet<String>>,
+}
+
+impl ResourceSubscriptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if the URI was newly subscribed
+    pub fn subscribe(&self, uri: &str) -> bool {
+        self.uris
+            .write()
+            .map(|mut uris| uris.insert(uri.to_string()))
+            .unwrap_or(false)
+    }
+
+    /// Returns `true` if the URI was subscribed before
+    pub fn unsubscribe(&self, uri: &str) -> bool {
+        self.uris
+            .write()
+            .map(|mut uris| uris.remove(uri))
+            .unwrap_or(false)
+    }
+
+    pub fn is_subscribed(&self, uri: &str) -> bool {
+        self.uris
+            .read()
+            .map(|uris| uris.contains(uri))
+            .unwrap_or(false)
+    }
+
+    /// Filters `candidates` down to the URIs that have subscribers
+    pub fn subscribed_among(&self, candidates: &[String]) -> Vec<String> {
+        candidates
+            .iter()
+            .filter(|uri| self.is_subscribed(uri))
+            .cloned()
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.uris.read().map(|uris| uris.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uri_helpers() {
+        assert_eq!(issue_uri("PROJ-1"), "jira://issue/PROJ-1");
+        assert_eq!(project_uri("PROJ"), "jira://project/PROJ");
+        assert_eq!(parse_issue_uri("jira://issue/PROJ-1"), Some("PROJ-1"));
+        assert_eq!(parse_issue_uri("jira://issue/"), None);
+        assert_eq!(parse_issue_uri("jira://project/PROJ"), None);
+    }
+
+    #[test]
+    fn test_subscribe_and_unsubscribe() {
+        let subs = ResourceSubscriptions::new();
+        assert!(subs.subscribe("jira://issue/PROJ-1"));
+        assert!(!subs.subscribe("jira://issue/PROJ-1")); // Already subscribed
+        assert!(subs.is_subscribed("jira://issue/PROJ-1"));
+        assert_eq!(subs.len(), 1);
+
+        assert!(subs.unsubscribe("jira://issue/PROJ-1"));
+        assert!(!subs.unsubscribe("jira://issue/PROJ-1"));
+        assert!(subs.is_empty());
+    }
+
+    #[test]
+    fn test_subscribed_among() {
+        let subs = ResourceSubscriptions::new();
+        subs.subscribe("jira://project/PROJ");
+
+        let candidates = vec![issue_uri("PROJ-7"), project_uri("PROJ")];
+        assert_eq!(
+            subs.subscribed_among(&candidates),
+            vec![project_uri("PROJ")]
+        );
+    }
+}
@@ -6,6 +6,43 @@ use std::collections::HashMap;
 pub const PROTOCOL_VERSION: &str = "2024-11-05";
 pub const PROTOCOL_VERSION_2025: &str = "2025-06-18";
 
+/// Versions this server speaks, newest first
+pub const SUPPORTED_PROTOCOL_VERSIONS: [&str; 3] =
+    [PROTOCOL_VERSION_2025, "2025-03-26", PROTOCOL_VERSION];
+
+/// Picks the version to answer `initialize` with.
+///
+/// A supported version is echoed back. A newer, well-formed version gets our
+/// latest, which the client may accept or disconnect on. Anything else (older
+/// than we support, or not a `YYYY-MM-DD` version) is rejected with the
+/// spec's `Unsupported protocol version` error listing what we support.
+pub fn negotiate_protocol_version(requested: &str) -> Result<&'static str, JsonRpcError> {
+    if let Some(version) = SUPPORTED_PROTOCOL_VERSIONS
+        .iter()
+        .find(|version| **version == requested)
+    {
+        return Ok(version);
+    }
+    if is_date_version(requested) && requested > PROTOCOL_VERSION_2025 {
+        return Ok(PROTOCOL_VERSION_2025);
+    }
+    Err(JsonRpcError::unsupported_protocol_version(requested))
+}
+
+/// `structuredContent` and `outputSchema` arrived in 2025-06-18
+pub fn supports_structured_content(version: &str) -> bool {
+    version >= PROTOCOL_VERSION_2025
+}
+
+fn is_date_version(version: &str) -> bool {
+    let bytes = version.as_bytes();
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+}
+
 /// JSON-RPC Request
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct JsonRpcRequest {
@@ -26,6 +63,24 @@ pub struct JsonRpcResponse {
     pub id: Option<Value>,
 }
 
+/// What one line from the client is answered with: a response, or an array
+/// of responses for a batch request (notifications in it get none)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum JsonRpcReply {
+    Single(JsonRpcResponse),
+    Batch(Vec<JsonRpcResponse>),
+}
+
+/// JSON-RPC Notification (server-initiated, no id)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JsonRpcNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
 /// JSON-RPC Error
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct JsonRpcError {
@@ -73,6 +128,10 @@ pub struct InitializeResult {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ServerCapabilities {
     pub tools: HashMap<String, Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<HashMap<String, Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompts: Option<HashMap<String, Value>>,
     #[serde(default)]
     pub experimental: HashMap<String, Value>,
 }
@@ -91,6 +150,13 @@ pub struct Tool {
     pub description: String,
     #[serde(rename = "inputSchema")]
     pub input_schema: ToolInputSchema,
+    /// JSON Schema of `structuredContent` (2025-06-18)
+    #[serde(
+        rename = "outputSchema",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub output_schema: Option<Value>,
 }
 
 /// Tool Input Schema
@@ -103,17 +169,150 @@ pub struct ToolInputSchema {
     pub required: Vec<String>,
 }
 
-/// Property Definition
+/// JSON Schema `type`: one type name or a union such as `["string", "object"]`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum SchemaType {
+    One(String),
+    Any(Vec<String>),
+}
+
+impl SchemaType {
+    pub fn names(&self) -> Vec<&str> {
+        match self {
+            SchemaType::One(name) => vec![name.as_str()],
+            SchemaType::Any(names) => names.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+impl From<&str> for SchemaType {
+    fn from(name: &str) -> Self {
+        SchemaType::One(name.to_string())
+    }
+}
+
+impl From<Vec<&str>> for SchemaType {
+    fn from(names: Vec<&str>) -> Self {
+        SchemaType::Any(names.into_iter().map(String::from).collect())
+    }
+}
+
+impl PartialEq<&str> for SchemaType {
+    fn eq(&self, other: &&str) -> bool {
+        matches!(self, SchemaType::One(name) if name == other)
+    }
+}
+
+/// Property Definition: the subset of JSON Schema tool inputs use, nestable
+/// through `items` (arrays) and `properties` (objects)
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Property {
     #[serde(rename = "type")]
-    pub property_type: Value,
+    pub property_type: SchemaType,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<Value>,
     #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
     pub enum_values: Option<Vec<Value>>,
+    /// Schema of every array element
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<Property>>,
+    /// Known keys of an object
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<HashMap<String, Property>>,
+    /// Required keys of an object
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required: Option<Vec<String>>,
+    /// Schema of object values not in `properties` (e.g. custom fields)
+    #[serde(
+        rename = "additionalProperties",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub additional_properties: Option<Box<Property>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
+    #[serde(rename = "minLength", default, skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(rename = "maxLength", default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    #[serde(rename = "minItems", default, skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<usize>,
+    #[serde(rename = "maxItems", default, skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+}
+
+impl Property {
+    pub fn new(property_type: impl Into<SchemaType>) -> Self {
+        Self {
+            property_type: property_type.into(),
+            description: None,
+            default: None,
+            enum_values: None,
+            items: None,
+            properties: None,
+            required: None,
+            additional_properties: None,
+            minimum: None,
+            maximum: None,
+            min_length: None,
+            max_length: None,
+            min_items: None,
+            max_items: None,
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_default(mut self, default: Value) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    pub fn with_enum(mut self, values: Vec<Value>) -> Self {
+        self.enum_values = Some(values);
+        self
+    }
+
+    pub fn with_items(mut self, items: Property) -> Self {
+        self.items = Some(Box::new(items));
+        self
+    }
+
+    pub fn with_properties(mut self, properties: HashMap<String, Property>) -> Self {
+        self.properties = Some(properties);
+        self
+    }
+
+    pub fn with_required(mut self, required: Vec<String>) -> Self {
+        self.required = Some(required);
+        self
+    }
+
+    pub fn with_additional_properties(mut self, schema: Property) -> Self {
+        self.additional_properties = Some(Box::new(schema));
+        self
+    }
+
+    /// Inclusive numeric bounds
+    pub fn with_range(mut self, minimum: Option<f64>, maximum: Option<f64>) -> Self {
+        self.minimum = minimum;
+        self.maximum = maximum;
+        self
+    }
+
+    pub fn with_length(mut self, min_length: Option<usize>, max_length: Option<usize>) -> Self {
+        self.min_length = min_length;
+        self.max_length = max_length;
+        self
+    }
 }
 
 /// List Tools Result
@@ -127,12 +326,25 @@ pub struct ListToolsResult {
 pub struct CallToolRequest {
     pub name: String,
     pub arguments: Value,
+    /// Request metadata; `progressToken` opts in to `notifications/progress`
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
 }
 
 /// Call Tool Result
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CallToolResult {
     pub content: Vec<ToolContent>,
+    /// The result as JSON for 2025-06-18 clients; `content` keeps the text form
+    #[serde(
+        rename = "structuredContent",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub structured_content: Option<Value>,
+    /// Server metadata (e.g., `optimization` stats when `RESPONSE_METRICS` is enabled)
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
 }
 
 /// Tool Content
@@ -171,6 +383,15 @@ impl JsonRpcError {
         }
     }
 
+    /// `invalid_request` for a stdin line over `MCP_MAX_MESSAGE_BYTES`
+    pub fn message_too_large(bytes: usize, max_bytes: usize) -> Self {
+        Self {
+            code: error_codes::INVALID_REQUEST,
+            message: "Message too large".to_string(),
+            data: Some(serde_json::json!({"bytes": bytes, "max_bytes": max_bytes})),
+        }
+    }
+
     pub fn method_not_found(method: &str) -> Self {
         Self {
             code: error_codes::METHOD_NOT_FOUND,
@@ -187,6 +408,33 @@ impl JsonRpcError {
         }
     }
 
+    pub fn unsupported_protocol_version(requested: &str) -> Self {
+        Self {
+            code: error_codes::INVALID_PARAMS,
+            message: "Unsupported protocol version".to_string(),
+            data: Some(serde_json::json!({
+                "supported": SUPPORTED_PROTOCOL_VERSIONS,
+                "requested": requested
+            })),
+        }
+    }
+
+    /// `invalid_params` listing every missing and invalid tool argument
+    pub fn invalid_arguments(args: &crate::tools::args::InvalidArgs) -> Self {
+        Self {
+            code: error_codes::INVALID_PARAMS,
+            message: args.to_string(),
+            data: Some(serde_json::json!({
+                "missing": args.missing,
+                "invalid": args
+                    .invalid
+                    .iter()
+                    .map(|(field, reason)| serde_json::json!({"field": field, "reason": reason}))
+                    .collect::<Vec<_>>()
+            })),
+        }
+    }
+
     pub fn internal_error(message: String) -> Self {
         Self {
             code: error_codes::INTERNAL_ERROR,
@@ -196,6 +444,16 @@ impl JsonRpcError {
     }
 }
 
+impl JsonRpcNotification {
+    pub fn new(method: &str, params: Option<Value>) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params,
+        }
+    }
+}
+
 impl JsonRpcResponse {
     pub fn success(id: Option<Value>, result: Value) -> Self {
         Self {
@@ -229,6 +487,39 @@ mod tests {
         assert_eq!(PROTOCOL_VERSION_2025, "2025-06-18");
     }
 
+    #[test]
+    fn test_invalid_arguments_error() {
+        let args = crate::tools::args::InvalidArgs {
+            missing: vec!["issue_key".to_string()],
+            invalid: vec![("limit".to_string(), "expected u32".to_string())],
+        };
+        let error = JsonRpcError::invalid_arguments(&args);
+        assert_eq!(error.code, error_codes::INVALID_PARAMS);
+        let data = error.data.unwrap();
+        assert_eq!(data["missing"], json!(["issue_key"]));
+        assert_eq!(data["invalid"][0]["field"], "limit");
+    }
+
+    #[test]
+    fn test_negotiate_protocol_version() {
+        for version in SUPPORTED_PROTOCOL_VERSIONS {
+            assert_eq!(negotiate_protocol_version(version).unwrap(), version);
+        }
+        assert_eq!(
+            negotiate_protocol_version("2026-01-01").unwrap(),
+            PROTOCOL_VERSION_2025
+        );
+
+        for requested in ["2024-10-07", "1.0.0", "2025", ""] {
+            let error = negotiate_protocol_version(requested).unwrap_err();
+            assert_eq!(error.code, error_codes::INVALID_PARAMS);
+            assert_eq!(error.message, "Unsupported protocol version");
+            let data = error.data.unwrap();
+            assert_eq!(data["requested"], requested);
+            assert_eq!(data["supported"][0], PROTOCOL_VERSION_2025);
+        }
+    }
+
     #[test]
     fn test_error_codes_constants() {
         assert_eq!(error_codes::PARSE_ERROR, -32700);
@@ -348,6 +639,8 @@ mod tests {
             protocol_version: PROTOCOL_VERSION_2025.to_string(),
             capabilities: ServerCapabilities {
                 tools,
+                resources: None,
+                prompts: None,
                 experimental: HashMap::new(),
             },
             server_info: ServerInfo {
@@ -365,12 +658,7 @@ mod tests {
         let mut properties = HashMap::new();
         properties.insert(
             "query".to_string(),
-            Property {
-                property_type: json!("string"),
-                description: Some("Search query".to_string()),
-                default: None,
-                enum_values: None,
-            },
+            Property::new("string").with_description("Search query"),
         );
 
         let tool = Tool {
@@ -381,6 +669,7 @@ mod tests {
                 properties,
                 required: vec!["query".to_string()],
             },
+            output_schema: None,
         };
 
         assert_eq!(tool.name, "jira_search");
@@ -393,6 +682,7 @@ mod tests {
         let request = CallToolRequest {
             name: "jira_search".to_string(),
             arguments: json!({"jql": "status = Open"}),
+            meta: None,
         };
 
         assert_eq!(request.name, "jira_search");
@@ -438,6 +728,8 @@ mod tests {
                     text: "Result 2".to_string(),
                 },
             ],
+            structured_content: None,
+            meta: None,
         };
 
         assert_eq!(result.content.len(), 2);
@@ -445,14 +737,25 @@ mod tests {
 
     #[test]
     fn test_property_with_enum() {
-        let property = Property {
-            property_type: json!("string"),
-            description: Some("Status field".to_string()),
-            default: None,
-            enum_values: Some(vec![json!("Open"), json!("In Progress"), json!("Closed")]),
-        };
+        let property = Property::new("string")
+            .with_description("Status field")
+            .with_enum(vec![json!("Open"), json!("In Progress"), json!("Closed")]);
 
-        assert_eq!(property.property_type, json!("string"));
+        assert_eq!(property.property_type, "string");
         assert_eq!(property.enum_values.as_ref().unwrap().len(), 3);
     }
+
+    #[test]
+    fn test_notification_serialization_has_no_id() {
+        let notification = JsonRpcNotification::new(
+            "notifications/resources/updated",
+            Some(json!({"uri": "jira://issue/PROJ-1"})),
+        );
+
+        let serialized = serde_json::to_value(&notification).unwrap();
+        assert_eq!(serialized["jsonrpc"], "2.0");
+        assert_eq!(serialized["method"], "notifications/resources/updated");
+        assert_eq!(serialized["params"]["uri"], "jira://issue/PROJ-1");
+        assert!(serialized.get("id").is_none());
+    }
 }
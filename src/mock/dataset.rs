@@ -0,0 +1,1539 @@
+//! In-memory Jira and Confluence data behind `ATLASSIAN_MODE=mock`
+//!
+//! [`Dataset::sample`] holds two Jira projects and two Confluence spaces.
+//! [`Dataset::handle`] answers the REST endpoints the core tools call with
+//! the shapes Atlassian Cloud returns, so the real handlers run unchanged:
+//! issues can be searched, created, updated, commented on and transitioned,
+//! pages searched, created and updated (with version checks). Endpoints the
+//! mock does not model answer 404 naming the endpoint.
+
+use serde_json::{Map, Value, json};
+
+use crate::tools::excerpts;
+use crate::tools::jira::adf_utils;
+use crate::utils::time;
+
+use super::fixtures::{IssueFixture, PageFixture};
+use super::query::{self, Clause};
+
+/// Status code and JSON body of a mock response; `None` for 204
+pub struct Reply {
+    pub status: u16,
+    pub body: Option<Value>,
+}
+
+fn ok(body: Value) -> Reply {
+    Reply {
+        status: 200,
+        body: Some(body),
+    }
+}
+
+fn created(body: Value) -> Reply {
+    Reply {
+        status: 201,
+        body: Some(body),
+    }
+}
+
+fn no_content() -> Reply {
+    Reply {
+        status: 204,
+        body: None,
+    }
+}
+
+/// Jira's error body
+fn jira_error(status: u16, message: &str) -> Reply {
+    Reply {
+        status,
+        body: Some(json!({"errorMessages": [message], "errors": {}})),
+    }
+}
+
+/// Jira's error body for a rejected field
+fn field_error(field: &str, message: &str) -> Reply {
+    Reply {
+        status: 400,
+        body: Some(json!({"errorMessages": [], "errors": {field: message}})),
+    }
+}
+
+/// Confluence v2's error body
+fn confluence_error(status: u16, message: &str) -> Reply {
+    Reply {
+        status,
+        body: Some(json!({"errors": [{"status": status, "title": message}]})),
+    }
+}
+
+/// Transition id and target status of the mock workflow; any status can
+/// move to any other
+const WORKFLOW: &[(&str, &str, &str)] = &[
+    ("11", "To Do", "new"),
+    ("21", "In Progress", "indeterminate"),
+    ("31", "Done", "done"),
+];
+
+const ISSUE_TYPES: &[&str] = &["Task", "Bug", "Story", "Epic", "Subtask"];
+
+/// Default `maxResults` / `limit`
+const PAGE_SIZE: usize = 50;
+
+fn timestamp(unix: i64) -> String {
+    let utc = time::Timezone::parse("UTC").expect("UTC is built in");
+    time::format_timestamp(unix, 0, &utc)
+}
+
+/// Jira's form (`...000+0000`) and Confluence's (`...000Z`) of `days_ago`
+fn jira_time(days_ago: i64) -> String {
+    timestamp(time::now() - days_ago * 86_400)
+}
+
+fn confluence_time(days_ago: i64) -> String {
+    jira_time(days_ago).replace("+0000", "Z")
+}
+
+fn user(account_id: &str, name: &str) -> Value {
+    json!({
+        "accountId": account_id,
+        "displayName": name,
+        "active": true,
+        "accountType": "atlassian"
+    })
+}
+
+/// Plain text of a storage-format body
+fn storage_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                text.push(' ');
+            }
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    excerpts::one_line(&text, usize::MAX)
+}
+
+fn status(name: &str) -> Value {
+    let category = WORKFLOW
+        .iter()
+        .find(|(_, status, _)| *status == name)
+        .map_or("new", |(_, _, category)| category);
+    json!({"name": name, "statusCategory": {"key": category}})
+}
+
+fn lower(values: &[&Value]) -> Vec<String> {
+    values
+        .iter()
+        .filter_map(|v| v.as_str())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn params<'a>(query: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    query
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+fn number_param(query: &[(String, String)], name: &str, default: usize) -> usize {
+    params(query, name)
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+pub struct Dataset {
+    me: Value,
+    users: Vec<Value>,
+    projects: Vec<Value>,
+    issues: Vec<Value>,
+    /// Per comment: the issue key and the comment
+    comments: Vec<(String, Value)>,
+    /// Per changelog entry: the issue key and the history
+    histories: Vec<(String, Value)>,
+    /// Per remote link: the issue key and the link
+    remote_links: Vec<(String, Value)>,
+    spaces: Vec<Value>,
+    pages: Vec<Value>,
+    /// Superseded page versions, for `?version=`
+    page_versions: Vec<Value>,
+    footer_comments: Vec<Value>,
+    inline_comments: Vec<Value>,
+    next_id: u64,
+}
+
+impl Dataset {
+    /// A site with nothing but the authenticated user, `mock-user-1`
+    pub fn empty() -> Self {
+        let me = user("mock-user-1", "Alex Morgan");
+        Self {
+            me: me.clone(),
+            users: vec![me],
+            projects: Vec::new(),
+            issues: Vec::new(),
+            comments: Vec::new(),
+            histories: Vec::new(),
+            remote_links: Vec::new(),
+            spaces: Vec::new(),
+            pages: Vec::new(),
+            page_versions: Vec::new(),
+            footer_comments: Vec::new(),
+            inline_comments: Vec::new(),
+            next_id: 20_000,
+        }
+    }
+
+    /// The built-in sample site: projects DEMO and OPS, spaces ENG and TEAM
+    pub fn sample() -> Self {
+        let me = "mock-user-1";
+        let sam = "mock-user-2";
+        let mut dataset = Self::empty()
+            .with_user(sam, "Sam Rivera")
+            .with_project("DEMO", "Demo Project")
+            .with_project("OPS", "Operations")
+            .with_space("ENG", "Engineering")
+            .with_space("TEAM", "Team Handbook");
+
+        for issue in [
+            IssueFixture::new("DEMO-1", "Set up CI pipeline")
+                .status("Done")
+                .assignee(me)
+                .labels(&["ci"])
+                .updated_days_ago(12),
+            IssueFixture::new("DEMO-2", "Login page returns 500 on invalid email")
+                .issue_type("Bug")
+                .status("In Progress")
+                .assignee(sam)
+                .priority("High")
+                .labels(&["auth"])
+                .updated_days_ago(3),
+            IssueFixture::new("DEMO-3", "Add dark mode to settings")
+                .issue_type("Story")
+                .priority("Low")
+                .updated_days_ago(6),
+            IssueFixture::new("DEMO-4", "Write onboarding guide")
+                .assignee(me)
+                .labels(&["docs"])
+                .updated_days_ago(1),
+            IssueFixture::new("OPS-1", "Rotate database credentials")
+                .status("In Progress")
+                .assignee(me)
+                .priority("High")
+                .labels(&["security"])
+                .description("Rotate the primary and replica credentials, then update the vault.")
+                .updated_days_ago(2),
+            IssueFixture::new("OPS-2", "Investigate slow search queries")
+                .issue_type("Bug")
+                .assignee(sam)
+                .updated_days_ago(8),
+        ] {
+            dataset = dataset.with_issue(issue);
+        }
+        dataset = dataset
+            .with_comment("DEMO-2", sam, "Reproduced with an address missing the @.")
+            .with_comment(
+                "DEMO-2",
+                me,
+                "Validation moves to the form; fix is in review.",
+            );
+        dataset.push_history("DEMO-1", "status", "In Progress", "Done", 12);
+        dataset.push_history("DEMO-2", "status", "To Do", "In Progress", 3);
+
+        for page in [
+            PageFixture::new("ENG", "Engineering Home", "<p>Start here for engineering docs.</p>")
+                .id("2001")
+                .updated_days_ago(30),
+            PageFixture::new(
+                "ENG",
+                "Architecture Overview",
+                "<h2>Services</h2><p>The API gateway routes requests to the search and login services.</p>",
+            )
+            .id("2002")
+            .parent("2001")
+            .updated_days_ago(9),
+            PageFixture::new(
+                "ENG",
+                "Release Process",
+                "<p>Releases ship every Tuesday after the CI pipeline is green.</p>",
+            )
+            .id("2003")
+            .parent("2001")
+            .updated_days_ago(4),
+            PageFixture::new("TEAM", "Team Handbook", "<p>How we work together.</p>")
+                .id("2101")
+                .updated_days_ago(40),
+            PageFixture::new(
+                "TEAM",
+                "Onboarding Checklist",
+                "<ul><li>Get database access</li><li>Read the release process</li></ul>",
+            )
+            .id("2102")
+            .parent("2101")
+            .updated_days_ago(2),
+        ] {
+            dataset = dataset.with_page(page);
+        }
+        dataset.footer_comments.push(json!({
+            "id": "3001",
+            "status": "current",
+            "title": "Re: Release Process",
+            "pageId": "2003",
+            "version": {"number": 1, "createdAt": confluence_time(3), "authorId": sam},
+            "body": {"storage": {"representation": "storage", "value": "<p>Should hotfixes follow this too?</p>"}}
+        }));
+        dataset.footer_comments.push(json!({
+            "id": "3002",
+            "status": "current",
+            "title": "Re: Release Process",
+            "parentCommentId": "3001",
+            "version": {"number": 1, "createdAt": confluence_time(2), "authorId": "mock-user-1"},
+            "body": {"storage": {"representation": "storage", "value": "<p>Yes, with a second reviewer.</p>"}}
+        }));
+        for (id, parent, status, text) in [
+            ("3101", None, "open", "Which pipeline?"),
+            ("3102", Some("3101"), "open", "The release one."),
+            ("3103", None, "resolved", "Typo in Tuesday"),
+        ] {
+            let mut comment = json!({
+                "id": id,
+                "status": "current",
+                "resolutionStatus": status,
+                "properties": {"inlineOriginalSelection": "CI pipeline"},
+                "version": {"number": 1, "createdAt": confluence_time(1), "authorId": sam},
+                "body": {"storage": {"representation": "storage", "value": format!("<p>{}</p>", text)}}
+            });
+            match parent {
+                Some(parent) => comment["parentCommentId"] = json!(parent),
+                None => comment["pageId"] = json!("2003"),
+            }
+            dataset.inline_comments.push(comment);
+        }
+        dataset
+    }
+
+    /// Adds a user others can be assigned or mentioned as
+    pub fn with_user(mut self, account_id: &str, display_name: &str) -> Self {
+        self.users.push(user(account_id, display_name));
+        self
+    }
+
+    pub fn with_project(mut self, key: &str, name: &str) -> Self {
+        let id = self.next_id();
+        self.projects
+            .push(json!({"id": id, "key": key, "name": name}));
+        self
+    }
+
+    pub fn with_space(mut self, key: &str, name: &str) -> Self {
+        let id = self.next_id();
+        self.spaces
+            .push(json!({"id": id, "key": key, "name": name, "type": "global"}));
+        self
+    }
+
+    /// Adds an issue, and its project when the key names a new one
+    pub fn with_issue(mut self, issue: IssueFixture) -> Self {
+        let project_key = issue.key.split('-').next().unwrap_or_default().to_string();
+        if self.project(&project_key).is_none() {
+            self = self.with_project(&project_key, &project_key);
+        }
+        let project = self.project(&project_key).cloned().unwrap_or_default();
+        let assignee = issue
+            .assignee
+            .as_deref()
+            .map_or(Value::Null, |id| self.user(id));
+        let description = issue
+            .description
+            .unwrap_or_else(|| format!("{}. Sample issue of the mock site.", issue.summary));
+        let id = self.next_id();
+        self.issues.push(json!({
+            "id": id,
+            "key": issue.key,
+            "fields": {
+                "summary": issue.summary,
+                "description": adf_utils::text_to_adf(&description),
+                "issuetype": {"name": issue.issue_type, "subtask": false},
+                "status": status(&issue.status),
+                "priority": {"name": issue.priority},
+                "assignee": assignee,
+                "reporter": self.me,
+                "labels": issue.labels,
+                "project": project,
+                "created": jira_time(issue.updated_days_ago + 7),
+                "updated": jira_time(issue.updated_days_ago)
+            }
+        }));
+        self
+    }
+
+    /// Adds a page, and its space when the key names a new one
+    pub fn with_page(mut self, page: PageFixture) -> Self {
+        if self.space(&page.space_key).is_none() {
+            self = self.with_space(&page.space_key, &page.space_key);
+        }
+        let space_id = self.space(&page.space_key).map(|s| s["id"].clone());
+        let id = page.id.unwrap_or_else(|| self.next_id());
+        self.pages.push(json!({
+            "id": id,
+            "status": "current",
+            "title": page.title,
+            "spaceId": space_id,
+            "parentId": page.parent_id,
+            "parentType": page.parent_id.as_ref().map(|_| "page"),
+            "authorId": self.me["accountId"],
+            "createdAt": confluence_time(page.updated_days_ago + 30),
+            "version": {"number": 1, "createdAt": confluence_time(page.updated_days_ago), "authorId": self.me["accountId"], "message": ""},
+            "body": {"storage": {"representation": "storage", "value": page.body}}
+        }));
+        self
+    }
+
+    /// Adds a plain-text comment by `account_id`, dated now
+    pub fn with_comment(mut self, issue_key: &str, account_id: &str, text: &str) -> Self {
+        let id = self.next_id();
+        let comment = json!({
+            "id": id,
+            "author": self.user(account_id),
+            "body": adf_utils::text_to_adf(text),
+            "created": jira_time(0),
+            "updated": jira_time(0)
+        });
+        self.comments.push((issue_key.to_string(), comment));
+        self
+    }
+
+    fn next_id(&mut self) -> String {
+        self.next_id += 1;
+        self.next_id.to_string()
+    }
+
+    /// A known user, or one named after an unknown account ID
+    fn user(&self, account_id: &str) -> Value {
+        self.users
+            .iter()
+            .find(|u| u["accountId"] == account_id)
+            .cloned()
+            .unwrap_or_else(|| user(account_id, account_id))
+    }
+
+    fn push_history(&mut self, key: &str, field: &str, from: &str, to: &str, days_ago: i64) {
+        let id = self.next_id();
+        let history = json!({
+            "id": id,
+            "author": self.me,
+            "created": jira_time(days_ago),
+            "items": [{"field": field, "fromString": from, "toString": to}]
+        });
+        self.histories.push((key.to_string(), history));
+    }
+
+    fn project(&self, key: &str) -> Option<&Value> {
+        self.projects.iter().find(|p| {
+            p["key"]
+                .as_str()
+                .is_some_and(|k| k.eq_ignore_ascii_case(key))
+        })
+    }
+
+    fn issue_index(&self, key_or_id: &str) -> Option<usize> {
+        self.issues.iter().position(|issue| {
+            issue["id"] == key_or_id
+                || issue["key"]
+                    .as_str()
+                    .is_some_and(|k| k.eq_ignore_ascii_case(key_or_id))
+        })
+    }
+
+    fn space(&self, key_or_id: &str) -> Option<&Value> {
+        self.spaces
+            .iter()
+            .find(|s| s["id"] == key_or_id || s["key"] == key_or_id)
+    }
+
+    fn page_index(&self, id: &str) -> Option<usize> {
+        self.pages.iter().position(|page| page["id"] == id)
+    }
+
+    /// Answers one REST call; `path` excludes the query string
+    pub fn handle(
+        &mut self,
+        method: &str,
+        path: &str,
+        query: &[(String, String)],
+        body: &Value,
+    ) -> Reply {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match (method, segments.as_slice()) {
+            ("GET", ["rest", "api", "3", "myself"]) => ok(self.me.clone()),
+            ("GET", ["rest", "api", "3", "mypermissions"]) => self.permissions(query),
+            ("GET", ["rest", "api", "3", "user", "search"]) => self.find_users(query),
+            ("GET", ["rest", "api", "3", "project", "search"]) => self.search_projects(query),
+            ("GET", ["rest", "api", "3", "status"]) => ok(json!(
+                WORKFLOW
+                    .iter()
+                    .map(|(_, name, _)| status(name))
+                    .collect::<Vec<_>>()
+            )),
+            ("GET", ["rest", "api", "3", "search", "jql"]) => self.search_issues(query),
+            ("POST", ["rest", "api", "3", "issue"]) => self.create_issue(body),
+            ("POST", ["rest", "api", "3", "issue", "bulk"]) => self.create_issues(body),
+            ("GET", ["rest", "api", "3", "issue", key]) => self.get_issue(key, query),
+            ("PUT", ["rest", "api", "3", "issue", key]) => self.update_issue(key, body),
+            ("GET", ["rest", "api", "3", "issue", key, "comment"]) => {
+                self.list_comments(key, query)
+            }
+            ("POST", ["rest", "api", "3", "issue", key, "comment"]) => self.add_comment(key, body),
+            ("GET", ["rest", "api", "3", "issue", key, "comment", id]) => self.get_comment(key, id),
+            ("PUT", ["rest", "api", "3", "issue", key, "comment", id]) => {
+                self.update_comment(key, id, body)
+            }
+            ("GET", ["rest", "api", "3", "issue", key, "changelog"]) => {
+                self.list_changelog(key, query)
+            }
+            ("GET", ["rest", "api", "3", "issue", key, "remotelink"]) => {
+                self.list_remote_links(key)
+            }
+            ("POST", ["rest", "api", "3", "issue", key, "remotelink"]) => {
+                self.add_remote_link(key, body)
+            }
+            ("GET", ["rest", "api", "3", "issue", key, "transitions"]) => self.transitions(key),
+            ("POST", ["rest", "api", "3", "issue", key, "transitions"]) => {
+                self.transition(key, body)
+            }
+            ("GET", ["wiki", "rest", "api", "search"]) => self.search_content(query),
+            ("GET", ["wiki", "api", "v2", "spaces"]) => self.list_spaces(query),
+            ("GET", ["wiki", "api", "v2", "pages"]) => self.list_pages(query),
+            ("POST", ["wiki", "api", "v2", "pages"]) => self.create_page(body),
+            ("GET", ["wiki", "api", "v2", "pages", id]) => self.get_page(id, query),
+            ("PUT", ["wiki", "api", "v2", "pages", id]) => self.update_page(id, body),
+            ("GET", ["wiki", "api", "v2", "pages", id, "children"]) => self.children(id, query),
+            (
+                "GET",
+                [
+                    "wiki",
+                    "api",
+                    "v2",
+                    "pages",
+                    id,
+                    kind @ ("footer-comments" | "inline-comments"),
+                ],
+            ) => self.page_comments(id, kind, query),
+            (
+                "GET",
+                [
+                    "wiki",
+                    "api",
+                    "v2",
+                    kind @ ("footer-comments" | "inline-comments"),
+                    id,
+                    "children",
+                ],
+            ) => self.comment_replies(id, kind),
+            _ => jira_error(
+                404,
+                &format!("{} {} is not available in mock mode", method, path),
+            ),
+        }
+    }
+
+    fn search_projects(&self, query: &[(String, String)]) -> Reply {
+        let projects: Vec<&Value> = self
+            .projects
+            .iter()
+            .take(number_param(query, "maxResults", PAGE_SIZE))
+            .collect();
+        ok(json!({ "values": projects, "total": self.projects.len() }))
+    }
+
+    fn permissions(&self, query: &[(String, String)]) -> Reply {
+        let held: Map<String, Value> = params(query, "permissions")
+            .unwrap_or_default()
+            .split(',')
+            .filter(|p| !p.is_empty())
+            .map(|p| (p.to_string(), json!({"key": p, "havePermission": true})))
+            .collect();
+        ok(json!({ "permissions": held }))
+    }
+
+    fn find_users(&self, query: &[(String, String)]) -> Reply {
+        let wanted = params(query, "query").unwrap_or_default().to_lowercase();
+        let found: Vec<&Value> = self
+            .users
+            .iter()
+            .filter(|u| {
+                u["displayName"]
+                    .as_str()
+                    .is_some_and(|name| name.to_lowercase().contains(&wanted))
+            })
+            .collect();
+        ok(json!(found))
+    }
+
+    // Jira
+
+    fn issue_comments(&self, key: &str) -> Vec<&Value> {
+        self.comments
+            .iter()
+            .filter(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, comment)| comment)
+            .collect()
+    }
+
+    /// Values of `field` for JQL, lowercase; `None` for unknown fields
+    fn issue_values(&self, issue: &Value, field: &str) -> Option<Vec<String>> {
+        let fields = &issue["fields"];
+        let person = |person: &Value| lower(&[&person["displayName"], &person["accountId"]]);
+        Some(match field {
+            "project" => lower(&[&fields["project"]["key"], &fields["project"]["name"]]),
+            "key" | "issuekey" | "id" => lower(&[&issue["key"], &issue["id"]]),
+            "status" => lower(&[&fields["status"]["name"]]),
+            "statuscategory" => {
+                let key = &fields["status"]["statusCategory"]["key"];
+                let name = match key.as_str() {
+                    Some("done") => "Done",
+                    Some("indeterminate") => "In Progress",
+                    _ => "To Do",
+                };
+                lower(&[key, &json!(name)])
+            }
+            "type" | "issuetype" => lower(&[&fields["issuetype"]["name"]]),
+            "priority" => lower(&[&fields["priority"]["name"]]),
+            "assignee" | "reporter" => person(&fields[field]),
+            "labels" => lower(&fields["labels"].as_array()?.iter().collect::<Vec<_>>()),
+            "summary" => lower(&[&fields["summary"]]),
+            "description" => vec![excerpts::adf_plain_text(&fields["description"]).to_lowercase()],
+            "comment" | "text" => {
+                let mut text = vec![
+                    fields["summary"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_lowercase(),
+                    excerpts::adf_plain_text(&fields["description"]).to_lowercase(),
+                ];
+                text.extend(
+                    self.issue_comments(issue["key"].as_str().unwrap_or_default())
+                        .iter()
+                        .map(|c| excerpts::adf_plain_text(&c["body"]).to_lowercase()),
+                );
+                text
+            }
+            _ => return None,
+        })
+    }
+
+    fn issue_matches(&self, issue: &Value, clause: &Clause) -> bool {
+        // The mock user stands in for currentUser()
+        let mut clause = clause.clone();
+        for value in &mut clause.values {
+            if value.eq_ignore_ascii_case("currentuser()") {
+                *value = self.me["accountId"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+            }
+        }
+        clause.holds(self.issue_values(issue, &clause.field))
+    }
+
+    /// An issue with only the requested fields (`fields=a,b` or `*all`)
+    fn project_fields(&self, issue: &Value, wanted: Option<&str>) -> Value {
+        let mut issue = issue.clone();
+        let key = issue["key"].as_str().unwrap_or_default().to_string();
+        let comments: Vec<Value> = self.issue_comments(&key).into_iter().cloned().collect();
+        issue["fields"]["comment"] = json!({"comments": comments, "total": comments.len()});
+        issue["self"] = json!(format!(
+            "/rest/api/3/issue/{}",
+            issue["id"].as_str().unwrap_or_default()
+        ));
+        let Some(wanted) =
+            wanted.filter(|w| !w.split(',').any(|f| f == "*all" || f == "*navigable"))
+        else {
+            return issue;
+        };
+        let wanted: Vec<&str> = wanted.split(',').map(str::trim).collect();
+        if let Some(fields) = issue["fields"].as_object_mut() {
+            fields.retain(|name, _| wanted.contains(&name.as_str()));
+        }
+        issue
+    }
+
+    fn search_issues(&self, query: &[(String, String)]) -> Reply {
+        let parsed = query::parse(params(query, "jql").unwrap_or_default());
+        let mut found: Vec<&Value> = self
+            .issues
+            .iter()
+            .filter(|issue| parsed.clauses.iter().all(|c| self.issue_matches(issue, c)))
+            .collect();
+
+        // Default order: most recently updated first
+        let order = parsed.order.unwrap_or(query::Order {
+            field: "updated".to_string(),
+            descending: true,
+        });
+        found.sort_by_key(|issue| match order.field.as_str() {
+            "key" | "issuekey" => format!(
+                "{:>12}",
+                issue["key"]
+                    .as_str()
+                    .and_then(|k| k.rsplit('-').next())
+                    .unwrap_or_default()
+            ),
+            field => issue["fields"][field]
+                .as_str()
+                .or_else(|| issue["fields"][field]["name"].as_str())
+                .unwrap_or_default()
+                .to_string(),
+        });
+        if order.descending {
+            found.reverse();
+        }
+
+        let start = number_param(query, "nextPageToken", 0);
+        let size = number_param(query, "maxResults", PAGE_SIZE);
+        let issues: Vec<Value> = found
+            .iter()
+            .skip(start)
+            .take(size)
+            .map(|issue| self.project_fields(issue, params(query, "fields")))
+            .collect();
+        let mut result = json!({ "issues": issues, "isLast": start + size >= found.len() });
+        if start + size < found.len() {
+            result["nextPageToken"] = json!((start + size).to_string());
+        }
+        ok(result)
+    }
+
+    fn get_issue(&self, key: &str, query: &[(String, String)]) -> Reply {
+        let Some(index) = self.issue_index(key) else {
+            return jira_error(
+                404,
+                "Issue does not exist or you do not have permission to see it.",
+            );
+        };
+        let issue = &self.issues[index];
+        let mut data = self.project_fields(issue, params(query, "fields"));
+        if params(query, "expand").is_some_and(|e| e.contains("changelog")) {
+            let histories: Vec<&Value> = self
+                .histories
+                .iter()
+                .filter(|(k, _)| Some(k.as_str()) == issue["key"].as_str())
+                .map(|(_, history)| history)
+                .collect();
+            data["changelog"] = json!({"histories": histories, "total": histories.len()});
+        }
+        ok(data)
+    }
+
+    fn create_issue(&mut self, body: &Value) -> Reply {
+        let fields = &body["fields"];
+        let Some(project) = fields["project"]["key"]
+            .as_str()
+            .and_then(|key| self.project(key))
+            .cloned()
+        else {
+            return field_error("project", "Specify a valid project ID or key");
+        };
+        let summary = fields["summary"].as_str().unwrap_or_default();
+        if summary.trim().is_empty() {
+            return field_error("summary", "You must specify a summary of the issue.");
+        }
+        let Some(kind) = fields["issuetype"]["name"]
+            .as_str()
+            .and_then(|name| ISSUE_TYPES.iter().find(|t| t.eq_ignore_ascii_case(name)))
+        else {
+            return field_error("issuetype", "Specify a valid issue type");
+        };
+
+        let project_key = project["key"].as_str().unwrap_or_default();
+        let number = self
+            .issues
+            .iter()
+            .filter_map(|issue| {
+                issue["key"]
+                    .as_str()?
+                    .strip_prefix(&format!("{}-", project_key))?
+                    .parse::<u64>()
+                    .ok()
+            })
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let key = format!("{}-{}", project_key, number);
+        let id = self.next_id();
+
+        let mut issue_fields = fields.as_object().cloned().unwrap_or_default();
+        issue_fields.insert("project".to_string(), project);
+        issue_fields.insert(
+            "issuetype".to_string(),
+            json!({"name": kind, "subtask": false}),
+        );
+        issue_fields.insert("status".to_string(), status("To Do"));
+        issue_fields.insert("reporter".to_string(), self.me.clone());
+        issue_fields.entry("assignee").or_insert(Value::Null);
+        issue_fields.entry("labels").or_insert(json!([]));
+        issue_fields
+            .entry("priority")
+            .or_insert(json!({"name": "Medium"}));
+        issue_fields.insert("created".to_string(), json!(jira_time(0)));
+        issue_fields.insert("updated".to_string(), json!(jira_time(0)));
+        self.issues
+            .push(json!({"id": id, "key": key, "fields": issue_fields}));
+
+        created(json!({
+            "id": id,
+            "key": key,
+            "self": format!("/rest/api/3/issue/{}", id)
+        }))
+    }
+
+    /// `issueUpdates` one by one: 201 when all were created, else 400 with
+    /// the created `issues` and an `errors` entry per failed element
+    fn create_issues(&mut self, body: &Value) -> Reply {
+        let mut issues = Vec::new();
+        let mut errors = Vec::new();
+        let updates = body["issueUpdates"].as_array().cloned().unwrap_or_default();
+        for (position, update) in updates.iter().enumerate() {
+            let reply = self.create_issue(update);
+            match reply.body {
+                Some(issue) if reply.status == 201 => issues.push(issue),
+                element_errors => errors.push(json!({
+                    "status": reply.status,
+                    "elementErrors": element_errors,
+                    "failedElementNumber": position
+                })),
+            }
+        }
+        Reply {
+            status: if errors.is_empty() { 201 } else { 400 },
+            body: Some(json!({"issues": issues, "errors": errors})),
+        }
+    }
+
+    fn update_issue(&mut self, key: &str, body: &Value) -> Reply {
+        let Some(index) = self.issue_index(key) else {
+            return jira_error(
+                404,
+                "Issue does not exist or you do not have permission to see it.",
+            );
+        };
+        let Some(updates) = body["fields"].as_object() else {
+            return no_content();
+        };
+        let issue_key = self.issues[index]["key"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        for (field, value) in updates {
+            let value = match field.as_str() {
+                "assignee" => match value["accountId"].as_str() {
+                    Some(id) => match self.users.iter().find(|u| u["accountId"] == id) {
+                        Some(user) => user.clone(),
+                        None => {
+                            return field_error(
+                                "assignee",
+                                &format!("User '{}' does not exist.", id),
+                            );
+                        }
+                    },
+                    None => Value::Null,
+                },
+                "status" => {
+                    return field_error(
+                        "status",
+                        "Field 'status' cannot be set. It is not on the appropriate screen, or unknown.",
+                    );
+                }
+                _ => value.clone(),
+            };
+            let old = &self.issues[index]["fields"][field];
+            let describe = |v: &Value| match v {
+                Value::String(text) => text.clone(),
+                Value::Null => String::new(),
+                other => other["name"]
+                    .as_str()
+                    .or_else(|| other["displayName"].as_str())
+                    .map_or_else(|| other.to_string(), String::from),
+            };
+            if field != "description" {
+                let (from, to) = (describe(old), describe(&value));
+                self.push_history(&issue_key, field, &from, &to, 0);
+            }
+            self.issues[index]["fields"][field] = value;
+        }
+        self.issues[index]["fields"]["updated"] = json!(jira_time(0));
+        no_content()
+    }
+
+    fn list_comments(&self, key: &str, query: &[(String, String)]) -> Reply {
+        if self.issue_index(key).is_none() {
+            return jira_error(
+                404,
+                "Issue does not exist or you do not have permission to see it.",
+            );
+        }
+        let mut comments = self.issue_comments(key);
+        if params(query, "orderBy").is_some_and(|o| o.starts_with('-')) {
+            comments.reverse();
+        }
+        let start = number_param(query, "startAt", 0);
+        let size = number_param(query, "maxResults", PAGE_SIZE);
+        ok(json!({
+            "startAt": start,
+            "maxResults": size,
+            "total": comments.len(),
+            "comments": comments.into_iter().skip(start).take(size).collect::<Vec<_>>()
+        }))
+    }
+
+    fn list_changelog(&self, key: &str, query: &[(String, String)]) -> Reply {
+        let Some(index) = self.issue_index(key) else {
+            return jira_error(
+                404,
+                "Issue does not exist or you do not have permission to see it.",
+            );
+        };
+        let issue_key = self.issues[index]["key"].as_str();
+        let histories: Vec<&Value> = self
+            .histories
+            .iter()
+            .filter(|(k, _)| Some(k.as_str()) == issue_key)
+            .map(|(_, history)| history)
+            .collect();
+        let start = number_param(query, "startAt", 0);
+        let size = number_param(query, "maxResults", PAGE_SIZE);
+        ok(json!({
+            "startAt": start,
+            "maxResults": size,
+            "total": histories.len(),
+            "isLast": start + size >= histories.len(),
+            "values": histories.into_iter().skip(start).take(size).collect::<Vec<_>>()
+        }))
+    }
+
+    fn add_comment(&mut self, key: &str, body: &Value) -> Reply {
+        let Some(index) = self.issue_index(key) else {
+            return jira_error(
+                404,
+                "Issue does not exist or you do not have permission to see it.",
+            );
+        };
+        let issue_key = self.issues[index]["key"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let id = self.next_id();
+        let mut comment = json!({
+            "id": id,
+            "author": self.me,
+            "body": body["body"],
+            "created": jira_time(0),
+            "updated": jira_time(0)
+        });
+        if !body["visibility"].is_null() {
+            comment["visibility"] = body["visibility"].clone();
+        }
+        self.comments.push((issue_key, comment.clone()));
+        self.issues[index]["fields"]["updated"] = json!(jira_time(0));
+        created(comment)
+    }
+
+    fn list_remote_links(&self, key: &str) -> Reply {
+        if self.issue_index(key).is_none() {
+            return jira_error(
+                404,
+                "Issue does not exist or you do not have permission to see it.",
+            );
+        }
+        let links: Vec<&Value> = self
+            .remote_links
+            .iter()
+            .filter(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, link)| link)
+            .collect();
+        ok(json!(links))
+    }
+
+    fn add_remote_link(&mut self, key: &str, body: &Value) -> Reply {
+        let Some(index) = self.issue_index(key) else {
+            return jira_error(
+                404,
+                "Issue does not exist or you do not have permission to see it.",
+            );
+        };
+        if body["object"]["url"].as_str().is_none_or(str::is_empty) {
+            return field_error("object", "Link url is required");
+        }
+        let issue_key = self.issues[index]["key"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let id = self.next_id();
+        let link = json!({"id": id.parse::<u64>().unwrap_or_default(), "object": body["object"]});
+        self.remote_links.push((issue_key.clone(), link));
+        created(json!({
+            "id": id.parse::<u64>().unwrap_or_default(),
+            "self": format!("/rest/api/3/issue/{}/remotelink/{}", issue_key, id)
+        }))
+    }
+
+    fn comment_position(&self, key: &str, id: &str) -> Option<usize> {
+        self.comments
+            .iter()
+            .position(|(k, c)| k.eq_ignore_ascii_case(key) && c["id"] == id)
+    }
+
+    fn get_comment(&self, key: &str, id: &str) -> Reply {
+        match self.comment_position(key, id) {
+            Some(position) => ok(self.comments[position].1.clone()),
+            None => jira_error(404, "Can not find a comment for the id"),
+        }
+    }
+
+    fn update_comment(&mut self, key: &str, id: &str, body: &Value) -> Reply {
+        let Some(position) = self.comment_position(key, id) else {
+            return jira_error(404, "Can not find a comment for the id");
+        };
+        let comment = &mut self.comments[position].1;
+        comment["body"] = body["body"].clone();
+        comment["updated"] = json!(jira_time(0));
+        if !body["visibility"].is_null() {
+            comment["visibility"] = body["visibility"].clone();
+        }
+        ok(comment.clone())
+    }
+
+    fn transitions(&self, key: &str) -> Reply {
+        let Some(index) = self.issue_index(key) else {
+            return jira_error(
+                404,
+                "Issue does not exist or you do not have permission to see it.",
+            );
+        };
+        let current = &self.issues[index]["fields"]["status"]["name"];
+        let transitions: Vec<Value> = WORKFLOW
+            .iter()
+            .filter(|(_, name, _)| current != name)
+            .map(|(id, name, _)| json!({"id": id, "name": format!("Move to {}", name), "to": status(name)}))
+            .collect();
+        ok(json!({ "transitions": transitions }))
+    }
+
+    fn transition(&mut self, key: &str, body: &Value) -> Reply {
+        let Some(index) = self.issue_index(key) else {
+            return jira_error(
+                404,
+                "Issue does not exist or you do not have permission to see it.",
+            );
+        };
+        let id = body["transition"]["id"].as_str().unwrap_or_default();
+        let current = self.issues[index]["fields"]["status"]["name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let Some((_, target, _)) = WORKFLOW
+            .iter()
+            .find(|(tid, name, _)| *tid == id && *name != current)
+        else {
+            return jira_error(
+                400,
+                &format!("Transition id '{}' is not valid for this issue.", id),
+            );
+        };
+        let issue_key = self.issues[index]["key"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        self.issues[index]["fields"]["status"] = status(target);
+        self.issues[index]["fields"]["updated"] = json!(jira_time(0));
+        self.push_history(&issue_key, "status", &current, target, 0);
+        no_content()
+    }
+
+    // Confluence
+
+    fn page_space_key(&self, page: &Value) -> &str {
+        page["spaceId"]
+            .as_str()
+            .and_then(|id| self.space(id))
+            .and_then(|space| space["key"].as_str())
+            .unwrap_or_default()
+    }
+
+    fn webui(&self, page: &Value) -> String {
+        format!(
+            "/spaces/{}/pages/{}/{}",
+            self.page_space_key(page),
+            page["id"].as_str().unwrap_or_default(),
+            page["title"].as_str().unwrap_or_default().replace(' ', "+")
+        )
+    }
+
+    fn is_ancestor(&self, page: &Value, ancestor: &str) -> bool {
+        let mut parent = page["parentId"].as_str();
+        while let Some(id) = parent {
+            if id == ancestor {
+                return true;
+            }
+            parent = self
+                .page_index(id)
+                .and_then(|i| self.pages[i]["parentId"].as_str());
+        }
+        false
+    }
+
+    /// Values of `field` for CQL, lowercase; `None` for unknown fields
+    fn page_values(&self, page: &Value, field: &str) -> Option<Vec<String>> {
+        let body = storage_text(
+            page["body"]["storage"]["value"]
+                .as_str()
+                .unwrap_or_default(),
+        );
+        Some(match field {
+            "type" => vec!["page".to_string()],
+            "space" | "space.key" => vec![self.page_space_key(page).to_lowercase()],
+            "space.id" => lower(&[&page["spaceId"]]),
+            "id" | "content" => lower(&[&page["id"]]),
+            "title" => lower(&[&page["title"]]),
+            "text" => vec![
+                page["title"].as_str().unwrap_or_default().to_lowercase(),
+                body.to_lowercase(),
+            ],
+            "parent" => lower(&[&page["parentId"]]),
+            "ancestor" => self
+                .pages
+                .iter()
+                .filter_map(|candidate| candidate["id"].as_str())
+                .filter(|id| self.is_ancestor(page, id))
+                .map(String::from)
+                .collect(),
+            _ => return None,
+        })
+    }
+
+    fn search_content(&self, query: &[(String, String)]) -> Reply {
+        let parsed = query::parse(params(query, "cql").unwrap_or_default());
+        let mut found: Vec<&Value> = self
+            .pages
+            .iter()
+            .filter(|page| {
+                parsed
+                    .clauses
+                    .iter()
+                    .all(|c| c.holds(self.page_values(page, &c.field)))
+            })
+            .collect();
+        found.sort_by_key(|page| std::cmp::Reverse(page["version"]["createdAt"].as_str()));
+
+        let start = number_param(query, "start", 0);
+        let size = number_param(query, "limit", 25);
+        let results: Vec<Value> = found
+            .iter()
+            .skip(start)
+            .take(size)
+            .map(|page| {
+                let url = self.webui(page);
+                let space = page["spaceId"]
+                    .as_str()
+                    .and_then(|id| self.space(id))
+                    .cloned()
+                    .unwrap_or_default();
+                json!({
+                    "content": {
+                        "id": page["id"],
+                        "type": "page",
+                        "status": "current",
+                        "title": page["title"],
+                        "space": {"key": space["key"], "name": space["name"]},
+                        "_links": {"webui": url}
+                    },
+                    "title": page["title"],
+                    "excerpt": excerpts::one_line(
+                        &storage_text(page["body"]["storage"]["value"].as_str().unwrap_or_default()),
+                        excerpts::EXCERPT_CHARS
+                    ),
+                    "url": url,
+                    "resultGlobalContainer": {"title": space["name"]},
+                    "lastModified": page["version"]["createdAt"],
+                    "entityType": "content"
+                })
+            })
+            .collect();
+        ok(json!({
+            "results": results,
+            "start": start,
+            "limit": size,
+            "size": results.len(),
+            "totalSize": found.len()
+        }))
+    }
+
+    fn list_spaces(&self, query: &[(String, String)]) -> Reply {
+        let keys: Vec<&str> = params(query, "keys")
+            .map(|keys| keys.split(',').collect())
+            .unwrap_or_default();
+        let spaces: Vec<&Value> = self
+            .spaces
+            .iter()
+            .filter(|s| keys.is_empty() || keys.iter().any(|k| s["key"] == *k))
+            .take(number_param(query, "limit", PAGE_SIZE))
+            .collect();
+        ok(json!({ "results": spaces }))
+    }
+
+    /// A page as v2 returns it
+    fn page_view(&self, page: &Value) -> Value {
+        let mut page = page.clone();
+        page["_links"] = json!({"webui": self.webui(&page)});
+        page
+    }
+
+    fn list_pages(&self, query: &[(String, String)]) -> Reply {
+        let pages: Vec<Value> = self
+            .pages
+            .iter()
+            .filter(|p| params(query, "space-id").is_none_or(|id| p["spaceId"] == id))
+            .filter(|p| params(query, "title").is_none_or(|title| p["title"] == title))
+            .filter(|p| {
+                params(query, "id").is_none_or(|ids| ids.split(',').any(|id| p["id"] == id))
+            })
+            .take(number_param(query, "limit", 25))
+            .map(|p| self.page_view(p))
+            .collect();
+        ok(json!({ "results": pages }))
+    }
+
+    fn get_page(&self, id: &str, query: &[(String, String)]) -> Reply {
+        let Some(index) = self.page_index(id) else {
+            return confluence_error(404, "Page not found");
+        };
+        let page = &self.pages[index];
+        match params(query, "version").and_then(|v| v.parse::<u64>().ok()) {
+            Some(version) if page["version"]["number"] != version => self
+                .page_versions
+                .iter()
+                .find(|old| old["id"] == id && old["version"]["number"] == version)
+                .map_or_else(
+                    || confluence_error(404, "Version not found"),
+                    |old| ok(self.page_view(old)),
+                ),
+            _ => ok(self.page_view(page)),
+        }
+    }
+
+    fn create_page(&mut self, body: &Value) -> Reply {
+        let space_id = body["spaceId"].as_str().unwrap_or_default();
+        if self.space(space_id).is_none() {
+            return confluence_error(400, "Space not found");
+        }
+        let title = body["title"].as_str().unwrap_or_default();
+        if title.trim().is_empty() {
+            return confluence_error(400, "Title is required");
+        }
+        if self
+            .pages
+            .iter()
+            .any(|p| p["spaceId"] == space_id && p["title"] == title)
+        {
+            return confluence_error(
+                400,
+                "A page with this title already exists: A page already exists with the same TITLE in this space",
+            );
+        }
+        let parent = body["parentId"].as_str();
+        if parent.is_some_and(|id| self.page_index(id).is_none()) {
+            return confluence_error(400, "Parent page not found");
+        }
+
+        let id = self.next_id();
+        let page = json!({
+            "id": id,
+            "status": "current",
+            "title": title,
+            "spaceId": space_id,
+            "parentId": parent,
+            "parentType": parent.map(|_| "page"),
+            "authorId": self.me["accountId"],
+            "createdAt": confluence_time(0),
+            "version": {"number": 1, "createdAt": confluence_time(0), "authorId": self.me["accountId"], "message": ""},
+            "body": {"storage": {"representation": "storage", "value": body["body"]["value"]}}
+        });
+        self.pages.push(page.clone());
+        ok(self.page_view(&page))
+    }
+
+    fn update_page(&mut self, id: &str, body: &Value) -> Reply {
+        let Some(index) = self.page_index(id) else {
+            return confluence_error(404, "Page not found");
+        };
+        let current = self.pages[index]["version"]["number"].as_u64().unwrap_or(1);
+        if body["version"]["number"].as_u64() != Some(current + 1) {
+            return confluence_error(
+                409,
+                &format!(
+                    "Version must be incremented on update. Current version is: {}",
+                    current
+                ),
+            );
+        }
+        self.page_versions.push(self.pages[index].clone());
+        let page = &mut self.pages[index];
+        if let Some(title) = body["title"].as_str() {
+            page["title"] = json!(title);
+        }
+        page["body"]["storage"]["value"] = body["body"]["value"].clone();
+        page["version"] = json!({
+            "number": current + 1,
+            "createdAt": confluence_time(0),
+            "authorId": self.me["accountId"],
+            "message": body["version"]["message"].as_str().unwrap_or_default()
+        });
+        let page = page.clone();
+        ok(self.page_view(&page))
+    }
+
+    /// Children in pages of `limit`; the cursor is the next offset
+    fn children(&self, id: &str, query: &[(String, String)]) -> Reply {
+        if self.page_index(id).is_none() {
+            return confluence_error(404, "Page not found");
+        }
+        let start = number_param(query, "cursor", 0);
+        let limit = number_param(query, "limit", 25);
+        let all: Vec<&Value> = self.pages.iter().filter(|p| p["parentId"] == id).collect();
+        let children: Vec<Value> = all
+            .iter()
+            .skip(start)
+            .take(limit)
+            .map(|p| {
+                json!({
+                    "id": p["id"],
+                    "status": p["status"],
+                    "title": p["title"],
+                    "spaceId": p["spaceId"]
+                })
+            })
+            .collect();
+        let mut body = json!({ "results": children, "_links": {} });
+        if start + limit < all.len() {
+            body["_links"]["next"] = json!(format!(
+                "/wiki/api/v2/pages/{}/children?limit={}&cursor={}",
+                id,
+                limit,
+                start + limit
+            ));
+        }
+        ok(body)
+    }
+
+    fn comment_list(&self, kind: &str) -> &[Value] {
+        if kind == "inline-comments" {
+            &self.inline_comments
+        } else {
+            &self.footer_comments
+        }
+    }
+
+    /// Top-level comments; `resolution-status` filters inline ones
+    fn page_comments(&self, id: &str, kind: &str, query: &[(String, String)]) -> Reply {
+        if self.page_index(id).is_none() {
+            return confluence_error(404, "Page not found");
+        }
+        let statuses: Vec<&str> = params(query, "resolution-status")
+            .map(|statuses| statuses.split(',').collect())
+            .unwrap_or_default();
+        let comments: Vec<&Value> = self
+            .comment_list(kind)
+            .iter()
+            .filter(|c| c["pageId"] == id)
+            .filter(|c| {
+                statuses.is_empty()
+                    || statuses
+                        .iter()
+                        .any(|status| c["resolutionStatus"] == *status)
+            })
+            .collect();
+        ok(json!({ "results": comments }))
+    }
+
+    fn comment_replies(&self, id: &str, kind: &str) -> Reply {
+        let list = self.comment_list(kind);
+        if !list.iter().any(|c| c["id"] == id) {
+            return confluence_error(404, "Comment not found");
+        }
+        let replies: Vec<&Value> = list.iter().filter(|c| c["parentCommentId"] == id).collect();
+        ok(json!({ "results": replies }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(dataset: &mut Dataset, path: &str, query: &[(&str, &str)]) -> Reply {
+        let query: Vec<(String, String)> = query
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        dataset.handle("GET", path, &query, &Value::Null)
+    }
+
+    #[test]
+    fn test_jql_search_filters_and_pages() {
+        let mut dataset = Dataset::sample();
+        let reply = get(
+            &mut dataset,
+            "/rest/api/3/search/jql",
+            &[
+                ("jql", "project = DEMO AND status != Done ORDER BY key ASC"),
+                ("maxResults", "2"),
+                ("fields", "summary,status"),
+            ],
+        );
+        let body = reply.body.unwrap();
+        let keys: Vec<&str> = body["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i["key"].as_str().unwrap())
+            .collect();
+        assert_eq!(keys, vec!["DEMO-2", "DEMO-3"]);
+        assert_eq!(body["nextPageToken"], "2");
+        assert!(body["issues"][0]["fields"]["priority"].is_null());
+
+        let reply = get(
+            &mut dataset,
+            "/rest/api/3/search/jql",
+            &[("jql", "assignee = currentUser() AND text ~ credentials")],
+        );
+        assert_eq!(reply.body.unwrap()["issues"][0]["key"], "OPS-1");
+    }
+
+    #[test]
+    fn test_issue_create_update_transition() {
+        let mut dataset = Dataset::sample();
+        let reply = dataset.handle(
+            "POST",
+            "/rest/api/3/issue",
+            &[],
+            &json!({"fields": {"project": {"key": "OPS"}, "summary": "Renew TLS certificate", "issuetype": {"name": "task"}}}),
+        );
+        assert_eq!(reply.status, 201);
+        assert_eq!(reply.body.unwrap()["key"], "OPS-3");
+
+        let reply = dataset.handle(
+            "PUT",
+            "/rest/api/3/issue/OPS-3",
+            &[],
+            &json!({"fields": {"summary": "Renew TLS certificates", "assignee": {"accountId": "mock-user-2"}}}),
+        );
+        assert_eq!(reply.status, 204);
+        let reply = dataset.handle(
+            "POST",
+            "/rest/api/3/issue/OPS-3/transitions",
+            &[],
+            &json!({"transition": {"id": "31"}}),
+        );
+        assert_eq!(reply.status, 204);
+
+        let issue = get(
+            &mut dataset,
+            "/rest/api/3/issue/OPS-3",
+            &[("expand", "changelog")],
+        )
+        .body
+        .unwrap();
+        assert_eq!(issue["fields"]["summary"], "Renew TLS certificates");
+        assert_eq!(issue["fields"]["assignee"]["displayName"], "Sam Rivera");
+        assert_eq!(issue["fields"]["status"]["name"], "Done");
+        assert_eq!(issue["changelog"]["total"], 3);
+
+        let reply = dataset.handle(
+            "POST",
+            "/rest/api/3/issue",
+            &[],
+            &json!({"fields": {"project": {"key": "OPS"}, "summary": "x", "issuetype": {"name": "Incident"}}}),
+        );
+        assert_eq!(reply.status, 400);
+        assert_eq!(
+            reply.body.unwrap()["errors"]["issuetype"],
+            "Specify a valid issue type"
+        );
+    }
+
+    #[test]
+    fn test_page_update_checks_versions() {
+        let mut dataset = Dataset::sample();
+        let update = |version: u64| json!({"id": "2003", "title": "Release Process", "body": {"representation": "storage", "value": "<p>Mondays now.</p>"}, "version": {"number": version}});
+        assert_eq!(
+            dataset
+                .handle("PUT", "/wiki/api/v2/pages/2003", &[], &update(5))
+                .status,
+            409
+        );
+        assert_eq!(
+            dataset
+                .handle("PUT", "/wiki/api/v2/pages/2003", &[], &update(2))
+                .status,
+            200
+        );
+
+        let old = get(&mut dataset, "/wiki/api/v2/pages/2003", &[("version", "1")])
+            .body
+            .unwrap();
+        assert!(
+            old["body"]["storage"]["value"]
+                .as_str()
+                .unwrap()
+                .contains("Tuesday")
+        );
+
+        let found = get(
+            &mut dataset,
+            "/wiki/rest/api/search",
+            &[(
+                "cql",
+                "type = page AND space = \"ENG\" AND text ~ \"mondays\"",
+            )],
+        )
+        .body
+        .unwrap();
+        assert_eq!(found["totalSize"], 1);
+        assert_eq!(found["results"][0]["content"]["id"], "2003");
+    }
+
+    #[test]
+    fn test_page_titles_are_unique_per_space() {
+        let mut dataset = Dataset::sample();
+        let space_id = |dataset: &mut Dataset, key: &str| {
+            get(dataset, "/wiki/api/v2/spaces", &[("keys", key)])
+                .body
+                .unwrap()["results"][0]["id"]
+                .clone()
+        };
+        let page = |space: Value| json!({"spaceId": space, "title": "Release Process", "body": {"representation": "storage", "value": "<p>x</p>"}});
+        let eng = space_id(&mut dataset, "ENG");
+        let reply = dataset.handle("POST", "/wiki/api/v2/pages", &[], &page(eng));
+        assert_eq!(reply.status, 400);
+        let team = space_id(&mut dataset, "TEAM");
+        let reply = dataset.handle("POST", "/wiki/api/v2/pages", &[], &page(team));
+        assert_eq!(reply.status, 200);
+    }
+
+    #[test]
+    fn test_unknown_endpoints_are_named() {
+        let mut dataset = Dataset::sample();
+        let reply = get(&mut dataset, "/rest/api/3/dashboard", &[]);
+        assert_eq!(reply.status, 404);
+        assert_eq!(
+            reply.body.unwrap()["errorMessages"][0],
+            "GET /rest/api/3/dashboard is not available in mock mode"
+        );
+    }
+}
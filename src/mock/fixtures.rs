@@ -0,0 +1,116 @@
+//! Issues and pages to seed a [`Dataset`](super::dataset::Dataset) with
+//!
+//! Builders with the mock site's defaults, so a test states only what it
+//! checks: `IssueFixture::new("APP-1", "Crash on start").status("Done")`.
+
+/// A Jira issue; the project is taken from the key
+#[derive(Debug, Clone)]
+pub struct IssueFixture {
+    pub(crate) key: String,
+    pub(crate) summary: String,
+    pub(crate) issue_type: String,
+    pub(crate) status: String,
+    pub(crate) priority: String,
+    /// Account ID
+    pub(crate) assignee: Option<String>,
+    pub(crate) labels: Vec<String>,
+    pub(crate) description: Option<String>,
+    pub(crate) updated_days_ago: i64,
+}
+
+impl IssueFixture {
+    /// An unassigned Medium Task in To Do, updated today
+    pub fn new(key: &str, summary: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            summary: summary.to_string(),
+            issue_type: "Task".to_string(),
+            status: "To Do".to_string(),
+            priority: "Medium".to_string(),
+            assignee: None,
+            labels: Vec::new(),
+            description: None,
+            updated_days_ago: 0,
+        }
+    }
+
+    pub fn issue_type(mut self, issue_type: &str) -> Self {
+        self.issue_type = issue_type.to_string();
+        self
+    }
+
+    /// `To Do`, `In Progress` or `Done`
+    pub fn status(mut self, status: &str) -> Self {
+        self.status = status.to_string();
+        self
+    }
+
+    pub fn priority(mut self, priority: &str) -> Self {
+        self.priority = priority.to_string();
+        self
+    }
+
+    /// Assigns the user with this account ID
+    pub fn assignee(mut self, account_id: &str) -> Self {
+        self.assignee = Some(account_id.to_string());
+        self
+    }
+
+    pub fn labels(mut self, labels: &[&str]) -> Self {
+        self.labels = labels.iter().map(|l| l.to_string()).collect();
+        self
+    }
+
+    /// Plain text, stored as ADF
+    pub fn description(mut self, text: &str) -> Self {
+        self.description = Some(text.to_string());
+        self
+    }
+
+    /// Last update `days` ago (created a week before that)
+    pub fn updated_days_ago(mut self, days: i64) -> Self {
+        self.updated_days_ago = days;
+        self
+    }
+}
+
+/// A current Confluence page at version 1
+#[derive(Debug, Clone)]
+pub struct PageFixture {
+    pub(crate) id: Option<String>,
+    pub(crate) space_key: String,
+    pub(crate) title: String,
+    /// Storage format
+    pub(crate) body: String,
+    pub(crate) parent_id: Option<String>,
+    pub(crate) updated_days_ago: i64,
+}
+
+impl PageFixture {
+    /// A space-root page with a generated ID, updated today
+    pub fn new(space_key: &str, title: &str, body: &str) -> Self {
+        Self {
+            id: None,
+            space_key: space_key.to_string(),
+            title: title.to_string(),
+            body: body.to_string(),
+            parent_id: None,
+            updated_days_ago: 0,
+        }
+    }
+
+    pub fn id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    pub fn parent(mut self, page_id: &str) -> Self {
+        self.parent_id = Some(page_id.to_string());
+        self
+    }
+
+    pub fn updated_days_ago(mut self, days: i64) -> Self {
+        self.updated_days_ago = days;
+        self
+    }
+}
@@ -0,0 +1,1051 @@
+//! Mock Atlassian site (`ATLASSIAN_MODE=mock`)
+//!
+//! Lets people try the server without an Atlassian account. A small HTTP
+//! server on a random loopback port answers the Jira and Confluence REST
+//! endpoints from an in-memory [`dataset::Dataset`], and the configuration's
+//! base URL points at it, so every tool runs its real code path. Changes
+//! (created issues, comments, page edits) live until the process exits.
+//! Endpoints outside the core issue and page tools answer 404. With the
+//! `test-support` feature the same server backs `test_support` for
+//! integration tests, seeded through [`fixtures`].
+
+pub mod dataset;
+pub mod fixtures;
+pub mod query;
+
+use anyhow::Result;
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tracing::debug;
+
+use crate::webhook::{HttpRequest, read_http_request};
+
+use self::dataset::{Dataset, Reply};
+
+/// The mock site; stops serving when dropped
+pub struct MockAtlassianServer {
+    addr: SocketAddr,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl MockAtlassianServer {
+    /// Serves the sample dataset on `127.0.0.1:<random port>`
+    pub async fn start() -> Result<Self> {
+        Self::with_dataset(Dataset::sample()).await
+    }
+
+    /// Serves `dataset` on `127.0.0.1:<random port>`
+    pub async fn with_dataset(dataset: Dataset) -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let dataset = Arc::new(Mutex::new(dataset));
+        let task = tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    break;
+                };
+                let dataset = dataset.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = handle_connection(stream, &dataset).await {
+                        debug!("Mock connection failed: {}", e);
+                    }
+                });
+            }
+        });
+        Ok(Self { addr, task })
+    }
+
+    /// What `ATLASSIAN_DOMAIN` would be for the mock site
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+}
+
+impl Drop for MockAtlassianServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn answer(request: &HttpRequest, dataset: &Mutex<Dataset>) -> Reply {
+    let query: Vec<(String, String)> =
+        reqwest::Url::parse(&format!("http://localhost{}", request.target))
+            .map(|url| url.query_pairs().into_owned().collect())
+            .unwrap_or_default();
+    let body: Value = serde_json::from_slice(&request.body).unwrap_or(Value::Null);
+    let reply = dataset.lock().unwrap_or_else(|e| e.into_inner()).handle(
+        &request.method,
+        request.path(),
+        &query,
+        &body,
+    );
+    debug!(
+        method = %request.method,
+        path = request.path(),
+        status = reply.status,
+        "Mock request"
+    );
+    reply
+}
+
+async fn handle_connection(mut stream: TcpStream, dataset: &Mutex<Dataset>) -> Result<()> {
+    let reply = match read_http_request(&mut stream).await {
+        Ok(request) => answer(&request, dataset),
+        Err(e) => Reply {
+            status: 400,
+            body: Some(serde_json::json!({ "errorMessages": [e.to_string()] })),
+        },
+    };
+
+    let body = reply.body.map(|b| b.to_string()).unwrap_or_default();
+    let reason = reqwest::StatusCode::from_u16(reply.status)
+        .ok()
+        .and_then(|s| s.canonical_reason())
+        .unwrap_or("");
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        reply.status,
+        reason,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, Mode};
+    use crate::mcp::handlers::RequestHandler;
+    use crate::mcp::notifications::Notifier;
+    use crate::mcp::types::ToolContent;
+    use crate::test_support::ConfigBuilder;
+    use serde_json::json;
+
+    fn mock_config(base_url: String) -> Config {
+        ConfigBuilder::new()
+            .base_url(&base_url)
+            .mode(Mode::Mock)
+            .build()
+    }
+
+    async fn call(handler: &RequestHandler, config: &Config, tool: &str, args: Value) -> Value {
+        handler
+            .call_tool(tool, args, config)
+            .await
+            .unwrap()
+            .structured_content
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_tools_run_against_the_mock_site() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let config = Arc::new(mock_config(mock.base_url()));
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+
+        let created = call(
+            &handler,
+            &config,
+            "jira_create_issue",
+            json!({"project_key": "DEMO", "summary": "Try mock mode", "issue_type": "Task"}),
+        )
+        .await;
+        assert_eq!(created["key"], "DEMO-5");
+
+        call(
+            &handler,
+            &config,
+            "jira_transition_issue",
+            json!({"issue_key": "DEMO-5", "transition_id": "21"}),
+        )
+        .await;
+        let found = call(
+            &handler,
+            &config,
+            "jira_search",
+            json!({"jql": "status = \"In Progress\" AND text ~ mock"}),
+        )
+        .await;
+        assert_eq!(found["issues"][0]["key"], "DEMO-5");
+
+        let issue = call(
+            &handler,
+            &config,
+            "jira_get_issue",
+            json!({"issue_key": "DEMO-5"}),
+        )
+        .await;
+        assert_eq!(issue["issue"]["fields"]["status"]["name"], "In Progress");
+
+        let updated = call(
+            &handler,
+            &config,
+            "confluence_update_page",
+            json!({"page_id": "2003", "title": "Release Process", "content": "<p>Mondays now.</p>"}),
+        )
+        .await;
+        assert_eq!(updated["version"], 2);
+        let hits = call(
+            &handler,
+            &config,
+            "confluence_search",
+            json!({"query": "text ~ \"mondays\""}),
+        )
+        .await;
+        assert_eq!(hits["results"][0]["page_id"], "2003");
+        // Hits are completed from v2
+        let content = &hits["results"][0]["content"];
+        assert!(content["version"]["number"].is_u64());
+        assert!(content["body"]["storage"]["value"].is_string());
+        assert_eq!(content["space"]["key"], "ENG");
+    }
+
+    #[tokio::test]
+    async fn test_space_filter_takes_keys_and_ids() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let mut config = mock_config(mock.base_url());
+        let confluence = crate::client::Confluence::new(&config);
+        let eng = confluence.space_id("ENG").await.unwrap();
+        assert_eq!(
+            crate::tools::confluence::spaces::space_id(&confluence, &config, "ENG")
+                .await
+                .unwrap(),
+            eng
+        );
+
+        config.confluence_spaces_filter = vec![eng.clone(), "NOPE".to_string()];
+        let ids = crate::tools::confluence::spaces::filter_space_ids(
+            &crate::client::Confluence::new(&config),
+            &config,
+        )
+        .await
+        .unwrap();
+        assert_eq!(ids, vec![eng.clone()]);
+
+        // The mock skips `OR`, so the search is scoped by the ID alone
+        config.confluence_spaces_filter = vec![eng];
+        let config = Arc::new(config);
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+        let hits = call(
+            &handler,
+            &config,
+            "confluence_search",
+            json!({"query": "type = page", "limit": 50}),
+        )
+        .await;
+        let results = hits["results"].as_array().unwrap();
+        assert!(!results.is_empty());
+        assert!(
+            results
+                .iter()
+                .all(|hit| hit["content"]["space"]["key"] == "ENG")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_page_children_pages_and_nests() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let config = Arc::new(mock_config(mock.base_url()));
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+        call(
+            &handler,
+            &config,
+            "confluence_create_page",
+            json!({"space_key": "ENG", "title": "Hotfixes", "content": "<p>Rarely.</p>", "parent_id": "2003"}),
+        )
+        .await;
+
+        let first = call(
+            &handler,
+            &config,
+            "confluence_get_page_children",
+            json!({"page_id": "2001", "limit": 1}),
+        )
+        .await;
+        assert_eq!(first["children"][0]["id"], "2002");
+        assert_eq!(first["has_more"], true);
+        let rest = call(
+            &handler,
+            &config,
+            "confluence_get_page_children",
+            json!({"page_id": "2001", "limit": 1, "cursor": first["next_cursor"]}),
+        )
+        .await;
+        assert_eq!(rest["children"][0]["id"], "2003");
+        assert_eq!(rest["has_more"], false);
+
+        let tree = call(
+            &handler,
+            &config,
+            "confluence_get_page_children",
+            json!({"page_id": "2001", "depth": 3}),
+        )
+        .await;
+        assert_eq!(tree["pages"], 3);
+        assert_eq!(tree["truncated"], false);
+        assert_eq!(tree["children"][1]["children"][0]["title"], "Hotfixes");
+        assert!(tree["children"][0].get("children").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_comments_are_threaded_with_inline_filters() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let config = Arc::new(mock_config(mock.base_url()));
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+
+        let footer = call(
+            &handler,
+            &config,
+            "confluence_get_comments",
+            json!({"page_id": "2003"}),
+        )
+        .await;
+        assert_eq!(footer["total"], 2);
+        assert_eq!(footer["comments"][0]["id"], "3001");
+        let reply = &footer["comments"][0]["replies"][0];
+        assert_eq!(
+            (&reply["id"], &reply["parent_id"]),
+            (&json!("3002"), &json!("3001"))
+        );
+        assert!(footer.get("inline_comments").is_none());
+
+        let open = call(
+            &handler,
+            &config,
+            "confluence_get_comments",
+            json!({"page_id": "2003", "include_inline": true, "resolution": "open"}),
+        )
+        .await;
+        let inline = open["inline_comments"].as_array().unwrap();
+        assert_eq!(inline.len(), 1);
+        assert_eq!(inline[0]["id"], "3101");
+        assert_eq!(inline[0]["replies"][0]["id"], "3102");
+
+        let resolved = call(
+            &handler,
+            &config,
+            "confluence_get_comments",
+            json!({"page_id": "2003", "include_inline": true, "resolution": "resolved"}),
+        )
+        .await;
+        assert_eq!(resolved["inline_comments"][0]["id"], "3103");
+        assert_eq!(resolved["inline_total"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_jira_comments_page_newest_first() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let config = Arc::new(mock_config(mock.base_url()));
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+
+        let newest = call(
+            &handler,
+            &config,
+            "jira_get_comments",
+            json!({"issue_key": "DEMO-2", "max_results": 1, "markdown": true}),
+        )
+        .await;
+        assert_eq!(newest["total"], 2);
+        assert!(
+            newest["comments"][0]["body"]
+                .as_str()
+                .unwrap()
+                .starts_with("Validation moves")
+        );
+        assert_eq!(newest["has_more"], true);
+        let older = call(
+            &handler,
+            &config,
+            "jira_get_comments",
+            json!({"issue_key": "DEMO-2", "start_at": newest["next_start_at"], "markdown": true}),
+        )
+        .await;
+        assert!(
+            older["comments"][0]["body"]
+                .as_str()
+                .unwrap()
+                .starts_with("Reproduced")
+        );
+        assert_eq!(older["has_more"], false);
+
+        let recent = call(
+            &handler,
+            &config,
+            "jira_get_comments",
+            json!({"issue_key": "DEMO-2", "since": "1h", "order_by": "created"}),
+        )
+        .await;
+        assert_eq!(recent["returned"], 2);
+        let none = call(
+            &handler,
+            &config,
+            "jira_get_comments",
+            json!({"issue_key": "DEMO-2", "since": "2999-01-01T00:00:00Z"}),
+        )
+        .await;
+        assert_eq!(none["returned"], 0);
+        assert_eq!(none["has_more"], false);
+    }
+
+    #[tokio::test]
+    async fn test_issue_delta_since() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let config = Arc::new(mock_config(mock.base_url()));
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+
+        for summary in [
+            "Login form rejects valid emails",
+            "Signup form rejects valid emails",
+        ] {
+            call(
+                &handler,
+                &config,
+                "jira_update_issue",
+                json!({"issue_key": "DEMO-2", "fields": {"summary": summary}}),
+            )
+            .await;
+        }
+        let delta = call(
+            &handler,
+            &config,
+            "jira_issue_delta",
+            json!({"issue_key": "DEMO-2", "since": "1h", "markdown": true}),
+        )
+        .await;
+        assert_eq!(delta["changed"], true);
+        assert_eq!(delta["comments"].as_array().unwrap().len(), 2);
+        assert!(
+            delta["comments"][0]["body"]
+                .as_str()
+                .unwrap()
+                .starts_with("Reproduced")
+        );
+        // The 3-day-old status change is before `since`
+        assert_eq!(delta["changelog"].as_array().unwrap().len(), 2);
+        let field_changes = delta["field_changes"].as_array().unwrap();
+        assert_eq!(field_changes.len(), 1);
+        assert_eq!(field_changes[0]["field"], "summary");
+        assert_eq!(field_changes[0]["to"], "Signup form rejects valid emails");
+        assert_eq!(field_changes[0]["changes"], 2);
+
+        let quiet = call(
+            &handler,
+            &config,
+            "jira_issue_delta",
+            json!({"issue_key": "DEMO-2", "since": "2999-01-01T00:00:00Z"}),
+        )
+        .await;
+        assert_eq!(quiet["changed"], false);
+        assert_eq!(quiet["field_changes"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_standup_digest_sections() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let config = Arc::new(mock_config(mock.base_url()));
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+
+        // The mock ignores the date conditions, so sections follow status alone
+        let digest = call(
+            &handler,
+            &config,
+            "jira_standup_digest",
+            json!({"project": "DEMO", "since": "7d", "format": "json"}),
+        )
+        .await;
+        assert_eq!(digest["scope"], json!(["DEMO"]));
+        assert_eq!(digest["completed"]["issues"][0]["key"], "DEMO-1");
+        let started = digest["started"]["issues"].as_array().unwrap();
+        assert_eq!(started.len(), 1);
+        assert_eq!(started[0]["key"], "DEMO-2");
+        assert!(
+            digest["overdue"]["issues"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .all(|issue| issue["key"] != "DEMO-1")
+        );
+
+        let markdown = handler
+            .call_tool(
+                "jira_standup_digest",
+                json!({"project": "DEMO", "max_per_section": 1}),
+                &config,
+            )
+            .await
+            .unwrap();
+        let ToolContent::Text { text } = &markdown.content[0] else {
+            panic!("expected text content");
+        };
+        assert!(text.contains("*Stand-up: DEMO, "));
+        assert!(text.contains("*Completed (1)*"));
+        assert!(text.contains("- DEMO-2 Login page returns 500 on invalid email (@Sam Rivera)"));
+        assert!(text.contains("…and more"));
+    }
+
+    #[tokio::test]
+    async fn test_workload_by_assignee() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let config = Arc::new(mock_config(mock.base_url()));
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+
+        let workload = call(
+            &handler,
+            &config,
+            "jira_workload_by_assignee",
+            json!({"project": "DEMO"}),
+        )
+        .await;
+        // DEMO-1 is done; DEMO-2 (Sam, in progress), DEMO-3 (unassigned), DEMO-4 (me)
+        assert_eq!(workload["totals"]["open_issues"], 3);
+        assert_eq!(workload["totals"]["in_progress"], 1);
+        let by_assignee = workload["by_assignee"].as_array().unwrap();
+        assert_eq!(by_assignee.len(), 3);
+        assert_eq!(by_assignee[2]["assignee"], "Unassigned");
+        assert!(by_assignee[2]["account_id"].is_null());
+        let sam = by_assignee
+            .iter()
+            .find(|entry| entry["assignee"] == "Sam Rivera")
+            .unwrap();
+        assert_eq!(sam["in_progress"], 1);
+        assert_eq!(workload["truncated"], false);
+    }
+
+    #[tokio::test]
+    async fn test_search_schema_names_site_projects_and_statuses() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let mut config = mock_config(mock.base_url());
+        config.jira_schema_examples = true;
+        let handler = RequestHandler::new(Arc::new(config), Notifier::channel().0)
+            .await
+            .unwrap();
+
+        let tools = handler.list_tools().await;
+        let search = tools.iter().find(|t| t.name == "jira_search").unwrap();
+        assert_eq!(
+            search.description,
+            "Search Jira issues using JQL. On this site: projects DEMO, OPS; statuses \"To Do\", \"In Progress\", \"Done\" (e.g., 'project = DEMO AND status = \"In Progress\" ORDER BY updated DESC')"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_undo_last_reverts_updates_transitions_and_page_edits() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let config = Arc::new(mock_config(mock.base_url()));
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+
+        call(
+            &handler,
+            &config,
+            "jira_update_issue",
+            json!({"issue_key": "DEMO-3", "fields": {"summary": "Dark mode everywhere"}}),
+        )
+        .await;
+        call(
+            &handler,
+            &config,
+            "jira_transition_issue",
+            json!({"issue_key": "DEMO-3", "transition_id": "21"}),
+        )
+        .await;
+        call(
+            &handler,
+            &config,
+            "confluence_append_to_page",
+            json!({"page_id": "2003", "content": "<p>Oops</p>"}),
+        )
+        .await;
+
+        let preview = call(
+            &handler,
+            &config,
+            "atlassian_undo_last",
+            json!({"dry_run": true}),
+        )
+        .await;
+        assert_eq!(preview["tool"], "confluence_append_to_page");
+        assert_eq!(preview["change"], "restores page 2003 to version 1");
+        let undone = call(&handler, &config, "atlassian_undo_last", json!({})).await;
+        assert_eq!(undone["reverted"]["version"], 3);
+        let page = call(
+            &handler,
+            &config,
+            "confluence_get_page",
+            json!({"page_id": "2003"}),
+        )
+        .await;
+        assert!(!page.to_string().contains("Oops"));
+
+        let undone = call(&handler, &config, "atlassian_undo_last", json!({})).await;
+        assert_eq!(undone["change"], "moves DEMO-3 back to To Do");
+        let issue = call(
+            &handler,
+            &config,
+            "jira_get_issue",
+            json!({"issue_key": "DEMO-3"}),
+        )
+        .await;
+        assert_eq!(issue["issue"]["fields"]["status"]["name"], "To Do");
+
+        let undone = call(&handler, &config, "atlassian_undo_last", json!({})).await;
+        assert_eq!(undone["change"], "restores summary on DEMO-3");
+        let issue = call(
+            &handler,
+            &config,
+            "jira_get_issue",
+            json!({"issue_key": "DEMO-3"}),
+        )
+        .await;
+        assert_eq!(
+            issue["issue"]["fields"]["summary"],
+            "Add dark mode to settings"
+        );
+
+        let error = handler
+            .call_tool("atlassian_undo_last", json!({}), &config)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Nothing to undo"));
+    }
+
+    #[tokio::test]
+    async fn test_transition_to_done_waits_for_confirm_token() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let mut config = mock_config(mock.base_url());
+        config.confirm_destructive_writes = true;
+        let config = Arc::new(config);
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+        let tools = handler.list_tools().await;
+        let transition = tools
+            .iter()
+            .find(|t| t.name == "jira_transition_issue")
+            .unwrap();
+        assert!(
+            transition
+                .input_schema
+                .properties
+                .contains_key("confirm_token")
+        );
+
+        let args = json!({"issue_key": "DEMO-3", "transition_id": "31"});
+        let held = call(&handler, &config, "jira_transition_issue", args.clone()).await;
+        assert_eq!(held["confirmation_required"], true);
+        assert_eq!(held["change"], "moves DEMO-3 to Done");
+        let status = |issue: Value| issue["issue"]["fields"]["status"]["name"].clone();
+        let issue = call(
+            &handler,
+            &config,
+            "jira_get_issue",
+            json!({"issue_key": "DEMO-3"}),
+        )
+        .await;
+        assert_eq!(status(issue), "To Do");
+
+        let mut confirmed = args.clone();
+        confirmed["confirm_token"] = held["confirm_token"].clone();
+        let done = call(
+            &handler,
+            &config,
+            "jira_transition_issue",
+            confirmed.clone(),
+        )
+        .await;
+        assert_eq!(done["success"], true);
+        let issue = call(
+            &handler,
+            &config,
+            "jira_get_issue",
+            json!({"issue_key": "DEMO-3"}),
+        )
+        .await;
+        assert_eq!(status(issue), "Done");
+        // Tokens are single-use
+        assert!(
+            handler
+                .call_tool("jira_transition_issue", confirmed, &config)
+                .await
+                .is_err()
+        );
+
+        // Other transitions run straight away
+        let reopened = call(
+            &handler,
+            &config,
+            "jira_transition_issue",
+            json!({"issue_key": "DEMO-3", "transition_id": "21"}),
+        )
+        .await;
+        assert_eq!(reopened["success"], true);
+    }
+
+    #[tokio::test]
+    async fn test_writes_are_limited_to_allowed_projects_and_spaces() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let mut config = mock_config(mock.base_url());
+        config.write_allowed_projects = vec!["OPS".to_string()];
+        config.write_allowed_spaces = vec!["ENG".to_string()];
+        let config = Arc::new(config);
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+
+        let denied = handler
+            .call_tool(
+                "jira_add_comment",
+                json!({"issue_key": "DEMO-1", "comment": "Hi"}),
+                &config,
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            denied
+                .to_string()
+                .contains("Project 'DEMO' is not in WRITE_ALLOWED_PROJECTS (OPS)")
+        );
+        let commented = call(
+            &handler,
+            &config,
+            "jira_add_comment",
+            json!({"issue_key": "OPS-1", "comment": "Hi"}),
+        )
+        .await;
+        assert_eq!(commented["success"], true);
+        // Reads are not affected
+        let issue = call(
+            &handler,
+            &config,
+            "jira_get_issue",
+            json!({"issue_key": "DEMO-1"}),
+        )
+        .await;
+        assert_eq!(issue["issue"]["key"], "DEMO-1");
+
+        let denied = handler
+            .call_tool(
+                "confluence_append_to_page",
+                json!({"page_id": "2101", "content": "<p>x</p>"}),
+                &config,
+            )
+            .await
+            .unwrap_err();
+        assert!(denied.to_string().contains("Page 2101: Space"));
+        assert!(denied.to_string().contains("WRITE_ALLOWED_SPACES (ENG)"));
+        let appended = call(
+            &handler,
+            &config,
+            "confluence_append_to_page",
+            json!({"page_id": "2003", "content": "<p>x</p>"}),
+        )
+        .await;
+        assert_eq!(appended["success"], true);
+    }
+
+    #[tokio::test]
+    async fn test_create_incident_links_postmortem() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let mut config = mock_config(mock.base_url());
+        config.incident_project = Some("OPS".to_string());
+        config.incident_postmortem_parent = Some("2001".to_string());
+        let config = Arc::new(config);
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+
+        let incident = call(
+            &handler,
+            &config,
+            "create_incident",
+            json!({
+                "summary": "Checkout errors",
+                "impact": "Some payments fail",
+                "severity": "SEV-2",
+                "issue_type": "Task"
+            }),
+        )
+        .await;
+        assert_eq!(incident["issue_key"], "OPS-3");
+        assert_eq!(incident["postmortem"]["parent_id"], "2001");
+        assert_eq!(
+            incident["postmortem"]["title"],
+            "Postmortem: OPS-3 Checkout errors"
+        );
+        assert!(incident.get("postmortem_error").is_none());
+        assert!(incident.get("link_error").is_none());
+
+        let children = call(
+            &handler,
+            &config,
+            "confluence_get_page_children",
+            json!({"page_id": "2001"}),
+        )
+        .await;
+        assert!(
+            children["children"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|child| child["id"] == incident["postmortem"]["page_id"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_action_item_issues_are_audited_creates() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let audit_path =
+            std::env::temp_dir().join(format!("mcp-action-items-audit-{}", std::process::id()));
+        let mut config = mock_config(mock.base_url());
+        config.audit_log = Some(audit_path.display().to_string());
+        config.audit_log_max_bytes = 1024 * 1024;
+        config.write_allowed_projects = vec!["DEMO".to_string()];
+        let config = Arc::new(config);
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+        let page = call(
+            &handler,
+            &config,
+            "confluence_create_page",
+            json!({
+                "space_key": "ENG",
+                "title": "Retro",
+                "content": "<ac:task-list><ac:task><ac:task-id>1</ac:task-id><ac:task-status>incomplete</ac:task-status><ac:task-body>Fix flaky test</ac:task-body></ac:task></ac:task-list>"
+            }),
+        )
+        .await;
+        let page_id = page["page_id"].as_str().unwrap().to_string();
+
+        let args = |project: &str| json!({"page_id": page_id, "create_issues": true, "project_key": project});
+        let blocked = call(
+            &handler,
+            &config,
+            "confluence_extract_action_items",
+            args("OPS"),
+        )
+        .await;
+        let error = blocked["action_items"][0]["error"].as_str().unwrap();
+        assert!(error.contains("WRITE_ALLOWED_PROJECTS"), "{}", error);
+
+        let created = call(
+            &handler,
+            &config,
+            "confluence_extract_action_items",
+            args("DEMO"),
+        )
+        .await;
+        let item = &created["action_items"][0];
+        assert_eq!(item["issue_key"], "DEMO-5");
+        assert!(item.get("create").is_none());
+
+        let audit = std::fs::read_to_string(&audit_path).unwrap();
+        std::fs::remove_file(&audit_path).ok();
+        let tools: Vec<Value> = audit
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap()["tool"].clone())
+            .collect();
+        assert_eq!(
+            tools,
+            vec![
+                json!("confluence_create_page"),
+                json!("jira_create_issue"),
+                json!("confluence_extract_action_items"),
+                json!("jira_create_issue"),
+                json!("confluence_extract_action_items"),
+            ]
+        );
+
+        let mut read_only = (*config).clone();
+        read_only.read_only = true;
+        let error = handler
+            .call_tool("confluence_extract_action_items", args("DEMO"), &read_only)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("READ_ONLY_MODE"));
+    }
+
+    #[tokio::test]
+    async fn test_meeting_notes_comment_through_jira_add_comment() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let audit_path =
+            std::env::temp_dir().join(format!("mcp-meeting-notes-audit-{}", std::process::id()));
+        let mut config = mock_config(mock.base_url());
+        config.audit_log = Some(audit_path.display().to_string());
+        config.audit_log_max_bytes = 1024 * 1024;
+        config.write_allowed_projects = vec!["DEMO".to_string()];
+        let config = Arc::new(config);
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+
+        let published = call(
+            &handler,
+            &config,
+            "confluence_publish_meeting_notes",
+            json!({
+                "title": "Weekly sync",
+                "notes": "- DEMO-1 is unblocked\n- OPS-1 needs an owner",
+                "parent_id": "2001"
+            }),
+        )
+        .await;
+        assert_eq!(published["commented_issues"], json!(["DEMO-1"]));
+        assert_eq!(published["comment_errors"][0]["issue_key"], "OPS-1");
+        assert!(published.get("comments").is_none());
+
+        let audit = std::fs::read_to_string(&audit_path).unwrap();
+        std::fs::remove_file(&audit_path).ok();
+        let tools: Vec<Value> = audit
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap()["tool"].clone())
+            .collect();
+        assert_eq!(
+            tools,
+            vec![
+                json!("jira_add_comment"),
+                json!("jira_add_comment"),
+                json!("confluence_publish_meeting_notes"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cached_get_responses_expire_on_writes() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let dir = std::env::temp_dir().join(format!("mcp-response-cache-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut config = mock_config(mock.base_url());
+        config.cache_dir = Some(dir.display().to_string());
+        config.cache_ttl_secs = 60;
+        let config = Arc::new(config);
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+        let summary = |issue: Value| issue["issue"]["fields"]["summary"].clone();
+        let get = json!({"issue_key": "DEMO-1"});
+        let before = summary(call(&handler, &config, "jira_get_issue", get.clone()).await);
+
+        // A change made elsewhere shows once the entry expires
+        crate::client::Jira::new(&config)
+            .update_issue(
+                "DEMO-1",
+                serde_json::Map::from_iter([("summary".to_string(), json!("Changed elsewhere"))]),
+            )
+            .await
+            .unwrap();
+        let cached = summary(call(&handler, &config, "jira_get_issue", get.clone()).await);
+        assert_eq!(cached, before);
+
+        // One made through the server shows at once
+        call(
+            &handler,
+            &config,
+            "jira_update_issue",
+            json!({"issue_key": "DEMO-1", "fields": {"summary": "Changed here"}}),
+        )
+        .await;
+        let fresh = summary(call(&handler, &config, "jira_get_issue", get).await);
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(fresh, "Changed here");
+    }
+
+    #[tokio::test]
+    async fn test_page_updates_never_start_from_cached_versions() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let dir = std::env::temp_dir().join(format!("mcp-update-cache-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut config = mock_config(mock.base_url());
+        config.cache_dir = Some(dir.display().to_string());
+        config.cache_ttl_secs = 60;
+        let config = Arc::new(config);
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+
+        // Cache version 1 under the URL an update reads its base from
+        let confluence = crate::client::Confluence::new(&config);
+        let url = format!("{}/wiki/api/v2/pages/2003", mock.base_url());
+        let query = [("include-version", "true"), ("body-format", "storage")];
+        let cached =
+            crate::client::rest::get_json(confluence.http(), &config, &url, &query, "page")
+                .await
+                .unwrap();
+        assert_eq!(cached["version"]["number"], 1);
+        let page = confluence.get_page_storage("2003", None).await.unwrap();
+        confluence
+            .update_page("2003", &page.title, "<p>Saved elsewhere.</p>", 2, &[])
+            .await
+            .unwrap();
+
+        // A stale base would be refused with 409 on every attempt
+        let updated = call(
+            &handler,
+            &config,
+            "confluence_update_page",
+            json!({"page_id": "2003", "title": "Release Process", "content": "<p>Mondays now.</p>"}),
+        )
+        .await;
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(updated["version"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_csv_import_reports_failed_and_partial_imports() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let config = Arc::new(mock_config(mock.base_url()));
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0)
+            .await
+            .unwrap();
+        let import = |csv: &str| json!({"csv": csv, "project_key": "DEMO"});
+
+        let partial = call(
+            &handler,
+            &config,
+            "jira_import_csv",
+            import("Summary,Issue Type\nWrite docs,Task\nShip it,Epic Saga\n"),
+        )
+        .await;
+        assert_eq!(partial["success"], true);
+        assert_eq!(partial["partial"], true);
+        assert_eq!(partial["created"], 1);
+        assert_eq!(partial["results"][0]["key"], "DEMO-5");
+        let error = partial["results"][1]["error"].as_str().unwrap();
+        assert!(error.contains("issuetype"), "{}", error);
+
+        let failed = call(
+            &handler,
+            &config,
+            "jira_import_csv",
+            import("Summary,Issue Type\nShip it,Epic Saga\n"),
+        )
+        .await;
+        assert_eq!(failed["success"], false);
+        assert_eq!(failed["partial"], false);
+        assert_eq!(failed["created"], 0);
+        assert_eq!(failed["failed"], 1);
+    }
+}
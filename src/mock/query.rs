@@ -0,0 +1,300 @@
+//! The JQL/CQL subset the mock understands
+//!
+//! Queries are split into `AND`ed clauses (parentheses are flattened) of the
+//! form `field op value`. A record matches when every clause it can evaluate
+//! holds; clauses on unknown fields, date comparisons and anything with `OR`
+//! are ignored, so a query never fails, it only filters less.
+
+/// A comparison in a query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op {
+    Eq,
+    NotEq,
+    Contains,
+    NotContains,
+    In,
+    NotIn,
+    Is,
+    IsNot,
+    /// `<`, `>=`, ... and clauses that did not parse
+    Ignored,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clause {
+    /// Lowercase field name
+    pub field: String,
+    pub op: Op,
+    /// Unquoted values; one except for `IN`
+    pub values: Vec<String>,
+}
+
+/// The `ORDER BY` part (field, descending), if any
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Order {
+    pub field: String,
+    pub descending: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Query {
+    pub clauses: Vec<Clause>,
+    pub order: Option<Order>,
+}
+
+/// Byte offsets of `word` as a standalone keyword outside quotes and parens
+fn top_level_keyword(text: &str, word: &str) -> Vec<usize> {
+    let lower = text.to_ascii_lowercase();
+    let bytes = lower.as_bytes();
+    let mut depth = 0i32;
+    let mut quote = None;
+    let mut found = Vec::new();
+    for (i, c) in lower.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            (_, Some(_)) => {}
+            ('(', None) => depth += 1,
+            (')', None) => depth -= 1,
+            _ if depth == 0 && lower[i..].starts_with(word) => {
+                let before = i == 0 || bytes[i - 1].is_ascii_whitespace() || bytes[i - 1] == b')';
+                let end = i + word.len();
+                let after =
+                    end == bytes.len() || bytes[end].is_ascii_whitespace() || bytes[end] == b'(';
+                if before && after {
+                    found.push(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    found
+}
+
+/// `(a)` → `a` when the parentheses enclose the whole text
+fn strip_parens(text: &str) -> Option<&str> {
+    let inner = text.strip_prefix('(')?.strip_suffix(')')?;
+    let mut depth = 0;
+    for c in inner.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return None,
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    Some(inner.trim())
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+        .to_string()
+}
+
+/// `("A", B)` → `["A", "B"]`
+fn list_values(text: &str) -> Vec<String> {
+    let inner = strip_parens(text.trim()).unwrap_or(text);
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    for c in inner.chars() {
+        match (c, quote) {
+            ('"' | '\'', None) => {
+                quote = Some(c);
+                current.push(c);
+            }
+            (c, Some(q)) if c == q => {
+                quote = None;
+                current.push(c);
+            }
+            (',', None) => values.push(unquote(&std::mem::take(&mut current))),
+            _ => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        values.push(unquote(&current));
+    }
+    values
+}
+
+fn parse_clause(text: &str) -> Clause {
+    let end = text
+        .find(|c: char| c.is_whitespace() || "=!~<>".contains(c))
+        .unwrap_or(text.len());
+    let field = unquote(&text[..end]).to_lowercase();
+    let rest = text[end..].trim_start();
+    let lower = rest.to_ascii_lowercase();
+
+    // Longest operators first
+    let operators: [(&str, Op); 12] = [
+        ("!=", Op::NotEq),
+        ("!~", Op::NotContains),
+        (">=", Op::Ignored),
+        ("<=", Op::Ignored),
+        ("=", Op::Eq),
+        ("~", Op::Contains),
+        (">", Op::Ignored),
+        ("<", Op::Ignored),
+        ("not in", Op::NotIn),
+        ("in", Op::In),
+        ("is not", Op::IsNot),
+        ("is", Op::Is),
+    ];
+    let Some((operator, op)) = operators
+        .into_iter()
+        .find(|(operator, _)| lower.starts_with(operator))
+    else {
+        return Clause {
+            field,
+            op: Op::Ignored,
+            values: Vec::new(),
+        };
+    };
+    let value = &rest[operator.len()..];
+    let values = match op {
+        Op::In | Op::NotIn => list_values(value),
+        _ => vec![unquote(value)],
+    };
+    Clause { field, op, values }
+}
+
+fn parse_conditions(text: &str, clauses: &mut Vec<Clause>) {
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+    let ands = top_level_keyword(text, "and");
+    if !ands.is_empty() {
+        let mut start = 0;
+        for at in ands {
+            parse_conditions(&text[start..at], clauses);
+            start = at + 3;
+        }
+        parse_conditions(&text[start..], clauses);
+    } else if let Some(inner) = strip_parens(text) {
+        parse_conditions(inner, clauses);
+    } else if !top_level_keyword(text, "or").is_empty() {
+        tracing::debug!("Mock search ignores OR clause: {}", text);
+    } else {
+        clauses.push(parse_clause(text));
+    }
+}
+
+/// Parses a JQL or CQL query
+pub fn parse(query: &str) -> Query {
+    let (conditions, order) = match top_level_keyword(query, "order by").first() {
+        Some(&at) => (&query[..at], Some(&query[at + "order by".len()..])),
+        None => (query, None),
+    };
+    let mut clauses = Vec::new();
+    parse_conditions(conditions, &mut clauses);
+
+    let order = order.and_then(|order| {
+        let first = order.split(',').next()?.trim();
+        let mut words = first.split_whitespace();
+        let field = unquote(words.next()?).to_lowercase();
+        let descending = words.next().is_some_and(|w| w.eq_ignore_ascii_case("desc"));
+        Some(Order { field, descending })
+    });
+    Query { clauses, order }
+}
+
+impl Clause {
+    /// Whether a record whose `field` has `actual` (lowercase) values passes;
+    /// `None` for a field the record does not know
+    pub fn holds(&self, actual: Option<Vec<String>>) -> bool {
+        let Some(actual) = actual else {
+            return true;
+        };
+        let wanted: Vec<String> = self.values.iter().map(|v| v.to_lowercase()).collect();
+        let equals = || actual.iter().any(|a| wanted.contains(a));
+        let contains = || {
+            let text = actual.join(" ");
+            wanted
+                .iter()
+                .flat_map(|value| value.split_whitespace())
+                .map(|word| word.trim_matches('*'))
+                .all(|word| text.contains(word))
+        };
+        let empty =
+            || actual.is_empty() && wanted.first().is_some_and(|v| v == "empty" || v == "null");
+        match self.op {
+            Op::Eq | Op::In => equals(),
+            Op::NotEq | Op::NotIn => !equals(),
+            Op::Contains => contains(),
+            Op::NotContains => !contains(),
+            Op::Is => empty(),
+            Op::IsNot => !empty(),
+            Op::Ignored => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scoped_query() {
+        let query = parse(
+            r#"project IN ("DEMO","OPS") AND (status != Done AND text ~ "login error") AND updated >= -7d ORDER BY created DESC"#,
+        );
+        assert_eq!(
+            query.clauses,
+            vec![
+                Clause {
+                    field: "project".to_string(),
+                    op: Op::In,
+                    values: vec!["DEMO".to_string(), "OPS".to_string()]
+                },
+                Clause {
+                    field: "status".to_string(),
+                    op: Op::NotEq,
+                    values: vec!["Done".to_string()]
+                },
+                Clause {
+                    field: "text".to_string(),
+                    op: Op::Contains,
+                    values: vec!["login error".to_string()]
+                },
+                Clause {
+                    field: "updated".to_string(),
+                    op: Op::Ignored,
+                    values: vec!["-7d".to_string()]
+                },
+            ]
+        );
+        assert_eq!(
+            query.order,
+            Some(Order {
+                field: "created".to_string(),
+                descending: true
+            })
+        );
+    }
+
+    #[test]
+    fn test_or_is_ignored_and_keywords_in_values_are_kept() {
+        let query = parse(r#"summary ~ "bread and butter" AND (status = A OR status = B)"#);
+        assert_eq!(query.clauses.len(), 1);
+        assert_eq!(query.clauses[0].values, vec!["bread and butter"]);
+        assert!(query.order.is_none());
+    }
+
+    #[test]
+    fn test_clause_holds() {
+        let clause = parse("assignee is EMPTY").clauses.remove(0);
+        assert!(clause.holds(Some(Vec::new())));
+        assert!(!clause.holds(Some(vec!["sam".to_string()])));
+
+        let clause = parse("text ~ \"slow search\"").clauses.remove(0);
+        assert!(clause.holds(Some(vec!["investigate slow search queries".to_string()])));
+        assert!(!clause.holds(Some(vec!["slow login".to_string()])));
+        assert!(clause.holds(None));
+    }
+}
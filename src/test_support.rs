@@ -0,0 +1,274 @@
+//! Integration test harness (`test-support` feature)
+//!
+//! For projects embedding this crate: the mock site behind
+//! `ATLASSIAN_MODE=mock`, seeded with your own [`Dataset`] and
+//! [`fixtures`], a [`ConfigBuilder`] that needs no environment variables, and
+//! a [`TestHarness`] tying them to a [`RequestHandler`] so a test calls tools
+//! by name and gets their JSON result. Enable it for tests only:
+//! `mcp-atlassian = { ..., features = ["test-support"] }` under
+//! `[dev-dependencies]`.
+
+use anyhow::Result;
+use serde_json::Value;
+use std::sync::Arc;
+
+pub use crate::config::{Config, Mode};
+pub use crate::mcp::handlers::RequestHandler;
+pub use crate::mock::MockAtlassianServer;
+pub use crate::mock::dataset::Dataset;
+pub use crate::mock::fixtures;
+
+use crate::mcp::notifications::Notifier;
+use crate::mcp::types::ToolContent;
+
+/// Builds a [`Config`] without reading the environment
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl Default for ConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigBuilder {
+    /// Placeholder credentials for `test.atlassian.net`, no filters, and every
+    /// optional feature (webhooks, audit log, proxies, circuit breaker) off
+    pub fn new() -> Self {
+        Self {
+            config: Config {
+                atlassian_domain: "test.atlassian.net".to_string(),
+                atlassian_email: "test@example.com".to_string(),
+                atlassian_api_token: "test-token".to_string(),
+                request_timeout_ms: 30000,
+                jira_projects_filter: vec![],
+                confluence_spaces_filter: vec![],
+                jira_search_default_fields: None,
+                jira_search_custom_fields: vec![],
+                response_exclude_fields: None,
+                response_metrics: false,
+                jira_webhook_listen_addr: None,
+                jira_webhook_public_url: None,
+                jira_webhook_secret: None,
+                bitbucket_workspace: None,
+                bitbucket_username: None,
+                bitbucket_app_password: None,
+                trello_api_key: None,
+                trello_token: None,
+                read_only: false,
+                jira_templates_dir: None,
+                confluence_meeting_notes_parent: None,
+                audit_log: None,
+                audit_log_max_bytes: 0,
+                verify_credentials: false,
+                enabled_tools: vec![],
+                config_file: None,
+                config_reload_secs: 0,
+                max_message_bytes: 1024 * 1024,
+                stdin_keepalive_secs: 0,
+                timezone: None,
+                session_stats_log_secs: 0,
+                circuit_breaker_threshold: 0,
+                circuit_breaker_cooldown_secs: 30,
+                mode: Mode::Live,
+                fixtures_dir: "fixtures".to_string(),
+                cache_ttl_secs: 0,
+                undo_journal_size: 20,
+                confirm_destructive_writes: false,
+                write_allowed_spaces: vec![],
+                write_allowed_projects: vec![],
+                jira_schema_examples: false,
+                tool_schema_verbosity: crate::tools::schema::Verbosity::Standard,
+                locale: crate::mcp::i18n::Locale::En,
+                tool_name_prefix: None,
+                atlassian_org_id: None,
+                compass_enabled: false,
+                incident_postmortem_template: None,
+                incident_postmortem_parent: None,
+                incident_project: None,
+                jsm_enabled: false,
+                cache_max_bytes: 0,
+                cache_dir: None,
+                https_proxy: None,
+                no_proxy: None,
+                ca_cert_path: None,
+                insecure_skip_verify: false,
+                tool_timeouts_ms: Default::default(),
+                deadline: None,
+                base_url: "https://test.atlassian.net".to_string(),
+            },
+        }
+    }
+
+    /// Pointed at a mock site
+    pub fn mock(server: &MockAtlassianServer) -> Self {
+        Self::new().base_url(&server.base_url()).mode(Mode::Mock)
+    }
+
+    /// Where requests go, e.g. `http://127.0.0.1:8080` for your own stub
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.config.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.config.mode = mode;
+        self
+    }
+
+    pub fn jira_projects_filter(mut self, keys: &[&str]) -> Self {
+        self.config.jira_projects_filter = keys.iter().map(|k| k.to_string()).collect();
+        self
+    }
+
+    pub fn confluence_spaces_filter(mut self, keys: &[&str]) -> Self {
+        self.config.confluence_spaces_filter = keys.iter().map(|k| k.to_string()).collect();
+        self
+    }
+
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.config.read_only = read_only;
+        self
+    }
+
+    /// Tool allowlist (`MCP_ENABLED_TOOLS` patterns)
+    pub fn enabled_tools(mut self, patterns: &[&str]) -> Self {
+        self.config.enabled_tools = patterns.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    /// IANA zone or fixed offset for response timestamps
+    pub fn timezone(mut self, zone: &str) -> Self {
+        self.config.timezone = Some(zone.to_string());
+        self
+    }
+
+    /// Sets any other field
+    pub fn with(mut self, set: impl FnOnce(&mut Config)) -> Self {
+        set(&mut self.config);
+        self
+    }
+
+    pub fn build(self) -> Config {
+        self.config
+    }
+}
+
+/// A mock site with a request handler configured against it
+pub struct TestHarness {
+    pub server: MockAtlassianServer,
+    pub config: Arc<Config>,
+    pub handler: RequestHandler,
+}
+
+impl TestHarness {
+    /// The sample site `ATLASSIAN_MODE=mock` serves
+    pub async fn start() -> Result<Self> {
+        Self::with_dataset(Dataset::sample()).await
+    }
+
+    /// A site holding only `dataset`
+    pub async fn with_dataset(dataset: Dataset) -> Result<Self> {
+        Self::new(dataset, |config| config).await
+    }
+
+    /// `configure` adjusts the default mock configuration, e.g. to set
+    /// project filters or read-only mode
+    pub async fn new(
+        dataset: Dataset,
+        configure: impl FnOnce(ConfigBuilder) -> ConfigBuilder,
+    ) -> Result<Self> {
+        let server = MockAtlassianServer::with_dataset(dataset).await?;
+        let config = Arc::new(configure(ConfigBuilder::mock(&server)).build());
+        let handler = RequestHandler::new(config.clone(), Notifier::channel().0).await?;
+        Ok(Self {
+            server,
+            config,
+            handler,
+        })
+    }
+
+    /// Runs a tool as an MCP client would; the result is its structured
+    /// content, or its text for tools without one
+    pub async fn call(&self, tool: &str, args: Value) -> Result<Value> {
+        let result = self.handler.call_tool(tool, args, &self.config).await?;
+        if let Some(structured) = result.structured_content {
+            return Ok(structured);
+        }
+        let text = result
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                ToolContent::Text { text } => Some(text.as_str()),
+                ToolContent::Image { .. } => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Ok(Value::String(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::{IssueFixture, PageFixture};
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_harness_serves_a_seeded_site() {
+        let dataset = Dataset::empty()
+            .with_user("u-7", "Robin Lee")
+            .with_issue(
+                IssueFixture::new("APP-1", "Crash on start")
+                    .issue_type("Bug")
+                    .assignee("u-7"),
+            )
+            .with_issue(IssueFixture::new("APP-2", "Dark mode").status("Done"))
+            .with_page(PageFixture::new("DOCS", "Runbook", "<p>Restart it.</p>").id("42"));
+        let harness = TestHarness::with_dataset(dataset).await.unwrap();
+
+        let issue = harness
+            .call("jira_get_issue", json!({"issue_key": "APP-1"}))
+            .await
+            .unwrap();
+        assert_eq!(issue["issue"]["fields"]["summary"], "Crash on start");
+        assert_eq!(
+            issue["issue"]["fields"]["assignee"]["displayName"],
+            "Robin Lee"
+        );
+
+        let open = harness
+            .call(
+                "jira_search",
+                json!({"jql": "project = APP AND status != Done"}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(open["issues"].as_array().unwrap().len(), 1);
+
+        let page = harness
+            .call("confluence_get_page", json!({"page_id": "42"}))
+            .await
+            .unwrap();
+        assert_eq!(page["page"]["title"], "Runbook");
+    }
+
+    #[tokio::test]
+    async fn test_harness_applies_config() {
+        let harness = TestHarness::new(Dataset::sample(), |config| config.read_only(true))
+            .await
+            .unwrap();
+        assert!(harness.config.read_only);
+        assert_eq!(harness.config.base_url, harness.server.base_url());
+        assert!(
+            harness
+                .call(
+                    "jira_create_issue",
+                    json!({"project_key": "DEMO", "summary": "x", "issue_type": "Task"})
+                )
+                .await
+                .is_err()
+        );
+    }
+}
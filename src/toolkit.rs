@@ -0,0 +1,108 @@
+//! [`AtlassianToolkit`]: this crate as a library, without the MCP server
+
+use anyhow::Result;
+
+use crate::client::{Confluence, Jira};
+use crate::config::Config;
+
+/// A validated configuration and the Jira and Confluence clients for it
+pub struct AtlassianToolkit {
+    config: Config,
+}
+
+impl AtlassianToolkit {
+    /// Reads the environment (and the optional TOML file) like the server does
+    pub fn from_env(config_path: Option<&str>) -> Result<Self> {
+        Self::new(Config::load(config_path)?)
+    }
+
+    pub fn new(config: Config) -> Result<Self> {
+        config.validate()?;
+        Ok(Self { config })
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn jira(&self) -> Jira<'_> {
+        Jira::new(&self.config)
+    }
+
+    pub fn confluence(&self) -> Confluence<'_> {
+        Confluence::new(&self.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockAtlassianServer;
+    use crate::test_support::ConfigBuilder;
+    use serde_json::{Map, json};
+
+    #[tokio::test]
+    async fn test_toolkit_round_trip_against_the_mock_site() {
+        let mock = MockAtlassianServer::start().await.unwrap();
+        let toolkit = AtlassianToolkit::new(ConfigBuilder::mock(&mock).build()).unwrap();
+        let jira = toolkit.jira();
+
+        let created = jira
+            .create_issue("DEMO", "From the library", "Task", json!(null))
+            .await
+            .unwrap();
+        let mut fields = Map::new();
+        fields.insert("labels".to_string(), json!(["lib"]));
+        jira.update_issue(&created.key, fields).await.unwrap();
+
+        let transitions = jira.get_transitions(&created.key).await.unwrap();
+        let done = transitions
+            .iter()
+            .find(|t| t.to.as_ref().is_some_and(|to| to.name == "Done"))
+            .unwrap();
+        jira.transition_issue(&created.key, &done.id).await.unwrap();
+
+        let issue = jira.get_issue(&created.key).await.unwrap();
+        assert_eq!(issue.fields.summary.as_deref(), Some("From the library"));
+        assert_eq!(issue.fields.status.unwrap().name, "Done");
+
+        let found = jira
+            .search("labels = lib", &["summary".to_string()], 10, None)
+            .await
+            .unwrap();
+        assert_eq!(found.issues[0].key, created.key);
+
+        let confluence = toolkit.confluence();
+        let space_id = confluence.space_id("ENG").await.unwrap();
+        let page = confluence
+            .find_page_by_title(&space_id, "Release Process")
+            .await
+            .unwrap()
+            .unwrap();
+        let current = confluence.get_page_storage(&page.id, None).await.unwrap();
+        let version = current.version_number().unwrap();
+        let saved = confluence
+            .update_page(&page.id, &page.title, "<p>New</p>", version + 1, &[])
+            .await
+            .unwrap();
+        assert_eq!(saved.version_number(), Some(version + 1));
+
+        let stale = confluence
+            .update_page(&page.id, &page.title, "<p>Old</p>", version + 1, &[])
+            .await
+            .unwrap_err();
+        assert!(
+            stale
+                .downcast_ref::<crate::client::confluence::VersionConflict>()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_toolkit_validates_config() {
+        let config = ConfigBuilder::new()
+            .with(|config| config.atlassian_domain = String::new())
+            .build();
+        assert!(AtlassianToolkit::new(config).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+//! `applied_filters` metadata on search results
+//!
+//! Searches are rewritten before they run: `JIRA_PROJECTS_FILTER` and
+//! `CONFLUENCE_SPACES_FILTER` are injected when the query names no
+//! project/space, and `jira_search` adds its relative date windows. Without
+//! an echo, results missing from other projects look like a search bug, so
+//! a rewritten search reports the query that actually ran and, when a scope
+//! was injected, which setting did it.
+
+use serde_json::{Value, json};
+
+/// A configured scope that was injected into the query
+pub struct InjectedScope<'a> {
+    /// `projects` or `spaces`
+    pub kind: &'a str,
+    pub values: &'a [String],
+    /// The environment variable it came from
+    pub setting: &'a str,
+}
+
+/// Metadata for a search that ran as `effective` instead of `requested`;
+/// `None` when nothing was rewritten
+pub fn describe(
+    query_key: &str,
+    requested: &str,
+    effective: &str,
+    scope: Option<InjectedScope>,
+) -> Option<Value> {
+    if requested.trim() == effective.trim() {
+        return None;
+    }
+    let mut applied = json!({ query_key: effective });
+    if let Some(scope) = scope {
+        applied[scope.kind] = json!(scope.values);
+        applied["setting"] = json!(scope.setting);
+    }
+    Some(applied)
+}
+
+/// Adds `applied_filters` to an object result, or a closing line to a text
+/// (Markdown table) result
+pub fn attach(result: Value, applied: Option<Value>) -> Value {
+    let Some(applied) = applied else {
+        return result;
+    };
+    match result {
+        Value::Object(mut map) => {
+            map.insert("applied_filters".to_string(), applied);
+            Value::Object(map)
+        }
+        Value::String(mut text) => {
+            let query = applied
+                .as_object()
+                .and_then(|map| map.iter().find(|(key, _)| key.starts_with("effective_")))
+                .and_then(|(_, query)| query.as_str())
+                .unwrap_or_default();
+            text.push_str(&format!("\nQuery as run: `{}`", query));
+            if let Some(setting) = applied["setting"].as_str() {
+                text.push_str(&format!(" (scope from {})", setting));
+            }
+            text.push('\n');
+            Value::String(text)
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_only_rewritten_queries() {
+        assert!(describe("effective_jql", "project = A", " project = A", None).is_none());
+
+        let projects = vec!["A".to_string(), "B".to_string()];
+        let applied = describe(
+            "effective_jql",
+            "status = Open",
+            "project IN (\"A\",\"B\") AND (status = Open)",
+            Some(InjectedScope {
+                kind: "projects",
+                values: &projects,
+                setting: "JIRA_PROJECTS_FILTER",
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            applied,
+            json!({
+                "effective_jql": "project IN (\"A\",\"B\") AND (status = Open)",
+                "projects": ["A", "B"],
+                "setting": "JIRA_PROJECTS_FILTER"
+            })
+        );
+    }
+
+    #[test]
+    fn test_attach_to_objects_and_tables() {
+        let applied = describe("effective_jql", "x = 1", "(x = 1) AND updated >= -7d", None);
+
+        let object = attach(json!({"success": true}), applied.clone());
+        assert_eq!(
+            object["applied_filters"]["effective_jql"],
+            "(x = 1) AND updated >= -7d"
+        );
+
+        let table = attach(json!("| key |\n"), applied);
+        assert_eq!(
+            table,
+            json!("| key |\n\nQuery as run: `(x = 1) AND updated >= -7d`\n")
+        );
+        assert_eq!(attach(json!({"a": 1}), None), json!({"a": 1}));
+    }
+}
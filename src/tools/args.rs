@@ -0,0 +1,295 @@
+//! Typed tool arguments
+//!
+//! A handler using this declares a `#[derive(Deserialize)]` struct for its
+//! arguments and calls [`parse_args`]. Unlike a plain
+//! `serde_json::from_value`, which stops at the first problem and does not
+//! name the field for type errors, [`parse`] keeps going and reports every
+//! missing and invalid top-level field in one [`InvalidArgs`] error, which the
+//! server answers with `invalid_params`.
+//!
+//! So far that is the core issue and comment handlers in `jira/mod.rs`, the
+//! page handlers in `confluence/mod.rs`, and the digest, delta, workload,
+//! chunk, teams, Compass, JSM and incident tools. The others still read
+//! `args` field by field and leave required and mistyped fields to the schema
+//! check in `call_tool`.
+//!
+//! Unknown fields are ignored: the server adds its own (e.g. the progress
+//! token) and older clients may send arguments a tool no longer reads.
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, Visitor};
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Every missing and invalid argument of one tool call
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InvalidArgs {
+    pub missing: Vec<String>,
+    /// `(field, reason)`
+    pub invalid: Vec<(String, String)>,
+}
+
+impl fmt::Display for InvalidArgs {
+    /// `Missing issue_key, summary; Invalid limit: <reason>`, in the wording
+    /// handlers have always used for a single missing argument
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if !self.missing.is_empty() {
+            parts.push(format!("Missing {}", self.missing.join(", ")));
+        }
+        for (field, reason) in &self.invalid {
+            parts.push(format!("Invalid {}: {}", field, reason));
+        }
+        f.write_str(&parts.join("; "))
+    }
+}
+
+impl std::error::Error for InvalidArgs {}
+
+/// Placeholders tried in place of a bad field so the rest can still be checked
+const PLACEHOLDERS: fn() -> [Value; 5] = || {
+    [
+        Value::String(String::new()),
+        Value::from(0),
+        Value::Bool(false),
+        Value::Array(Vec::new()),
+        Value::Object(Map::new()),
+    ]
+};
+
+/// Deserializes `args` into `T`, collecting all argument errors at once
+pub fn parse<T: DeserializeOwned>(args: &Value) -> Result<T, InvalidArgs> {
+    let mut object = match args {
+        Value::Object(map) => map.clone(),
+        Value::Null => Map::new(),
+        other => {
+            return Err(InvalidArgs {
+                missing: Vec::new(),
+                invalid: vec![(
+                    "arguments".to_string(),
+                    format!("expected an object, got {}", kind(other)),
+                )],
+            });
+        }
+    };
+
+    let mut errors = InvalidArgs::default();
+    let mut seen: Vec<String> = Vec::new();
+    loop {
+        let field = match T::deserialize(Fields(object.clone())) {
+            Ok(value) if errors == InvalidArgs::default() => return Ok(value),
+            Ok(_) => return Err(errors),
+            Err(FieldError::Missing(field)) => {
+                if !errors.invalid.iter().any(|(f, _)| *f == field) {
+                    errors.missing.push(field.clone());
+                }
+                field
+            }
+            Err(FieldError::Invalid(field, reason)) => {
+                errors.invalid.push((field.clone(), reason));
+                field
+            }
+            Err(FieldError::Other(reason)) => {
+                errors.invalid.push(("arguments".to_string(), reason));
+                return Err(errors);
+            }
+        };
+        // A field that stays bad whatever we put there has been reported already
+        if seen.contains(&field) {
+            return Err(errors);
+        }
+        seen.push(field.clone());
+
+        // Swap in something `T` accepts (or drop the field) and look for more.
+        // Values are checked in order, so an error about another field means
+        // this one passed; dropping it only shows as `Missing` at the end.
+        let replacement = PLACEHOLDERS()
+            .into_iter()
+            .map(Some)
+            .chain(std::iter::once(None))
+            .map(|placeholder| {
+                let mut candidate = object.clone();
+                match placeholder {
+                    Some(placeholder) => candidate.insert(field.clone(), placeholder),
+                    None => candidate.remove(&field),
+                };
+                candidate
+            })
+            .find(|candidate| {
+                T::deserialize(Fields(candidate.clone()))
+                    .map_or_else(|e| !e.is_about(&field), |_| true)
+            });
+        match replacement {
+            Some(candidate) => object = candidate,
+            None => return Err(errors),
+        }
+    }
+}
+
+/// Parses `args` into `T`, as an `anyhow` error for handlers
+pub fn parse_args<T: DeserializeOwned>(args: &Value) -> anyhow::Result<T> {
+    Ok(parse(args)?)
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Errors raised by [`Fields`], tagged with the field they concern
+#[derive(Debug)]
+enum FieldError {
+    Missing(String),
+    Invalid(String, String),
+    Other(String),
+}
+
+impl FieldError {
+    fn is_about(&self, field: &str) -> bool {
+        match self {
+            FieldError::Missing(f) | FieldError::Invalid(f, _) => f == field,
+            FieldError::Other(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldError::Missing(field) => write!(f, "missing field `{}`", field),
+            FieldError::Invalid(field, reason) => write!(f, "{}: {}", field, reason),
+            FieldError::Other(reason) => f.write_str(reason),
+        }
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+impl de::Error for FieldError {
+    fn custom<M: fmt::Display>(msg: M) -> Self {
+        FieldError::Other(msg.to_string())
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        FieldError::Missing(field.to_string())
+    }
+}
+
+/// A JSON object whose value errors carry the top-level field name
+struct Fields(Map<String, Value>);
+
+impl<'de> de::Deserializer<'de> for Fields {
+    type Error = FieldError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(FieldsAccess {
+            entries: self.0.into_iter(),
+            field: None,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct FieldsAccess {
+    entries: serde_json::map::IntoIter,
+    field: Option<(String, Value)>,
+}
+
+impl<'de> MapAccess<'de> for FieldsAccess {
+    type Error = FieldError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        let deserializer: de::value::StringDeserializer<FieldError> =
+            key.clone().into_deserializer();
+        let key_value = seed.deserialize(deserializer)?;
+        self.field = Some((key, value));
+        Ok(Some(key_value))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .field
+            .take()
+            .ok_or_else(|| FieldError::Other("value without a key".to_string()))?;
+        seed.deserialize(value)
+            .map_err(|e| FieldError::Invalid(key, e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Example {
+        issue_key: String,
+        summary: String,
+        #[serde(default)]
+        limit: Option<u32>,
+        #[serde(default)]
+        labels: Vec<String>,
+    }
+
+    #[test]
+    fn test_parse_valid_args_ignores_unknown_fields() {
+        let args = json!({"issue_key": "ENG-1", "summary": "Fix", "extra": true});
+        assert_eq!(
+            parse::<Example>(&args).unwrap(),
+            Example {
+                issue_key: "ENG-1".to_string(),
+                summary: "Fix".to_string(),
+                limit: None,
+                labels: vec![]
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_reports_every_problem() {
+        let args = json!({"summary": 5, "limit": "ten", "labels": ["a", 1]});
+        let errors = parse::<Example>(&args).unwrap_err();
+        assert_eq!(errors.missing, vec!["issue_key"]);
+        let invalid: Vec<&str> = errors.invalid.iter().map(|(f, _)| f.as_str()).collect();
+        assert_eq!(invalid.len(), 3, "{:?}", errors);
+        for field in ["summary", "limit", "labels"] {
+            assert!(
+                invalid.contains(&field),
+                "{} not reported: {}",
+                field,
+                errors
+            );
+        }
+        assert!(
+            errors
+                .to_string()
+                .starts_with("Missing issue_key; Invalid ")
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_non_object() {
+        let errors = parse::<Example>(&json!([1])).unwrap_err();
+        assert_eq!(errors.invalid[0].0, "arguments");
+    }
+}
@@ -0,0 +1,396 @@
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema, number_prop, string_prop, union_prop};
+use crate::utils::http_utils::{create_atlassian_client, create_bitbucket_auth_header};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+/// Bitbucket Cloud REST API v2 base URL
+pub const BITBUCKET_API_URL: &str = "https://api.bitbucket.org/2.0";
+
+/// Partial-response fields for PR listings (Bitbucket `fields` query param)
+const PR_LIST_FIELDS: &str = "size,values.id,values.title,values.state,values.author.display_name,values.source.branch.name,values.destination.branch.name,values.created_on,values.updated_on,values.comment_count,values.links.html.href";
+
+/// Partial-response fields for pipeline listings
+const PIPELINE_LIST_FIELDS: &str = "size,values.uuid,values.build_number,values.state.name,values.state.result.name,values.target.ref_name,values.target.commit.hash,values.trigger.name,values.creator.display_name,values.created_on,values.completed_on,values.duration_in_seconds";
+
+const PR_STATES: &[&str] = &["OPEN", "MERGED", "DECLINED", "SUPERSEDED"];
+
+// Handlers for each Bitbucket tool
+pub struct ListPullRequestsHandler;
+pub struct GetPullRequestHandler;
+pub struct CreatePullRequestCommentHandler;
+pub struct GetPipelinesHandler;
+
+/// Resolves `workspace` (argument, else `BITBUCKET_WORKSPACE`) and `repo_slug`
+/// into the repository API URL.
+fn repository_url(args: &Value, config: &Config) -> Result<String> {
+    let workspace = args["workspace"]
+        .as_str()
+        .or(config.bitbucket_workspace.as_deref())
+        .ok_or_else(|| anyhow::anyhow!("Missing workspace (set BITBUCKET_WORKSPACE)"))?;
+    let repo_slug = args["repo_slug"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing repo_slug"))?;
+
+    for (name, value) in [("workspace", workspace), ("repo_slug", repo_slug)] {
+        if value.is_empty() || value.contains(['/', '?', '#']) {
+            anyhow::bail!("Invalid {}: '{}'", name, value);
+        }
+    }
+
+    Ok(format!(
+        "{}/repositories/{}/{}",
+        BITBUCKET_API_URL, workspace, repo_slug
+    ))
+}
+
+/// Pull request ids may arrive as numbers or numeric strings
+fn pr_id(args: &Value) -> Result<u64> {
+    args["pr_id"]
+        .as_u64()
+        .or_else(|| args["pr_id"].as_str().and_then(|s| s.parse().ok()))
+        .ok_or_else(|| anyhow::anyhow!("Missing pr_id"))
+}
+
+fn page_len(args: &Value, default: u64) -> u64 {
+    args["limit"].as_u64().unwrap_or(default).clamp(1, 50)
+}
+
+/// `workspace` + `repo_slug` shared by every Bitbucket tool
+fn repo_props() -> HashMap<String, Property> {
+    let mut props = HashMap::new();
+    props.insert(
+        "workspace".to_string(),
+        string_prop("Workspace ID (default: BITBUCKET_WORKSPACE)", false),
+    );
+    props.insert(
+        "repo_slug".to_string(),
+        string_prop("Repository slug", true),
+    );
+    props
+}
+
+#[async_trait]
+impl ToolHandler for ListPullRequestsHandler {
+    fn name(&self) -> &'static str {
+        "bitbucket_list_prs"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = repo_props();
+        props.insert(
+            "state".to_string(),
+            Property::new("string")
+                .with_description("Pull request state (default: OPEN)")
+                .with_default(json!("OPEN"))
+                .with_enum(vec![
+                    json!("OPEN"),
+                    json!("MERGED"),
+                    json!("DECLINED"),
+                    json!("SUPERSEDED"),
+                ]),
+        );
+        props.insert(
+            "limit".to_string(),
+            number_prop("Maximum results (default: 20, max: 50)", 20),
+        );
+        ToolSchema::new(
+            "List Bitbucket pull requests",
+            props,
+            vec!["repo_slug".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let repo_url = repository_url(&args, config)?;
+        let state = args["state"]
+            .as_str()
+            .unwrap_or("OPEN")
+            .to_ascii_uppercase();
+        if !PR_STATES.contains(&state.as_str()) {
+            anyhow::bail!(
+                "Invalid state '{}': expected one of {}",
+                state,
+                PR_STATES.join(", ")
+            );
+        }
+
+        let client = create_atlassian_client(config);
+        let url = format!("{}/pullrequests", repo_url);
+        let query_params = [
+            ("state", state),
+            ("pagelen", page_len(&args, 20).to_string()),
+            ("fields", PR_LIST_FIELDS.to_string()),
+        ];
+
+        let response = client
+            .get(&url)
+            .header("Authorization", create_bitbucket_auth_header(config))
+            .header("Accept", "application/json")
+            .query(&query_params)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to list pull requests: {}", error);
+        }
+
+        let data: Value = response.json().await?;
+
+        Ok(json!({
+            "success": true,
+            "pull_requests": data["values"],
+            "total": data["size"]
+        }))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for GetPullRequestHandler {
+    fn name(&self) -> &'static str {
+        "bitbucket_get_pr"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = repo_props();
+        props.insert(
+            "pr_id".to_string(),
+            union_prop("Pull request ID", vec!["number", "string"]),
+        );
+        ToolSchema::new(
+            "Get Bitbucket pull request",
+            props,
+            vec!["repo_slug".to_string(), "pr_id".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let repo_url = repository_url(&args, config)?;
+        let pr_id = pr_id(&args)?;
+
+        let client = create_atlassian_client(config);
+        let url = format!("{}/pullrequests/{}", repo_url, pr_id);
+
+        let response = client
+            .get(&url)
+            .header("Authorization", create_bitbucket_auth_header(config))
+            .header("Accept", "application/json")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to get pull request: {}", error);
+        }
+
+        let data: Value = response.json().await?;
+        Ok(data)
+    }
+}
+
+#[async_trait]
+impl ToolHandler for CreatePullRequestCommentHandler {
+    fn name(&self) -> &'static str {
+        "bitbucket_create_pr_comment"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = repo_props();
+        props.insert(
+            "pr_id".to_string(),
+            union_prop("Pull request ID", vec!["number", "string"]),
+        );
+        props.insert(
+            "content".to_string(),
+            string_prop("Comment text (Markdown)", true),
+        );
+        ToolSchema::new(
+            "Add comment to Bitbucket pull request",
+            props,
+            vec![
+                "repo_slug".to_string(),
+                "pr_id".to_string(),
+                "content".to_string(),
+            ],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let repo_url = repository_url(&args, config)?;
+        let pr_id = pr_id(&args)?;
+        let content = args["content"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing content"))?;
+
+        let client = create_atlassian_client(config);
+        let url = format!("{}/pullrequests/{}/comments", repo_url, pr_id);
+
+        // Bitbucket renders `raw` as Markdown
+        let body = json!({
+            "content": { "raw": content }
+        });
+
+        let response = client
+            .post(&url)
+            .header("Authorization", create_bitbucket_auth_header(config))
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to add pull request comment: {}", error);
+        }
+
+        let data: Value = response.json().await?;
+
+        Ok(json!({
+            "success": true,
+            "id": data["id"],
+            "url": data["links"]["html"]["href"]
+        }))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for GetPipelinesHandler {
+    fn name(&self) -> &'static str {
+        "bitbucket_get_pipelines"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = repo_props();
+        props.insert(
+            "limit".to_string(),
+            number_prop("Maximum results, newest first (default: 10, max: 50)", 10),
+        );
+        ToolSchema::new(
+            "List recent Bitbucket Pipelines runs",
+            props,
+            vec!["repo_slug".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let repo_url = repository_url(&args, config)?;
+
+        let client = create_atlassian_client(config);
+        // Trailing slash is required by the pipelines endpoint
+        let url = format!("{}/pipelines/", repo_url);
+        let query_params = [
+            ("sort", "-created_on".to_string()),
+            ("pagelen", page_len(&args, 10).to_string()),
+            ("fields", PIPELINE_LIST_FIELDS.to_string()),
+        ];
+
+        let response = client
+            .get(&url)
+            .header("Authorization", create_bitbucket_auth_header(config))
+            .header("Accept", "application/json")
+            .query(&query_params)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to get pipelines: {}", error);
+        }
+
+        let data: Value = response.json().await?;
+
+        Ok(json!({
+            "success": true,
+            "pipelines": data["values"],
+            "total": data["size"]
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ConfigBuilder;
+
+    fn create_test_config(workspace: Option<&str>) -> Config {
+        ConfigBuilder::new()
+            .with(|config| config.bitbucket_workspace = workspace.map(String::from))
+            .build()
+    }
+
+    #[test]
+    fn test_repository_url_uses_configured_workspace() {
+        let config = create_test_config(Some("acme"));
+        let url = repository_url(&json!({"repo_slug": "api"}), &config).unwrap();
+        assert_eq!(url, "https://api.bitbucket.org/2.0/repositories/acme/api");
+    }
+
+    #[test]
+    fn test_repository_url_argument_overrides_workspace() {
+        let config = create_test_config(Some("acme"));
+        let args = json!({"workspace": "other", "repo_slug": "api"});
+        let url = repository_url(&args, &config).unwrap();
+        assert!(url.ends_with("/repositories/other/api"));
+    }
+
+    #[test]
+    fn test_repository_url_requires_workspace_and_slug() {
+        let config = create_test_config(None);
+        let result = repository_url(&json!({"repo_slug": "api"}), &config);
+        assert!(result.unwrap_err().to_string().contains("workspace"));
+
+        let config = create_test_config(Some("acme"));
+        let result = repository_url(&json!({}), &config);
+        assert!(result.unwrap_err().to_string().contains("repo_slug"));
+    }
+
+    #[test]
+    fn test_repository_url_rejects_path_injection() {
+        let config = create_test_config(Some("acme"));
+        let result = repository_url(&json!({"repo_slug": "api/../../users"}), &config);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_pr_id_accepts_number_or_string() {
+        assert_eq!(pr_id(&json!({"pr_id": 42})).unwrap(), 42);
+        assert_eq!(pr_id(&json!({"pr_id": "42"})).unwrap(), 42);
+        assert!(pr_id(&json!({"pr_id": "abc"})).is_err());
+        assert!(pr_id(&json!({})).is_err());
+    }
+
+    #[test]
+    fn test_page_len_is_clamped() {
+        assert_eq!(page_len(&json!({}), 20), 20);
+        assert_eq!(page_len(&json!({"limit": 500}), 20), 50);
+        assert_eq!(page_len(&json!({"limit": 0}), 20), 1);
+    }
+
+    #[test]
+    fn test_list_prs_rejects_invalid_state() {
+        let handler = ListPullRequestsHandler;
+        let config = create_test_config(Some("acme"));
+        let args = json!({"repo_slug": "api", "state": "closed"});
+
+        let result = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(handler.execute(args, &config));
+        assert!(result.unwrap_err().to_string().contains("Invalid state"));
+    }
+
+    #[test]
+    fn test_create_pr_comment_missing_content() {
+        let handler = CreatePullRequestCommentHandler;
+        let config = create_test_config(Some("acme"));
+        let args = json!({"repo_slug": "api", "pr_id": 1});
+
+        let result = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(handler.execute(args, &config));
+        assert!(result.unwrap_err().to_string().contains("Missing content"));
+    }
+}
@@ -0,0 +1,421 @@
+//! Compass component catalog tools (opt-in with `COMPASS_ENABLED=true`)
+//!
+//! Compass is only reachable through the GraphQL gateway
+//! ([`Gateway`](crate::client::graphql::Gateway)). Its results are unions
+//! whose `QueryError` member carries the failure, surfaced here as tool
+//! errors. Components point at their owner by team ID, so the owner's name
+//! is looked up separately and left out when the Teams query fails;
+//! scorecard scores take one field per scorecard and are batched.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+
+use crate::client::graphql::{Batched, DEFAULT_COST_BUDGET, Gateway};
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::args::parse_args;
+use crate::tools::schema::{ToolSchema, number_prop, string_prop};
+
+pub struct ListComponentsHandler;
+pub struct GetComponentHandler;
+
+const DEFAULT_LIMIT: u64 = 25;
+const MAX_LIMIT: u64 = 100;
+
+const SEARCH_QUERY: &str =
+    "query compassSearchComponents($cloudId: String!, $query: CompassSearchComponentQuery) {
+  compass {
+    searchComponents(cloudId: $cloudId, query: $query) {
+      ... on CompassSearchComponentConnection {
+        nodes { component { id name typeId description ownerId labels { name } } }
+        pageInfo { hasNextPage endCursor }
+      }
+      ... on QueryError { message }
+    }
+  }
+}";
+
+const COMPONENT_QUERY: &str = "query compassComponent($id: ID!) {
+  compass {
+    component(id: $id) {
+      ... on CompassComponent {
+        id name typeId description ownerId labels { name }
+        links { name type url }
+        scorecards { id name }
+      }
+      ... on QueryError { message }
+    }
+  }
+}";
+
+const SCORECARD_SCORE: Batched<'static> = Batched {
+    variables: &[("id", "ID!"), ("scorecardId", "ID!")],
+    selection: "compass {
+  component(id: $id) {
+    ... on CompassComponent {
+      scorecardScore(query: {scorecardId: $scorecardId}) { totalScore maxTotalScore }
+    }
+  }
+}",
+    cost: 20,
+};
+
+const OWNER_QUERY: &str = "query compassOwner($id: ID!, $siteId: String!) {
+  team {
+    teamV2(id: $id, siteId: $siteId) { id displayName }
+  }
+}";
+
+#[derive(Deserialize)]
+struct ListArgs {
+    query: Option<String>,
+    #[serde(rename = "type")]
+    component_type: Option<String>,
+    limit: Option<u64>,
+    cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GetArgs {
+    component: String,
+}
+
+/// The member of a result union, or its `QueryError` message as an error
+fn union_result(value: &Value, what: &str) -> Result<Value> {
+    if value.is_null() {
+        anyhow::bail!("No {} returned", what);
+    }
+    if let Some(message) = value["message"].as_str() {
+        anyhow::bail!("Failed to get {}: {}", what, message);
+    }
+    Ok(value.clone())
+}
+
+/// `{id, name, type, description, owner_id, labels}` for a component
+fn component_entry(component: &Value) -> Value {
+    json!({
+        "id": component["id"],
+        "name": component["name"],
+        "type": component["typeId"],
+        "description": component["description"],
+        "owner_id": component["ownerId"],
+        "labels": component["labels"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|label| label["name"].as_str())
+            .collect::<Vec<_>>()
+    })
+}
+
+/// Search variables: free text, and a type filter
+fn search_query(
+    text: Option<&str>,
+    component_type: Option<&str>,
+    limit: u64,
+    cursor: Option<&str>,
+) -> Value {
+    let mut query = json!({"first": limit});
+    if let Some(text) = text {
+        query["query"] = json!(text);
+    }
+    if let Some(component_type) = component_type {
+        query["fieldFilters"] = json!([{
+            "name": "type",
+            "filter": {"eq": component_type.to_uppercase()}
+        }]);
+    }
+    if let Some(cursor) = cursor {
+        query["after"] = json!(cursor);
+    }
+    query
+}
+
+async fn search(gateway: &Gateway<'_>, cloud_id: &str, query: Value) -> Result<Value> {
+    let data = gateway
+        .query(SEARCH_QUERY, json!({"cloudId": cloud_id, "query": query}))
+        .await?;
+    union_result(&data["compass"]["searchComponents"], "components")
+}
+
+/// The ID of a component given as an ID (ARI) or its name
+async fn component_id(gateway: &Gateway<'_>, cloud_id: &str, component: &str) -> Result<String> {
+    if component.starts_with("ari:") {
+        return Ok(component.to_string());
+    }
+    let found = search(
+        gateway,
+        cloud_id,
+        search_query(Some(component), None, 10, None),
+    )
+    .await?;
+    let nodes: Vec<&Value> = found["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|node| &node["component"])
+        .collect();
+    if let Some(exact) = nodes.iter().find(|c| {
+        c["name"]
+            .as_str()
+            .is_some_and(|name| name.eq_ignore_ascii_case(component))
+    }) {
+        return Ok(exact["id"].as_str().unwrap_or_default().to_string());
+    }
+    match nodes.as_slice() {
+        [] => anyhow::bail!("No Compass component named '{}'", component),
+        [only] => Ok(only["id"].as_str().unwrap_or_default().to_string()),
+        several => anyhow::bail!(
+            "'{}' matches several components: {}",
+            component,
+            several
+                .iter()
+                .filter_map(|c| c["name"].as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// `{id, name, score, max_score}` per scorecard; a score that failed to
+/// load is null with its `error`
+fn scorecard_entry(scorecard: &Value, score: Result<Value>) -> Value {
+    let mut entry = json!({"id": scorecard["id"], "name": scorecard["name"]});
+    match score {
+        Ok(score) => {
+            let score = &score["component"]["scorecardScore"];
+            entry["score"] = score["totalScore"].clone();
+            entry["max_score"] = score["maxTotalScore"].clone();
+        }
+        Err(e) => {
+            entry["score"] = Value::Null;
+            entry["error"] = json!(e.to_string());
+        }
+    }
+    entry
+}
+
+#[async_trait]
+impl ToolHandler for ListComponentsHandler {
+    fn name(&self) -> &'static str {
+        "compass_list_components"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "query".to_string(),
+            string_prop("Text to search component names and descriptions for", false),
+        );
+        props.insert(
+            "type".to_string(),
+            string_prop(
+                "Component type: SERVICE, LIBRARY, APPLICATION, CAPABILITY, CLOUD_RESOURCE, DATA_PIPELINE, MACHINE_LEARNING_MODEL, UI_ELEMENT, WEBSITE or OTHER",
+                false,
+            ),
+        );
+        props.insert(
+            "limit".to_string(),
+            number_prop("Components per page (1-100)", DEFAULT_LIMIT as i32)
+                .with_range(Some(1.0), Some(MAX_LIMIT as f64)),
+        );
+        props.insert(
+            "cursor".to_string(),
+            string_prop("next_cursor from the previous page", false),
+        );
+        ToolSchema::new(
+            "Search the Compass component catalog (services, libraries, ...) with their types and owner team IDs",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let ListArgs {
+            query,
+            component_type,
+            limit,
+            cursor,
+        } = parse_args(&args)?;
+        let non_empty = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(String::from)
+        };
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+        let gateway = Gateway::new(config);
+        let cloud_id = gateway.cloud_id().await?;
+        let found = search(
+            &gateway,
+            &cloud_id,
+            search_query(
+                non_empty(&query).as_deref(),
+                non_empty(&component_type).as_deref(),
+                limit,
+                non_empty(&cursor).as_deref(),
+            ),
+        )
+        .await?;
+
+        let components: Vec<Value> = found["nodes"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|node| component_entry(&node["component"]))
+            .collect();
+        let page_info = &found["pageInfo"];
+        let next_cursor = page_info["endCursor"]
+            .as_str()
+            .filter(|_| page_info["hasNextPage"].as_bool() == Some(true));
+        Ok(json!({
+            "success": true,
+            "components": components,
+            "has_more": next_cursor.is_some(),
+            "next_cursor": next_cursor
+        }))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for GetComponentHandler {
+    fn name(&self) -> &'static str {
+        "compass_get_component"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "component".to_string(),
+            string_prop("Component ID (ari:cloud:compass:...) or name", true),
+        );
+        ToolSchema::new(
+            "A Compass component with its owner team, links, labels and scorecard scores, for 'who owns service X'",
+            props,
+            vec!["component".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let GetArgs { component } = parse_args(&args)?;
+        let component = component.trim();
+        if component.is_empty() {
+            anyhow::bail!("Missing component");
+        }
+
+        let gateway = Gateway::new(config);
+        let cloud_id = gateway.cloud_id().await?;
+        let id = component_id(&gateway, &cloud_id, component).await?;
+        let data = gateway.query(COMPONENT_QUERY, json!({"id": id})).await?;
+        let details = union_result(&data["compass"]["component"], "component")?;
+
+        let scorecards: Vec<Value> = details["scorecards"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let items: Vec<Map<String, Value>> = scorecards
+            .iter()
+            .map(|scorecard| {
+                let mut item = Map::new();
+                item.insert("id".to_string(), json!(id));
+                item.insert("scorecardId".to_string(), scorecard["id"].clone());
+                item
+            })
+            .collect();
+        let scores = gateway
+            .query_each(&SCORECARD_SCORE, &items, DEFAULT_COST_BUDGET)
+            .await?;
+
+        let mut result = component_entry(&details);
+        result["success"] = json!(true);
+        result["links"] = json!(
+            details["links"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|link| json!({"name": link["name"], "type": link["type"], "url": link["url"]}))
+                .collect::<Vec<_>>()
+        );
+        result["scorecards"] = json!(
+            scorecards
+                .iter()
+                .zip(scores)
+                .map(|(scorecard, score)| scorecard_entry(scorecard, score))
+                .collect::<Vec<_>>()
+        );
+
+        result["owner"] = match details["ownerId"].as_str() {
+            Some(owner_id) => {
+                let name = gateway
+                    .query(OWNER_QUERY, json!({"id": owner_id, "siteId": cloud_id}))
+                    .await
+                    .ok()
+                    .and_then(|data| {
+                        data["team"]["teamV2"]["displayName"]
+                            .as_str()
+                            .map(String::from)
+                    });
+                json!({"id": owner_id, "name": name})
+            }
+            None => Value::Null,
+        };
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scorecard_entry_keeps_failed_scores() {
+        let scorecard = json!({"id": "s-1", "name": "Readiness"});
+        let score =
+            json!({"component": {"scorecardScore": {"totalScore": 80, "maxTotalScore": 100}}});
+        let entry = scorecard_entry(&scorecard, Ok(score));
+        assert_eq!(entry["score"], 80);
+        assert_eq!(entry["max_score"], 100);
+
+        let failed = scorecard_entry(&scorecard, Err(anyhow::anyhow!("GraphQL error: denied")));
+        assert!(failed["score"].is_null());
+        assert_eq!(failed["error"], "GraphQL error: denied");
+    }
+
+    #[test]
+    fn test_search_query_filters_type() {
+        let query = search_query(Some("payments"), Some("service"), 25, Some("c1"));
+        assert_eq!(
+            query,
+            json!({
+                "first": 25,
+                "query": "payments",
+                "fieldFilters": [{"name": "type", "filter": {"eq": "SERVICE"}}],
+                "after": "c1"
+            })
+        );
+        assert_eq!(search_query(None, None, 10, None), json!({"first": 10}));
+    }
+
+    #[test]
+    fn test_union_result_surfaces_query_errors() {
+        let error = union_result(&json!({"message": "Component not found"}), "component");
+        assert!(
+            error
+                .unwrap_err()
+                .to_string()
+                .contains("Component not found")
+        );
+        assert!(union_result(&Value::Null, "component").is_err());
+        assert_eq!(
+            component_entry(
+                &json!({"id": "ari:1", "name": "api", "typeId": "SERVICE", "labels": [{"name": "tier-1"}]})
+            )["labels"],
+            json!(["tier-1"])
+        );
+    }
+}
@@ -0,0 +1,300 @@
+//! Action item extraction
+//!
+//! `confluence_extract_action_items` reads a page's storage body and lists its
+//! tasks (`<ac:task>` inside `<ac:task-list>`), with the assignee mention and
+//! due date when present. With `create_issues` the call counts as a write:
+//! `call_tool` turns each listed item into a `jira_create_issue` call (so
+//! read-only mode, `WRITE_ALLOWED_PROJECTS` and the audit log apply as to
+//! any create), carrying the page URL in its description, and links the
+//! issue back to the page.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema, string_prop};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+use std::future::Future;
+
+use super::edit::visible_text;
+use super::fetch_page_storage;
+
+pub struct ExtractActionItemsHandler;
+
+/// Jira's summary length limit
+const MAX_SUMMARY_CHARS: usize = 255;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionItem {
+    pub id: Option<String>,
+    pub complete: bool,
+    pub text: String,
+    pub assignee_account_id: Option<String>,
+    pub due_date: Option<String>,
+}
+
+/// Text between `<tag>` and `</tag>` at or after `from`
+fn element<'a>(body: &'a str, tag: &str, from: usize) -> Option<&'a str> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = body[from..].find(&open)? + from + open.len();
+    let end = body[start..].find(&close)? + start;
+    Some(&body[start..end])
+}
+
+/// Value of `name="..."` in the first tag containing it
+fn attribute<'a>(fragment: &'a str, name: &str) -> Option<&'a str> {
+    let needle = format!("{}=\"", name);
+    let start = fragment.find(&needle)? + needle.len();
+    let end = fragment[start..].find('"')? + start;
+    Some(&fragment[start..end])
+}
+
+/// Every task on the page, in document order
+pub fn parse_action_items(storage: &str) -> Vec<ActionItem> {
+    let mut items = Vec::new();
+    let mut from = 0;
+    while let Some(pos) = storage[from..].find("<ac:task>") {
+        let start = from + pos;
+        from = start + "<ac:task>".len();
+
+        let Some(body) = element(storage, "ac:task-body", start) else {
+            continue;
+        };
+        // Stop at this task's body so a nested task's fields are not picked up
+        let body_end = storage[start..]
+            .find("<ac:task-body>")
+            .map(|p| start + p)
+            .unwrap_or(storage.len());
+        let head = &storage[start..body_end];
+
+        let text = visible_text(body);
+        if text.is_empty() {
+            continue;
+        }
+        items.push(ActionItem {
+            id: element(head, "ac:task-id", 0).map(String::from),
+            complete: element(head, "ac:task-status", 0) == Some("complete"),
+            text,
+            assignee_account_id: attribute(body, "ri:account-id").map(String::from),
+            due_date: body
+                .find("<time")
+                .and_then(|p| attribute(&body[p..], "datetime"))
+                .map(String::from),
+        });
+    }
+    items
+}
+
+fn summary(text: &str) -> String {
+    if text.chars().count() <= MAX_SUMMARY_CHARS {
+        return text.to_string();
+    }
+    let mut s: String = text.chars().take(MAX_SUMMARY_CHARS - 1).collect();
+    s.push('…');
+    s
+}
+
+fn item_json(item: &ActionItem) -> Value {
+    json!({
+        "id": item.id,
+        "status": if item.complete { "complete" } else { "incomplete" },
+        "text": item.text,
+        "assignee_account_id": item.assignee_account_id,
+        "due_date": item.due_date
+    })
+}
+
+pub async fn link_to_page(
+    client: &reqwest::Client,
+    config: &Config,
+    issue_key: &str,
+    page_url: &str,
+    page_title: &str,
+) -> Result<()> {
+    let url = format!(
+        "{}/rest/api/3/issue/{}/remotelink",
+        config.get_atlassian_base_url(),
+        issue_key
+    );
+    let response = client
+        .post(&url)
+        .header("Authorization", create_auth_header(config))
+        .header("Content-Type", "application/json")
+        .json(&json!({
+            "object": {
+                "url": page_url,
+                "title": page_title
+            }
+        }))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to link issue to page: {}", response.status());
+    }
+    Ok(())
+}
+
+/// Creates the issues a `create_issues` result lists under each item's
+/// `create`, through `create` (a `jira_create_issue` call), and links them
+/// to the page; each item reports its `issue_key` or `error`
+pub async fn create_issues<F, Fut>(result: &mut Value, config: &Config, mut create: F)
+where
+    F: FnMut(Value) -> Fut,
+    Fut: Future<Output = Result<Value>>,
+{
+    let client = create_atlassian_client(config);
+    let title = result["title"].as_str().unwrap_or_default().to_string();
+    let page_url = result["url"].as_str().unwrap_or_default().to_string();
+    let Some(items) = result["action_items"].as_array_mut() else {
+        return;
+    };
+    for item in items {
+        let Some(issue) = item.as_object_mut().and_then(|i| i.remove("create")) else {
+            continue;
+        };
+        match create(issue).await {
+            Ok(created) => {
+                let key = created["key"].as_str().unwrap_or_default().to_string();
+                item["issue_key"] = json!(key);
+                if let Err(e) = link_to_page(&client, config, &key, &page_url, &title).await {
+                    item["link_error"] = json!(e.to_string());
+                }
+            }
+            Err(e) => item["error"] = json!(e.to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for ExtractActionItemsHandler {
+    fn name(&self) -> &'static str {
+        "confluence_extract_action_items"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("page_id".to_string(), string_prop("Page ID", true));
+        props.insert(
+            "include_complete".to_string(),
+            Property::new("boolean")
+                .with_description("Include completed tasks")
+                .with_default(json!(false)),
+        );
+        props.insert("create_issues".to_string(), Property::new("boolean").with_description("Create a Jira issue per listed item, linked back to the page (not in read-only mode)").with_default(json!(false)));
+        props.insert(
+            "project_key".to_string(),
+            string_prop(
+                "Project for created issues (required with create_issues)",
+                false,
+            ),
+        );
+        props.insert(
+            "issue_type".to_string(),
+            string_prop("Issue type for created issues (default: Task)", false),
+        );
+        ToolSchema::new(
+            "List a page's action items (task lists) with assignee and due date; optionally create a Jira issue for each",
+            props,
+            vec!["page_id".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let page_id = args["page_id"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing page_id"))?;
+        let include_complete = args["include_complete"].as_bool().unwrap_or(false);
+        let create_issues = args["create_issues"].as_bool().unwrap_or(false);
+        let project_key = args["project_key"].as_str();
+        if create_issues && project_key.is_none() {
+            anyhow::bail!("project_key is required with create_issues");
+        }
+
+        let client = create_atlassian_client(config);
+        let page = fetch_page_storage(&client, config, page_id, None).await?;
+        let storage = page["body"]["storage"]["value"]
+            .as_str()
+            .unwrap_or_default();
+        let title = page["title"].as_str().unwrap_or_default();
+        let page_url = format!(
+            "{}/wiki/pages/viewpage.action?pageId={}",
+            config.get_atlassian_base_url(),
+            page_id
+        );
+
+        let items: Vec<ActionItem> = parse_action_items(storage)
+            .into_iter()
+            .filter(|item| include_complete || !item.complete)
+            .collect();
+
+        let mut results: Vec<Value> = items.iter().map(item_json).collect();
+        if let (true, Some(project_key)) = (create_issues, project_key) {
+            let issue_type = args["issue_type"].as_str().unwrap_or("Task");
+            for (item, result) in items.iter().zip(results.iter_mut()) {
+                result["create"] = json!({
+                    "project_key": project_key,
+                    "issue_type": issue_type,
+                    "summary": summary(&item.text),
+                    "description": format!(
+                        "{}\n\nAction item from Confluence page \"{}\": {}",
+                        item.text, title, page_url
+                    )
+                });
+            }
+        }
+
+        Ok(json!({
+            "success": true,
+            "page_id": page_id,
+            "title": title,
+            "url": page_url,
+            "count": results.len(),
+            "action_items": results
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STORAGE: &str = r#"<p>Notes</p>
+<ac:task-list>
+<ac:task><ac:task-id>1</ac:task-id><ac:task-status>incomplete</ac:task-status><ac:task-body><ac:link><ri:user ri:account-id="557058:abc" /></ac:link> Fix the <strong>login</strong> bug by <time datetime="2026-10-20" /></ac:task-body></ac:task>
+<ac:task><ac:task-id>2</ac:task-id><ac:task-status>complete</ac:task-status><ac:task-body>Send recap</ac:task-body></ac:task>
+<ac:task><ac:task-id>3</ac:task-id><ac:task-status>incomplete</ac:task-status><ac:task-body>   </ac:task-body></ac:task>
+</ac:task-list>"#;
+
+    #[test]
+    fn test_parse_action_items() {
+        let items = parse_action_items(STORAGE);
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[0],
+            ActionItem {
+                id: Some("1".to_string()),
+                complete: false,
+                text: "Fix the login bug by".to_string(),
+                assignee_account_id: Some("557058:abc".to_string()),
+                due_date: Some("2026-10-20".to_string()),
+            }
+        );
+        assert!(items[1].complete);
+        assert_eq!(items[1].assignee_account_id, None);
+        assert!(parse_action_items("<p>No tasks</p>").is_empty());
+    }
+
+    #[test]
+    fn test_summary_truncated_to_jira_limit() {
+        assert_eq!(summary("short"), "short");
+        let long = "x".repeat(300);
+        let cut = summary(&long);
+        assert_eq!(cut.chars().count(), MAX_SUMMARY_CHARS);
+        assert!(cut.ends_with('…'));
+    }
+}
@@ -0,0 +1,191 @@
+//! Recently viewed and edited content
+//!
+//! `confluence_recently_viewed` runs the `recentlyViewedContent()` CQL
+//! function for the calling user and, unless `include_edited` is false, a
+//! second search for content they contributed to in the same window.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema, number_prop};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+use super::search_page;
+
+pub struct RecentlyViewedHandler;
+
+const DEFAULT_DAYS: u64 = 7;
+const MAX_DAYS: u64 = 30;
+const DEFAULT_LIMIT: u64 = 25;
+const MAX_LIMIT: u64 = 100;
+
+/// `condition ORDER BY ...` limited to `CONFLUENCE_SPACES_FILTER` when set
+pub fn scoped_cql(condition: &str, order_by: &str, config: &Config) -> String {
+    let scope = super::spaces::cql_scope(&config.confluence_spaces_filter)
+        .map(|scope| format!("{} AND ", scope))
+        .unwrap_or_default();
+    format!("{}{} ORDER BY {}", scope, condition, order_by)
+}
+
+/// `(viewed, edited)` CQL for the last `days`
+pub fn activity_cql(days: u64, limit: u64, config: &Config) -> (String, String) {
+    (
+        scoped_cql(
+            &format!("id in recentlyViewedContent({}, {})", limit, days),
+            "lastmodified DESC",
+            config,
+        ),
+        scoped_cql(
+            &format!(
+                "contributor = currentUser() AND lastmodified >= now(\"-{}d\")",
+                days
+            ),
+            "lastmodified DESC",
+            config,
+        ),
+    )
+}
+
+/// `{id, type, title, space, url, last_modified}` per search result
+pub fn compact_results(data: &Value) -> Vec<Value> {
+    data["results"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|result| {
+            json!({
+                "id": result["content"]["id"],
+                "type": result["content"]["type"],
+                "title": result["content"]["title"].as_str().or(result["title"].as_str()),
+                "space": result["resultGlobalContainer"]["title"],
+                "url": result["url"],
+                "last_modified": result["lastModified"]
+            })
+        })
+        .collect()
+}
+
+#[async_trait]
+impl ToolHandler for RecentlyViewedHandler {
+    fn name(&self) -> &'static str {
+        "confluence_recently_viewed"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "days".to_string(),
+            number_prop("Look-back window in days (max 30)", 7),
+        );
+        props.insert(
+            "limit".to_string(),
+            number_prop("Maximum results per list (max 100)", 25),
+        );
+        props.insert(
+            "include_edited".to_string(),
+            Property::new("boolean")
+                .with_description("Also list content you contributed to in the window")
+                .with_default(json!(true)),
+        );
+        ToolSchema::new(
+            "Pages and blog posts you recently viewed and edited",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let days = args["days"]
+            .as_u64()
+            .unwrap_or(DEFAULT_DAYS)
+            .clamp(1, MAX_DAYS);
+        let limit = args["limit"]
+            .as_u64()
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+        let include_edited = args["include_edited"].as_bool().unwrap_or(true);
+        let (viewed_cql, edited_cql) = activity_cql(days, limit, config);
+
+        let client = create_atlassian_client(config);
+        let url = format!("{}/wiki/rest/api/search", config.get_atlassian_base_url());
+        let auth = create_auth_header(config);
+
+        let viewed = search_page(
+            client.clone(),
+            url.clone(),
+            auth.clone(),
+            vec![("cql".to_string(), viewed_cql)],
+            0,
+            limit as usize,
+        )
+        .await?;
+        let mut result = json!({
+            "success": true,
+            "days": days,
+            "viewed": compact_results(&viewed)
+        });
+
+        if include_edited {
+            let edited = search_page(
+                client,
+                url,
+                auth,
+                vec![("cql".to_string(), edited_cql)],
+                0,
+                limit as usize,
+            )
+            .await?;
+            result["edited"] = json!(compact_results(&edited));
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ConfigBuilder;
+
+    fn create_test_config(confluence_spaces_filter: Vec<String>) -> Config {
+        ConfigBuilder::new()
+            .with(|config| config.confluence_spaces_filter = confluence_spaces_filter)
+            .build()
+    }
+
+    #[test]
+    fn test_activity_cql_scoped_to_spaces() {
+        let (viewed, edited) = activity_cql(3, 25, &create_test_config(vec![]));
+        assert_eq!(
+            viewed,
+            "id in recentlyViewedContent(25, 3) ORDER BY lastmodified DESC"
+        );
+        assert_eq!(
+            edited,
+            "contributor = currentUser() AND lastmodified >= now(\"-3d\") ORDER BY lastmodified DESC"
+        );
+
+        let (viewed, _) = activity_cql(1, 10, &create_test_config(vec!["ENG".to_string()]));
+        assert!(viewed.starts_with("space IN (\"ENG\") AND id in recentlyViewedContent(10, 1)"));
+    }
+
+    #[test]
+    fn test_compact_results() {
+        let data = json!({"results": [{
+            "content": {"id": "42", "type": "page", "title": "Runbook"},
+            "resultGlobalContainer": {"title": "Engineering"},
+            "url": "/spaces/ENG/pages/42",
+            "lastModified": "2026-10-13T09:00:00.000Z"
+        }]});
+        assert_eq!(
+            compact_results(&data),
+            vec![json!({
+                "id": "42", "type": "page", "title": "Runbook", "space": "Engineering",
+                "url": "/spaces/ENG/pages/42", "last_modified": "2026-10-13T09:00:00.000Z"
+            })]
+        );
+    }
+}
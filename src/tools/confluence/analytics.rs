@@ -0,0 +1,225 @@
+//! Page view analytics
+//!
+//! `confluence_get_page_views` reads view and distinct-viewer counts from the
+//! analytics API for given pages or the pages of a space, least read first,
+//! so a cleanup can start with what nobody opens. Analytics exist on the
+//! Standard plan and above; other sites answer 403/404.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::pagination;
+use crate::tools::schema::{ToolSchema, array_prop, number_prop, string_prop};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+use super::activity::scoped_cql;
+use super::search_page;
+
+pub struct GetPageViewsHandler;
+
+const DEFAULT_LIMIT: u64 = 50;
+const MAX_LIMIT: u64 = 100;
+
+/// Pages sorted by views, then viewers, ascending; pages without numbers last
+pub fn sort_least_read(pages: &mut [Value]) {
+    pages.sort_by_key(|page| {
+        (
+            page["views"].as_u64().unwrap_or(u64::MAX),
+            page["viewers"].as_u64().unwrap_or(u64::MAX),
+        )
+    });
+}
+
+async fn count(
+    client: reqwest::Client,
+    auth: String,
+    url: String,
+    from_date: Option<String>,
+) -> Result<Option<u64>> {
+    let mut request = client
+        .get(&url)
+        .header("Authorization", auth)
+        .header("Accept", "application/json");
+    if let Some(from_date) = from_date {
+        request = request.query(&[("fromDate", from_date)]);
+    }
+    let response = request.send().await?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::FORBIDDEN || status == reqwest::StatusCode::NOT_FOUND {
+        anyhow::bail!(
+            "Failed to get page analytics: {} (analytics need Confluence Standard or above)",
+            status
+        );
+    }
+    if !status.is_success() {
+        anyhow::bail!("Failed to get page analytics: {}", status);
+    }
+    let data: Value = response.json().await?;
+    Ok(data["count"].as_u64())
+}
+
+/// Page IDs and titles from `page_ids`/`page_id`, else the pages of `space_key`
+async fn target_pages(
+    client: &reqwest::Client,
+    config: &Config,
+    args: &Value,
+    limit: u64,
+) -> Result<Vec<(String, Value)>> {
+    let mut ids: Vec<String> = args["page_ids"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|id| id.as_str().map(str::to_string))
+        .collect();
+    if let Some(id) = args["page_id"].as_str() {
+        ids.push(id.to_string());
+    }
+    if !ids.is_empty() {
+        ids.truncate(limit as usize);
+        return Ok(ids.into_iter().map(|id| (id, Value::Null)).collect());
+    }
+
+    let space_key = args["space_key"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Pass page_ids, page_id or space_key"))?;
+    let cql = scoped_cql(
+        &format!("space = \"{}\" AND type = page", space_key.replace('"', "")),
+        "lastmodified ASC",
+        config,
+    );
+    let data = search_page(
+        client.clone(),
+        format!("{}/wiki/rest/api/search", config.get_atlassian_base_url()),
+        create_auth_header(config),
+        vec![("cql".to_string(), cql)],
+        0,
+        limit as usize,
+    )
+    .await?;
+    Ok(data["results"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|result| {
+            let id = result["content"]["id"].as_str()?;
+            Some((id.to_string(), result["content"]["title"].clone()))
+        })
+        .collect())
+}
+
+#[async_trait]
+impl ToolHandler for GetPageViewsHandler {
+    fn name(&self) -> &'static str {
+        "confluence_get_page_views"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("page_ids".to_string(), array_prop("Page IDs to report on"));
+        props.insert("page_id".to_string(), string_prop("Single page ID", false));
+        props.insert(
+            "space_key".to_string(),
+            string_prop(
+                "Report on the pages of this space instead (oldest edits first)",
+                false,
+            ),
+        );
+        props.insert(
+            "from_date".to_string(),
+            string_prop(
+                "Only count views since this date (ISO 8601, e.g. 2026-01-01T00:00:00.000Z)",
+                false,
+            ),
+        );
+        props.insert(
+            "limit".to_string(),
+            number_prop("Maximum pages to report (max 100)", 50),
+        );
+        ToolSchema::new(
+            "View and distinct viewer counts per page, least read first (Confluence analytics)",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let limit = args["limit"]
+            .as_u64()
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+        let from_date = args["from_date"].as_str().map(str::to_string);
+
+        let client = create_atlassian_client(config);
+        let pages = target_pages(&client, config, &args, limit).await?;
+
+        let base_url = config.get_atlassian_base_url().to_string();
+        let auth = create_auth_header(config);
+        let indexes: Vec<usize> = (0..pages.len()).collect();
+        let counts = pagination::fetch_offsets(&indexes, |i| {
+            let (client, auth, from_date) = (client.clone(), auth.clone(), from_date.clone());
+            let url = format!(
+                "{}/wiki/rest/api/analytics/content/{}",
+                base_url, pages[i].0
+            );
+            async move {
+                let views = count(
+                    client.clone(),
+                    auth.clone(),
+                    format!("{}/views", url),
+                    from_date.clone(),
+                )
+                .await?;
+                let viewers = count(client, auth, format!("{}/viewers", url), from_date).await?;
+                Ok(json!({"views": views, "viewers": viewers}))
+            }
+        })
+        .await?;
+
+        let mut results: Vec<Value> = pages
+            .into_iter()
+            .zip(counts)
+            .map(|((id, title), counts)| {
+                json!({
+                    "id": id,
+                    "title": title,
+                    "views": counts["views"],
+                    "viewers": counts["viewers"]
+                })
+            })
+            .collect();
+        sort_least_read(&mut results);
+
+        let mut result = json!({
+            "success": true,
+            "total": results.len(),
+            "pages": results
+        });
+        if let Some(from_date) = args["from_date"].as_str() {
+            result["from_date"] = json!(from_date);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_least_read() {
+        let mut pages = vec![
+            json!({"id": "1", "views": 40, "viewers": 9}),
+            json!({"id": "2", "views": null, "viewers": null}),
+            json!({"id": "3", "views": 2, "viewers": 2}),
+            json!({"id": "4", "views": 2, "viewers": 1}),
+        ];
+        sort_least_read(&mut pages);
+        let ids: Vec<&str> = pages.iter().filter_map(|p| p["id"].as_str()).collect();
+        assert_eq!(ids, vec!["4", "3", "1", "2"]);
+    }
+}
@@ -0,0 +1,124 @@
+//! Child page trees for `confluence_get_page_children`
+//!
+//! `depth: 1` is one page of direct children, continued with `cursor`. A
+//! larger depth also fetches the first `limit` children of every child,
+//! level by level, nesting them under `children`; a node whose own children
+//! did not fit gets `has_more_children`. The walk stops at `MAX_DEPTH`
+//! levels or `MAX_DESCENDANTS` pages, whichever comes first.
+
+use anyhow::Result;
+use serde_json::{Value, json};
+
+use crate::client::Confluence;
+
+/// Levels below the page, including the direct children
+pub const MAX_DEPTH: u64 = 5;
+/// Pages returned by one call, across all levels
+pub const MAX_DESCENDANTS: usize = 200;
+
+/// Pages nested by level; `truncated` when `MAX_DESCENDANTS` cut the walk
+#[derive(Debug, Default)]
+pub struct Tree {
+    pub children: Vec<Value>,
+    pub pages: usize,
+    pub truncated: bool,
+}
+
+/// `nodes` in walk order (parents before their children) as nested `key`
+/// arrays; `None` parents are the top level
+pub fn nest(nodes: Vec<(Option<usize>, Value)>, key: &str) -> Vec<Value> {
+    let mut children: Vec<Vec<Value>> = vec![Vec::new(); nodes.len()];
+    let mut top = Vec::new();
+    for (index, (parent, mut node)) in nodes.into_iter().enumerate().rev() {
+        let mut own = std::mem::take(&mut children[index]);
+        if !own.is_empty() {
+            own.reverse();
+            node[key] = Value::Array(own);
+        }
+        match parent {
+            Some(parent) => children[parent].push(node),
+            None => top.push(node),
+        }
+    }
+    top.reverse();
+    top
+}
+
+/// `top` (the page's direct children) and their descendants down to `depth`
+/// levels; `query` is sent for every level and should carry `limit`
+pub async fn descendants(
+    confluence: &Confluence<'_>,
+    top: Vec<Value>,
+    query: &[(String, String)],
+    depth: u64,
+) -> Result<Tree> {
+    let mut truncated = top.len() > MAX_DESCENDANTS;
+    let mut nodes: Vec<(Option<usize>, Value)> = top
+        .into_iter()
+        .take(MAX_DESCENDANTS)
+        .map(|page| (None, page))
+        .collect();
+    let mut level: Vec<usize> = (0..nodes.len()).collect();
+
+    for _ in 1..depth.min(MAX_DEPTH) {
+        let mut next_level = Vec::new();
+        for parent in level {
+            if nodes.len() >= MAX_DESCENDANTS {
+                truncated = true;
+                break;
+            }
+            let Some(id) = nodes[parent].1["id"].as_str().map(String::from) else {
+                continue;
+            };
+            let listing = confluence.get_page_children(&id, query).await?;
+            if listing.next.is_some() {
+                nodes[parent].1["has_more_children"] = json!(true);
+            }
+            for page in listing.results {
+                if nodes.len() >= MAX_DESCENDANTS {
+                    truncated = true;
+                    break;
+                }
+                next_level.push(nodes.len());
+                nodes.push((Some(parent), serde_json::to_value(page)?));
+            }
+        }
+        if next_level.is_empty() || truncated {
+            break;
+        }
+        level = next_level;
+    }
+
+    Ok(Tree {
+        pages: nodes.len(),
+        children: nest(nodes, "children"),
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nest_keeps_walk_order() {
+        let nodes = vec![
+            (None, json!({"id": "1"})),
+            (None, json!({"id": "2"})),
+            (Some(0), json!({"id": "11"})),
+            (Some(0), json!({"id": "12"})),
+            (Some(1), json!({"id": "21"})),
+            (Some(2), json!({"id": "111"})),
+        ];
+        assert_eq!(
+            nest(nodes, "children"),
+            vec![
+                json!({"id": "1", "children": [
+                    {"id": "11", "children": [{"id": "111"}]},
+                    {"id": "12"}
+                ]}),
+                json!({"id": "2", "children": [{"id": "21"}]})
+            ]
+        );
+    }
+}
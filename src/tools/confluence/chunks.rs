@@ -0,0 +1,250 @@
+//! Large page bodies in bounded chunks
+//!
+//! `confluence_get_page_chunk` returns up to `max_chars` characters of a
+//! page's storage body starting at `offset`, with `has_more` and
+//! `next_offset` to continue from. Chunks end before a tag or after a line
+//! break when one is in the second half of the window, so markup is rarely
+//! split mid-tag. The result names the page version it was cut from; passing
+//! it back as `version` keeps every chunk from the same revision.
+//! `format: text` chunks the plain-text rendering (`plain_text.rs`) instead.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+use crate::client::Confluence;
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::args::parse_args;
+use crate::tools::confluence::{page_links, plain_text};
+use crate::tools::schema::{Property, ToolSchema, number_prop, string_prop};
+
+/// Characters per chunk unless `max_chars` says otherwise
+pub const DEFAULT_CHUNK_CHARS: usize = 20_000;
+/// Largest `max_chars` accepted
+pub const MAX_CHUNK_CHARS: usize = 100_000;
+
+pub struct GetPageChunkHandler;
+
+#[derive(Deserialize)]
+struct PageChunkArgs {
+    page_id: String,
+    #[serde(default)]
+    offset: usize,
+    max_chars: Option<usize>,
+    version: Option<u64>,
+    #[serde(default)]
+    format: ChunkFormat,
+}
+
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum ChunkFormat {
+    #[default]
+    Storage,
+    Text,
+}
+
+/// `[start, end)` of the body, in characters
+#[derive(Debug, PartialEq, Eq)]
+pub struct Chunk<'a> {
+    pub text: &'a str,
+    pub start: usize,
+    pub end: usize,
+    pub total: usize,
+}
+
+impl Chunk<'_> {
+    pub fn has_more(&self) -> bool {
+        self.end < self.total
+    }
+}
+
+/// Up to `max_chars` characters of `body` from character `offset`
+pub fn chunk(body: &str, offset: usize, max_chars: usize) -> Chunk<'_> {
+    let total = body.chars().count();
+    let start = offset.min(total);
+    let byte_at = |n: usize| body.char_indices().nth(n).map_or(body.len(), |(i, _)| i);
+
+    let from = byte_at(start);
+    let mut end = start.saturating_add(max_chars).min(total);
+    let mut to = byte_at(end);
+    if end < total {
+        // Before the last tag or after the last line break
+        let window = &body[from..to];
+        let cut = window
+            .rfind(['<', '\n'])
+            .map(|i| {
+                if window.as_bytes()[i] == b'\n' {
+                    i + 1
+                } else {
+                    i
+                }
+            })
+            .filter(|&cut| cut > 0 && cut >= window.len() / 2);
+        if let Some(cut) = cut {
+            to = from + cut;
+            end = start + window[..cut].chars().count();
+        }
+    }
+
+    Chunk {
+        text: &body[from..to],
+        start,
+        end,
+        total,
+    }
+}
+
+#[async_trait]
+impl ToolHandler for GetPageChunkHandler {
+    fn name(&self) -> &'static str {
+        "confluence_get_page_chunk"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "page_id".to_string(),
+            string_prop("Page ID or page link", true),
+        );
+        props.insert(
+            "offset".to_string(),
+            number_prop(
+                "Character offset to start from: 0, then the previous chunk's next_offset",
+                0,
+            )
+            .with_range(Some(0.0), None),
+        );
+        props.insert(
+            "max_chars".to_string(),
+            number_prop(
+                "Characters per chunk (1-100000)",
+                DEFAULT_CHUNK_CHARS as i32,
+            )
+            .with_range(Some(1.0), Some(MAX_CHUNK_CHARS as f64)),
+        );
+        props.insert(
+            "version".to_string(),
+            Property::new("integer")
+                .with_description(
+                    "Page version to read; pass the first chunk's version so later chunks match it (default: current)",
+                )
+                .with_range(Some(1.0), None),
+        );
+        props.insert(
+            "format".to_string(),
+            Property::new("string")
+                .with_description("storage: HTML storage format; text: plain text, a line per block, cached by version with ATLASSIAN_CACHE_DIR (default: storage)")
+                .with_default(json!("storage"))
+                .with_enum(vec![json!("storage"), json!("text")]),
+        );
+        ToolSchema::new(
+            "Read a large Confluence page's body in chunks, continuing from next_offset",
+            props,
+            vec!["page_id".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let PageChunkArgs {
+            page_id,
+            offset,
+            max_chars,
+            version,
+            format,
+        } = parse_args(&args)?;
+        let max_chars = max_chars
+            .unwrap_or(DEFAULT_CHUNK_CHARS)
+            .clamp(1, MAX_CHUNK_CHARS);
+
+        let confluence = Confluence::new(config);
+        let page_id = page_links::resolve_page_id(confluence.http(), config, &page_id).await?;
+        let (title, version, body) = match format {
+            ChunkFormat::Storage => {
+                let page = confluence.get_page_storage(&page_id, version).await?;
+                let version = page.version_number().unwrap_or_default();
+                let body = page.storage().unwrap_or_default().to_string();
+                (page.title, version, body)
+            }
+            ChunkFormat::Text => {
+                let page = plain_text::page_text(&confluence, config, &page_id, version).await?;
+                (page.title, page.version, page.text)
+            }
+        };
+        let total = body.chars().count();
+        if offset > 0 && offset >= total {
+            anyhow::bail!(
+                "Offset {} is past the end of page {} ({} characters)",
+                offset,
+                page_id,
+                total
+            );
+        }
+
+        let chunk = chunk(&body, offset, max_chars);
+        let mut result = json!({
+            "success": true,
+            "page_id": page_id,
+            "title": title,
+            "version": version,
+            "offset": chunk.start,
+            "total_chars": chunk.total,
+            "has_more": chunk.has_more(),
+            "content": chunk.text
+        });
+        if chunk.has_more() {
+            result["next_offset"] = json!(chunk.end);
+            result["continuation"] = json!(format!(
+                "Call confluence_get_page_chunk with page_id {}, version {}, offset {}{} for the rest",
+                page_id,
+                version,
+                chunk.end,
+                if format == ChunkFormat::Text {
+                    " and format text"
+                } else {
+                    ""
+                }
+            ));
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_ends_before_a_tag() {
+        let body = "<p>one</p><p>two</p><p>three</p>";
+        let first = chunk(body, 0, 14);
+        assert_eq!(first.text, "<p>one</p>");
+        assert!(first.has_more());
+
+        let second = chunk(body, first.end, 14);
+        assert_eq!(second.text, "<p>two</p>");
+        let rest = chunk(body, second.end, 100);
+        assert_eq!(rest.text, "<p>three</p>");
+        assert!(!rest.has_more());
+    }
+
+    #[test]
+    fn test_chunk_cuts_hard_without_a_boundary() {
+        let body = "abcdefghij";
+        let first = chunk(body, 0, 4);
+        assert_eq!((first.text, first.end, first.total), ("abcd", 4, 10));
+        assert_eq!(chunk(body, 8, 4).text, "ij");
+        assert_eq!(chunk(body, 20, 4).text, "");
+    }
+
+    #[test]
+    fn test_chunk_offsets_are_characters() {
+        let body = "한국어 텍스트입니다";
+        let first = chunk(body, 0, 3);
+        assert_eq!(first.text, "한국어");
+        assert_eq!(chunk(body, first.end, 100).text, " 텍스트입니다");
+    }
+}
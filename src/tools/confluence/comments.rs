@@ -0,0 +1,336 @@
+//! Footer and inline comments
+//!
+//! `confluence_add_comment` posts a Markdown comment (see `markdown.rs`) on a
+//! page, or a reply under `parent_comment_id`. `@Display Name` mentions are
+//! resolved as in Jira comments and become `ri:user` links, which notify the
+//! mentioned user.
+//!
+//! `confluence_get_comments` reads them as threads: v2 lists only top-level
+//! comments, so [`threads`] fetches each comment's replies level by level and
+//! nests them under `replies` (each with `parent_id`), up to `MAX_COMMENTS`
+//! per kind. Inline comments can be limited to open or resolved threads.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::client::Confluence;
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::jira::user_mentions::{self, Segment};
+use crate::tools::schema::{ToolSchema, string_prop};
+use crate::tools::write_policy;
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+use super::children::nest;
+use super::markdown::markdown_to_storage;
+
+pub struct AddCommentHandler;
+
+/// Comments read per kind, top-level and replies together
+pub const MAX_COMMENTS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentKind {
+    Footer,
+    Inline,
+}
+
+impl CommentKind {
+    fn path(self) -> &'static str {
+        match self {
+            CommentKind::Footer => "footer-comments",
+            CommentKind::Inline => "inline-comments",
+        }
+    }
+}
+
+/// Which inline comment threads to read; footer comments are never resolved
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Resolution {
+    #[default]
+    All,
+    Open,
+    Resolved,
+}
+
+impl Resolution {
+    /// `resolutionStatus` values kept; empty for all
+    fn statuses(self) -> &'static [&'static str] {
+        match self {
+            Resolution::All => &[],
+            Resolution::Open => &["open", "reopened"],
+            Resolution::Resolved => &["resolved"],
+        }
+    }
+
+    pub fn keeps(self, comment: &Value) -> bool {
+        let statuses = self.statuses();
+        statuses.is_empty()
+            || comment["resolutionStatus"]
+                .as_str()
+                .is_some_and(|status| statuses.contains(&status))
+    }
+}
+
+/// Top-level comments with their replies nested under `replies`
+#[derive(Debug, Default)]
+pub struct Threads {
+    pub comments: Vec<Value>,
+    /// Comments read, replies included
+    pub total: usize,
+    /// `MAX_COMMENTS` cut the walk or a list had more pages
+    pub truncated: bool,
+}
+
+async fn get_results(
+    client: &reqwest::Client,
+    config: &Config,
+    url: &str,
+    query: &[(String, String)],
+) -> Result<(Vec<Value>, bool)> {
+    let response = client
+        .get(url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .query(query)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get comments: {}", response.status());
+    }
+    let mut data: Value = response.json().await?;
+    let has_more = data["_links"]["next"].is_string();
+    let results = match data["results"].take() {
+        Value::Array(results) => results,
+        _ => Vec::new(),
+    };
+    Ok((results, has_more))
+}
+
+/// The page's `kind` comments as threads; `query` is sent with every list
+pub async fn threads(
+    client: &reqwest::Client,
+    config: &Config,
+    page_id: &str,
+    kind: CommentKind,
+    query: &[(String, String)],
+    resolution: Resolution,
+) -> Result<Threads> {
+    let base_url = format!("{}/wiki/api/v2", config.get_atlassian_base_url());
+    let mut top_query = query.to_vec();
+    if kind == CommentKind::Inline && resolution != Resolution::All {
+        top_query.push((
+            "resolution-status".to_string(),
+            resolution.statuses().join(","),
+        ));
+    }
+    let (top, mut truncated) = get_results(
+        client,
+        config,
+        &format!("{}/pages/{}/{}", base_url, page_id, kind.path()),
+        &top_query,
+    )
+    .await?;
+
+    let mut nodes: Vec<(Option<usize>, Value)> = top
+        .into_iter()
+        .filter(|comment| kind == CommentKind::Footer || resolution.keeps(comment))
+        .map(|comment| (None, comment))
+        .collect();
+    if nodes.len() > MAX_COMMENTS {
+        nodes.truncate(MAX_COMMENTS);
+        truncated = true;
+    }
+    let mut next = 0;
+    while next < nodes.len() {
+        if nodes.len() >= MAX_COMMENTS {
+            truncated = true;
+            break;
+        }
+        let parent = next;
+        next += 1;
+        let Some(id) = nodes[parent].1["id"].as_str().map(String::from) else {
+            continue;
+        };
+        let (replies, has_more) = get_results(
+            client,
+            config,
+            &format!("{}/{}/{}/children", base_url, kind.path(), id),
+            query,
+        )
+        .await?;
+        truncated |= has_more;
+        for mut reply in replies {
+            if nodes.len() >= MAX_COMMENTS {
+                truncated = true;
+                break;
+            }
+            reply["parent_id"] = json!(id);
+            nodes.push((Some(parent), reply));
+        }
+    }
+
+    Ok(Threads {
+        total: nodes.len(),
+        comments: nest(nodes, "replies"),
+        truncated,
+    })
+}
+
+/// Private-use markers that survive Markdown conversion untouched
+const MENTION_OPEN: char = '\u{E000}';
+const MENTION_CLOSE: char = '\u{E001}';
+
+/// Storage body for Markdown text with resolved mentions
+pub fn segments_to_storage(segments: &[Segment]) -> String {
+    let mut text = String::new();
+    let mut account_ids = Vec::new();
+    for segment in segments {
+        match segment {
+            Segment::Text(part) => text.push_str(&part.replace([MENTION_OPEN, MENTION_CLOSE], "")),
+            Segment::Mention(mention) => {
+                text.push_str(&format!(
+                    "{}{}{}",
+                    MENTION_OPEN,
+                    account_ids.len(),
+                    MENTION_CLOSE
+                ));
+                account_ids.push(mention.account_id.as_str());
+            }
+        }
+    }
+
+    let mut storage = markdown_to_storage(&text);
+    for (i, account_id) in account_ids.iter().enumerate() {
+        storage = storage.replace(
+            &format!("{}{}{}", MENTION_OPEN, i, MENTION_CLOSE),
+            &format!(
+                "<ac:link><ri:user ri:account-id=\"{}\" /></ac:link>",
+                account_id.replace('"', "&quot;")
+            ),
+        );
+    }
+    storage
+}
+
+#[async_trait]
+impl ToolHandler for AddCommentHandler {
+    fn name(&self) -> &'static str {
+        "confluence_add_comment"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("page_id".to_string(), string_prop("Page ID", true));
+        props.insert(
+            "comment".to_string(),
+            string_prop(
+                "Comment in Markdown; `@Display Name` or `@[Full Name]` mention users",
+                true,
+            ),
+        );
+        props.insert(
+            "parent_comment_id".to_string(),
+            string_prop("Footer comment ID to reply to", false),
+        );
+        ToolSchema::new(
+            "Add a footer comment to a page, with @mentions",
+            props,
+            vec!["page_id".to_string(), "comment".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let page_id = args["page_id"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing page_id"))?;
+        let comment = args["comment"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing comment"))?;
+
+        let client = create_atlassian_client(config);
+        let confluence = Confluence::with_client(config, client.clone());
+        write_policy::check_page(&confluence, config, page_id).await?;
+        let (segments, unresolved_mentions) =
+            user_mentions::resolve(&client, config, comment).await?;
+
+        let mut body = json!({
+            "body": {
+                "representation": "storage",
+                "value": segments_to_storage(&segments)
+            }
+        });
+        match args["parent_comment_id"].as_str() {
+            Some(parent_id) => body["parentCommentId"] = json!(parent_id),
+            None => body["pageId"] = json!(page_id),
+        }
+
+        let url = format!(
+            "{}/wiki/api/v2/footer-comments",
+            config.get_atlassian_base_url()
+        );
+        let response = client
+            .post(&url)
+            .header("Authorization", create_auth_header(config))
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to add comment: {}", error);
+        }
+
+        let data: Value = response.json().await?;
+        let mut result = json!({
+            "success": true,
+            "comment_id": data["id"],
+            "page_id": page_id
+        });
+        if let Some(parent_id) = args["parent_comment_id"].as_str() {
+            result["parent_comment_id"] = json!(parent_id);
+        }
+        if !unresolved_mentions.is_empty() {
+            result["unresolved_mentions"] = json!(unresolved_mentions);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::jira::user_mentions::Mention;
+
+    #[test]
+    fn test_segments_to_storage_links_mentions() {
+        let storage = segments_to_storage(&[
+            Segment::Text("Thanks ".to_string()),
+            Segment::Mention(Mention {
+                account_id: "557058:abc".to_string(),
+                display_name: "Jane Doe".to_string(),
+            }),
+            Segment::Text(", **approved** <done>".to_string()),
+        ]);
+        assert_eq!(
+            storage,
+            "<p>Thanks <ac:link><ri:user ri:account-id=\"557058:abc\" /></ac:link>, <strong>approved</strong> &lt;done&gt;</p>"
+        );
+    }
+
+    #[test]
+    fn test_resolution_keeps_matching_threads() {
+        let open = json!({"resolutionStatus": "reopened"});
+        let resolved = json!({"resolutionStatus": "resolved"});
+        assert!(Resolution::Open.keeps(&open));
+        assert!(!Resolution::Open.keeps(&resolved));
+        assert!(Resolution::Resolved.keeps(&resolved));
+        assert!(Resolution::All.keeps(&json!({})));
+        assert!(!Resolution::Resolved.keeps(&json!({})));
+    }
+}
@@ -0,0 +1,475 @@
+//! Partial page reads and edits
+//!
+//! Instead of round-tripping the whole body through the model (which drops
+//! macros it didn't echo back), these tools fetch the storage body, splice
+//! in a sanitized fragment and write it back. A 409 re-fetches and re-applies
+//! the edit to the newer body. `confluence_get_page_section` returns the
+//! content under one heading, found the same way `confluence_replace_section`
+//! finds it.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::client::Confluence;
+use crate::client::confluence::VersionConflict;
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::confluence::page_links;
+use crate::tools::schema::{Property, ToolSchema, string_prop};
+use crate::tools::write_policy;
+use std::collections::HashMap;
+
+use super::{MAX_UPDATE_ATTEMPTS, sanitize};
+
+pub struct AppendToPageHandler;
+pub struct ReplaceSectionHandler;
+pub struct GetPageSectionHandler;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    pub level: u8,
+    pub text: String,
+    /// Byte offset of `<hN`
+    pub start: usize,
+    /// Byte offset just past `</hN>`
+    pub end: usize,
+}
+
+/// Level of an opening heading tag such as `<h2>` or `<h2 id="x">`
+fn heading_level(tag: &str) -> Option<u8> {
+    let bytes = tag.as_bytes();
+    let level = *bytes.get(2)?;
+    (matches!(bytes.get(1), Some(b'h' | b'H'))
+        && (b'1'..=b'6').contains(&level)
+        && matches!(bytes.get(3), Some(b'>' | b'/') | Some(b' ' | b'\t' | b'\n')))
+    .then_some(level - b'0')
+}
+
+/// Visible text of a storage fragment: tags removed, common entities decoded, whitespace collapsed
+pub(super) fn visible_text(inner: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in inner.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Calls `visit(offset, tag)` for each tag, skipping comments and CDATA;
+/// stops early when `visit` returns `false`
+fn for_each_tag(body: &str, from: usize, mut visit: impl FnMut(usize, &str) -> bool) {
+    let mut i = from;
+    while let Some(offset) = body[i..].find('<') {
+        let at = i + offset;
+        let rest = &body[at..];
+        let skip_to = if rest.starts_with("<![CDATA[") {
+            rest.find("]]>").map(|e| e + 3)
+        } else if rest.starts_with("<!--") {
+            rest.find("-->").map(|e| e + 3)
+        } else {
+            let Some(close) = rest.find('>') else {
+                return;
+            };
+            if !visit(at, &rest[..=close]) {
+                return;
+            }
+            Some(close + 1)
+        };
+        match skip_to {
+            Some(len) => i = at + len,
+            None => return,
+        }
+    }
+}
+
+/// Every heading in document order
+pub fn find_headings(body: &str) -> Vec<Heading> {
+    let mut headings = Vec::new();
+    for_each_tag(body, 0, |at, tag| {
+        if let Some(level) = heading_level(tag) {
+            let close = format!("</h{}>", level);
+            let inner_start = at + tag.len();
+            if let Some(len) = body[inner_start..].to_ascii_lowercase().find(&close) {
+                headings.push(Heading {
+                    level,
+                    text: visible_text(&body[inner_start..inner_start + len]),
+                    start: at,
+                    end: inner_start + len + close.len(),
+                });
+            }
+        }
+        true
+    });
+    headings
+}
+
+/// End of the section that starts at `from`: the next heading of the same or
+/// higher level, the close of the element containing the heading, or the end
+fn section_end(body: &str, from: usize, level: u8) -> usize {
+    let mut depth = 0i32;
+    let mut end = body.len();
+    for_each_tag(body, from, |at, tag| {
+        if tag.starts_with("</") {
+            depth -= 1;
+            if depth < 0 {
+                end = at;
+                return false;
+            }
+        } else if !(tag.ends_with("/>") || tag.starts_with("<!") || tag.starts_with("<?")) {
+            if depth == 0 && heading_level(tag).is_some_and(|l| l <= level) {
+                end = at;
+                return false;
+            }
+            depth += 1;
+        }
+        true
+    });
+    end
+}
+
+/// A heading and where its content ends
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub heading: Heading,
+    /// Byte offset where the content under the heading ends
+    pub end: usize,
+}
+
+impl Section {
+    /// The storage content under the heading, subsections included
+    pub fn content<'a>(&self, body: &'a str) -> &'a str {
+        &body[self.heading.end..self.end]
+    }
+}
+
+/// The first section whose heading matches `heading` (case-insensitive)
+pub fn find_section(body: &str, heading: &str) -> Result<Section> {
+    let headings = find_headings(body);
+    let wanted = visible_text(heading).to_lowercase();
+    let Some(found) = headings.iter().find(|h| h.text.to_lowercase() == wanted) else {
+        let available: Vec<&str> = headings.iter().map(|h| h.text.as_str()).collect();
+        anyhow::bail!(
+            "Heading '{}' not found; available headings: {}",
+            heading,
+            if available.is_empty() {
+                "(none)".to_string()
+            } else {
+                available.join(", ")
+            }
+        );
+    };
+
+    Ok(Section {
+        end: section_end(body, found.end, found.level),
+        heading: found.clone(),
+    })
+}
+
+/// Replaces the content under the first heading matching `heading`
+/// (case-insensitive), keeping the heading itself
+pub fn replace_section(body: &str, heading: &str, content: &str) -> Result<String> {
+    let section = find_section(body, heading)?;
+    Ok(format!(
+        "{}{}{}",
+        &body[..section.heading.end],
+        content,
+        &body[section.end..]
+    ))
+}
+
+/// Fetches, edits and writes back a page, re-applying the edit after a 409
+async fn edit_page(
+    config: &Config,
+    page_id: &str,
+    edit: impl Fn(&str) -> Result<String>,
+) -> Result<Value> {
+    let confluence = Confluence::new(config);
+    write_policy::check_page(&confluence, config, page_id).await?;
+
+    for _ in 0..MAX_UPDATE_ATTEMPTS {
+        let page = confluence.get_page_storage(page_id, None).await?;
+        let version = page
+            .version_number()
+            .ok_or_else(|| anyhow::anyhow!("Failed to get current version"))?;
+
+        let content = edit(page.storage().unwrap_or_default())?;
+        let saved = match confluence
+            .update_page(page_id, &page.title, &content, version + 1, &[])
+            .await
+        {
+            Err(e) if e.is::<VersionConflict>() => {
+                tracing::debug!("Version conflict editing page {}, retrying", page_id);
+                continue;
+            }
+            other => other?,
+        };
+        return Ok(json!({
+            "success": true,
+            "page_id": saved.id,
+            "version": saved.version_number()
+        }));
+    }
+
+    anyhow::bail!(
+        "Failed to update page: version conflict persisted after {} attempts",
+        MAX_UPDATE_ATTEMPTS
+    )
+}
+
+fn required_str<'a>(args: &'a Value, name: &str) -> Result<&'a str> {
+    args[name]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing {}", name))
+}
+
+#[async_trait]
+impl ToolHandler for AppendToPageHandler {
+    fn name(&self) -> &'static str {
+        "confluence_append_to_page"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("page_id".to_string(), string_prop("Page ID", true));
+        props.insert(
+            "content".to_string(),
+            string_prop("Fragment in HTML storage format", true),
+        );
+        props.insert(
+            "position".to_string(),
+            Property::new("string")
+                .with_description("Where to insert (default: end)")
+                .with_default(json!("end"))
+                .with_enum(vec![json!("end"), json!("start")]),
+        );
+        ToolSchema::new(
+            "Append or prepend content to a Confluence page, keeping the rest of the body intact",
+            props,
+            vec!["page_id".to_string(), "content".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let page_id = required_str(&args, "page_id")?;
+        let content = required_str(&args, "content")?;
+        let prepend = match args["position"].as_str() {
+            None | Some("end") => false,
+            Some("start") => true,
+            Some(other) => {
+                anyhow::bail!("Invalid position '{}': expected 'start' or 'end'", other)
+            }
+        };
+
+        let fragment = sanitize::sanitize_storage(content);
+        let mut result = edit_page(config, page_id, |body| {
+            Ok(if prepend {
+                format!("{}{}", fragment.content, body)
+            } else {
+                format!("{}{}", body, fragment.content)
+            })
+        })
+        .await?;
+
+        result["sanitized"] = json!(fragment.changes);
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl ToolHandler for ReplaceSectionHandler {
+    fn name(&self) -> &'static str {
+        "confluence_replace_section"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("page_id".to_string(), string_prop("Page ID", true));
+        props.insert(
+            "heading".to_string(),
+            string_prop("Heading text of the section (case-insensitive)", true),
+        );
+        props.insert(
+            "content".to_string(),
+            string_prop(
+                "New section content in HTML storage format (heading is kept)",
+                true,
+            ),
+        );
+        ToolSchema::new(
+            "Replace the content under a heading, up to the next heading of the same or higher level",
+            props,
+            vec![
+                "page_id".to_string(),
+                "heading".to_string(),
+                "content".to_string(),
+            ],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let page_id = required_str(&args, "page_id")?;
+        let heading = required_str(&args, "heading")?;
+        let content = required_str(&args, "content")?;
+
+        let fragment = sanitize::sanitize_storage(content);
+        let mut result = edit_page(config, page_id, |body| {
+            replace_section(body, heading, &fragment.content)
+        })
+        .await?;
+
+        result["heading"] = json!(heading);
+        result["sanitized"] = json!(fragment.changes);
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl ToolHandler for GetPageSectionHandler {
+    fn name(&self) -> &'static str {
+        "confluence_get_page_section"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "page_id".to_string(),
+            string_prop("Page ID or page link", true),
+        );
+        props.insert(
+            "heading".to_string(),
+            string_prop("Heading text of the section (case-insensitive)", true),
+        );
+        props.insert(
+            "format".to_string(),
+            Property::new("string")
+                .with_description(
+                    "storage: HTML storage format; text: visible text only (default: storage)",
+                )
+                .with_default(json!("storage"))
+                .with_enum(vec![json!("storage"), json!("text")]),
+        );
+        ToolSchema::new(
+            "Get the content under a heading of a Confluence page, up to the next heading of the same or higher level",
+            props,
+            vec!["page_id".to_string(), "heading".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let page_id = required_str(&args, "page_id")?;
+        let heading = required_str(&args, "heading")?;
+        let as_text = match args["format"].as_str() {
+            None | Some("storage") => false,
+            Some("text") => true,
+            Some(other) => {
+                anyhow::bail!("Invalid format '{}': expected 'storage' or 'text'", other)
+            }
+        };
+
+        let confluence = Confluence::new(config);
+        let page_id = page_links::resolve_page_id(confluence.http(), config, page_id).await?;
+        let page = confluence.get_page_storage(&page_id, None).await?;
+        let body = page.storage().unwrap_or_default();
+        let section = find_section(body, heading)?;
+
+        let content = section.content(body);
+        let subsections: Vec<String> = find_headings(content).into_iter().map(|h| h.text).collect();
+        Ok(json!({
+            "success": true,
+            "page_id": page_id,
+            "title": page.title,
+            "version": page.version_number(),
+            "heading": section.heading.text,
+            "level": section.heading.level,
+            "subsections": subsections,
+            "content": if as_text { visible_text(content) } else { content.to_string() }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "<p>Intro</p><h1>Setup</h1><p>old</p><h2>Details</h2><p>more</p><h1>Usage &amp; Tips</h1><p>use</p>";
+
+    #[test]
+    fn test_find_headings() {
+        let headings = find_headings(DOC);
+        let texts: Vec<(u8, &str)> = headings
+            .iter()
+            .map(|h| (h.level, h.text.as_str()))
+            .collect();
+        assert_eq!(
+            texts,
+            vec![(1, "Setup"), (2, "Details"), (1, "Usage & Tips")]
+        );
+    }
+
+    #[test]
+    fn test_replace_section_includes_subsections() {
+        let out = replace_section(DOC, "setup", "<p>new</p>").unwrap();
+        assert_eq!(
+            out,
+            "<p>Intro</p><h1>Setup</h1><p>new</p><h1>Usage &amp; Tips</h1><p>use</p>"
+        );
+    }
+
+    #[test]
+    fn test_replace_section_stops_at_same_level() {
+        let out = replace_section(DOC, "Details", "<p>x</p>").unwrap();
+        assert!(out.contains("<h2>Details</h2><p>x</p><h1>Usage"));
+    }
+
+    #[test]
+    fn test_replace_last_section() {
+        let out = replace_section(DOC, "Usage & Tips", "<p>end</p>").unwrap();
+        assert!(out.ends_with("<h1>Usage &amp; Tips</h1><p>end</p>"));
+    }
+
+    #[test]
+    fn test_replace_section_stays_inside_container() {
+        let body = "<ac:layout-cell><h2>A</h2><p>a</p></ac:layout-cell><ac:layout-cell><p>b</p></ac:layout-cell>";
+        let out = replace_section(body, "A", "<p>new</p>").unwrap();
+        assert_eq!(
+            out,
+            "<ac:layout-cell><h2>A</h2><p>new</p></ac:layout-cell><ac:layout-cell><p>b</p></ac:layout-cell>"
+        );
+    }
+
+    #[test]
+    fn test_replace_section_ignores_cdata() {
+        let body = "<h2>Code</h2><ac:plain-text-body><![CDATA[<h2>not a heading</h2>]]></ac:plain-text-body><h2>Next</h2>";
+        assert_eq!(find_headings(body).len(), 2);
+        let out = replace_section(body, "Code", "").unwrap();
+        assert_eq!(out, "<h2>Code</h2><h2>Next</h2>");
+    }
+
+    #[test]
+    fn test_find_section_content() {
+        let section = find_section(DOC, "SETUP").unwrap();
+        assert_eq!(section.heading.text, "Setup");
+        assert_eq!(
+            section.content(DOC),
+            "<p>old</p><h2>Details</h2><p>more</p>"
+        );
+        let last = find_section(DOC, "Usage & Tips").unwrap();
+        assert_eq!(last.content(DOC), "<p>use</p>");
+    }
+
+    #[test]
+    fn test_replace_section_missing_heading() {
+        let err = replace_section(DOC, "Nope", "").unwrap_err().to_string();
+        assert!(err.contains("available headings: Setup, Details, Usage & Tips"));
+    }
+}
@@ -0,0 +1,230 @@
+//! Page export to PDF or Word
+//!
+//! Confluence Cloud has no REST endpoint for exports; the same actions the UI
+//! uses are called instead. `output: "url"` returns the export link for a
+//! signed-in browser, `output: "base64"` downloads the file with API auth.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema, string_prop};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+/// Largest export returned inline as base64
+pub const MAX_EXPORT_BYTES: usize = 10 * 1024 * 1024;
+
+pub struct ExportPageHandler;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Pdf,
+    Word,
+}
+
+impl ExportFormat {
+    pub fn parse(value: &Value) -> Result<Self> {
+        match value.as_str() {
+            None | Some("pdf") => Ok(Self::Pdf),
+            Some("word") => Ok(Self::Word),
+            Some(other) => anyhow::bail!("Invalid format '{}': expected 'pdf' or 'word'", other),
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Pdf => "pdf",
+            Self::Word => "doc",
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Pdf => "application/pdf",
+            Self::Word => "application/msword",
+        }
+    }
+}
+
+/// Export action URL for a page
+pub fn export_url(config: &Config, page_id: &str, format: ExportFormat) -> String {
+    let base_url = config.get_atlassian_base_url();
+    match format {
+        ExportFormat::Pdf => format!(
+            "{}/wiki/spaces/flyingpdf/pdfpageexport.action?pageId={}",
+            base_url, page_id
+        ),
+        ExportFormat::Word => format!("{}/wiki/exportword?pageId={}", base_url, page_id),
+    }
+}
+
+#[async_trait]
+impl ToolHandler for ExportPageHandler {
+    fn name(&self) -> &'static str {
+        "confluence_export_page"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("page_id".to_string(), string_prop("Page ID", true));
+        props.insert(
+            "format".to_string(),
+            Property::new("string")
+                .with_description("Export format (default: pdf)")
+                .with_default(json!("pdf"))
+                .with_enum(vec![json!("pdf"), json!("word")]),
+        );
+        props.insert(
+            "output".to_string(),
+            Property::new("string")
+                .with_description(
+                    "url: browser download link; base64: file content (max 10MB)".to_string(),
+                )
+                .with_default(json!("url"))
+                .with_enum(vec![json!("url"), json!("base64")]),
+        );
+        ToolSchema::new(
+            "Export a Confluence page as PDF or Word",
+            props,
+            vec!["page_id".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let page_id = args["page_id"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing page_id"))?;
+        if page_id.is_empty() || !page_id.chars().all(|c| c.is_ascii_digit()) {
+            anyhow::bail!("Invalid page_id '{}': expected a numeric ID", page_id);
+        }
+        let format = ExportFormat::parse(&args["format"])?;
+        let output = args["output"].as_str().unwrap_or("url");
+        let url = export_url(config, page_id, format);
+        let filename = format!("page-{}.{}", page_id, format.extension());
+
+        match output {
+            "url" => {
+                return Ok(json!({
+                    "success": true,
+                    "page_id": page_id,
+                    "download_url": url,
+                    "filename": filename
+                }));
+            }
+            "base64" => {}
+            other => anyhow::bail!("Invalid output '{}': expected 'url' or 'base64'", other),
+        }
+
+        let client = create_atlassian_client(config);
+        let mut response = client
+            .get(&url)
+            .header("Authorization", create_auth_header(config))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to export page: {}", response.status());
+        }
+
+        // A login or error page comes back as HTML with a 200
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        if content_type.starts_with("text/html") {
+            anyhow::bail!("Export did not return a document; check page permissions");
+        }
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+            if bytes.len() > MAX_EXPORT_BYTES {
+                anyhow::bail!(
+                    "Export exceeds {} bytes; use output 'url' instead",
+                    MAX_EXPORT_BYTES
+                );
+            }
+        }
+
+        Ok(json!({
+            "success": true,
+            "page_id": page_id,
+            "filename": filename,
+            "content_type": format.content_type(),
+            "size": bytes.len(),
+            "content_base64": STANDARD.encode(&bytes)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ConfigBuilder;
+
+    fn create_test_config() -> Config {
+        ConfigBuilder::new().build()
+    }
+
+    #[test]
+    fn test_export_format_parse() {
+        assert_eq!(
+            ExportFormat::parse(&Value::Null).unwrap(),
+            ExportFormat::Pdf
+        );
+        assert_eq!(
+            ExportFormat::parse(&json!("word")).unwrap(),
+            ExportFormat::Word
+        );
+        assert!(ExportFormat::parse(&json!("html")).is_err());
+    }
+
+    #[test]
+    fn test_export_url() {
+        let config = create_test_config();
+        assert_eq!(
+            export_url(&config, "12345", ExportFormat::Pdf),
+            "https://test.atlassian.net/wiki/spaces/flyingpdf/pdfpageexport.action?pageId=12345"
+        );
+        assert_eq!(
+            export_url(&config, "12345", ExportFormat::Word),
+            "https://test.atlassian.net/wiki/exportword?pageId=12345"
+        );
+    }
+
+    #[test]
+    fn test_export_url_output() {
+        let handler = ExportPageHandler;
+        let config = create_test_config();
+        let args = json!({"page_id": "12345", "format": "word"});
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(handler.execute(args, &config)).unwrap();
+
+        assert_eq!(result["filename"], "page-12345.doc");
+        assert!(
+            result["download_url"]
+                .as_str()
+                .unwrap()
+                .ends_with("/wiki/exportword?pageId=12345")
+        );
+    }
+
+    #[test]
+    fn test_export_rejects_invalid_page_id() {
+        let handler = ExportPageHandler;
+        let config = create_test_config();
+        let args = json!({"page_id": "123&os_authType=none"});
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(handler.execute(args, &config));
+
+        assert!(result.unwrap_err().to_string().contains("Invalid page_id"));
+    }
+}
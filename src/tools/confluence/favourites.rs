@@ -0,0 +1,124 @@
+//! Starred content and spaces
+//!
+//! `confluence_get_favourites` finds saved pages and blog posts with the
+//! `favourite = currentUser()` CQL field and starred spaces with the v1
+//! space list's `favourite` flag.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{ToolSchema, number_prop};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+use super::activity::{compact_results, scoped_cql};
+use super::search_page;
+
+pub struct GetFavouritesHandler;
+
+const DEFAULT_LIMIT: u64 = 25;
+const MAX_LIMIT: u64 = 100;
+
+/// `{key, name, type}` per space, limited to `allowed` keys and IDs when not
+/// empty
+pub fn compact_spaces(data: &Value, allowed: &[String]) -> Vec<Value> {
+    data["results"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|space| {
+            allowed.is_empty()
+                || allowed.iter().any(|s| {
+                    space["key"] == s.as_str() || space["id"].to_string().trim_matches('"') == s
+                })
+        })
+        .map(|space| json!({"key": space["key"], "name": space["name"], "type": space["type"]}))
+        .collect()
+}
+
+#[async_trait]
+impl ToolHandler for GetFavouritesHandler {
+    fn name(&self) -> &'static str {
+        "confluence_get_favourites"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "limit".to_string(),
+            number_prop("Maximum pages and spaces to return (max 100)", 25),
+        );
+        ToolSchema::new(
+            "Your saved (starred) Confluence pages and starred spaces",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let limit = args["limit"]
+            .as_u64()
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+
+        let client = create_atlassian_client(config);
+        let base_url = config.get_atlassian_base_url();
+        let auth = create_auth_header(config);
+
+        let content = search_page(
+            client.clone(),
+            format!("{}/wiki/rest/api/search", base_url),
+            auth.clone(),
+            vec![(
+                "cql".to_string(),
+                scoped_cql("favourite = currentUser()", "lastmodified DESC", config),
+            )],
+            0,
+            limit as usize,
+        )
+        .await?;
+
+        let response = client
+            .get(format!("{}/wiki/rest/api/space", base_url))
+            .header("Authorization", auth)
+            .header("Accept", "application/json")
+            .query(&[("favourite", "true"), ("limit", &limit.to_string())])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to get favourite spaces: {}", response.status());
+        }
+        let spaces: Value = response.json().await?;
+
+        Ok(json!({
+            "success": true,
+            "pages": compact_results(&content),
+            "spaces": compact_spaces(&spaces, &config.confluence_spaces_filter)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_spaces_respects_allowlist() {
+        let data = json!({"results": [
+            {"key": "ENG", "name": "Engineering", "type": "global", "_links": {}},
+            {"id": 98306, "key": "~jane", "name": "Jane", "type": "personal"}
+        ]});
+        assert_eq!(compact_spaces(&data, &[]).len(), 2);
+        assert_eq!(
+            compact_spaces(&data, &["ENG".to_string()]),
+            vec![json!({"key": "ENG", "name": "Engineering", "type": "global"})]
+        );
+        assert_eq!(
+            compact_spaces(&data, &["98306".to_string()]),
+            vec![json!({"key": "~jane", "name": "Jane", "type": "personal"})]
+        );
+    }
+}
@@ -156,33 +156,60 @@ pub fn apply_v2_filtering(
     params
 }
 
-/// Legacy helper for v1 search endpoint (still uses expand parameter)
+/// How `confluence_search` reads its hits. CQL only runs on the v1 search
+/// endpoint (v2 has no search), so that call expands only what v2 lacks;
+/// page and blog post hits are then read from v2 with `body_format`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchExpansion {
+    /// `content.*` expansions of the v1 search (space, history, ...)
+    pub expand: Option<String>,
+    /// v2 `body-format` of the hydrated hits
+    pub body_format: String,
+}
+
+/// v1 body expansions and the v2 `body-format` each maps to
+const BODY_EXPANSIONS: &[(&str, &str)] = &[
+    ("body.storage", "storage"),
+    ("body.atlas_doc_format", "atlas_doc_format"),
+    ("body.view", "view"),
+    ("body.export_view", "export_view"),
+];
+
+/// Maps `include_all_fields` and v1-style `additional_expand` names onto the
+/// v1 search and the v2 reads; `version` needs nothing since v2 always returns it
 pub fn apply_expand_filtering(
-    url: &str,
     include_all_fields: Option<bool>,
     additional_expand: Option<Vec<String>>,
-) -> (String, Option<String>) {
-    // For v1 search API, we still use expand parameter
-    let expand_params = if include_all_fields.unwrap_or(false) {
-        vec!["body.storage", "version", "space", "history", "metadata"]
+) -> SearchExpansion {
+    let mut requested: Vec<String> = if include_all_fields.unwrap_or(false) {
+        vec!["space", "history", "metadata"]
+            .into_iter()
+            .map(String::from)
+            .collect()
     } else {
-        vec!["body.storage", "version"]
+        Vec::new()
     };
-
-    let mut expand = expand_params
-        .iter()
-        .map(|s| s.to_string())
-        .collect::<Vec<_>>();
-
-    if let Some(additional) = additional_expand {
-        for param in additional {
-            if !expand.contains(&param) {
-                expand.push(param);
+    requested.extend(additional_expand.unwrap_or_default());
+
+    let mut body_format = "storage".to_string();
+    let mut expand: Vec<String> = Vec::new();
+    for name in requested {
+        // Older callers wrote the search-relative path
+        let name = name.strip_prefix("content.").unwrap_or(&name);
+        if let Some((_, format)) = BODY_EXPANSIONS.iter().find(|(v1, _)| *v1 == name) {
+            body_format = format.to_string();
+        } else if name != "version" && !name.is_empty() {
+            let name = format!("content.{}", name);
+            if !expand.contains(&name) {
+                expand.push(name);
             }
         }
     }
 
-    (url.to_string(), Some(expand.join(",")))
+    SearchExpansion {
+        expand: (!expand.is_empty()).then(|| expand.join(",")),
+        body_format,
+    }
 }
 
 #[cfg(test)]
@@ -311,46 +338,38 @@ mod tests {
 
     #[test]
     fn test_apply_expand_filtering_default() {
-        let (url, expand) = apply_expand_filtering(
-            "https://test.atlassian.net/wiki/rest/api/search",
-            None,
-            None,
-        );
+        let expansion = apply_expand_filtering(None, None);
 
-        assert_eq!(url, "https://test.atlassian.net/wiki/rest/api/search");
-        assert_eq!(expand, Some("body.storage,version".to_string()));
+        assert_eq!(expansion.expand, None);
+        assert_eq!(expansion.body_format, "storage");
     }
 
     #[test]
     fn test_apply_expand_filtering_all_fields() {
-        let (url, expand) = apply_expand_filtering(
-            "https://test.atlassian.net/wiki/rest/api/search",
-            Some(true),
-            None,
-        );
+        let expansion = apply_expand_filtering(Some(true), None);
 
-        assert_eq!(url, "https://test.atlassian.net/wiki/rest/api/search");
         assert_eq!(
-            expand,
-            Some("body.storage,version,space,history,metadata".to_string())
+            expansion.expand,
+            Some("content.space,content.history,content.metadata".to_string())
         );
+        assert_eq!(expansion.body_format, "storage");
     }
 
     #[test]
     fn test_apply_expand_filtering_with_additional() {
-        let additional = vec!["ancestors".to_string(), "children".to_string()];
-        let (url, expand) = apply_expand_filtering(
-            "https://test.atlassian.net/wiki/rest/api/search",
-            None,
-            Some(additional),
-        );
+        let additional = vec![
+            "ancestors".to_string(),
+            "content.children".to_string(),
+            "version".to_string(),
+            "body.view".to_string(),
+        ];
+        let expansion = apply_expand_filtering(None, Some(additional));
 
-        assert_eq!(url, "https://test.atlassian.net/wiki/rest/api/search");
-        let expand_str = expand.unwrap();
-        assert!(expand_str.contains("body.storage"));
-        assert!(expand_str.contains("version"));
-        assert!(expand_str.contains("ancestors"));
-        assert!(expand_str.contains("children"));
+        assert_eq!(
+            expansion.expand,
+            Some("content.ancestors,content.children".to_string())
+        );
+        assert_eq!(expansion.body_format, "view");
     }
 
     #[test]
@@ -0,0 +1,317 @@
+//! Folders (v2 API)
+//!
+//! Folders group pages without a body of their own. The v2 API has no
+//! per-space folder listing, so `confluence_list_folders` uses CQL
+//! (`type = folder`); `confluence_get_folder` returns a folder with its
+//! direct children of every type (pages, folders, whiteboards, databases,
+//! embeds). Pages go into a folder through `confluence_create_page` with the
+//! folder as `parent_id`, or its title as `parent_title`.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::client::Confluence;
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{ToolSchema, number_prop, string_prop};
+use crate::tools::write_policy;
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+use super::activity::scoped_cql;
+use super::{resolve_space_id, search_page};
+
+pub struct ListFoldersHandler;
+pub struct GetFolderHandler;
+pub struct CreateFolderHandler;
+
+const DEFAULT_LIMIT: u64 = 50;
+const MAX_LIMIT: u64 = 250;
+
+fn folder_cql(space_key: &str, title: Option<&str>, config: &Config) -> String {
+    let mut condition = format!(
+        "type = folder AND space = \"{}\"",
+        space_key.replace('"', "")
+    );
+    if let Some(title) = title {
+        condition.push_str(&format!(" AND title = \"{}\"", title.replace('"', "\\\"")));
+    }
+    scoped_cql(&condition, "title ASC", config)
+}
+
+/// `{id, title, last_modified}` per folder in a CQL search page
+pub fn compact_folders(data: &Value) -> Vec<Value> {
+    data["results"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|result| {
+            json!({
+                "id": result["content"]["id"],
+                "title": result["content"]["title"],
+                "last_modified": result["lastModified"]
+            })
+        })
+        .collect()
+}
+
+/// `{id, type, title, status}` per entry of a `direct-children` page
+pub fn compact_children(data: &Value) -> Vec<Value> {
+    data["results"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|child| {
+            json!({
+                "id": child["id"],
+                "type": child["type"],
+                "title": child["title"],
+                "status": child["status"]
+            })
+        })
+        .collect()
+}
+
+async fn search_folders(
+    client: &reqwest::Client,
+    config: &Config,
+    cql: String,
+    limit: u64,
+) -> Result<Vec<Value>> {
+    let data = search_page(
+        client.clone(),
+        format!("{}/wiki/rest/api/search", config.get_atlassian_base_url()),
+        create_auth_header(config),
+        vec![("cql".to_string(), cql)],
+        0,
+        limit as usize,
+    )
+    .await?;
+    Ok(compact_folders(&data))
+}
+
+/// The folder titled exactly `title` in a space, if any
+pub(super) async fn find_folder_by_title(
+    client: &reqwest::Client,
+    config: &Config,
+    space_key: &str,
+    title: &str,
+) -> Result<Option<Value>> {
+    let folders = search_folders(
+        client,
+        config,
+        folder_cql(space_key, Some(title), config),
+        1,
+    )
+    .await?;
+    Ok(folders.into_iter().next())
+}
+
+async fn get_json(client: &reqwest::Client, config: &Config, url: &str) -> Result<Value> {
+    let response = client
+        .get(url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get folder: {}", response.status());
+    }
+    Ok(response.json().await?)
+}
+
+#[async_trait]
+impl ToolHandler for ListFoldersHandler {
+    fn name(&self) -> &'static str {
+        "confluence_list_folders"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("space_key".to_string(), string_prop("Space key", true));
+        props.insert(
+            "limit".to_string(),
+            number_prop("Maximum folders to return (max 250)", 50),
+        );
+        ToolSchema::new(
+            "List the folders in a space",
+            props,
+            vec!["space_key".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let space_key = args["space_key"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing space_key"))?;
+        let limit = args["limit"]
+            .as_u64()
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+
+        let client = create_atlassian_client(config);
+        let folders =
+            search_folders(&client, config, folder_cql(space_key, None, config), limit).await?;
+        Ok(json!({
+            "success": true,
+            "space_key": space_key,
+            "total": folders.len(),
+            "folders": folders
+        }))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for GetFolderHandler {
+    fn name(&self) -> &'static str {
+        "confluence_get_folder"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("folder_id".to_string(), string_prop("Folder ID", true));
+        props.insert(
+            "limit".to_string(),
+            number_prop("Maximum children to return (max 250)", 50),
+        );
+        ToolSchema::new(
+            "Get a folder and its direct children (pages, folders, whiteboards, databases, embeds)",
+            props,
+            vec!["folder_id".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let folder_id = args["folder_id"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing folder_id"))?;
+        let limit = args["limit"]
+            .as_u64()
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+
+        let client = create_atlassian_client(config);
+        let url = format!(
+            "{}/wiki/api/v2/folders/{}",
+            config.get_atlassian_base_url(),
+            folder_id
+        );
+        let folder = get_json(&client, config, &url).await?;
+        let children = get_json(
+            &client,
+            config,
+            &format!("{}/direct-children?limit={}", url, limit),
+        )
+        .await?;
+
+        Ok(json!({
+            "success": true,
+            "folder": {
+                "id": folder["id"],
+                "title": folder["title"],
+                "space_id": folder["spaceId"],
+                "parent_id": folder["parentId"],
+                "parent_type": folder["parentType"],
+                "created_at": folder["createdAt"]
+            },
+            "children": compact_children(&children),
+            "has_more": children["_links"]["next"].is_string()
+        }))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for CreateFolderHandler {
+    fn name(&self) -> &'static str {
+        "confluence_create_folder"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("space_key".to_string(), string_prop("Space key", true));
+        props.insert("title".to_string(), string_prop("Folder title", true));
+        props.insert(
+            "parent_id".to_string(),
+            string_prop("Parent page or folder ID (default: space root)", false),
+        );
+        ToolSchema::new(
+            "Create a folder; put pages in it with confluence_create_page parent_id",
+            props,
+            vec!["space_key".to_string(), "title".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let space_key = args["space_key"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing space_key"))?;
+        let title = args["title"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing title"))?;
+
+        let client = create_atlassian_client(config);
+        let confluence = Confluence::with_client(config, client.clone());
+        write_policy::check_space(&confluence, config, space_key).await?;
+        let space_id = resolve_space_id(&client, config, space_key).await?;
+
+        let mut body = json!({"spaceId": space_id, "title": title});
+        if let Some(parent_id) = args["parent_id"].as_str() {
+            body["parentId"] = json!(parent_id);
+        }
+
+        let url = format!("{}/wiki/api/v2/folders", config.get_atlassian_base_url());
+        let response = client
+            .post(&url)
+            .header("Authorization", create_auth_header(config))
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to create folder: {}", error);
+        }
+
+        let data: Value = response.json().await?;
+        Ok(json!({
+            "success": true,
+            "folder_id": data["id"],
+            "title": data["title"],
+            "parent_id": data["parentId"]
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_children_keeps_types() {
+        let data = json!({"results": [
+            {"id": "1", "type": "page", "title": "Runbook", "status": "current", "childPosition": 0},
+            {"id": "2", "type": "whiteboard", "title": "Architecture", "status": "current"}
+        ], "_links": {}});
+        let children = compact_children(&data);
+        assert_eq!(children.len(), 2);
+        assert_eq!(
+            children[1],
+            json!({"id": "2", "type": "whiteboard", "title": "Architecture", "status": "current"})
+        );
+    }
+
+    #[test]
+    fn test_compact_folders() {
+        let data = json!({"results": [{"content": {"id": "9", "type": "folder", "title": "Archive"},
+            "lastModified": "2026-10-01T00:00:00.000Z"}]});
+        assert_eq!(
+            compact_folders(&data),
+            vec![
+                json!({"id": "9", "title": "Archive", "last_modified": "2026-10-01T00:00:00.000Z"})
+            ]
+        );
+    }
+}
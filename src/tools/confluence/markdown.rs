@@ -0,0 +1,241 @@
+//! Markdown to storage-format conversion
+//!
+//! Covers the subset meeting notes are written in: ATX headings, paragraphs
+//! (line breaks kept), flat bullet and numbered lists, task items
+//! (`- [ ]` / `- [x]`, emitted as Confluence tasks), fenced code blocks and
+//! the inline marks `**bold**`, `*em*`, `` `code` `` and `[text](url)`.
+//! Everything else is treated as paragraph text and escaped.
+
+/// Escapes text for XHTML element content and attribute values
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// `<tag>` around the text between a leading `marker` and its next occurrence
+fn wrapped<'a>(rest: &'a str, marker: &str, tag: &str) -> Option<(String, &'a str)> {
+    let inner = rest.strip_prefix(marker)?;
+    let end = inner.find(marker).filter(|&end| end > 0)?;
+    let body = if tag == "code" {
+        escape(&inner[..end])
+    } else {
+        inline(&inner[..end])
+    };
+    Some((
+        format!("<{}>{}</{}>", tag, body, tag),
+        &inner[end + marker.len()..],
+    ))
+}
+
+/// A leading `[label](url)`
+fn link(rest: &str) -> Option<(String, &str)> {
+    let inner = rest.strip_prefix('[')?;
+    let (label, after) = inner.split_once("](")?;
+    let (url, after) = after.split_once(')')?;
+    Some((
+        format!("<a href=\"{}\">{}</a>", escape(url), inline(label)),
+        after,
+    ))
+}
+
+/// `**x**`, `*x*`, `` `x` `` and `[x](url)` within one line
+fn inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let matched = match c {
+            '`' => wrapped(rest, "`", "code"),
+            '*' if rest.starts_with("**") => wrapped(rest, "**", "strong"),
+            '*' => wrapped(rest, "*", "em"),
+            '[' => link(rest),
+            _ => None,
+        };
+        match matched {
+            Some((html, after)) => {
+                out.push_str(&html);
+                rest = after;
+            }
+            None => {
+                out.push_str(&escape(&c.to_string()));
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    out
+}
+
+#[derive(PartialEq)]
+enum List {
+    Bullet,
+    Numbered,
+    Tasks,
+}
+
+impl List {
+    fn tags(&self) -> (&'static str, &'static str) {
+        match self {
+            List::Bullet => ("<ul>", "</ul>"),
+            List::Numbered => ("<ol>", "</ol>"),
+            List::Tasks => ("<ac:task-list>", "</ac:task-list>"),
+        }
+    }
+}
+
+/// Converts the Markdown subset into storage format
+pub fn markdown_to_storage(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut list: Option<List> = None;
+    let mut code: Option<(String, Vec<&str>)> = None;
+    let mut task_id = 0;
+
+    fn flush(out: &mut String, paragraph: &mut Vec<String>, list: &mut Option<List>) {
+        if !paragraph.is_empty() {
+            out.push_str(&format!("<p>{}</p>", paragraph.join("<br />")));
+            paragraph.clear();
+        }
+        if let Some(open) = list.take() {
+            out.push_str(open.tags().1);
+        }
+    }
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+
+        if let Some((language, body)) = code.as_mut() {
+            if trimmed.starts_with("```") {
+                out.push_str("<ac:structured-macro ac:name=\"code\">");
+                if !language.is_empty() {
+                    out.push_str(&format!(
+                        "<ac:parameter ac:name=\"language\">{}</ac:parameter>",
+                        escape(language)
+                    ));
+                }
+                out.push_str(&format!(
+                    "<ac:plain-text-body><![CDATA[{}]]></ac:plain-text-body></ac:structured-macro>",
+                    body.join("\n").replace("]]>", "]]]]><![CDATA[>")
+                ));
+                code = None;
+            } else {
+                body.push(line);
+            }
+            continue;
+        }
+
+        let hashes = trimmed.chars().take_while(|c| *c == '#').count();
+        let numbered = trimmed
+            .split_once(". ")
+            .filter(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+            .map(|(_, item)| item);
+        let bullet = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "));
+        let task = bullet.and_then(|item| {
+            item.strip_prefix("[ ] ")
+                .map(|text| (false, text))
+                .or_else(|| {
+                    item.strip_prefix("[x] ")
+                        .or_else(|| item.strip_prefix("[X] "))
+                        .map(|text| (true, text))
+                })
+        });
+
+        let item = match (task, bullet, numbered) {
+            (Some((done, text)), _, _) => Some((List::Tasks, text, done)),
+            (None, Some(text), _) => Some((List::Bullet, text, false)),
+            (None, None, Some(text)) => Some((List::Numbered, text, false)),
+            _ => None,
+        };
+
+        if let Some((kind, text, done)) = item {
+            if !paragraph.is_empty() || list.as_ref().is_some_and(|open| *open != kind) {
+                flush(&mut out, &mut paragraph, &mut list);
+            }
+            if list.is_none() {
+                out.push_str(kind.tags().0);
+            }
+            let text = inline(text.trim());
+            if kind == List::Tasks {
+                task_id += 1;
+                out.push_str(&format!(
+                    "<ac:task><ac:task-id>{}</ac:task-id><ac:task-status>{}</ac:task-status><ac:task-body>{}</ac:task-body></ac:task>",
+                    task_id,
+                    if done { "complete" } else { "incomplete" },
+                    text
+                ));
+            } else {
+                out.push_str(&format!("<li>{}</li>", text));
+            }
+            list = Some(kind);
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            flush(&mut out, &mut paragraph, &mut list);
+        } else if let Some(language) = trimmed.strip_prefix("```") {
+            flush(&mut out, &mut paragraph, &mut list);
+            code = Some((language.trim().to_string(), Vec::new()));
+        } else if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+            flush(&mut out, &mut paragraph, &mut list);
+            out.push_str(&format!(
+                "<h{}>{}</h{}>",
+                hashes,
+                inline(trimmed[hashes..].trim()),
+                hashes
+            ));
+        } else if trimmed == "---" || trimmed == "***" {
+            flush(&mut out, &mut paragraph, &mut list);
+            out.push_str("<hr />");
+        } else {
+            if list.is_some() {
+                flush(&mut out, &mut paragraph, &mut list);
+            }
+            paragraph.push(inline(trimmed));
+        }
+    }
+    flush(&mut out, &mut paragraph, &mut list);
+    // An unterminated fence keeps its lines as text rather than dropping them
+    if let Some((_, body)) = code {
+        out.push_str(&format!("<pre>{}</pre>", escape(&body.join("\n"))));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_storage_blocks() {
+        let storage = markdown_to_storage(
+            "## Decisions\n- Ship **v2** on Friday\n- Keep `beta` flag\n\nNotes line one\nline <two>\n\n1. First\n2. Second\n---",
+        );
+        assert_eq!(
+            storage,
+            "<h2>Decisions</h2>\
+             <ul><li>Ship <strong>v2</strong> on Friday</li><li>Keep <code>beta</code> flag</li></ul>\
+             <p>Notes line one<br />line &lt;two&gt;</p>\
+             <ol><li>First</li><li>Second</li></ol>\
+             <hr />"
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_storage_tasks_links_and_code() {
+        let storage = markdown_to_storage(
+            "- [ ] Fix PROJ-1 see [spec](https://x.test/?a=1&b=2)\n- [x] *Done* item\n```rust\nlet a = 1;\n```",
+        );
+        assert_eq!(
+            storage,
+            "<ac:task-list>\
+             <ac:task><ac:task-id>1</ac:task-id><ac:task-status>incomplete</ac:task-status><ac:task-body>Fix PROJ-1 see <a href=\"https://x.test/?a=1&amp;b=2\">spec</a></ac:task-body></ac:task>\
+             <ac:task><ac:task-id>2</ac:task-id><ac:task-status>complete</ac:task-status><ac:task-body><em>Done</em> item</ac:task-body></ac:task>\
+             </ac:task-list>\
+             <ac:structured-macro ac:name=\"code\"><ac:parameter ac:name=\"language\">rust</ac:parameter>\
+             <ac:plain-text-body><![CDATA[let a = 1;]]></ac:plain-text-body></ac:structured-macro>"
+        );
+    }
+}
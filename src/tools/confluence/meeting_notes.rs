@@ -0,0 +1,273 @@
+//! Meeting notes publishing
+//!
+//! `confluence_publish_meeting_notes` turns Markdown notes into a Confluence page under
+//! a parent (the `parent_id` argument, else `CONFLUENCE_MEETING_NOTES_PARENT`),
+//! labels it, and comments a link to the page on every Jira issue key found
+//! in the notes. Each comment is a `jira_add_comment` call made by
+//! `call_tool`, so the audit log, undo journal and circuit breaker see it
+//! like any other. Comment failures are reported per issue; the page stays.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::client::Confluence;
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema, string_prop};
+use crate::tools::write_policy;
+use crate::utils::http_utils::create_auth_header;
+use crate::utils::issue_keys::find_issue_keys;
+use std::collections::HashMap;
+use std::future::Future;
+
+use super::markdown::markdown_to_storage;
+
+pub struct PublishMeetingNotesHandler;
+
+const DEFAULT_LABEL: &str = "meeting-notes";
+
+/// Confluence labels are lowercase and cannot contain whitespace
+fn normalize_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
+
+/// Requested labels, or the default when none are given
+fn labels(args: &Value) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::new();
+    for label in args["labels"].as_array().into_iter().flatten() {
+        let label = normalize_label(label.as_str().unwrap_or_default());
+        if !label.is_empty() && !labels.contains(&label) {
+            labels.push(label);
+        }
+    }
+    if labels.is_empty() {
+        labels.push(DEFAULT_LABEL.to_string());
+    }
+    labels
+}
+
+/// Issue keys in the notes, limited to `JIRA_PROJECTS_FILTER` when set
+fn referenced_issues(notes: &str, config: &Config) -> Vec<String> {
+    find_issue_keys(notes)
+        .into_iter()
+        .filter(|key| {
+            config.jira_projects_filter.is_empty()
+                || key.rsplit_once('-').is_some_and(|(project, _)| {
+                    config.jira_projects_filter.iter().any(|p| p == project)
+                })
+        })
+        .collect()
+}
+
+/// Comment body: a sentence with the page title linked
+fn link_comment(title: &str, url: &str) -> Value {
+    json!({
+        "type": "doc",
+        "version": 1,
+        "content": [{
+            "type": "paragraph",
+            "content": [
+                {"type": "text", "text": "Discussed in meeting notes: "},
+                {"type": "text", "text": title, "marks": [{"type": "link", "attrs": {"href": url}}]}
+            ]
+        }]
+    })
+}
+
+async fn add_labels(
+    client: &reqwest::Client,
+    config: &Config,
+    page_id: &str,
+    labels: &[String],
+) -> Result<()> {
+    let url = format!(
+        "{}/wiki/rest/api/content/{}/label",
+        config.get_atlassian_base_url(),
+        page_id
+    );
+    let body: Vec<Value> = labels
+        .iter()
+        .map(|name| json!({"prefix": "global", "name": name}))
+        .collect();
+    let response = client
+        .post(&url)
+        .header("Authorization", create_auth_header(config))
+        .header("Content-Type", "application/json")
+        .json(&body)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to add labels: {}", response.status());
+    }
+    Ok(())
+}
+
+/// Adds the comments a publish result lists under `comments`, through
+/// `comment` (a `jira_add_comment` call); the issues that got one go to
+/// `commented_issues`, the others to `comment_errors`
+pub async fn comment_issues<F, Fut>(result: &mut Value, mut comment: F)
+where
+    F: FnMut(Value) -> Fut,
+    Fut: Future<Output = Result<Value>>,
+{
+    let comments = match result.as_object_mut().and_then(|r| r.remove("comments")) {
+        Some(Value::Array(comments)) => comments,
+        _ => Vec::new(),
+    };
+    let mut commented = Vec::new();
+    let mut failed = Vec::new();
+    for args in comments {
+        let issue_key = args["issue_key"].clone();
+        match comment(args).await {
+            Ok(_) => commented.push(issue_key),
+            Err(e) => failed.push(json!({"issue_key": issue_key, "error": e.to_string()})),
+        }
+    }
+    result["commented_issues"] = json!(commented);
+    if !failed.is_empty() {
+        result["comment_errors"] = json!(failed);
+    }
+}
+
+#[async_trait]
+impl ToolHandler for PublishMeetingNotesHandler {
+    fn name(&self) -> &'static str {
+        "confluence_publish_meeting_notes"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("title".to_string(), string_prop("Page title", true));
+        props.insert(
+            "notes".to_string(),
+            string_prop(
+                "Notes in Markdown (headings, lists, `- [ ]` tasks, code blocks, bold/italic/code/links)",
+                true,
+            ),
+        );
+        props.insert(
+            "parent_id".to_string(),
+            string_prop(
+                "Parent page ID (default: CONFLUENCE_MEETING_NOTES_PARENT)",
+                false,
+            ),
+        );
+        props.insert(
+            "labels".to_string(),
+            Property::new("array").with_description("Page labels (default: [\"meeting-notes\"])"),
+        );
+        props.insert(
+            "comment_issues".to_string(),
+            Property::new("boolean")
+                .with_description(
+                    "Comment a link to the page on each issue key found in the notes".to_string(),
+                )
+                .with_default(json!(true)),
+        );
+        ToolSchema::new(
+            "Publish Markdown meeting notes as a labeled Confluence page and link it from the Jira issues they mention",
+            props,
+            vec!["title".to_string(), "notes".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let title = args["title"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing title"))?;
+        let notes = args["notes"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing notes"))?;
+        let parent_id = args["parent_id"]
+            .as_str()
+            .or(config.confluence_meeting_notes_parent.as_deref())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Missing parent_id (or set CONFLUENCE_MEETING_NOTES_PARENT for a default)"
+                )
+            })?;
+        let comment_issues = args["comment_issues"].as_bool().unwrap_or(true);
+        let labels = labels(&args);
+
+        let confluence = Confluence::new(config);
+        let client = confluence.http();
+        let parent = confluence.get_page_storage(parent_id, None).await?;
+        let space_id = parent
+            .space_id
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Parent page {} has no spaceId", parent_id))?;
+        write_policy::check_space(&confluence, config, space_id).await?;
+
+        let page = confluence
+            .create_page(
+                space_id,
+                title,
+                &markdown_to_storage(notes),
+                Some(parent_id),
+                &[],
+            )
+            .await?;
+        let page_id = page.id;
+        let page_url = format!(
+            "{}/wiki/pages/viewpage.action?pageId={}",
+            config.get_atlassian_base_url(),
+            page_id
+        );
+
+        let mut result = json!({
+            "success": true,
+            "page_id": page_id,
+            "title": page.title,
+            "url": page_url,
+            "parent_id": parent_id,
+            "labels": labels
+        });
+        if let Err(e) = add_labels(client, config, &page_id, &labels).await {
+            result["label_error"] = json!(e.to_string());
+        }
+
+        let issues = referenced_issues(notes, config);
+        if comment_issues {
+            let comments: Vec<Value> = issues
+                .iter()
+                .map(|issue_key| {
+                    json!({
+                        "issue_key": issue_key,
+                        "comment": link_comment(title, &page_url)
+                    })
+                })
+                .collect();
+            result["comments"] = json!(comments);
+        }
+        result["referenced_issues"] = json!(issues);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels_normalized_with_default() {
+        assert_eq!(labels(&json!({})), vec![DEFAULT_LABEL]);
+        assert_eq!(
+            labels(&json!({"labels": ["Weekly Sync", "weekly  sync", "team-a", ""]})),
+            vec!["weekly-sync", "team-a"]
+        );
+    }
+
+    #[test]
+    fn test_link_comment_marks_title() {
+        let comment = link_comment("Sync 2026-10-14", "https://x.test/p");
+        let link = &comment["content"][0]["content"][1];
+        assert_eq!(link["text"], "Sync 2026-10-14");
+        assert_eq!(link["marks"][0]["attrs"]["href"], "https://x.test/p");
+    }
+}
@@ -0,0 +1,197 @@
+//! Three-way merge of storage-format bodies
+//!
+//! Bodies are split into blocks at tag boundaries (`...><...`) and newlines,
+//! then merged diff3-style: a region changed on one side only takes that
+//! side; a region changed differently on both sides is a conflict.
+
+/// Cap on the LCS table for the differing middle of two bodies
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub base: String,
+    pub ours: String,
+    pub theirs: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeResult {
+    Clean(String),
+    Conflicts(Vec<Conflict>),
+}
+
+/// Splits after each newline and between adjacent tags; joining the blocks
+/// gives back the input exactly
+pub fn split_blocks(s: &str) -> Vec<&str> {
+    let bytes = s.as_bytes();
+    let mut blocks = Vec::new();
+    let mut start = 0;
+    for i in 0..bytes.len() {
+        let boundary = bytes[i] == b'\n' || (bytes[i] == b'>' && bytes.get(i + 1) == Some(&b'<'));
+        if boundary {
+            blocks.push(&s[start..=i]);
+            start = i + 1;
+        }
+    }
+    if start < s.len() {
+        blocks.push(&s[start..]);
+    }
+    blocks
+}
+
+/// Index pairs of a longest common subsequence, or `None` when too large
+fn lcs_pairs(a: &[&str], b: &[&str]) -> Option<Vec<(usize, usize)>> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a_mid, b_mid) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    if a_mid.len().saturating_mul(b_mid.len()) > MAX_LCS_CELLS {
+        return None;
+    }
+
+    let cols = b_mid.len() + 1;
+    let mut table = vec![0u32; (a_mid.len() + 1) * cols];
+    for i in (0..a_mid.len()).rev() {
+        for j in (0..b_mid.len()).rev() {
+            table[i * cols + j] = if a_mid[i] == b_mid[j] {
+                table[(i + 1) * cols + j + 1] + 1
+            } else {
+                table[(i + 1) * cols + j].max(table[i * cols + j + 1])
+            };
+        }
+    }
+
+    let mut pairs: Vec<(usize, usize)> = (0..prefix).map(|i| (i, i)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < a_mid.len() && j < b_mid.len() {
+        if a_mid[i] == b_mid[j] {
+            pairs.push((prefix + i, prefix + j));
+            i += 1;
+            j += 1;
+        } else if table[(i + 1) * cols + j] >= table[i * cols + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs.extend((0..suffix).map(|k| (a.len() - suffix + k, b.len() - suffix + k)));
+    Some(pairs)
+}
+
+/// Merges `ours` and `theirs`, both edited from `base`
+pub fn merge3(base: &str, ours: &str, theirs: &str) -> MergeResult {
+    if ours == theirs || theirs == base {
+        return MergeResult::Clean(ours.to_string());
+    }
+    if ours == base {
+        return MergeResult::Clean(theirs.to_string());
+    }
+
+    let (b, o, t) = (split_blocks(base), split_blocks(ours), split_blocks(theirs));
+    let whole_conflict = || {
+        MergeResult::Conflicts(vec![Conflict {
+            base: base.to_string(),
+            ours: ours.to_string(),
+            theirs: theirs.to_string(),
+        }])
+    };
+    let (Some(with_ours), Some(with_theirs)) = (lcs_pairs(&b, &o), lcs_pairs(&b, &t)) else {
+        return whole_conflict();
+    };
+
+    // Base blocks unchanged on both sides anchor the merge
+    let mut ours_at = vec![None; b.len()];
+    for (bi, oi) in with_ours {
+        ours_at[bi] = Some(oi);
+    }
+    let mut anchors: Vec<(usize, usize, usize)> = with_theirs
+        .into_iter()
+        .filter_map(|(bi, ti)| ours_at[bi].map(|oi| (bi, oi, ti)))
+        .collect();
+    anchors.push((b.len(), o.len(), t.len()));
+
+    let mut merged = String::with_capacity(ours.len().max(theirs.len()));
+    let mut conflicts = Vec::new();
+    let (mut pb, mut po, mut pt) = (0, 0, 0);
+    for (bi, oi, ti) in anchors {
+        let (base_chunk, ours_chunk, theirs_chunk) = (&b[pb..bi], &o[po..oi], &t[pt..ti]);
+        if ours_chunk == base_chunk || ours_chunk == theirs_chunk {
+            merged.extend(theirs_chunk.iter().copied());
+        } else if theirs_chunk == base_chunk {
+            merged.extend(ours_chunk.iter().copied());
+        } else {
+            conflicts.push(Conflict {
+                base: base_chunk.concat(),
+                ours: ours_chunk.concat(),
+                theirs: theirs_chunk.concat(),
+            });
+        }
+        if bi < b.len() {
+            merged.push_str(b[bi]);
+        }
+        (pb, po, pt) = (bi + 1, oi + 1, ti + 1);
+    }
+
+    if conflicts.is_empty() {
+        MergeResult::Clean(merged)
+    } else {
+        MergeResult::Conflicts(conflicts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_blocks_round_trip() {
+        let body = "<h1>T</h1><ul><li>a</li></ul>\ntext";
+        let blocks = split_blocks(body);
+        assert_eq!(
+            blocks,
+            vec!["<h1>T</h1>", "<ul>", "<li>a</li>", "</ul>\n", "text"]
+        );
+        assert_eq!(blocks.concat(), body);
+    }
+
+    #[test]
+    fn test_merge_non_overlapping_edits() {
+        let base = "<p>one</p><p>two</p><p>three</p>";
+        let ours = "<p>ONE</p><p>two</p><p>three</p>";
+        let theirs = "<p>one</p><p>two</p><p>three</p><p>four</p>";
+        assert_eq!(
+            merge3(base, ours, theirs),
+            MergeResult::Clean("<p>ONE</p><p>two</p><p>three</p><p>four</p>".to_string())
+        );
+    }
+
+    #[test]
+    fn test_merge_same_edit_on_both_sides() {
+        let base = "<p>one</p><p>two</p>";
+        let edited = "<p>one</p><p>2</p>";
+        assert_eq!(
+            merge3(base, edited, edited),
+            MergeResult::Clean(edited.to_string())
+        );
+    }
+
+    #[test]
+    fn test_merge_conflict() {
+        let base = "<p>one</p><p>two</p>";
+        let ours = "<p>one</p><p>ours</p>";
+        let theirs = "<p>one</p><p>theirs</p>";
+        assert_eq!(
+            merge3(base, ours, theirs),
+            MergeResult::Conflicts(vec![Conflict {
+                base: "<p>two</p>".to_string(),
+                ours: "<p>ours</p>".to_string(),
+                theirs: "<p>theirs</p>".to_string(),
+            }])
+        );
+    }
+}
@@ -1,11 +1,42 @@
+use crate::client::Confluence;
+use crate::client::confluence::VersionConflict;
 use crate::config::Config;
 use crate::tools::ToolHandler;
+use crate::tools::applied_filters;
+use crate::tools::args::parse_args;
+use crate::tools::excerpts;
+use crate::tools::jira::mentions;
+use crate::tools::pagination;
+use crate::tools::schema::{Property, ToolSchema, number_prop, string_prop};
+use crate::tools::write_policy;
 use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
 use anyhow::Result;
 use async_trait::async_trait;
+use serde::Deserialize;
 use serde_json::{Value, json};
-
+use std::collections::{HashMap, HashSet};
+
+pub mod action_items;
+pub mod activity;
+pub mod analytics;
+pub mod children;
+pub mod chunks;
+pub mod comments;
+pub mod edit;
+pub mod export;
+pub mod favourites;
 pub mod field_filtering;
+pub mod folders;
+pub mod markdown;
+pub mod meeting_notes;
+pub mod merge;
+pub mod page_links;
+pub mod plain_text;
+pub mod ranking;
+pub mod restrictions;
+pub mod sanitize;
+pub mod spaces;
+pub mod whiteboards;
 use field_filtering::{apply_expand_filtering, apply_v2_filtering};
 
 // Handlers for each Confluence tool
@@ -15,38 +46,132 @@ pub struct GetPageChildrenHandler;
 pub struct GetCommentsHandler;
 pub struct CreatePageHandler;
 pub struct UpdatePageHandler;
+pub struct GetPageByTitleHandler;
+pub struct GetTasksHandler;
+pub struct UpdateTaskStatusHandler;
+
+/// `confluence_get_page_children` page size unless `limit` says otherwise
+const DEFAULT_CHILDREN_LIMIT: u64 = 25;
+/// Largest page size of the v2 children endpoint
+const MAX_CHILDREN_LIMIT: u64 = 250;
+
+/// Task statuses accepted by the v2 tasks API
+const TASK_STATUSES: &[&str] = &["complete", "incomplete"];
+
+/// `on_conflict` modes when `expected_version` is stale
+const UPDATE_CONFLICT_MODES: &[&str] = &["error", "merge"];
+/// `on_duplicate` modes when the title is already taken in the space
+const DUPLICATE_TITLE_MODES: &[&str] = &["error", "update"];
+/// PUT attempts before giving up on repeated 409s
+const MAX_UPDATE_ATTEMPTS: usize = 3;
+
+// Typed arguments (see `tools::args`)
+#[derive(Deserialize)]
+struct PageReadArgs {
+    page_id: String,
+    include_all_fields: Option<bool>,
+    additional_expand: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct CommentsArgs {
+    page_id: String,
+    include_all_fields: Option<bool>,
+    additional_expand: Option<Vec<String>>,
+    #[serde(default)]
+    include_inline: bool,
+    #[serde(default)]
+    resolution: comments::Resolution,
+}
+
+#[derive(Deserialize)]
+struct PageChildrenArgs {
+    page_id: String,
+    include_all_fields: Option<bool>,
+    additional_expand: Option<Vec<String>>,
+    cursor: Option<String>,
+    limit: Option<u64>,
+    depth: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct PageByTitleArgs {
+    space_key: String,
+    title: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum TaskStatus {
+    Complete,
+    Incomplete,
+}
+
+impl TaskStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Complete => "complete",
+            TaskStatus::Incomplete => "incomplete",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct UpdateTaskStatusArgs {
+    task_id: String,
+    status: TaskStatus,
+}
+
+fn parse_task_status(value: &Value) -> Result<Option<&str>> {
+    match value.as_str() {
+        None => Ok(None),
+        Some(s) if TASK_STATUSES.contains(&s) => Ok(Some(s)),
+        Some(other) => anyhow::bail!(
+            "Invalid status '{}': expected 'complete' or 'incomplete'",
+            other
+        ),
+    }
+}
 
 #[async_trait]
 impl ToolHandler for SearchHandler {
+    fn name(&self) -> &'static str {
+        "confluence_search"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("query".to_string(), string_prop("CQL query. Format: field operator value (e.g., 'type=page AND space=\"SPACE\"'). Use text ~ \"keyword\" for text search.", true));
+        props.insert("limit".to_string(), number_prop("Max results", 10));
+        props.insert("auto_paginate".to_string(), Property::new("boolean").with_description("Fetch every page until the results are exhausted; 'limit' becomes the total (capped at 500). Duplicates are removed and page counts are reported under 'pagination'.").with_default(json!(false)));
+        props.insert("group_by_page".to_string(), Property::new("boolean").with_description("Fold attachment and comment hits into one result per containing page, with 'matched_via' and 'hits'. Set false for the raw CQL hits.").with_default(json!(true)));
+        props.insert("sort".to_string(), Property::new("string").with_description("Order of grouped results: 'relevance' (CQL order, default) or 'last_modified' (newest first)").with_default(json!("relevance")).with_enum(vec![json!("relevance"), json!("last_modified")]));
+        ToolSchema::new(
+            "Search Confluence using CQL",
+            props,
+            vec!["query".to_string()],
+        )
+    }
+
     async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
         let cql = args["query"]
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("Missing query parameter"))?;
         let limit = args["limit"].as_u64().unwrap_or(10);
+        let ranking = ranking::Ranking::from_args(&args)?;
 
         // Apply space filter if configured and not already in CQL
-        let final_cql = if !config.confluence_spaces_filter.is_empty() {
-            let cql_lower = cql.to_lowercase();
-            // Check if CQL already contains space condition
-            if cql_lower.contains("space ")
-                || cql_lower.contains("space=")
-                || cql_lower.contains("space in")
-            {
-                // User explicitly specified space, use their CQL as-is
-                cql.to_string()
-            } else {
-                // Add space filter
-                let spaces = config
-                    .confluence_spaces_filter
-                    .iter()
-                    .map(|s| format!("\"{}\"", s))
-                    .collect::<Vec<_>>()
-                    .join(",");
-                format!("space IN ({}) AND ({})", spaces, cql)
-            }
-        } else {
-            cql.to_string()
-        };
+        let final_cql = spaces::scope_search_cql(cql, &config.confluence_spaces_filter);
+        let applied = applied_filters::describe(
+            "effective_cql",
+            cql,
+            &final_cql,
+            Some(applied_filters::InjectedScope {
+                kind: "spaces",
+                values: &config.confluence_spaces_filter,
+                setting: "CONFLUENCE_SPACES_FILTER",
+            }),
+        );
 
         let include_all_fields = args["include_all_fields"].as_bool();
         let additional_expand = args["additional_expand"].as_array().map(|arr| {
@@ -58,131 +183,489 @@ impl ToolHandler for SearchHandler {
         let client = create_atlassian_client(config);
         let url = format!("{}/wiki/rest/api/search", config.get_atlassian_base_url());
 
-        let (url, expand_param) =
-            apply_expand_filtering(&url, include_all_fields, additional_expand);
+        let expansion = apply_expand_filtering(include_all_fields, additional_expand);
 
-        let mut query_params = vec![
-            ("cql".to_string(), final_cql),
-            ("limit".to_string(), limit.to_string()),
-        ];
-
-        if let Some(expand) = expand_param {
+        let mut query_params = vec![("cql".to_string(), final_cql)];
+        if let Some(expand) = expansion.expand {
             query_params.push(("expand".to_string(), expand));
         }
 
-        let response = client
-            .get(&url)
-            .header("Authorization", create_auth_header(config))
-            .header("Accept", "application/json")
-            .query(&query_params)
-            .send()
+        if args["auto_paginate"].as_bool() == Some(true) {
+            let result = search_all(
+                client,
+                config,
+                url,
+                query_params,
+                &args,
+                ranking,
+                &expansion.body_format,
+            )
             .await?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("Search failed: {}", response.status());
+            return Ok(applied_filters::attach(result, applied));
         }
 
-        let data: Value = response.json().await?;
-        Ok(json!({
+        let data = search_page(
+            client.clone(),
+            url,
+            create_auth_header(config),
+            query_params,
+            0,
+            limit as usize,
+        )
+        .await?;
+        let mut results = data["results"].as_array().cloned().unwrap_or_default();
+        hydrate_results(&client, config, &mut results, &expansion.body_format).await;
+        let result = json!({
             "success": true,
-            "results": data["results"],
+            "results": arrange_results(results, &args, ranking),
             "total": data["totalSize"]
-        }))
+        });
+        Ok(applied_filters::attach(result, applied))
+    }
+}
+
+/// Completes page and blog post hits from v2: `body` and `version` (in
+/// `body_format`), `spaceId`, `parentId` and the rest are merged into their
+/// `content`. A failed read is logged and leaves the hits as v1 returned them.
+async fn hydrate_results(
+    client: &reqwest::Client,
+    config: &Config,
+    results: &mut [Value],
+    body_format: &str,
+) {
+    let confluence = Confluence::with_client(config, client.clone());
+    for (kind, collection) in [("page", "pages"), ("blogpost", "blogposts")] {
+        let ids: Vec<String> = results
+            .iter()
+            .filter(|r| r["content"]["type"] == kind)
+            .filter_map(|r| r["content"]["id"].as_str().map(String::from))
+            .collect();
+        if ids.is_empty() {
+            continue;
+        }
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+        let fetched = if kind == "page" {
+            confluence.get_pages(&ids, body_format).await
+        } else {
+            confluence.get_blog_posts(&ids, body_format).await
+        };
+        let contents: HashMap<String, Value> = match fetched {
+            Ok(contents) => contents
+                .into_iter()
+                .filter_map(|c| Some((c.id.clone(), serde_json::to_value(c).ok()?)))
+                .collect(),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to read {} of search hits from v2", collection);
+                continue;
+            }
+        };
+        for result in results.iter_mut().filter(|r| r["content"]["type"] == kind) {
+            let Some(Value::Object(v2)) = result["content"]["id"]
+                .as_str()
+                .and_then(|id| contents.get(id))
+            else {
+                continue;
+            };
+            // v1 keeps its own title and links
+            for (key, value) in v2 {
+                if key != "title" && key != "_links" {
+                    result["content"][key] = value.clone();
+                }
+            }
+        }
     }
 }
 
+/// Search hits with cleaned-up excerpts, grouped by containing page unless
+/// `group_by_page: false`
+fn arrange_results(mut results: Vec<Value>, args: &Value, ranking: ranking::Ranking) -> Vec<Value> {
+    for result in &mut results {
+        if let Some(excerpt) = result["excerpt"].as_str() {
+            result["excerpt"] = json!(excerpts::from_confluence(excerpt));
+        }
+    }
+    if args["group_by_page"].as_bool() == Some(false) {
+        return results;
+    }
+    ranking::group_by_page(results, ranking)
+}
+
+/// Page size used when auto-paginating
+const SEARCH_PAGE_SIZE: usize = 50;
+
+/// One page of CQL search results starting at `start`
+async fn search_page(
+    client: reqwest::Client,
+    url: String,
+    auth: String,
+    mut query_params: Vec<(String, String)>,
+    start: usize,
+    limit: usize,
+) -> Result<Value> {
+    query_params.push(("start".to_string(), start.to_string()));
+    query_params.push(("limit".to_string(), limit.to_string()));
+
+    let response = client
+        .get(&url)
+        .header("Authorization", auth)
+        .header("Accept", "application/json")
+        .query(&query_params)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Search failed: {}", response.status());
+    }
+
+    Ok(response.json().await?)
+}
+
+/// Fetches the first page for `totalSize`, then the remaining offsets concurrently
+async fn search_all(
+    client: reqwest::Client,
+    config: &Config,
+    url: String,
+    query_params: Vec<(String, String)>,
+    args: &Value,
+    ranking: ranking::Ranking,
+    body_format: &str,
+) -> Result<Value> {
+    let limit = pagination::auto_paginate_limit(args);
+    let page_size = limit.min(SEARCH_PAGE_SIZE);
+    let auth = create_auth_header(config);
+
+    let first = search_page(
+        client.clone(),
+        url.clone(),
+        auth.clone(),
+        query_params.clone(),
+        0,
+        page_size,
+    )
+    .await?;
+    let total = first["totalSize"].as_u64().unwrap_or(0) as usize;
+    let offsets: Vec<usize> = (page_size..total.min(limit)).step_by(page_size).collect();
+    let rest = pagination::fetch_offsets(&offsets, |start| {
+        search_page(
+            client.clone(),
+            url.clone(),
+            auth.clone(),
+            query_params.clone(),
+            start,
+            page_size,
+        )
+    })
+    .await?;
+
+    // The same content can match as a page and through its attachments/comments
+    let mut collected = pagination::Collected::default();
+    let mut seen = HashSet::new();
+    for page in std::iter::once(first).chain(rest) {
+        let results = page["results"].as_array().cloned().unwrap_or_default();
+        if !collected.extend(results, limit, &mut seen, |r| {
+            r["content"]["id"]
+                .as_str()
+                .or_else(|| r["url"].as_str())
+                .map(String::from)
+        }) {
+            break;
+        }
+    }
+    collected.truncated |= total > limit;
+    hydrate_results(&client, config, &mut collected.items, body_format).await;
+
+    Ok(json!({
+        "success": true,
+        "pagination": collected.metadata(limit),
+        "results": arrange_results(collected.items, args, ranking),
+        "total": total
+    }))
+}
+
 #[async_trait]
 impl ToolHandler for GetPageHandler {
-    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
-        let page_id = args["page_id"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing page_id"))?;
-
-        let include_all_fields = args["include_all_fields"].as_bool();
-        let additional_includes = args["additional_expand"].as_array().map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().map(String::from))
-                .collect()
-        });
+    fn name(&self) -> &'static str {
+        "confluence_get_page"
+    }
 
-        let client = create_atlassian_client(config);
-        let url = format!(
-            "{}/wiki/api/v2/pages/{}",
-            config.get_atlassian_base_url(),
-            page_id
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "page_id".to_string(),
+            string_prop(
+                "Page ID or page link (.../pages/<id>/..., .../display/SPACE/Title)",
+                true,
+            ),
         );
+        ToolSchema::new(
+            "Get Confluence page by ID or link",
+            props,
+            vec!["page_id".to_string()],
+        )
+    }
 
-        let query_params = apply_v2_filtering(include_all_fields, additional_includes);
-
-        let response = client
-            .get(&url)
-            .header("Authorization", create_auth_header(config))
-            .header("Accept", "application/json")
-            .query(&query_params)
-            .send()
-            .await?;
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let PageReadArgs {
+            page_id,
+            include_all_fields,
+            additional_expand: additional_includes,
+        } = parse_args(&args)?;
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to get page: {}", response.status());
-        }
+        let confluence = Confluence::new(config);
+        let client = confluence.http();
+        let page_id = page_links::resolve_page_id(client, config, &page_id).await?;
 
-        let data: Value = response.json().await?;
-        Ok(json!({
+        let query_params = apply_v2_filtering(include_all_fields, additional_includes);
+        let page = confluence.get_page(&page_id, &query_params).await?;
+        let mut result = json!({
             "success": true,
-            "page": data
-        }))
+            "page": page
+        });
+        let body = result["page"]["body"].clone();
+        mentions::attach(client, config, &mut result, &body, None).await;
+        Ok(result)
     }
 }
 
 #[async_trait]
 impl ToolHandler for GetPageChildrenHandler {
+    fn name(&self) -> &'static str {
+        "confluence_get_page_children"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "page_id".to_string(),
+            string_prop("Page ID or page link", true),
+        );
+        props.insert(
+            "cursor".to_string(),
+            string_prop("next_cursor of the previous call, for the next page", false),
+        );
+        props.insert(
+            "limit".to_string(),
+            number_prop(
+                "Children per page, and per page at each deeper level (1-250)",
+                DEFAULT_CHILDREN_LIMIT as i32,
+            )
+            .with_range(Some(1.0), Some(MAX_CHILDREN_LIMIT as f64)),
+        );
+        props.insert(
+            "depth".to_string(),
+            number_prop(
+                "Levels to fetch: 1 for direct children, up to 5 to nest grandchildren under children (at most 200 pages per call)",
+                1,
+            )
+            .with_range(Some(1.0), Some(children::MAX_DEPTH as f64)),
+        );
+        ToolSchema::new("Get page child pages", props, vec!["page_id".to_string()])
+    }
+
     async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
-        let page_id = args["page_id"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing page_id"))?;
+        let PageChildrenArgs {
+            page_id,
+            include_all_fields,
+            additional_expand: additional_includes,
+            cursor,
+            limit,
+            depth,
+        } = parse_args(&args)?;
+        let limit = limit
+            .unwrap_or(DEFAULT_CHILDREN_LIMIT)
+            .clamp(1, MAX_CHILDREN_LIMIT);
+        let depth = depth.unwrap_or(1).clamp(1, children::MAX_DEPTH);
+
+        let confluence = Confluence::new(config);
+        let page_id = page_links::resolve_page_id(confluence.http(), config, &page_id).await?;
+
+        let mut query_params = apply_v2_filtering(include_all_fields, additional_includes);
+        query_params.push(("limit".to_string(), limit.to_string()));
+        let mut first_page = query_params.clone();
+        if let Some(cursor) = cursor {
+            first_page.push(("cursor".to_string(), cursor));
+        }
+        let listing = confluence.get_page_children(&page_id, &first_page).await?;
+        let next_cursor = listing.next_cursor();
 
-        let include_all_fields = args["include_all_fields"].as_bool();
-        let additional_includes = args["additional_expand"].as_array().map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().map(String::from))
-                .collect()
+        let mut result = json!({
+            "success": true,
+            "children": listing.results,
+            "has_more": next_cursor.is_some()
         });
+        if let Some(next) = &listing.next {
+            result["next_cursor"] = json!(next_cursor);
+            result["_links"] = json!({ "next": next });
+        }
+        if depth > 1 {
+            let top = serde_json::from_value(result["children"].take())?;
+            let tree = children::descendants(&confluence, top, &query_params, depth).await?;
+            result["children"] = json!(tree.children);
+            result["depth"] = json!(depth);
+            result["pages"] = json!(tree.pages);
+            result["truncated"] = json!(tree.truncated);
+        }
+        Ok(result)
+    }
+}
 
-        let client = create_atlassian_client(config);
-        let url = format!(
-            "{}/wiki/api/v2/pages/{}/children",
-            config.get_atlassian_base_url(),
-            page_id
+#[async_trait]
+impl ToolHandler for GetCommentsHandler {
+    fn name(&self) -> &'static str {
+        "confluence_get_comments"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "page_id".to_string(),
+            string_prop("Page ID or page link", true),
+        );
+        props.insert(
+            "include_inline".to_string(),
+            Property::new("boolean")
+                .with_description("Also return inline comments, as inline_comments")
+                .with_default(json!(false)),
         );
+        props.insert(
+            "resolution".to_string(),
+            Property::new("string")
+                .with_description(
+                    "Inline comment threads to return: open (including reopened), resolved or all; footer comments are never resolved (default: all)",
+                )
+                .with_default(json!("all"))
+                .with_enum(vec![json!("all"), json!("open"), json!("resolved")]),
+        );
+        ToolSchema::new(
+            "Get page comments as threads, replies nested under replies",
+            props,
+            vec!["page_id".to_string()],
+        )
+    }
 
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let CommentsArgs {
+            page_id,
+            include_all_fields,
+            additional_expand: additional_includes,
+            include_inline,
+            resolution,
+        } = parse_args(&args)?;
+
+        let client = create_atlassian_client(config);
+        let page_id = page_links::resolve_page_id(&client, config, &page_id).await?;
         let query_params = apply_v2_filtering(include_all_fields, additional_includes);
 
-        let response = client
-            .get(&url)
-            .header("Authorization", create_auth_header(config))
-            .header("Accept", "application/json")
-            .query(&query_params)
-            .send()
+        let footer = comments::threads(
+            &client,
+            config,
+            &page_id,
+            comments::CommentKind::Footer,
+            &query_params,
+            comments::Resolution::All,
+        )
+        .await?;
+        let mut truncated = footer.truncated;
+        let mut result = json!({
+            "success": true,
+            "comments": footer.comments,
+            "total": footer.total
+        });
+        if include_inline {
+            let inline = comments::threads(
+                &client,
+                config,
+                &page_id,
+                comments::CommentKind::Inline,
+                &query_params,
+                resolution,
+            )
             .await?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to get child pages: {}", response.status());
+            truncated |= inline.truncated;
+            result["inline_comments"] = json!(inline.comments);
+            result["inline_total"] = json!(inline.total);
         }
-
-        let data: Value = response.json().await?;
-        Ok(json!({
-            "success": true,
-            "children": data["results"]
-        }))
+        if truncated {
+            result["truncated"] = json!(true);
+        }
+        let content = json!([result["comments"], result["inline_comments"]]);
+        mentions::attach(&client, config, &mut result, &content, None).await;
+        Ok(result)
     }
 }
 
 #[async_trait]
-impl ToolHandler for GetCommentsHandler {
+impl ToolHandler for CreatePageHandler {
+    fn name(&self) -> &'static str {
+        "confluence_create_page"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("space_key".to_string(), string_prop("Space key", true));
+        props.insert("title".to_string(), string_prop("Page title", true));
+        props.insert(
+            "content".to_string(),
+            string_prop("Page content in HTML storage format.", true),
+        );
+        props.insert(
+            "parent_id".to_string(),
+            string_prop("Parent page or folder ID (default: space root)", false),
+        );
+        props.insert(
+            "parent_title".to_string(),
+            string_prop(
+                "Parent page (or folder) title in the same space (used when parent_id is not given)",
+                false,
+            ),
+        );
+        props.insert(
+            "on_duplicate".to_string(),
+            Property::new("string").with_description("When the title already exists in the space: error (default) or update that page in place"
+                        .to_string(),).with_default(json!("error")).with_enum(vec![json!("error"), json!("update")]),
+        );
+        props.insert(
+            "validate_only".to_string(),
+            Property::new("boolean")
+                .with_description(
+                    "Only sanitize and report what would be stripped; nothing is saved".to_string(),
+                )
+                .with_default(json!(false)),
+        );
+        ToolSchema::new(
+            "Create Confluence page",
+            props,
+            vec![
+                "space_key".to_string(),
+                "title".to_string(),
+                "content".to_string(),
+            ],
+        )
+    }
+
     async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
-        let page_id = args["page_id"]
+        let space_key = args["space_key"]
             .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing page_id"))?;
+            .ok_or_else(|| anyhow::anyhow!("Missing space_key"))?;
+        let title = args["title"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing title"))?;
+        let content = args["content"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing content"))?;
+
+        let on_duplicate = args["on_duplicate"].as_str().unwrap_or("error");
+        if !DUPLICATE_TITLE_MODES.contains(&on_duplicate) {
+            anyhow::bail!(
+                "Invalid on_duplicate '{}': expected 'error' or 'update'",
+                on_duplicate
+            );
+        }
+
+        let sanitized = sanitize::sanitize_storage(content);
+        if args["validate_only"].as_bool().unwrap_or(false) {
+            return Ok(sanitized.validation_report());
+        }
 
         let include_all_fields = args["include_all_fields"].as_bool();
         let additional_includes = args["additional_expand"].as_array().map(|arr| {
@@ -192,40 +675,144 @@ impl ToolHandler for GetCommentsHandler {
         });
 
         let client = create_atlassian_client(config);
-        let url = format!(
-            "{}/wiki/api/v2/pages/{}/footer-comments",
-            config.get_atlassian_base_url(),
-            page_id
-        );
-
-        let query_params = apply_v2_filtering(include_all_fields, additional_includes);
-
-        let response = client
-            .get(&url)
-            .header("Authorization", create_auth_header(config))
-            .header("Accept", "application/json")
-            .query(&query_params)
-            .send()
-            .await?;
+        write_policy::check_space(
+            &Confluence::with_client(config, client.clone()),
+            config,
+            space_key,
+        )
+        .await?;
+
+        let space_id = resolve_space_id(&client, config, space_key).await?;
+
+        // Without either, the page is created at the space root
+        let parent_id = match (args["parent_id"].as_str(), args["parent_title"].as_str()) {
+            (Some(id), _) => Some(id.to_string()),
+            (None, Some(parent_title)) => {
+                let parent =
+                    match find_page_by_title(&client, config, &space_id, parent_title).await? {
+                        Some(page) => Some(page),
+                        None => {
+                            folders::find_folder_by_title(&client, config, space_key, parent_title)
+                                .await?
+                        }
+                    }
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Parent page or folder '{}' not found in space '{}'",
+                            parent_title,
+                            space_key
+                        )
+                    })?;
+                parent["id"].as_str().map(String::from)
+            }
+            (None, None) => None,
+        };
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to get comments: {}", response.status());
+        if let Some(existing) = find_page_by_title(&client, config, &space_id, title).await? {
+            let existing_id = existing["id"].as_str().unwrap_or_default();
+            match on_duplicate {
+                "update" => {
+                    let confluence = Confluence::with_client(config, client.clone());
+                    let version = confluence
+                        .get_page_storage(existing_id, None)
+                        .await?
+                        .version_number()
+                        .ok_or_else(|| anyhow::anyhow!("Failed to get current version"))?;
+                    let query_params = apply_v2_filtering(include_all_fields, additional_includes);
+                    let page = confluence
+                        .update_page(
+                            existing_id,
+                            title,
+                            &sanitized.content,
+                            version + 1,
+                            &query_params,
+                        )
+                        .await?;
+                    return Ok(json!({
+                        "success": true,
+                        "page_id": page.id,
+                        "title": page.title,
+                        "updated_existing": true,
+                        "version": page.version_number(),
+                        "sanitized": sanitized.changes
+                    }));
+                }
+                _ => anyhow::bail!(
+                    "A page titled '{}' already exists in space '{}' (page_id {}); pass on_duplicate: \"update\" to overwrite it",
+                    title,
+                    space_key,
+                    existing_id
+                ),
+            }
         }
 
-        let data: Value = response.json().await?;
+        // Now create the page with v2 API
+        let query_params = apply_v2_filtering(include_all_fields, additional_includes);
+        let page = Confluence::with_client(config, client)
+            .create_page(
+                &space_id,
+                title,
+                &sanitized.content,
+                parent_id.as_deref(),
+                &query_params,
+            )
+            .await?;
         Ok(json!({
             "success": true,
-            "comments": data["results"]
+            "page_id": page.id,
+            "title": page.title,
+            "sanitized": sanitized.changes
         }))
     }
 }
 
 #[async_trait]
-impl ToolHandler for CreatePageHandler {
+impl ToolHandler for UpdatePageHandler {
+    fn name(&self) -> &'static str {
+        "confluence_update_page"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("page_id".to_string(), string_prop("Page ID", true));
+        props.insert("title".to_string(), string_prop("Page title", true));
+        props.insert(
+            "content".to_string(),
+            string_prop("Page content in HTML storage format", true),
+        );
+        props.insert(
+            "expected_version".to_string(),
+            Property::new("number").with_description("Version the content was based on. If the page has moved on, the update is refused (or merged, see on_conflict)"
+                        .to_string(),),
+        );
+        props.insert(
+            "on_conflict".to_string(),
+            Property::new("string").with_description("error: return both bodies; merge: 3-way merge with the current version, conflicts are returned"
+                        .to_string(),).with_default(json!("error")).with_enum(vec![json!("error"), json!("merge")]),
+        );
+        props.insert(
+            "validate_only".to_string(),
+            Property::new("boolean")
+                .with_description(
+                    "Only sanitize and report what would be stripped; nothing is saved".to_string(),
+                )
+                .with_default(json!(false)),
+        );
+        ToolSchema::new(
+            "Update Confluence page",
+            props,
+            vec![
+                "page_id".to_string(),
+                "title".to_string(),
+                "content".to_string(),
+            ],
+        )
+    }
+
     async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
-        let space_key = args["space_key"]
+        let page_id = args["page_id"]
             .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing space_key"))?;
+            .ok_or_else(|| anyhow::anyhow!("Missing page_id"))?;
         let title = args["title"]
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("Missing title"))?;
@@ -233,6 +820,23 @@ impl ToolHandler for CreatePageHandler {
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("Missing content"))?;
 
+        let expected_version = args["expected_version"].as_u64();
+        let on_conflict = args["on_conflict"].as_str().unwrap_or("error");
+        if !UPDATE_CONFLICT_MODES.contains(&on_conflict) {
+            anyhow::bail!(
+                "Invalid on_conflict '{}': expected 'error' or 'merge'",
+                on_conflict
+            );
+        }
+
+        let sanitized = sanitize::sanitize_storage(content);
+        if args["validate_only"].as_bool().unwrap_or(false) {
+            return Ok(sanitized.validation_report());
+        }
+
+        let confluence = Confluence::new(config);
+        write_policy::check_page(&confluence, config, page_id).await?;
+
         let include_all_fields = args["include_all_fields"].as_bool();
         let additional_includes = args["additional_expand"].as_array().map(|arr| {
             arr.iter()
@@ -240,157 +844,344 @@ impl ToolHandler for CreatePageHandler {
                 .collect()
         });
 
-        let client = create_atlassian_client(config);
+        let query_params = apply_v2_filtering(include_all_fields, additional_includes);
 
-        // First, convert space_key to space_id using v2 API
-        let space_url = format!("{}/wiki/api/v2/spaces", config.get_atlassian_base_url());
+        // A 409 means someone saved between our read and write: re-read and
+        // re-check against expected_version before trying again
+        for _ in 0..MAX_UPDATE_ATTEMPTS {
+            let current_page = confluence.get_page_storage(page_id, None).await?;
+            let current_version = current_page
+                .version_number()
+                .ok_or_else(|| anyhow::anyhow!("Failed to get current version"))?;
+
+            let mut merged = false;
+            let content = match expected_version {
+                Some(expected) if expected != current_version => {
+                    let current_body = current_page.storage().unwrap_or_default();
+                    if on_conflict != "merge" {
+                        return Ok(conflict_report(
+                            page_id,
+                            expected,
+                            current_version,
+                            &sanitized.content,
+                            current_body,
+                            &[],
+                        ));
+                    }
+
+                    let base_page = confluence.get_page_storage(page_id, Some(expected)).await?;
+                    let base_body = base_page.storage().unwrap_or_default();
+                    match merge::merge3(base_body, &sanitized.content, current_body) {
+                        merge::MergeResult::Clean(content) => {
+                            merged = true;
+                            content
+                        }
+                        merge::MergeResult::Conflicts(conflicts) => {
+                            return Ok(conflict_report(
+                                page_id,
+                                expected,
+                                current_version,
+                                &sanitized.content,
+                                current_body,
+                                &conflicts,
+                            ));
+                        }
+                    }
+                }
+                _ => sanitized.content.clone(),
+            };
+
+            let page = match confluence
+                .update_page(page_id, title, &content, current_version + 1, &query_params)
+                .await
+            {
+                Err(e) if e.is::<VersionConflict>() => {
+                    tracing::debug!("Version conflict updating page {}, retrying", page_id);
+                    continue;
+                }
+                other => other?,
+            };
+            return Ok(json!({
+                "success": true,
+                "page_id": page.id,
+                "version": page.version_number(),
+                "merged": merged,
+                "sanitized": sanitized.changes
+            }));
+        }
 
-        let space_response = client
-            .get(&space_url)
-            .query(&[("keys", space_key)]) // Automatic URL encoding
-            .header("Authorization", create_auth_header(config))
-            .header("Accept", "application/json")
-            .send()
-            .await?;
+        anyhow::bail!(
+            "Failed to update page: version conflict persisted after {} attempts",
+            MAX_UPDATE_ATTEMPTS
+        )
+    }
+}
 
-        if !space_response.status().is_success() {
-            anyhow::bail!(
-                "Failed to get space ID for key '{}': {}",
-                space_key,
-                space_response.status()
-            );
+/// Space id (v2) for a space key (or ID)
+async fn resolve_space_id(
+    client: &reqwest::Client,
+    config: &Config,
+    space_key: &str,
+) -> Result<String> {
+    spaces::space_id(
+        &Confluence::with_client(config, client.clone()),
+        config,
+        space_key,
+    )
+    .await
+}
+
+/// The current page with exactly this title in the space, if any
+async fn find_page_by_title(
+    client: &reqwest::Client,
+    config: &Config,
+    space_id: &str,
+    title: &str,
+) -> Result<Option<Value>> {
+    let page = Confluence::with_client(config, client.clone())
+        .find_page_by_title(space_id, title)
+        .await?;
+    Ok(page.map(serde_json::to_value).transpose()?)
+}
+
+#[async_trait]
+impl ToolHandler for GetPageByTitleHandler {
+    fn name(&self) -> &'static str {
+        "confluence_get_page_by_title"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("space_key".to_string(), string_prop("Space key", true));
+        props.insert("title".to_string(), string_prop("Exact page title", true));
+        ToolSchema::new(
+            "Find a Confluence page by exact title within a space",
+            props,
+            vec!["space_key".to_string(), "title".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let PageByTitleArgs { space_key, title } = parse_args(&args)?;
+
+        let client = create_atlassian_client(config);
+        let space_id = resolve_space_id(&client, config, &space_key).await?;
+
+        match find_page_by_title(&client, config, &space_id, &title).await? {
+            Some(page) => Ok(json!({
+                "success": true,
+                "found": true,
+                "page": page
+            })),
+            None => Ok(json!({
+                "success": true,
+                "found": false,
+                "space_key": space_key,
+                "title": title
+            })),
         }
+    }
+}
 
-        let space_data: Value = space_response.json().await?;
-        let space_id = space_data["results"]
-            .as_array()
-            .and_then(|arr| arr.first())
-            .and_then(|space| space["id"].as_str())
-            .ok_or_else(|| anyhow::anyhow!("Space '{}' not found", space_key))?;
+/// Fetches a page with its storage body, optionally at a past version
+async fn fetch_page_storage(
+    client: &reqwest::Client,
+    config: &Config,
+    page_id: &str,
+    version: Option<u64>,
+) -> Result<Value> {
+    let page = Confluence::with_client(config, client.clone())
+        .get_page_storage(page_id, version)
+        .await?;
+    Ok(serde_json::to_value(page)?)
+}
 
-        // Now create the page with v2 API
-        let url = format!("{}/wiki/api/v2/pages", config.get_atlassian_base_url());
+/// Structured (non-error) result for an update that was not applied
+fn conflict_report(
+    page_id: &str,
+    expected_version: u64,
+    current_version: u64,
+    your_content: &str,
+    current_content: &str,
+    conflicts: &[merge::Conflict],
+) -> Value {
+    json!({
+        "success": false,
+        "conflict": true,
+        "page_id": page_id,
+        "expected_version": expected_version,
+        "current_version": current_version,
+        "your_content": your_content,
+        "current_content": current_content,
+        "conflicts": conflicts
+            .iter()
+            .map(|c| json!({"base": c.base, "yours": c.ours, "theirs": c.theirs}))
+            .collect::<Vec<_>>()
+    })
+}
 
-        let query_params = apply_v2_filtering(include_all_fields, additional_includes);
+#[async_trait]
+impl ToolHandler for GetTasksHandler {
+    fn name(&self) -> &'static str {
+        "confluence_get_tasks"
+    }
 
-        let body = json!({
-            "spaceId": space_id,
-            "title": title,
-            "body": {
-                "representation": "storage",
-                "value": content
-            }
-        });
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "page_id".to_string(),
+            string_prop(
+                "Page ID (page_id or space_id required unless CONFLUENCE_SPACES_FILTER is set)",
+                false,
+            ),
+        );
+        props.insert(
+            "space_id".to_string(),
+            string_prop(
+                "Space key or numeric space ID (default: the spaces in CONFLUENCE_SPACES_FILTER)",
+                false,
+            ),
+        );
+        props.insert(
+            "status".to_string(),
+            Property::new("string")
+                .with_description("Filter by task status")
+                .with_enum(vec![json!("incomplete"), json!("complete")]),
+        );
+        props.insert(
+            "assigned_to".to_string(),
+            string_prop("Assignee account ID", false),
+        );
+        props.insert("limit".to_string(), number_prop("Max results", 25));
+        ToolSchema::new("Get Confluence tasks (action items)", props, vec![])
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let page_id = args["page_id"].as_str();
+        let space = args["space_id"].as_str();
+        if page_id.is_none() && space.is_none() && config.confluence_spaces_filter.is_empty() {
+            anyhow::bail!("Missing page_id or space_id");
+        }
+        let status = parse_task_status(&args["status"])?;
+        let limit = args["limit"].as_u64().unwrap_or(25);
+
+        let client = create_atlassian_client(config);
+        let confluence = Confluence::with_client(config, client.clone());
+        let space_ids = match (page_id, space) {
+            (_, Some(space)) => vec![spaces::space_id(&confluence, config, space).await?],
+            (None, None) => spaces::filter_space_ids(&confluence, config).await?,
+            (Some(_), None) => Vec::new(),
+        };
+        let url = format!("{}/wiki/api/v2/tasks", config.get_atlassian_base_url());
+
+        let mut query_params = vec![
+            ("limit", limit.to_string()),
+            ("body-format", "storage".to_string()),
+            ("include-blank-tasks", "false".to_string()),
+        ];
+        if let Some(page_id) = page_id {
+            query_params.push(("page-id", page_id.to_string()));
+        }
+        if !space_ids.is_empty() {
+            query_params.push(("space-id", space_ids.join(",")));
+        }
+        if let Some(status) = status {
+            query_params.push(("status", status.to_string()));
+        }
+        if let Some(assignee) = args["assigned_to"].as_str() {
+            query_params.push(("assigned-to", assignee.to_string()));
+        }
 
         let response = client
-            .post(&url)
+            .get(&url)
             .header("Authorization", create_auth_header(config))
-            .header("Content-Type", "application/json")
+            .header("Accept", "application/json")
             .query(&query_params)
-            .json(&body)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let error = response.text().await?;
-            anyhow::bail!("Failed to create page: {}", error);
+            anyhow::bail!("Failed to get tasks: {}", response.status());
         }
 
         let data: Value = response.json().await?;
         Ok(json!({
             "success": true,
-            "page_id": data["id"],
-            "title": data["title"]
+            "tasks": data["results"]
         }))
     }
 }
 
 #[async_trait]
-impl ToolHandler for UpdatePageHandler {
-    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
-        let page_id = args["page_id"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing page_id"))?;
-        let title = args["title"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing title"))?;
-        let content = args["content"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing content"))?;
+impl ToolHandler for UpdateTaskStatusHandler {
+    fn name(&self) -> &'static str {
+        "confluence_update_task_status"
+    }
 
-        let client = create_atlassian_client(config);
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("task_id".to_string(), string_prop("Task ID", true));
+        props.insert(
+            "status".to_string(),
+            Property::new("string")
+                .with_description("New task status")
+                .with_enum(vec![json!("complete"), json!("incomplete")]),
+        );
+        ToolSchema::new(
+            "Check off or reopen a Confluence task",
+            props,
+            vec!["task_id".to_string(), "status".to_string()],
+        )
+    }
 
-        let include_all_fields = args["include_all_fields"].as_bool();
-        let additional_includes = args["additional_expand"].as_array().map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().map(String::from))
-                .collect()
-        });
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let UpdateTaskStatusArgs { task_id, status } = parse_args(&args)?;
+        let status = status.as_str();
 
-        // First, get the current page to get the version number using v2 API
-        let get_url = format!(
-            "{}/wiki/api/v2/pages/{}",
+        let client = create_atlassian_client(config);
+        let url = format!(
+            "{}/wiki/api/v2/tasks/{}",
             config.get_atlassian_base_url(),
-            page_id
+            task_id
         );
 
+        // The update endpoint expects the full task object; only status is mutable
         let get_response = client
-            .get(&get_url)
+            .get(&url)
             .header("Authorization", create_auth_header(config))
             .header("Accept", "application/json")
-            .query(&[("include-version", "true")])
             .send()
             .await?;
 
         if !get_response.status().is_success() {
-            anyhow::bail!("Failed to get page for update: {}", get_response.status());
+            anyhow::bail!("Failed to get task for update: {}", get_response.status());
         }
 
-        let current_page: Value = get_response.json().await?;
-        let current_version = current_page["version"]["number"]
-            .as_u64()
-            .ok_or_else(|| anyhow::anyhow!("Failed to get current version"))?;
-
-        // Now update the page with v2 API
-        let update_url = format!(
-            "{}/wiki/api/v2/pages/{}",
-            config.get_atlassian_base_url(),
-            page_id
-        );
-
-        let query_params = apply_v2_filtering(include_all_fields, additional_includes);
-
-        let body = json!({
-            "id": page_id,
-            "title": title,
-            "body": {
-                "representation": "storage",
-                "value": content
-            },
-            "version": {
-                "number": current_version + 1
-            }
-        });
+        let mut task: Value = get_response.json().await?;
+        if let Some(space_id) = task["spaceId"].as_str() {
+            let confluence = Confluence::with_client(config, client.clone());
+            write_policy::check_space(&confluence, config, space_id).await?;
+        }
+        task["status"] = json!(status);
 
         let response = client
-            .put(&update_url)
+            .put(&url)
             .header("Authorization", create_auth_header(config))
             .header("Content-Type", "application/json")
-            .query(&query_params)
-            .json(&body)
+            .json(&task)
             .send()
             .await?;
 
         if !response.status().is_success() {
             let error = response.text().await?;
-            anyhow::bail!("Failed to update page: {}", error);
+            anyhow::bail!("Failed to update task: {}", error);
         }
 
         let data: Value = response.json().await?;
         Ok(json!({
             "success": true,
-            "page_id": data["id"],
-            "version": data["version"]["number"]
+            "task_id": data["id"],
+            "status": data["status"]
         }))
     }
 }
@@ -399,21 +1190,13 @@ impl ToolHandler for UpdatePageHandler {
 mod tests {
     use super::*;
     use crate::config::Config;
+    use crate::test_support::ConfigBuilder;
 
     // Helper function to create test config
     fn create_test_config(confluence_spaces_filter: Vec<String>) -> Config {
-        Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token123".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter,
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        }
+        ConfigBuilder::new()
+            .with(|config| config.confluence_spaces_filter = confluence_spaces_filter)
+            .build()
     }
 
     // T017: Confluence SearchHandler tests
@@ -457,26 +1240,7 @@ mod tests {
         let config = create_test_config(vec!["SPACE1".to_string(), "SPACE2".to_string()]);
         let cql = "type = page";
 
-        // Simulate space filter logic
-        let final_cql = if !config.confluence_spaces_filter.is_empty() {
-            let cql_lower = cql.to_lowercase();
-            if cql_lower.contains("space ")
-                || cql_lower.contains("space=")
-                || cql_lower.contains("space in")
-            {
-                cql.to_string()
-            } else {
-                let spaces = config
-                    .confluence_spaces_filter
-                    .iter()
-                    .map(|s| format!("\"{}\"", s))
-                    .collect::<Vec<_>>()
-                    .join(",");
-                format!("space IN ({}) AND ({})", spaces, cql)
-            }
-        } else {
-            cql.to_string()
-        };
+        let final_cql = spaces::scope_search_cql(cql, &config.confluence_spaces_filter);
 
         assert_eq!(
             final_cql,
@@ -489,26 +1253,7 @@ mod tests {
         let config = create_test_config(vec!["SPACE1".to_string()]);
         let cql = "space = MYSPACE AND type = page";
 
-        // Simulate space filter logic
-        let final_cql = if !config.confluence_spaces_filter.is_empty() {
-            let cql_lower = cql.to_lowercase();
-            if cql_lower.contains("space ")
-                || cql_lower.contains("space=")
-                || cql_lower.contains("space in")
-            {
-                cql.to_string()
-            } else {
-                let spaces = config
-                    .confluence_spaces_filter
-                    .iter()
-                    .map(|s| format!("\"{}\"", s))
-                    .collect::<Vec<_>>()
-                    .join(",");
-                format!("space IN ({}) AND ({})", spaces, cql)
-            }
-        } else {
-            cql.to_string()
-        };
+        let final_cql = spaces::scope_search_cql(cql, &config.confluence_spaces_filter);
 
         assert_eq!(final_cql, "space = MYSPACE AND type = page");
     }
@@ -669,6 +1414,59 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Missing content"));
     }
 
+    #[test]
+    fn test_create_page_handler_invalid_on_duplicate() {
+        let handler = CreatePageHandler;
+        let config = create_test_config(vec![]);
+        let args = json!({
+            "space_key": "TEST",
+            "title": "Test Page",
+            "content": "<p>x</p>",
+            "on_duplicate": "skip"
+        });
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(handler.execute(args, &config));
+
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid on_duplicate")
+        );
+    }
+
+    #[test]
+    fn test_get_page_by_title_missing_title() {
+        let handler = GetPageByTitleHandler;
+        let config = create_test_config(vec![]);
+        let args = json!({"space_key": "TEST"});
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(handler.execute(args, &config));
+
+        assert!(result.unwrap_err().to_string().contains("Missing title"));
+    }
+
+    #[test]
+    fn test_create_page_handler_validate_only() {
+        let handler = CreatePageHandler;
+        let config = create_test_config(vec![]);
+        let args = json!({
+            "space_key": "TEST",
+            "title": "Test Page",
+            "content": "<p>Hi<script>alert(1)</script>",
+            "validate_only": true
+        });
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(handler.execute(args, &config)).unwrap();
+
+        assert_eq!(result["validate_only"], true);
+        assert_eq!(result["content"], "<p>Hi</p>");
+        assert_eq!(result["changes"].as_array().unwrap().len(), 2);
+    }
+
     #[test]
     fn test_create_page_handler_body_format() {
         let title = "Test Page";
@@ -739,6 +1537,45 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Missing content"));
     }
 
+    #[test]
+    fn test_update_page_handler_invalid_on_conflict() {
+        let handler = UpdatePageHandler;
+        let config = create_test_config(vec![]);
+        let args = json!({
+            "page_id": "12345",
+            "title": "Test Page",
+            "content": "<p>x</p>",
+            "on_conflict": "overwrite"
+        });
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(handler.execute(args, &config));
+
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid on_conflict")
+        );
+    }
+
+    #[test]
+    fn test_conflict_report() {
+        let conflicts = vec![merge::Conflict {
+            base: "<p>a</p>".to_string(),
+            ours: "<p>b</p>".to_string(),
+            theirs: "<p>c</p>".to_string(),
+        }];
+        let report = conflict_report("12345", 3, 5, "<p>b</p>", "<p>c</p>", &conflicts);
+
+        assert_eq!(report["success"], false);
+        assert_eq!(report["conflict"], true);
+        assert_eq!(report["expected_version"], 3);
+        assert_eq!(report["current_version"], 5);
+        assert_eq!(report["conflicts"][0]["yours"], "<p>b</p>");
+        assert_eq!(report["conflicts"][0]["theirs"], "<p>c</p>");
+    }
+
     #[test]
     fn test_update_page_handler_body_format() {
         let page_id = "12345";
@@ -764,4 +1601,59 @@ mod tests {
         assert_eq!(body["body"]["value"], "<p>Updated content</p>");
         assert_eq!(body["version"]["number"], 6);
     }
+
+    // Task handler tests
+    #[test]
+    fn test_get_tasks_handler_requires_page_or_space() {
+        let handler = GetTasksHandler;
+        let config = create_test_config(vec![]);
+        let args = json!({"status": "incomplete"});
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(handler.execute(args, &config));
+
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Missing page_id or space_id")
+        );
+    }
+
+    #[test]
+    fn test_parse_task_status() {
+        assert_eq!(parse_task_status(&Value::Null).unwrap(), None);
+        assert_eq!(
+            parse_task_status(&json!("complete")).unwrap(),
+            Some("complete")
+        );
+        assert!(parse_task_status(&json!("done")).is_err());
+    }
+
+    #[test]
+    fn test_update_task_status_handler_missing_status() {
+        let handler = UpdateTaskStatusHandler;
+        let config = create_test_config(vec![]);
+        let args = json!({"task_id": "42"});
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(handler.execute(args, &config));
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Missing status"));
+    }
+
+    #[test]
+    fn test_update_task_status_handler_missing_task_id() {
+        let handler = UpdateTaskStatusHandler;
+        let config = create_test_config(vec![]);
+        let args = json!({"status": "complete"});
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(handler.execute(args, &config));
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Missing task_id"));
+    }
 }
@@ -0,0 +1,137 @@
+//! Page IDs from pasted links
+//!
+//! Page tools take a `page_id`, but users paste what their browser shows:
+//! `.../wiki/spaces/ENG/pages/123/Title`, `.../viewpage.action?pageId=123`
+//! or the older pretty form `.../wiki/display/ENG/Page+Title`, which has no
+//! ID and is looked up by title in its space.
+
+use anyhow::Result;
+use reqwest::Url;
+
+use crate::config::Config;
+
+use super::{find_page_by_title, resolve_space_id};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PageRef {
+    Id(String),
+    Title { space_key: String, title: String },
+}
+
+fn is_id(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Decodes a path segment, where Confluence writes spaces as `+`
+fn decode_segment(segment: &str) -> String {
+    Url::parse(&format!("http://localhost/?s={}", segment))
+        .ok()
+        .and_then(|url| {
+            url.query_pairs()
+                .next()
+                .map(|(_, value)| value.into_owned())
+        })
+        .unwrap_or_else(|| segment.to_string())
+}
+
+/// What a `page_id` argument points at, if it is an ID or a page link
+pub fn parse_page_ref(input: &str) -> Option<PageRef> {
+    let input = input.trim();
+    if is_id(input) {
+        return Some(PageRef::Id(input.to_string()));
+    }
+
+    let url = Url::parse(input).ok()?;
+    if let Some((_, id)) = url.query_pairs().find(|(key, _)| key == "pageId") {
+        return is_id(&id).then(|| PageRef::Id(id.into_owned()));
+    }
+
+    let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+    // `/spaces/ENG/pages/123/Title`, `/pages/edit-v2/123`
+    if let Some(at) = segments.iter().position(|s| *s == "pages") {
+        return segments[at + 1..]
+            .iter()
+            .take(2)
+            .find(|s| is_id(s))
+            .map(|id| PageRef::Id(id.to_string()));
+    }
+    // `/display/ENG/Page+Title`
+    if let Some(at) = segments.iter().position(|s| *s == "display")
+        && let [space_key, title, ..] = &segments[at + 1..]
+    {
+        return Some(PageRef::Title {
+            space_key: decode_segment(space_key),
+            title: decode_segment(title),
+        });
+    }
+    None
+}
+
+/// The page ID behind a `page_id` argument (an ID or any page link)
+pub async fn resolve_page_id(
+    client: &reqwest::Client,
+    config: &Config,
+    input: &str,
+) -> Result<String> {
+    match parse_page_ref(input) {
+        Some(PageRef::Id(id)) => Ok(id),
+        Some(PageRef::Title { space_key, title }) => {
+            let space_id = resolve_space_id(client, config, &space_key).await?;
+            let page = find_page_by_title(client, config, &space_id, &title)
+                .await?
+                .ok_or_else(|| {
+                    anyhow::anyhow!("No page titled '{}' in space {}", title, space_key)
+                })?;
+            page["id"]
+                .as_str()
+                .map(String::from)
+                .ok_or_else(|| anyhow::anyhow!("Page lookup returned no ID"))
+        }
+        None => anyhow::bail!(
+            "Invalid page_id '{}': expected a page ID or page link (.../pages/<id>/...)",
+            input
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_page_ref_from_ids_and_links() {
+        let id = |id: &str| Some(PageRef::Id(id.to_string()));
+        assert_eq!(parse_page_ref(" 12345 "), id("12345"));
+        assert_eq!(
+            parse_page_ref("https://x.atlassian.net/wiki/spaces/ENG/pages/12345/Release+Notes"),
+            id("12345")
+        );
+        assert_eq!(
+            parse_page_ref(
+                "https://x.atlassian.net/wiki/spaces/ENG/pages/edit-v2/777?draftShareId=a"
+            ),
+            id("777")
+        );
+        assert_eq!(
+            parse_page_ref("https://x.atlassian.net/wiki/pages/viewpage.action?pageId=42"),
+            id("42")
+        );
+        assert_eq!(
+            parse_page_ref("https://x.atlassian.net/wiki/display/ENG/Release+Notes%3A+2026"),
+            Some(PageRef::Title {
+                space_key: "ENG".to_string(),
+                title: "Release Notes: 2026".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_page_ref_rejects_other_input() {
+        assert_eq!(parse_page_ref("Release Notes"), None);
+        assert_eq!(
+            parse_page_ref("https://x.atlassian.net/wiki/spaces/ENG/overview"),
+            None
+        );
+        assert_eq!(parse_page_ref("https://x.atlassian.net/wiki/x/AbCd"), None);
+    }
+}
@@ -0,0 +1,235 @@
+//! Plain-text renderings of pages, cached by page version
+//!
+//! [`storage_to_text`] keeps one line per block (paragraph, heading, list
+//! item, table row) and drops the markup. With `ATLASSIAN_CACHE_DIR` set,
+//! renderings go to the disk cache as `page-text/<page id>-<version>.json`:
+//! a version never changes, so an unchanged page is served from disk after
+//! a one-request version check, or with no request at all when the caller
+//! names the version. Saving a version removes the page's other ones.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::client::Confluence;
+use crate::config::Config;
+use crate::utils::disk_cache::DiskCache;
+
+/// Elements that start and end a line of text
+const BLOCK_TAGS: &[&str] = &[
+    "p",
+    "div",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "li",
+    "tr",
+    "br",
+    "hr",
+    "pre",
+    "blockquote",
+    "table",
+    "ul",
+    "ol",
+    "ac:task",
+    "ac:layout-cell",
+];
+
+/// Elements whose content is not text: macro parameters, task IDs and states
+const SKIPPED_TAGS: &[&str] = &["ac:parameter", "ac:task-id", "ac:task-status"];
+
+/// The visible text of a storage body, a line per block; code macro bodies
+/// (CDATA) are kept as written
+pub fn storage_to_text(storage: &str) -> String {
+    let mut text = String::new();
+    let mut rest = storage;
+    while let Some(at) = rest.find('<') {
+        text.push_str(&decode_entities(&rest[..at]));
+        rest = &rest[at..];
+
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = cdata.find("]]>").unwrap_or(cdata.len());
+            text.push('\n');
+            text.push_str(&cdata[..end]);
+            text.push('\n');
+            rest = cdata.get(end + 3..).unwrap_or_default();
+            continue;
+        }
+        if rest.starts_with("<!--") {
+            rest = rest.find("-->").map_or("", |end| &rest[end + 3..]);
+            continue;
+        }
+
+        let Some(close) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[1..close];
+        rest = &rest[close + 1..];
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match name.as_str() {
+            name if SKIPPED_TAGS.contains(&name) && !closing && !tag.ends_with('/') => {
+                let end = format!("</{}>", name);
+                rest = rest.find(&end).map_or("", |at| &rest[at + end.len()..]);
+            }
+            "td" | "th" if closing => text.push_str(" | "),
+            "li" if !closing => text.push_str("\n- "),
+            // A paragraph inside a list item stays on the bullet's line
+            name if BLOCK_TAGS.contains(&name) && !text.ends_with("\n- ") => text.push('\n'),
+            _ => {}
+        }
+    }
+    text.push_str(&decode_entities(rest));
+    tidy(&text)
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Whitespace collapsed within lines, blank lines dropped, trailing `|` of
+/// table rows removed
+fn tidy(text: &str) -> String {
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .map(|line| line.trim_end_matches(['|', ' ']).to_string())
+        .filter(|line| !line.is_empty() && line != "-")
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A page's plain text at one version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageText {
+    pub title: String,
+    pub version: u64,
+    pub text: String,
+}
+
+/// `page-text` entries of the disk cache, `<page id>-<version>.json`
+pub struct TextCache {
+    cache: DiskCache,
+}
+
+impl TextCache {
+    /// The cache for the configured site, when `ATLASSIAN_CACHE_DIR` is set
+    pub fn from_config(config: &Config) -> Option<Self> {
+        DiskCache::from_config(config, "page-text").map(|cache| Self { cache })
+    }
+
+    /// Page IDs are numeric; anything else is not cached
+    fn key(page_id: &str, version: u64) -> Option<String> {
+        (!page_id.is_empty() && page_id.bytes().all(|b| b.is_ascii_digit()))
+            .then(|| format!("{}-{}.json", page_id, version))
+    }
+
+    pub fn get(&self, page_id: &str, version: u64) -> Option<PageText> {
+        let text = self.cache.get(&Self::key(page_id, version)?)?;
+        serde_json::from_str(&text).ok()
+    }
+
+    /// Saves `text` and removes the page's other versions
+    pub fn put(&self, page_id: &str, text: &PageText) {
+        let Some(key) = Self::key(page_id, text.version) else {
+            return;
+        };
+        let prefix = format!("{}-", page_id);
+        self.cache.remove_where(|name| {
+            name.strip_prefix(&prefix)
+                .and_then(|rest| rest.strip_suffix(".json"))
+                .is_some_and(|version| version.bytes().all(|b| b.is_ascii_digit()))
+        });
+        match serde_json::to_string(text) {
+            Ok(value) => self.cache.put(&key, &value),
+            Err(e) => tracing::warn!(error = %e, "Failed to cache page text"),
+        }
+    }
+}
+
+/// The page's plain text at `version` (default: current), from the cache
+/// when it has that version
+pub async fn page_text(
+    confluence: &Confluence<'_>,
+    config: &Config,
+    page_id: &str,
+    version: Option<u64>,
+) -> Result<PageText> {
+    let cache = TextCache::from_config(config);
+    let version = match (&cache, version) {
+        // The version alone is a small request; the body is fetched on a miss
+        (Some(_), None) => confluence.get_page(page_id, &[]).await?.version_number(),
+        (_, version) => version,
+    };
+    if let (Some(cache), Some(version)) = (&cache, version)
+        && let Some(cached) = cache.get(page_id, version)
+    {
+        crate::mcp::stats::record_cache_hit();
+        return Ok(cached);
+    }
+
+    let page = confluence.get_page_storage(page_id, version).await?;
+    let text = PageText {
+        version: page.version_number().unwrap_or_default(),
+        text: storage_to_text(page.storage().unwrap_or_default()),
+        title: page.title,
+    };
+    if let Some(cache) = &cache {
+        cache.put(page_id, &text);
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_to_text_keeps_blocks_apart() {
+        let storage = "<h1>Runbook</h1><p>Restart the <strong>api</strong>&nbsp;pods &amp; wait.</p>\
+            <ul><li><p>one</p></li><li>two</li></ul>\
+            <table><tbody><tr><th>Env</th><th>Owner</th></tr><tr><td>prod</td><td>ops</td></tr></tbody></table>\
+            <ac:structured-macro ac:name=\"code\"><ac:parameter ac:name=\"language\">bash</ac:parameter><ac:plain-text-body><![CDATA[kubectl get <pods>]]></ac:plain-text-body></ac:structured-macro>\
+            <!-- hidden --><ac:task-list><ac:task><ac:task-id>3</ac:task-id><ac:task-status>incomplete</ac:task-status><ac:task-body>Ship it</ac:task-body></ac:task></ac:task-list>\
+            <p>Done<br/>really</p>";
+        assert_eq!(
+            storage_to_text(storage),
+            "Runbook\nRestart the api pods & wait.\n- one\n- two\nEnv | Owner\nprod | ops\nkubectl get <pods>\nShip it\nDone\nreally"
+        );
+    }
+
+    #[test]
+    fn test_text_cache_keeps_the_latest_version() {
+        let dir = std::env::temp_dir().join(format!("mcp-text-cache-{}", std::process::id()));
+        let cache = TextCache {
+            cache: DiskCache::new(&dir, "site", "page-text", 0),
+        };
+        let text = |version| PageText {
+            title: "Runbook".to_string(),
+            version,
+            text: format!("v{}", version),
+        };
+
+        cache.put("42", &text(1));
+        assert_eq!(cache.get("42", 1), Some(text(1)));
+        cache.put("42", &text(2));
+        assert_eq!(cache.get("42", 1), None);
+        assert_eq!(cache.get("42", 2), Some(text(2)));
+        assert_eq!(cache.get("../42", 2), None);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,229 @@
+//! Grouping and ranking of CQL search hits
+//!
+//! A text search matches a page, each of its attachments and its comments as
+//! separate results, so one page can fill half of a `limit: 10` answer.
+//! [`group_by_page`] folds every hit into one result per containing page:
+//! the page's own hit when it matched, otherwise the best-ranked hit, with
+//! `matched_via` listing the content types that matched and `hits` counting
+//! them. Groups keep CQL's relevance order (their best hit's position) or are
+//! sorted by their newest `lastModified`.
+
+use serde_json::{Value, json};
+
+use crate::utils::time;
+
+use super::page_links::{PageRef, parse_page_ref};
+
+/// Result order after grouping
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ranking {
+    /// CQL's own order
+    Relevance,
+    /// Newest `lastModified` first
+    LastModified,
+}
+
+impl Ranking {
+    pub fn from_args(args: &Value) -> anyhow::Result<Self> {
+        match args["sort"].as_str() {
+            None | Some("relevance") => Ok(Self::Relevance),
+            Some("last_modified") => Ok(Self::LastModified),
+            Some(other) => anyhow::bail!(
+                "Invalid sort '{}': expected 'relevance' or 'last_modified'",
+                other
+            ),
+        }
+    }
+}
+
+/// The page a hit belongs to: the page itself, or the page an attachment or
+/// comment hangs off (`pageId=` or `/pages/<id>` in its `url`)
+fn containing_page_id(result: &Value) -> Option<String> {
+    let content = &result["content"];
+    if matches!(content["type"].as_str(), Some("page" | "blogpost")) {
+        return content["id"].as_str().map(String::from);
+    }
+    if let Some(id) = content["container"]["id"].as_str() {
+        return Some(id.to_string());
+    }
+    let link = result["url"]
+        .as_str()
+        .or(content["_links"]["webui"].as_str())?;
+    match parse_page_ref(&format!("http://localhost/wiki{}", link)) {
+        Some(PageRef::Id(id)) => Some(id),
+        _ => None,
+    }
+}
+
+fn last_modified(result: &Value) -> i64 {
+    result["lastModified"]
+        .as_str()
+        .and_then(time::parse_timestamp)
+        .map_or(i64::MIN, |(utc, _)| utc)
+}
+
+struct Group {
+    page_id: Option<String>,
+    representative: Value,
+    has_page_hit: bool,
+    matched_via: Vec<String>,
+    hits: usize,
+    last_modified: i64,
+}
+
+/// One result per containing page, ordered by `ranking`
+pub fn group_by_page(results: Vec<Value>, ranking: Ranking) -> Vec<Value> {
+    let mut groups: Vec<Group> = Vec::new();
+    for result in results {
+        let page_id = containing_page_id(&result);
+        let content_type = result["content"]["type"]
+            .as_str()
+            .unwrap_or("unknown")
+            .to_string();
+        let is_page = matches!(content_type.as_str(), "page" | "blogpost");
+        let modified = last_modified(&result);
+
+        let group = page_id
+            .as_ref()
+            .and_then(|id| groups.iter_mut().find(|g| g.page_id.as_ref() == Some(id)));
+        match group {
+            Some(group) => {
+                group.hits += 1;
+                group.last_modified = group.last_modified.max(modified);
+                if !group.matched_via.contains(&content_type) {
+                    group.matched_via.push(content_type);
+                }
+                if is_page && !group.has_page_hit {
+                    group.representative = result;
+                    group.has_page_hit = true;
+                }
+            }
+            None => groups.push(Group {
+                page_id,
+                representative: result,
+                has_page_hit: is_page,
+                matched_via: vec![content_type],
+                hits: 1,
+                last_modified: modified,
+            }),
+        }
+    }
+
+    if ranking == Ranking::LastModified {
+        // Stable, so equally recent groups keep their relevance order
+        groups.sort_by_key(|g| std::cmp::Reverse(g.last_modified));
+    }
+
+    groups
+        .into_iter()
+        .map(|group| {
+            let mut result = group.representative;
+            if let Some(object) = result.as_object_mut() {
+                if let Some(page_id) = group.page_id {
+                    object.insert("page_id".to_string(), json!(page_id));
+                }
+                object.insert("matched_via".to_string(), json!(group.matched_via));
+                object.insert("hits".to_string(), json!(group.hits));
+            }
+            result
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(id: &str, content_type: &str, url: &str, modified: &str) -> Value {
+        json!({
+            "content": {"id": id, "type": content_type},
+            "title": id,
+            "url": url,
+            "lastModified": modified
+        })
+    }
+
+    #[test]
+    fn test_hits_are_grouped_under_their_page() {
+        let results = vec![
+            hit(
+                "att1",
+                "attachment",
+                "/pages/viewpageattachments.action?pageId=10&preview=/10/att1/a.pdf",
+                "2026-01-05T10:00:00.000Z",
+            ),
+            hit(
+                "20",
+                "page",
+                "/spaces/ENG/pages/20/Other",
+                "2026-01-01T10:00:00.000Z",
+            ),
+            hit(
+                "10",
+                "page",
+                "/spaces/ENG/pages/10/Spec",
+                "2026-01-02T10:00:00.000Z",
+            ),
+            hit(
+                "c1",
+                "comment",
+                "/spaces/ENG/pages/10/Spec?focusedCommentId=c1#comment-c1",
+                "2026-01-03T10:00:00.000Z",
+            ),
+        ];
+
+        let grouped = group_by_page(results, Ranking::Relevance);
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped[0]["content"]["id"], "10");
+        assert_eq!(grouped[0]["page_id"], "10");
+        assert_eq!(grouped[0]["hits"], 3);
+        assert_eq!(
+            grouped[0]["matched_via"],
+            json!(["attachment", "page", "comment"])
+        );
+        assert_eq!(grouped[1]["content"]["id"], "20");
+        assert_eq!(grouped[1]["hits"], 1);
+    }
+
+    #[test]
+    fn test_last_modified_ranking_uses_newest_hit() {
+        let results = vec![
+            hit(
+                "20",
+                "page",
+                "/spaces/ENG/pages/20/Old",
+                "2026-01-01T10:00:00.000Z",
+            ),
+            hit(
+                "30",
+                "page",
+                "/spaces/ENG/pages/30/New",
+                "2026-02-01T10:00:00.000Z",
+            ),
+            hit(
+                "c2",
+                "comment",
+                "/spaces/ENG/pages/20/Old?focusedCommentId=c2",
+                "2026-03-01T10:00:00.000Z",
+            ),
+            json!({"content": {"type": "space"}, "title": "ENG"}),
+        ];
+
+        let grouped = group_by_page(results, Ranking::LastModified);
+        let ids: Vec<&str> = grouped
+            .iter()
+            .map(|r| r["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["20", "30", "ENG"]);
+    }
+
+    #[test]
+    fn test_sort_argument() {
+        assert_eq!(Ranking::from_args(&json!({})).unwrap(), Ranking::Relevance);
+        assert_eq!(
+            Ranking::from_args(&json!({"sort": "last_modified"})).unwrap(),
+            Ranking::LastModified
+        );
+        assert!(Ranking::from_args(&json!({"sort": "score"})).is_err());
+    }
+}
@@ -0,0 +1,324 @@
+//! Page read/update restrictions
+//!
+//! Confluence replaces every restriction on a page with each set call, so
+//! `confluence_set_page_restrictions` starts from the current restrictions and
+//! only swaps the operations it is given (an empty list lifts that one). The
+//! calling user is added to each restricted operation unless `include_self` is
+//! false, so a restricted page stays editable by whoever restricted it.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::client::Confluence;
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema, string_prop};
+use crate::tools::write_policy;
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+pub struct GetPageRestrictionsHandler;
+pub struct SetPageRestrictionsHandler;
+
+const OPERATIONS: [&str; 2] = ["read", "update"];
+
+/// Users (account IDs) and groups (names) allowed for one operation
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Allowed {
+    pub users: Vec<String>,
+    pub groups: Vec<String>,
+}
+
+impl Allowed {
+    fn is_empty(&self) -> bool {
+        self.users.is_empty() && self.groups.is_empty()
+    }
+}
+
+fn strings(value: &Value) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for item in value.as_array().into_iter().flatten() {
+        if let Some(s) = item.as_str().map(str::trim).filter(|s| !s.is_empty())
+            && !out.iter().any(|o| o == s)
+        {
+            out.push(s.to_string());
+        }
+    }
+    out
+}
+
+/// `{read, update}` from a `/restriction` response
+pub fn parse_restrictions(data: &Value) -> [Allowed; 2] {
+    OPERATIONS.map(|operation| {
+        let Some(entry) = data["results"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|r| r["operation"] == operation)
+        else {
+            return Allowed::default();
+        };
+        let field = |kind: &str, key: &str| -> Vec<String> {
+            entry["restrictions"][kind]["results"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|item| item[key].as_str().map(str::to_string))
+                .collect()
+        };
+        Allowed {
+            users: field("user", "accountId"),
+            groups: field("group", "name"),
+        }
+    })
+}
+
+/// Request body for `PUT /restriction`; operations without anyone are left out
+pub fn restrictions_body(restrictions: &[Allowed; 2]) -> Value {
+    let entries: Vec<Value> = OPERATIONS
+        .iter()
+        .zip(restrictions)
+        .filter(|(_, allowed)| !allowed.is_empty())
+        .map(|(operation, allowed)| {
+            let users: Vec<Value> = allowed
+                .users
+                .iter()
+                .map(|id| json!({"type": "known", "accountId": id}))
+                .collect();
+            let groups: Vec<Value> = allowed
+                .groups
+                .iter()
+                .map(|name| json!({"type": "group", "name": name}))
+                .collect();
+            json!({"operation": operation, "restrictions": {"user": users, "group": groups}})
+        })
+        .collect();
+    json!(entries)
+}
+
+fn restrictions_json(restrictions: &[Allowed; 2]) -> Value {
+    let mut out = json!({});
+    for (operation, allowed) in OPERATIONS.iter().zip(restrictions) {
+        out[*operation] = json!({
+            "restricted": !allowed.is_empty(),
+            "users": allowed.users,
+            "groups": allowed.groups
+        });
+    }
+    out
+}
+
+async fn fetch_restrictions(
+    client: &reqwest::Client,
+    config: &Config,
+    page_id: &str,
+) -> Result<[Allowed; 2]> {
+    let url = format!(
+        "{}/wiki/rest/api/content/{}/restriction?expand=restrictions.user,restrictions.group",
+        config.get_atlassian_base_url(),
+        page_id
+    );
+    let response = client
+        .get(&url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get page restrictions: {}", response.status());
+    }
+    let data: Value = response.json().await?;
+    Ok(parse_restrictions(&data))
+}
+
+async fn current_account_id(client: &reqwest::Client, config: &Config) -> Result<String> {
+    let url = format!(
+        "{}/wiki/rest/api/user/current",
+        config.get_atlassian_base_url()
+    );
+    let response = client
+        .get(&url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get current user: {}", response.status());
+    }
+    let data: Value = response.json().await?;
+    data["accountId"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("Current user has no accountId"))
+}
+
+fn page_id(args: &Value) -> Result<&str> {
+    args["page_id"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing page_id"))
+}
+
+#[async_trait]
+impl ToolHandler for GetPageRestrictionsHandler {
+    fn name(&self) -> &'static str {
+        "confluence_get_page_restrictions"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("page_id".to_string(), string_prop("Page ID", true));
+        ToolSchema::new(
+            "Get who may read and update a page (users and groups per operation)",
+            props,
+            vec!["page_id".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let page_id = page_id(&args)?;
+        let client = create_atlassian_client(config);
+        let restrictions = fetch_restrictions(&client, config, page_id).await?;
+        Ok(json!({
+            "success": true,
+            "page_id": page_id,
+            "restrictions": restrictions_json(&restrictions)
+        }))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for SetPageRestrictionsHandler {
+    fn name(&self) -> &'static str {
+        "confluence_set_page_restrictions"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("page_id".to_string(), string_prop("Page ID", true));
+        for operation in ["read", "update"] {
+            props.insert(
+                operation.to_string(),
+                Property::new("object").with_description(format!(
+                        "Who may {} the page: {{\"users\": [accountId], \"groups\": [name]}}; empty lists lift the restriction, omitted leaves it as-is",
+                        operation
+                    )),
+            );
+        }
+        props.insert(
+            "include_self".to_string(),
+            Property::new("boolean").with_description("Add the calling user to each restricted operation so the page stays accessible"
+                        .to_string(),).with_default(json!(true)),
+        );
+        ToolSchema::new(
+            "Restrict who may read or update a page by user and group",
+            props,
+            vec!["page_id".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let page_id = page_id(&args)?;
+        if OPERATIONS.iter().all(|op| args[*op].is_null()) {
+            anyhow::bail!("Nothing to change: pass read and/or update");
+        }
+        let include_self = args["include_self"].as_bool().unwrap_or(true);
+
+        let client = create_atlassian_client(config);
+        let confluence = Confluence::with_client(config, client.clone());
+        write_policy::check_page(&confluence, config, page_id).await?;
+        let mut restrictions = fetch_restrictions(&client, config, page_id).await?;
+        for (operation, allowed) in OPERATIONS.iter().zip(restrictions.iter_mut()) {
+            let given = &args[*operation];
+            if given.is_null() {
+                continue;
+            }
+            *allowed = Allowed {
+                users: strings(&given["users"]),
+                groups: strings(&given["groups"]),
+            };
+        }
+        if include_self && restrictions.iter().any(|allowed| !allowed.is_empty()) {
+            let me = current_account_id(&client, config).await?;
+            for allowed in restrictions.iter_mut() {
+                if !allowed.is_empty() && !allowed.users.contains(&me) {
+                    allowed.users.push(me.clone());
+                }
+            }
+        }
+
+        let url = format!(
+            "{}/wiki/rest/api/content/{}/restriction",
+            config.get_atlassian_base_url(),
+            page_id
+        );
+        // An empty PUT body is rejected; lifting everything is a DELETE
+        let request = if restrictions.iter().all(Allowed::is_empty) {
+            client.delete(&url)
+        } else {
+            client
+                .put(&url)
+                .header("Content-Type", "application/json")
+                .json(&restrictions_body(&restrictions))
+        };
+        let response = request
+            .header("Authorization", create_auth_header(config))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to set page restrictions: {}", error);
+        }
+
+        Ok(json!({
+            "success": true,
+            "page_id": page_id,
+            "restrictions": restrictions_json(&restrictions)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_restrictions() {
+        let data = json!({"results": [
+            {"operation": "update", "restrictions": {
+                "user": {"results": [{"accountId": "a1", "displayName": "Jane"}]},
+                "group": {"results": [{"name": "editors"}]}
+            }},
+            {"operation": "read", "restrictions": {"user": {"results": []}, "group": {"results": []}}}
+        ]});
+        let [read, update] = parse_restrictions(&data);
+        assert_eq!(read, Allowed::default());
+        assert_eq!(update.users, vec!["a1"]);
+        assert_eq!(update.groups, vec!["editors"]);
+    }
+
+    #[test]
+    fn test_restrictions_body_skips_open_operations() {
+        let body = restrictions_body(&[
+            Allowed::default(),
+            Allowed {
+                users: vec!["a1".to_string()],
+                groups: vec!["editors".to_string()],
+            },
+        ]);
+        assert_eq!(
+            body,
+            json!([{"operation": "update", "restrictions": {
+                "user": [{"type": "known", "accountId": "a1"}],
+                "group": [{"type": "group", "name": "editors"}]
+            }}])
+        );
+        assert_eq!(
+            strings(&json!(["g", " g ", "", "h"])),
+            vec!["g".to_string(), "h".to_string()]
+        );
+    }
+}
@@ -0,0 +1,496 @@
+//! HTML to storage-format normalization
+//!
+//! Confluence storage format is XHTML: it rejects unclosed tags, bare `&`,
+//! `<br>` without a slash and anything scripted. Model-written HTML is fixed
+//! up here before create/update, and every change is recorded so a
+//! `validate_only` call can report what would be stripped.
+//!
+//! `ac:`/`ri:` macro markup, comments and CDATA sections pass through as-is.
+
+use std::collections::BTreeMap;
+
+use serde_json::{Value, json};
+
+/// Removed together with their content
+const DROP_WITH_CONTENT: &[&str] = &[
+    "script", "style", "iframe", "object", "noscript", "template", "title",
+];
+/// Removed, tag only (void in HTML)
+const DROP_TAG: &[&str] = &["meta", "link", "base", "input", "embed"];
+/// Tag removed, children kept
+const UNWRAP: &[&str] = &["html", "head", "body", "form", "font", "center"];
+/// Emitted self-closed
+const VOID: &[&str] = &[
+    "area", "br", "col", "hr", "img", "param", "source", "track", "wbr",
+];
+/// Elements whose unclosed predecessor is implicitly closed by a sibling
+const IMPLICIT_CLOSE: &[&str] = &["p", "li"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sanitized {
+    pub content: String,
+    /// Human-readable changes, e.g. `removed <script> element (x2)`
+    pub changes: Vec<String>,
+}
+
+impl Sanitized {
+    /// Response for a `validate_only` call
+    pub fn validation_report(&self) -> Value {
+        json!({
+            "success": true,
+            "validate_only": true,
+            "valid": self.changes.is_empty(),
+            "changes": self.changes,
+            "content": self.content
+        })
+    }
+}
+
+struct Tag {
+    name: String,
+    attrs: Vec<(String, Option<String>)>,
+    self_closing: bool,
+}
+
+/// Normalizes `input` into storage-format-safe markup
+pub fn sanitize_storage(input: &str) -> Sanitized {
+    Sanitizer {
+        input,
+        pos: 0,
+        out: String::with_capacity(input.len()),
+        stack: Vec::new(),
+        changes: BTreeMap::new(),
+    }
+    .run()
+}
+
+struct Sanitizer<'a> {
+    input: &'a str,
+    pos: usize,
+    out: String,
+    stack: Vec<String>,
+    changes: BTreeMap<String, usize>,
+}
+
+impl Sanitizer<'_> {
+    fn run(mut self) -> Sanitized {
+        while self.pos < self.input.len() {
+            let rest = &self.input[self.pos..];
+            if rest.starts_with("<!--") {
+                self.copy_section("-->", "comment");
+            } else if rest.starts_with("<![CDATA[") {
+                self.copy_section("]]>", "CDATA section");
+            } else if rest.starts_with("<!") || rest.starts_with("<?") {
+                self.pos += rest.find('>').map_or(rest.len(), |i| i + 1);
+                self.note("removed <!DOCTYPE> or processing instruction".to_string());
+            } else if rest.starts_with("</") {
+                self.closing_tag();
+            } else if rest.starts_with('<')
+                && rest.as_bytes().get(1).is_some_and(u8::is_ascii_alphabetic)
+            {
+                self.opening_tag();
+            } else if rest.starts_with('<') {
+                self.escape_lt();
+            } else if rest.starts_with('&') {
+                if !is_entity(rest) {
+                    self.note("escaped bare &".to_string());
+                }
+                self.out.push_str(escape_amp(rest));
+                self.pos += 1;
+            } else {
+                let end = rest.find(['<', '&']).unwrap_or(rest.len());
+                self.out.push_str(&rest[..end]);
+                self.pos += end;
+            }
+        }
+
+        while let Some(name) = self.stack.pop() {
+            self.out.push_str(&format!("</{}>", name));
+            self.note(format!("closed unclosed <{}>", name));
+        }
+
+        Sanitized {
+            content: self.out,
+            changes: self
+                .changes
+                .into_iter()
+                .map(|(change, n)| match n {
+                    1 => change,
+                    n => format!("{} (x{})", change, n),
+                })
+                .collect(),
+        }
+    }
+
+    fn note(&mut self, change: String) {
+        *self.changes.entry(change).or_insert(0) += 1;
+    }
+
+    fn escape_lt(&mut self) {
+        self.out.push_str("&lt;");
+        self.pos += 1;
+        self.note("escaped bare <".to_string());
+    }
+
+    fn copy_section(&mut self, terminator: &str, what: &str) {
+        let rest = &self.input[self.pos..];
+        match rest.find(terminator) {
+            Some(i) => {
+                let end = i + terminator.len();
+                self.out.push_str(&rest[..end]);
+                self.pos += end;
+            }
+            None => {
+                self.pos = self.input.len();
+                self.note(format!("removed unterminated {}", what));
+            }
+        }
+    }
+
+    fn closing_tag(&mut self) {
+        let rest = &self.input[self.pos..];
+        let name_len = tag_name_len(&rest[2..]);
+        let Some(end) = rest.find('>').filter(|_| name_len > 0) else {
+            self.escape_lt();
+            return;
+        };
+        let name = normalize_name(&rest[2..2 + name_len]);
+        self.pos += end + 1;
+
+        // Their opening tags were already dropped, unwrapped or self-closed
+        if [DROP_TAG, UNWRAP, VOID]
+            .iter()
+            .any(|set| set.contains(&name.as_str()))
+        {
+            return;
+        }
+
+        match self.stack.iter().rposition(|open| *open == name) {
+            Some(index) => {
+                while self.stack.len() > index + 1 {
+                    let inner = self.stack.pop().unwrap_or_default();
+                    self.out.push_str(&format!("</{}>", inner));
+                    self.note(format!("closed unclosed <{}>", inner));
+                }
+                self.stack.pop();
+                self.out.push_str(&format!("</{}>", name));
+            }
+            None => self.note(format!("dropped stray </{}>", name)),
+        }
+    }
+
+    fn opening_tag(&mut self) {
+        let rest = &self.input[self.pos..];
+        let Some((tag, consumed)) = parse_tag(rest) else {
+            self.escape_lt();
+            return;
+        };
+        self.pos += consumed;
+        let name = tag.name.as_str();
+
+        if DROP_WITH_CONTENT.contains(&name) {
+            if !tag.self_closing {
+                self.skip_element(name);
+            } else {
+                self.note(format!("removed <{}> element", name));
+            }
+            return;
+        }
+        if DROP_TAG.contains(&name) {
+            self.note(format!("removed <{}>", name));
+            return;
+        }
+        if UNWRAP.contains(&name) {
+            self.note(format!("removed <{}> tag (content kept)", name));
+            return;
+        }
+
+        if IMPLICIT_CLOSE.contains(&name) && self.stack.last().map(String::as_str) == Some(name) {
+            self.stack.pop();
+            self.out.push_str(&format!("</{}>", name));
+            self.note(format!("closed unclosed <{}>", name));
+        }
+
+        let mut seen: Vec<String> = Vec::new();
+        let mut rendered = format!("<{}", name);
+        for (attr, value) in &tag.attrs {
+            let attr = normalize_name(attr);
+            if attr.starts_with("on") {
+                self.note("removed on* event attribute".to_string());
+                continue;
+            }
+            if matches!(attr.as_str(), "href" | "src")
+                && value.as_deref().is_some_and(|v| {
+                    v.trim_start()
+                        .to_ascii_lowercase()
+                        .starts_with("javascript:")
+                })
+            {
+                self.note("removed javascript: URL".to_string());
+                continue;
+            }
+            if seen.contains(&attr) {
+                self.note("removed duplicate attribute".to_string());
+                continue;
+            }
+            let value = match value {
+                Some(v) => escape_attr(v),
+                None => {
+                    self.note("normalized bare attribute".to_string());
+                    attr.clone()
+                }
+            };
+            rendered.push_str(&format!(" {}=\"{}\"", attr, value));
+            seen.push(attr);
+        }
+
+        if VOID.contains(&name) {
+            if !tag.self_closing {
+                self.note(format!("self-closed <{}>", name));
+            }
+            rendered.push_str(" />");
+        } else if tag.self_closing {
+            rendered.push_str(" />");
+        } else {
+            rendered.push('>');
+            self.stack.push(tag.name.clone());
+        }
+        self.out.push_str(&rendered);
+    }
+
+    /// Skips past `</name>`, or to the end when the element never closes
+    fn skip_element(&mut self, name: &str) {
+        let rest = self.input[self.pos..].to_ascii_lowercase();
+        let close = rest
+            .find(&format!("</{}", name))
+            .and_then(|start| rest[start..].find('>').map(|end| start + end + 1));
+        match close {
+            Some(end) => {
+                self.pos += end;
+                self.note(format!("removed <{}> element", name));
+            }
+            None => {
+                self.pos = self.input.len();
+                self.note(format!("removed unclosed <{}> and trailing content", name));
+            }
+        }
+    }
+}
+
+fn tag_name_len(s: &str) -> usize {
+    s.find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, ':' | '-' | '_' | '.')))
+        .unwrap_or(s.len())
+}
+
+/// HTML names are case-insensitive; namespaced (`ac:`, `ri:`) names are kept
+fn normalize_name(name: &str) -> String {
+    if name.contains(':') {
+        name.to_string()
+    } else {
+        name.to_ascii_lowercase()
+    }
+}
+
+/// Parses an opening tag at the start of `s`; `None` when it is unterminated
+fn parse_tag(s: &str) -> Option<(Tag, usize)> {
+    let bytes = s.as_bytes();
+    let name_len = tag_name_len(&s[1..]);
+    let name = normalize_name(&s[1..1 + name_len]);
+    let mut attrs = Vec::new();
+    let mut i = 1 + name_len;
+
+    loop {
+        while bytes.get(i).is_some_and(u8::is_ascii_whitespace) {
+            i += 1;
+        }
+        match bytes.get(i)? {
+            b'>' => {
+                return Some((
+                    Tag {
+                        name,
+                        attrs,
+                        self_closing: false,
+                    },
+                    i + 1,
+                ));
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'>') => {
+                return Some((
+                    Tag {
+                        name,
+                        attrs,
+                        self_closing: true,
+                    },
+                    i + 2,
+                ));
+            }
+            _ => {}
+        }
+
+        let attr_start = i;
+        while bytes
+            .get(i)
+            .is_some_and(|b| !b.is_ascii_whitespace() && !matches!(b, b'=' | b'>' | b'/'))
+        {
+            i += 1;
+        }
+        if i == attr_start {
+            // Stray `/` or `=`
+            i += 1;
+            continue;
+        }
+        let attr = s[attr_start..i].to_string();
+
+        while bytes.get(i).is_some_and(u8::is_ascii_whitespace) {
+            i += 1;
+        }
+        if bytes.get(i) != Some(&b'=') {
+            attrs.push((attr, None));
+            continue;
+        }
+        i += 1;
+        while bytes.get(i).is_some_and(u8::is_ascii_whitespace) {
+            i += 1;
+        }
+
+        let value = match bytes.get(i)? {
+            quote @ (b'"' | b'\'') => {
+                let len = s[i + 1..].find(*quote as char)?;
+                let value = &s[i + 1..i + 1 + len];
+                i += len + 2;
+                value
+            }
+            _ => {
+                let start = i;
+                while bytes
+                    .get(i)
+                    .is_some_and(|b| !b.is_ascii_whitespace() && *b != b'>')
+                {
+                    i += 1;
+                }
+                &s[start..i]
+            }
+        };
+        attrs.push((attr, Some(value.to_string())));
+    }
+}
+
+/// Whether `s` starts with an entity or character reference (`&amp;`, `&#39;`)
+fn is_entity(s: &str) -> bool {
+    let body = &s[1..];
+    let len = body
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '#'))
+        .unwrap_or(body.len());
+    len > 0 && body[len..].starts_with(';')
+}
+
+fn escape_amp(s: &str) -> &'static str {
+    if is_entity(s) { "&" } else { "&amp;" }
+}
+
+fn escape_attr(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for (i, c) in value.char_indices() {
+        match c {
+            '&' => out.push_str(escape_amp(&value[i..])),
+            '"' => out.push_str("&quot;"),
+            '<' => out.push_str("&lt;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_storage_unchanged() {
+        let input = r#"<p>Hello <strong>world</strong> &amp; friends</p><ac:structured-macro ac:name="code"><ac:plain-text-body><![CDATA[if a < b && c]]></ac:plain-text-body></ac:structured-macro><br />"#;
+        let result = sanitize_storage(input);
+        assert_eq!(result.content, input);
+        assert!(result.changes.is_empty());
+    }
+
+    #[test]
+    fn test_removes_script_and_style() {
+        let result = sanitize_storage(
+            "<style>p{color:red}</style><p>Hi</p><SCRIPT>alert(1)</script><script>x</script>",
+        );
+        assert_eq!(result.content, "<p>Hi</p>");
+        assert_eq!(
+            result.changes,
+            vec!["removed <script> element (x2)", "removed <style> element"]
+        );
+    }
+
+    #[test]
+    fn test_closes_unclosed_and_drops_stray_tags() {
+        let result = sanitize_storage("<ul><li>One<li>Two</ul></div><p>Open");
+        assert_eq!(
+            result.content,
+            "<ul><li>One</li><li>Two</li></ul><p>Open</p>"
+        );
+        assert!(result.changes.contains(&"dropped stray </div>".to_string()));
+        assert!(
+            result
+                .changes
+                .contains(&"closed unclosed <li> (x2)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_normalizes_void_and_attributes() {
+        let result = sanitize_storage(
+            r#"<P onclick="x()">a<BR>b<img src=pic.png alt='say "hi"'></P><a href=" javascript:alert(1)">x</a><input checked>"#,
+        );
+        assert_eq!(
+            result.content,
+            r#"<p>a<br />b<img src="pic.png" alt="say &quot;hi&quot;" /></p><a>x</a>"#
+        );
+        assert!(
+            result
+                .changes
+                .contains(&"removed javascript: URL".to_string())
+        );
+        assert!(
+            result
+                .changes
+                .contains(&"removed on* event attribute".to_string())
+        );
+    }
+
+    #[test]
+    fn test_escapes_bare_characters() {
+        let result = sanitize_storage("<p>Q&A: 1 < 2 &nbsp;&#8212;</p>");
+        assert_eq!(result.content, "<p>Q&amp;A: 1 &lt; 2 &nbsp;&#8212;</p>");
+        assert_eq!(result.changes, vec!["escaped bare &", "escaped bare <"]);
+    }
+
+    #[test]
+    fn test_unwraps_document_tags() {
+        let result = sanitize_storage(
+            "<!DOCTYPE html><html><head><title>T</title><meta charset=\"utf-8\"></head><body><h1>Doc</h1></body></html>",
+        );
+        assert_eq!(result.content, "<h1>Doc</h1>");
+    }
+
+    #[test]
+    fn test_unclosed_script_drops_rest() {
+        let result = sanitize_storage("<p>ok</p><script>steal()");
+        assert_eq!(result.content, "<p>ok</p>");
+        assert_eq!(
+            result.changes,
+            vec!["removed unclosed <script> and trailing content"]
+        );
+    }
+
+    #[test]
+    fn test_validation_report() {
+        let report = sanitize_storage("<p>x").validation_report();
+        assert_eq!(report["valid"], false);
+        assert_eq!(report["content"], "<p>x</p>");
+        assert_eq!(report["changes"][0], "closed unclosed <p>");
+    }
+}
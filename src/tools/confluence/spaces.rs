@@ -0,0 +1,188 @@
+//! Space keys and v2 space IDs in `CONFLUENCE_SPACES_FILTER`
+//!
+//! Entries are space keys (`ENG`) or numeric v2 space IDs (`98306`), mixed
+//! freely. CQL takes either (`space IN (...)`, `space.id IN (...)`); v2
+//! endpoints filter by ID, so keys are resolved with one request and kept
+//! per site for the life of the process.
+
+use anyhow::Result;
+use std::sync::Mutex;
+
+use crate::client::Confluence;
+use crate::config::Config;
+
+/// `(site, key, id)` of every key resolved so far
+static SPACE_IDS: Mutex<Vec<(String, String, String)>> = Mutex::new(Vec::new());
+
+/// Space keys are never all digits; v2 space IDs always are
+pub fn is_space_id(entry: &str) -> bool {
+    !entry.is_empty() && entry.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// The CQL condition matching `spaces`, `None` when there are none
+pub fn cql_scope(spaces: &[String]) -> Option<String> {
+    let (ids, keys): (Vec<&String>, Vec<&String>) =
+        spaces.iter().partition(|entry| is_space_id(entry));
+    let list = |values: &[&String]| {
+        values
+            .iter()
+            .map(|v| format!("\"{}\"", v))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    match (keys.is_empty(), ids.is_empty()) {
+        (true, true) => None,
+        (false, true) => Some(format!("space IN ({})", list(&keys))),
+        (true, false) => Some(format!("space.id IN ({})", list(&ids))),
+        (false, false) => Some(format!(
+            "(space IN ({}) OR space.id IN ({}))",
+            list(&keys),
+            list(&ids)
+        )),
+    }
+}
+
+/// `cql` limited to `spaces`, unless it already names a space
+pub fn scope_search_cql(cql: &str, spaces: &[String]) -> String {
+    let cql_lower = cql.to_lowercase();
+    let names_space = cql_lower.contains("space ")
+        || cql_lower.contains("space=")
+        || cql_lower.contains("space in")
+        || cql_lower.contains("space.");
+    match cql_scope(spaces) {
+        Some(scope) if !names_space => format!("{} AND ({})", scope, cql),
+        _ => cql.to_string(),
+    }
+}
+
+fn cached_id(site: &str, key: &str) -> Option<String> {
+    let cache = SPACE_IDS.lock().unwrap_or_else(|e| e.into_inner());
+    cache
+        .iter()
+        .find(|(s, k, _)| s == site && k == key)
+        .map(|(_, _, id)| id.clone())
+}
+
+/// The v2 ID of each entry, in order. Keys are resolved (once per process);
+/// keys that match no space are logged and left out.
+pub async fn space_ids(
+    confluence: &Confluence<'_>,
+    config: &Config,
+    entries: &[String],
+) -> Result<Vec<String>> {
+    let site = config.get_atlassian_base_url();
+    let missing: Vec<&str> = entries
+        .iter()
+        .filter(|entry| !is_space_id(entry) && cached_id(site, entry).is_none())
+        .map(String::as_str)
+        .collect();
+    if missing.is_empty() {
+        if entries.iter().any(|entry| !is_space_id(entry)) {
+            crate::mcp::stats::record_cache_hit();
+        }
+    } else {
+        let found = confluence.get_spaces_by_key(&missing).await?;
+        let mut cache = SPACE_IDS.lock().unwrap_or_else(|e| e.into_inner());
+        for space in found {
+            cache.push((site.to_string(), space.key, space.id));
+        }
+    }
+
+    let mut ids = Vec::with_capacity(entries.len());
+    for entry in entries {
+        if is_space_id(entry) {
+            ids.push(entry.clone());
+        } else if let Some(id) = cached_id(site, entry) {
+            ids.push(id);
+        } else {
+            tracing::warn!(space_key = %entry, "Space not found; it is left out of the filter");
+        }
+    }
+    Ok(ids)
+}
+
+/// The v2 ID for a space key or ID
+pub async fn space_id(
+    confluence: &Confluence<'_>,
+    config: &Config,
+    key_or_id: &str,
+) -> Result<String> {
+    if is_space_id(key_or_id) {
+        return Ok(key_or_id.to_string());
+    }
+    let site = config.get_atlassian_base_url();
+    if let Some(id) = cached_id(site, key_or_id) {
+        crate::mcp::stats::record_cache_hit();
+        return Ok(id);
+    }
+    let id = confluence.space_id(key_or_id).await?;
+    SPACE_IDS.lock().unwrap_or_else(|e| e.into_inner()).push((
+        site.to_string(),
+        key_or_id.to_string(),
+        id.clone(),
+    ));
+    Ok(id)
+}
+
+/// `CONFLUENCE_SPACES_FILTER` as v2 IDs; empty when the filter is not set.
+/// A filter none of whose spaces exist is an error rather than no filter.
+pub async fn filter_space_ids(confluence: &Confluence<'_>, config: &Config) -> Result<Vec<String>> {
+    let filter = &config.confluence_spaces_filter;
+    let ids = space_ids(confluence, config, filter).await?;
+    if ids.is_empty() && !filter.is_empty() {
+        anyhow::bail!(
+            "None of the spaces in CONFLUENCE_SPACES_FILTER ({}) were found",
+            filter.join(",")
+        );
+    }
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spaces(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_is_space_id() {
+        assert!(is_space_id("98306"));
+        assert!(!is_space_id("ENG"));
+        assert!(!is_space_id("ENG2"));
+        assert!(!is_space_id("~5b10ac8d82e05b22cc7d4ef5"));
+        assert!(!is_space_id(""));
+    }
+
+    #[test]
+    fn test_cql_scope_mixes_keys_and_ids() {
+        assert_eq!(cql_scope(&[]), None);
+        assert_eq!(
+            cql_scope(&spaces(&["ENG", "TEAM"])).as_deref(),
+            Some("space IN (\"ENG\",\"TEAM\")")
+        );
+        assert_eq!(
+            cql_scope(&spaces(&["98306"])).as_deref(),
+            Some("space.id IN (\"98306\")")
+        );
+        assert_eq!(
+            cql_scope(&spaces(&["ENG", "98306"])).as_deref(),
+            Some("(space IN (\"ENG\") OR space.id IN (\"98306\"))")
+        );
+    }
+
+    #[test]
+    fn test_scope_search_cql_keeps_explicit_spaces() {
+        let filter = spaces(&["ENG"]);
+        assert_eq!(
+            scope_search_cql("type = page", &filter),
+            "space IN (\"ENG\") AND (type = page)"
+        );
+        assert_eq!(
+            scope_search_cql("space.id = 98306 AND type = page", &filter),
+            "space.id = 98306 AND type = page"
+        );
+        assert_eq!(scope_search_cql("type = page", &[]), "type = page");
+    }
+}
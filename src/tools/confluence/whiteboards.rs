@@ -0,0 +1,117 @@
+//! Whiteboards (v2 API)
+//!
+//! `confluence_get_whiteboard` resolves a whiteboard by ID or pasted link to
+//! its title, location and authorship. Confluence has no API for whiteboard
+//! contents, so the result says so (`content_available: false`) and links
+//! the board for viewing instead of returning nothing silently.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{ToolSchema, string_prop};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+pub struct GetWhiteboardHandler;
+
+/// Whiteboard ID from an ID or a `.../whiteboard/<id>` link
+pub fn whiteboard_id(input: &str) -> Option<&str> {
+    let input = input.trim();
+    let id = match input.split_once("/whiteboard/") {
+        Some((_, rest)) => rest.split(['/', '?', '#']).next().unwrap_or_default(),
+        None => input,
+    };
+    (!id.is_empty() && id.bytes().all(|b| b.is_ascii_digit())).then_some(id)
+}
+
+#[async_trait]
+impl ToolHandler for GetWhiteboardHandler {
+    fn name(&self) -> &'static str {
+        "confluence_get_whiteboard"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "whiteboard_id".to_string(),
+            string_prop("Whiteboard ID or link (.../whiteboard/<id>)", true),
+        );
+        ToolSchema::new(
+            "Get a whiteboard's title, location and authorship (contents are not exposed by the API)",
+            props,
+            vec!["whiteboard_id".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let input = args["whiteboard_id"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing whiteboard_id"))?;
+        let id = whiteboard_id(input).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid whiteboard_id '{}': expected an ID or whiteboard link",
+                input
+            )
+        })?;
+
+        let client = create_atlassian_client(config);
+        let url = format!(
+            "{}/wiki/api/v2/whiteboards/{}",
+            config.get_atlassian_base_url(),
+            id
+        );
+        let response = client
+            .get(&url)
+            .header("Authorization", create_auth_header(config))
+            .header("Accept", "application/json")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to get whiteboard: {}", response.status());
+        }
+        let data: Value = response.json().await?;
+
+        Ok(json!({
+            "success": true,
+            "whiteboard": {
+                "id": data["id"],
+                "title": data["title"],
+                "status": data["status"],
+                "space_id": data["spaceId"],
+                "parent_id": data["parentId"],
+                "parent_type": data["parentType"],
+                "author_id": data["authorId"],
+                "owner_id": data["ownerId"],
+                "created_at": data["createdAt"],
+                "version": data["version"]["number"]
+            },
+            "url": data["_links"]["webui"].as_str().map(|path| {
+                format!("{}/wiki{}", config.get_atlassian_base_url(), path)
+            }),
+            "content_available": false
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whiteboard_id_from_id_or_link() {
+        assert_eq!(whiteboard_id("65538"), Some("65538"));
+        assert_eq!(
+            whiteboard_id("https://x.atlassian.net/wiki/spaces/ENG/whiteboard/65538?atl_f=1"),
+            Some("65538")
+        );
+        assert_eq!(
+            whiteboard_id("https://x.atlassian.net/wiki/spaces/ENG/pages/1"),
+            None
+        );
+        assert_eq!(whiteboard_id(""), None);
+    }
+}
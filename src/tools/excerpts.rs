@@ -0,0 +1,255 @@
+//! Short excerpts around matched search terms
+//!
+//! A few lines of context per hit let the assistant pick which result to
+//! open instead of fetching each one. Confluence search already returns an
+//! `excerpt` with `@@@hl@@@term@@@endhl@@@` markers; [`from_confluence`]
+//! turns those into `**term**` and trims it. Jira has no such field, so
+//! `jira_search` with `include_excerpts: true` fetches `description` and
+//! [`excerpt`] cuts a window around the first term of the JQL's `~` clauses.
+
+use serde_json::{Value, json};
+
+/// Longest excerpt, in characters
+pub const EXCERPT_CHARS: usize = 240;
+
+/// Whitespace runs (newlines included) as single spaces
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// First `max` characters, with `…` when cut
+fn truncate(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((at, _)) => format!("{}…", text[..at].trim_end()),
+        None => text.to_string(),
+    }
+}
+
+/// `text` on one line (whitespace collapsed), at most `max` characters
+pub fn one_line(text: &str, max: usize) -> String {
+    truncate(&collapse_whitespace(text), max)
+}
+
+/// A Confluence search excerpt with `**` highlights and collapsed whitespace
+pub fn from_confluence(excerpt: &str) -> String {
+    let text = excerpt
+        .replace("@@@hl@@@", "**")
+        .replace("@@@endhl@@@", "**");
+    one_line(&text, EXCERPT_CHARS)
+}
+
+/// Words searched for by the `~` (contains) clauses of a JQL query, without
+/// wildcards, e.g. `text ~ "login fail*"` gives `login`, `fail`
+pub fn search_terms(jql: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    let mut rest = jql;
+    while let Some(at) = rest.find('~') {
+        // `!~` (does not contain) terms will not be in the results
+        let negated = rest[..at].ends_with('!');
+        let after = rest[at + 1..].trim_start();
+        let (value, remainder) = match after.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let inner = &after[1..];
+                let end = inner.find(quote).unwrap_or(inner.len());
+                (&inner[..end], &inner[(end + 1).min(inner.len())..])
+            }
+            _ => {
+                let end = after
+                    .find(|c: char| c.is_whitespace() || c == ')')
+                    .unwrap_or(after.len());
+                after.split_at(end)
+            }
+        };
+        for word in value.split_whitespace().filter(|_| !negated) {
+            let word = word
+                .trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase();
+            if !word.is_empty() && !terms.contains(&word) {
+                terms.push(word);
+            }
+        }
+        rest = remainder;
+    }
+    terms
+}
+
+/// Case-insensitive character positions of `term` in `lowered`
+fn find_all(lowered: &[char], term: &[char]) -> Vec<usize> {
+    if term.is_empty() || term.len() > lowered.len() {
+        return Vec::new();
+    }
+    (0..=lowered.len() - term.len())
+        .filter(|&at| lowered[at..at + term.len()] == *term)
+        .collect()
+}
+
+/// Up to [`EXCERPT_CHARS`] of `text` around the first matched term, each
+/// match within it in `**`; the start of `text` when nothing matches
+pub fn excerpt(text: &str, terms: &[String]) -> String {
+    let chars: Vec<char> = collapse_whitespace(text).chars().collect();
+    let lowered: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+
+    // (start, length) of every match, earliest first, overlaps dropped
+    let mut matches: Vec<(usize, usize)> = terms
+        .iter()
+        .flat_map(|term| {
+            let term: Vec<char> = term.chars().collect();
+            find_all(&lowered, &term)
+                .into_iter()
+                .map(move |at| (at, term.len()))
+        })
+        .collect();
+    matches.sort();
+    matches.dedup_by(|next, kept| next.0 < kept.0 + kept.1);
+
+    let start = matches
+        .first()
+        .map_or(0, |(at, _)| at.saturating_sub(EXCERPT_CHARS / 3));
+    let end = (start + EXCERPT_CHARS).min(chars.len());
+
+    let mut out = String::new();
+    if start > 0 {
+        out.push('…');
+    }
+    let mut at = start;
+    for &(from, length) in matches.iter().filter(|(from, _)| *from >= start) {
+        let to = from + length;
+        if to > end {
+            break;
+        }
+        out.extend(&chars[at..from]);
+        out.push_str("**");
+        out.extend(&chars[from..to]);
+        out.push_str("**");
+        at = to;
+    }
+    out.extend(&chars[at..end]);
+    if end < chars.len() {
+        out.push('…');
+    }
+    out
+}
+
+/// The text of an ADF document (or a plain string), blocks separated by spaces
+pub fn adf_plain_text(node: &Value) -> String {
+    fn collect(node: &Value, out: &mut String) {
+        match node {
+            Value::String(text) => out.push_str(text),
+            Value::Object(_) => {
+                if let Some(text) = node["text"].as_str() {
+                    out.push_str(text);
+                }
+                for child in node["content"].as_array().into_iter().flatten() {
+                    collect(child, out);
+                }
+                out.push(' ');
+            }
+            _ => {}
+        }
+    }
+    let mut out = String::new();
+    collect(node, &mut out);
+    out
+}
+
+/// `jira_search` excerpts: added to each issue, dropping the description
+/// when only the excerpt needed it
+pub struct IssueExcerpts {
+    pub terms: Vec<String>,
+    pub strip_description: bool,
+}
+
+impl IssueExcerpts {
+    /// Adds `excerpt` to each issue of a JSON search result
+    pub fn apply(&self, mut result: Value) -> Value {
+        for issue in result["issues"].as_array_mut().into_iter().flatten() {
+            let text = adf_plain_text(&issue["fields"]["description"]);
+            issue["excerpt"] = json!(excerpt(&text, &self.terms));
+            if self.strip_description
+                && let Some(fields) = issue["fields"].as_object_mut()
+            {
+                fields.remove("description");
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confluence_excerpt_highlights() {
+        assert_eq!(
+            from_confluence("The @@@hl@@@deploy@@@endhl@@@\n  checklist"),
+            "The **deploy** checklist"
+        );
+        let long = "word ".repeat(100);
+        assert!(from_confluence(&long).chars().count() <= EXCERPT_CHARS + 1);
+        assert!(from_confluence(&long).ends_with('…'));
+    }
+
+    #[test]
+    fn test_search_terms_from_jql() {
+        assert_eq!(
+            search_terms(
+                r#"project = X AND text ~ "Login fail*" OR summary ~ 'SSO' ORDER BY created"#
+            ),
+            vec!["login", "fail", "sso"]
+        );
+        assert_eq!(
+            search_terms("(description ~ timeout) AND text !~ flaky"),
+            vec!["timeout"]
+        );
+        assert!(search_terms("project = X").is_empty());
+    }
+
+    #[test]
+    fn test_excerpt_windows_around_first_match() {
+        let text = format!(
+            "{} The login page fails on Safari. {}",
+            "a ".repeat(200),
+            "b ".repeat(200)
+        );
+        let excerpt = excerpt(&text, &["login".to_string(), "safari".to_string()]);
+        assert!(excerpt.starts_with('…'));
+        assert!(excerpt.ends_with('…'));
+        assert!(excerpt.contains("The **login** page fails on **Safari**."));
+        assert!(excerpt.chars().count() <= EXCERPT_CHARS + 10);
+    }
+
+    #[test]
+    fn test_excerpt_without_match_is_the_start() {
+        assert_eq!(
+            excerpt("Short description", &["x".to_string()]),
+            "Short description"
+        );
+        assert_eq!(excerpt("", &[]), "");
+    }
+
+    #[test]
+    fn test_issue_excerpts_from_adf() {
+        let result = json!({"issues": [{"key": "A-1", "fields": {"summary": "s", "description": {
+            "type": "doc",
+            "content": [
+                {"type": "paragraph", "content": [{"type": "text", "text": "Token expires"}]},
+                {"type": "paragraph", "content": [{"type": "text", "text": "after an hour"}]}
+            ]
+        }}}]});
+        let excerpts = IssueExcerpts {
+            terms: vec!["expires".to_string()],
+            strip_description: true,
+        };
+        let result = excerpts.apply(result);
+        assert_eq!(
+            result["issues"][0]["excerpt"],
+            "Token **expires** after an hour"
+        );
+        assert!(result["issues"][0]["fields"].get("description").is_none());
+        assert_eq!(result["issues"][0]["fields"]["summary"], "s");
+    }
+}
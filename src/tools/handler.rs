@@ -1,9 +1,16 @@
 use crate::config::Config;
+use crate::tools::schema::ToolSchema;
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::Value;
 
 #[async_trait]
 pub trait ToolHandler: Send + Sync {
+    /// Name the tool is registered and listed under
+    fn name(&self) -> &'static str;
+
+    /// Description and input schema shown by `tools/list`
+    fn schema(&self, config: &Config) -> ToolSchema;
+
     async fn execute(&self, args: Value, config: &Config) -> Result<Value>;
 }
@@ -0,0 +1,331 @@
+//! Credential and connectivity self-test
+//!
+//! Backs the `atlassian_health_check` tool and the `--check` CLI flag. Calls
+//! Jira `/myself` and `/mypermissions` and the Confluence spaces endpoint,
+//! then reports which services answered, the permissions held and the
+//! configured filters, so a bad token shows up here instead of as an opaque
+//! 401 on the first real tool call.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+/// Global and project permissions reported for the authenticated user
+const CHECKED_PERMISSIONS: &[&str] = &[
+    "BROWSE_PROJECTS",
+    "CREATE_ISSUES",
+    "EDIT_ISSUES",
+    "ADD_COMMENTS",
+    "TRANSITION_ISSUES",
+];
+
+/// Jira endpoint that identifies the authenticated user
+pub const JIRA_MYSELF_PATH: &str = "/rest/api/3/myself";
+/// Cheapest Confluence endpoint that requires product access
+pub const CONFLUENCE_SPACES_PATH: &str = "/wiki/api/v2/spaces?limit=1";
+
+pub struct HealthCheckHandler;
+
+/// Outcome of one probe request
+#[derive(Debug)]
+pub enum Probe {
+    Ok(Value),
+    Status(reqwest::StatusCode),
+    Unreachable(String),
+}
+
+impl Probe {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Probe::Ok(_))
+    }
+
+    /// Short human-readable reason for a failed probe
+    pub fn error(&self) -> Option<String> {
+        match self {
+            Probe::Ok(_) => None,
+            Probe::Status(status) => Some(match status.as_u16() {
+                401 => "401 Unauthorized: email or API token rejected".to_string(),
+                403 => "403 Forbidden: credentials valid but access denied".to_string(),
+                404 => "404 Not Found: product not available on this site".to_string(),
+                _ => format!("Unexpected status {}", status),
+            }),
+            Probe::Unreachable(e) => Some(format!("Unreachable: {}", e)),
+        }
+    }
+}
+
+/// GETs `path` on the Atlassian site
+pub async fn probe(client: &reqwest::Client, config: &Config, path: &str) -> Probe {
+    let url = format!("{}{}", config.get_atlassian_base_url(), path);
+    let response = match client
+        .get(&url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => return Probe::Unreachable(e.to_string()),
+    };
+
+    if !response.status().is_success() {
+        return Probe::Status(response.status());
+    }
+    match response.json().await {
+        Ok(data) => Probe::Ok(data),
+        Err(e) => Probe::Unreachable(format!("invalid JSON response: {}", e)),
+    }
+}
+
+/// `{"BROWSE_PROJECTS": true, ...}` from a `/mypermissions` response
+pub fn parse_permissions(data: &Value) -> Value {
+    let held = CHECKED_PERMISSIONS
+        .iter()
+        .map(|p| {
+            (
+                p.to_string(),
+                json!(data["permissions"][*p]["havePermission"].as_bool() == Some(true)),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+    Value::Object(held)
+}
+
+/// What is wrong with the token's shape, if anything; API tokens are `ATATT...`
+/// (or 24 characters for legacy tokens)
+fn token_problem(token: &str) -> Option<&'static str> {
+    if token.trim() != token {
+        Some("ATLASSIAN_API_TOKEN has leading or trailing whitespace")
+    } else if token.starts_with('"') || token.starts_with('\'') {
+        Some("ATLASSIAN_API_TOKEN is wrapped in quotes")
+    } else if !token.starts_with("ATATT") && token.len() != 24 {
+        Some(
+            "ATLASSIAN_API_TOKEN does not look like an Atlassian API token; create one at https://id.atlassian.com/manage-profile/security/api-tokens",
+        )
+    } else {
+        None
+    }
+}
+
+/// Remediation hints for failed probes; empty when both services answered
+pub fn diagnose(config: &Config, jira: &Probe, confluence: &Probe) -> Vec<String> {
+    let site = config.get_atlassian_base_url();
+    let email = &config.atlassian_email;
+    let unauthorized = |p: &Probe| matches!(p, Probe::Status(s) if s.as_u16() == 401);
+    let not_found = |p: &Probe| matches!(p, Probe::Status(s) if s.as_u16() == 404);
+
+    let mut hints = Vec::new();
+    if matches!(jira, Probe::Unreachable(_)) && matches!(confluence, Probe::Unreachable(_)) {
+        hints.push(format!(
+            "Cannot reach {}: check ATLASSIAN_DOMAIN (e.g. your-site.atlassian.net) and network or proxy settings",
+            site
+        ));
+    } else if not_found(jira) && not_found(confluence) {
+        hints.push(format!(
+            "{} has neither Jira nor Confluence: ATLASSIAN_DOMAIN is probably wrong",
+            site
+        ));
+    } else if unauthorized(jira) || unauthorized(confluence) {
+        hints.push(match token_problem(&config.atlassian_api_token) {
+            Some(problem) => format!("Authentication failed: {}", problem),
+            None => format!(
+                "Authentication failed: the token looks valid, so check that ATLASSIAN_EMAIL ({}) is the account that created it and that the token has not been revoked",
+                email
+            ),
+        });
+    } else if jira.is_ok() && !confluence.is_ok() {
+        hints.push(format!(
+            "Jira-only access: Confluence tools will fail ({}); grant {} Confluence access or ignore if the site has no Confluence",
+            confluence.error().unwrap_or_default(),
+            email
+        ));
+    } else if confluence.is_ok() && !jira.is_ok() {
+        hints.push(format!(
+            "Confluence-only access: Jira tools will fail ({}); grant {} Jira access or ignore if the site has no Jira",
+            jira.error().unwrap_or_default(),
+            email
+        ));
+    } else {
+        for (service, probe) in [("Jira", jira), ("Confluence", confluence)] {
+            if let Some(error) = probe.error() {
+                hints.push(format!("{}: {}", service, error));
+            }
+        }
+    }
+    hints
+}
+
+fn user_summary(user: &Value) -> Value {
+    json!({
+        "accountId": user["accountId"],
+        "displayName": user["displayName"],
+        "emailAddress": user["emailAddress"]
+    })
+}
+
+fn service_report(probe: &Probe, details: Value) -> Value {
+    let mut report = json!({ "reachable": probe.is_ok() });
+    match probe.error() {
+        Some(error) => report["error"] = json!(error),
+        None => {
+            if let (Some(report), Some(details)) = (report.as_object_mut(), details.as_object()) {
+                report.extend(details.clone());
+            }
+        }
+    }
+    report
+}
+
+/// Runs every probe; `healthy` is true when both Jira and Confluence answered
+pub async fn run_health_check(config: &Config, include_permissions: bool) -> Value {
+    let client = create_atlassian_client(config);
+
+    let myself = probe(&client, config, JIRA_MYSELF_PATH).await;
+    let jira_details = match &myself {
+        Probe::Ok(user) if !include_permissions => json!({
+            "user": user_summary(user)
+        }),
+        Probe::Ok(user) => {
+            let permissions = probe(
+                &client,
+                config,
+                &format!(
+                    "/rest/api/3/mypermissions?permissions={}",
+                    CHECKED_PERMISSIONS.join(",")
+                ),
+            )
+            .await;
+            json!({
+                "user": user_summary(user),
+                "permissions": match &permissions {
+                    Probe::Ok(data) => parse_permissions(data),
+                    other => json!({ "error": other.error() }),
+                }
+            })
+        }
+        _ => json!({}),
+    };
+
+    let spaces = probe(&client, config, CONFLUENCE_SPACES_PATH).await;
+
+    json!({
+        "healthy": myself.is_ok() && spaces.is_ok(),
+        "hints": diagnose(config, &myself, &spaces),
+        "site": config.get_atlassian_base_url(),
+        "jira": service_report(&myself, jira_details),
+        "confluence": service_report(&spaces, json!({})),
+        "configuration": {
+            "jira_projects_filter": config.jira_projects_filter,
+            "confluence_spaces_filter": config.confluence_spaces_filter,
+            "read_only": config.read_only,
+            "write_allowed_projects": config.write_allowed_projects,
+            "write_allowed_spaces": config.write_allowed_spaces,
+            "confirm_destructive_writes": config.confirm_destructive_writes,
+            "bitbucket_configured": config.bitbucket_workspace.is_some(),
+            "trello_configured": config.trello_api_key.is_some() && config.trello_token.is_some()
+        }
+    })
+}
+
+#[async_trait]
+impl ToolHandler for HealthCheckHandler {
+    fn name(&self) -> &'static str {
+        "atlassian_health_check"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "include_permissions".to_string(),
+            Property::new("boolean")
+                .with_description(
+                    "Also check the user's Jira permissions (one extra request)".to_string(),
+                )
+                .with_default(json!(true)),
+        );
+        ToolSchema::new(
+            "Verify credentials and connectivity: Jira user and permissions, Confluence access, configured filters",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let include_permissions = args["include_permissions"].as_bool().unwrap_or(true);
+        let mut report = run_health_check(config, include_permissions).await;
+        report["success"] = json!(true);
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ConfigBuilder;
+    use reqwest::StatusCode;
+
+    fn test_config(token: &str) -> Config {
+        ConfigBuilder::new()
+            .with(|config| config.atlassian_api_token = token.to_string())
+            .build()
+    }
+
+    #[test]
+    fn test_diagnose_credentials() {
+        let denied = Probe::Status(StatusCode::UNAUTHORIZED);
+
+        let hints = diagnose(&test_config("token123"), &denied, &denied);
+        assert!(hints[0].contains("does not look like an Atlassian API token"));
+
+        let hints = diagnose(&test_config("ATATTxyz"), &denied, &denied);
+        assert!(hints[0].contains("ATLASSIAN_EMAIL (test@example.com)"));
+    }
+
+    #[test]
+    fn test_diagnose_domain_and_partial_access() {
+        let config = test_config("ATATTxyz");
+        let missing = Probe::Status(StatusCode::NOT_FOUND);
+        let ok = Probe::Ok(json!({}));
+
+        let hints = diagnose(&config, &missing, &missing);
+        assert!(hints[0].contains("ATLASSIAN_DOMAIN is probably wrong"));
+
+        let hints = diagnose(&config, &ok, &missing);
+        assert!(hints[0].starts_with("Jira-only access"));
+
+        let hints = diagnose(&config, &Probe::Status(StatusCode::FORBIDDEN), &ok);
+        assert!(hints[0].starts_with("Confluence-only access"));
+
+        assert!(diagnose(&config, &ok, &ok).is_empty());
+    }
+
+    #[test]
+    fn test_parse_permissions() {
+        let data = json!({
+            "permissions": {
+                "BROWSE_PROJECTS": {"havePermission": true},
+                "CREATE_ISSUES": {"havePermission": false}
+            }
+        });
+        let held = parse_permissions(&data);
+        assert_eq!(held["BROWSE_PROJECTS"], true);
+        assert_eq!(held["CREATE_ISSUES"], false);
+        assert_eq!(held["TRANSITION_ISSUES"], false);
+    }
+
+    #[test]
+    fn test_service_report() {
+        let ok = service_report(&Probe::Ok(json!({})), json!({"user": "x"}));
+        assert_eq!(ok, json!({"reachable": true, "user": "x"}));
+
+        let denied = service_report(&Probe::Status(reqwest::StatusCode::UNAUTHORIZED), json!({}));
+        assert_eq!(denied["reachable"], false);
+        assert!(denied["error"].as_str().unwrap().starts_with("401"));
+    }
+}
@@ -0,0 +1,311 @@
+//! Incident kick-off (opt-in with `INCIDENT_PROJECT`)
+//!
+//! `create_incident` does the three things an incident commander otherwise
+//! clicks through by hand: it files the Jira issue (described with the
+//! `incident` template, so `JIRA_TEMPLATES_DIR/incident.md` overrides the
+//! built-in one), creates the postmortem page under
+//! `INCIDENT_POSTMORTEM_PARENT`, and adds a remote link from the issue to
+//! the page. The page body is the storage of the
+//! `INCIDENT_POSTMORTEM_TEMPLATE` page when set, else a built-in outline;
+//! both take `{{placeholder}}` tokens. Once the issue exists it is kept, and
+//! a failed page or link is reported next to it.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+
+use crate::client::{Confluence, Jira};
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::args::parse_args;
+use crate::tools::confluence::action_items::link_to_page;
+use crate::tools::confluence::markdown::{escape, markdown_to_storage};
+use crate::tools::jira::adf_utils::markdown_to_adf;
+use crate::tools::jira::templates::{fill_placeholders, load_template};
+use crate::tools::schema::{ToolSchema, array_prop, string_prop};
+use crate::tools::write_policy;
+
+pub struct CreateIncidentHandler;
+
+const DEFAULT_ISSUE_TYPE: &str = "Incident";
+
+/// Postmortem outline used without `INCIDENT_POSTMORTEM_TEMPLATE`
+const POSTMORTEM_TEMPLATE: &str = "## Summary\n{{summary}}\n\n- **Incident:** [{{issue_key}}]({{issue_url}})\n- **Severity:** {{severity|Not set}}\n\n## Impact\n{{impact}}\n\n## Timeline\n{{timeline|TBD}}\n\n## Root Cause\n{{root_cause|Under investigation}}\n\n## Remediation\n{{remediation|TBD}}\n\n## Action Items\n- [ ] Add follow-up actions\n\n## Lessons Learned\nTBD\n";
+
+#[derive(Deserialize)]
+struct IncidentArgs {
+    summary: String,
+    impact: String,
+    severity: Option<String>,
+    timeline: Option<Value>,
+    root_cause: Option<String>,
+    project_key: Option<String>,
+    issue_type: Option<String>,
+    parent_id: Option<String>,
+}
+
+/// Placeholder values shared by the issue and the postmortem templates
+fn template_values(args: &IncidentArgs, issue: Option<(&str, &str)>) -> Map<String, Value> {
+    let mut values = Map::new();
+    values.insert("summary".to_string(), json!(args.summary));
+    values.insert("impact".to_string(), json!(args.impact));
+    let optional = [
+        ("severity", args.severity.clone().map(Value::String)),
+        ("timeline", args.timeline.clone()),
+        ("root_cause", args.root_cause.clone().map(Value::String)),
+    ];
+    for (name, value) in optional {
+        if let Some(value) = value.filter(|v| !v.is_null() && v != "") {
+            values.insert(name.to_string(), value);
+        }
+    }
+    if let Some((key, url)) = issue {
+        values.insert("issue_key".to_string(), json!(key));
+        values.insert("issue_url".to_string(), json!(url));
+    }
+    values
+}
+
+/// Values with their text escaped, for storage-format templates
+fn escaped_values(values: &Map<String, Value>) -> Map<String, Value> {
+    values
+        .iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Value::String(s) => json!(escape(s)),
+                Value::Array(items) => json!(
+                    items
+                        .iter()
+                        .map(|item| match item {
+                            Value::String(s) => json!(escape(s)),
+                            other => json!(escape(&other.to_string())),
+                        })
+                        .collect::<Vec<_>>()
+                ),
+                other => json!(escape(&other.to_string())),
+            };
+            (name.clone(), value)
+        })
+        .collect()
+}
+
+/// The postmortem body in storage format
+async fn postmortem_body(
+    confluence: &Confluence<'_>,
+    config: &Config,
+    values: &Map<String, Value>,
+) -> Result<String> {
+    match config.incident_postmortem_template.as_deref() {
+        Some(template_id) => {
+            let template = confluence.get_page_storage(template_id, None).await?;
+            let storage = template.storage().ok_or_else(|| {
+                anyhow::anyhow!("Postmortem template page {} has no body", template_id)
+            })?;
+            fill_placeholders(storage, &escaped_values(values))
+        }
+        None => Ok(markdown_to_storage(&fill_placeholders(
+            POSTMORTEM_TEMPLATE,
+            values,
+        )?)),
+    }
+}
+
+/// Creates the postmortem page under `parent_id`; `(page ID, title, URL)`
+async fn create_postmortem(
+    config: &Config,
+    parent_id: &str,
+    title: &str,
+    values: &Map<String, Value>,
+) -> Result<(String, String, String)> {
+    let confluence = Confluence::new(config);
+    let parent = confluence.get_page_storage(parent_id, None).await?;
+    let space_id = parent
+        .space_id
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("Parent page {} has no spaceId", parent_id))?;
+    let body = postmortem_body(&confluence, config, values).await?;
+    let page = confluence
+        .create_page(space_id, title, &body, Some(parent_id), &[])
+        .await?;
+    let url = format!(
+        "{}/wiki/pages/viewpage.action?pageId={}",
+        config.get_atlassian_base_url(),
+        page.id
+    );
+    Ok((page.id, page.title, url))
+}
+
+#[async_trait]
+impl ToolHandler for CreateIncidentHandler {
+    fn name(&self) -> &'static str {
+        "create_incident"
+    }
+
+    fn schema(&self, config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "summary".to_string(),
+            string_prop("One-line incident summary", true),
+        );
+        props.insert(
+            "impact".to_string(),
+            string_prop("Who or what is affected, and how", true),
+        );
+        props.insert(
+            "severity".to_string(),
+            string_prop("Severity (e.g., 'SEV-2')", false),
+        );
+        props.insert(
+            "timeline".to_string(),
+            array_prop("Timeline entries so far (e.g., '09:12 alerts fired')"),
+        );
+        props.insert(
+            "root_cause".to_string(),
+            string_prop("Suspected root cause, if already known", false),
+        );
+        props.insert(
+            "project_key".to_string(),
+            string_prop(
+                &format!(
+                    "Project for the incident issue (default: {})",
+                    config
+                        .incident_project
+                        .as_deref()
+                        .unwrap_or("INCIDENT_PROJECT")
+                ),
+                false,
+            ),
+        );
+        props.insert(
+            "issue_type".to_string(),
+            string_prop(
+                "Issue type (default: the incident template's, 'Incident')",
+                false,
+            ),
+        );
+        props.insert(
+            "parent_id".to_string(),
+            string_prop(
+                "Confluence page to create the postmortem under (default: INCIDENT_POSTMORTEM_PARENT; without one no page is created)",
+                false,
+            ),
+        );
+        ToolSchema::new(
+            "Open an incident: create the Jira incident issue and a postmortem Confluence page from templates, linked to each other",
+            props,
+            vec!["summary".to_string(), "impact".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let args: IncidentArgs = parse_args(&args)?;
+        let given = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(String::from)
+        };
+        let project_key = given(&args.project_key)
+            .or_else(|| config.incident_project.clone())
+            .ok_or_else(|| {
+                anyhow::anyhow!("Missing project_key (or set INCIDENT_PROJECT for a default)")
+            })?;
+        let parent_id =
+            given(&args.parent_id).or_else(|| config.incident_postmortem_parent.clone());
+        // Both targets are checked up front so a rejected space leaves no issue behind
+        write_policy::check_project(config, &project_key)?;
+        if let Some(parent_id) = &parent_id {
+            write_policy::check_page(&Confluence::new(config), config, parent_id).await?;
+        }
+
+        let template = load_template("incident", config.jira_templates_dir.as_deref())?;
+        let issue_type = given(&args.issue_type)
+            .or(template.issue_type)
+            .unwrap_or_else(|| DEFAULT_ISSUE_TYPE.to_string());
+        let mut description = fill_placeholders(&template.body, &template_values(&args, None))?;
+        if let Some(severity) = given(&args.severity) {
+            description = format!("**Severity:** {}\n\n{}", severity, description);
+        }
+
+        let jira = Jira::new(config);
+        let created = jira
+            .create_issue(
+                &project_key,
+                &args.summary,
+                &issue_type,
+                markdown_to_adf(&description),
+            )
+            .await?;
+        let issue_url = format!("{}/browse/{}", config.get_atlassian_base_url(), created.key);
+        let mut result = json!({
+            "success": true,
+            "issue_key": created.key,
+            "issue_id": created.id,
+            "issue_url": issue_url,
+            "issue_type": issue_type
+        });
+
+        let Some(parent_id) = parent_id else {
+            result["postmortem"] = Value::Null;
+            return Ok(result);
+        };
+        let values = template_values(&args, Some((&created.key, &issue_url)));
+        let title = format!("Postmortem: {} {}", created.key, args.summary);
+        match create_postmortem(config, &parent_id, &title, &values).await {
+            Ok((page_id, page_title, page_url)) => {
+                result["postmortem"] = json!({
+                    "page_id": page_id,
+                    "title": page_title,
+                    "url": page_url,
+                    "parent_id": parent_id
+                });
+                if let Err(e) =
+                    link_to_page(jira.http(), config, &created.key, &page_url, &page_title).await
+                {
+                    result["link_error"] = json!(e.to_string());
+                }
+            }
+            Err(e) => {
+                result["postmortem"] = Value::Null;
+                result["postmortem_error"] = json!(e.to_string());
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(value: Value) -> IncidentArgs {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_builtin_postmortem_fills_issue_link() {
+        let args = args(json!({
+            "summary": "Checkout errors",
+            "impact": "5% of payments fail",
+            "timeline": ["09:12 alerts fired", "09:20 rollback started"],
+            "severity": ""
+        }));
+        let values = template_values(&args, Some(("OPS-9", "https://x.test/browse/OPS-9")));
+        let body = fill_placeholders(POSTMORTEM_TEMPLATE, &values).unwrap();
+        assert!(body.contains("[OPS-9](https://x.test/browse/OPS-9)"));
+        assert!(body.contains("**Severity:** Not set"));
+        assert!(body.contains("- 09:12 alerts fired\n- 09:20 rollback started"));
+        assert!(body.contains("Under investigation"));
+    }
+
+    #[test]
+    fn test_escaped_values_for_storage_templates() {
+        let args = args(json!({"summary": "5xx <api>", "impact": "A & B"}));
+        let values = escaped_values(&template_values(&args, None));
+        let body = fill_placeholders("<p>{{summary}}: {{impact}}</p>", &values).unwrap();
+        assert_eq!(body, "<p>5xx &lt;api&gt;: A &amp; B</p>");
+    }
+}
@@ -0,0 +1,188 @@
+//! Recent activity of the calling user
+//!
+//! Jira Cloud has no JSON activity stream, so `jira_recent_activity` merges
+//! two JQL searches over the last `days`: issues the user viewed
+//! (`issueHistory()`, by `lastViewed`) and issues the user changed
+//! (`updatedBy()`). Each issue appears once, flagged with how it was touched.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::applied_filters;
+use crate::tools::schema::{ToolSchema, number_prop, string_prop};
+use crate::utils::http_utils::create_atlassian_client;
+use std::collections::HashMap;
+
+use super::{applied_projects_filter, apply_projects_filter, search_page};
+
+pub struct RecentActivityHandler;
+
+const DEFAULT_DAYS: u64 = 1;
+const MAX_DAYS: u64 = 30;
+const DEFAULT_LIMIT: u64 = 30;
+const MAX_LIMIT: u64 = 100;
+const ACTIVITY_FIELDS: [&str; 5] = ["summary", "status", "updated", "assignee", "lastViewed"];
+
+/// `(viewed, updated)` queries for the last `days`; `account_id` replaces
+/// `currentUser()` for the update query
+pub fn activity_queries(days: u64, account_id: Option<&str>) -> (String, String) {
+    let user = account_id
+        .map(|id| format!("\"{}\"", id.replace('"', "")))
+        .unwrap_or_else(|| "currentUser()".to_string());
+    (
+        format!(
+            "issue in issueHistory() AND lastViewed >= -{}d ORDER BY lastViewed DESC",
+            days
+        ),
+        format!(
+            "issue in updatedBy({}, \"-{}d\") ORDER BY updated DESC",
+            user, days
+        ),
+    )
+}
+
+fn compact_issue(issue: &Value) -> Value {
+    let fields = &issue["fields"];
+    json!({
+        "key": issue["key"],
+        "summary": fields["summary"],
+        "status": fields["status"]["name"],
+        "assignee": fields["assignee"]["displayName"],
+        "updated": fields["updated"],
+        "last_viewed": fields["lastViewed"],
+        "viewed": false,
+        "updated_by_user": false
+    })
+}
+
+/// One entry per key, viewed issues first, flagged by the searches that found it
+pub fn merge_activity(viewed: &[Value], updated: &[Value], limit: usize) -> Vec<Value> {
+    let mut merged: Vec<Value> = Vec::new();
+    for (issues, flag) in [(viewed, "viewed"), (updated, "updated_by_user")] {
+        for issue in issues {
+            let position = merged.iter().position(|m| m["key"] == issue["key"]);
+            let entry = match position {
+                Some(i) => &mut merged[i],
+                None => {
+                    merged.push(compact_issue(issue));
+                    merged.last_mut().expect("just pushed")
+                }
+            };
+            entry[flag] = json!(true);
+        }
+    }
+    merged.truncate(limit);
+    merged
+}
+
+#[async_trait]
+impl ToolHandler for RecentActivityHandler {
+    fn name(&self) -> &'static str {
+        "jira_recent_activity"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "days".to_string(),
+            number_prop("Look-back window in days (max 30)", 1),
+        );
+        props.insert(
+            "limit".to_string(),
+            number_prop("Maximum issues to return (max 100)", 30),
+        );
+        props.insert(
+            "account_id".to_string(),
+            string_prop(
+                "Another user's account ID; only their updates are listed (view history is private)",
+                false,
+            ),
+        );
+        ToolSchema::new(
+            "Issues you recently viewed or updated, newest first, flagged by how they were touched",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let days = args["days"]
+            .as_u64()
+            .unwrap_or(DEFAULT_DAYS)
+            .clamp(1, MAX_DAYS);
+        let limit = args["limit"]
+            .as_u64()
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+        let account_id = args["account_id"].as_str();
+        let (viewed_jql, updated_jql) = activity_queries(days, account_id);
+
+        let client = create_atlassian_client(config);
+        let fields: Vec<String> = ACTIVITY_FIELDS.iter().map(|f| f.to_string()).collect();
+
+        // issueHistory() is the caller's own history; skip it for someone else
+        let viewed = if account_id.is_none() {
+            let jql = apply_projects_filter(&viewed_jql, config);
+            let data = search_page(&client, config, &jql, &fields, limit, None).await?;
+            data["issues"].as_array().cloned().unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let jql = apply_projects_filter(&updated_jql, config);
+        let data = search_page(&client, config, &jql, &fields, limit, None).await?;
+        let updated = data["issues"].as_array().cloned().unwrap_or_default();
+
+        let issues = merge_activity(&viewed, &updated, limit as usize);
+        let result = json!({
+            "success": true,
+            "days": days,
+            "total": issues.len(),
+            "issues": issues
+        });
+        let applied = applied_projects_filter(&updated_jql, &updated_jql, &jql, config);
+        Ok(applied_filters::attach(result, applied))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activity_queries() {
+        let (viewed, updated) = activity_queries(2, None);
+        assert_eq!(
+            viewed,
+            "issue in issueHistory() AND lastViewed >= -2d ORDER BY lastViewed DESC"
+        );
+        assert_eq!(
+            updated,
+            "issue in updatedBy(currentUser(), \"-2d\") ORDER BY updated DESC"
+        );
+        let (_, updated) = activity_queries(1, Some("557058:abc"));
+        assert!(updated.starts_with("issue in updatedBy(\"557058:abc\", \"-1d\")"));
+    }
+
+    #[test]
+    fn test_merge_activity_flags_each_key_once() {
+        let issue =
+            |key: &str| json!({"key": key, "fields": {"summary": key, "status": {"name": "Open"}}});
+        let merged = merge_activity(
+            &[issue("A-1"), issue("A-2")],
+            &[issue("A-2"), issue("A-3")],
+            10,
+        );
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[1]["key"], "A-2");
+        assert_eq!(merged[1]["viewed"], true);
+        assert_eq!(merged[1]["updated_by_user"], true);
+        assert_eq!(merged[2]["viewed"], false);
+        assert_eq!(
+            merge_activity(&[issue("A-1"), issue("A-2")], &[], 1).len(),
+            1
+        );
+    }
+}
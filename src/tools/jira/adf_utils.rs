@@ -160,6 +160,672 @@ pub fn process_comment_input(value: Value) -> Result<Value> {
     process_adf_input(value, "comment")
 }
 
+/// Processes Markdown input: strings go through `markdown_to_adf`, ADF
+/// objects and null are handled as in `process_adf_input`.
+pub fn process_markdown_input(value: Value, field_name: &str) -> Result<Value> {
+    match value {
+        Value::String(markdown) => Ok(markdown_to_adf(&markdown)),
+        other => process_adf_input(other, field_name),
+    }
+}
+
+/// Renders an ADF document as Markdown.
+///
+/// Covers the nodes Jira produces in descriptions and comments: headings,
+/// paragraphs, bullet/ordered/task lists, code blocks, quotes, panels
+/// (`> [!INFO]`), rules, tables and inline marks (strong, em, strike, code,
+/// link). Mentions become `[@Name](mention:ACCOUNT_ID)`, emojis their
+/// `:shortName:` and status lozenges `{status:TEXT|color}`, so
+/// `markdown_to_adf` can restore them. Inline cards become their URL and
+/// media a `[media]` placeholder; unknown nodes fall back to their text.
+pub fn adf_to_markdown(doc: &Value) -> String {
+    blocks_to_markdown(&doc["content"])
+}
+
+fn blocks_to_markdown(content: &Value) -> String {
+    content
+        .as_array()
+        .map(|nodes| {
+            nodes
+                .iter()
+                .map(block_to_markdown)
+                .filter(|b| !b.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        })
+        .unwrap_or_default()
+}
+
+/// Prefixes every line of `text` (quotes, list continuation indents)
+fn prefix_lines(text: &str, first: &str, rest: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let prefix = if i == 0 { first } else { rest };
+            if line.is_empty() && i > 0 {
+                prefix.trim_end().to_string()
+            } else {
+                format!("{}{}", prefix, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn list_to_markdown(node: &Value, ordered: bool) -> String {
+    let start = node["attrs"]["order"].as_u64().unwrap_or(1);
+    node["content"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    let marker = if ordered {
+                        format!("{}. ", start + i as u64)
+                    } else {
+                        "- ".to_string()
+                    };
+                    let indent = " ".repeat(marker.len());
+                    let body = blocks_to_markdown(&item["content"]).replace("\n\n", "\n");
+                    prefix_lines(&body, &marker, &indent)
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default()
+}
+
+fn table_to_markdown(node: &Value) -> String {
+    let rows: Vec<Vec<String>> = node["content"]
+        .as_array()
+        .map(|rows| {
+            rows.iter()
+                .map(|row| {
+                    row["content"]
+                        .as_array()
+                        .map(|cells| {
+                            cells
+                                .iter()
+                                .map(|cell| {
+                                    blocks_to_markdown(&cell["content"])
+                                        .replace('|', "\\|")
+                                        .replace('\n', " ")
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let Some(width) = rows.iter().map(Vec::len).max() else {
+        return String::new();
+    };
+
+    let line = |cells: &[String]| {
+        let mut padded = cells.to_vec();
+        padded.resize(width, String::new());
+        format!("| {} |", padded.join(" | "))
+    };
+    let mut out = vec![line(&rows[0]), format!("|{}", " --- |".repeat(width))];
+    out.extend(rows[1..].iter().map(|r| line(r)));
+    out.join("\n")
+}
+
+fn block_to_markdown(node: &Value) -> String {
+    match node["type"].as_str().unwrap_or_default() {
+        "paragraph" => inline_to_markdown(&node["content"]),
+        "heading" => {
+            let level = node["attrs"]["level"].as_u64().unwrap_or(1).clamp(1, 6) as usize;
+            format!(
+                "{} {}",
+                "#".repeat(level),
+                inline_to_markdown(&node["content"])
+            )
+        }
+        "bulletList" => list_to_markdown(node, false),
+        "orderedList" => list_to_markdown(node, true),
+        "taskList" => node["content"]
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|item| {
+                        let done = item["attrs"]["state"].as_str() == Some("DONE");
+                        format!(
+                            "- [{}] {}",
+                            if done { "x" } else { " " },
+                            inline_to_markdown(&item["content"])
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .unwrap_or_default(),
+        "codeBlock" => format!(
+            "```{}\n{}\n```",
+            node["attrs"]["language"].as_str().unwrap_or_default(),
+            inline_to_markdown(&node["content"])
+        ),
+        "blockquote" => prefix_lines(&blocks_to_markdown(&node["content"]), "> ", "> "),
+        "panel" => {
+            let kind = node["attrs"]["panelType"].as_str().unwrap_or("info");
+            let body = format!(
+                "[!{}]\n{}",
+                kind.to_uppercase(),
+                blocks_to_markdown(&node["content"])
+            );
+            prefix_lines(&body, "> ", "> ")
+        }
+        "rule" => "---".to_string(),
+        "table" => table_to_markdown(node),
+        "mediaSingle" | "mediaGroup" => "[media]".to_string(),
+        _ if node["content"]
+            .as_array()
+            .is_some_and(|c| c.iter().any(|n| n["type"] == "text")) =>
+        {
+            inline_to_markdown(&node["content"])
+        }
+        _ => blocks_to_markdown(&node["content"]),
+    }
+}
+
+fn text_with_marks(node: &Value) -> String {
+    let mut text = node["text"].as_str().unwrap_or_default().to_string();
+    let Some(marks) = node["marks"].as_array() else {
+        return text;
+    };
+    // Code first so emphasis wraps the backticks rather than the reverse
+    if marks.iter().any(|m| m["type"] == "code") {
+        text = format!("`{}`", text);
+    }
+    for mark in marks {
+        text = match mark["type"].as_str().unwrap_or_default() {
+            "strong" => format!("**{}**", text),
+            "em" => format!("*{}*", text),
+            "strike" => format!("~~{}~~", text),
+            "link" => format!(
+                "[{}]({})",
+                text,
+                mark["attrs"]["href"].as_str().unwrap_or_default()
+            ),
+            _ => text,
+        };
+    }
+    text
+}
+
+fn inline_to_markdown(content: &Value) -> String {
+    let Some(nodes) = content.as_array() else {
+        return String::new();
+    };
+    nodes
+        .iter()
+        .map(|node| match node["type"].as_str().unwrap_or_default() {
+            "text" => text_with_marks(node),
+            "hardBreak" => "\n".to_string(),
+            "mention" => {
+                let name = node["attrs"]["text"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .trim_start_matches('@');
+                match node["attrs"]["id"].as_str() {
+                    Some(id) => format!("[@{}](mention:{})", name, id),
+                    None => format!("@{}", name),
+                }
+            }
+            "emoji" => node["attrs"]["shortName"]
+                .as_str()
+                .or_else(|| node["attrs"]["text"].as_str())
+                .unwrap_or_default()
+                .to_string(),
+            "inlineCard" => node["attrs"]["url"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            "status" => format!(
+                "{{status:{}|{}}}",
+                node["attrs"]["text"].as_str().unwrap_or_default(),
+                node["attrs"]["color"].as_str().unwrap_or("neutral")
+            ),
+            _ => inline_to_markdown(&node["content"]),
+        })
+        .collect()
+}
+
+/// Parses Markdown into an ADF document; the inverse of `adf_to_markdown`.
+///
+/// Blocks: ATX headings, paragraphs (line breaks become `hardBreak`s), `-`,
+/// `*` and `1.` lists nested by indentation, `- [ ]`/`- [x]` tasks, fenced
+/// code, `---` rules, `|` tables, `>` quotes and `> [!INFO]` panels (INFO,
+/// NOTE, WARNING, SUCCESS, ERROR). Inline: `**strong**`, `*em*`,
+/// `~~strike~~`, `` `code` ``, `[text](url)`, `[@Name](mention:ACCOUNT_ID)`,
+/// `:emoji:` and `{status:TEXT|color}`.
+pub fn markdown_to_adf(markdown: &str) -> Value {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut ids = 0;
+    json!({
+        "type": "doc",
+        "version": 1,
+        "content": md_blocks(&lines, &mut ids)
+    })
+}
+
+/// Panel types by GitHub alert name
+const PANEL_TYPES: &[(&str, &str)] = &[
+    ("INFO", "info"),
+    ("NOTE", "note"),
+    ("WARNING", "warning"),
+    ("SUCCESS", "success"),
+    ("TIP", "success"),
+    ("ERROR", "error"),
+    ("CAUTION", "error"),
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum ListKind {
+    Bullet,
+    Ordered,
+    Task,
+}
+
+struct ListMarker<'a> {
+    indent: usize,
+    /// Indent plus marker, i.e. where continuation lines start
+    width: usize,
+    kind: ListKind,
+    order: u64,
+    done: bool,
+    text: &'a str,
+}
+
+fn list_marker(line: &str) -> Option<ListMarker<'_>> {
+    let body = line.trim_start_matches(' ');
+    let indent = line.len() - body.len();
+    if let Some(text) = body.strip_prefix("- ").or_else(|| body.strip_prefix("* ")) {
+        let task = text
+            .strip_prefix("[ ] ")
+            .map(|t| (false, t))
+            .or_else(|| text.strip_prefix("[x] ").map(|t| (true, t)))
+            .or_else(|| text.strip_prefix("[X] ").map(|t| (true, t)));
+        let (kind, done, text) = match task {
+            Some((done, text)) => (ListKind::Task, done, text),
+            None => (ListKind::Bullet, false, text),
+        };
+        return Some(ListMarker {
+            indent,
+            width: indent + 2,
+            kind,
+            order: 1,
+            done,
+            text,
+        });
+    }
+    let (number, text) = body.split_once(". ")?;
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(ListMarker {
+        indent,
+        width: indent + number.len() + 2,
+        kind: ListKind::Ordered,
+        order: number.parse().unwrap_or(1),
+        done: false,
+        text,
+    })
+}
+
+fn local_id(ids: &mut usize) -> String {
+    *ids += 1;
+    format!("md-{}", ids)
+}
+
+fn paragraph_node(lines: &[&str]) -> Value {
+    let mut content = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            content.push(json!({"type": "hardBreak"}));
+        }
+        content.extend(md_inline(line, &[]));
+    }
+    json!({"type": "paragraph", "content": content})
+}
+
+/// Cells of a `| a | b |` row, with `\|` unescaped
+fn table_cells(line: &str) -> Vec<String> {
+    let inner = line.trim().trim_start_matches('|');
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+    let mut cells = vec![String::new()];
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                chars.next();
+                cells.last_mut().unwrap().push('|');
+            }
+            '|' => cells.push(String::new()),
+            _ => cells.last_mut().unwrap().push(c),
+        }
+    }
+    cells.iter().map(|cell| cell.trim().to_string()).collect()
+}
+
+fn is_table_separator(line: &str) -> bool {
+    let cells = table_cells(line);
+    line.trim().starts_with('|')
+        && cells
+            .iter()
+            .all(|cell| !cell.is_empty() && cell.chars().all(|c| matches!(c, '-' | ':')))
+}
+
+fn table_node(rows: &[&str]) -> Value {
+    let has_header = rows.len() > 1 && is_table_separator(rows[1]);
+    let content: Vec<Value> = rows
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !(has_header && *i == 1))
+        .map(|(i, row)| {
+            let cell_type = if has_header && i == 0 {
+                "tableHeader"
+            } else {
+                "tableCell"
+            };
+            let cells: Vec<Value> = table_cells(row)
+                .iter()
+                .map(|cell| {
+                    json!({
+                        "type": cell_type,
+                        "content": [{"type": "paragraph", "content": md_inline(cell, &[])}]
+                    })
+                })
+                .collect();
+            json!({"type": "tableRow", "content": cells})
+        })
+        .collect();
+    json!({"type": "table", "content": content})
+}
+
+/// Parses the list starting at `lines[*i]`, advancing `i` past it
+fn list_node(lines: &[&str], i: &mut usize, ids: &mut usize) -> Value {
+    let Some(first) = list_marker(lines[*i]) else {
+        return Value::Null;
+    };
+    let (base, kind) = (first.indent, first.kind);
+    let mut items = Vec::new();
+
+    while *i < lines.len() {
+        let Some(marker) = list_marker(lines[*i]).filter(|m| m.indent == base && m.kind == kind)
+        else {
+            break;
+        };
+        *i += 1;
+        let mut children = Vec::new();
+        while *i < lines.len()
+            && !lines[*i].trim().is_empty()
+            && lines[*i].len() - lines[*i].trim_start_matches(' ').len() > base
+        {
+            let line = lines[*i];
+            let strip = (line.len() - line.trim_start_matches(' ').len()).min(marker.width);
+            children.push(&line[strip..]);
+            *i += 1;
+        }
+
+        if kind == ListKind::Task {
+            let mut content = md_inline(marker.text.trim(), &[]);
+            for child in &children {
+                content.push(json!({"type": "hardBreak"}));
+                content.extend(md_inline(child.trim(), &[]));
+            }
+            items.push(json!({
+                "type": "taskItem",
+                "attrs": {"localId": local_id(ids), "state": if marker.done { "DONE" } else { "TODO" }},
+                "content": content
+            }));
+        } else {
+            let mut content = vec![paragraph_node(&[marker.text.trim()])];
+            content.extend(md_blocks(&children, ids));
+            items.push(json!({"type": "listItem", "content": content}));
+        }
+    }
+
+    match kind {
+        ListKind::Bullet => json!({"type": "bulletList", "content": items}),
+        ListKind::Ordered if first.order != 1 => json!({
+            "type": "orderedList",
+            "attrs": {"order": first.order},
+            "content": items
+        }),
+        ListKind::Ordered => json!({"type": "orderedList", "content": items}),
+        ListKind::Task => json!({
+            "type": "taskList",
+            "attrs": {"localId": local_id(ids)},
+            "content": items
+        }),
+    }
+}
+
+fn md_blocks(lines: &[&str], ids: &mut usize) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut i = 0;
+
+    fn flush(paragraph: &mut Vec<&str>, blocks: &mut Vec<Value>) {
+        if !paragraph.is_empty() {
+            blocks.push(paragraph_node(paragraph));
+            paragraph.clear();
+        }
+    }
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+        let hashes = trimmed.chars().take_while(|c| *c == '#').count();
+
+        if trimmed.is_empty() {
+            flush(&mut paragraph, &mut blocks);
+            i += 1;
+        } else if let Some(language) = trimmed.strip_prefix("```") {
+            flush(&mut paragraph, &mut blocks);
+            let mut body = Vec::new();
+            i += 1;
+            while i < lines.len() && !lines[i].trim_start().starts_with("```") {
+                body.push(lines[i]);
+                i += 1;
+            }
+            i += 1;
+            let mut node = json!({"type": "codeBlock", "content": []});
+            if !language.trim().is_empty() {
+                node["attrs"] = json!({"language": language.trim()});
+            }
+            if !body.is_empty() {
+                node["content"] = json!([{"type": "text", "text": body.join("\n")}]);
+            }
+            blocks.push(node);
+        } else if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(json!({
+                "type": "heading",
+                "attrs": {"level": hashes},
+                "content": md_inline(trimmed[hashes..].trim(), &[])
+            }));
+            i += 1;
+        } else if matches!(trimmed, "---" | "***" | "___") {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(json!({"type": "rule"}));
+            i += 1;
+        } else if trimmed.starts_with('>') {
+            flush(&mut paragraph, &mut blocks);
+            let mut inner = Vec::new();
+            while i < lines.len() && lines[i].trim_start().starts_with('>') {
+                let quoted = &lines[i].trim_start()[1..];
+                inner.push(quoted.strip_prefix(' ').unwrap_or(quoted));
+                i += 1;
+            }
+            let panel = inner.first().and_then(|first| {
+                let name = first.trim().strip_prefix("[!")?.strip_suffix(']')?;
+                PANEL_TYPES
+                    .iter()
+                    .find(|(alert, _)| alert.eq_ignore_ascii_case(name))
+                    .map(|(_, panel_type)| *panel_type)
+            });
+            blocks.push(match panel {
+                Some(panel_type) => json!({
+                    "type": "panel",
+                    "attrs": {"panelType": panel_type},
+                    "content": md_blocks(&inner[1..], ids)
+                }),
+                None => json!({"type": "blockquote", "content": md_blocks(&inner, ids)}),
+            });
+        } else if trimmed.starts_with('|')
+            && lines.get(i + 1).is_some_and(|l| is_table_separator(l))
+        {
+            flush(&mut paragraph, &mut blocks);
+            let start = i;
+            while i < lines.len() && lines[i].trim().starts_with('|') {
+                i += 1;
+            }
+            blocks.push(table_node(&lines[start..i]));
+        } else if list_marker(line).is_some() {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(list_node(lines, &mut i, ids));
+        } else {
+            paragraph.push(trimmed);
+            i += 1;
+        }
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+fn marked(text: &str, marks: &[Value]) -> Value {
+    if marks.is_empty() {
+        json!({"type": "text", "text": text})
+    } else {
+        json!({"type": "text", "text": text, "marks": marks})
+    }
+}
+
+/// Adds an inner mark; `text_with_marks` applies marks innermost first
+fn with_mark(marks: &[Value], mark: Value) -> Vec<Value> {
+    let mut all = vec![mark];
+    all.extend_from_slice(marks);
+    all
+}
+
+/// Text between a leading `marker` and its next occurrence, and what follows
+fn delimited<'a>(rest: &'a str, marker: &str) -> Option<(&'a str, &'a str)> {
+    let inner = rest.strip_prefix(marker)?;
+    let end = inner.find(marker).filter(|&end| end > 0)?;
+    if inner.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some((&inner[..end], &inner[end + marker.len()..]))
+}
+
+/// `marker`-delimited text parsed recursively with one more mark
+fn wrapped_inline<'a>(
+    rest: &'a str,
+    marker: &str,
+    mark: &str,
+    marks: &[Value],
+) -> Option<(Vec<Value>, &'a str)> {
+    let (inner, after) = delimited(rest, marker)?;
+    Some((
+        md_inline(inner, &with_mark(marks, json!({"type": mark}))),
+        after,
+    ))
+}
+
+/// `[@Name](mention:ID)` or `[text](url)`
+fn link_inline<'a>(rest: &'a str, marks: &[Value]) -> Option<(Vec<Value>, &'a str)> {
+    let (label, after) = rest.strip_prefix('[')?.split_once("](")?;
+    let (url, after) = after.split_once(')')?;
+    let nodes = match url.strip_prefix("mention:") {
+        Some(id) if label.starts_with('@') => vec![json!({
+            "type": "mention",
+            "attrs": {"id": id, "text": label}
+        })],
+        _ => md_inline(
+            label,
+            &with_mark(marks, json!({"type": "link", "attrs": {"href": url}})),
+        ),
+    };
+    Some((nodes, after))
+}
+
+/// `:short_name:` (lowercase letters, digits, `_`, `+`, `-`)
+fn emoji_inline(rest: &str) -> Option<(Vec<Value>, &str)> {
+    let (name, after) = rest.strip_prefix(':')?.split_once(':')?;
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase() || c == '+')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "_+-".contains(c));
+    valid.then(|| {
+        (
+            vec![json!({"type": "emoji", "attrs": {"shortName": format!(":{}:", name)}})],
+            after,
+        )
+    })
+}
+
+/// `{status:TEXT|color}`; the color defaults to neutral
+fn status_inline(rest: &str) -> Option<(Vec<Value>, &str)> {
+    let (status, after) = rest.strip_prefix("{status:")?.split_once('}')?;
+    let (text, color) = status.split_once('|').unwrap_or((status, "neutral"));
+    Some((
+        vec![json!({"type": "status", "attrs": {"text": text, "color": color}})],
+        after,
+    ))
+}
+
+/// Inline nodes for one line of Markdown, each text node carrying `marks`
+fn md_inline(text: &str, marks: &[Value]) -> Vec<Value> {
+    let mut nodes = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let after_word = text[..text.len() - rest.len()]
+            .chars()
+            .next_back()
+            .is_some_and(|p| p.is_alphanumeric());
+        let matched: Option<(Vec<Value>, &str)> = match c {
+            '`' => delimited(rest, "`").map(|(inner, after)| {
+                (
+                    vec![marked(inner, &with_mark(marks, json!({"type": "code"})))],
+                    after,
+                )
+            }),
+            '*' if rest.starts_with("**") => wrapped_inline(rest, "**", "strong", marks),
+            '*' => wrapped_inline(rest, "*", "em", marks),
+            '~' if rest.starts_with("~~") => wrapped_inline(rest, "~~", "strike", marks),
+            '[' => link_inline(rest, marks),
+            ':' if !after_word => emoji_inline(rest),
+            '{' => status_inline(rest),
+            _ => None,
+        };
+
+        match matched {
+            Some((mut inline, after)) => {
+                if !plain.is_empty() {
+                    nodes.push(marked(&std::mem::take(&mut plain), marks));
+                }
+                nodes.append(&mut inline);
+                rest = after;
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !plain.is_empty() {
+        nodes.push(marked(&plain, marks));
+    }
+    nodes
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -926,4 +1592,109 @@ mod tests {
             "Document with 150 paragraphs should be valid"
         );
     }
+
+    #[test]
+    fn test_adf_to_markdown_blocks_and_marks() {
+        let doc = json!({
+            "type": "doc",
+            "version": 1,
+            "content": [
+                {"type": "heading", "attrs": {"level": 2}, "content": [{"type": "text", "text": "Steps"}]},
+                {"type": "paragraph", "content": [
+                    {"type": "text", "text": "Ask "},
+                    {"type": "mention", "attrs": {"text": "@Jane Doe"}},
+                    {"type": "text", "text": " about "},
+                    {"type": "text", "text": "SSO", "marks": [{"type": "strong"}]},
+                    {"type": "text", "text": " (docs)", "marks": [{"type": "link", "attrs": {"href": "https://x.test"}}]}
+                ]},
+                {"type": "orderedList", "content": [
+                    {"type": "listItem", "content": [{"type": "paragraph", "content": [{"type": "text", "text": "Open"}]}]},
+                    {"type": "listItem", "content": [
+                        {"type": "paragraph", "content": [{"type": "text", "text": "Click"}]},
+                        {"type": "bulletList", "content": [
+                            {"type": "listItem", "content": [{"type": "paragraph", "content": [{"type": "text", "text": "twice"}]}]}
+                        ]}
+                    ]}
+                ]},
+                {"type": "codeBlock", "attrs": {"language": "sh"}, "content": [{"type": "text", "text": "make test"}]},
+                {"type": "taskList", "content": [
+                    {"type": "taskItem", "attrs": {"state": "DONE"}, "content": [{"type": "text", "text": "Triage"}]}
+                ]}
+            ]
+        });
+
+        assert_eq!(
+            adf_to_markdown(&doc),
+            "## Steps\n\n\
+             Ask @Jane Doe about **SSO**[ (docs)](https://x.test)\n\n\
+             1. Open\n2. Click\n   - twice\n\n\
+             ```sh\nmake test\n```\n\n\
+             - [x] Triage"
+        );
+    }
+
+    #[test]
+    fn test_adf_to_markdown_table_and_quote() {
+        let cell = |t: &str| json!({"type": "tableCell", "content": [{"type": "paragraph", "content": [{"type": "text", "text": t}]}]});
+        let doc = json!({"type": "doc", "version": 1, "content": [
+            {"type": "table", "content": [
+                {"type": "tableRow", "content": [cell("Name"), cell("Value")]},
+                {"type": "tableRow", "content": [cell("a|b"), cell("1")]}
+            ]},
+            {"type": "blockquote", "content": [{"type": "paragraph", "content": [{"type": "text", "text": "quoted"}]}]}
+        ]});
+        assert_eq!(
+            adf_to_markdown(&doc),
+            "| Name | Value |\n| --- | --- |\n| a\\|b | 1 |\n\n> quoted"
+        );
+        assert_eq!(adf_to_markdown(&Value::Null), "");
+    }
+
+    #[test]
+    fn test_markdown_round_trip_keeps_rich_nodes() {
+        let markdown = "## Rollout :rocket:\n\n\
+             Ask [@Jane Doe](mention:557058:abc) about **SSO** and ~~*old*~~ `flags` {status:IN REVIEW|blue}\n\
+             see [docs](https://x.test)\n\n\
+             > [!WARNING]\n> Feature flag **must** stay off\n\n\
+             > quoted\n\n\
+             1. Open\n2. Click\n   - twice\n\n\
+             - [x] Triage\n- [ ] Ship\n\n\
+             | Name | Value |\n| --- | --- |\n| a\\|b | 1 |\n\n\
+             ```sh\nmake test\n```\n\n\
+             ---";
+        let adf = markdown_to_adf(markdown);
+        assert!(validate_adf(&adf).is_ok());
+        assert_eq!(adf_to_markdown(&adf), markdown);
+
+        let content = &adf["content"];
+        assert_eq!(content[0]["content"][1]["attrs"]["shortName"], ":rocket:");
+        let paragraph = &content[1]["content"];
+        assert_eq!(
+            paragraph[1],
+            json!({"type": "mention", "attrs": {"id": "557058:abc", "text": "@Jane Doe"}})
+        );
+        assert_eq!(
+            paragraph[5]["marks"],
+            json!([{"type": "em"}, {"type": "strike"}])
+        );
+        assert_eq!(paragraph[9]["attrs"]["color"], "blue");
+        assert_eq!(content[2]["type"], "panel");
+        assert_eq!(content[2]["attrs"]["panelType"], "warning");
+        assert_eq!(content[3]["type"], "blockquote");
+        assert_eq!(content[4]["content"][1]["content"][1]["type"], "bulletList");
+        assert_eq!(content[5]["content"][0]["attrs"]["state"], "DONE");
+        assert_eq!(
+            content[6]["content"][0]["content"][0]["type"],
+            "tableHeader"
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_adf_leaves_lookalikes_as_text() {
+        let adf = markdown_to_adf("At 10:30:00 see a:b: and *, {x} or [x]");
+        assert_eq!(
+            adf["content"][0]["content"],
+            json!([{"type": "text", "text": "At 10:30:00 see a:b: and *, {x} or [x]"}])
+        );
+    }
 }
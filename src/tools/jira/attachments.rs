@@ -0,0 +1,239 @@
+//! Text extraction from small issue attachments
+//!
+//! Only text-like files are read (by extension or `text/*` MIME type) and the
+//! download stops at the byte cap, so a large log never lands in the context
+//! in full.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{ToolSchema, number_prop, string_prop, union_prop};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+/// Extensions treated as text regardless of the MIME type Jira reports
+pub const TEXT_EXTENSIONS: &[&str] = &[
+    "txt", "log", "csv", "tsv", "json", "md", "markdown", "xml", "yaml", "yml",
+];
+pub const DEFAULT_MAX_BYTES: usize = 100 * 1024;
+pub const MAX_BYTES_LIMIT: usize = 1024 * 1024;
+
+pub struct ReadAttachmentTextHandler;
+
+/// Whether an attachment can be returned as text
+pub fn is_text_attachment(filename: &str, mime_type: &str) -> bool {
+    let extension = filename
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    TEXT_EXTENSIONS.contains(&extension.as_str())
+        || mime_type.starts_with("text/")
+        || mime_type == "application/json"
+}
+
+/// Decodes at most `max_bytes`, trimming a UTF-8 sequence split by the cap
+pub fn decode_capped(bytes: &[u8], max_bytes: usize) -> String {
+    let slice = &bytes[..bytes.len().min(max_bytes)];
+    match std::str::from_utf8(slice) {
+        Ok(text) => text.to_string(),
+        Err(e) if e.error_len().is_none() => {
+            // Incomplete trailing sequence from truncation
+            String::from_utf8_lossy(&slice[..e.valid_up_to()]).into_owned()
+        }
+        Err(_) => String::from_utf8_lossy(slice).into_owned(),
+    }
+}
+
+async fn find_attachment(client: &reqwest::Client, config: &Config, args: &Value) -> Result<Value> {
+    let base_url = config.get_atlassian_base_url();
+
+    if let Some(id) = args["attachment_id"]
+        .as_str()
+        .map(String::from)
+        .or_else(|| args["attachment_id"].as_u64().map(|n| n.to_string()))
+    {
+        let response = client
+            .get(format!("{}/rest/api/3/attachment/{}", base_url, id))
+            .header("Authorization", create_auth_header(config))
+            .header("Accept", "application/json")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to get attachment metadata: {}", response.status());
+        }
+        return Ok(response.json().await?);
+    }
+
+    let (Some(issue_key), Some(filename)) = (args["issue_key"].as_str(), args["filename"].as_str())
+    else {
+        anyhow::bail!("Missing attachment_id (or issue_key and filename)");
+    };
+
+    let response = client
+        .get(format!("{}/rest/api/3/issue/{}", base_url, issue_key))
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .query(&[("fields", "attachment")])
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get issue attachments: {}", response.status());
+    }
+
+    let mut issue: Value = response.json().await?;
+    let attachments = issue["fields"]["attachment"].take();
+    attachments
+        .as_array()
+        .and_then(|list| {
+            list.iter()
+                .find(|a| a["filename"].as_str() == Some(filename))
+        })
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("No attachment named '{}' on {}", filename, issue_key))
+}
+
+#[async_trait]
+impl ToolHandler for ReadAttachmentTextHandler {
+    fn name(&self) -> &'static str {
+        "jira_read_attachment_text"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "attachment_id".to_string(),
+            union_prop("Attachment ID", vec!["string", "number"]),
+        );
+        props.insert(
+            "issue_key".to_string(),
+            string_prop("Issue key (with filename, instead of attachment_id)", false),
+        );
+        props.insert(
+            "filename".to_string(),
+            string_prop("Attachment filename on the issue", false),
+        );
+        props.insert(
+            "max_bytes".to_string(),
+            number_prop(
+                "Maximum bytes to return (default: 102400, max: 1048576)",
+                102400,
+            ),
+        );
+        ToolSchema::new(
+            "Read a text attachment (txt, log, csv, json, md, xml, yaml); content is size-capped",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let max_bytes = args["max_bytes"]
+            .as_u64()
+            .map(|n| n as usize)
+            .unwrap_or(DEFAULT_MAX_BYTES)
+            .clamp(1, MAX_BYTES_LIMIT);
+
+        let client = create_atlassian_client(config);
+        let attachment = find_attachment(&client, config, &args).await?;
+
+        let id = match &attachment["id"] {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        let filename = attachment["filename"].as_str().unwrap_or_default();
+        let mime_type = attachment["mimeType"].as_str().unwrap_or_default();
+        let size = attachment["size"].as_u64();
+
+        if !is_text_attachment(filename, mime_type) {
+            anyhow::bail!(
+                "Attachment '{}' ({}) is not a supported text type: {}",
+                filename,
+                mime_type,
+                TEXT_EXTENSIONS.join(", ")
+            );
+        }
+
+        let url = format!(
+            "{}/rest/api/3/attachment/content/{}",
+            config.get_atlassian_base_url(),
+            id
+        );
+        let mut response = client
+            .get(&url)
+            .header("Authorization", create_auth_header(config))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to download attachment: {}", response.status());
+        }
+
+        // Stream until the cap so oversized files are never fully buffered
+        let mut bytes = Vec::new();
+        let mut truncated = false;
+        while let Some(chunk) = response.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+            if bytes.len() > max_bytes {
+                truncated = true;
+                break;
+            }
+        }
+
+        Ok(json!({
+            "success": true,
+            "attachment_id": id,
+            "filename": filename,
+            "mime_type": mime_type,
+            "size": size,
+            "truncated": truncated,
+            "content": decode_capped(&bytes, max_bytes)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ConfigBuilder;
+
+    #[test]
+    fn test_is_text_attachment() {
+        assert!(is_text_attachment("server.LOG", "application/octet-stream"));
+        assert!(is_text_attachment("data.csv", ""));
+        assert!(is_text_attachment("notes", "text/plain"));
+        assert!(is_text_attachment("payload", "application/json"));
+        assert!(!is_text_attachment("screenshot.png", "image/png"));
+        assert!(!is_text_attachment("archive.zip", "application/zip"));
+    }
+
+    #[test]
+    fn test_decode_capped() {
+        assert_eq!(decode_capped(b"hello world", 5), "hello");
+        assert_eq!(decode_capped(b"short", 100), "short");
+
+        // "é" is two bytes; a cap in the middle drops the partial character
+        let text = "caf\u{e9}".as_bytes();
+        assert_eq!(decode_capped(text, 4), "caf");
+    }
+
+    #[test]
+    fn test_read_attachment_requires_identifier() {
+        let handler = ReadAttachmentTextHandler;
+        let config = ConfigBuilder::new().build();
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(handler.execute(json!({"issue_key": "PROJ-1"}), &config));
+
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Missing attachment_id")
+        );
+    }
+}
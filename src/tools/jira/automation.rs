@@ -0,0 +1,317 @@
+//! Automation rules (Cloud Automation REST API)
+//!
+//! Rules live on `api.atlassian.com` under the site's cloud ID and are read
+//! with the same basic auth as the site. Listing walks the summary cursor and
+//! filters locally: a project filter keeps rules scoped to that project plus
+//! global (site-wide) rules, since both run on its issues.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema, number_prop, string_prop};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header, fetch_cloud_id};
+use std::collections::HashMap;
+
+pub struct ListAutomationRulesHandler;
+pub struct GetAutomationRuleHandler;
+
+const AUTOMATION_API: &str = "https://api.atlassian.com/automation/public/jira";
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 500;
+/// Summaries per request (the API maximum)
+const PAGE_SIZE: usize = 100;
+
+fn rules_url(cloud_id: &str, path: &str) -> String {
+    format!("{}/{}/rest/v1/{}", AUTOMATION_API, cloud_id, path)
+}
+
+/// `project`, `global` (site-wide) or `other` for a rule scope ARI
+fn scope_kind(ari: &str) -> &'static str {
+    if ari.contains(":project/") {
+        "project"
+    } else if ari.contains(":site/") {
+        "global"
+    } else {
+        "other"
+    }
+}
+
+/// Whether a rule runs on issues of the project with `project_id`
+pub fn applies_to_project(rule: &Value, project_id: &str) -> bool {
+    let scopes: Vec<&str> = rule["ruleScopeARIs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    scopes.iter().any(|ari| {
+        scope_kind(ari) == "global"
+            || ari
+                .rsplit_once(":project/")
+                .is_some_and(|(_, id)| id == project_id)
+    })
+}
+
+/// The fields of a rule summary worth showing
+pub fn summarize_rule(rule: &Value) -> Value {
+    let scopes: Vec<Value> = rule["ruleScopeARIs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(|ari| json!({"type": scope_kind(ari), "ari": ari}))
+        .collect();
+    json!({
+        "uuid": rule["uuid"],
+        "name": rule["name"],
+        "state": rule["state"],
+        "description": rule["description"],
+        "labels": rule["labels"],
+        "scopes": scopes,
+        "updated": rule["updated"]
+    })
+}
+
+/// Cursor of the next summary page from `links.next` (a URL or query string)
+fn next_cursor(data: &Value) -> Option<String> {
+    let next = data["links"]["next"].as_str()?;
+    let url = reqwest::Url::parse(AUTOMATION_API).ok()?.join(next).ok()?;
+    url.query_pairs()
+        .find(|(name, value)| name == "cursor" && !value.is_empty())
+        .map(|(_, value)| value.into_owned())
+}
+
+/// Component types in execution order, nested branches flattened
+fn component_types(components: &Value, out: &mut Vec<Value>) {
+    for component in components.as_array().into_iter().flatten() {
+        out.push(json!({
+            "component": component["component"],
+            "type": component["type"]
+        }));
+        component_types(&component["children"], out);
+    }
+}
+
+async fn project_id(client: &reqwest::Client, config: &Config, key: &str) -> Result<String> {
+    let url = format!(
+        "{}/rest/api/3/project/{}",
+        config.get_atlassian_base_url(),
+        key
+    );
+    let response = client
+        .get(&url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get project {}: {}", key, response.status());
+    }
+    let data: Value = response.json().await?;
+    data["id"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("Project {} has no id", key))
+}
+
+#[async_trait]
+impl ToolHandler for ListAutomationRulesHandler {
+    fn name(&self) -> &'static str {
+        "jira_list_automation_rules"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "project_key".to_string(),
+            string_prop(
+                "Only rules that run in this project (its own and global rules)",
+                false,
+            ),
+        );
+        props.insert(
+            "state".to_string(),
+            Property::new("string")
+                .with_description("Only rules in this state")
+                .with_enum(vec![json!("ENABLED"), json!("DISABLED")]),
+        );
+        props.insert(
+            "limit".to_string(),
+            number_prop("Maximum rules to return (max 500)", 100),
+        );
+        ToolSchema::new(
+            "List Jira automation rules with their state and scope",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let limit = args["limit"]
+            .as_u64()
+            .map(|n| (n as usize).clamp(1, MAX_LIMIT))
+            .unwrap_or(DEFAULT_LIMIT);
+        let state = args["state"].as_str().map(str::to_uppercase);
+        if let Some(state) = &state
+            && !matches!(state.as_str(), "ENABLED" | "DISABLED")
+        {
+            anyhow::bail!("Invalid state '{}': expected ENABLED or DISABLED", state);
+        }
+
+        let client = create_atlassian_client(config);
+        let project = match args["project_key"].as_str() {
+            Some(key) => Some((key, project_id(&client, config, key).await?)),
+            None => None,
+        };
+        let cloud_id = fetch_cloud_id(&client, config).await?;
+        let url = rules_url(&cloud_id, "rule/summary");
+
+        let mut rules = Vec::new();
+        let mut cursor: Option<String> = None;
+        let mut truncated = false;
+        loop {
+            let mut body = json!({"limit": PAGE_SIZE});
+            if let Some(cursor) = &cursor {
+                body["cursor"] = json!(cursor);
+            }
+            let response = client
+                .post(&url)
+                .header("Authorization", create_auth_header(config))
+                .header("Content-Type", "application/json")
+                .json(&body)
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                anyhow::bail!("Failed to list automation rules: {}", response.status());
+            }
+            let data: Value = response.json().await?;
+
+            for rule in data["data"].as_array().into_iter().flatten() {
+                if state.as_deref().is_some_and(|s| rule["state"] != s) {
+                    continue;
+                }
+                if project
+                    .as_ref()
+                    .is_some_and(|(_, id)| !applies_to_project(rule, id))
+                {
+                    continue;
+                }
+                if rules.len() == limit {
+                    truncated = true;
+                    break;
+                }
+                rules.push(summarize_rule(rule));
+            }
+            cursor = next_cursor(&data);
+            if truncated || cursor.is_none() {
+                break;
+            }
+        }
+
+        let mut result = json!({
+            "success": true,
+            "total": rules.len(),
+            "truncated": truncated,
+            "rules": rules
+        });
+        if let Some((key, _)) = project {
+            result["project_key"] = json!(key);
+        }
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl ToolHandler for GetAutomationRuleHandler {
+    fn name(&self) -> &'static str {
+        "jira_get_automation_rule"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "rule_uuid".to_string(),
+            string_prop("Rule UUID (from jira_list_automation_rules)", true),
+        );
+        ToolSchema::new(
+            "Get a Jira automation rule: trigger, steps and full definition",
+            props,
+            vec!["rule_uuid".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let rule_uuid = args["rule_uuid"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing rule_uuid"))?;
+
+        let client = create_atlassian_client(config);
+        let cloud_id = fetch_cloud_id(&client, config).await?;
+        let response = client
+            .get(rules_url(&cloud_id, &format!("rule/{}", rule_uuid)))
+            .header("Authorization", create_auth_header(config))
+            .header("Accept", "application/json")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to get automation rule: {}", response.status());
+        }
+        let data: Value = response.json().await?;
+        let rule = &data["rule"];
+
+        let mut steps = Vec::new();
+        component_types(&rule["components"], &mut steps);
+        Ok(json!({
+            "success": true,
+            "rule": summarize_rule(rule),
+            "trigger": rule["trigger"]["type"],
+            "steps": steps,
+            "definition": rule
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_applies_to_project_and_summary() {
+        let project = json!({"uuid": "r1", "name": "Close stale", "state": "ENABLED",
+            "ruleScopeARIs": ["ari:cloud:jira:c1:project/10001", "ari:cloud:jira:c1:project/10002"]});
+        let global = json!({"uuid": "r2", "ruleScopeARIs": ["ari:cloud:jira::site/c1"]});
+        assert!(applies_to_project(&project, "10002"));
+        assert!(!applies_to_project(&project, "1000"));
+        assert!(applies_to_project(&global, "10003"));
+        assert!(!applies_to_project(&json!({}), "10001"));
+
+        assert_eq!(summarize_rule(&global)["scopes"][0]["type"], "global");
+        assert_eq!(summarize_rule(&project)["scopes"][1]["type"], "project");
+    }
+
+    #[test]
+    fn test_next_cursor_and_component_types() {
+        assert_eq!(
+            next_cursor(&json!({"links": {"next": "?limit=100&cursor=abc%3D"}})),
+            Some("abc=".to_string())
+        );
+        assert_eq!(next_cursor(&json!({"links": {}})), None);
+
+        let components = json!([
+            {"component": "CONDITION", "type": "jira.issue.condition"},
+            {"component": "BRANCH", "type": "jira.issue.related", "children": [
+                {"component": "ACTION", "type": "jira.issue.transition"}
+            ]}
+        ]);
+        let mut steps = Vec::new();
+        component_types(&components, &mut steps);
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[2]["type"], "jira.issue.transition");
+    }
+}
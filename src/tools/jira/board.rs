@@ -0,0 +1,329 @@
+//! Board snapshot for stand-up summaries
+//!
+//! `jira_get_board_snapshot` reads the board configuration (columns and the
+//! statuses mapped to them), fetches the board's issues through the Agile API
+//! and groups them by column. Scrum boards are limited to open sprints.
+//! Issues whose status is not mapped to any column are hidden on the board
+//! itself, so they are only counted.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema, number_prop, string_prop};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+
+use super::tabular::OutputFormat;
+
+pub struct GetBoardSnapshotHandler;
+
+const SNAPSHOT_FIELDS: &str = "summary,status,assignee,issuetype,priority";
+const BOARD_PAGE_SIZE: u64 = 100;
+const DEFAULT_MAX_ISSUES: u64 = 200;
+const MAX_ISSUES_CAP: u64 = 500;
+
+/// `(column name, status ids)` from a board configuration response
+pub fn parse_columns(configuration: &Value) -> Vec<(String, Vec<String>)> {
+    configuration["columnConfig"]["columns"]
+        .as_array()
+        .map(|columns| {
+            columns
+                .iter()
+                .map(|column| {
+                    let statuses = column["statuses"]
+                        .as_array()
+                        .map(|s| {
+                            s.iter()
+                                .filter_map(|status| status["id"].as_str().map(String::from))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    (
+                        column["name"].as_str().unwrap_or_default().to_string(),
+                        statuses,
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn card(issue: &Value) -> Value {
+    let fields = &issue["fields"];
+    json!({
+        "key": issue["key"],
+        "summary": fields["summary"],
+        "status": fields["status"]["name"],
+        "type": fields["issuetype"]["name"],
+        "priority": fields["priority"]["name"],
+        "assignee": fields["assignee"]["displayName"]
+    })
+}
+
+/// Column objects in board order, plus the number of issues in no column
+pub fn group_by_column(columns: &[(String, Vec<String>)], issues: &[Value]) -> (Vec<Value>, usize) {
+    let column_of: HashMap<&str, usize> = columns
+        .iter()
+        .enumerate()
+        .flat_map(|(i, (_, statuses))| statuses.iter().map(move |s| (s.as_str(), i)))
+        .collect();
+
+    let mut cards: Vec<Vec<Value>> = vec![Vec::new(); columns.len()];
+    let mut unmapped = 0;
+    for issue in issues {
+        match issue["fields"]["status"]["id"]
+            .as_str()
+            .and_then(|id| column_of.get(id))
+        {
+            Some(&i) => cards[i].push(card(issue)),
+            None => unmapped += 1,
+        }
+    }
+
+    let grouped = columns
+        .iter()
+        .zip(cards)
+        .map(|((name, _), issues)| {
+            json!({
+                "name": name,
+                "count": issues.len(),
+                "issues": issues
+            })
+        })
+        .collect();
+    (grouped, unmapped)
+}
+
+/// Kanban overview: one heading per column, one line per issue
+pub fn render_markdown(board_name: &str, columns: &[Value], unmapped: usize) -> String {
+    let mut out = format!("# {}\n", board_name);
+    for column in columns {
+        out.push_str(&format!(
+            "\n## {} ({})\n",
+            column["name"].as_str().unwrap_or_default(),
+            column["count"]
+        ));
+        for issue in column["issues"].as_array().into_iter().flatten() {
+            out.push_str(&format!(
+                "- {} {}",
+                issue["key"].as_str().unwrap_or_default(),
+                issue["summary"].as_str().unwrap_or_default()
+            ));
+            if let Some(assignee) = issue["assignee"].as_str() {
+                out.push_str(&format!(" (@{})", assignee));
+            }
+            out.push('\n');
+        }
+    }
+    if unmapped > 0 {
+        out.push_str(&format!("\n{} issues in unmapped statuses\n", unmapped));
+    }
+    out
+}
+
+/// The board's configuration: name, type, `filter`, `columnConfig`
+pub async fn board_configuration(
+    client: &reqwest::Client,
+    config: &Config,
+    board_id: u64,
+) -> Result<Value> {
+    let url = format!(
+        "{}/rest/agile/1.0/board/{}/configuration",
+        config.get_atlassian_base_url(),
+        board_id
+    );
+    get_json(client, config, &url).await
+}
+
+async fn get_json(client: &reqwest::Client, config: &Config, url: &str) -> Result<Value> {
+    let response = client
+        .get(url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get board: {}", response.status());
+    }
+    Ok(response.json().await?)
+}
+
+#[async_trait]
+impl ToolHandler for GetBoardSnapshotHandler {
+    fn name(&self) -> &'static str {
+        "jira_get_board_snapshot"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "board_id".to_string(),
+            number_prop("Agile board ID (from the board URL)", 0),
+        );
+        props.insert(
+            "jql".to_string(),
+            string_prop(
+                "Extra JQL to narrow the issues (e.g., 'assignee = currentUser()')",
+                false,
+            ),
+        );
+        props.insert(
+            "max_issues".to_string(),
+            number_prop("Max issues across all columns (capped at 500)", 200),
+        );
+        props.insert("format".to_string(), Property::new("string").with_description("Output format: 'json' (default) or 'table' (Markdown kanban overview: one heading per column, one line per issue)").with_default(json!("json")).with_enum(vec![json!("json"), json!("table")]));
+        ToolSchema::new(
+            "Snapshot of a board's columns and the issues in each (open sprints only on scrum boards), for stand-up summaries",
+            props,
+            vec!["board_id".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let board_id = args["board_id"]
+            .as_u64()
+            .or_else(|| args["board_id"].as_str().and_then(|s| s.parse().ok()))
+            .ok_or_else(|| anyhow::anyhow!("Missing board_id"))?;
+        let max_issues = args["max_issues"]
+            .as_u64()
+            .unwrap_or(DEFAULT_MAX_ISSUES)
+            .clamp(1, MAX_ISSUES_CAP);
+        let output_format = OutputFormat::from_arg(&args["format"])?;
+
+        let client = create_atlassian_client(config);
+        let board_url = format!(
+            "{}/rest/agile/1.0/board/{}",
+            config.get_atlassian_base_url(),
+            board_id
+        );
+
+        let configuration = board_configuration(&client, config, board_id).await?;
+        let columns = parse_columns(&configuration);
+        let board_type = configuration["type"].as_str().unwrap_or("kanban");
+
+        let mut clauses = Vec::new();
+        if board_type == "scrum" {
+            clauses.push("sprint in openSprints()".to_string());
+        }
+        if let Some(jql) = args["jql"].as_str().filter(|j| !j.trim().is_empty()) {
+            clauses.push(format!("({})", jql));
+        }
+        let jql = clauses.join(" AND ");
+
+        let mut issues: Vec<Value> = Vec::new();
+        let total = loop {
+            let page_size = (max_issues - issues.len() as u64).min(BOARD_PAGE_SIZE);
+            let response = client
+                .get(format!("{}/issue", board_url))
+                .header("Authorization", create_auth_header(config))
+                .header("Accept", "application/json")
+                .query(&[
+                    ("jql", jql.as_str()),
+                    ("fields", SNAPSHOT_FIELDS),
+                    ("startAt", &issues.len().to_string()),
+                    ("maxResults", &page_size.to_string()),
+                ])
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                let error = response.text().await?;
+                anyhow::bail!("Failed to get board issues: {}", error);
+            }
+
+            let data: Value = response.json().await?;
+            let total = data["total"].as_u64().unwrap_or(0);
+            let page = data["issues"].as_array().cloned().unwrap_or_default();
+            let exhausted = page.is_empty();
+            issues.extend(page);
+            if exhausted || issues.len() as u64 >= total.min(max_issues) {
+                break total;
+            }
+        };
+
+        let (grouped, unmapped) = group_by_column(&columns, &issues);
+        let name = configuration["name"].as_str().unwrap_or_default();
+
+        if output_format == OutputFormat::Table {
+            return Ok(Value::String(render_markdown(name, &grouped, unmapped)));
+        }
+
+        Ok(json!({
+            "success": true,
+            "board": {"id": board_id, "name": name, "type": board_type},
+            "total": total,
+            "truncated": total > issues.len() as u64,
+            "unmapped": unmapped,
+            "columns": grouped
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn configuration() -> Value {
+        json!({
+            "name": "Team board",
+            "type": "scrum",
+            "columnConfig": {"columns": [
+                {"name": "To Do", "statuses": [{"id": "1"}]},
+                {"name": "In Progress", "statuses": [{"id": "3"}, {"id": "4"}]},
+                {"name": "Done", "statuses": [{"id": "10001"}]}
+            ]}
+        })
+    }
+
+    fn issue(key: &str, status_id: &str, assignee: Option<&str>) -> Value {
+        json!({
+            "key": key,
+            "fields": {
+                "summary": format!("{} summary", key),
+                "status": {"id": status_id, "name": "Status"},
+                "assignee": assignee.map(|a| json!({"displayName": a}))
+            }
+        })
+    }
+
+    #[test]
+    fn test_parse_columns() {
+        let columns = parse_columns(&configuration());
+        assert_eq!(columns.len(), 3);
+        assert_eq!(
+            columns[1],
+            (
+                "In Progress".to_string(),
+                vec!["3".to_string(), "4".to_string()]
+            )
+        );
+        assert!(parse_columns(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_group_by_column_and_render() {
+        let columns = parse_columns(&configuration());
+        let issues = vec![
+            issue("PROJ-1", "1", None),
+            issue("PROJ-2", "4", Some("Jane")),
+            issue("PROJ-3", "3", None),
+            issue("PROJ-4", "999", None),
+        ];
+        let (grouped, unmapped) = group_by_column(&columns, &issues);
+        assert_eq!(unmapped, 1);
+        assert_eq!(grouped[0]["count"], 1);
+        assert_eq!(grouped[1]["count"], 2);
+        assert_eq!(grouped[1]["issues"][0]["assignee"], "Jane");
+        assert_eq!(grouped[2]["count"], 0);
+
+        let markdown = render_markdown("Team board", &grouped, unmapped);
+        assert!(markdown.starts_with("# Team board\n"));
+        assert!(markdown.contains("## In Progress (2)\n- PROJ-2 PROJ-2 summary (@Jane)\n"));
+        assert!(markdown.contains("## Done (0)\n"));
+        assert!(markdown.ends_with("1 issues in unmapped statuses\n"));
+    }
+}
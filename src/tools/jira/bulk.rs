@@ -0,0 +1,540 @@
+//! Bulk operations over JQL results
+//!
+//! The matching issues are collected first (at most `max_issues`, hard-capped
+//! at `BULK_MAX_ISSUES_CAP`), then processed `BULK_CONCURRENCY` at a time.
+//! A failure on one issue is recorded and the rest carry on; the response
+//! lists every issue with its outcome. `dry_run` resolves what would happen
+//! without writing anything.
+//!
+//! `jira_bulk_update` is stricter: a call without `confirm` only previews the
+//! matched keys, and the edit runs only when `confirm` equals the number of
+//! matched issues. It works in batches of `BULK_BATCH_SIZE`, sending
+//! `notifications/progress` after each when the client supplied a progress
+//! token, and backs off on 429 responses using `Retry-After`.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::mcp::notifications::{Notifier, PROGRESS_TOKEN_ARG};
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema, number_prop, string_prop};
+use crate::tools::write_policy;
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+use super::{SEARCH_PAGE_SIZE, apply_projects_filter, search_page};
+
+pub struct BulkTransitionHandler;
+
+pub struct BulkUpdateHandler {
+    notifier: Notifier,
+}
+
+impl BulkUpdateHandler {
+    pub fn new(notifier: Notifier) -> Self {
+        Self { notifier }
+    }
+}
+
+pub const DEFAULT_BULK_MAX_ISSUES: usize = 50;
+pub const BULK_MAX_ISSUES_CAP: usize = 500;
+/// Issues processed in parallel
+pub const BULK_CONCURRENCY: usize = 4;
+/// Issues per progress step of `jira_bulk_update`
+pub const BULK_BATCH_SIZE: usize = 20;
+/// Attempts per issue when Jira answers 429
+const RATE_LIMIT_ATTEMPTS: u32 = 4;
+/// Wait when a 429 carries no usable `Retry-After`
+const DEFAULT_RETRY_AFTER_SECS: u64 = 2;
+const MAX_RETRY_AFTER_SECS: u64 = 30;
+
+/// `max_issues` argument clamped to 1..=`BULK_MAX_ISSUES_CAP`
+pub fn max_issues(args: &Value) -> usize {
+    args["max_issues"]
+        .as_u64()
+        .map(|n| (n as usize).clamp(1, BULK_MAX_ISSUES_CAP))
+        .unwrap_or(DEFAULT_BULK_MAX_ISSUES)
+}
+
+/// Keys and statuses of the issues matching `jql` (scoped to
+/// `JIRA_PROJECTS_FILTER` and `WRITE_ALLOWED_PROJECTS`); errors instead of silently processing a prefix
+/// when more than `max` issues match
+async fn matching_issues(
+    client: &reqwest::Client,
+    config: &Config,
+    jql: &str,
+    max: usize,
+) -> Result<Vec<Value>> {
+    let jql = write_policy::scope_jql(&apply_projects_filter(jql, config), config);
+    let fields = vec!["summary".to_string(), "status".to_string()];
+    let mut issues = Vec::new();
+    let mut token: Option<String> = None;
+
+    loop {
+        // One extra result tells "exactly max" from "more than max"
+        let page_size = (max + 1 - issues.len()).min(SEARCH_PAGE_SIZE) as u64;
+        let data = search_page(client, config, &jql, &fields, page_size, token.as_deref()).await?;
+        issues.extend(data["issues"].as_array().cloned().unwrap_or_default());
+        token = data["nextPageToken"].as_str().map(String::from);
+
+        if issues.len() > max {
+            anyhow::bail!(
+                "JQL matches more than max_issues ({}) issues; narrow the query or raise max_issues (up to {})",
+                max,
+                BULK_MAX_ISSUES_CAP
+            );
+        }
+        if data["isLast"].as_bool().unwrap_or(true) || token.is_none() {
+            return Ok(issues);
+        }
+    }
+}
+
+/// Runs `op` for every issue, `BULK_CONCURRENCY` at a time, keeping input
+/// order; an `Err` becomes a `failed` entry for that issue
+pub async fn for_each_issue<F, Fut>(issues: Vec<Value>, op: F) -> Vec<Value>
+where
+    F: Fn(Value) -> Fut,
+    Fut: Future<Output = Result<Value>> + Send + 'static,
+{
+    let mut results = Vec::with_capacity(issues.len());
+    for chunk in issues.chunks(BULK_CONCURRENCY) {
+        let tasks: Vec<_> = chunk
+            .iter()
+            .map(|issue| (issue["key"].clone(), tokio::spawn(op(issue.clone()))))
+            .collect();
+        for (key, task) in tasks {
+            let outcome = match task.await {
+                Ok(outcome) => outcome,
+                Err(e) => Err(e.into()),
+            };
+            results.push(match outcome {
+                Ok(result) => result,
+                Err(e) => json!({"key": key, "outcome": "failed", "error": e.to_string()}),
+            });
+        }
+    }
+    results
+}
+
+/// Outcome counts over `for_each_issue` results
+pub fn summarize(results: &[Value]) -> Value {
+    let count = |outcome: &str| {
+        results
+            .iter()
+            .filter(|r| r["outcome"].as_str() == Some(outcome))
+            .count()
+    };
+    json!({
+        "matched": results.len(),
+        "updated": count("updated"),
+        "would_update": count("would_update"),
+        "skipped": count("skipped"),
+        "failed": count("failed")
+    })
+}
+
+/// Transition whose name or target status matches `target` (case-insensitive), or whose ID equals it
+pub fn find_transition<'a>(transitions: &'a Value, target: &str) -> Option<&'a Value> {
+    transitions.as_array()?.iter().find(|t| {
+        t["id"].as_str() == Some(target)
+            || t["name"]
+                .as_str()
+                .is_some_and(|n| n.eq_ignore_ascii_case(target))
+            || t["to"]["name"]
+                .as_str()
+                .is_some_and(|n| n.eq_ignore_ascii_case(target))
+    })
+}
+
+async fn transition_one(
+    client: reqwest::Client,
+    config: Arc<Config>,
+    issue: Value,
+    target: Arc<str>,
+    dry_run: bool,
+) -> Result<Value> {
+    let key = issue["key"].as_str().unwrap_or_default().to_string();
+    let from = issue["fields"]["status"]["name"].clone();
+
+    if from
+        .as_str()
+        .is_some_and(|s| s.eq_ignore_ascii_case(&target))
+    {
+        return Ok(
+            json!({"key": key, "outcome": "skipped", "reason": "already in target status", "from": from}),
+        );
+    }
+
+    let url = format!(
+        "{}/rest/api/3/issue/{}/transitions",
+        config.get_atlassian_base_url(),
+        key
+    );
+    let response = client
+        .get(&url)
+        .header("Authorization", create_auth_header(&config))
+        .header("Accept", "application/json")
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get transitions: {}", response.status());
+    }
+    let data: Value = response.json().await?;
+
+    let Some(transition) = find_transition(&data["transitions"], &target) else {
+        return Ok(json!({
+            "key": key,
+            "outcome": "skipped",
+            "reason": format!("no transition to '{}' from this status", target),
+            "from": from
+        }));
+    };
+    let to = transition["to"]["name"].clone();
+
+    if dry_run {
+        return Ok(json!({"key": key, "outcome": "would_update", "from": from, "to": to}));
+    }
+
+    let response = client
+        .post(&url)
+        .header("Authorization", create_auth_header(&config))
+        .header("Content-Type", "application/json")
+        .json(&json!({"transition": {"id": transition["id"]}}))
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to transition issue: {}", response.status());
+    }
+    Ok(json!({"key": key, "outcome": "updated", "from": from, "to": to}))
+}
+
+#[async_trait]
+impl ToolHandler for BulkTransitionHandler {
+    fn name(&self) -> &'static str {
+        "jira_bulk_transition"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "jql".to_string(),
+            string_prop(
+                "JQL selecting the issues to transition (scoped to JIRA_PROJECTS_FILTER)",
+                true,
+            ),
+        );
+        props.insert(
+            "transition".to_string(),
+            string_prop(
+                "Transition name, target status name or transition ID (e.g., 'Done')",
+                true,
+            ),
+        );
+        props.insert(
+            "dry_run".to_string(),
+            Property::new("boolean")
+                .with_description(
+                    "Resolve the transition for every issue without applying it".to_string(),
+                )
+                .with_default(json!(false)),
+        );
+        props.insert(
+            "max_issues".to_string(),
+            number_prop("Refuse to run when more issues match (capped at 500)", 50),
+        );
+        ToolSchema::new(
+            "Transition every issue matching a JQL query (4 at a time); per-issue outcomes and errors are reported, issues already in the target status are skipped",
+            props,
+            vec!["jql".to_string(), "transition".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let jql = args["jql"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing jql"))?;
+        let target: Arc<str> = args["transition"]
+            .as_str()
+            .filter(|t| !t.trim().is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing transition"))?
+            .trim()
+            .into();
+        let dry_run = args["dry_run"].as_bool().unwrap_or(false);
+
+        let client = create_atlassian_client(config);
+        let issues = matching_issues(&client, config, jql, max_issues(&args)).await?;
+
+        let shared = Arc::new(config.clone());
+        let results = for_each_issue(issues, |issue| {
+            transition_one(
+                client.clone(),
+                shared.clone(),
+                issue,
+                target.clone(),
+                dry_run,
+            )
+        })
+        .await;
+
+        Ok(json!({
+            "success": true,
+            "dry_run": dry_run,
+            "transition": &*target,
+            "summary": summarize(&results),
+            "issues": results
+        }))
+    }
+}
+
+/// Seconds to wait from a `Retry-After` header, bounded to `MAX_RETRY_AFTER_SECS`
+pub fn retry_after_secs(header: Option<&str>) -> u64 {
+    header
+        .and_then(|h| h.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_RETRY_AFTER_SECS)
+        .min(MAX_RETRY_AFTER_SECS)
+}
+
+/// Checks the `fields`/`update` payload and the confirmation count
+pub fn update_payload(args: &Value) -> Result<Value> {
+    let mut payload = json!({});
+    for key in ["fields", "update"] {
+        match &args[key] {
+            Value::Null => {}
+            Value::Object(map) if !map.is_empty() => payload[key] = args[key].clone(),
+            _ => anyhow::bail!("{} must be a non-empty object", key),
+        }
+    }
+    if payload.as_object().is_some_and(|p| p.is_empty()) {
+        anyhow::bail!("Provide fields and/or update");
+    }
+    Ok(payload)
+}
+
+async fn update_one(
+    client: reqwest::Client,
+    config: Arc<Config>,
+    issue: Value,
+    payload: Arc<Value>,
+) -> Result<Value> {
+    let key = issue["key"].as_str().unwrap_or_default().to_string();
+    let url = format!(
+        "{}/rest/api/3/issue/{}",
+        config.get_atlassian_base_url(),
+        key
+    );
+
+    let mut attempt = 1;
+    loop {
+        let response = client
+            .put(&url)
+            .header("Authorization", create_auth_header(&config))
+            .header("Content-Type", "application/json")
+            .json(&*payload)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(json!({"key": key, "outcome": "updated", "attempts": attempt}));
+        }
+        if status.as_u16() == 429 && attempt < RATE_LIMIT_ATTEMPTS {
+            let wait = retry_after_secs(
+                response
+                    .headers()
+                    .get("Retry-After")
+                    .and_then(|v| v.to_str().ok()),
+            );
+            tracing::warn!(issue = %key, wait, "Rate limited by Jira; retrying");
+            tokio::time::sleep(Duration::from_secs(wait)).await;
+            attempt += 1;
+            continue;
+        }
+        let error = response.text().await?;
+        anyhow::bail!("Failed to update issue ({}): {}", status, error);
+    }
+}
+
+#[async_trait]
+impl ToolHandler for BulkUpdateHandler {
+    fn name(&self) -> &'static str {
+        "jira_bulk_update"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "jql".to_string(),
+            string_prop(
+                "JQL selecting the issues to edit (scoped to JIRA_PROJECTS_FILTER)",
+                true,
+            ),
+        );
+        props.insert("fields".to_string(), Property::new("object").with_description("Field values to set, as in the issue edit API (e.g., {\"assignee\": {\"accountId\": \"...\"}})"));
+        props.insert("update".to_string(), Property::new("object").with_description("Field operations, as in the issue edit API (e.g., {\"labels\": [{\"add\": \"triaged\"}], \"fixVersions\": [{\"set\": [{\"name\": \"1.2\"}]}]})"));
+        props.insert(
+            "confirm".to_string(),
+            number_prop(
+                "Number of matched issues from a preview call; without it nothing is changed",
+                0,
+            ),
+        );
+        props.insert(
+            "max_issues".to_string(),
+            number_prop("Refuse to run when more issues match (capped at 500)", 50),
+        );
+        ToolSchema::new(
+            "Apply a fields/update payload to every issue matching a JQL query. Call once without confirm to preview, then with confirm set to the matched count",
+            props,
+            vec!["jql".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let jql = args["jql"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing jql"))?;
+        let payload = Arc::new(update_payload(&args)?);
+
+        let client = create_atlassian_client(config);
+        let issues = matching_issues(&client, config, jql, max_issues(&args)).await?;
+        let matched = issues.len();
+        let keys: Vec<Value> = issues.iter().map(|i| i["key"].clone()).collect();
+
+        match args["confirm"].as_u64() {
+            None => {
+                return Ok(json!({
+                    "success": true,
+                    "applied": false,
+                    "matched": matched,
+                    "issues": keys,
+                    "message": format!("Nothing changed. Re-run with confirm: {} to update these issues", matched)
+                }));
+            }
+            Some(n) if n as usize != matched => anyhow::bail!(
+                "confirm ({}) does not match the {} issues the JQL now matches; preview again without confirm",
+                n,
+                matched
+            ),
+            Some(_) => {}
+        }
+
+        let token = args.get(PROGRESS_TOKEN_ARG).cloned();
+        let shared = Arc::new(config.clone());
+        let mut results = Vec::with_capacity(matched);
+        for batch in issues.chunks(BULK_BATCH_SIZE) {
+            let outcomes = for_each_issue(batch.to_vec(), |issue| {
+                update_one(client.clone(), shared.clone(), issue, payload.clone())
+            })
+            .await;
+            results.extend(outcomes);
+
+            if let Some(token) = &token {
+                self.notifier.progress(
+                    token,
+                    results.len(),
+                    matched,
+                    &format!("Updated {} of {} issues", results.len(), matched),
+                );
+            }
+        }
+
+        Ok(json!({
+            "success": true,
+            "applied": true,
+            "summary": summarize(&results),
+            "issues": results
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_transition_by_name_status_or_id() {
+        let transitions = json!([
+            {"id": "11", "name": "Start work", "to": {"name": "In Progress"}},
+            {"id": "31", "name": "Resolve", "to": {"name": "Done"}}
+        ]);
+        assert_eq!(
+            find_transition(&transitions, "resolve").unwrap()["id"],
+            "31"
+        );
+        assert_eq!(
+            find_transition(&transitions, "in progress").unwrap()["id"],
+            "11"
+        );
+        assert_eq!(
+            find_transition(&transitions, "31").unwrap()["name"],
+            "Resolve"
+        );
+        assert!(find_transition(&transitions, "Closed").is_none());
+    }
+
+    #[test]
+    fn test_max_issues_clamped() {
+        assert_eq!(max_issues(&json!({})), DEFAULT_BULK_MAX_ISSUES);
+        assert_eq!(max_issues(&json!({"max_issues": 0})), 1);
+        assert_eq!(
+            max_issues(&json!({"max_issues": 10_000})),
+            BULK_MAX_ISSUES_CAP
+        );
+    }
+
+    #[tokio::test]
+    async fn test_for_each_issue_collects_failures() {
+        let issues: Vec<Value> = (1..=6)
+            .map(|i| json!({"key": format!("PROJ-{}", i)}))
+            .collect();
+        let results = for_each_issue(issues, |issue| async move {
+            let key = issue["key"].as_str().unwrap().to_string();
+            if key == "PROJ-3" {
+                anyhow::bail!("Failed to transition issue: 403 Forbidden");
+            }
+            Ok(json!({"key": key, "outcome": "updated"}))
+        })
+        .await;
+
+        assert_eq!(results.len(), 6);
+        assert_eq!(results[2]["key"], "PROJ-3");
+        assert_eq!(results[2]["outcome"], "failed");
+        assert_eq!(results[5]["key"], "PROJ-6");
+
+        let summary = summarize(&results);
+        assert_eq!(summary["updated"], 5);
+        assert_eq!(summary["failed"], 1);
+    }
+
+    #[test]
+    fn test_update_payload_validation() {
+        let payload = update_payload(&json!({
+            "fields": {"assignee": {"accountId": "abc"}},
+            "update": {"labels": [{"add": "triaged"}]}
+        }))
+        .unwrap();
+        assert_eq!(payload["update"]["labels"][0]["add"], "triaged");
+        assert!(payload["fields"]["assignee"].is_object());
+
+        assert!(update_payload(&json!({})).is_err());
+        assert!(update_payload(&json!({"fields": {}})).is_err());
+        assert!(update_payload(&json!({"update": ["labels"]})).is_err());
+    }
+
+    #[test]
+    fn test_retry_after_secs() {
+        assert_eq!(retry_after_secs(Some("5")), 5);
+        assert_eq!(retry_after_secs(Some("3600")), MAX_RETRY_AFTER_SECS);
+        assert_eq!(
+            retry_after_secs(Some("Wed, 21 Oct 2026 07:28:00 GMT")),
+            DEFAULT_RETRY_AFTER_SECS
+        );
+        assert_eq!(retry_after_secs(None), DEFAULT_RETRY_AFTER_SECS);
+    }
+}
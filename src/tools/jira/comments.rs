@@ -0,0 +1,487 @@
+//! Comment metadata, listing, replies and visibility
+//!
+//! Jira Cloud comments are flat; a reply is a new comment that mentions the
+//! parent's author and quotes the start of the parent body. Visibility
+//! restrictions are validated before the write: role names against the
+//! project's roles, so a typo fails with the valid choices instead of a 400.
+//!
+//! `jira_get_comments` pages through an issue's comments, newest first by
+//! default. The API cannot filter by date, so `since` is applied while
+//! paging; newest first, paging stops at the first older comment, so a long
+//! ticket's recent discussion costs one or two requests.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::client::Jira;
+use crate::client::models::Comment;
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::args::parse_args;
+use crate::tools::schema::{Property, ToolSchema, markdown_prop, number_prop, string_prop};
+use crate::utils::http_utils::create_auth_header;
+use crate::utils::time;
+use std::collections::HashMap;
+
+use super::adf_utils::adf_to_markdown;
+use super::mentions;
+use super::roles::parse_role_map;
+
+/// Parent blocks quoted in a reply
+const REPLY_QUOTE_BLOCKS: usize = 2;
+
+/// `jira_get_comments` results unless `max_results` says otherwise
+const DEFAULT_COMMENTS: usize = 20;
+/// Largest `max_results` (and page size) accepted
+const MAX_COMMENTS: usize = 100;
+
+pub struct GetCommentHandler;
+pub struct GetCommentsHandler;
+
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+enum CommentOrder {
+    #[default]
+    #[serde(rename = "-created")]
+    NewestFirst,
+    #[serde(rename = "created")]
+    OldestFirst,
+}
+
+#[derive(Deserialize)]
+struct CommentsArgs {
+    issue_key: String,
+    #[serde(default)]
+    order_by: CommentOrder,
+    since: Option<String>,
+    max_results: Option<usize>,
+    #[serde(default)]
+    start_at: usize,
+    #[serde(default)]
+    markdown: bool,
+}
+
+/// Author, timestamps and visibility of a comment response
+pub fn comment_summary(comment: &Comment) -> Value {
+    let author = comment.author.as_ref();
+    json!({
+        "comment_id": comment.id,
+        "author": {
+            "displayName": author.and_then(|a| a.display_name.as_deref()),
+            "accountId": author.and_then(|a| a.account_id.as_deref())
+        },
+        "created": comment.created,
+        "updated": comment.updated,
+        "visibility": comment.visibility
+    })
+}
+
+/// Validates the `visibility` argument: `{"type": "role"|"group", "value": name}`
+pub fn parse_visibility(value: &Value) -> Result<Option<(&str, &str)>> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    let kind = value["type"].as_str().unwrap_or_default();
+    if kind != "role" && kind != "group" {
+        anyhow::bail!(
+            "Invalid visibility type '{}': expected 'role' or 'group'",
+            kind
+        );
+    }
+    let name = value["value"]
+        .as_str()
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| anyhow::anyhow!("Missing visibility value"))?;
+    Ok(Some((kind, name)))
+}
+
+/// Resolves `visibility` into the API shape, checking role names against
+/// the issue's project
+pub async fn resolve_visibility(
+    client: &reqwest::Client,
+    config: &Config,
+    issue_key: &str,
+    value: &Value,
+) -> Result<Option<Value>> {
+    let Some((kind, name)) = parse_visibility(value)? else {
+        return Ok(None);
+    };
+    if kind == "group" {
+        return Ok(Some(json!({ "type": "group", "value": name })));
+    }
+
+    let project_key = issue_key.split('-').next().unwrap_or(issue_key);
+    let url = format!(
+        "{}/rest/api/3/project/{}/role",
+        config.get_atlassian_base_url(),
+        project_key
+    );
+    let response = client
+        .get(&url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get project roles: {}", response.status());
+    }
+
+    let roles = parse_role_map(&response.json().await?);
+    let names: Vec<&str> = roles.iter().filter_map(|r| r["name"].as_str()).collect();
+    let role = names
+        .iter()
+        .find(|r| r.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Role '{}' not found in project {}; available roles: {}",
+                name,
+                project_key,
+                names.join(", ")
+            )
+        })?;
+    Ok(Some(json!({ "type": "role", "value": role })))
+}
+
+/// Prefixes `body` with a mention of the parent's author and a quote of its
+/// first paragraphs
+pub fn reply_adf(parent: &Comment, mut body: Value) -> Value {
+    let author = parent.author.clone().unwrap_or_default();
+    let mut intro = vec![json!({ "type": "text", "text": "Replying to " })];
+    match author.account_id {
+        Some(account_id) => intro.push(json!({
+            "type": "mention",
+            "attrs": {
+                "id": account_id,
+                "text": format!("@{}", author.display_name.unwrap_or_default())
+            }
+        })),
+        None => intro.push(json!({ "type": "text", "text": "comment" })),
+    }
+    intro.push(json!({ "type": "text", "text": ":" }));
+
+    let quoted: Vec<Value> = parent.body["content"]
+        .as_array()
+        .map(|blocks| {
+            blocks
+                .iter()
+                .filter(|b| b["type"] == "paragraph")
+                .take(REPLY_QUOTE_BLOCKS)
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut content = vec![json!({ "type": "paragraph", "content": intro })];
+    if !quoted.is_empty() {
+        content.push(json!({ "type": "blockquote", "content": quoted }));
+    }
+    if let Some(blocks) = body["content"].as_array_mut() {
+        content.append(blocks);
+    }
+    body["content"] = Value::Array(content);
+    body
+}
+
+#[async_trait]
+impl ToolHandler for GetCommentHandler {
+    fn name(&self) -> &'static str {
+        "jira_get_comment"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("issue_key".to_string(), string_prop("Issue key", true));
+        props.insert("comment_id".to_string(), string_prop("Comment ID", true));
+        props.insert(
+            "markdown".to_string(),
+            markdown_prop("Return the body as Markdown instead of ADF"),
+        );
+        ToolSchema::new(
+            "Get a single Jira comment with author, timestamps and visibility",
+            props,
+            vec!["issue_key".to_string(), "comment_id".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let issue_key = args["issue_key"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing issue_key"))?;
+        let comment_id = args["comment_id"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing comment_id"))?;
+
+        let jira = Jira::new(config);
+        let comment = jira.get_comment(issue_key, comment_id).await?;
+
+        let mut result = comment_summary(&comment);
+        result["success"] = json!(true);
+        result["body"] = if args["markdown"].as_bool().unwrap_or(false) {
+            json!(adf_to_markdown(&comment.body))
+        } else {
+            comment.body.clone()
+        };
+        mentions::attach(
+            jira.http(),
+            config,
+            &mut result,
+            &comment.body,
+            Some(issue_key),
+        )
+        .await;
+        Ok(result)
+    }
+}
+
+/// The instant a `since` argument means, read in the configured timezone
+pub fn parse_since(config: &Config, input: &str) -> Result<i64> {
+    let zone = time::user_timezone(config)?;
+    time::since_instant(input, &zone, time::now()).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid since '{}': expected a timestamp, a window such as '7d', or a date",
+            input
+        )
+    })
+}
+
+/// Created at or after `since` (unparseable timestamps are not)
+pub fn created_since(created: Option<&str>, since: i64) -> bool {
+    created
+        .and_then(time::parse_timestamp)
+        .is_some_and(|(created, _)| created >= since)
+}
+
+/// [`comment_summary`] with the body, as ADF or Markdown
+pub fn comment_entry(comment: &Comment, markdown: bool) -> Value {
+    let mut entry = comment_summary(comment);
+    entry["body"] = if markdown {
+        json!(adf_to_markdown(&comment.body))
+    } else {
+        comment.body.clone()
+    };
+    entry
+}
+
+/// Comments read by [`collect_comments`]
+pub struct CommentRun {
+    pub comments: Vec<Comment>,
+    /// Comments on the issue
+    pub total: usize,
+    /// Where to continue, when more may match
+    pub next_start_at: Option<usize>,
+}
+
+/// Up to `max_results` comments from `start_at` in the given order, only
+/// those created since `since` when set
+pub async fn collect_comments(
+    jira: &Jira<'_>,
+    issue_key: &str,
+    newest_first: bool,
+    since: Option<i64>,
+    start_at: usize,
+    max_results: usize,
+) -> Result<CommentRun> {
+    let mut comments = Vec::new();
+    let mut position = start_at;
+    let mut total = 0;
+    let mut exhausted = false;
+    while comments.len() < max_results && !exhausted {
+        let page = jira
+            .get_comments(issue_key, position, MAX_COMMENTS, newest_first)
+            .await?;
+        total = page.total().unwrap_or(0);
+        if page.comments.is_empty() {
+            exhausted = true;
+            break;
+        }
+        for comment in page.comments {
+            if comments.len() >= max_results {
+                break;
+            }
+            position += 1;
+            if since.is_none_or(|since| created_since(comment.created.as_deref(), since)) {
+                comments.push(comment);
+            } else if newest_first {
+                // Everything after this is older still
+                exhausted = true;
+                break;
+            }
+        }
+        exhausted |= position >= total;
+    }
+
+    Ok(CommentRun {
+        comments,
+        total,
+        next_start_at: (!exhausted && position < total).then_some(position),
+    })
+}
+
+#[async_trait]
+impl ToolHandler for GetCommentsHandler {
+    fn name(&self) -> &'static str {
+        "jira_get_comments"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("issue_key".to_string(), string_prop("Issue key", true));
+        props.insert(
+            "order_by".to_string(),
+            Property::new("string")
+                .with_description(
+                    "-created: newest first; created: oldest first (default: -created)",
+                )
+                .with_default(json!("-created"))
+                .with_enum(vec![json!("-created"), json!("created")]),
+        );
+        props.insert(
+            "since".to_string(),
+            string_prop(
+                "Only comments created at or after this: a timestamp, a window such as '24h' or '7d', or a day such as 'yesterday' or '2026-10-01'",
+                false,
+            ),
+        );
+        props.insert(
+            "max_results".to_string(),
+            number_prop("Comments to return (1-100)", DEFAULT_COMMENTS as i32)
+                .with_range(Some(1.0), Some(MAX_COMMENTS as f64)),
+        );
+        props.insert(
+            "start_at".to_string(),
+            number_prop(
+                "Position to continue from: the previous call's next_start_at",
+                0,
+            )
+            .with_range(Some(0.0), None),
+        );
+        props.insert(
+            "markdown".to_string(),
+            markdown_prop("Return bodies as Markdown instead of ADF"),
+        );
+        ToolSchema::new(
+            "List a Jira issue's comments, newest first, optionally only those since a time",
+            props,
+            vec!["issue_key".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let CommentsArgs {
+            issue_key,
+            order_by,
+            since,
+            max_results,
+            start_at,
+            markdown,
+        } = parse_args(&args)?;
+        let max_results = max_results
+            .unwrap_or(DEFAULT_COMMENTS)
+            .clamp(1, MAX_COMMENTS);
+        let since = since
+            .as_deref()
+            .map(|input| parse_since(config, input))
+            .transpose()?;
+
+        let jira = Jira::new(config);
+        let CommentRun {
+            comments,
+            total,
+            next_start_at,
+        } = collect_comments(
+            &jira,
+            &issue_key,
+            order_by == CommentOrder::NewestFirst,
+            since,
+            start_at,
+            max_results,
+        )
+        .await?;
+
+        let has_more = next_start_at.is_some();
+        let bodies: Vec<Value> = comments.iter().map(|c| c.body.clone()).collect();
+        let mut result = json!({
+            "success": true,
+            "issue_key": issue_key,
+            "total": total,
+            "returned": comments.len(),
+            "has_more": has_more,
+            "comments": comments
+                .iter()
+                .map(|comment| comment_entry(comment, markdown))
+                .collect::<Vec<_>>()
+        });
+        if let Some(next_start_at) = next_start_at {
+            result["next_start_at"] = json!(next_start_at);
+        }
+        mentions::attach(
+            jira.http(),
+            config,
+            &mut result,
+            &json!(bodies),
+            Some(&issue_key),
+        )
+        .await;
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::jira::adf_utils::text_to_adf;
+
+    #[test]
+    fn test_parse_visibility() {
+        assert_eq!(parse_visibility(&Value::Null).unwrap(), None);
+        assert_eq!(
+            parse_visibility(&json!({"type": "group", "value": "jira-admins"})).unwrap(),
+            Some(("group", "jira-admins"))
+        );
+        assert!(parse_visibility(&json!({"type": "user", "value": "x"})).is_err());
+        assert!(parse_visibility(&json!({"type": "role"})).is_err());
+    }
+
+    #[test]
+    fn test_comment_summary() {
+        let data = json!({
+            "id": "10001",
+            "author": {"displayName": "Jane", "accountId": "abc", "avatarUrls": {}},
+            "created": "2024-01-01T00:00:00.000+0000",
+            "updated": "2024-01-02T00:00:00.000+0000",
+            "visibility": {"type": "role", "value": "Developers"}
+        });
+        let summary = comment_summary(&serde_json::from_value(data).unwrap());
+        assert_eq!(summary["comment_id"], "10001");
+        assert_eq!(
+            summary["author"],
+            json!({"displayName": "Jane", "accountId": "abc"})
+        );
+        assert_eq!(summary["visibility"]["value"], "Developers");
+    }
+
+    #[test]
+    fn test_reply_adf() {
+        let parent = json!({
+            "author": {"displayName": "Jane", "accountId": "abc"},
+            "body": text_to_adf("Original question")
+        });
+        let reply = reply_adf(
+            &serde_json::from_value(parent).unwrap(),
+            text_to_adf("Answer"),
+        );
+        let content = reply["content"].as_array().unwrap();
+
+        assert_eq!(content.len(), 3);
+        assert_eq!(content[0]["content"][1]["type"], "mention");
+        assert_eq!(content[0]["content"][1]["attrs"]["text"], "@Jane");
+        assert_eq!(content[1]["type"], "blockquote");
+        assert_eq!(
+            content[1]["content"][0]["content"][0]["text"],
+            "Original question"
+        );
+        assert_eq!(content[2]["content"][0]["text"], "Answer");
+    }
+}
@@ -0,0 +1,244 @@
+//! What changed on an issue since a point in time
+//!
+//! `jira_issue_delta` answers "what happened since I last looked" in one
+//! call: the comments and changelog entries created since `since`, and per
+//! field its net change (the value before the first change and after the
+//! last). Comments are read newest first and the changelog from its last
+//! page back, so both stop at `since` and a quiet issue costs three small
+//! requests however long its history is.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+use crate::client::Jira;
+use crate::client::models::History;
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::args::parse_args;
+use crate::tools::schema::{ToolSchema, markdown_prop, string_prop};
+use crate::utils::time;
+
+use super::comments::{CommentRun, collect_comments, comment_entry, created_since, parse_since};
+use super::mentions;
+
+/// Comments, and changelog entries, returned per call
+pub const MAX_DELTA_ENTRIES: usize = 100;
+/// Changelog entries per request (the API's maximum)
+const CHANGELOG_PAGE: usize = 100;
+
+pub struct IssueDeltaHandler;
+
+#[derive(Deserialize)]
+struct DeltaArgs {
+    issue_key: String,
+    since: String,
+    #[serde(default)]
+    markdown: bool,
+}
+
+/// Changelog entries created since `since`, oldest first, at most
+/// `MAX_DELTA_ENTRIES` (the newest); `true` when older matching ones were
+/// left out
+async fn changes_since(
+    jira: &Jira<'_>,
+    issue_key: &str,
+    since: i64,
+) -> Result<(Vec<History>, bool)> {
+    let first = jira.get_changelog(issue_key, 0, CHANGELOG_PAGE).await?;
+    let total = first.total.max(first.values.len());
+    let mut found = Vec::new();
+    let mut page = Some(first).filter(|_| total <= CHANGELOG_PAGE);
+    let mut end = total;
+    while end > 0 {
+        let start = end.saturating_sub(CHANGELOG_PAGE);
+        let values = match page.take() {
+            Some(page) => page.values,
+            None => {
+                jira.get_changelog(issue_key, start, end - start)
+                    .await?
+                    .values
+            }
+        };
+        for history in values.into_iter().rev() {
+            if !created_since(history.created.as_deref(), since) {
+                found.reverse();
+                return Ok((found, false));
+            }
+            if found.len() == MAX_DELTA_ENTRIES {
+                found.reverse();
+                return Ok((found, true));
+            }
+            found.push(history);
+        }
+        end = start;
+    }
+    found.reverse();
+    Ok((found, false))
+}
+
+/// `{created, author, changes: [{field, from, to}]}` per entry
+fn compact_history(history: &History) -> Value {
+    json!({
+        "created": history.created,
+        "author": history.author.as_ref().and_then(|a| a.display_name.as_deref()),
+        "changes": history
+            .items
+            .iter()
+            .map(|item| json!({"field": item.field, "from": item.from_string, "to": item.to_string}))
+            .collect::<Vec<_>>()
+    })
+}
+
+/// Per field, in order of first change: the value before the first change,
+/// after the last, and how many changes there were. `reverted` marks fields
+/// that ended where they started.
+pub fn net_field_changes(histories: &[History]) -> Vec<Value> {
+    let mut fields: Vec<(&str, Option<&str>, Option<&str>, usize)> = Vec::new();
+    for item in histories.iter().flat_map(|history| &history.items) {
+        let to = item.to_string.as_deref();
+        match fields.iter_mut().find(|(field, ..)| *field == item.field) {
+            Some(entry) => {
+                entry.2 = to;
+                entry.3 += 1;
+            }
+            None => fields.push((&item.field, item.from_string.as_deref(), to, 1)),
+        }
+    }
+    fields
+        .into_iter()
+        .map(|(field, from, to, changes)| {
+            let mut entry = json!({"field": field, "from": from, "to": to, "changes": changes});
+            if changes > 1 && from == to {
+                entry["reverted"] = json!(true);
+            }
+            entry
+        })
+        .collect()
+}
+
+#[async_trait]
+impl ToolHandler for IssueDeltaHandler {
+    fn name(&self) -> &'static str {
+        "jira_issue_delta"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("issue_key".to_string(), string_prop("Issue key", true));
+        props.insert(
+            "since".to_string(),
+            string_prop(
+                "When you last looked: a timestamp, a window such as '24h' or '7d', or a day such as 'yesterday'",
+                true,
+            ),
+        );
+        props.insert(
+            "markdown".to_string(),
+            markdown_prop("Return comment bodies as Markdown instead of ADF"),
+        );
+        ToolSchema::new(
+            "What changed on a Jira issue since a time: new comments, changelog entries and the net field changes",
+            props,
+            vec!["issue_key".to_string(), "since".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let DeltaArgs {
+            issue_key,
+            since,
+            markdown,
+        } = parse_args(&args)?;
+        let since_at = parse_since(config, &since)?;
+
+        let jira = Jira::new(config);
+        let (issue, comments, changes) = tokio::join!(
+            jira.get_issue(&issue_key),
+            collect_comments(
+                &jira,
+                &issue_key,
+                true,
+                Some(since_at),
+                0,
+                MAX_DELTA_ENTRIES
+            ),
+            changes_since(&jira, &issue_key, since_at)
+        );
+        let issue = issue?;
+        let CommentRun {
+            mut comments,
+            next_start_at,
+            ..
+        } = comments?;
+        let (histories, changes_truncated) = changes?;
+        comments.reverse();
+
+        let bodies: Vec<Value> = comments.iter().map(|c| c.body.clone()).collect();
+        let zone = time::user_timezone(config)?;
+        let mut result = json!({
+            "success": true,
+            "issue_key": issue.key,
+            "summary": issue.fields.summary,
+            "status": issue.fields.status.as_ref().map(|s| s.name.as_str()),
+            "updated": issue.fields.updated,
+            "since": time::format_timestamp(since_at, 0, &zone),
+            "changed": !comments.is_empty() || !histories.is_empty(),
+            "field_changes": net_field_changes(&histories),
+            "comments": comments
+                .iter()
+                .map(|comment| comment_entry(comment, markdown))
+                .collect::<Vec<_>>(),
+            "changelog": histories.iter().map(compact_history).collect::<Vec<_>>()
+        });
+        if next_start_at.is_some() || changes_truncated {
+            result["truncated"] = json!(true);
+        }
+        mentions::attach(
+            jira.http(),
+            config,
+            &mut result,
+            &json!(bodies),
+            Some(&issue_key),
+        )
+        .await;
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(items: &[(&str, &str, &str)]) -> History {
+        serde_json::from_value(json!({
+            "id": "1",
+            "created": "2026-10-14T09:00:00.000+0000",
+            "items": items
+                .iter()
+                .map(|(field, from, to)| json!({"field": field, "fromString": from, "toString": to}))
+                .collect::<Vec<_>>()
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_net_field_changes() {
+        let histories = vec![
+            history(&[("status", "To Do", "In Progress"), ("assignee", "", "Jane")]),
+            history(&[("status", "In Progress", "In Review")]),
+            history(&[("priority", "High", "Low")]),
+            history(&[("priority", "Low", "High")]),
+        ];
+        assert_eq!(
+            net_field_changes(&histories),
+            vec![
+                json!({"field": "status", "from": "To Do", "to": "In Review", "changes": 2}),
+                json!({"field": "assignee", "from": "", "to": "Jane", "changes": 1}),
+                json!({"field": "priority", "from": "High", "to": "High", "changes": 2, "reverted": true}),
+            ]
+        );
+    }
+}
@@ -0,0 +1,212 @@
+//! Site-specific examples for the `jira_search` schema (`JIRA_SCHEMA_EXAMPLES`)
+//!
+//! Generic JQL examples (`project = KEY`) leave the model guessing project
+//! keys and status names, and a wrong guess is a 400. With the option on,
+//! [`discover`] reads a few project keys (the `JIRA_PROJECTS_FILTER` ones,
+//! else the most recently updated) and the site's status names once per site
+//! when the handler is built, and [`search_examples`] turns them into a
+//! sentence for the tool description. Reloads reuse the cached values; a
+//! failed lookup is logged and the description stays generic.
+
+use serde_json::Value;
+use std::sync::Mutex;
+
+use crate::client::rest;
+use crate::config::Config;
+use crate::utils::http_utils::create_atlassian_client;
+
+/// Project keys and statuses named in the description
+const MAX_PROJECTS: usize = 5;
+const MAX_STATUSES: usize = 8;
+
+struct SiteExamples {
+    projects: Vec<String>,
+    /// Status name and category key (`new`, `indeterminate`, `done`)
+    statuses: Vec<(String, String)>,
+}
+
+/// Per site base URL
+static EXAMPLES: Mutex<Vec<(String, SiteExamples)>> = Mutex::new(Vec::new());
+
+/// Distinct status names, to do first and done last, as the site lists them
+/// within a category
+fn status_names(statuses: &Value) -> Vec<(String, String)> {
+    let mut names: Vec<(String, String)> = Vec::new();
+    for status in statuses.as_array().into_iter().flatten() {
+        let Some(name) = status["name"].as_str() else {
+            continue;
+        };
+        if names
+            .iter()
+            .any(|(seen, _)| seen.eq_ignore_ascii_case(name))
+        {
+            continue;
+        }
+        let category = status["statusCategory"]["key"].as_str().unwrap_or("new");
+        names.push((name.to_string(), category.to_string()));
+    }
+    let rank = |category: &str| match category {
+        "new" => 0,
+        "indeterminate" => 1,
+        _ => 2,
+    };
+    names.sort_by_key(|(_, category)| rank(category));
+    names.truncate(MAX_STATUSES);
+    names
+}
+
+/// Looks up the examples for the configured site unless they are cached
+pub async fn discover(config: &Config) {
+    let base_url = config.get_atlassian_base_url();
+    let cached = EXAMPLES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .any(|(site, _)| site == base_url);
+    if cached {
+        return;
+    }
+
+    let client = create_atlassian_client(config);
+    let projects = async {
+        if !config.jira_projects_filter.is_empty() {
+            return Ok(config.jira_projects_filter.clone());
+        }
+        let url = format!("{}/rest/api/3/project/search", base_url);
+        let query = [
+            ("maxResults", MAX_PROJECTS.to_string()),
+            ("orderBy", "-lastIssueUpdatedDate".to_string()),
+        ];
+        let data = rest::get_json(&client, config, &url, &query, "projects").await?;
+        Ok::<_, anyhow::Error>(
+            data["values"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|p| p["key"].as_str().map(String::from))
+                .collect(),
+        )
+    };
+    let statuses_url = format!("{}/rest/api/3/status", base_url);
+    let statuses = rest::get_json(
+        &client,
+        config,
+        &statuses_url,
+        &[] as &[(&str, &str)],
+        "statuses",
+    );
+    let (mut projects, statuses) = match tokio::join!(projects, statuses) {
+        (Ok(projects), Ok(statuses)) => (projects, status_names(&statuses)),
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::warn!(
+                "JIRA_SCHEMA_EXAMPLES: {}; jira_search keeps its generic description",
+                e
+            );
+            return;
+        }
+    };
+
+    projects.truncate(MAX_PROJECTS);
+    tracing::info!(
+        "jira_search examples: {} projects, {} statuses",
+        projects.len(),
+        statuses.len()
+    );
+    EXAMPLES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push((base_url.to_string(), SiteExamples { projects, statuses }));
+}
+
+/// JQL values for double quotes
+fn quoted(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn describe(examples: &SiteExamples) -> Option<String> {
+    let mut parts = Vec::new();
+    if !examples.projects.is_empty() {
+        parts.push(format!("projects {}", examples.projects.join(", ")));
+    }
+    if !examples.statuses.is_empty() {
+        let names: Vec<String> = examples.statuses.iter().map(|(n, _)| quoted(n)).collect();
+        parts.push(format!("statuses {}", names.join(", ")));
+    }
+    if parts.is_empty() {
+        return None;
+    }
+
+    let mut clauses = Vec::new();
+    if let Some(project) = examples.projects.first() {
+        clauses.push(format!("project = {}", project));
+    }
+    let status = examples
+        .statuses
+        .iter()
+        .find(|(_, category)| category == "indeterminate")
+        .or(examples.statuses.first());
+    if let Some((name, _)) = status {
+        clauses.push(format!("status = {}", quoted(name)));
+    }
+    Some(format!(
+        "On this site: {} (e.g., '{} ORDER BY updated DESC')",
+        parts.join("; "),
+        clauses.join(" AND ")
+    ))
+}
+
+/// The sentence for `jira_search`'s description, once [`discover`] found
+/// the site's values
+pub fn search_examples(config: &Config) -> Option<String> {
+    if !config.jira_schema_examples {
+        return None;
+    }
+    let base_url = config.get_atlassian_base_url();
+    EXAMPLES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|(site, _)| site == base_url)
+        .and_then(|(_, examples)| describe(examples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_statuses_are_ordered_by_category() {
+        let statuses = json!([
+            {"name": "Done", "statusCategory": {"key": "done"}},
+            {"name": "In Review", "statusCategory": {"key": "indeterminate"}},
+            {"name": "To Do", "statusCategory": {"key": "new"}},
+            {"name": "done", "statusCategory": {"key": "done"}}
+        ]);
+        let names: Vec<String> = status_names(&statuses)
+            .into_iter()
+            .map(|(n, _)| n)
+            .collect();
+        assert_eq!(names, vec!["To Do", "In Review", "Done"]);
+    }
+
+    #[test]
+    fn test_describe_builds_an_example_query() {
+        let examples = SiteExamples {
+            projects: vec!["OPS".to_string(), "WEB".to_string()],
+            statuses: vec![
+                ("Backlog".to_string(), "new".to_string()),
+                ("In \"QA\"".to_string(), "indeterminate".to_string()),
+            ],
+        };
+        assert_eq!(
+            describe(&examples).unwrap(),
+            "On this site: projects OPS, WEB; statuses \"Backlog\", \"In \\\"QA\\\"\" (e.g., 'project = OPS AND status = \"In \\\"QA\\\"\" ORDER BY updated DESC')"
+        );
+        let empty = SiteExamples {
+            projects: vec![],
+            statuses: vec![],
+        };
+        assert_eq!(describe(&empty), None);
+    }
+}
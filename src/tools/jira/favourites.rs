@@ -0,0 +1,148 @@
+//! Favourite filters and the boards built on them
+//!
+//! Jira exposes starred boards only through internal APIs, so
+//! `jira_get_favourites` lists the user's favourite filters and, per filter,
+//! the boards that use it (`/board/filter/{id}`): "my usual board" is almost
+//! always one of those.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+pub struct GetFavouritesHandler;
+
+/// Filters whose boards are looked up (one request each)
+const MAX_BOARD_LOOKUPS: usize = 20;
+
+/// `{id, name, jql, owner}` of a favourite filter
+pub fn compact_filter(filter: &Value) -> Value {
+    json!({
+        "id": filter["id"],
+        "name": filter["name"],
+        "jql": filter["jql"],
+        "owner": filter["owner"]["displayName"]
+    })
+}
+
+/// `{id, name, type, project_key, filter_id}` per board in a `/board/filter` page
+pub fn compact_boards(data: &Value, filter_id: &Value) -> Vec<Value> {
+    data["values"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|board| {
+            json!({
+                "id": board["id"],
+                "name": board["name"],
+                "type": board["type"],
+                "project_key": board["location"]["projectKey"],
+                "filter_id": filter_id
+            })
+        })
+        .collect()
+}
+
+async fn get_json(client: &reqwest::Client, config: &Config, url: &str) -> Result<Value> {
+    let response = client
+        .get(url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get favourites: {}", response.status());
+    }
+    Ok(response.json().await?)
+}
+
+#[async_trait]
+impl ToolHandler for GetFavouritesHandler {
+    fn name(&self) -> &'static str {
+        "jira_get_favourites"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "include_boards".to_string(),
+            Property::new("boolean")
+                .with_description("Also list the boards built on each favourite filter".to_string())
+                .with_default(json!(true)),
+        );
+        ToolSchema::new(
+            "Your favourite Jira filters and the boards that use them; good defaults for 'my usual board'",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let include_boards = args["include_boards"].as_bool().unwrap_or(true);
+
+        let client = create_atlassian_client(config);
+        let base_url = config.get_atlassian_base_url();
+        let data = get_json(
+            &client,
+            config,
+            &format!("{}/rest/api/3/filter/favourite", base_url),
+        )
+        .await?;
+        let filters: Vec<Value> = data.as_array().cloned().unwrap_or_default();
+
+        let mut result = json!({
+            "success": true,
+            "filters": filters.iter().map(compact_filter).collect::<Vec<_>>()
+        });
+        if !include_boards {
+            return Ok(result);
+        }
+
+        let mut boards = Vec::new();
+        for filter in filters.iter().take(MAX_BOARD_LOOKUPS) {
+            let Some(id) = filter["id"].as_str() else {
+                continue;
+            };
+            let url = format!("{}/rest/agile/1.0/board/filter/{}", base_url, id);
+            // Filters without boards (or without Jira Software) answer 4xx
+            if let Ok(data) = get_json(&client, config, &url).await {
+                boards.extend(compact_boards(&data, &filter["id"]));
+            }
+        }
+        result["boards"] = json!(boards);
+        if filters.len() > MAX_BOARD_LOOKUPS {
+            result["boards_truncated"] = json!(true);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_filter_and_boards() {
+        let filter = json!({"id": "10100", "name": "My open bugs", "jql": "assignee = currentUser()",
+            "owner": {"displayName": "Jane"}, "favourite": true});
+        assert_eq!(
+            compact_filter(&filter),
+            json!({"id": "10100", "name": "My open bugs", "jql": "assignee = currentUser()", "owner": "Jane"})
+        );
+
+        let data = json!({"values": [{"id": 7, "name": "Team board", "type": "scrum",
+            "location": {"projectKey": "ENG"}}]});
+        assert_eq!(
+            compact_boards(&data, &json!("10100")),
+            vec![
+                json!({"id": 7, "name": "Team board", "type": "scrum", "project_key": "ENG", "filter_id": "10100"})
+            ]
+        );
+    }
+}
@@ -105,23 +105,18 @@ pub fn apply_field_filtering_to_url(base_url: &str) -> String {
 mod tests {
     use super::*;
     use crate::config::Config;
+    use crate::test_support::ConfigBuilder;
 
     fn create_test_config(
         default_fields: Option<Vec<String>>,
         custom_fields: Vec<String>,
     ) -> Config {
-        Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token123".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: default_fields,
-            jira_search_custom_fields: custom_fields,
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        }
+        ConfigBuilder::new()
+            .with(|config| {
+                config.jira_search_default_fields = default_fields;
+                config.jira_search_custom_fields = custom_fields;
+            })
+            .build()
     }
 
     #[test]
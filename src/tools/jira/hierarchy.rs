@@ -0,0 +1,327 @@
+//! Issue hierarchy lookups
+//!
+//! `jira_get_subtasks` returns the direct children of an issue and
+//! `jira_get_issue_hierarchy` the full epic → story → sub-task tree. Both are
+//! built from `parent = KEY` / `parent in (...)` JQL queries rather than one
+//! request per issue, and every node carries a status roll-up of its
+//! descendants.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::pagination::MAX_AUTO_PAGINATE_ITEMS;
+use crate::tools::schema::{ToolSchema, string_prop};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+
+use super::{SEARCH_PAGE_SIZE, search_page};
+
+pub struct GetSubtasksHandler;
+pub struct GetIssueHierarchyHandler;
+
+/// Fields needed to render a node
+const HIERARCHY_FIELDS: &[&str] = &["summary", "status", "issuetype", "assignee", "parent"];
+
+/// Parent keys per `parent in (...)` clause, keeping the JQL short
+const PARENT_KEYS_PER_QUERY: usize = 50;
+
+/// `parent in (...)` queries covering `keys`, `PARENT_KEYS_PER_QUERY` at a time
+pub fn parent_queries(keys: &[String]) -> Vec<String> {
+    keys.chunks(PARENT_KEYS_PER_QUERY)
+        .map(|chunk| format!("parent in ({}) ORDER BY rank", chunk.join(", ")))
+        .collect()
+}
+
+/// Compact node: key, summary, status and who owns it
+pub fn summarize_issue(issue: &Value) -> Value {
+    let fields = &issue["fields"];
+    json!({
+        "key": issue["key"],
+        "summary": fields["summary"],
+        "type": fields["issuetype"]["name"],
+        "status": fields["status"]["name"],
+        "status_category": fields["status"]["statusCategory"]["key"],
+        "assignee": fields["assignee"]["displayName"]
+    })
+}
+
+/// Counts of `nodes` per status category, with the share that is done
+pub fn progress<'a>(nodes: impl IntoIterator<Item = &'a Value>) -> Value {
+    let (mut total, mut done, mut in_progress) = (0u64, 0u64, 0u64);
+    for node in nodes {
+        total += 1;
+        match node["status_category"].as_str() {
+            Some("done") => done += 1,
+            Some("indeterminate") => in_progress += 1,
+            _ => {}
+        }
+    }
+    json!({
+        "total": total,
+        "done": done,
+        "in_progress": in_progress,
+        "to_do": total - done - in_progress,
+        "percent_done": (done * 100).checked_div(total).unwrap_or(0)
+    })
+}
+
+/// Nests `grandchildren` under `children` by parent key and attaches
+/// per-node roll-ups; the root's roll-up covers every descendant
+pub fn build_tree(root: &Value, children: &[Value], grandchildren: &[Value]) -> Value {
+    let mut by_parent: HashMap<&str, Vec<Value>> = HashMap::new();
+    for issue in grandchildren {
+        if let Some(parent) = issue["fields"]["parent"]["key"].as_str() {
+            by_parent
+                .entry(parent)
+                .or_default()
+                .push(summarize_issue(issue));
+        }
+    }
+
+    let mut descendants = Vec::new();
+    let nodes: Vec<Value> = children
+        .iter()
+        .map(|child| {
+            let mut node = summarize_issue(child);
+            descendants.push(node.clone());
+            let subtasks = key_of(child)
+                .and_then(|key| by_parent.remove(key))
+                .unwrap_or_default();
+            if !subtasks.is_empty() {
+                node["progress"] = progress(&subtasks);
+                descendants.extend(subtasks.iter().cloned());
+                node["children"] = json!(subtasks);
+            }
+            node
+        })
+        .collect();
+
+    let mut tree = summarize_issue(root);
+    tree["progress"] = progress(&descendants);
+    tree["children"] = json!(nodes);
+    tree
+}
+
+fn key_of(issue: &Value) -> Option<&str> {
+    issue["key"].as_str()
+}
+
+fn hierarchy_fields() -> Vec<String> {
+    HIERARCHY_FIELDS.iter().map(|f| f.to_string()).collect()
+}
+
+async fn get_root(client: &reqwest::Client, config: &Config, key: &str) -> Result<Value> {
+    let url = format!(
+        "{}/rest/api/3/issue/{}?fields={}",
+        config.get_atlassian_base_url(),
+        key,
+        HIERARCHY_FIELDS.join(",")
+    );
+    let response = client
+        .get(&url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get issue: {}", response.status());
+    }
+    Ok(response.json().await?)
+}
+
+/// Every issue matching `jql`, stopping at `MAX_AUTO_PAGINATE_ITEMS`;
+/// the flag is set when results were cut off
+async fn search_issues(
+    client: &reqwest::Client,
+    config: &Config,
+    jql: &str,
+) -> Result<(Vec<Value>, bool)> {
+    let fields = hierarchy_fields();
+    let mut issues = Vec::new();
+    let mut token: Option<String> = None;
+
+    loop {
+        let data = search_page(
+            client,
+            config,
+            jql,
+            &fields,
+            SEARCH_PAGE_SIZE as u64,
+            token.as_deref(),
+        )
+        .await?;
+        issues.extend(data["issues"].as_array().cloned().unwrap_or_default());
+        token = data["nextPageToken"].as_str().map(String::from);
+
+        let is_last = data["isLast"].as_bool().unwrap_or(true) || token.is_none();
+        if issues.len() >= MAX_AUTO_PAGINATE_ITEMS {
+            let truncated = issues.len() > MAX_AUTO_PAGINATE_ITEMS || !is_last;
+            issues.truncate(MAX_AUTO_PAGINATE_ITEMS);
+            return Ok((issues, truncated));
+        }
+        if is_last {
+            return Ok((issues, false));
+        }
+    }
+}
+
+fn required_key(args: &Value) -> Result<&str> {
+    args["issue_key"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing issue_key"))
+}
+
+#[async_trait]
+impl ToolHandler for GetSubtasksHandler {
+    fn name(&self) -> &'static str {
+        "jira_get_subtasks"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "issue_key".to_string(),
+            string_prop("Parent issue key (e.g., PROJ-123)", true),
+        );
+        ToolSchema::new(
+            "List direct children (sub-tasks, or an epic's issues) with statuses and a progress roll-up",
+            props,
+            vec!["issue_key".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let issue_key = required_key(&args)?;
+        let client = create_atlassian_client(config);
+
+        let (children, truncated) = search_issues(
+            &client,
+            config,
+            &format!("parent = {} ORDER BY rank", issue_key),
+        )
+        .await?;
+        let subtasks: Vec<Value> = children.iter().map(summarize_issue).collect();
+
+        Ok(json!({
+            "success": true,
+            "parent": issue_key,
+            "progress": progress(&subtasks),
+            "truncated": truncated,
+            "subtasks": subtasks
+        }))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for GetIssueHierarchyHandler {
+    fn name(&self) -> &'static str {
+        "jira_get_issue_hierarchy"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "issue_key".to_string(),
+            string_prop("Epic or parent issue key (e.g., PROJ-100)", true),
+        );
+        ToolSchema::new(
+            "Get the epic -> story -> sub-task tree under an issue in two JQL queries, with statuses and per-level progress roll-ups",
+            props,
+            vec!["issue_key".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let issue_key = required_key(&args)?;
+        let client = create_atlassian_client(config);
+
+        let root = get_root(&client, config, issue_key).await?;
+        let (children, mut truncated) = search_issues(
+            &client,
+            config,
+            &format!("parent = {} ORDER BY rank", issue_key),
+        )
+        .await?;
+
+        // Sub-tasks cannot have children, so skip the second level for them
+        let parents: Vec<String> = children
+            .iter()
+            .filter(|c| c["fields"]["issuetype"]["subtask"].as_bool() != Some(true))
+            .filter_map(|c| key_of(c).map(String::from))
+            .collect();
+        let mut grandchildren = Vec::new();
+        for jql in parent_queries(&parents) {
+            let (issues, cut) = search_issues(&client, config, &jql).await?;
+            grandchildren.extend(issues);
+            truncated |= cut;
+        }
+
+        Ok(json!({
+            "success": true,
+            "truncated": truncated,
+            "hierarchy": build_tree(&root, &children, &grandchildren)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(key: &str, category: &str, parent: Option<&str>) -> Value {
+        json!({
+            "key": key,
+            "fields": {
+                "summary": format!("{} summary", key),
+                "status": {"name": category, "statusCategory": {"key": category}},
+                "issuetype": {"name": "Story"},
+                "parent": parent.map(|p| json!({"key": p}))
+            }
+        })
+    }
+
+    #[test]
+    fn test_build_tree_nests_and_rolls_up() {
+        let root = issue("EPIC-1", "indeterminate", None);
+        let children = vec![
+            issue("PROJ-1", "done", Some("EPIC-1")),
+            issue("PROJ-2", "indeterminate", Some("EPIC-1")),
+        ];
+        let grandchildren = vec![
+            issue("PROJ-3", "done", Some("PROJ-2")),
+            issue("PROJ-4", "new", Some("PROJ-2")),
+        ];
+
+        let tree = build_tree(&root, &children, &grandchildren);
+        assert_eq!(tree["key"], "EPIC-1");
+        assert_eq!(tree["children"][0]["key"], "PROJ-1");
+        assert!(tree["children"][0].get("children").is_none());
+        assert_eq!(tree["children"][1]["children"][1]["key"], "PROJ-4");
+        assert_eq!(tree["children"][1]["progress"]["percent_done"], 50);
+
+        let rollup = &tree["progress"];
+        assert_eq!(rollup["total"], 4);
+        assert_eq!(rollup["done"], 2);
+        assert_eq!(rollup["in_progress"], 1);
+        assert_eq!(rollup["to_do"], 1);
+    }
+
+    #[test]
+    fn test_progress_empty() {
+        assert_eq!(progress(&[])["percent_done"], 0);
+    }
+
+    #[test]
+    fn test_parent_queries_chunked() {
+        let keys: Vec<String> = (1..=120).map(|i| format!("PROJ-{}", i)).collect();
+        let queries = parent_queries(&keys);
+        assert_eq!(queries.len(), 3);
+        assert!(queries[0].starts_with("parent in (PROJ-1, PROJ-2,"));
+        assert!(queries[2].starts_with("parent in (PROJ-101,"));
+        assert!(parent_queries(&[]).is_empty());
+    }
+}
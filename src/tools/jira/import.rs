@@ -0,0 +1,431 @@
+//! CSV issue import
+//!
+//! `jira_import_csv` turns each CSV row into a create payload and submits
+//! them through the bulk create endpoint, `BULK_CREATE_BATCH` at a time.
+//! Columns map to fields through `mapping` (header → field); without an entry
+//! a header that names a known field (`Summary`, `Issue Type`, `Labels`, ...)
+//! maps to it and any other column is ignored. The response carries one
+//! result per row plus the same as a Markdown table.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Map, Value, json};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema, string_prop};
+use crate::tools::write_policy;
+use crate::utils::csv;
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+use super::adf_utils;
+
+pub struct ImportCsvHandler;
+
+/// Issues per `/issue/bulk` request (the API maximum)
+const BULK_CREATE_BATCH: usize = 50;
+/// Data rows accepted per call
+pub const MAX_IMPORT_ROWS: usize = 200;
+
+/// Field id for a header without an explicit mapping
+fn known_field(header: &str) -> Option<&'static str> {
+    let normalized: String = header
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    Some(match normalized.as_str() {
+        "summary" | "title" => "summary",
+        "description" => "description",
+        "issuetype" | "type" => "issuetype",
+        "project" | "projectkey" => "project",
+        "priority" => "priority",
+        "labels" | "label" => "labels",
+        "assignee" => "assignee",
+        "reporter" => "reporter",
+        "components" | "component" => "components",
+        "fixversions" | "fixversion" => "fixVersions",
+        "duedate" | "due" => "duedate",
+        "parent" | "epic" => "parent",
+        _ => return None,
+    })
+}
+
+/// Column index → field id
+pub fn resolve_columns(headers: &[String], mapping: &Value) -> Result<Vec<Option<String>>> {
+    if !mapping.is_null() && !mapping.is_object() {
+        anyhow::bail!("mapping must be an object of column header -> field");
+    }
+    if let Some(map) = mapping.as_object() {
+        for header in map.keys() {
+            if !headers.iter().any(|h| h.trim() == header) {
+                anyhow::bail!("mapping refers to unknown column '{}'", header);
+            }
+        }
+    }
+
+    Ok(headers
+        .iter()
+        .map(|header| {
+            let header = header.trim();
+            match mapping.get(header) {
+                Some(Value::String(field)) if field.is_empty() => None,
+                Some(Value::String(field)) => Some(field.clone()),
+                Some(_) => None,
+                None => known_field(header).map(String::from),
+            }
+        })
+        .collect())
+}
+
+fn split_list(value: &str) -> Vec<&str> {
+    value
+        .split([',', ';'])
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+/// JSON value Jira expects for `field`, built from a cell
+pub fn field_value(field: &str, cell: &str) -> Result<Value> {
+    Ok(match field {
+        "description" => adf_utils::process_description_input(json!(cell))?,
+        "project" | "parent" => json!({ "key": cell }),
+        "issuetype" | "priority" => json!({ "name": cell }),
+        "assignee" | "reporter" => json!({ "accountId": cell }),
+        "labels" => json!(split_list(cell)),
+        "components" | "fixVersions" | "versions" => json!(
+            split_list(cell)
+                .into_iter()
+                .map(|name| json!({ "name": name }))
+                .collect::<Vec<_>>()
+        ),
+        _ => json!(cell),
+    })
+}
+
+/// `fields` object for one row, with the call's defaults filled in
+pub fn row_fields(
+    row: &[String],
+    columns: &[Option<String>],
+    defaults: &Map<String, Value>,
+) -> Result<Value> {
+    let mut fields = defaults.clone();
+    for (cell, field) in row.iter().zip(columns) {
+        let (Some(field), cell) = (field, cell.trim()) else {
+            continue;
+        };
+        if !cell.is_empty() {
+            fields.insert(field.clone(), field_value(field, cell)?);
+        }
+    }
+    for required in ["project", "issuetype", "summary"] {
+        if !fields.contains_key(required) {
+            anyhow::bail!("missing {}", required);
+        }
+    }
+    Ok(Value::Object(fields))
+}
+
+/// `| Row | Summary | Result |` table over per-row results
+pub fn result_table(results: &[Value]) -> String {
+    let mut table = String::from("| Row | Summary | Result |\n|---|---|---|\n");
+    for result in results {
+        let outcome = match result["key"].as_str() {
+            Some(key) => format!("created {}", key),
+            None => format!(
+                "{}: {}",
+                result["outcome"].as_str().unwrap_or("failed"),
+                result["error"].as_str().unwrap_or_default()
+            ),
+        };
+        table.push_str(&format!(
+            "| {} | {} | {} |\n",
+            result["row"],
+            result["summary"]
+                .as_str()
+                .unwrap_or_default()
+                .replace('|', "\\|"),
+            outcome.replace('|', "\\|").replace('\n', " ")
+        ));
+    }
+    table
+}
+
+/// First human-readable message from a bulk create `errors[]` entry
+fn bulk_error_message(error: &Value) -> String {
+    let body = &error["elementErrors"];
+    body["errors"]
+        .as_object()
+        .and_then(|errors| {
+            errors
+                .iter()
+                .map(|(field, message)| {
+                    format!("{}: {}", field, message.as_str().unwrap_or_default())
+                })
+                .reduce(|a, b| format!("{}; {}", a, b))
+        })
+        .or_else(|| {
+            body["errorMessages"]
+                .as_array()
+                .and_then(|m| m.first())
+                .and_then(|m| m.as_str())
+                .map(String::from)
+        })
+        .unwrap_or_else(|| "rejected by Jira".to_string())
+}
+
+#[async_trait]
+impl ToolHandler for ImportCsvHandler {
+    fn name(&self) -> &'static str {
+        "jira_import_csv"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "csv".to_string(),
+            string_prop("CSV text with a header row; at most 200 data rows", true),
+        );
+        props.insert("mapping".to_string(), Property::new("object").with_description("Column header -> field ID (e.g., {\"Story Points\": \"customfield_10016\", \"Notes\": \"\"}; empty ignores the column). Unmapped headers naming a known field (Summary, Description, Issue Type, Project, Priority, Labels, Assignee, Components, Fix Version, Due Date, Parent) map automatically").with_additional_properties(Property::new("string")));
+        props.insert(
+            "project_key".to_string(),
+            string_prop("Project for rows without a project column", false),
+        );
+        props.insert(
+            "issue_type".to_string(),
+            string_prop("Issue type for rows without an issue type column", false),
+        );
+        props.insert(
+            "dry_run".to_string(),
+            Property::new("boolean")
+                .with_description("Validate and map every row without creating issues")
+                .with_default(json!(false)),
+        );
+        ToolSchema::new(
+            "Create issues from CSV rows via bulk create; returns per-row results and a Markdown result table",
+            props,
+            vec!["csv".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let text = args["csv"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing csv"))?;
+        let dry_run = args["dry_run"].as_bool().unwrap_or(false);
+
+        let mut records = csv::parse(text)?.into_iter();
+        let headers = records
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("CSV has no header row"))?;
+        let rows: Vec<Vec<String>> = records.collect();
+        if rows.is_empty() {
+            anyhow::bail!("CSV has no data rows");
+        }
+        if rows.len() > MAX_IMPORT_ROWS {
+            anyhow::bail!(
+                "CSV has {} rows; import at most {} per call",
+                rows.len(),
+                MAX_IMPORT_ROWS
+            );
+        }
+        let columns = resolve_columns(&headers, &args["mapping"])?;
+
+        let mut defaults = Map::new();
+        if let Some(project) = args["project_key"].as_str() {
+            defaults.insert("project".to_string(), field_value("project", project)?);
+        }
+        if let Some(issue_type) = args["issue_type"].as_str() {
+            defaults.insert(
+                "issuetype".to_string(),
+                field_value("issuetype", issue_type)?,
+            );
+        }
+
+        // Row numbers are 1-based data rows, matching the spreadsheet minus its header
+        let mut results: Vec<Value> = Vec::with_capacity(rows.len());
+        let mut payloads: Vec<(usize, Value)> = Vec::new();
+        for (index, row) in rows.iter().enumerate() {
+            // Rows for projects outside WRITE_ALLOWED_PROJECTS are invalid, not fatal
+            let fields = row_fields(row, &columns, &defaults).and_then(|fields| {
+                let project = fields["project"]["key"].as_str().unwrap_or_default();
+                write_policy::check_project(config, project)?;
+                Ok(fields)
+            });
+            match fields {
+                Ok(fields) => {
+                    results.push(json!({
+                        "row": index + 1,
+                        "summary": fields["summary"],
+                        "outcome": if dry_run { "valid" } else { "pending" }
+                    }));
+                    payloads.push((index, json!({ "fields": fields })));
+                }
+                Err(e) => results.push(json!({
+                    "row": index + 1,
+                    "outcome": "invalid",
+                    "error": e.to_string()
+                })),
+            }
+        }
+
+        if !dry_run {
+            let client = create_atlassian_client(config);
+            let url = format!("{}/rest/api/3/issue/bulk", config.get_atlassian_base_url());
+
+            for batch in payloads.chunks(BULK_CREATE_BATCH) {
+                let body = json!({
+                    "issueUpdates": batch.iter().map(|(_, p)| p).collect::<Vec<_>>()
+                });
+                let response = client
+                    .post(&url)
+                    .header("Authorization", create_auth_header(config))
+                    .header("Content-Type", "application/json")
+                    .json(&body)
+                    .send()
+                    .await?;
+
+                // 201 when all were created, 400 with partial `issues`/`errors` otherwise
+                let status = response.status();
+                let data: Value = response.json().await.unwrap_or(Value::Null);
+                if !status.is_success() && data["errors"].as_array().is_none() {
+                    for (index, _) in batch {
+                        results[*index]["outcome"] = json!("failed");
+                        results[*index]["error"] = json!(format!("Bulk create failed: {}", status));
+                    }
+                    continue;
+                }
+
+                let mut created = data["issues"]
+                    .as_array()
+                    .cloned()
+                    .unwrap_or_default()
+                    .into_iter();
+                let failed: std::collections::HashMap<u64, String> = data["errors"]
+                    .as_array()
+                    .map(|errors| {
+                        errors
+                            .iter()
+                            .filter_map(|e| {
+                                e["failedElementNumber"]
+                                    .as_u64()
+                                    .map(|n| (n, bulk_error_message(e)))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                // Created issues come back in request order, skipping failed elements
+                for (position, (index, _)) in batch.iter().enumerate() {
+                    let result = &mut results[*index];
+                    if let Some(error) = failed.get(&(position as u64)) {
+                        result["outcome"] = json!("failed");
+                        result["error"] = json!(error);
+                    } else if let Some(issue) = created.next() {
+                        result["outcome"] = json!("created");
+                        result["key"] = issue["key"].clone();
+                    } else {
+                        result["outcome"] = json!("failed");
+                        result["error"] = json!("no result returned");
+                    }
+                }
+            }
+        }
+
+        let count = |outcome: &str| results.iter().filter(|r| r["outcome"] == outcome).count();
+        let succeeded = count(if dry_run { "valid" } else { "created" });
+        let failed = count("failed") + count("invalid");
+        Ok(json!({
+            // Every row failing is a failed import; some failing is a partial one
+            "success": succeeded > 0 || failed == 0,
+            "partial": succeeded > 0 && failed > 0,
+            "dry_run": dry_run,
+            "rows": results.len(),
+            "created": count("created"),
+            "failed": failed,
+            "table": result_table(&results),
+            "results": results
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_resolve_columns_mapping_and_known_headers() {
+        let columns = resolve_columns(
+            &headers(&["Title", "Issue Type", "Story Points", "Notes"]),
+            &json!({"Story Points": "customfield_10016", "Notes": ""}),
+        )
+        .unwrap();
+        assert_eq!(
+            columns,
+            vec![
+                Some("summary".to_string()),
+                Some("issuetype".to_string()),
+                Some("customfield_10016".to_string()),
+                None
+            ]
+        );
+
+        assert!(resolve_columns(&headers(&["Summary"]), &json!({"Nope": "labels"})).is_err());
+        assert!(resolve_columns(&headers(&["Summary"]), &json!("labels")).is_err());
+    }
+
+    #[test]
+    fn test_row_fields_converts_values_and_applies_defaults() {
+        let columns = resolve_columns(
+            &headers(&["Summary", "Labels", "Fix Version", "Assignee"]),
+            &Value::Null,
+        )
+        .unwrap();
+        let mut defaults = Map::new();
+        defaults.insert("project".to_string(), json!({"key": "PROJ"}));
+        defaults.insert("issuetype".to_string(), json!({"name": "Task"}));
+
+        let row = headers(&["Migrate DB", "infra; db", "1.2, 1.3", ""]);
+        let fields = row_fields(&row, &columns, &defaults).unwrap();
+        assert_eq!(fields["summary"], "Migrate DB");
+        assert_eq!(fields["labels"], json!(["infra", "db"]));
+        assert_eq!(
+            fields["fixVersions"],
+            json!([{"name": "1.2"}, {"name": "1.3"}])
+        );
+        assert!(fields.get("assignee").is_none());
+        assert_eq!(fields["project"]["key"], "PROJ");
+
+        let err = row_fields(&headers(&["", "x"]), &columns, &defaults).unwrap_err();
+        assert_eq!(err.to_string(), "missing summary");
+    }
+
+    #[test]
+    fn test_result_table() {
+        let table = result_table(&[
+            json!({"row": 1, "summary": "A | B", "outcome": "created", "key": "PROJ-1"}),
+            json!({"row": 2, "outcome": "invalid", "error": "missing summary"}),
+        ]);
+        assert!(table.contains("| 1 | A \\| B | created PROJ-1 |"));
+        assert!(table.contains("| 2 |  | invalid: missing summary |"));
+    }
+
+    #[test]
+    fn test_bulk_error_message() {
+        let error = json!({
+            "failedElementNumber": 0,
+            "elementErrors": {"errors": {"issuetype": "valid issue type is required"}}
+        });
+        assert_eq!(
+            bulk_error_message(&error),
+            "issuetype: valid issue type is required"
+        );
+        assert_eq!(bulk_error_message(&json!({})), "rejected by Jira");
+    }
+}
@@ -0,0 +1,179 @@
+//! Compact activity summary for `jira_get_issue`
+//!
+//! "What happened on this issue lately" usually takes a comments call and a
+//! changelog call after the issue itself. With `include_activity: true` the
+//! issue is read with its changelog and the latest comments are fetched
+//! alongside, then both are folded into an `activity` object: one line of
+//! text per comment and one line per changelog entry, newest first.
+
+use anyhow::Result;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::tools::excerpts;
+use crate::utils::http_utils::create_auth_header;
+
+/// Comments and changelog entries in the summary
+pub const ACTIVITY_ENTRIES: usize = 5;
+/// Characters kept of each comment
+const COMMENT_CHARS: usize = 160;
+
+/// `field: from → to` for each item of one changelog entry, `; `-separated
+fn describe_changes(items: &Value) -> String {
+    items
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|item| {
+            let value = |key: &str| match item[key].as_str() {
+                Some(text) if !text.is_empty() => excerpts::one_line(text, 60),
+                _ => "—".to_string(),
+            };
+            format!(
+                "{}: {} → {}",
+                item["field"].as_str().unwrap_or_default(),
+                value("fromString"),
+                value("toString")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// The summary from a `/comment` page (newest first) and the issue's
+/// `changelog.histories`
+pub fn summarize(comments: &Value, histories: &Value) -> Value {
+    let recent_comments: Vec<Value> = comments["comments"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .take(ACTIVITY_ENTRIES)
+        .map(|comment| {
+            json!({
+                "created": comment["created"],
+                "author": comment["author"]["displayName"],
+                "text": excerpts::one_line(&excerpts::adf_plain_text(&comment["body"]), COMMENT_CHARS)
+            })
+        })
+        .collect();
+
+    let mut histories: Vec<&Value> = histories.as_array().into_iter().flatten().collect();
+    // Timestamps share one format, so lexical order is chronological
+    histories.sort_by_key(|history| std::cmp::Reverse(history["created"].as_str()));
+    let recent_changes: Vec<Value> = histories
+        .into_iter()
+        .take(ACTIVITY_ENTRIES)
+        .map(|history| {
+            json!({
+                "created": history["created"],
+                "author": history["author"]["displayName"],
+                "changes": describe_changes(&history["items"])
+            })
+        })
+        .collect();
+
+    json!({
+        "comment_total": comments["total"],
+        "recent_comments": recent_comments,
+        "recent_changes": recent_changes
+    })
+}
+
+/// The latest comments of an issue, newest first
+pub async fn recent_comments(
+    client: &reqwest::Client,
+    config: &Config,
+    issue_key: &str,
+) -> Result<Value> {
+    let url = format!(
+        "{}/rest/api/3/issue/{}/comment",
+        config.get_atlassian_base_url(),
+        issue_key
+    );
+    let response = client
+        .get(&url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .query(&[
+            ("orderBy", "-created".to_string()),
+            ("maxResults", ACTIVITY_ENTRIES.to_string()),
+        ])
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get comments: {}", response.status());
+    }
+    Ok(response.json().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(created: &str, text: &str) -> Value {
+        json!({
+            "created": created,
+            "author": {"displayName": "Jane"},
+            "body": {"type": "doc", "version": 1, "content": [
+                {"type": "paragraph", "content": [{"type": "text", "text": text}]}
+            ]}
+        })
+    }
+
+    #[test]
+    fn test_summary_lines() {
+        let comments = json!({
+            "total": 12,
+            "comments": [
+                comment("2026-10-02T09:00:00.000+0000", "Deployed\n\nto staging"),
+                comment("2026-10-01T09:00:00.000+0000", &"long ".repeat(100))
+            ]
+        });
+        let histories = json!([
+            {"created": "2026-09-01T09:00:00.000+0000", "author": {"displayName": "Bob"},
+             "items": [{"field": "status", "fromString": "To Do", "toString": "In Progress"}]},
+            {"created": "2026-10-03T09:00:00.000+0000", "author": {"displayName": "Jane"},
+             "items": [
+                {"field": "status", "fromString": "In Progress", "toString": "Done"},
+                {"field": "assignee", "fromString": null, "toString": "Jane"}
+             ]}
+        ]);
+
+        let activity = summarize(&comments, &histories);
+        assert_eq!(activity["comment_total"], 12);
+        assert_eq!(
+            activity["recent_comments"][0]["text"],
+            "Deployed to staging"
+        );
+        assert_eq!(activity["recent_comments"][0]["author"], "Jane");
+        assert!(
+            activity["recent_comments"][1]["text"]
+                .as_str()
+                .unwrap()
+                .ends_with('…')
+        );
+        assert_eq!(
+            activity["recent_changes"][0]["changes"],
+            "status: In Progress → Done; assignee: — → Jane"
+        );
+        assert_eq!(activity["recent_changes"][1]["author"], "Bob");
+    }
+
+    #[test]
+    fn test_summary_keeps_the_latest_entries() {
+        let histories: Vec<Value> = (1..=9)
+            .map(|day| {
+                json!({
+                    "created": format!("2026-10-0{}T09:00:00.000+0000", day),
+                    "items": [{"field": "labels", "fromString": "", "toString": day.to_string()}]
+                })
+            })
+            .collect();
+        let activity = summarize(&Value::Null, &json!(histories));
+        let changes = activity["recent_changes"].as_array().unwrap();
+        assert_eq!(changes.len(), ACTIVITY_ENTRIES);
+        assert_eq!(changes[0]["changes"], "labels: — → 9");
+        assert!(activity["recent_comments"].as_array().unwrap().is_empty());
+    }
+}
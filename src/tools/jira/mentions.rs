@@ -0,0 +1,139 @@
+//! Issue keys mentioned in returned content
+//!
+//! Comment and page bodies often reference other issues by key. Readers
+//! attach a compact `mentioned_issues` list (key, summary, status) resolved
+//! with one bulk fetch, so those references need no follow-up calls. Lookup
+//! failures only drop the list; keys Jira does not know are left out.
+
+use anyhow::Result;
+use serde_json::{Value, json};
+use tracing::warn;
+
+use crate::config::Config;
+use crate::utils::http_utils::create_auth_header;
+use crate::utils::issue_keys::find_issue_keys;
+
+/// Keys resolved per response (the bulk fetch endpoint accepts up to 100)
+pub const MAX_MENTIONED_ISSUES: usize = 50;
+
+/// Every string inside `value`, one per line
+fn collect_text(value: &Value, out: &mut String) {
+    match value {
+        Value::String(s) => {
+            out.push_str(s);
+            out.push('\n');
+        }
+        Value::Array(items) => items.iter().for_each(|item| collect_text(item, out)),
+        Value::Object(map) => map.values().for_each(|item| collect_text(item, out)),
+        _ => {}
+    }
+}
+
+/// Distinct keys in the strings of `content` (ADF, storage or plain text),
+/// without `exclude` (the issue being read), at most `MAX_MENTIONED_ISSUES`
+pub fn mentioned_keys(content: &Value, exclude: Option<&str>) -> Vec<String> {
+    let mut text = String::new();
+    collect_text(content, &mut text);
+    find_issue_keys(&text)
+        .into_iter()
+        .filter(|key| Some(key.as_str()) != exclude)
+        .take(MAX_MENTIONED_ISSUES)
+        .collect()
+}
+
+/// `{key, summary, status}` per bulk-fetched issue
+pub fn compact_issues(data: &Value) -> Vec<Value> {
+    data["issues"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|issue| {
+            json!({
+                "key": issue["key"],
+                "summary": issue["fields"]["summary"],
+                "status": issue["fields"]["status"]["name"],
+                "status_category": issue["fields"]["status"]["statusCategory"]["key"]
+            })
+        })
+        .collect()
+}
+
+/// Resolves `keys` in one `/issue/bulkfetch` request
+pub async fn lookup(
+    client: &reqwest::Client,
+    config: &Config,
+    keys: &[String],
+) -> Result<Vec<Value>> {
+    let url = format!(
+        "{}/rest/api/3/issue/bulkfetch",
+        config.get_atlassian_base_url()
+    );
+    let response = client
+        .post(&url)
+        .header("Authorization", create_auth_header(config))
+        .header("Content-Type", "application/json")
+        .json(&json!({
+            "issueIdsOrKeys": keys,
+            "fields": ["summary", "status"]
+        }))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to look up mentioned issues: {}", response.status());
+    }
+    let data: Value = response.json().await?;
+    Ok(compact_issues(&data))
+}
+
+/// Adds `mentioned_issues` to `result` when `content` mentions any issue
+pub async fn attach(
+    client: &reqwest::Client,
+    config: &Config,
+    result: &mut Value,
+    content: &Value,
+    exclude: Option<&str>,
+) {
+    let keys = mentioned_keys(content, exclude);
+    if keys.is_empty() {
+        return;
+    }
+    match lookup(client, config, &keys).await {
+        Ok(issues) => result["mentioned_issues"] = json!(issues),
+        Err(e) => warn!("Skipping mentioned_issues: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mentioned_keys_from_adf_and_storage() {
+        let adf = json!({"type": "doc", "version": 1, "content": [{"type": "paragraph", "content": [
+            {"type": "text", "text": "Blocked by\nOPS-4, dup of PROJ-1"},
+            {"type": "inlineCard", "attrs": {"url": "https://x.atlassian.net/browse/OPS-9"}}
+        ]}]});
+        assert_eq!(mentioned_keys(&adf, Some("PROJ-1")), vec!["OPS-4", "OPS-9"]);
+
+        let storage = json!(
+            "<p>See <ac:structured-macro ac:name=\"jira\"><ac:parameter ac:name=\"key\">ENG-12</ac:parameter></ac:structured-macro></p>"
+        );
+        assert_eq!(mentioned_keys(&storage, None), vec!["ENG-12"]);
+        assert!(mentioned_keys(&Value::Null, None).is_empty());
+    }
+
+    #[test]
+    fn test_compact_issues() {
+        let data = json!({"issues": [{"key": "OPS-4", "fields": {
+            "summary": "Upgrade gateway",
+            "status": {"name": "In Progress", "statusCategory": {"key": "indeterminate"}}
+        }}], "issueErrors": []});
+        assert_eq!(
+            compact_issues(&data),
+            vec![
+                json!({"key": "OPS-4", "summary": "Upgrade gateway", "status": "In Progress", "status_category": "indeterminate"})
+            ]
+        );
+    }
+}
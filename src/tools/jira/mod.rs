@@ -1,16 +1,58 @@
+use crate::client::Jira;
+use crate::client::jira::SearchPage;
+use crate::client::models::IssueFields;
 use crate::config::Config;
 use crate::tools::ToolHandler;
-use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use crate::tools::applied_filters;
+use crate::tools::args::parse_args;
+use crate::tools::excerpts;
+use crate::tools::pagination;
+use crate::tools::schema::{
+    Property, ToolSchema, array_prop, markdown_prop, number_prop, string_prop, union_prop,
+};
+use crate::tools::write_policy;
+use crate::utils::time;
 use anyhow::Result;
 use async_trait::async_trait;
-use serde_json::{Value, json};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::collections::{HashMap, HashSet};
 
+pub mod activity;
 pub mod adf_utils;
+pub mod attachments;
+pub mod automation;
+pub mod board;
+pub mod bulk;
+pub mod comments;
+pub mod delta;
+pub mod examples;
+pub mod favourites;
 pub mod field_filtering;
+pub mod hierarchy;
+pub mod import;
+pub mod issue_activity;
+pub mod mentions;
+pub mod notify;
+pub mod properties;
+pub mod relative_dates;
+pub mod report;
+pub mod roles;
+pub mod schemes;
+pub mod scope;
+pub mod similar;
+pub mod standup;
+pub mod tabular;
+pub mod templates;
+pub mod time_tracking;
+pub mod user_mentions;
+pub mod watch;
+pub mod workload;
 
 // Handlers for each Jira tool
 pub struct GetIssueHandler;
 pub struct SearchHandler;
+pub struct ExportSearchHandler;
 pub struct CreateIssueHandler;
 pub struct UpdateIssueHandler;
 pub struct AddCommentHandler;
@@ -18,48 +60,409 @@ pub struct UpdateCommentHandler;
 pub struct TransitionIssueHandler;
 pub struct GetTransitionsHandler;
 
+/// `fields` of `jira_update_issue`: the common system fields are typed, the
+/// rest (custom fields, objects like `assignee`) pass through as any value
+fn update_fields_prop() -> Property {
+    let mut fields = HashMap::new();
+    fields.insert(
+        "summary".to_string(),
+        Property::new("string").with_length(Some(1), Some(255)),
+    );
+    fields.insert(
+        "description".to_string(),
+        union_prop(
+            "Plain text (auto-converted to ADF) or ADF object",
+            vec!["string", "object"],
+        ),
+    );
+    fields.insert("labels".to_string(), array_prop("Labels (no spaces)"));
+    fields.insert(
+        "duedate".to_string(),
+        Property::new(vec!["string", "null"]).with_description(
+            "YYYY-MM-DD, or today/tomorrow/in N days/next friday (in ATLASSIAN_TIMEZONE)",
+        ),
+    );
+    Property::new("object")
+        .with_description("Fields to update as JSON object (e.g., {\"summary\": \"New title\"}). Custom fields use 'customfield_*' format. The 'description' field accepts plain text (auto-converted to ADF) or ADF object.")
+        .with_properties(fields)
+}
+
+/// Comment `visibility`: `{"type": "role"|"group", "value": name}`
+fn visibility_prop() -> Property {
+    let mut restriction = HashMap::new();
+    restriction.insert(
+        "type".to_string(),
+        Property::new("string").with_enum(vec![json!("role"), json!("group")]),
+    );
+    restriction.insert(
+        "value".to_string(),
+        Property::new("string")
+            .with_description("Role or group name")
+            .with_length(Some(1), None),
+    );
+    Property::new("object")
+        .with_description("Restrict to a project role or group: {\"type\": \"role\"|\"group\", \"value\": name}. Role names are checked against the project")
+        .with_properties(restriction)
+        .with_required(vec!["type".to_string(), "value".to_string()])
+}
+
+// Typed arguments (see `tools::args`)
+#[derive(Deserialize)]
+struct IssueKeyArgs {
+    issue_key: String,
+}
+
+#[derive(Deserialize)]
+struct GetIssueArgs {
+    issue_key: String,
+    #[serde(default)]
+    markdown: bool,
+    #[serde(default)]
+    include_activity: bool,
+}
+
+#[derive(Deserialize)]
+struct CreateIssueArgs {
+    project_key: String,
+    summary: String,
+    issue_type: String,
+    /// Plain text, Markdown or ADF
+    #[serde(default)]
+    description: Value,
+    #[serde(default)]
+    markdown: bool,
+}
+
+#[derive(Deserialize)]
+struct UpdateIssueArgs {
+    issue_key: String,
+    fields: Map<String, Value>,
+    #[serde(default)]
+    markdown: bool,
+}
+
+#[derive(Deserialize)]
+struct AddCommentArgs {
+    issue_key: String,
+    /// Plain text, Markdown or ADF
+    comment: Value,
+    #[serde(default)]
+    markdown: bool,
+    reply_to: Option<String>,
+    #[serde(default)]
+    visibility: Value,
+}
+
+#[derive(Deserialize)]
+struct UpdateCommentArgs {
+    issue_key: String,
+    comment_id: String,
+    /// Plain text, Markdown or ADF
+    body: Value,
+    #[serde(default)]
+    markdown: bool,
+    #[serde(default)]
+    visibility: Value,
+}
+
+#[derive(Deserialize)]
+struct TransitionIssueArgs {
+    issue_key: String,
+    transition_id: String,
+}
+
 #[async_trait]
 impl ToolHandler for GetIssueHandler {
-    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
-        let issue_key = args["issue_key"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing issue_key"))?;
+    fn name(&self) -> &'static str {
+        "jira_get_issue"
+    }
 
-        let client = create_atlassian_client(config);
-        let base_url = format!(
-            "{}/rest/api/3/issue/{}",
-            config.get_atlassian_base_url(),
-            issue_key
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "issue_key".to_string(),
+            string_prop(
+                "Issue key (e.g., 'PROJECT-123') or a .../browse/PROJECT-123 link",
+                true,
+            ),
         );
+        props.insert(
+            "markdown".to_string(),
+            markdown_prop(
+                "Return the description and comment bodies as Markdown (mentions, emojis, status lozenges and panels are kept for a lossless edit round trip)",
+            ),
+        );
+        props.insert(
+            "include_activity".to_string(),
+            Property::new("boolean")
+                .with_description(
+                    "Append 'activity': the last 5 comments and the last 5 changelog entries, one line each",
+                )
+                .with_default(json!(false)),
+        );
+        ToolSchema::new(
+            "Get Jira issue by key",
+            props,
+            vec!["issue_key".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let args: GetIssueArgs = parse_args(&args)?;
+        let issue_key = args.issue_key.as_str();
+
+        let jira = Jira::new(config);
+        let client = jira.http();
+        let (mut issue, comments) = if args.include_activity {
+            let (issue, comments) = tokio::join!(
+                jira.get_issue_with_changelog(issue_key),
+                issue_activity::recent_comments(client, config, issue_key)
+            );
+            (issue?, Some(comments?))
+        } else {
+            (jira.get_issue(issue_key).await?, None)
+        };
+
+        let activity = comments.map(|comments| {
+            let histories = issue.extra.remove("changelog").unwrap_or_default();
+            issue_activity::summarize(&comments, &histories["histories"])
+        });
+        let mentioned = json!([&issue.fields.description, &issue.fields.comment]);
+        if args.markdown {
+            bodies_to_markdown(&mut issue.fields);
+        }
+        let mut result = json!({
+            "success": true,
+            "issue": issue
+        });
+        if let Some(activity) = activity {
+            result["activity"] = activity;
+        }
+        mentions::attach(client, config, &mut result, &mentioned, Some(issue_key)).await;
+        Ok(result)
+    }
+}
 
-        let url = field_filtering::apply_field_filtering_to_url(&base_url);
+/// Replaces the description and comment bodies with their Markdown rendering
+fn bodies_to_markdown(fields: &mut IssueFields) {
+    if let Some(description) = fields.description.as_mut().filter(|d| d.is_object()) {
+        *description = json!(adf_utils::adf_to_markdown(description));
+    }
+    let comments = fields
+        .comment
+        .iter_mut()
+        .flat_map(|page| &mut page.comments);
+    for comment in comments.filter(|c| c.body.is_object()) {
+        comment.body = json!(adf_utils::adf_to_markdown(&comment.body));
+    }
+}
+
+/// Page size used when auto-paginating
+const SEARCH_PAGE_SIZE: usize = 100;
+
+/// One page of `/search/jql` as JSON (`issues`, `nextPageToken`, `isLast`, `total`)
+async fn search_page(
+    client: &reqwest::Client,
+    config: &Config,
+    jql: &str,
+    fields: &[String],
+    max_results: u64,
+    next_page_token: Option<&str>,
+) -> Result<Value> {
+    let page = Jira::with_client(config, client.clone())
+        .search(jql, fields, max_results, next_page_token)
+        .await?;
+    Ok(serde_json::to_value(page)?)
+}
+
+/// Issues of one search page as tool output
+fn issues_json(page: SearchPage) -> Result<Vec<Value>> {
+    page.issues
+        .into_iter()
+        .map(|issue| Ok(serde_json::to_value(issue)?))
+        .collect()
+}
 
-        let response = client
-            .get(&url)
-            .header("Authorization", create_auth_header(config))
-            .header("Accept", "application/json")
-            .send()
+/// Follows `nextPageToken` until the results or the item cap run out
+async fn search_all(
+    jira: &Jira<'_>,
+    jql: &str,
+    fields: &[String],
+    args: &Value,
+    output_format: tabular::OutputFormat,
+) -> Result<Value> {
+    let limit = pagination::auto_paginate_limit(args);
+    let mut collected = pagination::Collected::default();
+    let mut seen = HashSet::new();
+    let mut token: Option<String> = None;
+
+    loop {
+        let page_size = (limit - collected.items.len()).min(SEARCH_PAGE_SIZE) as u64;
+        let mut page = jira
+            .search(jql, fields, page_size, token.as_deref())
             .await?;
+        token = page.next_page_token.take();
+        let is_last = page.is_last.unwrap_or(true) || page.issues.is_empty();
+        let issues = issues_json(page)?;
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to get issue: {}", response.status());
+        let more = collected.extend(issues, limit, &mut seen, |issue| {
+            issue["key"].as_str().map(String::from)
+        });
+        if is_last || token.is_none() {
+            break;
         }
+        if !more {
+            collected.truncated = true;
+            break;
+        }
+    }
 
-        let data: Value = response.json().await?;
-        Ok(json!({
-            "success": true,
-            "issue": data
-        }))
+    if output_format == tabular::OutputFormat::Csv {
+        return Ok(Value::String(tabular::issues_to_csv(
+            &collected.items,
+            fields,
+        )));
+    }
+    if output_format == tabular::OutputFormat::Table {
+        let mut table = tabular::issues_to_markdown_table(&collected.items, fields);
+        table.push_str(&format!(
+            "\n{} issues from {} pages{}\n",
+            collected.items.len(),
+            collected.pages,
+            if collected.truncated {
+                " (truncated)"
+            } else {
+                ""
+            }
+        ));
+        return Ok(Value::String(table));
+    }
+
+    Ok(json!({
+        "success": true,
+        "total": collected.items.len(),
+        "pagination": collected.metadata(limit),
+        "issues": collected.items
+    }))
+}
+
+/// `applied_filters` for a search requested as `requested`, rewritten to
+/// `windowed` (relative windows) and run as `effective`
+pub fn applied_projects_filter(
+    requested: &str,
+    windowed: &str,
+    effective: &str,
+    config: &Config,
+) -> Option<Value> {
+    let scope = (windowed != effective).then(|| applied_filters::InjectedScope {
+        kind: "projects",
+        values: &config.jira_projects_filter,
+        setting: "JIRA_PROJECTS_FILTER",
+    });
+    applied_filters::describe("effective_jql", requested, effective, scope)
+}
+
+/// `jql` split into its conditions and its ORDER BY clause (with a leading
+/// space), so conditions can be wrapped without moving the ordering inside
+pub fn split_order_by(jql: &str) -> (String, Option<String>) {
+    let jql_lower = jql.to_lowercase();
+    if let Some(pos) = jql_lower.find(" order by ") {
+        // ORDER BY found in middle/end of JQL (includes leading space)
+        (jql[..pos].to_string(), Some(jql[pos..].to_string()))
+    } else if jql_lower.starts_with("order by ") {
+        // ORDER BY at start of JQL (add leading space for consistency)
+        (String::new(), Some(format!(" {}", jql)))
+    } else {
+        // No ORDER BY clause
+        (jql.to_string(), None)
+    }
+}
+
+/// Restricts `jql` to `JIRA_PROJECTS_FILTER` unless it already names a project,
+/// keeping any ORDER BY clause at the end
+pub fn apply_projects_filter(jql: &str, config: &Config) -> String {
+    // Split JQL at ORDER BY to avoid placing ORDER BY inside parentheses
+    let (conditions, order_by) = split_order_by(jql);
+
+    // Apply project filter if configured and not already in JQL
+    if !config.jira_projects_filter.is_empty() {
+        let conditions_lower = conditions.to_lowercase();
+        // Check if JQL already contains project condition
+        if conditions_lower.contains("project ")
+            || conditions_lower.contains("project=")
+            || conditions_lower.contains("project in")
+        {
+            // User explicitly specified project, use their JQL as-is
+            jql.to_string()
+        } else {
+            // Add project filter and append ORDER BY at the end
+            let projects = config
+                .jira_projects_filter
+                .iter()
+                .map(|p| format!("\"{}\"", p))
+                .collect::<Vec<_>>()
+                .join(",");
+            // Handle empty conditions (e.g., "ORDER BY created DESC" with no conditions)
+            let base = if conditions.trim().is_empty() {
+                format!("project IN ({})", projects)
+            } else {
+                format!("project IN ({}) AND ({})", projects, conditions.trim())
+            };
+            if let Some(ref order_clause) = order_by {
+                format!("{}{}", base, order_clause)
+            } else {
+                base
+            }
+        }
+    } else {
+        jql.to_string()
     }
 }
 
 #[async_trait]
 impl ToolHandler for SearchHandler {
+    fn name(&self) -> &'static str {
+        "jira_search"
+    }
+
+    fn schema(&self, config: &Config) -> ToolSchema {
+        // Resolve the actual fields that will be used
+        let resolved_fields = field_filtering::resolve_search_fields(None, config);
+        let fields_count = resolved_fields.len();
+        let fields_list = resolved_fields.join(", ");
+
+        let mut props = HashMap::new();
+        props.insert("jql".to_string(), string_prop("JQL query. Must include search condition before ORDER BY (e.g., 'project = KEY ORDER BY created DESC'). ORDER BY only works with orderable fields (dates, versions).", true));
+        props.insert(
+            "limit".to_string(),
+            number_prop("Maximum results (default: 20)", 20),
+        );
+        props.insert(
+            "fields".to_string(),
+            array_prop(&format!(
+                "Optional: Array of field names to return. If not specified, returns {} default fields: {}\n\n\
+                To minimize tokens, specify only the fields you need (e.g., [\"key\",\"summary\",\"status\",\"assignee\"]).",
+                fields_count, fields_list
+            )),
+        );
+        props.insert("format".to_string(), Property::new("string").with_description("Output format: 'json' (default) or 'table' (compact Markdown table with nested fields flattened, e.g. status.name, assignee.displayName; columns empty in every row are left out). Use 'table' for large result sets.").with_default(json!("json")).with_enum(vec![json!("json"), json!("table")]));
+        props.insert("export_format".to_string(), Property::new("string").with_description("'csv' returns CSV text (key plus requested fields, nested values flattened) for pasting into spreadsheets; overrides 'format'").with_enum(vec![json!("csv")]));
+        props.insert("auto_paginate".to_string(), Property::new("boolean").with_description("Fetch every page until the results are exhausted; 'limit' becomes the total (capped at 500). Duplicates are removed and page counts are reported under 'pagination'.").with_default(json!(false)));
+        props.insert("include_excerpts".to_string(), Property::new("boolean").with_description("Add an 'excerpt' per issue: up to 240 characters of the description around the first word of the JQL's ~ clauses, matches in **bold** (json format only; fetches description, which is dropped again unless requested in 'fields')").with_default(json!(false)));
+        relative_dates::window_props(&mut props);
+        let description = match examples::search_examples(config) {
+            Some(examples) => format!("Search Jira issues using JQL. {}", examples),
+            None => "Search Jira issues using JQL".to_string(),
+        };
+        ToolSchema::new(description, props, vec!["jql".to_string()])
+    }
+
     async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
         let jql = args["jql"]
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("Missing jql"))?;
         let limit = args["limit"].as_u64().unwrap_or(20);
+        let output_format = tabular::OutputFormat::from_args(&args)?;
 
         // Extract fields parameter from API call
         let api_fields = args["fields"].as_array().map(|arr| {
@@ -68,59 +471,30 @@ impl ToolHandler for SearchHandler {
                 .collect()
         });
 
-        // Split JQL at ORDER BY to avoid placing ORDER BY inside parentheses
-        let jql_lower = jql.to_lowercase();
-        let (conditions, order_by) = if let Some(pos) = jql_lower.find(" order by ") {
-            // ORDER BY found in middle/end of JQL (includes leading space)
-            (jql[..pos].to_string(), Some(jql[pos..].to_string()))
-        } else if jql_lower.starts_with("order by ") {
-            // ORDER BY at start of JQL (add leading space for consistency)
-            (String::new(), Some(format!(" {}", jql)))
-        } else {
-            // No ORDER BY clause
-            (jql.to_string(), None)
-        };
+        let windowed_jql = relative_dates::apply_relative_windows(jql, &args)?;
+        let final_jql = apply_projects_filter(&windowed_jql, config);
+        // CSV stays pure data
+        let applied = (output_format != tabular::OutputFormat::Csv)
+            .then(|| applied_projects_filter(jql, &windowed_jql, &final_jql, config))
+            .flatten();
 
-        // Apply project filter if configured and not already in JQL
-        let final_jql = if !config.jira_projects_filter.is_empty() {
-            let conditions_lower = conditions.to_lowercase();
-            // Check if JQL already contains project condition
-            if conditions_lower.contains("project ")
-                || conditions_lower.contains("project=")
-                || conditions_lower.contains("project in")
-            {
-                // User explicitly specified project, use their JQL as-is
-                jql.to_string()
-            } else {
-                // Add project filter and append ORDER BY at the end
-                let projects = config
-                    .jira_projects_filter
-                    .iter()
-                    .map(|p| format!("\"{}\"", p))
-                    .collect::<Vec<_>>()
-                    .join(",");
-                // Handle empty conditions (e.g., "ORDER BY created DESC" with no conditions)
-                let base = if conditions.trim().is_empty() {
-                    format!("project IN ({})", projects)
-                } else {
-                    format!("project IN ({}) AND ({})", projects, conditions.trim())
-                };
-                if let Some(ref order_clause) = order_by {
-                    format!("{}{}", base, order_clause)
-                } else {
-                    base
-                }
-            }
-        } else {
-            jql.to_string()
-        };
-
-        let client = create_atlassian_client(config);
-        let base_url = config.get_atlassian_base_url();
-        let url = format!("{}/rest/api/3/search/jql", base_url);
+        let jira = Jira::new(config);
 
         // Resolve fields using priority hierarchy
-        let fields = field_filtering::resolve_search_fields(api_fields, config);
+        let mut fields = field_filtering::resolve_search_fields(api_fields, config);
+
+        let excerpts = (args["include_excerpts"].as_bool() == Some(true)
+            && output_format == tabular::OutputFormat::Json)
+            .then(|| {
+                let strip_description = !fields.iter().any(|f| f == "description");
+                if strip_description {
+                    fields.push("description".to_string());
+                }
+                excerpts::IssueExcerpts {
+                    terms: excerpts::search_terms(&final_jql),
+                    strip_description,
+                }
+            });
 
         tracing::info!(
             "Jira search JQL: {}, {} fields: {}",
@@ -129,125 +503,198 @@ impl ToolHandler for SearchHandler {
             fields.join(",")
         );
 
-        let query_params = vec![
-            ("jql".to_string(), final_jql),
-            ("maxResults".to_string(), limit.to_string()),
-            ("fields".to_string(), fields.join(",")),
-        ];
-
-        let response = client
-            .get(&url)
-            .header("Authorization", create_auth_header(config))
-            .header("Accept", "application/json")
-            .query(&query_params)
-            .send()
-            .await?;
+        if args["auto_paginate"].as_bool() == Some(true) {
+            let result = search_all(&jira, &final_jql, &fields, &args, output_format).await?;
+            let result = match &excerpts {
+                Some(excerpts) => excerpts.apply(result),
+                None => result,
+            };
+            return Ok(applied_filters::attach(result, applied));
+        }
 
-        if !response.status().is_success() {
-            let error = response.text().await?;
-            anyhow::bail!("Search failed: {}", error);
+        let page = jira.search(&final_jql, &fields, limit, None).await?;
+        let total = page.total;
+        let issues = issues_json(page)?;
+
+        if output_format == tabular::OutputFormat::Csv {
+            return Ok(Value::String(tabular::issues_to_csv(&issues, &fields)));
+        }
+        if output_format == tabular::OutputFormat::Table {
+            let mut table = tabular::issues_to_markdown_table(&issues, &fields);
+            if let Some(total) = total {
+                table.push_str(&format!("\n{} of {} issues\n", issues.len(), total));
+            }
+            return Ok(applied_filters::attach(Value::String(table), applied));
         }
 
-        let data: Value = response.json().await?;
-        Ok(json!({
+        let result = json!({
             "success": true,
-            "issues": data["issues"],
-            "total": data["total"]
-        }))
+            "issues": issues,
+            "total": total
+        });
+        let result = match &excerpts {
+            Some(excerpts) => excerpts.apply(result),
+            None => result,
+        };
+        Ok(applied_filters::attach(result, applied))
     }
 }
 
+/// `jira_search` with CSV output, paging through up to the auto-pagination cap
 #[async_trait]
-impl ToolHandler for CreateIssueHandler {
-    async fn execute(&self, mut args: Value, config: &Config) -> Result<Value> {
-        let client = create_atlassian_client(config);
-        let base_url = format!("{}/rest/api/3/issue", config.get_atlassian_base_url());
+impl ToolHandler for ExportSearchHandler {
+    fn name(&self) -> &'static str {
+        "jira_export_search"
+    }
 
-        let url = field_filtering::apply_field_filtering_to_url(&base_url);
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "jql".to_string(),
+            string_prop("JQL query (same scoping as jira_search)", true),
+        );
+        props.insert(
+            "limit".to_string(),
+            number_prop("Max issues across all pages (capped at 500)", 500),
+        );
+        props.insert(
+            "fields".to_string(),
+            array_prop("Columns after 'key' (default: the jira_search default fields)"),
+        );
+        relative_dates::window_props(&mut props);
+        ToolSchema::new(
+            "Export JQL search results as CSV text, paging through all results up to the limit",
+            props,
+            vec!["jql".to_string()],
+        )
+    }
 
-        // Process description input - supports both string and ADF object (zero-copy via take)
-        let description_value = args
-            .get_mut("description")
-            .map(|v| std::mem::replace(v, Value::Null))
-            .unwrap_or(Value::Null);
-        let description_adf = adf_utils::process_description_input(description_value)?;
+    async fn execute(&self, mut args: Value, config: &Config) -> Result<Value> {
+        args["export_format"] = json!("csv");
+        args["auto_paginate"] = json!(true);
+        if args["limit"].is_null() {
+            args["limit"] = json!(pagination::MAX_AUTO_PAGINATE_ITEMS);
+        }
+        SearchHandler.execute(args, config).await
+    }
+}
 
-        let body = json!({
-            "fields": {
-                "project": {
-                    "key": args["project_key"]
-                },
-                "summary": args["summary"],
-                "issuetype": {
-                    "name": args["issue_type"]
-                },
-                "description": description_adf
-            }
-        });
+#[async_trait]
+impl ToolHandler for CreateIssueHandler {
+    fn name(&self) -> &'static str {
+        "jira_create_issue"
+    }
 
-        let response = client
-            .post(&url)
-            .header("Authorization", create_auth_header(config))
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await?;
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("project_key".to_string(), string_prop("Project key", true));
+        props.insert("summary".to_string(), string_prop("Issue summary", true));
+        props.insert(
+            "issue_type".to_string(),
+            string_prop("Issue type name (e.g., 'Task', 'Bug', 'Story').", true),
+        );
+        props.insert(
+            "description".to_string(),
+            union_prop(
+                "Issue description - accepts plain text (string, auto-converted to ADF) or ADF object",
+                vec!["string", "object"],
+            ),
+        );
+        props.insert(
+            "markdown".to_string(),
+            markdown_prop(
+                "Parse a string description as Markdown (the syntax jira_get_issue emits with markdown: true)",
+            ),
+        );
+        ToolSchema::new(
+            "Create Jira issue",
+            props,
+            vec![
+                "project_key".to_string(),
+                "summary".to_string(),
+                "issue_type".to_string(),
+            ],
+        )
+    }
 
-        if !response.status().is_success() {
-            let error = response.text().await?;
-            anyhow::bail!("Failed to create issue: {}", error);
-        }
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let args: CreateIssueArgs = parse_args(&args)?;
+        write_policy::check_project(config, &args.project_key)?;
+
+        // Process description input - supports both string and ADF object
+        let description_adf = if args.markdown {
+            adf_utils::process_markdown_input(args.description, "description")?
+        } else {
+            adf_utils::process_description_input(args.description)?
+        };
 
-        let data: Value = response.json().await?;
+        let created = Jira::new(config)
+            .create_issue(
+                &args.project_key,
+                &args.summary,
+                &args.issue_type,
+                description_adf,
+            )
+            .await?;
         Ok(json!({
             "success": true,
-            "key": data["key"],
-            "id": data["id"]
+            "key": created.key,
+            "id": created.id
         }))
     }
 }
 
 #[async_trait]
 impl ToolHandler for UpdateIssueHandler {
-    async fn execute(&self, mut args: Value, config: &Config) -> Result<Value> {
-        let issue_key = args["issue_key"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing issue_key"))?
-            .to_string();
+    fn name(&self) -> &'static str {
+        "jira_update_issue"
+    }
 
-        let client = create_atlassian_client(config);
-        let url = format!(
-            "{}/rest/api/3/issue/{}",
-            config.get_atlassian_base_url(),
-            issue_key
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("issue_key".to_string(), string_prop("Issue key", true));
+        props.insert("fields".to_string(), update_fields_prop());
+        props.insert(
+            "markdown".to_string(),
+            markdown_prop(
+                "Parse a string description as Markdown (the syntax jira_get_issue emits with markdown: true)",
+            ),
         );
+        ToolSchema::new(
+            "Update Jira issue",
+            props,
+            vec!["issue_key".to_string(), "fields".to_string()],
+        )
+    }
 
-        // Process fields - handle description with ADF support if present (zero-copy via take)
-        #[allow(clippy::collapsible_if)]
-        if let Some(fields) = args.get_mut("fields") {
-            if let Some(description_ref) = fields.get_mut("description") {
-                // Extract description value (zero-copy via mem::replace)
-                let description = std::mem::replace(description_ref, Value::Null);
-                // Process description input - supports both string and ADF object
-                let description_adf = adf_utils::process_description_input(description)?;
-                fields["description"] = description_adf;
-            }
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let UpdateIssueArgs {
+            issue_key,
+            mut fields,
+            markdown,
+        } = parse_args(&args)?;
+        let jira = Jira::new(config);
+        write_policy::check_issue(&jira, config, &issue_key).await?;
+
+        // Process fields - handle description with ADF support if present
+        if let Some(description) = fields.remove("description") {
+            // Process description input - supports string, Markdown and ADF object
+            let description_adf = if markdown {
+                adf_utils::process_markdown_input(description, "description")?
+            } else {
+                adf_utils::process_description_input(description)?
+            };
+            fields.insert("description".to_string(), description_adf);
         }
-
-        let response = client
-            .put(&url)
-            .header("Authorization", create_auth_header(config))
-            .header("Content-Type", "application/json")
-            .json(&json!({
-                "fields": args["fields"]
-            }))
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to update issue: {}", response.status());
+        // `tomorrow`, `next friday`, ... in the user's timezone
+        if let Some(Value::String(due)) = fields.get("duedate") {
+            let zone = time::user_timezone(config)?;
+            let due = time::jira_date(due, &zone)?;
+            fields.insert("duedate".to_string(), json!(due));
         }
 
+        jira.update_issue(&issue_key, fields).await?;
+
         Ok(json!({
             "success": true,
             "message": format!("Issue {} updated", issue_key)
@@ -257,140 +704,178 @@ impl ToolHandler for UpdateIssueHandler {
 
 #[async_trait]
 impl ToolHandler for AddCommentHandler {
-    async fn execute(&self, mut args: Value, config: &Config) -> Result<Value> {
-        let issue_key = args["issue_key"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing issue_key"))?
-            .to_string();
-
-        // Process comment input - supports both string and ADF object (zero-copy via take)
-        let comment_value = args
-            .get_mut("comment")
-            .map(|v| std::mem::replace(v, Value::Null))
-            .unwrap_or(Value::Null);
-        let comment_adf = adf_utils::process_comment_input(comment_value)?;
+    fn name(&self) -> &'static str {
+        "jira_add_comment"
+    }
 
-        let client = create_atlassian_client(config);
-        let base_url = format!(
-            "{}/rest/api/3/issue/{}/comment",
-            config.get_atlassian_base_url(),
-            issue_key
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("issue_key".to_string(), string_prop("Issue key", true));
+        props.insert(
+            "comment".to_string(),
+            union_prop(
+                "Comment text - accepts plain text (string, auto-converted to ADF; `@Display Name` or `@[Full Name]` become mentions) or ADF object",
+                vec!["string", "object"],
+            ),
+        );
+        props.insert(
+            "reply_to".to_string(),
+            string_prop(
+                "Comment ID to reply to; the reply mentions its author and quotes it",
+                false,
+            ),
         );
+        props.insert("visibility".to_string(), visibility_prop());
+        props.insert(
+            "markdown".to_string(),
+            markdown_prop(
+                "Parse a string comment as Markdown; mentions then use [@Name](mention:ACCOUNT_ID)",
+            ),
+        );
+        ToolSchema::new(
+            "Add comment to Jira issue",
+            props,
+            vec!["issue_key".to_string(), "comment".to_string()],
+        )
+    }
 
-        let url = field_filtering::apply_field_filtering_to_url(&base_url);
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let args: AddCommentArgs = parse_args(&args)?;
+        let issue_key = args.issue_key;
+
+        // Process comment input - supports both string and ADF object
+        let jira = Jira::new(config);
+        write_policy::check_issue(&jira, config, &issue_key).await?;
+        let client = jira.http();
+        let (comment_adf, unresolved_mentions) = match args.comment {
+            Value::String(text) if args.markdown => (adf_utils::markdown_to_adf(&text), Vec::new()),
+            Value::String(text) => user_mentions::comment_adf(client, config, &text).await?,
+            other => (adf_utils::process_comment_input(other)?, Vec::new()),
+        };
 
-        let body = json!({
-            "body": comment_adf
-        });
+        let comment_adf = match args.reply_to.as_deref() {
+            Some(parent_id) => {
+                let parent = jira.get_comment(&issue_key, parent_id).await?;
+                comments::reply_adf(&parent, comment_adf)
+            }
+            None => comment_adf,
+        };
+        let visibility =
+            comments::resolve_visibility(client, config, &issue_key, &args.visibility).await?;
 
-        let response = client
-            .post(&url)
-            .header("Authorization", create_auth_header(config))
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
+        let comment = jira
+            .add_comment(&issue_key, comment_adf, visibility)
             .await?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to add comment: {}", response.status());
+        let mut result = comments::comment_summary(&comment);
+        result["success"] = json!(true);
+        if let Some(parent_id) = args.reply_to {
+            result["reply_to"] = json!(parent_id);
         }
-
-        let data: Value = response.json().await?;
-        Ok(json!({
-            "success": true,
-            "comment_id": data["id"]
-        }))
+        if !unresolved_mentions.is_empty() {
+            result["unresolved_mentions"] = json!(unresolved_mentions);
+        }
+        Ok(result)
     }
 }
 
 #[async_trait]
 impl ToolHandler for UpdateCommentHandler {
-    async fn execute(&self, mut args: Value, config: &Config) -> Result<Value> {
-        let issue_key = args["issue_key"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing issue_key"))?
-            .to_string();
-        let comment_id = args["comment_id"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing comment_id"))?
-            .to_string();
-
-        // Process comment body input - supports both string and ADF object (zero-copy via take)
-        let body_value = args
-            .get_mut("body")
-            .map(|v| std::mem::replace(v, Value::Null))
-            .unwrap_or(Value::Null);
-        let body_adf = adf_utils::process_comment_input(body_value)?;
+    fn name(&self) -> &'static str {
+        "jira_update_comment"
+    }
 
-        let client = create_atlassian_client(config);
-        let base_url = format!(
-            "{}/rest/api/3/issue/{}/comment/{}",
-            config.get_atlassian_base_url(),
-            issue_key,
-            comment_id
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "issue_key".to_string(),
+            string_prop("Issue key (e.g., 'PROJ-123')", true),
         );
+        props.insert(
+            "comment_id".to_string(),
+            string_prop(
+                "Comment ID to update (obtained from comment object's 'id' field)",
+                true,
+            ),
+        );
+        props.insert(
+            "body".to_string(),
+            union_prop(
+                "Comment body - accepts plain text (string, auto-converted to ADF; `@Display Name` or `@[Full Name]` become mentions) or ADF object",
+                vec!["string", "object"],
+            ),
+        );
+        props.insert("visibility".to_string(), visibility_prop());
+        props.insert(
+            "markdown".to_string(),
+            markdown_prop(
+                "Parse a string body as Markdown; mentions then use [@Name](mention:ACCOUNT_ID)",
+            ),
+        );
+        ToolSchema::new(
+            "Update an existing comment on a Jira issue with rich text formatting (ADF)",
+            props,
+            vec![
+                "issue_key".to_string(),
+                "comment_id".to_string(),
+                "body".to_string(),
+            ],
+        )
+    }
 
-        let url = field_filtering::apply_field_filtering_to_url(&base_url);
-
-        let body = json!({
-            "body": body_adf
-        });
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let args: UpdateCommentArgs = parse_args(&args)?;
+        let (issue_key, comment_id) = (args.issue_key, args.comment_id);
+
+        // Process comment body input - supports both string and ADF object
+        let jira = Jira::new(config);
+        write_policy::check_issue(&jira, config, &issue_key).await?;
+        let client = jira.http();
+        let (body_adf, unresolved_mentions) = match args.body {
+            Value::String(text) if args.markdown => (adf_utils::markdown_to_adf(&text), Vec::new()),
+            Value::String(text) => user_mentions::comment_adf(client, config, &text).await?,
+            other => (adf_utils::process_comment_input(other)?, Vec::new()),
+        };
+        let visibility =
+            comments::resolve_visibility(client, config, &issue_key, &args.visibility).await?;
 
-        let response = client
-            .put(&url)
-            .header("Authorization", create_auth_header(config))
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
+        let comment = jira
+            .update_comment(&issue_key, &comment_id, body_adf, visibility)
             .await?;
-
-        if !response.status().is_success() {
-            let error = response.text().await?;
-            anyhow::bail!("Failed to update comment: {}", error);
+        let mut result = comments::comment_summary(&comment);
+        result["success"] = json!(true);
+        if !unresolved_mentions.is_empty() {
+            result["unresolved_mentions"] = json!(unresolved_mentions);
         }
-
-        let data: Value = response.json().await?;
-        Ok(json!({
-            "success": true,
-            "comment_id": data["id"]
-        }))
+        Ok(result)
     }
 }
 
 #[async_trait]
 impl ToolHandler for TransitionIssueHandler {
-    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
-        let issue_key = args["issue_key"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing issue_key"))?;
-        let transition_id = args["transition_id"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing transition_id"))?;
-
-        let client = create_atlassian_client(config);
-        let url = format!(
-            "{}/rest/api/3/issue/{}/transitions",
-            config.get_atlassian_base_url(),
-            issue_key
-        );
+    fn name(&self) -> &'static str {
+        "jira_transition_issue"
+    }
 
-        let body = json!({
-            "transition": {
-                "id": transition_id
-            }
-        });
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("issue_key".to_string(), string_prop("Issue key", true));
+        props.insert("transition_id".to_string(), string_prop("Transition ID. Get available transition IDs using jira_get_transitions for the issue's current status.", true));
+        ToolSchema::new(
+            "Transition Jira issue status",
+            props,
+            vec!["issue_key".to_string(), "transition_id".to_string()],
+        )
+    }
 
-        let response = client
-            .post(&url)
-            .header("Authorization", create_auth_header(config))
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await?;
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let TransitionIssueArgs {
+            issue_key,
+            transition_id,
+        } = parse_args(&args)?;
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to transition issue: {}", response.status());
-        }
+        let jira = Jira::new(config);
+        write_policy::check_issue(&jira, config, &issue_key).await?;
+        jira.transition_issue(&issue_key, &transition_id).await?;
 
         Ok(json!({
             "success": true,
@@ -401,35 +886,27 @@ impl ToolHandler for TransitionIssueHandler {
 
 #[async_trait]
 impl ToolHandler for GetTransitionsHandler {
-    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
-        let issue_key = args["issue_key"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing issue_key"))?;
-
-        let client = create_atlassian_client(config);
-        let base_url = format!(
-            "{}/rest/api/3/issue/{}/transitions",
-            config.get_atlassian_base_url(),
-            issue_key
-        );
-
-        let url = field_filtering::apply_field_filtering_to_url(&base_url);
+    fn name(&self) -> &'static str {
+        "jira_get_transitions"
+    }
 
-        let response = client
-            .get(&url)
-            .header("Authorization", create_auth_header(config))
-            .header("Accept", "application/json")
-            .send()
-            .await?;
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("issue_key".to_string(), string_prop("Issue key", true));
+        ToolSchema::new(
+            "Get Jira issue transitions",
+            props,
+            vec!["issue_key".to_string()],
+        )
+    }
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to get transitions: {}", response.status());
-        }
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let IssueKeyArgs { issue_key } = parse_args(&args)?;
 
-        let data: Value = response.json().await?;
+        let transitions = Jira::new(config).get_transitions(&issue_key).await?;
         Ok(json!({
             "success": true,
-            "transitions": data["transitions"]
+            "transitions": transitions
         }))
     }
 }
@@ -438,24 +915,19 @@ impl ToolHandler for GetTransitionsHandler {
 mod tests {
     use super::*;
     use crate::config::Config;
+    use crate::test_support::ConfigBuilder;
 
     // Helper function to create test config
     fn create_test_config(
         jira_projects_filter: Vec<String>,
         jira_search_default_fields: Option<Vec<String>>,
     ) -> Config {
-        Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token123".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter,
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        }
+        ConfigBuilder::new()
+            .with(|config| {
+                config.jira_projects_filter = jira_projects_filter;
+                config.jira_search_default_fields = jira_search_default_fields;
+            })
+            .build()
     }
 
     // T013: Jira SearchHandler tests
@@ -474,6 +946,20 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Missing jql"));
     }
 
+    #[test]
+    fn test_search_handler_invalid_format() {
+        // Unknown output formats are rejected before any HTTP call
+        let handler = SearchHandler;
+        let config = create_test_config(vec![], None);
+        let args = json!({"jql": "status = Open", "format": "xml"});
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(handler.execute(args, &config));
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Invalid format"));
+    }
+
     #[test]
     fn test_search_handler_default_limit() {
         // Test that default limit is 20 when not specified
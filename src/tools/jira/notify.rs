@@ -0,0 +1,276 @@
+//! Issue notification emails (`POST /rest/api/3/issue/{key}/notify`)
+//!
+//! Subject and body support `{{placeholder}}` substitution from the issue:
+//! `issue_key`, `summary`, `status`, `assignee`, `reporter`, `priority`, `url`.
+//! The issue is only fetched when a placeholder other than `issue_key`/`url`
+//! is present.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Map, Value, json};
+
+use crate::client::Jira;
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{ToolSchema, array_prop, string_prop};
+use crate::tools::write_policy;
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+use super::tabular::flatten_field;
+
+/// Placeholders resolved from issue fields (`issue_key` and `url` need no fetch)
+const FIELD_PLACEHOLDERS: &[(&str, &str)] = &[
+    ("summary", "summary"),
+    ("status", "status"),
+    ("assignee", "assignee"),
+    ("reporter", "reporter"),
+    ("priority", "priority"),
+];
+
+/// Role recipients accepted by the notify API
+const ROLE_RECIPIENTS: &[&str] = &["reporter", "assignee", "watchers", "voters"];
+
+pub struct NotifyHandler;
+
+/// Issue fields referenced by `templates`, as Jira field names
+pub fn referenced_fields(templates: &[&str]) -> Vec<&'static str> {
+    FIELD_PLACEHOLDERS
+        .iter()
+        .filter(|(placeholder, _)| {
+            let token = format!("{{{{{}}}}}", placeholder);
+            templates.iter().any(|t| t.contains(&token))
+        })
+        .map(|(_, field)| *field)
+        .collect()
+}
+
+/// Substitutes `{{placeholder}}` tokens. Unknown placeholders are left as-is.
+pub fn render_template(
+    template: &str,
+    issue_key: &str,
+    browse_url: &str,
+    fields: &Value,
+) -> String {
+    let mut out = template
+        .replace("{{issue_key}}", issue_key)
+        .replace("{{url}}", browse_url);
+    for (placeholder, field) in FIELD_PLACEHOLDERS {
+        let token = format!("{{{{{}}}}}", placeholder);
+        if out.contains(&token) {
+            out = out.replace(&token, &flatten_field(&fields[*field]));
+        }
+    }
+    out
+}
+
+/// Builds the `to` object from role flags, account ids and group names
+pub fn build_recipients(args: &Value) -> Result<Value> {
+    let mut to = Map::new();
+
+    if let Some(roles) = args["roles"].as_array() {
+        for role in roles {
+            let role = role.as_str().unwrap_or_default();
+            if !ROLE_RECIPIENTS.contains(&role) {
+                anyhow::bail!(
+                    "Invalid role '{}': expected one of {}",
+                    role,
+                    ROLE_RECIPIENTS.join(", ")
+                );
+            }
+            to.insert(role.to_string(), Value::Bool(true));
+        }
+    }
+
+    let ids = |name: &str, key: &str| -> Vec<Value> {
+        args[name]
+            .as_array()
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(Value::as_str)
+                    .map(|id| json!({ key: id }))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let users = ids("account_ids", "accountId");
+    if !users.is_empty() {
+        to.insert("users".to_string(), Value::Array(users));
+    }
+    let groups = ids("groups", "name");
+    if !groups.is_empty() {
+        to.insert("groups".to_string(), Value::Array(groups));
+    }
+
+    if to.is_empty() {
+        anyhow::bail!("No recipients: provide roles, account_ids or groups");
+    }
+    Ok(Value::Object(to))
+}
+
+#[async_trait]
+impl ToolHandler for NotifyHandler {
+    fn name(&self) -> &'static str {
+        "jira_notify"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("issue_key".to_string(), string_prop("Issue key", true));
+        props.insert(
+            "subject".to_string(),
+            string_prop("Email subject. Placeholders: {{issue_key}}, {{summary}}, {{status}}, {{assignee}}, {{reporter}}, {{priority}}, {{url}}", true),
+        );
+        props.insert(
+            "body".to_string(),
+            string_prop("Plain-text email body (same placeholders as subject)", true),
+        );
+        props.insert(
+            "roles".to_string(),
+            array_prop("Role recipients: reporter, assignee, watchers, voters"),
+        );
+        props.insert(
+            "account_ids".to_string(),
+            array_prop("User account IDs to notify"),
+        );
+        props.insert("groups".to_string(), array_prop("Group names to notify"));
+        ToolSchema::new(
+            "Send a notification email about a Jira issue (at least one recipient required)",
+            props,
+            vec![
+                "issue_key".to_string(),
+                "subject".to_string(),
+                "body".to_string(),
+            ],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let issue_key = args["issue_key"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing issue_key"))?;
+        let subject = args["subject"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing subject"))?;
+        let body = args["body"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing body"))?;
+        let to = build_recipients(&args)?;
+
+        let client = create_atlassian_client(config);
+        write_policy::check_issue(
+            &Jira::with_client(config, client.clone()),
+            config,
+            issue_key,
+        )
+        .await?;
+        let base_url = config.get_atlassian_base_url();
+        let browse_url = format!("{}/browse/{}", base_url, issue_key);
+
+        // Only fetch the issue when the templates reference its fields
+        let fields_needed = referenced_fields(&[subject, body]);
+        let fields = if fields_needed.is_empty() {
+            Value::Null
+        } else {
+            let response = client
+                .get(format!("{}/rest/api/3/issue/{}", base_url, issue_key))
+                .header("Authorization", create_auth_header(config))
+                .header("Accept", "application/json")
+                .query(&[("fields", fields_needed.join(","))])
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                anyhow::bail!("Failed to get issue for template: {}", response.status());
+            }
+
+            let mut issue: Value = response.json().await?;
+            issue["fields"].take()
+        };
+
+        let subject = render_template(subject, issue_key, &browse_url, &fields);
+        let text_body = render_template(body, issue_key, &browse_url, &fields);
+
+        let request_body = json!({
+            "subject": subject,
+            "textBody": text_body,
+            "to": to
+        });
+
+        let url = format!("{}/rest/api/3/issue/{}/notify", base_url, issue_key);
+        let response = client
+            .post(&url)
+            .header("Authorization", create_auth_header(config))
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to send notification: {}", error);
+        }
+
+        Ok(json!({
+            "success": true,
+            "issue_key": issue_key,
+            "subject": subject,
+            "to": to
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let fields = json!({
+            "summary": "Login broken",
+            "status": {"name": "In Review"},
+            "assignee": {"displayName": "Jane Doe"}
+        });
+        let out = render_template(
+            "[{{issue_key}}] {{summary}} is {{status}} ({{assignee}}) {{url}} {{unknown}}",
+            "PROJ-1",
+            "https://x.atlassian.net/browse/PROJ-1",
+            &fields,
+        );
+        assert_eq!(
+            out,
+            "[PROJ-1] Login broken is In Review (Jane Doe) https://x.atlassian.net/browse/PROJ-1 {{unknown}}"
+        );
+    }
+
+    #[test]
+    fn test_referenced_fields() {
+        assert!(referenced_fields(&["Please look at {{issue_key}} {{url}}"]).is_empty());
+        assert_eq!(
+            referenced_fields(&["{{summary}}", "now {{status}}"]),
+            vec!["summary", "status"]
+        );
+    }
+
+    #[test]
+    fn test_build_recipients() {
+        let args = json!({
+            "roles": ["assignee", "watchers"],
+            "account_ids": ["abc123"],
+            "groups": ["jira-admins"]
+        });
+        let to = build_recipients(&args).unwrap();
+        assert_eq!(to["assignee"], true);
+        assert_eq!(to["watchers"], true);
+        assert_eq!(to["users"][0]["accountId"], "abc123");
+        assert_eq!(to["groups"][0]["name"], "jira-admins");
+    }
+
+    #[test]
+    fn test_build_recipients_validation() {
+        assert!(build_recipients(&json!({})).is_err());
+        let result = build_recipients(&json!({"roles": ["everyone"]}));
+        assert!(result.unwrap_err().to_string().contains("Invalid role"));
+    }
+}
@@ -0,0 +1,325 @@
+//! Issue entity properties
+//!
+//! Entity properties are app-specific JSON documents stored against an issue
+//! (`/rest/api/3/issue/{key}/properties/{propertyKey}`). They are not fields:
+//! regular issue updates neither read nor write them.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::client::Jira;
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{ToolSchema, string_prop, union_prop};
+use crate::tools::write_policy;
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+/// Jira rejects property keys longer than this
+pub const MAX_PROPERTY_KEY_LEN: usize = 255;
+/// Jira rejects property values larger than this (serialized JSON)
+pub const MAX_PROPERTY_VALUE_BYTES: usize = 32 * 1024;
+
+pub struct GetIssuePropertiesHandler;
+pub struct SetIssuePropertyHandler;
+pub struct DeleteIssuePropertyHandler;
+
+/// Builds the properties URL, percent-encoding the issue and property keys
+pub fn properties_url(
+    config: &Config,
+    issue_key: &str,
+    property_key: Option<&str>,
+) -> Result<String> {
+    let mut url = reqwest::Url::parse(config.get_atlassian_base_url())?;
+    {
+        let mut segments = url
+            .path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("Invalid base URL"))?;
+        segments.pop_if_empty();
+        segments.extend(["rest", "api", "3", "issue", issue_key, "properties"]);
+        if let Some(key) = property_key {
+            segments.push(key);
+        }
+    }
+    Ok(url.to_string())
+}
+
+fn property_key(args: &Value) -> Result<&str> {
+    let key = args["property_key"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing property_key"))?;
+    if key.is_empty() || key.len() > MAX_PROPERTY_KEY_LEN {
+        anyhow::bail!("property_key must be 1-{} characters", MAX_PROPERTY_KEY_LEN);
+    }
+    Ok(key)
+}
+
+fn issue_key(args: &Value) -> Result<&str> {
+    args["issue_key"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing issue_key"))
+}
+
+#[async_trait]
+impl ToolHandler for GetIssuePropertiesHandler {
+    fn name(&self) -> &'static str {
+        "jira_get_issue_properties"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("issue_key".to_string(), string_prop("Issue key", true));
+        props.insert(
+            "property_key".to_string(),
+            string_prop("Property key; omit to list all keys", false),
+        );
+        ToolSchema::new(
+            "Get Jira issue entity properties (app metadata, not fields)",
+            props,
+            vec!["issue_key".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let issue_key = issue_key(&args)?;
+        // Without property_key, list the keys; with it, fetch the value
+        let property_key = match args["property_key"] {
+            Value::Null => None,
+            _ => Some(property_key(&args)?),
+        };
+
+        let client = create_atlassian_client(config);
+        let url = properties_url(config, issue_key, property_key)?;
+
+        let response = client
+            .get(&url)
+            .header("Authorization", create_auth_header(config))
+            .header("Accept", "application/json")
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND
+            && let Some(key) = property_key
+        {
+            anyhow::bail!("Property '{}' not found on {}", key, issue_key);
+        }
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to get issue properties: {}", response.status());
+        }
+
+        let data: Value = response.json().await?;
+
+        match property_key {
+            Some(key) => Ok(json!({
+                "success": true,
+                "property_key": key,
+                "value": data["value"]
+            })),
+            None => {
+                let keys: Vec<&Value> = data["keys"]
+                    .as_array()
+                    .map(|keys| keys.iter().map(|k| &k["key"]).collect())
+                    .unwrap_or_default();
+                Ok(json!({
+                    "success": true,
+                    "keys": keys
+                }))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for SetIssuePropertyHandler {
+    fn name(&self) -> &'static str {
+        "jira_set_issue_property"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("issue_key".to_string(), string_prop("Issue key", true));
+        props.insert(
+            "property_key".to_string(),
+            string_prop("Property key (max 255 chars)", true),
+        );
+        props.insert(
+            "value".to_string(),
+            union_prop(
+                "JSON value to store (max 32KB)",
+                vec!["object", "array", "string", "number", "boolean"],
+            ),
+        );
+        ToolSchema::new(
+            "Create or replace a Jira issue entity property",
+            props,
+            vec![
+                "issue_key".to_string(),
+                "property_key".to_string(),
+                "value".to_string(),
+            ],
+        )
+    }
+
+    async fn execute(&self, mut args: Value, config: &Config) -> Result<Value> {
+        let value = args
+            .get_mut("value")
+            .map(|v| std::mem::replace(v, Value::Null))
+            .filter(|v| !v.is_null())
+            .ok_or_else(|| anyhow::anyhow!("Missing value"))?;
+        let issue_key = issue_key(&args)?;
+        let property_key = property_key(&args)?;
+
+        let body = serde_json::to_vec(&value)?;
+        if body.len() > MAX_PROPERTY_VALUE_BYTES {
+            anyhow::bail!(
+                "Property value is {} bytes; Jira allows at most {}",
+                body.len(),
+                MAX_PROPERTY_VALUE_BYTES
+            );
+        }
+
+        let jira = Jira::new(config);
+        write_policy::check_issue(&jira, config, issue_key).await?;
+        let url = properties_url(config, issue_key, Some(property_key))?;
+
+        let response = jira
+            .http()
+            .put(&url)
+            .header("Authorization", create_auth_header(config))
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to set issue property: {}", error);
+        }
+
+        // 201 when created, 200 when an existing value was replaced
+        let created = response.status() == reqwest::StatusCode::CREATED;
+        Ok(json!({
+            "success": true,
+            "issue_key": issue_key,
+            "property_key": property_key,
+            "created": created
+        }))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for DeleteIssuePropertyHandler {
+    fn name(&self) -> &'static str {
+        "jira_delete_issue_property"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("issue_key".to_string(), string_prop("Issue key", true));
+        props.insert(
+            "property_key".to_string(),
+            string_prop("Property key", true),
+        );
+        ToolSchema::new(
+            "Delete a Jira issue entity property",
+            props,
+            vec!["issue_key".to_string(), "property_key".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let issue_key = issue_key(&args)?;
+        let property_key = property_key(&args)?;
+
+        let jira = Jira::new(config);
+        write_policy::check_issue(&jira, config, issue_key).await?;
+        let url = properties_url(config, issue_key, Some(property_key))?;
+
+        let response = jira
+            .http()
+            .delete(&url)
+            .header("Authorization", create_auth_header(config))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to delete issue property: {}", error);
+        }
+
+        Ok(json!({
+            "success": true,
+            "issue_key": issue_key,
+            "property_key": property_key
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ConfigBuilder;
+
+    fn create_test_config() -> Config {
+        ConfigBuilder::new().build()
+    }
+
+    #[test]
+    fn test_properties_url() {
+        let config = create_test_config();
+        assert_eq!(
+            properties_url(&config, "PROJ-1", None).unwrap(),
+            "https://test.atlassian.net/rest/api/3/issue/PROJ-1/properties"
+        );
+        assert_eq!(
+            properties_url(&config, "PROJ-1", Some("com.example.flags")).unwrap(),
+            "https://test.atlassian.net/rest/api/3/issue/PROJ-1/properties/com.example.flags"
+        );
+    }
+
+    #[test]
+    fn test_properties_url_encodes_key() {
+        let config = create_test_config();
+        let url = properties_url(&config, "PROJ-1", Some("a/b c")).unwrap();
+        assert!(url.ends_with("/properties/a%2Fb%20c"));
+    }
+
+    #[test]
+    fn test_property_key_validation() {
+        assert!(property_key(&json!({})).is_err());
+        assert!(property_key(&json!({"property_key": ""})).is_err());
+        let long = "k".repeat(MAX_PROPERTY_KEY_LEN + 1);
+        assert!(property_key(&json!({"property_key": long})).is_err());
+        assert_eq!(property_key(&json!({"property_key": "ok"})).unwrap(), "ok");
+    }
+
+    #[test]
+    fn test_set_property_missing_value() {
+        let handler = SetIssuePropertyHandler;
+        let config = create_test_config();
+        let args = json!({"issue_key": "PROJ-1", "property_key": "flags"});
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(handler.execute(args, &config));
+
+        assert!(result.unwrap_err().to_string().contains("Missing value"));
+    }
+
+    #[test]
+    fn test_set_property_rejects_oversized_value() {
+        let handler = SetIssuePropertyHandler;
+        let config = create_test_config();
+        let args = json!({
+            "issue_key": "PROJ-1",
+            "property_key": "blob",
+            "value": "x".repeat(MAX_PROPERTY_VALUE_BYTES)
+        });
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(handler.execute(args, &config));
+
+        assert!(result.unwrap_err().to_string().contains("allows at most"));
+    }
+}
@@ -0,0 +1,132 @@
+//! Relative date windows for JQL searches
+//!
+//! Models often get JQL date math wrong (`updated > 7d`, `updated >= now() - 7`,
+//! a hard-coded date from their training cutoff). `updated_within: "7d"`
+//! says the same thing as an argument and is turned into `updated >= -7d`
+//! here, ANDed with the caller's conditions and ahead of any ORDER BY.
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::tools::args::InvalidArgs;
+use crate::tools::schema::Property;
+
+/// `(argument, JQL field)`
+const WINDOW_ARGS: &[(&str, &str)] = &[
+    ("updated_within", "updated"),
+    ("created_within", "created"),
+    ("resolved_within", "resolved"),
+];
+
+/// Schema entries for the window arguments
+pub fn window_props(props: &mut HashMap<String, Property>) {
+    for (arg, field) in WINDOW_ARGS {
+        props.insert(
+            arg.to_string(),
+            Property::new("string").with_description(format!(
+                "Optional: only issues {} within this window before now: a number and a unit \
+                 (m=minutes, h=hours, d=days, w=weeks), e.g. '7d' adds '{} >= -7d' to the JQL",
+                field, field
+            )),
+        );
+    }
+}
+
+/// The JQL offset for a window such as `7d` (`-7d`)
+fn parse_window(input: &str) -> Result<String, String> {
+    let input = input.trim();
+    let (amount, unit) = input.split_at(input.len().saturating_sub(1));
+    let digits = !amount.is_empty() && amount.bytes().all(|b| b.is_ascii_digit());
+    match amount.parse::<u32>() {
+        Ok(amount) if digits && matches!(unit, "m" | "h" | "d" | "w") => {
+            Ok(format!("-{}{}", amount, unit))
+        }
+        _ => Err(format!(
+            "expected a number and a unit m/h/d/w (e.g. '7d', '2w', '12h'), got '{}'",
+            input
+        )),
+    }
+}
+
+/// Adds a `<field> >= -<window>` condition for each window argument present
+pub fn apply_relative_windows(jql: &str, args: &Value) -> Result<String, InvalidArgs> {
+    let mut errors = InvalidArgs::default();
+    let mut clauses = Vec::new();
+    for (arg, field) in WINDOW_ARGS {
+        let Some(input) = args[*arg].as_str() else {
+            continue;
+        };
+        match parse_window(input) {
+            Ok(offset) => clauses.push(format!("{} >= {}", field, offset)),
+            Err(reason) => errors.invalid.push((arg.to_string(), reason)),
+        }
+    }
+    if !errors.invalid.is_empty() {
+        return Err(errors);
+    }
+    if clauses.is_empty() {
+        return Ok(jql.to_string());
+    }
+
+    let jql_lower = jql.to_lowercase();
+    let (conditions, order_by) = if let Some(pos) = jql_lower.find(" order by ") {
+        (jql[..pos].trim(), &jql[pos..])
+    } else if jql_lower.starts_with("order by ") {
+        ("", jql)
+    } else {
+        (jql.trim(), "")
+    };
+    let windows = clauses.join(" AND ");
+    let conditions = if conditions.is_empty() {
+        windows
+    } else {
+        format!("({}) AND {}", conditions, windows)
+    };
+    Ok(format!("{} {}", conditions, order_by.trim())
+        .trim_end()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_windows_become_relative_conditions() {
+        let args = json!({"updated_within": "7d", "created_within": " 2w "});
+        assert_eq!(
+            apply_relative_windows("project = KEY OR assignee = currentUser()", &args).unwrap(),
+            "(project = KEY OR assignee = currentUser()) AND updated >= -7d AND created >= -2w"
+        );
+        assert_eq!(
+            apply_relative_windows(
+                "status = Done ORDER BY resolved DESC",
+                &json!({"resolved_within": "012h"})
+            )
+            .unwrap(),
+            "(status = Done) AND resolved >= -12h ORDER BY resolved DESC"
+        );
+        assert_eq!(
+            apply_relative_windows("ORDER BY updated DESC", &json!({"updated_within": "30m"}))
+                .unwrap(),
+            "updated >= -30m ORDER BY updated DESC"
+        );
+        assert_eq!(
+            apply_relative_windows("project = KEY", &json!({})).unwrap(),
+            "project = KEY"
+        );
+    }
+
+    #[test]
+    fn test_invalid_windows_are_reported() {
+        let err = apply_relative_windows(
+            "project = KEY",
+            &json!({"updated_within": "7 days", "created_within": "1M"}),
+        )
+        .unwrap_err();
+        let fields: Vec<&str> = err.invalid.iter().map(|(f, _)| f.as_str()).collect();
+        assert_eq!(fields, vec!["updated_within", "created_within"]);
+        assert!(apply_relative_windows("x = 1", &json!({"updated_within": "d"})).is_err());
+    }
+}
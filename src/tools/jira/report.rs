@@ -0,0 +1,394 @@
+//! Consolidated Markdown report for one issue
+//!
+//! `jira_issue_report` fetches the issue with its changelog and its latest
+//! comments (two requests) and renders a single document: header fields,
+//! description, linked issues, attachments, recent comments and the changelog
+//! entries that matter for a status summary (`HIGHLIGHT_FIELDS`).
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{ToolSchema, number_prop, string_prop};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+use super::adf_utils::adf_to_markdown;
+use super::tabular::flatten_field;
+
+pub struct IssueReportHandler;
+
+const REPORT_FIELDS: &str = "summary,status,issuetype,priority,assignee,reporter,created,updated,labels,components,fixVersions,parent,description,issuelinks,attachment,resolution,duedate";
+/// Changelog fields worth reporting; routine edits (description, rank) are skipped
+const HIGHLIGHT_FIELDS: &[&str] = &[
+    "status",
+    "assignee",
+    "priority",
+    "resolution",
+    "Fix Version",
+    "Sprint",
+    "labels",
+    "duedate",
+    "issuetype",
+];
+const DEFAULT_COMMENTS: u64 = 5;
+const DEFAULT_CHANGES: usize = 10;
+
+fn or_dash(value: String) -> String {
+    if value.is_empty() {
+        "—".to_string()
+    } else {
+        value
+    }
+}
+
+fn human_size(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1024 * 1024 => format!("{:.1} MB", b as f64 / (1024.0 * 1024.0)),
+        b if b >= 1024 => format!("{:.1} KB", b as f64 / 1024.0),
+        b => format!("{} B", b),
+    }
+}
+
+/// `- blocks PROJ-2 Summary (Status)` per link
+pub fn linked_issues(links: &Value) -> Vec<String> {
+    links
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|link| {
+            let (relation, issue) = if link["outwardIssue"].is_object() {
+                (&link["type"]["outward"], &link["outwardIssue"])
+            } else if link["inwardIssue"].is_object() {
+                (&link["type"]["inward"], &link["inwardIssue"])
+            } else {
+                return None;
+            };
+            Some(format!(
+                "- {} {} {} ({})",
+                relation.as_str().unwrap_or("relates to"),
+                issue["key"].as_str().unwrap_or_default(),
+                issue["fields"]["summary"].as_str().unwrap_or_default(),
+                issue["fields"]["status"]["name"]
+                    .as_str()
+                    .unwrap_or_default()
+            ))
+        })
+        .collect()
+}
+
+/// Newest highlighted changes first, at most `limit`
+pub fn changelog_highlights(histories: &Value, limit: usize) -> Vec<String> {
+    let mut lines: Vec<String> = histories
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|history| {
+            let when = history["created"].as_str().unwrap_or_default().to_string();
+            let who = history["author"]["displayName"]
+                .as_str()
+                .unwrap_or("Unknown")
+                .to_string();
+            history["items"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|item| {
+                    item["field"]
+                        .as_str()
+                        .is_some_and(|f| HIGHLIGHT_FIELDS.iter().any(|h| h.eq_ignore_ascii_case(f)))
+                })
+                .map(move |item| {
+                    format!(
+                        "- {} {}: {} {} → {}",
+                        when,
+                        who,
+                        item["field"].as_str().unwrap_or_default(),
+                        or_dash(item["fromString"].as_str().unwrap_or_default().to_string()),
+                        or_dash(item["toString"].as_str().unwrap_or_default().to_string())
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    // Timestamps share one format, so lexical order is chronological
+    lines.sort_by(|a, b| b.cmp(a));
+    lines.truncate(limit);
+    lines
+}
+
+/// The full report; `comments` is a `/comment` page ordered newest first
+pub fn render_report(issue: &Value, comments: &Value, changes: usize) -> String {
+    let fields = &issue["fields"];
+    let field = |name: &str| or_dash(flatten_field(&fields[name]));
+
+    let mut out = format!(
+        "# {}: {}\n\n",
+        issue["key"].as_str().unwrap_or_default(),
+        fields["summary"].as_str().unwrap_or_default()
+    );
+    out.push_str(&format!(
+        "**Type:** {} · **Status:** {} · **Priority:** {} · **Resolution:** {}\n",
+        field("issuetype"),
+        field("status"),
+        field("priority"),
+        field("resolution")
+    ));
+    out.push_str(&format!(
+        "**Assignee:** {} · **Reporter:** {}\n",
+        field("assignee"),
+        field("reporter")
+    ));
+    out.push_str(&format!(
+        "**Created:** {} · **Updated:** {} · **Due:** {}\n",
+        field("created"),
+        field("updated"),
+        field("duedate")
+    ));
+    for (label, name) in [
+        ("Labels", "labels"),
+        ("Components", "components"),
+        ("Fix versions", "fixVersions"),
+    ] {
+        let value = flatten_field(&fields[name]);
+        if !value.is_empty() {
+            out.push_str(&format!("**{}:** {}\n", label, value));
+        }
+    }
+    if let Some(parent) = fields["parent"]["key"].as_str() {
+        out.push_str(&format!(
+            "**Parent:** {} {}\n",
+            parent,
+            fields["parent"]["fields"]["summary"]
+                .as_str()
+                .unwrap_or_default()
+        ));
+    }
+
+    out.push_str("\n## Description\n\n");
+    let description = adf_to_markdown(&fields["description"]);
+    out.push_str(if description.is_empty() {
+        "_No description_"
+    } else {
+        &description
+    });
+    out.push('\n');
+
+    let links = linked_issues(&fields["issuelinks"]);
+    if !links.is_empty() {
+        out.push_str("\n## Linked Issues\n\n");
+        out.push_str(&links.join("\n"));
+        out.push('\n');
+    }
+
+    if let Some(attachments) = fields["attachment"].as_array().filter(|a| !a.is_empty()) {
+        out.push_str("\n## Attachments\n\n");
+        for attachment in attachments {
+            out.push_str(&format!(
+                "- {} ({}, {}) — {}, {}\n",
+                attachment["filename"].as_str().unwrap_or_default(),
+                human_size(attachment["size"].as_u64().unwrap_or(0)),
+                attachment["mimeType"].as_str().unwrap_or_default(),
+                attachment["author"]["displayName"]
+                    .as_str()
+                    .unwrap_or_default(),
+                attachment["created"].as_str().unwrap_or_default()
+            ));
+        }
+    }
+
+    let shown = comments["comments"].as_array().cloned().unwrap_or_default();
+    if !shown.is_empty() {
+        let total = comments["total"].as_u64().unwrap_or(shown.len() as u64);
+        out.push_str(&format!(
+            "\n## Recent Comments ({} of {})\n",
+            shown.len(),
+            total
+        ));
+        for comment in &shown {
+            out.push_str(&format!(
+                "\n### {} — {}\n\n{}\n",
+                comment["author"]["displayName"]
+                    .as_str()
+                    .unwrap_or("Unknown"),
+                comment["created"].as_str().unwrap_or_default(),
+                adf_to_markdown(&comment["body"])
+            ));
+        }
+    }
+
+    let highlights = changelog_highlights(&issue["changelog"]["histories"], changes);
+    if !highlights.is_empty() {
+        out.push_str("\n## Changelog Highlights\n\n");
+        out.push_str(&highlights.join("\n"));
+        out.push('\n');
+    }
+    out
+}
+
+async fn get_json(
+    client: &reqwest::Client,
+    config: &Config,
+    url: &str,
+    query: &[(&str, String)],
+) -> Result<Value> {
+    let response = client
+        .get(url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .query(query)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get issue: {}", response.status());
+    }
+    Ok(response.json().await?)
+}
+
+#[async_trait]
+impl ToolHandler for IssueReportHandler {
+    fn name(&self) -> &'static str {
+        "jira_issue_report"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "issue_key".to_string(),
+            string_prop("Issue key (e.g., PROJ-123)", true),
+        );
+        props.insert(
+            "comments".to_string(),
+            number_prop("Most recent comments to include (0 for none)", 5),
+        );
+        props.insert(
+            "changes".to_string(),
+            number_prop("Changelog highlights to include (status, assignee, priority, resolution, version, sprint, labels)", 10),
+        );
+        ToolSchema::new(
+            "One Markdown report for an issue: fields, description, linked issues, attachments, recent comments and changelog highlights",
+            props,
+            vec!["issue_key".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let issue_key = args["issue_key"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing issue_key"))?;
+        let comments = args["comments"].as_u64().unwrap_or(DEFAULT_COMMENTS);
+        let changes = args["changes"]
+            .as_u64()
+            .map(|c| c as usize)
+            .unwrap_or(DEFAULT_CHANGES);
+
+        let client = create_atlassian_client(config);
+        let base = format!(
+            "{}/rest/api/3/issue/{}",
+            config.get_atlassian_base_url(),
+            issue_key
+        );
+
+        let issue = get_json(
+            &client,
+            config,
+            &base,
+            &[
+                ("fields", REPORT_FIELDS.to_string()),
+                ("expand", "changelog".to_string()),
+            ],
+        )
+        .await?;
+        let comments = if comments == 0 {
+            Value::Null
+        } else {
+            get_json(
+                &client,
+                config,
+                &format!("{}/comment", base),
+                &[
+                    ("orderBy", "-created".to_string()),
+                    ("maxResults", comments.to_string()),
+                ],
+            )
+            .await?
+        };
+
+        Ok(Value::String(render_report(&issue, &comments, changes)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn issue() -> Value {
+        json!({
+            "key": "PROJ-7",
+            "fields": {
+                "summary": "Checkout times out",
+                "status": {"name": "In Progress"},
+                "issuetype": {"name": "Bug"},
+                "assignee": {"displayName": "Jane"},
+                "labels": ["payments"],
+                "description": {"type": "doc", "version": 1, "content": [
+                    {"type": "paragraph", "content": [{"type": "text", "text": "Fails after 30s"}]}
+                ]},
+                "issuelinks": [
+                    {"type": {"inward": "is blocked by", "outward": "blocks"},
+                     "inwardIssue": {"key": "PROJ-3", "fields": {"summary": "Gateway upgrade", "status": {"name": "Done"}}}}
+                ],
+                "attachment": [
+                    {"filename": "trace.har", "size": 2048, "mimeType": "application/json", "author": {"displayName": "Bob"}, "created": "2026-01-02"}
+                ]
+            },
+            "changelog": {"histories": [
+                {"created": "2026-01-01T10:00:00.000+0000", "author": {"displayName": "Bob"},
+                 "items": [{"field": "status", "fromString": "To Do", "toString": "In Progress"},
+                           {"field": "description", "fromString": "a", "toString": "b"}]},
+                {"created": "2026-01-03T10:00:00.000+0000", "author": {"displayName": "Jane"},
+                 "items": [{"field": "assignee", "fromString": null, "toString": "Jane"}]}
+            ]}
+        })
+    }
+
+    #[test]
+    fn test_render_report_sections() {
+        let comments = json!({"total": 4, "comments": [
+            {"author": {"displayName": "Jane"}, "created": "2026-01-03",
+             "body": {"type": "doc", "version": 1, "content": [{"type": "paragraph", "content": [{"type": "text", "text": "Looking"}]}]}}
+        ]});
+        let report = render_report(&issue(), &comments, 10);
+
+        assert!(report.starts_with("# PROJ-7: Checkout times out\n"));
+        assert!(report.contains("**Type:** Bug · **Status:** In Progress · **Priority:** — "));
+        assert!(report.contains("**Labels:** payments\n"));
+        assert!(report.contains("## Description\n\nFails after 30s\n"));
+        assert!(report.contains("- is blocked by PROJ-3 Gateway upgrade (Done)"));
+        assert!(report.contains("- trace.har (2.0 KB, application/json) — Bob, 2026-01-02"));
+        assert!(
+            report.contains("## Recent Comments (1 of 4)\n\n### Jane — 2026-01-03\n\nLooking\n")
+        );
+        assert!(!report.contains("description a"));
+    }
+
+    #[test]
+    fn test_changelog_highlights_newest_first() {
+        let lines = changelog_highlights(&issue()["changelog"]["histories"], 10);
+        assert_eq!(
+            lines,
+            vec![
+                "- 2026-01-03T10:00:00.000+0000 Jane: assignee — → Jane",
+                "- 2026-01-01T10:00:00.000+0000 Bob: status To Do → In Progress"
+            ]
+        );
+        assert_eq!(
+            changelog_highlights(&issue()["changelog"]["histories"], 1).len(),
+            1
+        );
+    }
+}
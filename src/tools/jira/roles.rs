@@ -0,0 +1,295 @@
+//! Project role management
+//!
+//! Listing is always available; adding users to a role is a write tool and is
+//! hidden in `READ_ONLY_MODE`.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{ToolSchema, string_prop, union_prop};
+use crate::tools::write_policy;
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use std::collections::HashMap;
+
+pub struct GetProjectRolesHandler;
+pub struct AddUserToRoleHandler;
+
+fn project_key<'a>(args: &'a Value, config: &Config) -> Result<&'a str> {
+    let key = args["project_key"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing project_key"))?;
+
+    // Role changes are project administration; respect the project allowlist
+    if !config.jira_projects_filter.is_empty()
+        && !config
+            .jira_projects_filter
+            .iter()
+            .any(|p| p.eq_ignore_ascii_case(key))
+    {
+        anyhow::bail!("Project '{}' is not in JIRA_PROJECTS_FILTER", key);
+    }
+    Ok(key)
+}
+
+fn role_id(args: &Value) -> Result<Option<u64>> {
+    match &args["role_id"] {
+        Value::Null => Ok(None),
+        v => v
+            .as_u64()
+            .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+            .map(Some)
+            .ok_or_else(|| anyhow::anyhow!("Invalid role_id: expected a numeric ID")),
+    }
+}
+
+/// Converts `{"Developers": ".../role/10001", ...}` into `[{name, id}]`, sorted by name
+pub fn parse_role_map(data: &Value) -> Vec<Value> {
+    let mut roles: Vec<Value> = data
+        .as_object()
+        .map(|map| {
+            map.iter()
+                .map(|(name, url)| {
+                    let id = url
+                        .as_str()
+                        .and_then(|u| u.rsplit('/').next())
+                        .and_then(|id| id.parse::<u64>().ok());
+                    json!({ "name": name, "id": id })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    roles.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+    roles
+}
+
+/// Keeps only the identifying fields of each role actor
+pub fn summarize_actors(actors: &Value) -> Vec<Value> {
+    actors
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .map(|actor| {
+                    json!({
+                        "type": actor["type"],
+                        "displayName": actor["displayName"],
+                        "accountId": actor["actorUser"]["accountId"],
+                        "groupName": actor["actorGroup"]["name"]
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[async_trait]
+impl ToolHandler for GetProjectRolesHandler {
+    fn name(&self) -> &'static str {
+        "jira_get_project_roles"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("project_key".to_string(), string_prop("Project key", true));
+        props.insert(
+            "role_id".to_string(),
+            union_prop(
+                "Role ID; when given, returns the role's members",
+                vec!["number", "string"],
+            ),
+        );
+        ToolSchema::new(
+            "List Jira project roles, or members of one role",
+            props,
+            vec!["project_key".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let project_key = project_key(&args, config)?;
+        let role_id = role_id(&args)?;
+
+        let client = create_atlassian_client(config);
+        let base = format!(
+            "{}/rest/api/3/project/{}/role",
+            config.get_atlassian_base_url(),
+            project_key
+        );
+        let url = match role_id {
+            Some(id) => format!("{}/{}", base, id),
+            None => base,
+        };
+
+        let response = client
+            .get(&url)
+            .header("Authorization", create_auth_header(config))
+            .header("Accept", "application/json")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to get project roles: {}", response.status());
+        }
+
+        let data: Value = response.json().await?;
+
+        match role_id {
+            Some(id) => Ok(json!({
+                "success": true,
+                "id": id,
+                "name": data["name"],
+                "actors": summarize_actors(&data["actors"])
+            })),
+            None => Ok(json!({
+                "success": true,
+                "roles": parse_role_map(&data)
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for AddUserToRoleHandler {
+    fn name(&self) -> &'static str {
+        "jira_add_user_to_role"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("project_key".to_string(), string_prop("Project key", true));
+        props.insert(
+            "role_id".to_string(),
+            union_prop(
+                "Role ID (from jira_get_project_roles)",
+                vec!["number", "string"],
+            ),
+        );
+        props.insert(
+            "account_id".to_string(),
+            string_prop("User account ID", true),
+        );
+        ToolSchema::new(
+            "Add a user to a Jira project role",
+            props,
+            vec![
+                "project_key".to_string(),
+                "role_id".to_string(),
+                "account_id".to_string(),
+            ],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let project_key = project_key(&args, config)?;
+        write_policy::check_project(config, project_key)?;
+        let role_id = role_id(&args)?.ok_or_else(|| anyhow::anyhow!("Missing role_id"))?;
+        let account_id = args["account_id"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing account_id"))?;
+
+        let client = create_atlassian_client(config);
+        let url = format!(
+            "{}/rest/api/3/project/{}/role/{}",
+            config.get_atlassian_base_url(),
+            project_key,
+            role_id
+        );
+
+        let body = json!({ "user": [account_id] });
+
+        let response = client
+            .post(&url)
+            .header("Authorization", create_auth_header(config))
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to add user to role: {}", error);
+        }
+
+        let data: Value = response.json().await?;
+        Ok(json!({
+            "success": true,
+            "project_key": project_key,
+            "role": data["name"],
+            "account_id": account_id
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ConfigBuilder;
+
+    fn create_test_config(jira_projects_filter: Vec<String>) -> Config {
+        ConfigBuilder::new()
+            .with(|config| config.jira_projects_filter = jira_projects_filter)
+            .build()
+    }
+
+    #[test]
+    fn test_parse_role_map() {
+        let data = json!({
+            "Developers": "https://test.atlassian.net/rest/api/3/project/PROJ/role/10001",
+            "Administrators": "https://test.atlassian.net/rest/api/3/project/PROJ/role/10002"
+        });
+        let roles = parse_role_map(&data);
+        assert_eq!(roles[0], json!({"name": "Administrators", "id": 10002}));
+        assert_eq!(roles[1], json!({"name": "Developers", "id": 10001}));
+    }
+
+    #[test]
+    fn test_summarize_actors() {
+        let actors = json!([
+            {"type": "atlassian-user-role-actor", "displayName": "Jane", "actorUser": {"accountId": "abc"}},
+            {"type": "atlassian-group-role-actor", "displayName": "devs", "actorGroup": {"name": "devs"}}
+        ]);
+        let summary = summarize_actors(&actors);
+        assert_eq!(summary[0]["accountId"], "abc");
+        assert_eq!(summary[1]["groupName"], "devs");
+    }
+
+    #[test]
+    fn test_project_filter_enforced() {
+        let config = create_test_config(vec!["PROJ".to_string()]);
+        assert!(project_key(&json!({"project_key": "proj"}), &config).is_ok());
+        let args = json!({"project_key": "OTHER"});
+        let result = project_key(&args, &config);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("JIRA_PROJECTS_FILTER")
+        );
+    }
+
+    #[test]
+    fn test_role_id_parsing() {
+        assert_eq!(role_id(&json!({})).unwrap(), None);
+        assert_eq!(role_id(&json!({"role_id": "10001"})).unwrap(), Some(10001));
+        assert!(role_id(&json!({"role_id": "dev"})).is_err());
+    }
+
+    #[test]
+    fn test_add_user_to_role_missing_account_id() {
+        let handler = AddUserToRoleHandler;
+        let config = create_test_config(vec![]);
+        let args = json!({"project_key": "PROJ", "role_id": 10001});
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(handler.execute(args, &config));
+
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Missing account_id")
+        );
+    }
+}
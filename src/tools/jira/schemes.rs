@@ -0,0 +1,591 @@
+//! Project workflow and permission scheme inspection
+//!
+//! `jira_get_project_workflow` follows project → workflow scheme → workflow
+//! (the one mapped to `issue_type`, else the scheme default) and returns its
+//! statuses and transitions, with the conditions and validators that explain a
+//! missing transition. `format: mermaid` renders the graph as a state diagram.
+//! `jira_get_permission_scheme` lists who holds each project permission.
+//! `jira_get_statuses` lists status names (site-wide, or per issue type of a
+//! project) so phrasing like "move it to QA" maps to a real status first.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema, string_prop};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+
+pub struct GetProjectWorkflowHandler;
+pub struct GetPermissionSchemeHandler;
+pub struct GetStatusesHandler;
+
+async fn get_json(
+    client: &reqwest::Client,
+    config: &Config,
+    url: &str,
+    what: &str,
+) -> Result<Value> {
+    let response = client
+        .get(url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get {}: {}", what, response.status());
+    }
+    Ok(response.json().await?)
+}
+
+fn project_key(args: &Value) -> Result<&str> {
+    args["project_key"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing project_key"))
+}
+
+/// Workflow name for `issue_type_id` in a workflow scheme, else its default
+pub fn workflow_for_issue_type(scheme: &Value, issue_type_id: Option<&str>) -> Option<String> {
+    issue_type_id
+        .and_then(|id| scheme["issueTypeMappings"][id].as_str())
+        .or_else(|| scheme["defaultWorkflow"].as_str())
+        .map(str::to_string)
+}
+
+fn rule_types(rules: &Value) -> Vec<Value> {
+    rules
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|rule| rule["type"].clone())
+        .collect()
+}
+
+/// `{statuses, transitions}` from a `/workflow/search` entry; a transition
+/// with an empty `from` is available from every status
+pub fn summarize_workflow(workflow: &Value) -> Value {
+    let statuses: Vec<Value> = workflow["statuses"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|status| json!({"id": status["id"], "name": status["name"]}))
+        .collect();
+    let transitions: Vec<Value> = workflow["transitions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|transition| {
+            json!({
+                "id": transition["id"],
+                "name": transition["name"],
+                "type": transition["type"],
+                "from": transition["from"].as_array().cloned().unwrap_or_default(),
+                "to": transition["to"],
+                "conditions": rule_types(&transition["rules"]["conditions"]),
+                "validators": rule_types(&transition["rules"]["validators"])
+            })
+        })
+        .collect();
+    json!({
+        "name": workflow["id"]["name"],
+        "statuses": statuses,
+        "transitions": transitions
+    })
+}
+
+fn mermaid_label(text: &str) -> String {
+    text.replace('"', "'").replace([':', ';'], " ")
+}
+
+/// `stateDiagram-v2` for a summarized workflow; global transitions start from
+/// an "Any status" node and the initial transition from `[*]`
+pub fn workflow_to_mermaid(summary: &Value) -> String {
+    let mut out = String::from("```mermaid\nstateDiagram-v2\n");
+    for status in summary["statuses"].as_array().into_iter().flatten() {
+        out.push_str(&format!(
+            "    state \"{}\" as s{}\n",
+            mermaid_label(status["name"].as_str().unwrap_or_default()),
+            status["id"].as_str().unwrap_or_default()
+        ));
+    }
+
+    let transitions = summary["transitions"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let has_global = transitions
+        .iter()
+        .any(|t| t["type"] != "initial" && t["from"].as_array().is_none_or(|f| f.is_empty()));
+    if has_global {
+        out.push_str("    state \"Any status\" as any\n");
+    }
+    for transition in &transitions {
+        let to = format!("s{}", transition["to"].as_str().unwrap_or_default());
+        let name = mermaid_label(transition["name"].as_str().unwrap_or_default());
+        let from: Vec<String> = transition["from"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(|id| format!("s{}", id))
+            .collect();
+        let sources = match (transition["type"].as_str(), from.is_empty()) {
+            (Some("initial"), _) => vec!["[*]".to_string()],
+            (_, true) => vec!["any".to_string()],
+            (_, false) => from,
+        };
+        for source in sources {
+            out.push_str(&format!("    {} --> {} : {}\n", source, to, name));
+        }
+    }
+    out.push_str("```\n");
+    out
+}
+
+fn holder_name(holder: &Value) -> Value {
+    let name = holder["projectRole"]["name"]
+        .as_str()
+        .or_else(|| holder["group"]["name"].as_str())
+        .or_else(|| holder["user"]["displayName"].as_str())
+        .or_else(|| holder["value"].as_str())
+        .or_else(|| holder["parameter"].as_str());
+    match name {
+        Some(name) => json!({"type": holder["type"], "name": name}),
+        None => json!({"type": holder["type"]}),
+    }
+}
+
+/// Holders per permission key, sorted by key; `only` keeps one key
+pub fn group_permissions(scheme: &Value, only: Option<&str>) -> BTreeMap<String, Vec<Value>> {
+    let mut grouped: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+    for grant in scheme["permissions"].as_array().into_iter().flatten() {
+        let Some(permission) = grant["permission"].as_str() else {
+            continue;
+        };
+        if only.is_some_and(|only| !only.eq_ignore_ascii_case(permission)) {
+            continue;
+        }
+        grouped
+            .entry(permission.to_string())
+            .or_default()
+            .push(holder_name(&grant["holder"]));
+    }
+    grouped
+}
+
+/// `{id, name, category}` of a status
+fn compact_status(status: &Value) -> Value {
+    json!({
+        "id": status["id"],
+        "name": status["name"],
+        "category": status["statusCategory"]["name"]
+    })
+}
+
+/// Lowercase words, punctuation dropped: "In-QA" and "in qa" compare equal
+fn normalize_status(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether a status name and user phrasing share a whole-word run ("QA" ~ "In QA")
+pub fn status_matches(name: &str, query: &str) -> bool {
+    let (name, query) = (normalize_status(name), normalize_status(query));
+    if name.is_empty() || query.is_empty() {
+        return false;
+    }
+    let (name, query) = (format!(" {} ", name), format!(" {} ", query));
+    name.contains(&query) || query.contains(&name)
+}
+
+/// Distinct compact statuses, keeping those matching `query` when given
+fn distinct_statuses<'a>(
+    statuses: impl Iterator<Item = &'a Value>,
+    query: Option<&str>,
+) -> Vec<Value> {
+    let mut seen = Vec::new();
+    let mut out = Vec::new();
+    for status in statuses {
+        let name = status["name"].as_str().unwrap_or_default();
+        if query.is_some_and(|q| !status_matches(name, q)) || seen.contains(&&status["id"]) {
+            continue;
+        }
+        seen.push(&status["id"]);
+        out.push(compact_status(status));
+    }
+    out
+}
+
+#[async_trait]
+impl ToolHandler for GetProjectWorkflowHandler {
+    fn name(&self) -> &'static str {
+        "jira_get_project_workflow"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("project_key".to_string(), string_prop("Project key", true));
+        props.insert(
+            "issue_type".to_string(),
+            string_prop(
+                "Issue type name; defaults to the scheme's default workflow",
+                false,
+            ),
+        );
+        props.insert(
+            "format".to_string(),
+            Property::new("string")
+                .with_description(
+                    "'json' (default) or 'mermaid' for a stateDiagram-v2 of the workflow"
+                        .to_string(),
+                )
+                .with_default(json!("json"))
+                .with_enum(vec![json!("json"), json!("mermaid")]),
+        );
+        ToolSchema::new(
+            "Get a project's workflow: statuses, transitions with conditions and validators, optionally as a Mermaid diagram",
+            props,
+            vec!["project_key".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let project_key = project_key(&args)?;
+        let mermaid = match args["format"].as_str() {
+            None => false,
+            Some(s) if s.eq_ignore_ascii_case("json") => false,
+            Some(s) if s.eq_ignore_ascii_case("mermaid") => true,
+            Some(other) => {
+                anyhow::bail!("Invalid format '{}': expected 'json' or 'mermaid'", other)
+            }
+        };
+
+        let client = create_atlassian_client(config);
+        let base_url = config.get_atlassian_base_url();
+        let project = get_json(
+            &client,
+            config,
+            &format!("{}/rest/api/3/project/{}", base_url, project_key),
+            "project",
+        )
+        .await?;
+        let project_id = project["id"].as_str().unwrap_or_default();
+
+        let issue_type = match args["issue_type"].as_str() {
+            Some(name) => Some(
+                project["issueTypes"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .find(|t| {
+                        t["name"]
+                            .as_str()
+                            .is_some_and(|n| n.eq_ignore_ascii_case(name))
+                    })
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("Issue type '{}' not found in {}", name, project_key)
+                    })?,
+            ),
+            None => None,
+        };
+
+        let schemes = get_json(
+            &client,
+            config,
+            &format!(
+                "{}/rest/api/3/workflowscheme/project?projectId={}",
+                base_url, project_id
+            ),
+            "workflow scheme",
+        )
+        .await?;
+        let scheme = &schemes["values"][0]["workflowScheme"];
+        let workflow_name =
+            workflow_for_issue_type(scheme, issue_type.and_then(|t| t["id"].as_str()))
+                .ok_or_else(|| anyhow::anyhow!("No workflow scheme found for {}", project_key))?;
+
+        let response = client
+            .get(format!("{}/rest/api/3/workflow/search", base_url))
+            .header("Authorization", create_auth_header(config))
+            .header("Accept", "application/json")
+            .query(&[
+                ("workflowName", workflow_name.as_str()),
+                ("expand", "statuses,transitions,transitions.rules"),
+            ])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to get workflow: {}", response.status());
+        }
+        let data: Value = response.json().await?;
+        let workflow = data["values"]
+            .as_array()
+            .and_then(|values| values.first())
+            .ok_or_else(|| anyhow::anyhow!("Workflow '{}' not found", workflow_name))?;
+        let summary = summarize_workflow(workflow);
+
+        if mermaid {
+            return Ok(Value::String(workflow_to_mermaid(&summary)));
+        }
+
+        Ok(json!({
+            "success": true,
+            "project_key": project_key,
+            "issue_type": issue_type.map(|t| t["name"].clone()),
+            "workflow_scheme": scheme["name"],
+            "workflow": summary
+        }))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for GetPermissionSchemeHandler {
+    fn name(&self) -> &'static str {
+        "jira_get_permission_scheme"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("project_key".to_string(), string_prop("Project key", true));
+        props.insert(
+            "permission".to_string(),
+            string_prop("Only this permission key, e.g. TRANSITION_ISSUES", false),
+        );
+        ToolSchema::new(
+            "Get a project's permission scheme: holders (roles, groups, users) per permission",
+            props,
+            vec!["project_key".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let project_key = project_key(&args)?;
+
+        let client = create_atlassian_client(config);
+        let url = format!(
+            "{}/rest/api/3/project/{}/permissionscheme?expand=permissions,user,group,projectRole",
+            config.get_atlassian_base_url(),
+            project_key
+        );
+        let scheme = get_json(&client, config, &url, "permission scheme").await?;
+
+        Ok(json!({
+            "success": true,
+            "project_key": project_key,
+            "scheme": {"id": scheme["id"], "name": scheme["name"]},
+            "permissions": group_permissions(&scheme, args["permission"].as_str())
+        }))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for GetStatusesHandler {
+    fn name(&self) -> &'static str {
+        "jira_get_statuses"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "project_key".to_string(),
+            string_prop(
+                "Project key; lists statuses per issue type instead of site-wide",
+                false,
+            ),
+        );
+        props.insert(
+            "issue_type".to_string(),
+            string_prop("Issue type name (with project_key)", false),
+        );
+        props.insert(
+            "query".to_string(),
+            string_prop(
+                "User phrasing to match against status names, e.g. 'QA'",
+                false,
+            ),
+        );
+        ToolSchema::new(
+            "List Jira statuses (id, name, category), site-wide or per project issue type; use before transitions to map phrasing to real status names",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let query = args["query"].as_str().filter(|q| !q.trim().is_empty());
+        let client = create_atlassian_client(config);
+        let base_url = config.get_atlassian_base_url();
+
+        let Some(project_key) = args["project_key"].as_str() else {
+            let data = get_json(
+                &client,
+                config,
+                &format!("{}/rest/api/3/status", base_url),
+                "statuses",
+            )
+            .await?;
+            return Ok(json!({
+                "success": true,
+                "statuses": distinct_statuses(data.as_array().into_iter().flatten(), query)
+            }));
+        };
+
+        let data = get_json(
+            &client,
+            config,
+            &format!("{}/rest/api/3/project/{}/statuses", base_url, project_key),
+            "project statuses",
+        )
+        .await?;
+        let issue_type = args["issue_type"].as_str();
+        let issue_types: Vec<&Value> = data
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|t| {
+                issue_type.is_none_or(|wanted| {
+                    t["name"]
+                        .as_str()
+                        .is_some_and(|n| n.eq_ignore_ascii_case(wanted))
+                })
+            })
+            .collect();
+        if let Some(wanted) = issue_type
+            && issue_types.is_empty()
+        {
+            anyhow::bail!("Issue type '{}' not found in {}", wanted, project_key);
+        }
+
+        let per_type: Vec<Value> = issue_types
+            .iter()
+            .map(|t| {
+                json!({
+                    "issue_type": t["name"],
+                    "statuses": distinct_statuses(
+                        t["statuses"].as_array().into_iter().flatten(),
+                        query
+                    )
+                })
+            })
+            .collect();
+        let all = distinct_statuses(
+            issue_types
+                .iter()
+                .flat_map(|t| t["statuses"].as_array().into_iter().flatten()),
+            query,
+        );
+
+        Ok(json!({
+            "success": true,
+            "project_key": project_key,
+            "statuses": all,
+            "issue_types": per_type
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workflow() -> Value {
+        json!({
+            "id": {"name": "Software workflow"},
+            "statuses": [{"id": "1", "name": "To Do"}, {"id": "3", "name": "In Progress"}, {"id": "10001", "name": "Done"}],
+            "transitions": [
+                {"id": "1", "name": "Create", "type": "initial", "from": [], "to": "1"},
+                {"id": "11", "name": "Start: work", "type": "directed", "from": ["1"], "to": "3",
+                 "rules": {"conditions": [{"type": "PermissionCondition"}], "validators": []}},
+                {"id": "31", "name": "Done", "type": "global", "from": [], "to": "10001"}
+            ]
+        })
+    }
+
+    #[test]
+    fn test_workflow_for_issue_type() {
+        let scheme =
+            json!({"defaultWorkflow": "Default", "issueTypeMappings": {"10004": "Bug flow"}});
+        assert_eq!(
+            workflow_for_issue_type(&scheme, Some("10004")).as_deref(),
+            Some("Bug flow")
+        );
+        assert_eq!(
+            workflow_for_issue_type(&scheme, Some("10001")).as_deref(),
+            Some("Default")
+        );
+        assert_eq!(workflow_for_issue_type(&json!(null), None), None);
+    }
+
+    #[test]
+    fn test_summarize_workflow_and_mermaid() {
+        let summary = summarize_workflow(&workflow());
+        assert_eq!(
+            summary["transitions"][1]["conditions"],
+            json!(["PermissionCondition"])
+        );
+        assert_eq!(summary["transitions"][2]["from"], json!([]));
+
+        let diagram = workflow_to_mermaid(&summary);
+        assert!(diagram.contains("    state \"In Progress\" as s3\n"));
+        assert!(diagram.contains("    [*] --> s1 : Create\n"));
+        assert!(diagram.contains("    s1 --> s3 : Start  work\n"));
+        assert!(diagram.contains("    state \"Any status\" as any\n    "));
+        assert!(diagram.contains("    any --> s10001 : Done\n"));
+    }
+
+    #[test]
+    fn test_group_permissions() {
+        let scheme = json!({"permissions": [
+            {"permission": "TRANSITION_ISSUES", "holder": {"type": "projectRole", "parameter": "10002", "projectRole": {"name": "Developers"}}},
+            {"permission": "BROWSE_PROJECTS", "holder": {"type": "applicationRole"}},
+            {"permission": "TRANSITION_ISSUES", "holder": {"type": "group", "parameter": "g1", "group": {"name": "jira-admins"}}}
+        ]});
+        let grouped = group_permissions(&scheme, None);
+        assert_eq!(
+            grouped.keys().collect::<Vec<_>>(),
+            vec!["BROWSE_PROJECTS", "TRANSITION_ISSUES"]
+        );
+        assert_eq!(
+            grouped["BROWSE_PROJECTS"],
+            vec![json!({"type": "applicationRole"})]
+        );
+        assert_eq!(
+            group_permissions(&scheme, Some("transition_issues"))["TRANSITION_ISSUES"],
+            vec![
+                json!({"type": "projectRole", "name": "Developers"}),
+                json!({"type": "group", "name": "jira-admins"})
+            ]
+        );
+    }
+
+    #[test]
+    fn test_status_matches() {
+        assert!(status_matches("In QA", "qa"));
+        assert!(status_matches("QA", "move to qa"));
+        assert!(status_matches("Code-Review", "code review"));
+        assert!(!status_matches("Done", "QA"));
+        assert!(!status_matches("Do", "done"));
+        assert!(!status_matches("Done", " "));
+    }
+
+    #[test]
+    fn test_distinct_statuses() {
+        let statuses = [
+            json!({"id": "3", "name": "In QA", "statusCategory": {"name": "In Progress"}}),
+            json!({"id": "3", "name": "In QA", "statusCategory": {"name": "In Progress"}}),
+            json!({"id": "5", "name": "Done", "statusCategory": {"name": "Done"}}),
+        ];
+        assert_eq!(distinct_statuses(statuses.iter(), None).len(), 2);
+        assert_eq!(
+            distinct_statuses(statuses.iter(), Some("qa")),
+            vec![json!({"id": "3", "name": "In QA", "category": "In Progress"})]
+        );
+    }
+}
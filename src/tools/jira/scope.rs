@@ -0,0 +1,130 @@
+//! Team scopes for the stand-up and workload tools
+//!
+//! A scope is any mix of a board (its saved filter, as `filter = <id>`), a
+//! project, a Jira group standing in for the team (issues assigned to its
+//! members) and extra JQL, ANDed together. With none of them the scope is
+//! `JIRA_PROJECTS_FILTER`, so a configured site never means "everything".
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::client::Jira;
+use crate::config::Config;
+use crate::tools::schema::{Property, string_prop};
+
+use super::board;
+
+/// The scope arguments, flattened into a tool's own
+#[derive(Debug, Default, Deserialize)]
+pub struct ScopeArgs {
+    board_id: Option<Value>,
+    project: Option<String>,
+    group: Option<String>,
+    jql: Option<String>,
+}
+
+/// JQL conditions for a scope (without ORDER BY), and what to call it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scope {
+    pub jql: String,
+    pub names: Vec<String>,
+}
+
+/// `"a"` with inner quotes escaped, for JQL values
+pub fn quoted(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Schema entries for [`ScopeArgs`]
+pub fn scope_props(props: &mut HashMap<String, Property>) {
+    props.insert(
+        "board_id".to_string(),
+        Property::new("integer").with_description("Agile board ID: covers the board's filter"),
+    );
+    props.insert(
+        "project".to_string(),
+        string_prop("Project key to cover", false),
+    );
+    props.insert(
+        "group".to_string(),
+        string_prop(
+            "Jira group standing in for the team: only issues assigned to its members",
+            false,
+        ),
+    );
+    props.insert(
+        "jql".to_string(),
+        string_prop(
+            "Extra JQL to narrow the scope (e.g., a team field condition)",
+            false,
+        ),
+    );
+}
+
+fn board_id(value: &Value) -> Result<Option<u64>> {
+    match value {
+        Value::Null => Ok(None),
+        value => value
+            .as_u64()
+            .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+            .map(Some)
+            .ok_or_else(|| anyhow::anyhow!("Invalid board_id '{}'", value)),
+    }
+}
+
+/// The scope's JQL; reads the board configuration when a board is given
+pub async fn resolve(jira: &Jira<'_>, config: &Config, args: &ScopeArgs) -> Result<Scope> {
+    let mut clauses = Vec::new();
+    let mut names = Vec::new();
+    if let Some(board_id) = board_id(args.board_id.as_ref().unwrap_or(&Value::Null))? {
+        let configuration = board::board_configuration(jira.http(), config, board_id).await?;
+        let filter = &configuration["filter"]["id"];
+        let filter_id = filter
+            .as_str()
+            .map(String::from)
+            .or_else(|| filter.as_u64().map(|id| id.to_string()))
+            .ok_or_else(|| anyhow::anyhow!("Board {} has no filter", board_id))?;
+        clauses.push(format!("filter = {}", filter_id));
+        names.push(
+            configuration["name"]
+                .as_str()
+                .map_or_else(|| format!("board {}", board_id), String::from),
+        );
+    }
+    let given = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(String::from)
+    };
+    if let Some(project) = given(&args.project) {
+        clauses.push(format!("project = {}", quoted(&project)));
+        names.push(project);
+    }
+    if let Some(group) = given(&args.group) {
+        clauses.push(format!("assignee in membersOf({})", quoted(&group)));
+        names.push(group);
+    }
+    if let Some(jql) = given(&args.jql) {
+        clauses.push(format!("({})", jql));
+    }
+    if clauses.is_empty() {
+        if config.jira_projects_filter.is_empty() {
+            anyhow::bail!("Give a board_id, project, group or jql to cover");
+        }
+        let projects: Vec<String> = config
+            .jira_projects_filter
+            .iter()
+            .map(|p| quoted(p))
+            .collect();
+        clauses.push(format!("project IN ({})", projects.join(",")));
+        names.push(config.jira_projects_filter.join(", "));
+    }
+    Ok(Scope {
+        jql: clauses.join(" AND "),
+        names,
+    })
+}
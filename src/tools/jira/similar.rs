@@ -0,0 +1,292 @@
+//! Duplicate issue detection
+//!
+//! `jira_find_similar_issues` extracts keywords from a draft summary and
+//! description, runs `summary ~` and `text ~` searches over the configured
+//! projects and ranks the merged candidates by a local fuzzy score: word
+//! overlap plus character-bigram similarity between the summaries. Jira's own
+//! relevance ordering is too coarse to tell a duplicate from a related issue.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{ToolSchema, number_prop, string_prop};
+use crate::utils::http_utils::create_atlassian_client;
+
+use super::search_page;
+
+pub struct FindSimilarIssuesHandler;
+
+const CANDIDATE_FIELDS: &[&str] = &["summary", "status", "issuetype", "resolution", "created"];
+/// Candidates fetched per search before local ranking
+const CANDIDATES_PER_SEARCH: u64 = 50;
+/// Keywords kept for the `~` clauses
+const MAX_KEYWORDS: usize = 8;
+const DEFAULT_LIMIT: usize = 10;
+
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "can", "does", "for", "from", "has",
+    "have", "how", "if", "in", "into", "is", "it", "its", "not", "of", "on", "or", "should",
+    "that", "the", "this", "to", "was", "when", "where", "which", "while", "will", "with",
+];
+
+/// Lowercased alphanumeric words of 3+ characters, stop words removed
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|w| w.chars().count() >= 3 && !STOP_WORDS.contains(&w.as_str()))
+        .collect()
+}
+
+/// Distinct summary words first, then the most frequent description words
+pub fn keywords(summary: &str, description: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut words: Vec<String> = tokenize(summary)
+        .into_iter()
+        .filter(|w| seen.insert(w.clone()))
+        .collect();
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for word in tokenize(description) {
+        *counts.entry(word).or_default() += 1;
+    }
+    let mut frequent: Vec<(String, usize)> = counts
+        .into_iter()
+        .filter(|(w, _)| !seen.contains(w))
+        .collect();
+    frequent.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    words.extend(frequent.into_iter().map(|(w, _)| w));
+
+    words.truncate(MAX_KEYWORDS);
+    words
+}
+
+fn bigrams(text: &str) -> HashSet<(char, char)> {
+    let chars: Vec<char> = text
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect();
+    chars.windows(2).map(|w| (w[0], w[1])).collect()
+}
+
+/// 0.0–1.0: the mean of word Jaccard and character-bigram Dice coefficients
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let words_a: HashSet<String> = tokenize(a).into_iter().collect();
+    let words_b: HashSet<String> = tokenize(b).into_iter().collect();
+    let union = words_a.union(&words_b).count();
+    let jaccard = if union == 0 {
+        0.0
+    } else {
+        words_a.intersection(&words_b).count() as f64 / union as f64
+    };
+
+    let (bi_a, bi_b) = (bigrams(a), bigrams(b));
+    let total = bi_a.len() + bi_b.len();
+    let dice = if total == 0 {
+        0.0
+    } else {
+        2.0 * bi_a.intersection(&bi_b).count() as f64 / total as f64
+    };
+
+    (jaccard + dice) / 2.0
+}
+
+/// `field ~ "..."` searches, limited to the given projects
+pub fn similarity_queries(keywords: &[String], projects: &[String]) -> Vec<String> {
+    if keywords.is_empty() {
+        return Vec::new();
+    }
+    let terms = keywords.join(" ");
+    let scope = if projects.is_empty() {
+        String::new()
+    } else {
+        format!("project in ({}) AND ", projects.join(", "))
+    };
+    ["summary", "text"]
+        .iter()
+        .map(|field| format!("{}{} ~ \"{}\"", scope, field, terms))
+        .collect()
+}
+
+/// Candidates sorted by score, best first; `exclude` drops the draft itself
+pub fn rank_candidates(
+    summary: &str,
+    issues: &[Value],
+    exclude: Option<&str>,
+    limit: usize,
+) -> Vec<Value> {
+    let mut seen = HashSet::new();
+    let mut ranked: Vec<(f64, Value)> = issues
+        .iter()
+        .filter(|issue| {
+            let key = issue["key"].as_str().unwrap_or_default();
+            Some(key) != exclude && seen.insert(key.to_string())
+        })
+        .map(|issue| {
+            let fields = &issue["fields"];
+            let score = similarity(summary, fields["summary"].as_str().unwrap_or_default());
+            (
+                score,
+                json!({
+                    "key": issue["key"],
+                    "summary": fields["summary"],
+                    "status": fields["status"]["name"],
+                    "type": fields["issuetype"]["name"],
+                    "resolution": fields["resolution"]["name"],
+                    "created": fields["created"],
+                    "score": (score * 100.0).round() / 100.0
+                }),
+            )
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+    ranked.into_iter().take(limit).map(|(_, v)| v).collect()
+}
+
+#[async_trait]
+impl ToolHandler for FindSimilarIssuesHandler {
+    fn name(&self) -> &'static str {
+        "jira_find_similar_issues"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "summary".to_string(),
+            string_prop("Summary of the issue about to be filed", true),
+        );
+        props.insert(
+            "description".to_string(),
+            string_prop(
+                "Optional description; its most frequent words widen the text search",
+                false,
+            ),
+        );
+        props.insert(
+            "project_key".to_string(),
+            string_prop(
+                "Project to search (default: JIRA_PROJECTS_FILTER, else all projects)",
+                false,
+            ),
+        );
+        props.insert(
+            "exclude_key".to_string(),
+            string_prop(
+                "Issue key to leave out, e.g. the issue being checked",
+                false,
+            ),
+        );
+        props.insert(
+            "limit".to_string(),
+            number_prop("Max candidates returned", 10),
+        );
+        ToolSchema::new(
+            "Find likely duplicates of a draft issue: summary/text searches ranked by local fuzzy match score (0-1)",
+            props,
+            vec!["summary".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let summary = args["summary"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing summary"))?;
+        let description = args["description"].as_str().unwrap_or_default();
+        let limit = args["limit"]
+            .as_u64()
+            .map(|l| l as usize)
+            .unwrap_or(DEFAULT_LIMIT);
+        let projects: Vec<String> = match args["project_key"].as_str() {
+            Some(project) => vec![project.to_string()],
+            None => config.jira_projects_filter.clone(),
+        };
+
+        let keywords = keywords(summary, description);
+        let queries = similarity_queries(&keywords, &projects);
+        if queries.is_empty() {
+            anyhow::bail!("summary has no searchable words");
+        }
+
+        let client = create_atlassian_client(config);
+        let fields: Vec<String> = CANDIDATE_FIELDS.iter().map(|f| f.to_string()).collect();
+
+        let mut issues = Vec::new();
+        for jql in &queries {
+            let data =
+                search_page(&client, config, jql, &fields, CANDIDATES_PER_SEARCH, None).await?;
+            issues.extend(data["issues"].as_array().cloned().unwrap_or_default());
+        }
+
+        let candidates = rank_candidates(summary, &issues, args["exclude_key"].as_str(), limit);
+        Ok(json!({
+            "success": true,
+            "keywords": keywords,
+            "searched": issues.len(),
+            "candidates": candidates
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keywords_prefer_summary() {
+        let words = keywords(
+            "Login fails with SSO on Safari",
+            "The login page hangs. SSO redirect loops; redirect never returns to the redirect URL",
+        );
+        assert_eq!(words[..4], ["login", "fails", "sso", "safari"]);
+        assert_eq!(words[4], "redirect");
+        assert!(!words.contains(&"the".to_string()));
+    }
+
+    #[test]
+    fn test_similarity_orders_duplicates_first() {
+        let draft = "Login fails with SSO on Safari";
+        let duplicate = similarity(draft, "SSO login failing in Safari");
+        let related = similarity(draft, "Safari rendering glitch on dashboard");
+        assert!(duplicate > related);
+        assert_eq!(similarity(draft, draft), 1.0);
+        assert_eq!(similarity("", ""), 0.0);
+    }
+
+    #[test]
+    fn test_similarity_queries_scope() {
+        let words = vec!["login".to_string(), "sso".to_string()];
+        let queries = similarity_queries(&words, &["PROJ".to_string(), "OPS".to_string()]);
+        assert_eq!(
+            queries,
+            vec![
+                "project in (PROJ, OPS) AND summary ~ \"login sso\"",
+                "project in (PROJ, OPS) AND text ~ \"login sso\""
+            ]
+        );
+        assert!(similarity_queries(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_rank_candidates_dedupes_and_excludes() {
+        let issue = |key: &str, summary: &str| json!({"key": key, "fields": {"summary": summary}});
+        let issues = vec![
+            issue("PROJ-1", "Dashboard is slow"),
+            issue("PROJ-2", "SSO login fails on Safari"),
+            issue("PROJ-2", "SSO login fails on Safari"),
+            issue("PROJ-9", "Login fails with SSO on Safari"),
+        ];
+        let ranked = rank_candidates(
+            "Login fails with SSO on Safari",
+            &issues,
+            Some("PROJ-9"),
+            10,
+        );
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0]["key"], "PROJ-2");
+        assert!(ranked[0]["score"].as_f64().unwrap() > ranked[1]["score"].as_f64().unwrap());
+    }
+}
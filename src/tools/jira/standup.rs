@@ -0,0 +1,411 @@
+//! Stand-up digest for a board, project or team
+//!
+//! `jira_standup_digest` runs four searches over one scope at once and
+//! renders them as a short Markdown block that pastes cleanly into Slack:
+//! issues completed and started in the date range (by the date their status
+//! category last changed), issues blocked now (flagged, or in a blocked
+//! status) and open issues past their due date. Range bounds become
+//! relative JQL offsets (`-90m`), so they mean the same instant whatever
+//! timezone the Jira profile uses.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+use crate::client::Jira;
+use crate::client::models::Issue;
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::args::parse_args;
+use crate::tools::schema::{Property, ToolSchema, array_prop, number_prop, string_prop};
+use crate::utils::time;
+
+use super::apply_projects_filter;
+use super::scope::{self, ScopeArgs, quoted};
+
+pub struct StandupDigestHandler;
+
+const DIGEST_FIELDS: [&str; 5] = ["summary", "status", "assignee", "duedate", "priority"];
+const DEFAULT_PER_SECTION: u64 = 10;
+const MAX_PER_SECTION: u64 = 50;
+
+#[derive(Deserialize)]
+struct DigestArgs {
+    #[serde(flatten)]
+    scope: ScopeArgs,
+    since: Option<String>,
+    until: Option<String>,
+    blocked_statuses: Option<Vec<String>>,
+    max_per_section: Option<u64>,
+    #[serde(default)]
+    format: DigestFormat,
+}
+
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum DigestFormat {
+    #[default]
+    Markdown,
+    Json,
+}
+
+/// One section's issues; `more` when the search had further results
+#[derive(Debug, Default)]
+pub struct Section {
+    pub issues: Vec<Issue>,
+    pub more: bool,
+}
+
+/// The relative JQL offset for an instant before `now`, in whole minutes
+/// (rounded away from `now` when `widen`, towards it otherwise)
+pub fn jql_offset(instant: i64, now: i64, widen: bool) -> String {
+    let seconds = (now - instant).max(0);
+    let minutes = if widen {
+        (seconds + 59) / 60
+    } else {
+        seconds / 60
+    };
+    format!("-{}m", minutes)
+}
+
+/// `statusCategoryChangedDate` within `[since, until)`
+fn changed_within(since: i64, until: Option<i64>, now: i64) -> String {
+    let mut clause = format!(
+        "statusCategoryChangedDate >= {}",
+        jql_offset(since, now, true)
+    );
+    if let Some(until) = until {
+        clause.push_str(&format!(
+            " AND statusCategoryChangedDate < {}",
+            jql_offset(until, now, false)
+        ));
+    }
+    clause
+}
+
+/// The four section queries over `scope`: completed, started, blocked,
+/// overdue
+pub fn section_queries(
+    scope: &str,
+    since: i64,
+    until: Option<i64>,
+    now: i64,
+    blocked_statuses: &[String],
+) -> [String; 4] {
+    let within = changed_within(since, until, now);
+    let mut blocked = vec!["flagged = Impediment".to_string()];
+    if !blocked_statuses.is_empty() {
+        let statuses: Vec<String> = blocked_statuses.iter().map(|s| quoted(s)).collect();
+        blocked.push(format!("status in ({})", statuses.join(", ")));
+    }
+    [
+        format!(
+            "({}) AND statusCategory = Done AND {} ORDER BY statusCategoryChangedDate DESC",
+            scope, within
+        ),
+        format!(
+            "({}) AND statusCategory = \"In Progress\" AND {} ORDER BY statusCategoryChangedDate DESC",
+            scope, within
+        ),
+        format!(
+            "({}) AND statusCategory != Done AND ({}) ORDER BY priority DESC",
+            scope,
+            blocked.join(" OR ")
+        ),
+        format!(
+            "({}) AND statusCategory != Done AND duedate < startOfDay() ORDER BY duedate ASC",
+            scope
+        ),
+    ]
+}
+
+async fn search_section(
+    jira: &Jira<'_>,
+    config: &Config,
+    jql: &str,
+    limit: u64,
+) -> Result<Section> {
+    let fields: Vec<String> = DIGEST_FIELDS.iter().map(|f| f.to_string()).collect();
+    let page = jira
+        .search(&apply_projects_filter(jql, config), &fields, limit, None)
+        .await?;
+    Ok(Section {
+        more: page.next_page_token.is_some() || page.is_last == Some(false),
+        issues: page.issues,
+    })
+}
+
+/// `KEY Summary (@Assignee, due 2026-10-10)`
+fn issue_line(issue: &Issue, show_due: bool) -> String {
+    let fields = &issue.fields;
+    let mut notes = Vec::new();
+    if let Some(name) = fields
+        .assignee
+        .as_ref()
+        .and_then(|a| a.display_name.as_deref())
+    {
+        notes.push(format!("@{}", name));
+    }
+    if show_due && let Some(due) = fields.duedate.as_deref() {
+        notes.push(format!("due {}", due));
+    }
+    let mut line = format!(
+        "{} {}",
+        issue.key,
+        fields.summary.as_deref().unwrap_or_default()
+    );
+    if !notes.is_empty() {
+        line.push_str(&format!(" ({})", notes.join(", ")));
+    }
+    line
+}
+
+/// `(title, section)` in display order; a failed search keeps its error
+pub type Sections<'a> = [(&'a str, Result<Section>); 4];
+
+/// The Slack-ready block: a bold title line, then a bold heading and one
+/// bullet per issue for each section
+pub fn render_digest(title: &str, sections: &Sections) -> String {
+    let mut out = format!("*{}*\n", title);
+    for (name, section) in sections {
+        match section {
+            Ok(section) => {
+                let count = if section.more {
+                    format!("{}+", section.issues.len())
+                } else {
+                    section.issues.len().to_string()
+                };
+                out.push_str(&format!("\n*{} ({})*\n", name, count));
+                if section.issues.is_empty() {
+                    out.push_str("- none\n");
+                }
+                for issue in &section.issues {
+                    out.push_str(&format!("- {}\n", issue_line(issue, *name == "Overdue")));
+                }
+                if section.more {
+                    out.push_str("- …and more\n");
+                }
+            }
+            Err(e) => out.push_str(&format!("\n*{}*\n- unavailable: {}\n", name, e)),
+        }
+    }
+    out
+}
+
+/// `YYYY-MM-DD HH:MM` in the configured zone
+fn local_minute(instant: i64, zone: &time::Timezone) -> String {
+    time::format_timestamp(instant, 0, zone)[..16].replace('T', " ")
+}
+
+#[async_trait]
+impl ToolHandler for StandupDigestHandler {
+    fn name(&self) -> &'static str {
+        "jira_standup_digest"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        scope::scope_props(&mut props);
+        props.insert(
+            "since".to_string(),
+            string_prop(
+                "Start of the range: a timestamp, a window such as '24h', or a day such as 'yesterday' or 'friday' (default: yesterday)",
+                false,
+            ),
+        );
+        props.insert(
+            "until".to_string(),
+            string_prop("End of the range, in the same forms (default: now)", false),
+        );
+        props.insert(
+            "blocked_statuses".to_string(),
+            array_prop(
+                "Statuses that count as blocked, besides flagged issues (default: [\"Blocked\"])",
+            ),
+        );
+        props.insert(
+            "max_per_section".to_string(),
+            number_prop(
+                "Issues listed per section (1-50)",
+                DEFAULT_PER_SECTION as i32,
+            )
+            .with_range(Some(1.0), Some(MAX_PER_SECTION as f64)),
+        );
+        props.insert(
+            "format".to_string(),
+            Property::new("string")
+                .with_description("markdown: a block ready to paste into Slack; json: the sections as compact issues (default: markdown)")
+                .with_default(json!("markdown"))
+                .with_enum(vec![json!("markdown"), json!("json")]),
+        );
+        ToolSchema::new(
+            "Stand-up digest for a board, project or team: issues completed and started in a date range, blocked now, and overdue",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let DigestArgs {
+            scope,
+            since,
+            until,
+            blocked_statuses,
+            max_per_section,
+            format,
+        } = parse_args(&args)?;
+        let limit = max_per_section
+            .unwrap_or(DEFAULT_PER_SECTION)
+            .clamp(1, MAX_PER_SECTION);
+        let blocked_statuses = blocked_statuses.unwrap_or_else(|| vec!["Blocked".to_string()]);
+
+        let zone = time::user_timezone(config)?;
+        let now = time::now();
+        let instant = |input: &str, arg: &str| {
+            time::since_instant(input, &zone, now).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Invalid {} '{}': expected a timestamp, a window such as '24h', or a date",
+                    arg,
+                    input
+                )
+            })
+        };
+        let since_at = instant(since.as_deref().unwrap_or("yesterday"), "since")?;
+        let until_at = until.as_deref().map(|u| instant(u, "until")).transpose()?;
+        if until_at.is_some_and(|until| until <= since_at) {
+            anyhow::bail!("until must be after since");
+        }
+
+        let jira = Jira::new(config);
+        let scope::Scope { jql, names } = scope::resolve(&jira, config, &scope).await?;
+
+        let [completed, started, blocked, overdue] =
+            section_queries(&jql, since_at, until_at, now, &blocked_statuses);
+        let (completed, started, mut blocked, overdue) = tokio::join!(
+            search_section(&jira, config, &completed, limit),
+            search_section(&jira, config, &started, limit),
+            search_section(&jira, config, &blocked, limit),
+            search_section(&jira, config, &overdue, limit)
+        );
+        // An unknown status fails the whole query; flagged issues still count
+        if blocked.is_err() && !blocked_statuses.is_empty() {
+            let [_, _, flagged, _] = section_queries(&jql, since_at, until_at, now, &[]);
+            blocked = search_section(&jira, config, &flagged, limit).await;
+        }
+        let sections: Sections = [
+            ("Completed", completed),
+            ("Started", started),
+            ("Blocked", blocked),
+            ("Overdue", overdue),
+        ];
+
+        let range = format!(
+            "{} → {}",
+            local_minute(since_at, &zone),
+            until_at.map_or_else(|| "now".to_string(), |until| local_minute(until, &zone))
+        );
+        if format == DigestFormat::Markdown {
+            let title = if names.is_empty() {
+                format!("Stand-up, {}", range)
+            } else {
+                format!("Stand-up: {}, {}", names.join(" / "), range)
+            };
+            return Ok(Value::String(render_digest(&title, &sections)));
+        }
+
+        let mut result = json!({
+            "success": true,
+            "scope": names,
+            "since": time::format_timestamp(since_at, 0, &zone),
+            "until": until_at.map(|until| time::format_timestamp(until, 0, &zone)),
+        });
+        for (name, section) in &sections {
+            result[name.to_lowercase()] = match section {
+                Ok(section) => json!({
+                    "issues": section.issues.iter().map(|issue| json!({
+                        "key": issue.key,
+                        "summary": issue.fields.summary,
+                        "status": issue.fields.status.as_ref().map(|s| s.name.as_str()),
+                        "assignee": issue.fields.assignee.as_ref().and_then(|a| a.display_name.as_deref()),
+                        "duedate": issue.fields.duedate
+                    })).collect::<Vec<_>>(),
+                    "has_more": section.more
+                }),
+                Err(e) => json!({"error": e.to_string()}),
+            };
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(key: &str, summary: &str, assignee: Option<&str>, due: Option<&str>) -> Issue {
+        serde_json::from_value(json!({
+            "key": key,
+            "fields": {
+                "summary": summary,
+                "assignee": assignee.map(|name| json!({"displayName": name})),
+                "duedate": due
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_section_queries_use_relative_offsets() {
+        let now = 1_000_000;
+        let [completed, started, blocked, overdue] = section_queries(
+            "project = \"DEMO\"",
+            now - 90 * 60 - 1,
+            Some(now - 30 * 60 - 1),
+            now,
+            &["Blocked".to_string()],
+        );
+        assert_eq!(
+            completed,
+            "(project = \"DEMO\") AND statusCategory = Done AND statusCategoryChangedDate >= -91m \
+             AND statusCategoryChangedDate < -30m ORDER BY statusCategoryChangedDate DESC"
+        );
+        assert!(
+            started
+                .contains("statusCategory = \"In Progress\" AND statusCategoryChangedDate >= -91m")
+        );
+        assert_eq!(
+            blocked,
+            "(project = \"DEMO\") AND statusCategory != Done AND (flagged = Impediment OR status in (\"Blocked\")) ORDER BY priority DESC"
+        );
+        assert!(overdue.contains("duedate < startOfDay()"));
+    }
+
+    #[test]
+    fn test_render_digest() {
+        let sections: Sections = [
+            (
+                "Completed",
+                Ok(Section {
+                    issues: vec![issue("DEMO-1", "Set up CI", Some("Sam"), None)],
+                    more: false,
+                }),
+            ),
+            ("Started", Ok(Section::default())),
+            ("Blocked", Err(anyhow::anyhow!("Search failed: 400"))),
+            (
+                "Overdue",
+                Ok(Section {
+                    issues: vec![issue("DEMO-4", "Write guide", None, Some("2026-10-10"))],
+                    more: true,
+                }),
+            ),
+        ];
+        assert_eq!(
+            render_digest("Stand-up: DEMO", &sections),
+            "*Stand-up: DEMO*\n\n*Completed (1)*\n- DEMO-1 Set up CI (@Sam)\n\n*Started (0)*\n- none\n\
+             \n*Blocked*\n- unavailable: Search failed: 400\n\n*Overdue (1+)*\n- DEMO-4 Write guide (due 2026-10-10)\n- …and more\n"
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Tabular rendering of Jira search results
+//!
+//! Flattens nested issue fields (e.g., `status.name`, `assignee.displayName`)
+//! into single-line cell values and renders them as a compact Markdown table.
+//! A table costs a fraction of the tokens of the equivalent pretty-printed JSON
+//! for large result sets. The same flattening backs CSV export.
+
+use anyhow::Result;
+use serde_json::Value;
+
+/// Output formats supported by `jira_search`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Optimized JSON (default)
+    Json,
+    /// Markdown table with flattened fields
+    Table,
+    /// CSV text with flattened fields (`export_format: "csv"`)
+    Csv,
+}
+
+impl OutputFormat {
+    /// Parse the `format` argument (missing means JSON)
+    pub fn from_arg(value: &Value) -> Result<Self> {
+        match value.as_str() {
+            None => Ok(Self::Json),
+            Some(s) if s.eq_ignore_ascii_case("json") => Ok(Self::Json),
+            Some(s) if s.eq_ignore_ascii_case("table") => Ok(Self::Table),
+            Some(other) => anyhow::bail!("Invalid format '{}': expected 'json' or 'table'", other),
+        }
+    }
+
+    /// `export_format` when given, otherwise `format`
+    pub fn from_args(args: &Value) -> Result<Self> {
+        match args["export_format"].as_str() {
+            None => Self::from_arg(&args["format"]),
+            Some(s) if s.eq_ignore_ascii_case("csv") => Ok(Self::Csv),
+            Some(other) => anyhow::bail!("Invalid export_format '{}': expected 'csv'", other),
+        }
+    }
+}
+
+/// Keys probed (in order) when flattening an object to a single display value
+const DISPLAY_KEYS: &[&str] = &["displayName", "name", "value", "key", "id"];
+
+/// Flattens a Jira field value into a single-line display string.
+///
+/// - Objects use the first present of `displayName`, `name`, `value`, `key`, `id`
+/// - Arrays are flattened element-wise and joined with `", "`
+/// - `null` becomes an empty string
+pub fn flatten_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Array(items) => items
+            .iter()
+            .map(flatten_field)
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Object(map) => DISPLAY_KEYS
+            .iter()
+            .find_map(|k| map.get(*k).filter(|v| !v.is_null()))
+            .map(flatten_field)
+            .unwrap_or_default(),
+    }
+}
+
+/// Extracts the flattened cell value for `field` from a search result issue.
+///
+/// `key` lives at the top level of the issue; everything else under `fields`.
+pub fn issue_cell(issue: &Value, field: &str) -> String {
+    if field == "key" {
+        return flatten_field(&issue["key"]);
+    }
+    flatten_field(&issue["fields"][field])
+}
+
+/// `key` first, then the requested fields in order
+fn table_columns(fields: &[String]) -> Vec<&str> {
+    let mut columns: Vec<&str> = vec!["key"];
+    columns.extend(fields.iter().map(String::as_str).filter(|f| *f != "key"));
+    columns
+}
+
+fn escape_markdown_cell(cell: &str) -> String {
+    cell.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// Renders issues as a Markdown table with one column per requested field.
+///
+/// `key` is always the first column, even when not listed in `fields`.
+/// Columns empty in every row are left out.
+pub fn issues_to_markdown_table(issues: &[Value], fields: &[String]) -> String {
+    let columns = table_columns(fields);
+    let rows: Vec<Vec<String>> = issues
+        .iter()
+        .map(|issue| {
+            columns
+                .iter()
+                .map(|c| escape_markdown_cell(&issue_cell(issue, c)))
+                .collect()
+        })
+        .collect();
+    // Without rows there is nothing to judge a column by
+    let kept: Vec<usize> = (0..columns.len())
+        .filter(|&i| i == 0 || rows.is_empty() || rows.iter().any(|row| !row[i].is_empty()))
+        .collect();
+
+    let mut out = String::new();
+    out.push_str("| ");
+    let header: Vec<&str> = kept.iter().map(|&i| columns[i]).collect();
+    out.push_str(&header.join(" | "));
+    out.push_str(" |\n|");
+    for _ in &kept {
+        out.push_str(" --- |");
+    }
+    out.push('\n');
+
+    for row in &rows {
+        let cells: Vec<&str> = kept.iter().map(|&i| row[i].as_str()).collect();
+        out.push_str("| ");
+        out.push_str(&cells.join(" | "));
+        out.push_str(" |\n");
+    }
+
+    out
+}
+
+/// Renders issues as CSV with a header row, using the same columns and
+/// flattening as the Markdown table
+pub fn issues_to_csv(issues: &[Value], fields: &[String]) -> String {
+    let columns = table_columns(fields);
+    let header: Vec<String> = columns.iter().map(|c| c.to_string()).collect();
+    let rows = issues.iter().map(|issue| {
+        columns
+            .iter()
+            .map(|c| issue_cell(issue, c))
+            .collect::<Vec<_>>()
+    });
+    crate::utils::csv::write(std::iter::once(header).chain(rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_issue() -> Value {
+        json!({
+            "key": "PROJ-1",
+            "fields": {
+                "summary": "Fix | login",
+                "status": {"name": "In Progress", "statusCategory": {"key": "indeterminate"}},
+                "assignee": {"displayName": "Jane Doe", "accountId": "abc"},
+                "labels": ["backend", "urgent"],
+                "components": [{"name": "API"}, {"name": "Auth"}],
+                "parent": {"key": "PROJ-0", "fields": {"summary": "Epic"}},
+                "duedate": null,
+                "customfield_10016": 5
+            }
+        })
+    }
+
+    #[test]
+    fn test_output_format_from_arg() {
+        assert_eq!(
+            OutputFormat::from_arg(&Value::Null).unwrap(),
+            OutputFormat::Json
+        );
+        assert_eq!(
+            OutputFormat::from_arg(&json!("TABLE")).unwrap(),
+            OutputFormat::Table
+        );
+        assert!(OutputFormat::from_arg(&json!("xml")).is_err());
+    }
+
+    #[test]
+    fn test_export_format_takes_precedence() {
+        assert_eq!(
+            OutputFormat::from_args(&json!({"export_format": "csv", "format": "table"})).unwrap(),
+            OutputFormat::Csv
+        );
+        assert_eq!(
+            OutputFormat::from_args(&json!({"format": "table"})).unwrap(),
+            OutputFormat::Table
+        );
+        assert!(OutputFormat::from_args(&json!({"export_format": "xlsx"})).is_err());
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let fields = vec!["summary".to_string(), "labels".to_string()];
+        let csv = issues_to_csv(&[sample_issue()], &fields);
+        assert_eq!(
+            csv,
+            "key,summary,labels\nPROJ-1,Fix | login,\"backend, urgent\"\n"
+        );
+    }
+
+    #[test]
+    fn test_flatten_nested_objects() {
+        let issue = sample_issue();
+        assert_eq!(issue_cell(&issue, "status"), "In Progress");
+        assert_eq!(issue_cell(&issue, "assignee"), "Jane Doe");
+        assert_eq!(issue_cell(&issue, "parent"), "PROJ-0");
+    }
+
+    #[test]
+    fn test_flatten_arrays_and_scalars() {
+        let issue = sample_issue();
+        assert_eq!(issue_cell(&issue, "labels"), "backend, urgent");
+        assert_eq!(issue_cell(&issue, "components"), "API, Auth");
+        assert_eq!(issue_cell(&issue, "customfield_10016"), "5");
+        assert_eq!(issue_cell(&issue, "duedate"), "");
+        assert_eq!(issue_cell(&issue, "missing"), "");
+    }
+
+    #[test]
+    fn test_markdown_table_layout() {
+        let fields = vec![
+            "key".to_string(),
+            "summary".to_string(),
+            "status".to_string(),
+        ];
+        let table = issues_to_markdown_table(&[sample_issue()], &fields);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines[0], "| key | summary | status |");
+        assert_eq!(lines[1], "| --- | --- | --- |");
+        assert_eq!(lines[2], "| PROJ-1 | Fix \\| login | In Progress |");
+    }
+
+    #[test]
+    fn test_markdown_table_always_leads_with_key() {
+        let table = issues_to_markdown_table(&[sample_issue()], &["summary".to_string()]);
+        assert!(table.starts_with("| key | summary |"));
+    }
+
+    #[test]
+    fn test_markdown_table_drops_empty_columns() {
+        let other = json!({"key": "PROJ-2", "fields": {"summary": "Other"}});
+        let fields = vec![
+            "summary".to_string(),
+            "duedate".to_string(),
+            "labels".to_string(),
+        ];
+        let table = issues_to_markdown_table(&[sample_issue(), other], &fields);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "| key | summary | labels |");
+        assert_eq!(lines[1], "| --- | --- | --- |");
+        assert_eq!(lines[3], "| PROJ-2 | Other |  |");
+
+        let empty = issues_to_markdown_table(&[], &fields);
+        assert!(empty.starts_with("| key | summary | duedate | labels |"));
+    }
+
+    #[test]
+    fn test_markdown_table_escapes_newlines() {
+        let issue = json!({"key": "A-1", "fields": {"summary": "line1\nline2"}});
+        let table = issues_to_markdown_table(&[issue], &["summary".to_string()]);
+        assert!(table.contains("| A-1 | line1 line2 |"));
+    }
+}
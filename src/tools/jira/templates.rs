@@ -0,0 +1,431 @@
+//! Issue description templates
+//!
+//! Templates are a small Markdown subset (`#` headings, `-`/`*` bullets,
+//! `1.` numbered items, paragraphs) with `{{placeholder}}` and
+//! `{{placeholder|default}}` tokens. Sources, in lookup order:
+//!
+//! - `confluence_page_id`: the page body, fetched as ADF so its structure is kept
+//! - `JIRA_TEMPLATES_DIR/<name>.md`
+//! - built-in `bug`, `story` and `incident`
+//!
+//! A file may start with an `issue_type: <name>` line to set its default type.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Map, Value, json};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema, string_prop};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+
+use super::CreateIssueHandler;
+use super::adf_utils::markdown_to_adf;
+
+/// `(name, default issue type, body)`
+const BUILTIN_TEMPLATES: &[(&str, &str, &str)] = &[
+    (
+        "bug",
+        "Bug",
+        "## Summary\n{{summary}}\n\n## Steps to Reproduce\n{{steps}}\n\n## Expected Result\n{{expected}}\n\n## Actual Result\n{{actual}}\n\n## Environment\n{{environment|Not specified}}\n",
+    ),
+    (
+        "story",
+        "Story",
+        "## User Story\nAs a {{persona}}, I want {{goal}} so that {{benefit}}.\n\n## Acceptance Criteria\n{{acceptance_criteria}}\n\n## Notes\n{{notes|None}}\n",
+    ),
+    (
+        "incident",
+        "Incident",
+        "## Impact\n{{impact}}\n\n## Timeline\n{{timeline|TBD}}\n\n## Root Cause\n{{root_cause|Under investigation}}\n\n## Remediation\n{{remediation|TBD}}\n",
+    ),
+];
+
+pub struct CreateIssueFromTemplateHandler;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    pub issue_type: Option<String>,
+    pub body: String,
+}
+
+impl Template {
+    /// Splits off a leading `issue_type:` line
+    pub fn parse(source: &str) -> Self {
+        let mut lines = source.splitn(2, '\n');
+        let first = lines.next().unwrap_or_default();
+        match first.strip_prefix("issue_type:") {
+            Some(issue_type) => Self {
+                issue_type: Some(issue_type.trim().to_string()),
+                body: lines
+                    .next()
+                    .unwrap_or_default()
+                    .trim_start_matches('\n')
+                    .to_string(),
+            },
+            None => Self {
+                issue_type: None,
+                body: source.to_string(),
+            },
+        }
+    }
+}
+
+/// Looks up a named template: templates directory first, then built-ins
+pub fn load_template(name: &str, templates_dir: Option<&str>) -> Result<Template> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!("Invalid template name '{}'", name);
+    }
+
+    if let Some(dir) = templates_dir {
+        let path = Path::new(dir).join(format!("{}.md", name));
+        if path.is_file() {
+            let source = std::fs::read_to_string(&path)?;
+            return Ok(Template::parse(&source));
+        }
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _, _)| *builtin == name)
+        .map(|(_, issue_type, body)| Template {
+            issue_type: Some(issue_type.to_string()),
+            body: body.to_string(),
+        })
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Template '{}' not found; available: {}",
+                name,
+                available_templates(templates_dir).join(", ")
+            )
+        })
+}
+
+/// Built-in names plus `*.md` files in the templates directory
+pub fn available_templates(templates_dir: Option<&str>) -> Vec<String> {
+    let mut names: BTreeSet<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _, _)| name.to_string())
+        .collect();
+    if let Some(entries) = templates_dir.and_then(|dir| std::fs::read_dir(dir).ok()) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "md")
+                && let Some(stem) = path.file_stem().and_then(|s| s.to_str())
+            {
+                names.insert(stem.to_string());
+            }
+        }
+    }
+    names.into_iter().collect()
+}
+
+/// Fills `{{name}}`/`{{name|default}}` tokens; unfilled tokens without a
+/// default are reported together
+pub fn fill_placeholders(template: &str, values: &Map<String, Value>) -> Result<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut missing = BTreeSet::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let token = &rest[start + 2..start + 2 + len];
+        let (name, default) = match token.split_once('|') {
+            Some((name, default)) => (name.trim(), Some(default.trim())),
+            None => (token.trim(), None),
+        };
+
+        match (values.get(name), default) {
+            (Some(Value::String(s)), _) => out.push_str(s),
+            (Some(Value::Array(items)), _) => {
+                // Arrays become bullet lists
+                let lines: Vec<String> = items.iter().map(|i| format!("- {}", plain(i))).collect();
+                out.push_str(&lines.join("\n"));
+            }
+            (Some(v), _) if !v.is_null() => out.push_str(&plain(v)),
+            (_, Some(default)) => out.push_str(default),
+            _ => {
+                missing.insert(name.to_string());
+            }
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "Missing template values: {}",
+            missing.into_iter().collect::<Vec<_>>().join(", ")
+        );
+    }
+    Ok(out)
+}
+
+fn plain(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Fills placeholders inside the text nodes of an ADF template
+pub fn fill_adf_placeholders(node: &mut Value, values: &Map<String, Value>) -> Result<()> {
+    match node {
+        Value::Object(map) => {
+            if map.get("type").and_then(Value::as_str) == Some("text")
+                && let Some(Value::String(text)) = map.get_mut("text")
+            {
+                *text = fill_placeholders(text, values)?;
+            }
+            for child in map.values_mut() {
+                fill_adf_placeholders(child, values)?;
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                fill_adf_placeholders(item, values)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// A Confluence page body as an ADF template
+async fn fetch_confluence_template(config: &Config, page_id: &str) -> Result<Value> {
+    let client = create_atlassian_client(config);
+    let url = format!(
+        "{}/wiki/api/v2/pages/{}",
+        config.get_atlassian_base_url(),
+        page_id
+    );
+    let response = client
+        .get(&url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .query(&[("body-format", "atlas_doc_format")])
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get template page: {}", response.status());
+    }
+
+    let page: Value = response.json().await?;
+    // The ADF body arrives as a JSON string
+    let adf = page["body"]["atlas_doc_format"]["value"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Template page has no ADF body"))?;
+    Ok(serde_json::from_str(adf)?)
+}
+
+#[async_trait]
+impl ToolHandler for CreateIssueFromTemplateHandler {
+    fn name(&self) -> &'static str {
+        "jira_create_issue_from_template"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("project_key".to_string(), string_prop("Project key", true));
+        props.insert(
+            "summary".to_string(),
+            string_prop("Issue summary ({{placeholders}} allowed)", true),
+        );
+        props.insert(
+            "template".to_string(),
+            string_prop(
+                "Template name: bug, story, incident, or a JIRA_TEMPLATES_DIR file",
+                false,
+            ),
+        );
+        props.insert(
+            "confluence_page_id".to_string(),
+            string_prop("Use a Confluence page as the template instead", false),
+        );
+        props.insert(
+            "values".to_string(),
+            Property::new("object")
+                .with_description("Placeholder values; arrays become bullet lists"),
+        );
+        props.insert(
+            "issue_type".to_string(),
+            string_prop("Issue type (default: from the template)", false),
+        );
+        props.insert(
+            "preview".to_string(),
+            Property::new("boolean")
+                .with_description(
+                    "Return the rendered summary and ADF without creating the issue".to_string(),
+                )
+                .with_default(json!(false)),
+        );
+        ToolSchema::new(
+            "Create a Jira issue from a description template (bug, story, incident, custom)",
+            props,
+            vec!["project_key".to_string(), "summary".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let project_key = args["project_key"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing project_key"))?;
+        let summary = args["summary"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing summary"))?;
+        let empty = Map::new();
+        let values = args["values"].as_object().unwrap_or(&empty);
+
+        let (description, default_type) = match args["confluence_page_id"].as_str() {
+            Some(page_id) => {
+                let mut adf = fetch_confluence_template(config, page_id).await?;
+                fill_adf_placeholders(&mut adf, values)?;
+                (adf, None)
+            }
+            None => {
+                let name = args["template"]
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("Missing template or confluence_page_id"))?;
+                let template = load_template(name, config.jira_templates_dir.as_deref())?;
+                let filled = fill_placeholders(&template.body, values)?;
+                (markdown_to_adf(&filled), template.issue_type)
+            }
+        };
+
+        let issue_type = args["issue_type"]
+            .as_str()
+            .map(String::from)
+            .or(default_type)
+            .ok_or_else(|| anyhow::anyhow!("Missing issue_type"))?;
+        let summary = fill_placeholders(summary, values)?;
+
+        if args["preview"].as_bool().unwrap_or(false) {
+            return Ok(json!({
+                "success": true,
+                "preview": true,
+                "project_key": project_key,
+                "issue_type": issue_type,
+                "summary": summary,
+                "description": description
+            }));
+        }
+
+        CreateIssueHandler
+            .execute(
+                json!({
+                    "project_key": project_key,
+                    "summary": summary,
+                    "issue_type": issue_type,
+                    "description": description
+                }),
+                config,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(v: Value) -> Map<String, Value> {
+        v.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_fill_placeholders() {
+        let out = fill_placeholders(
+            "{{who}} saw {{count}} errors in {{env|prod}}",
+            &values(json!({"who": "Jane", "count": 3})),
+        )
+        .unwrap();
+        assert_eq!(out, "Jane saw 3 errors in prod");
+    }
+
+    #[test]
+    fn test_fill_placeholders_reports_missing() {
+        let err = fill_placeholders("{{a}} {{b}} {{a}}", &Map::new()).unwrap_err();
+        assert_eq!(err.to_string(), "Missing template values: a, b");
+    }
+
+    #[test]
+    fn test_fill_placeholders_array_as_bullets() {
+        let out =
+            fill_placeholders("{{steps}}", &values(json!({"steps": ["Open", "Click"]}))).unwrap();
+        assert_eq!(out, "- Open\n- Click");
+    }
+
+    #[test]
+    fn test_markdown_to_adf() {
+        let adf = markdown_to_adf("## Steps\n1. Open\n2. Click\n\nLine one\nline two\n- a\n- b");
+        let content = adf["content"].as_array().unwrap();
+
+        assert_eq!(content[0]["type"], "heading");
+        assert_eq!(content[0]["attrs"]["level"], 2);
+        assert_eq!(content[1]["type"], "orderedList");
+        assert_eq!(content[1]["content"].as_array().unwrap().len(), 2);
+        assert_eq!(content[2]["type"], "paragraph");
+        assert_eq!(content[2]["content"][1]["type"], "hardBreak");
+        assert_eq!(content[3]["type"], "bulletList");
+        assert!(crate::tools::jira::adf_utils::validate_adf(&adf).is_ok());
+    }
+
+    #[test]
+    fn test_template_parse_issue_type() {
+        let template = Template::parse("issue_type: Task\n\n## Body\n{{x}}");
+        assert_eq!(template.issue_type.as_deref(), Some("Task"));
+        assert_eq!(template.body, "## Body\n{{x}}");
+
+        assert_eq!(Template::parse("## Body").issue_type, None);
+    }
+
+    #[test]
+    fn test_load_builtin_and_override() {
+        let bug = load_template("bug", None).unwrap();
+        assert_eq!(bug.issue_type.as_deref(), Some("Bug"));
+
+        let dir = std::env::temp_dir().join(format!("mcp-templates-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("bug.md"), "issue_type: Defect\n## Custom").unwrap();
+        std::fs::write(dir.join("spike.md"), "## Spike").unwrap();
+
+        let dir_str = dir.to_str().unwrap();
+        let custom = load_template("bug", Some(dir_str)).unwrap();
+        assert_eq!(custom.issue_type.as_deref(), Some("Defect"));
+        assert_eq!(
+            available_templates(Some(dir_str)),
+            vec!["bug", "incident", "spike", "story"]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_template_rejects_paths() {
+        assert!(load_template("../etc/passwd", None).is_err());
+        let err = load_template("epic", None).unwrap_err().to_string();
+        assert!(err.contains("available: bug, incident, story"));
+    }
+
+    #[test]
+    fn test_fill_adf_placeholders() {
+        let mut adf = json!({
+            "type": "doc",
+            "version": 1,
+            "content": [{"type": "paragraph", "content": [{"type": "text", "text": "Owner: {{owner}}"}]}]
+        });
+        fill_adf_placeholders(&mut adf, &values(json!({"owner": "SRE"}))).unwrap();
+        assert_eq!(adf["content"][0]["content"][0]["text"], "Owner: SRE");
+    }
+}
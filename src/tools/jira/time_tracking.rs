@@ -0,0 +1,246 @@
+//! Time tracking roll-ups
+//!
+//! `jira_time_tracking_summary` totals original estimate, time spent and
+//! remaining estimate over an epic's children or a JQL result set, overall
+//! and per assignee. Epic children are read with the `aggregate*` fields so
+//! their sub-tasks count too; JQL results use each issue's own fields, since
+//! the set may already contain the sub-tasks.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::pagination::MAX_AUTO_PAGINATE_ITEMS;
+use crate::tools::schema::{ToolSchema, string_prop};
+use crate::utils::http_utils::create_atlassian_client;
+
+use super::{SEARCH_PAGE_SIZE, apply_projects_filter, search_page};
+
+pub struct TimeTrackingSummaryHandler;
+
+/// Jira's default working day and week, used for `1w 2d 3h` renderings
+const SECONDS_PER_HOUR: u64 = 3600;
+const HOURS_PER_DAY: u64 = 8;
+const DAYS_PER_WEEK: u64 = 5;
+
+/// `(original estimate, time spent, remaining)` field IDs
+const OWN_FIELDS: [&str; 3] = ["timeoriginalestimate", "timespent", "timeestimate"];
+const AGGREGATE_FIELDS: [&str; 3] = [
+    "aggregatetimeoriginalestimate",
+    "aggregatetimespent",
+    "aggregatetimeestimate",
+];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Totals {
+    pub issues: u64,
+    pub unestimated: u64,
+    pub original_estimate: u64,
+    pub time_spent: u64,
+    pub remaining: u64,
+}
+
+impl Totals {
+    fn add(&mut self, issue: &Value, fields: &[&str; 3]) {
+        let seconds = |field: &str| issue["fields"][field].as_u64();
+        self.issues += 1;
+        match seconds(fields[0]) {
+            Some(estimate) => self.original_estimate += estimate,
+            None => self.unestimated += 1,
+        }
+        self.time_spent += seconds(fields[1]).unwrap_or(0);
+        self.remaining += seconds(fields[2]).unwrap_or(0);
+    }
+
+    fn to_json(self) -> Value {
+        let progress = (self.time_spent + self.remaining > 0).then(|| {
+            (self.time_spent as f64 * 100.0 / (self.time_spent + self.remaining) as f64).round()
+        });
+        json!({
+            "issues": self.issues,
+            "unestimated_issues": self.unestimated,
+            "original_estimate_seconds": self.original_estimate,
+            "time_spent_seconds": self.time_spent,
+            "remaining_seconds": self.remaining,
+            "original_estimate": format_duration(self.original_estimate),
+            "time_spent": format_duration(self.time_spent),
+            "remaining": format_duration(self.remaining),
+            "progress_percent": progress
+        })
+    }
+}
+
+/// Seconds as Jira renders them: `1w 2d 3h 30m`, `0m` for nothing
+pub fn format_duration(seconds: u64) -> String {
+    let day = SECONDS_PER_HOUR * HOURS_PER_DAY;
+    let week = day * DAYS_PER_WEEK;
+    let parts: Vec<String> = [
+        (seconds / week, "w"),
+        (seconds % week / day, "d"),
+        (seconds % day / SECONDS_PER_HOUR, "h"),
+        (seconds % SECONDS_PER_HOUR / 60, "m"),
+    ]
+    .iter()
+    .filter(|(n, _)| *n > 0)
+    .map(|(n, unit)| format!("{}{}", n, unit))
+    .collect();
+    if parts.is_empty() {
+        "0m".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// Overall totals and totals per assignee display name ("Unassigned" for none)
+pub fn summarize(issues: &[Value], fields: &[&str; 3]) -> (Totals, BTreeMap<String, Totals>) {
+    let mut total = Totals::default();
+    let mut per_assignee: BTreeMap<String, Totals> = BTreeMap::new();
+    for issue in issues {
+        total.add(issue, fields);
+        let assignee = issue["fields"]["assignee"]["displayName"]
+            .as_str()
+            .unwrap_or("Unassigned");
+        per_assignee
+            .entry(assignee.to_string())
+            .or_default()
+            .add(issue, fields);
+    }
+    (total, per_assignee)
+}
+
+#[async_trait]
+impl ToolHandler for TimeTrackingSummaryHandler {
+    fn name(&self) -> &'static str {
+        "jira_time_tracking_summary"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "epic_key".to_string(),
+            string_prop(
+                "Epic whose children (with their sub-tasks) are totalled",
+                false,
+            ),
+        );
+        props.insert(
+            "jql".to_string(),
+            string_prop(
+                "JQL selecting the issues to total (instead of epic_key)",
+                false,
+            ),
+        );
+        ToolSchema::new(
+            "Total original estimate, time spent and remaining over an epic or JQL set, overall and per assignee",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let (jql, time_fields) = match (args["epic_key"].as_str(), args["jql"].as_str()) {
+            (Some(epic), None) => (format!("parent = {}", epic), AGGREGATE_FIELDS),
+            (None, Some(jql)) => (apply_projects_filter(jql, config), OWN_FIELDS),
+            _ => anyhow::bail!("Pass exactly one of epic_key or jql"),
+        };
+
+        let client = create_atlassian_client(config);
+        let mut fields: Vec<String> = time_fields.iter().map(|f| f.to_string()).collect();
+        fields.push("assignee".to_string());
+
+        let mut issues: Vec<Value> = Vec::new();
+        let mut token: Option<String> = None;
+        let truncated = loop {
+            let data = search_page(
+                &client,
+                config,
+                &jql,
+                &fields,
+                SEARCH_PAGE_SIZE as u64,
+                token.as_deref(),
+            )
+            .await?;
+            issues.extend(data["issues"].as_array().cloned().unwrap_or_default());
+            token = data["nextPageToken"].as_str().map(String::from);
+            let is_last = data["isLast"].as_bool().unwrap_or(true) || token.is_none();
+            if issues.len() >= MAX_AUTO_PAGINATE_ITEMS {
+                let truncated = issues.len() > MAX_AUTO_PAGINATE_ITEMS || !is_last;
+                issues.truncate(MAX_AUTO_PAGINATE_ITEMS);
+                break truncated;
+            }
+            if is_last {
+                break false;
+            }
+        };
+
+        let (total, per_assignee) = summarize(&issues, &time_fields);
+        let by_assignee: Vec<Value> = per_assignee
+            .into_iter()
+            .map(|(assignee, totals)| {
+                let mut entry = totals.to_json();
+                entry["assignee"] = json!(assignee);
+                entry
+            })
+            .collect();
+
+        Ok(json!({
+            "success": true,
+            "scope": args["epic_key"].as_str().map_or(jql.clone(), |epic| format!("epic {}", epic)),
+            "truncated": truncated,
+            "totals": total.to_json(),
+            "by_assignee": by_assignee
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0m");
+        assert_eq!(format_duration(90 * 60), "1h 30m");
+        assert_eq!(format_duration(SECONDS_PER_HOUR * HOURS_PER_DAY), "1d");
+        assert_eq!(
+            format_duration(
+                SECONDS_PER_HOUR * (HOURS_PER_DAY * DAYS_PER_WEEK + HOURS_PER_DAY * 2 + 3)
+            ),
+            "1w 2d 3h"
+        );
+    }
+
+    #[test]
+    fn test_summarize_per_assignee() {
+        let issue = |assignee: Option<&str>, estimate: Option<u64>, spent: u64, remaining: u64| {
+            json!({"fields": {
+                "assignee": assignee.map(|name| json!({"displayName": name})),
+                "timeoriginalestimate": estimate,
+                "timespent": spent,
+                "timeestimate": remaining
+            }})
+        };
+        let issues = [
+            issue(Some("Jane"), Some(7200), 3600, 3600),
+            issue(Some("Jane"), None, 1800, 0),
+            issue(None, Some(3600), 0, 3600),
+        ];
+        let (total, per_assignee) = summarize(&issues, &OWN_FIELDS);
+        assert_eq!(
+            total,
+            Totals {
+                issues: 3,
+                unestimated: 1,
+                original_estimate: 10800,
+                time_spent: 5400,
+                remaining: 7200
+            }
+        );
+        assert_eq!(per_assignee["Jane"].time_spent, 5400);
+        assert_eq!(per_assignee["Unassigned"].issues, 1);
+        assert_eq!(total.to_json()["progress_percent"], json!(43.0));
+    }
+}
@@ -0,0 +1,367 @@
+//! `@Display Name` mentions in plain-text comments
+//!
+//! A mention starts at an `@` that does not follow a letter or digit (so
+//! email addresses are left alone) and is either `@[Full Name]` or up to
+//! three words, the later ones capitalized (`@Jane Doe`). Each is resolved
+//! with the user search API, preferring the longest exact display-name match
+//! and otherwise a lone search result. Several equally good users fail the
+//! call with the candidates as suggestions; no user at all leaves the text
+//! as typed and reports it as unresolved.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::utils::http_utils::create_auth_header;
+
+/// Words an unbracketed mention may span
+const MAX_NAME_WORDS: usize = 3;
+const USER_SEARCH_LIMIT: u32 = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mention {
+    pub account_id: String,
+    pub display_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    Text(String),
+    Mention(Mention),
+}
+
+/// An `@` and the spans it could cover
+#[derive(Debug, PartialEq, Eq)]
+struct Candidate {
+    start: usize,
+    /// `(end, name)` from the shortest span to the longest
+    spans: Vec<(usize, String)>,
+    /// `@[...]` form: the single span is the whole name
+    explicit: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Choice {
+    Resolved { end: usize, mention: Mention },
+    Ambiguous(Vec<String>),
+    Unmatched,
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '.' | '-' | '\'' | '_')
+}
+
+fn candidates(text: &str) -> Vec<Candidate> {
+    let mut found = Vec::new();
+    for (start, _) in text.match_indices('@') {
+        if text[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric())
+        {
+            continue;
+        }
+        let rest = &text[start + 1..];
+
+        if let Some(inner) = rest.strip_prefix('[') {
+            if let Some(close) = inner.find(']').filter(|&close| close > 0) {
+                found.push(Candidate {
+                    start,
+                    spans: vec![(start + 2 + close + 1, inner[..close].trim().to_string())],
+                    explicit: true,
+                });
+            }
+            continue;
+        }
+
+        let mut spans = Vec::new();
+        let mut offset = start + 1;
+        for word_index in 0..MAX_NAME_WORDS {
+            let tail = &text[offset..];
+            let (gap, tail) = if word_index == 0 {
+                (0, tail)
+            } else {
+                match tail.strip_prefix(' ') {
+                    Some(tail) if tail.starts_with(char::is_uppercase) => (1, tail),
+                    _ => break,
+                }
+            };
+            let len: usize = tail
+                .chars()
+                .take_while(|&c| is_name_char(c))
+                .map(char::len_utf8)
+                .sum();
+            // A trailing period ends the sentence, not the name
+            let word = tail[..len].trim_end_matches('.');
+            if word.is_empty() {
+                break;
+            }
+            offset += gap + word.len();
+            spans.push((offset, text[start + 1..offset].to_string()));
+        }
+        if !spans.is_empty() {
+            found.push(Candidate {
+                start,
+                spans,
+                explicit: false,
+            });
+        }
+    }
+    found
+}
+
+fn display_name(user: &Value) -> &str {
+    user["displayName"].as_str().unwrap_or_default()
+}
+
+fn mention_of(user: &Value) -> Mention {
+    Mention {
+        account_id: user["accountId"].as_str().unwrap_or_default().to_string(),
+        display_name: display_name(user).to_string(),
+    }
+}
+
+/// Picks the user for a candidate from the search results for its first word
+fn choose(candidate: &Candidate, users: &[Value]) -> Choice {
+    for (end, name) in candidate.spans.iter().rev() {
+        let exact: Vec<&Value> = users
+            .iter()
+            .filter(|user| display_name(user).eq_ignore_ascii_case(name))
+            .collect();
+        match exact.as_slice() {
+            [user] => {
+                return Choice::Resolved {
+                    end: *end,
+                    mention: mention_of(user),
+                };
+            }
+            [] => {}
+            several => {
+                return Choice::Ambiguous(several.iter().map(|user| suggestion(user)).collect());
+            }
+        }
+    }
+    match users {
+        [] => Choice::Unmatched,
+        [user] if !candidate.explicit => Choice::Resolved {
+            end: candidate.spans[0].0,
+            mention: mention_of(user),
+        },
+        [_] => Choice::Unmatched,
+        several => Choice::Ambiguous(several.iter().map(suggestion).collect()),
+    }
+}
+
+fn suggestion(user: &Value) -> String {
+    match user["emailAddress"].as_str() {
+        Some(email) => format!("{} <{}>", display_name(user), email),
+        None => format!(
+            "{} ({})",
+            display_name(user),
+            user["accountId"].as_str().unwrap_or_default()
+        ),
+    }
+}
+
+async fn search_users(
+    client: &reqwest::Client,
+    config: &Config,
+    query: &str,
+) -> Result<Vec<Value>> {
+    let url = format!("{}/rest/api/3/user/search", config.get_atlassian_base_url());
+    let response = client
+        .get(&url)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .query(&[
+            ("query", query.to_string()),
+            ("maxResults", USER_SEARCH_LIMIT.to_string()),
+        ])
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to search users: {}", response.status());
+    }
+    let users: Vec<Value> = response.json().await?;
+    // Apps and deactivated accounts cannot be notified
+    Ok(users
+        .into_iter()
+        .filter(|user| {
+            user["active"].as_bool().unwrap_or(true)
+                && user["accountType"].as_str().unwrap_or("atlassian") == "atlassian"
+        })
+        .collect())
+}
+
+/// Splits `text` into text and resolved mentions; the second value lists
+/// the mentions no user matched (kept as text)
+pub async fn resolve(
+    client: &reqwest::Client,
+    config: &Config,
+    text: &str,
+) -> Result<(Vec<Segment>, Vec<String>)> {
+    let mut cache: HashMap<String, Vec<Value>> = HashMap::new();
+    let mut segments = Vec::new();
+    let mut unresolved = Vec::new();
+    let mut ambiguous = Vec::new();
+    let mut cursor = 0;
+
+    for candidate in candidates(text) {
+        if candidate.start < cursor {
+            continue;
+        }
+        let query = candidate.spans[0].1.to_lowercase();
+        if !cache.contains_key(&query) {
+            let users = search_users(client, config, &query).await?;
+            cache.insert(query.clone(), users);
+        }
+        match choose(&candidate, &cache[&query]) {
+            Choice::Resolved { end, mention } => {
+                if candidate.start > cursor {
+                    segments.push(Segment::Text(text[cursor..candidate.start].to_string()));
+                }
+                segments.push(Segment::Mention(mention));
+                cursor = end;
+            }
+            Choice::Ambiguous(options) => ambiguous.push(format!(
+                "@{} matches {}",
+                candidate
+                    .spans
+                    .last()
+                    .map(|(_, name)| name.as_str())
+                    .unwrap_or_default(),
+                options.join(", ")
+            )),
+            Choice::Unmatched => {
+                let (_, name) = candidate.spans.last().cloned().unwrap_or_default();
+                unresolved.push(format!("@{}", name));
+            }
+        }
+    }
+    if !ambiguous.is_empty() {
+        anyhow::bail!(
+            "Ambiguous mention: {}. Write the full name as @[Display Name]",
+            ambiguous.join("; ")
+        );
+    }
+    if cursor < text.len() {
+        segments.push(Segment::Text(text[cursor..].to_string()));
+    }
+    Ok((segments, unresolved))
+}
+
+/// Single-paragraph ADF document with `mention` nodes, like `text_to_adf`
+pub fn segments_to_adf(segments: &[Segment]) -> Value {
+    let content: Vec<Value> = segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => json!({"type": "text", "text": text}),
+            Segment::Mention(mention) => json!({
+                "type": "mention",
+                "attrs": {
+                    "id": mention.account_id,
+                    "text": format!("@{}", mention.display_name)
+                }
+            }),
+        })
+        .collect();
+    json!({
+        "type": "doc",
+        "version": 1,
+        "content": [{"type": "paragraph", "content": content}]
+    })
+}
+
+/// Comment ADF for a plain-text comment, plus the unresolved mentions
+pub async fn comment_adf(
+    client: &reqwest::Client,
+    config: &Config,
+    text: &str,
+) -> Result<(Value, Vec<String>)> {
+    let (segments, unresolved) = resolve(client, config, text).await?;
+    Ok((segments_to_adf(&segments), unresolved))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(name: &str, id: &str) -> Value {
+        json!({"displayName": name, "accountId": id, "accountType": "atlassian", "active": true})
+    }
+
+    #[test]
+    fn test_candidates() {
+        let found = candidates("cc @Jane Doe. and @[Li Wei] but not me@example.com or @ alone");
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].start, 3);
+        assert_eq!(
+            found[0]
+                .spans
+                .iter()
+                .map(|(_, n)| n.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Jane", "Jane Doe"]
+        );
+        assert_eq!(found[1].spans[0].1, "Li Wei");
+        assert!(found[1].explicit);
+
+        // Lowercase follow-up words are not part of the name
+        let found = candidates("@jane please review");
+        assert_eq!(found[0].spans.len(), 1);
+    }
+
+    #[test]
+    fn test_choose_prefers_longest_exact_match() {
+        let text = "@Jane Doe please";
+        let candidate = &candidates(text)[0];
+        let users = vec![user("Jane Doe", "1"), user("Jane Roe", "2")];
+        match choose(candidate, &users) {
+            Choice::Resolved { end, mention } => {
+                assert_eq!(&text[end..], " please");
+                assert_eq!(mention.account_id, "1");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_choose_single_result_and_ambiguity() {
+        let candidate = &candidates("@jane")[0];
+        assert!(matches!(
+            choose(candidate, &[user("Jane Doe", "1")]),
+            Choice::Resolved { .. }
+        ));
+        assert_eq!(
+            choose(candidate, &[user("Jane Doe", "1"), user("Jane Roe", "2")]),
+            Choice::Ambiguous(vec!["Jane Doe (1)".to_string(), "Jane Roe (2)".to_string()])
+        );
+        assert_eq!(choose(candidate, &[]), Choice::Unmatched);
+
+        let explicit = &candidates("@[Jane]")[0];
+        assert_eq!(
+            choose(explicit, &[user("Jane Doe", "1")]),
+            Choice::Unmatched
+        );
+    }
+
+    #[test]
+    fn test_segments_to_adf() {
+        let adf = segments_to_adf(&[
+            Segment::Text("Thanks ".to_string()),
+            Segment::Mention(Mention {
+                account_id: "1".to_string(),
+                display_name: "Jane Doe".to_string(),
+            }),
+        ]);
+        let content = &adf["content"][0]["content"];
+        assert_eq!(content[0]["text"], "Thanks ");
+        assert_eq!(
+            content[1],
+            json!({"type": "mention", "attrs": {"id": "1", "text": "@Jane Doe"}})
+        );
+    }
+}
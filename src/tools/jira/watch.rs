@@ -0,0 +1,459 @@
+//! Polling-based JQL watches
+//!
+//! `jira_watch_query` registers a JQL that is re-run on an interval by a
+//! background tokio task. Issues that appear in the results, or whose
+//! `updated` timestamp changes, are reported to the client as
+//! `notifications/resources/updated` for `jira://issue/KEY`.
+//!
+//! This is the stdio-only alternative to the webhook receiver: no inbound
+//! network access is needed, at the cost of one search request per interval.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use crate::config::Config;
+use crate::mcp::notifications::Notifier;
+use crate::mcp::subscriptions::issue_uri;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{ToolSchema, number_prop, string_prop};
+
+use super::SearchHandler;
+
+pub const DEFAULT_INTERVAL_SECS: u64 = 60;
+pub const MIN_INTERVAL_SECS: u64 = 15;
+pub const MAX_INTERVAL_SECS: u64 = 3600;
+/// Upper bound on concurrent watches (each one costs a search per interval)
+pub const MAX_WATCHES: usize = 10;
+/// Issues fetched per poll; changes beyond this window are not detected
+const POLL_LIMIT: u64 = 50;
+
+/// `issue key -> updated timestamp` as of the last poll
+pub type Snapshot = HashMap<String, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Updated,
+}
+
+/// Builds a snapshot from a search result's `issues` array
+pub fn snapshot_from_issues(issues: &[Value]) -> Snapshot {
+    issues
+        .iter()
+        .filter_map(|issue| {
+            let key = issue["key"].as_str()?;
+            let updated = issue["fields"]["updated"].as_str().unwrap_or_default();
+            Some((key.to_string(), updated.to_string()))
+        })
+        .collect()
+}
+
+/// Issues that are new in `current` or whose `updated` value changed, sorted by key
+pub fn diff_snapshots(previous: &Snapshot, current: &Snapshot) -> Vec<(String, ChangeKind)> {
+    let mut changes: Vec<(String, ChangeKind)> = current
+        .iter()
+        .filter_map(|(key, updated)| match previous.get(key) {
+            None => Some((key.clone(), ChangeKind::Added)),
+            Some(prev) if prev != updated => Some((key.clone(), ChangeKind::Updated)),
+            Some(_) => None,
+        })
+        .collect();
+    changes.sort_by(|a, b| a.0.cmp(&b.0));
+    changes
+}
+
+async fn poll(jql: &str, config: &Config) -> Result<Snapshot> {
+    let args = json!({
+        "jql": jql,
+        "limit": POLL_LIMIT,
+        "fields": ["key", "updated"]
+    });
+    let data = SearchHandler.execute(args, config).await?;
+    let issues = data["issues"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+    Ok(snapshot_from_issues(issues))
+}
+
+struct Watch {
+    jql: String,
+    interval_secs: u64,
+    task: JoinHandle<()>,
+}
+
+/// Owns the background polling tasks for all active watches
+pub struct QueryWatcher {
+    notifier: Notifier,
+    watches: Mutex<HashMap<String, Watch>>,
+    next_id: AtomicU64,
+}
+
+impl QueryWatcher {
+    pub fn new(notifier: Notifier) -> Self {
+        Self {
+            notifier,
+            watches: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    fn watches(&self) -> std::sync::MutexGuard<'_, HashMap<String, Watch>> {
+        self.watches.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Starts polling `jql` from `baseline`. Returns the new watch id.
+    pub fn start(
+        &self,
+        jql: String,
+        interval_secs: u64,
+        baseline: Snapshot,
+        mut config: Config,
+    ) -> Result<String> {
+        // Polls outlive the tool call that started them, so its deadline
+        // must not carry over
+        config.deadline = None;
+        let mut watches = self.watches();
+        if watches.len() >= MAX_WATCHES {
+            anyhow::bail!(
+                "Too many active watches (max {}). Stop one with jira_unwatch_query first.",
+                MAX_WATCHES
+            );
+        }
+
+        let id = format!("watch-{}", self.next_id.fetch_add(1, Ordering::Relaxed));
+        let notifier = self.notifier.clone();
+        let task_jql = jql.clone();
+        let task_id = id.clone();
+
+        let task = tokio::spawn(async move {
+            let mut previous = baseline;
+            let mut ticker = tokio::time::interval(Duration::from_secs(interval_secs));
+            ticker.tick().await; // First tick completes immediately
+
+            loop {
+                ticker.tick().await;
+                let current = match poll(&task_jql, &config).await {
+                    Ok(snapshot) => snapshot,
+                    Err(e) => {
+                        tracing::warn!(watch = %task_id, error = %e, "Watch poll failed");
+                        continue;
+                    }
+                };
+
+                for (key, kind) in diff_snapshots(&previous, &current) {
+                    tracing::debug!(watch = %task_id, issue = %key, change = ?kind, "Watched issue changed");
+                    notifier.resource_updated(&issue_uri(&key));
+                }
+                previous = current;
+            }
+        });
+
+        watches.insert(
+            id.clone(),
+            Watch {
+                jql,
+                interval_secs,
+                task,
+            },
+        );
+        Ok(id)
+    }
+
+    /// Stops a watch. Returns its JQL if it existed.
+    pub fn stop(&self, id: &str) -> Option<String> {
+        self.watches().remove(id).map(|watch| {
+            watch.task.abort();
+            watch.jql
+        })
+    }
+
+    /// Active watches as `(id, jql, interval_secs)`, sorted by id
+    pub fn list(&self) -> Vec<(String, String, u64)> {
+        let mut list: Vec<_> = self
+            .watches()
+            .iter()
+            .map(|(id, w)| (id.clone(), w.jql.clone(), w.interval_secs))
+            .collect();
+        list.sort();
+        list
+    }
+}
+
+impl Drop for QueryWatcher {
+    fn drop(&mut self) {
+        for (_, watch) in self.watches().drain() {
+            watch.task.abort();
+        }
+    }
+}
+
+pub struct WatchQueryHandler {
+    watcher: Arc<QueryWatcher>,
+}
+
+impl WatchQueryHandler {
+    pub fn new(watcher: Arc<QueryWatcher>) -> Self {
+        Self { watcher }
+    }
+}
+
+pub struct UnwatchQueryHandler {
+    watcher: Arc<QueryWatcher>,
+}
+
+impl UnwatchQueryHandler {
+    pub fn new(watcher: Arc<QueryWatcher>) -> Self {
+        Self { watcher }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for WatchQueryHandler {
+    fn name(&self) -> &'static str {
+        "jira_watch_query"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "jql".to_string(),
+            string_prop(
+                "JQL to poll (e.g., 'project = KEY AND status = \"In Review\"')",
+                true,
+            ),
+        );
+        props.insert(
+            "interval_seconds".to_string(),
+            number_prop("Poll interval in seconds (15-3600, default: 60)", 60).with_range(
+                Some(MIN_INTERVAL_SECS as f64),
+                Some(MAX_INTERVAL_SECS as f64),
+            ),
+        );
+        ToolSchema::new(
+            "Watch a JQL query; new or updated issues are sent as notifications/resources/updated for jira://issue/KEY",
+            props,
+            vec!["jql".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let jql = args["jql"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing jql"))?;
+        let interval_secs = args["interval_seconds"]
+            .as_u64()
+            .unwrap_or(DEFAULT_INTERVAL_SECS);
+
+        if !(MIN_INTERVAL_SECS..=MAX_INTERVAL_SECS).contains(&interval_secs) {
+            anyhow::bail!(
+                "interval_seconds must be between {} and {}",
+                MIN_INTERVAL_SECS,
+                MAX_INTERVAL_SECS
+            );
+        }
+
+        // Initial poll validates the JQL and establishes the baseline
+        let baseline = poll(jql, config).await?;
+        let baseline_count = baseline.len();
+        let watch_id =
+            self.watcher
+                .start(jql.to_string(), interval_secs, baseline, config.clone())?;
+
+        Ok(json!({
+            "success": true,
+            "watch_id": watch_id,
+            "jql": jql,
+            "interval_seconds": interval_secs,
+            "baseline_issues": baseline_count
+        }))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for UnwatchQueryHandler {
+    fn name(&self) -> &'static str {
+        "jira_unwatch_query"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "watch_id".to_string(),
+            string_prop("Watch ID returned by jira_watch_query", true),
+        );
+        ToolSchema::new("Stop a JQL watch", props, vec!["watch_id".to_string()])
+    }
+
+    async fn execute(&self, args: Value, _config: &Config) -> Result<Value> {
+        let watch_id = args["watch_id"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing watch_id"))?;
+
+        match self.watcher.stop(watch_id) {
+            Some(jql) => Ok(json!({
+                "success": true,
+                "watch_id": watch_id,
+                "jql": jql
+            })),
+            None => {
+                let active: Vec<String> = self.watcher.list().into_iter().map(|w| w.0).collect();
+                anyhow::bail!(
+                    "Unknown watch_id '{}'. Active watches: [{}]",
+                    watch_id,
+                    active.join(", ")
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ConfigBuilder;
+
+    fn create_test_config() -> Config {
+        ConfigBuilder::new().build()
+    }
+
+    fn snapshot(entries: &[(&str, &str)]) -> Snapshot {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_snapshot_from_issues() {
+        let issues = vec![
+            json!({"key": "PROJ-1", "fields": {"updated": "2025-01-01T00:00:00.000+0000"}}),
+            json!({"key": "PROJ-2", "fields": {}}),
+            json!({"fields": {"updated": "ignored"}}),
+        ];
+        let snap = snapshot_from_issues(&issues);
+        assert_eq!(snap.len(), 2);
+        assert_eq!(snap["PROJ-1"], "2025-01-01T00:00:00.000+0000");
+        assert_eq!(snap["PROJ-2"], "");
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let previous = snapshot(&[("PROJ-1", "t1"), ("PROJ-2", "t1"), ("PROJ-3", "t1")]);
+        let current = snapshot(&[("PROJ-1", "t1"), ("PROJ-2", "t2"), ("PROJ-4", "t1")]);
+
+        let changes = diff_snapshots(&previous, &current);
+        assert_eq!(
+            changes,
+            vec![
+                ("PROJ-2".to_string(), ChangeKind::Updated),
+                ("PROJ-4".to_string(), ChangeKind::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_snapshots_no_changes() {
+        let snap = snapshot(&[("PROJ-1", "t1")]);
+        assert!(diff_snapshots(&snap, &snap).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_watcher_start_stop() {
+        let (notifier, _rx) = Notifier::channel();
+        let watcher = QueryWatcher::new(notifier);
+
+        let id = watcher
+            .start(
+                "project = PROJ".to_string(),
+                MAX_INTERVAL_SECS,
+                Snapshot::new(),
+                create_test_config(),
+            )
+            .unwrap();
+        assert_eq!(watcher.list().len(), 1);
+
+        assert_eq!(watcher.stop(&id).as_deref(), Some("project = PROJ"));
+        assert!(watcher.stop(&id).is_none());
+        assert!(watcher.list().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_watcher_enforces_limit() {
+        let (notifier, _rx) = Notifier::channel();
+        let watcher = QueryWatcher::new(notifier);
+
+        for _ in 0..MAX_WATCHES {
+            watcher
+                .start(
+                    "project = PROJ".to_string(),
+                    MAX_INTERVAL_SECS,
+                    Snapshot::new(),
+                    create_test_config(),
+                )
+                .unwrap();
+        }
+        let result = watcher.start(
+            "project = PROJ".to_string(),
+            MAX_INTERVAL_SECS,
+            Snapshot::new(),
+            create_test_config(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_watcher_polls_after_the_call_deadline() {
+        let mock = crate::mock::MockAtlassianServer::start().await.unwrap();
+        let config = ConfigBuilder::mock(&mock)
+            .build()
+            .with_deadline(Duration::from_millis(50));
+        let (notifier, mut rx) = Notifier::channel();
+        let watcher = QueryWatcher::new(notifier);
+
+        watcher
+            .start("project = DEMO".to_string(), 1, Snapshot::new(), config)
+            .unwrap();
+        let notification = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("no poll succeeded after the deadline")
+            .unwrap();
+        assert_eq!(notification.method, "notifications/resources/updated");
+    }
+
+    #[tokio::test]
+    async fn test_watch_query_validates_args() {
+        let (notifier, _rx) = Notifier::channel();
+        let handler = WatchQueryHandler::new(Arc::new(QueryWatcher::new(notifier)));
+        let config = create_test_config();
+
+        let result = handler.execute(json!({}), &config).await;
+        assert!(result.unwrap_err().to_string().contains("Missing jql"));
+
+        let result = handler
+            .execute(
+                json!({"jql": "project = PROJ", "interval_seconds": 1}),
+                &config,
+            )
+            .await;
+        assert!(result.unwrap_err().to_string().contains("interval_seconds"));
+    }
+
+    #[tokio::test]
+    async fn test_unwatch_unknown_id() {
+        let (notifier, _rx) = Notifier::channel();
+        let handler = UnwatchQueryHandler::new(Arc::new(QueryWatcher::new(notifier)));
+        let config = create_test_config();
+
+        let result = handler
+            .execute(json!({"watch_id": "watch-99"}), &config)
+            .await;
+        assert!(result.unwrap_err().to_string().contains("Unknown watch_id"));
+
+        let result = handler.execute(json!({}), &config).await;
+        assert!(result.unwrap_err().to_string().contains("Missing watch_id"));
+    }
+}
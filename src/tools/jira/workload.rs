@@ -0,0 +1,350 @@
+//! Open work per assignee, for capacity questions
+//!
+//! `jira_workload_by_assignee` pages through the open issues of a scope
+//! (`scope.rs`) with a handful of fields and totals them per assignee: how
+//! many issues, how many already in progress, overdue or unestimated, the
+//! remaining and original time estimates, and story points when the field
+//! is named. Sorted by open issues, heaviest first, unassigned work last.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+use crate::client::Jira;
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::args::parse_args;
+use crate::tools::schema::{Property, ToolSchema, number_prop, string_prop};
+use crate::utils::time;
+
+use super::scope::{self, ScopeArgs};
+use super::tabular::OutputFormat;
+use super::time_tracking::format_duration;
+use super::{SEARCH_PAGE_SIZE, apply_projects_filter};
+
+pub struct WorkloadByAssigneeHandler;
+
+pub const WORKLOAD_FIELDS: [&str; 5] = [
+    "assignee",
+    "status",
+    "duedate",
+    "timeoriginalestimate",
+    "timeestimate",
+];
+const DEFAULT_MAX_ISSUES: usize = 1000;
+const MAX_ISSUES_CAP: usize = 2000;
+
+#[derive(Deserialize)]
+struct WorkloadArgs {
+    #[serde(flatten)]
+    scope: ScopeArgs,
+    points_field: Option<String>,
+    max_issues: Option<usize>,
+}
+
+/// One assignee's open work; seconds and points are summed over issues
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Load {
+    pub issues: u64,
+    pub in_progress: u64,
+    pub overdue: u64,
+    pub unestimated: u64,
+    pub remaining: u64,
+    pub original_estimate: u64,
+    pub points: f64,
+}
+
+impl Load {
+    fn add(&mut self, issue: &Value, points_field: Option<&str>, today: &str) {
+        let fields = &issue["fields"];
+        let seconds = |field: &str| fields[field].as_u64();
+        let points = points_field.and_then(|field| fields[field].as_f64());
+        self.issues += 1;
+        if fields["status"]["statusCategory"]["key"] == "indeterminate" {
+            self.in_progress += 1;
+        }
+        if fields["duedate"].as_str().is_some_and(|due| due < today) {
+            self.overdue += 1;
+        }
+        if seconds("timeoriginalestimate").is_none()
+            && seconds("timeestimate").is_none()
+            && points.is_none()
+        {
+            self.unestimated += 1;
+        }
+        self.remaining += seconds("timeestimate").unwrap_or(0);
+        self.original_estimate += seconds("timeoriginalestimate").unwrap_or(0);
+        self.points += points.unwrap_or(0.0);
+    }
+
+    pub fn to_json(&self, with_points: bool) -> Value {
+        let mut entry = json!({
+            "open_issues": self.issues,
+            "in_progress": self.in_progress,
+            "overdue": self.overdue,
+            "unestimated": self.unestimated,
+            "remaining_seconds": self.remaining,
+            "remaining": format_duration(self.remaining),
+            "original_estimate": format_duration(self.original_estimate),
+        });
+        if with_points {
+            entry["story_points"] = json!(self.points);
+        }
+        entry
+    }
+}
+
+/// `(account ID, display name, load)` per assignee, heaviest first and
+/// unassigned (`None`) last, with the overall total
+pub fn aggregate(
+    issues: &[Value],
+    points_field: Option<&str>,
+    today: &str,
+) -> (Load, Vec<(Option<String>, String, Load)>) {
+    let mut total = Load::default();
+    let mut loads: Vec<(Option<String>, String, Load)> = Vec::new();
+    for issue in issues {
+        total.add(issue, points_field, today);
+        let assignee = &issue["fields"]["assignee"];
+        let account_id = assignee["accountId"].as_str().map(String::from);
+        let index = match loads.iter().position(|(id, ..)| *id == account_id) {
+            Some(index) => index,
+            None => {
+                let name = assignee["displayName"]
+                    .as_str()
+                    .or(account_id.as_deref())
+                    .unwrap_or("Unassigned")
+                    .to_string();
+                loads.push((account_id, name, Load::default()));
+                loads.len() - 1
+            }
+        };
+        loads[index].2.add(issue, points_field, today);
+    }
+    loads.sort_by(|a, b| {
+        a.0.is_none()
+            .cmp(&b.0.is_none())
+            .then(b.2.issues.cmp(&a.2.issues))
+            .then_with(|| a.1.cmp(&b.1))
+    });
+    (total, loads)
+}
+
+/// Issues matching `jql` with `fields`, at most `max_issues`; `true` when
+/// more matched
+pub async fn collect_issues(
+    jira: &Jira<'_>,
+    jql: &str,
+    fields: &[String],
+    max_issues: usize,
+) -> Result<(Vec<Value>, bool)> {
+    let mut issues: Vec<Value> = Vec::new();
+    let mut token: Option<String> = None;
+    let truncated = loop {
+        let page_size = (max_issues - issues.len()).min(SEARCH_PAGE_SIZE) as u64;
+        let mut page = jira
+            .search(jql, fields, page_size, token.as_deref())
+            .await?;
+        token = page.next_page_token.take();
+        let is_last = page.is_last.unwrap_or(true) || page.issues.is_empty() || token.is_none();
+        for issue in page.issues {
+            issues.push(serde_json::to_value(issue)?);
+        }
+        if issues.len() >= max_issues {
+            break !is_last;
+        }
+        if is_last {
+            break false;
+        }
+    };
+    Ok((issues, truncated))
+}
+
+/// One row per assignee, then the total
+fn render_table(
+    loads: &[(Option<String>, String, Load)],
+    total: &Load,
+    with_points: bool,
+) -> String {
+    let mut out =
+        String::from("| Assignee | Open | In progress | Overdue | Unestimated | Remaining |");
+    if with_points {
+        out.push_str(" Points |");
+    }
+    out.push_str("\n|---|---|---|---|---|---|");
+    if with_points {
+        out.push_str("---|");
+    }
+    out.push('\n');
+    let rows = loads
+        .iter()
+        .map(|(_, name, load)| (name.as_str(), load))
+        .chain(std::iter::once(("**Total**", total)));
+    for (name, load) in rows {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |",
+            name,
+            load.issues,
+            load.in_progress,
+            load.overdue,
+            load.unestimated,
+            format_duration(load.remaining)
+        ));
+        if with_points {
+            out.push_str(&format!(" {} |", load.points));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[async_trait]
+impl ToolHandler for WorkloadByAssigneeHandler {
+    fn name(&self) -> &'static str {
+        "jira_workload_by_assignee"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        scope::scope_props(&mut props);
+        props.insert(
+            "points_field".to_string(),
+            string_prop(
+                "Story points field ID to total as well (e.g., 'customfield_10016')",
+                false,
+            ),
+        );
+        props.insert(
+            "max_issues".to_string(),
+            number_prop(
+                "Open issues read at most (1-2000)",
+                DEFAULT_MAX_ISSUES as i32,
+            )
+            .with_range(Some(1.0), Some(MAX_ISSUES_CAP as f64)),
+        );
+        props.insert("format".to_string(), Property::new("string").with_description("Output format: 'json' (default) or 'table' (Markdown, one row per assignee and a total)").with_default(json!("json")).with_enum(vec![json!("json"), json!("table")]));
+        ToolSchema::new(
+            "Open issue counts and estimates per assignee for a board, project or team, for capacity questions",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let output_format = OutputFormat::from_arg(&args["format"])?;
+        let WorkloadArgs {
+            scope,
+            points_field,
+            max_issues,
+        } = parse_args(&args)?;
+        let points_field = points_field.filter(|f| !f.trim().is_empty());
+        let max_issues = max_issues
+            .unwrap_or(DEFAULT_MAX_ISSUES)
+            .clamp(1, MAX_ISSUES_CAP);
+
+        let jira = Jira::new(config);
+        let scope = scope::resolve(&jira, config, &scope).await?;
+        let jql = apply_projects_filter(
+            &format!("({}) AND statusCategory != Done", scope.jql),
+            config,
+        );
+        let mut fields: Vec<String> = WORKLOAD_FIELDS.iter().map(|f| f.to_string()).collect();
+        fields.extend(points_field.clone());
+
+        let (issues, truncated) = collect_issues(&jira, &jql, &fields, max_issues).await?;
+
+        let zone = time::user_timezone(config)?;
+        let today = time::jira_date("today", &zone)?;
+        let (total, loads) = aggregate(&issues, points_field.as_deref(), &today);
+        let with_points = points_field.is_some();
+
+        if output_format == OutputFormat::Table {
+            let mut table = render_table(&loads, &total, with_points);
+            if truncated {
+                table.push_str(&format!("\nFirst {} open issues only\n", issues.len()));
+            }
+            return Ok(Value::String(table));
+        }
+
+        let by_assignee: Vec<Value> = loads
+            .iter()
+            .map(|(account_id, name, load)| {
+                let mut entry = load.to_json(with_points);
+                entry["assignee"] = json!(name);
+                entry["account_id"] = json!(account_id);
+                if total.issues > 0 {
+                    entry["share_percent"] =
+                        json!((load.issues as f64 * 100.0 / total.issues as f64).round());
+                }
+                entry
+            })
+            .collect();
+        Ok(json!({
+            "success": true,
+            "scope": scope.names,
+            "jql": jql,
+            "truncated": truncated,
+            "totals": total.to_json(with_points),
+            "by_assignee": by_assignee
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(
+        assignee: Option<(&str, &str)>,
+        category: &str,
+        due: Option<&str>,
+        fields: Value,
+    ) -> Value {
+        let mut issue = json!({"fields": {
+            "assignee": assignee.map(|(id, name)| json!({"accountId": id, "displayName": name})),
+            "status": {"statusCategory": {"key": category}},
+            "duedate": due
+        }});
+        for (key, value) in fields.as_object().unwrap() {
+            issue["fields"][key] = value.clone();
+        }
+        issue
+    }
+
+    #[test]
+    fn test_aggregate_per_assignee() {
+        let jane = Some(("a1", "Jane"));
+        let sam = Some(("a2", "Sam"));
+        let issues = [
+            issue(None, "new", None, json!({"timeestimate": 3600})),
+            issue(sam, "new", None, json!({"customfield_10016": 3.0})),
+            issue(
+                jane,
+                "indeterminate",
+                Some("2026-10-01"),
+                json!({"timeestimate": 7200, "timeoriginalestimate": 7200}),
+            ),
+            issue(jane, "new", Some("2026-12-01"), json!({})),
+        ];
+        let (total, loads) = aggregate(&issues, Some("customfield_10016"), "2026-10-14");
+        assert_eq!(
+            total,
+            Load {
+                issues: 4,
+                in_progress: 1,
+                overdue: 1,
+                unestimated: 1,
+                remaining: 10800,
+                original_estimate: 7200,
+                points: 3.0
+            }
+        );
+        let names: Vec<&str> = loads.iter().map(|(_, name, _)| name.as_str()).collect();
+        assert_eq!(names, ["Jane", "Sam", "Unassigned"]);
+        assert_eq!(loads[0].2.issues, 2);
+        assert_eq!(loads[0].2.unestimated, 1);
+        assert_eq!(loads[1].2.points, 3.0);
+    }
+}
@@ -0,0 +1,318 @@
+//! Jira Service Management tools (opt-in with `JSM_ENABLED=true`)
+//!
+//! JSM has its own REST API under `/rest/servicedeskapi` on the same site
+//! and credentials as Jira. `jsm_sla_breaches` pages through a service
+//! desk's open requests with their SLAs expanded and keeps the ones whose
+//! ongoing SLA cycle is breached or ends within `within_hours`, most urgent
+//! first, so they can be escalated before (or as soon as) they slip.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+use crate::client::rest;
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::args::parse_args;
+use crate::tools::schema::{Property, ToolSchema, number_prop, string_prop};
+use crate::utils::http_utils::create_atlassian_client;
+
+pub struct SlaBreachesHandler;
+
+/// Requests per page (the API's maximum is 100, but SLA expansion is slow)
+const REQUEST_PAGE_SIZE: usize = 50;
+const DEFAULT_WITHIN_HOURS: u64 = 4;
+const MAX_WITHIN_HOURS: u64 = 168;
+const DEFAULT_MAX_REQUESTS: usize = 200;
+const MAX_REQUESTS_CAP: usize = 1000;
+
+#[derive(Deserialize)]
+struct SlaBreachesArgs {
+    service_desk: Option<Value>,
+    within_hours: Option<u64>,
+    #[serde(default)]
+    include_paused: bool,
+    max_requests: Option<usize>,
+}
+
+/// How urgent an ongoing SLA cycle is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Urgency {
+    Breached,
+    DueSoon,
+}
+
+/// `path` below the service desk REST API
+fn api_url(config: &Config, path: &str) -> String {
+    format!(
+        "{}/rest/servicedeskapi/{}",
+        config.get_atlassian_base_url(),
+        path
+    )
+}
+
+/// The ID of a service desk given as an ID or its project key
+async fn service_desk_id(client: &reqwest::Client, config: &Config, desk: &str) -> Result<String> {
+    if desk.bytes().all(|b| b.is_ascii_digit()) {
+        return Ok(desk.to_string());
+    }
+    let mut start = 0;
+    loop {
+        let query = [("start", start.to_string()), ("limit", "100".to_string())];
+        let url = api_url(config, "servicedesk");
+        let page = rest::get_json(client, config, &url, &query, "service desks").await?;
+        let desks = page["values"].as_array().cloned().unwrap_or_default();
+        if let Some(found) = desks.iter().find(|d| {
+            d["projectKey"]
+                .as_str()
+                .is_some_and(|key| key.eq_ignore_ascii_case(desk))
+        }) {
+            return Ok(found["id"].as_str().unwrap_or_default().to_string());
+        }
+        if desks.is_empty() || page["isLastPage"].as_bool().unwrap_or(true) {
+            anyhow::bail!("No service desk for project '{}'", desk);
+        }
+        start += desks.len();
+    }
+}
+
+/// The urgency of one SLA and its remaining time in milliseconds (negative
+/// once breached); `None` for completed, comfortable or (unless
+/// `include_paused`) paused SLAs
+pub fn classify(sla: &Value, within_millis: i64, include_paused: bool) -> Option<(Urgency, i64)> {
+    let cycle = sla.get("ongoingCycle").filter(|c| c.is_object())?;
+    if cycle["paused"].as_bool() == Some(true) && !include_paused {
+        return None;
+    }
+    let remaining = cycle["remainingTime"]["millis"].as_i64().unwrap_or(0);
+    if cycle["breached"].as_bool() == Some(true) {
+        Some((Urgency::Breached, remaining.min(0)))
+    } else if remaining <= within_millis {
+        Some((Urgency::DueSoon, remaining))
+    } else {
+        None
+    }
+}
+
+/// One entry per urgent SLA of `request`
+fn urgent_entries(
+    request: &Value,
+    within_millis: i64,
+    include_paused: bool,
+) -> Vec<(Urgency, i64, Value)> {
+    let summary = request["requestFieldValues"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|field| field["fieldId"] == "summary")
+        .map(|field| field["value"].clone())
+        .unwrap_or(Value::Null);
+    request["sla"]["values"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|sla| {
+            let (urgency, remaining) = classify(sla, within_millis, include_paused)?;
+            let cycle = &sla["ongoingCycle"];
+            let entry = json!({
+                "issue_key": request["issueKey"],
+                "summary": summary,
+                "status": request["currentStatus"]["status"],
+                "reporter": request["reporter"]["displayName"],
+                "sla": sla["name"],
+                "remaining": cycle["remainingTime"]["friendly"],
+                "remaining_millis": remaining,
+                "breach_time": cycle["breachTime"]["iso8601"],
+                "paused": cycle["paused"].as_bool().unwrap_or(false)
+            });
+            Some((urgency, remaining, entry))
+        })
+        .collect()
+}
+
+#[async_trait]
+impl ToolHandler for SlaBreachesHandler {
+    fn name(&self) -> &'static str {
+        "jsm_sla_breaches"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "service_desk".to_string(),
+            string_prop(
+                "Service desk ID or project key (default: every desk you can see)",
+                false,
+            ),
+        );
+        props.insert(
+            "within_hours".to_string(),
+            number_prop(
+                "Also list SLAs ending within this many hours (0-168)",
+                DEFAULT_WITHIN_HOURS as i32,
+            )
+            .with_range(Some(0.0), Some(MAX_WITHIN_HOURS as f64)),
+        );
+        props.insert(
+            "include_paused".to_string(),
+            Property::new("boolean")
+                .with_description("Include SLAs whose clock is paused (default: false)")
+                .with_default(json!(false)),
+        );
+        props.insert(
+            "max_requests".to_string(),
+            number_prop(
+                "Open requests scanned at most (1-1000)",
+                DEFAULT_MAX_REQUESTS as i32,
+            )
+            .with_range(Some(1.0), Some(MAX_REQUESTS_CAP as f64)),
+        );
+        ToolSchema::new(
+            "Open service requests whose SLA is breached or due within N hours, most urgent first, for escalation",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let SlaBreachesArgs {
+            service_desk,
+            within_hours,
+            include_paused,
+            max_requests,
+        } = parse_args(&args)?;
+        let within_hours = within_hours
+            .unwrap_or(DEFAULT_WITHIN_HOURS)
+            .min(MAX_WITHIN_HOURS);
+        let within_millis = (within_hours * 3_600_000) as i64;
+        let max_requests = max_requests
+            .unwrap_or(DEFAULT_MAX_REQUESTS)
+            .clamp(1, MAX_REQUESTS_CAP);
+
+        let client = create_atlassian_client(config);
+        let desk = match service_desk {
+            Some(Value::String(desk)) if !desk.trim().is_empty() => {
+                Some(service_desk_id(&client, config, desk.trim()).await?)
+            }
+            Some(Value::Number(id)) => Some(id.to_string()),
+            _ => None,
+        };
+
+        let mut query = vec![
+            ("requestOwnership", "ALL_REQUESTS".to_string()),
+            ("requestStatus", "OPEN_REQUESTS".to_string()),
+            ("expand", "sla".to_string()),
+        ];
+        if let Some(desk) = &desk {
+            query.push(("serviceDeskId", desk.clone()));
+        }
+
+        let mut urgent = Vec::new();
+        let mut scanned = 0;
+        let truncated = loop {
+            let limit = (max_requests - scanned).min(REQUEST_PAGE_SIZE);
+            let mut page_query = query.clone();
+            page_query.push(("start", scanned.to_string()));
+            page_query.push(("limit", limit.to_string()));
+            let url = api_url(config, "request");
+            let page = rest::get_json(&client, config, &url, &page_query, "requests").await?;
+            let requests = page["values"].as_array().cloned().unwrap_or_default();
+            scanned += requests.len();
+            for request in &requests {
+                urgent.extend(urgent_entries(request, within_millis, include_paused));
+            }
+            let is_last = page["isLastPage"].as_bool().unwrap_or(true) || requests.is_empty();
+            if is_last {
+                break false;
+            }
+            if scanned >= max_requests {
+                break true;
+            }
+        };
+
+        urgent.sort_by_key(|(_, remaining, _)| *remaining);
+        let (breached, due_soon): (Vec<_>, Vec<_>) = urgent
+            .into_iter()
+            .partition(|(urgency, ..)| *urgency == Urgency::Breached);
+        let entries = |list: Vec<(Urgency, i64, Value)>| {
+            list.into_iter()
+                .map(|(_, _, entry)| entry)
+                .collect::<Vec<_>>()
+        };
+        Ok(json!({
+            "success": true,
+            "service_desk_id": desk,
+            "within_hours": within_hours,
+            "scanned_requests": scanned,
+            "truncated": truncated,
+            "breached": entries(breached),
+            "due_soon": entries(due_soon)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sla(name: &str, cycle: Value) -> Value {
+        json!({"name": name, "ongoingCycle": cycle, "completedCycles": []})
+    }
+
+    #[test]
+    fn test_classify_ongoing_cycles() {
+        let hour = 3_600_000;
+        let breached = sla(
+            "Time to resolution",
+            json!({"breached": true, "paused": false, "remainingTime": {"millis": -600_000}}),
+        );
+        let soon = sla(
+            "Time to first response",
+            json!({"breached": false, "paused": false, "remainingTime": {"millis": hour}}),
+        );
+        let paused = sla(
+            "Time to first response",
+            json!({"breached": false, "paused": true, "remainingTime": {"millis": hour}}),
+        );
+        let completed =
+            json!({"name": "Time to first response", "completedCycles": [{"breached": true}]});
+
+        assert_eq!(
+            classify(&breached, 4 * hour, false),
+            Some((Urgency::Breached, -600_000))
+        );
+        assert_eq!(
+            classify(&soon, 4 * hour, false),
+            Some((Urgency::DueSoon, hour))
+        );
+        assert_eq!(classify(&soon, hour / 2, false), None);
+        assert_eq!(classify(&paused, 4 * hour, false), None);
+        assert_eq!(
+            classify(&paused, 4 * hour, true),
+            Some((Urgency::DueSoon, hour))
+        );
+        assert_eq!(classify(&completed, 4 * hour, false), None);
+    }
+
+    #[test]
+    fn test_urgent_entries_name_the_request() {
+        let request = json!({
+            "issueKey": "HELP-7",
+            "requestFieldValues": [{"fieldId": "summary", "value": "VPN is down"}],
+            "currentStatus": {"status": "Waiting for support"},
+            "reporter": {"displayName": "Sam"},
+            "sla": {"values": [sla(
+                "Time to resolution",
+                json!({"breached": true, "remainingTime": {"millis": -1, "friendly": "-1m"}, "breachTime": {"iso8601": "2026-10-14T09:00:00+0000"}})
+            )]}
+        });
+        let entries = urgent_entries(&request, 0, false);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0, Urgency::Breached);
+        assert_eq!(entries[0].2["summary"], "VPN is down");
+        assert_eq!(entries[0].2["remaining"], "-1m");
+        assert_eq!(entries[0].2["paused"], false);
+    }
+}
@@ -1,6 +1,22 @@
+pub mod applied_filters;
+pub mod args;
+pub mod bitbucket;
+pub mod compass;
 pub mod confluence;
+pub mod excerpts;
 pub mod handler;
+pub mod health;
+pub mod incident;
 pub mod jira;
+pub mod jsm;
+pub mod pagination;
 pub mod response_optimizer;
+pub mod schema;
+pub mod session_stats;
+pub mod teams;
+#[cfg(feature = "trello")]
+pub mod trello;
+pub mod undo;
+pub mod write_policy;
 
 pub use handler::ToolHandler;
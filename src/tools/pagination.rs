@@ -0,0 +1,149 @@
+//! Auto-pagination shared by the search tools
+//!
+//! With `auto_paginate: true` a search keeps fetching pages until the result
+//! set is exhausted or `MAX_AUTO_PAGINATE_ITEMS` is reached, deduplicates the
+//! merged items and reports what it did under `pagination`. Cursor-based APIs
+//! (Jira `nextPageToken`) are fetched sequentially; offset-based ones
+//! (Confluence `start`) fetch up to `AUTO_PAGINATE_CONCURRENCY` pages at once.
+
+use anyhow::Result;
+use serde_json::{Value, json};
+use std::collections::HashSet;
+use std::future::Future;
+
+/// Hard cap on items collected by one auto-paginated search
+pub const MAX_AUTO_PAGINATE_ITEMS: usize = 500;
+
+/// Pages fetched in parallel for offset-based APIs
+pub const AUTO_PAGINATE_CONCURRENCY: usize = 4;
+
+/// Items wanted: the `limit` argument capped at `MAX_AUTO_PAGINATE_ITEMS`
+pub fn auto_paginate_limit(args: &Value) -> usize {
+    args["limit"]
+        .as_u64()
+        .map(|l| (l as usize).clamp(1, MAX_AUTO_PAGINATE_ITEMS))
+        .unwrap_or(MAX_AUTO_PAGINATE_ITEMS)
+}
+
+#[derive(Debug, Default)]
+pub struct Collected {
+    pub items: Vec<Value>,
+    pub pages: usize,
+    pub duplicates: usize,
+    /// More results existed beyond the limit
+    pub truncated: bool,
+}
+
+impl Collected {
+    /// Appends `page`, skipping items whose key was already seen; `false` once
+    /// `limit` items are collected
+    pub fn extend(
+        &mut self,
+        page: Vec<Value>,
+        limit: usize,
+        seen: &mut HashSet<String>,
+        key: impl Fn(&Value) -> Option<String>,
+    ) -> bool {
+        self.pages += 1;
+        for item in page {
+            if self.items.len() >= limit {
+                self.truncated = true;
+                return false;
+            }
+            if key(&item).is_some_and(|k| !seen.insert(k)) {
+                self.duplicates += 1;
+            } else {
+                self.items.push(item);
+            }
+        }
+        self.items.len() < limit
+    }
+
+    pub fn metadata(&self, limit: usize) -> Value {
+        json!({
+            "pages_fetched": self.pages,
+            "items": self.items.len(),
+            "duplicates_removed": self.duplicates,
+            "truncated": self.truncated,
+            "limit": limit
+        })
+    }
+}
+
+/// Runs `fetch(offset)` for each offset, `AUTO_PAGINATE_CONCURRENCY` at a
+/// time; results keep the order of `offsets`
+pub async fn fetch_offsets<F, Fut>(offsets: &[usize], fetch: F) -> Result<Vec<Value>>
+where
+    F: Fn(usize) -> Fut,
+    Fut: Future<Output = Result<Value>> + Send + 'static,
+{
+    let mut pages = Vec::with_capacity(offsets.len());
+    for chunk in offsets.chunks(AUTO_PAGINATE_CONCURRENCY) {
+        let tasks: Vec<_> = chunk
+            .iter()
+            .map(|&offset| tokio::spawn(fetch(offset)))
+            .collect();
+        for task in tasks {
+            pages.push(task.await??);
+        }
+    }
+    Ok(pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(item: &Value) -> Option<String> {
+        item["id"].as_str().map(String::from)
+    }
+
+    #[test]
+    fn test_collected_dedupes_and_caps() {
+        let mut collected = Collected::default();
+        let mut seen = HashSet::new();
+
+        let more = collected.extend(
+            vec![json!({"id": "1"}), json!({"id": "2"})],
+            3,
+            &mut seen,
+            id,
+        );
+        assert!(more);
+
+        let more = collected.extend(
+            vec![json!({"id": "2"}), json!({"id": "3"}), json!({"id": "4"})],
+            3,
+            &mut seen,
+            id,
+        );
+        assert!(!more);
+        assert_eq!(collected.items.len(), 3);
+        assert_eq!(collected.duplicates, 1);
+        assert!(collected.truncated);
+        assert_eq!(collected.metadata(3)["pages_fetched"], 2);
+    }
+
+    #[test]
+    fn test_auto_paginate_limit() {
+        assert_eq!(auto_paginate_limit(&json!({})), MAX_AUTO_PAGINATE_ITEMS);
+        assert_eq!(auto_paginate_limit(&json!({"limit": 120})), 120);
+        assert_eq!(
+            auto_paginate_limit(&json!({"limit": 5000})),
+            MAX_AUTO_PAGINATE_ITEMS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_offsets_keeps_order() {
+        let offsets: Vec<usize> = (0..10).map(|i| i * 25).collect();
+        let pages = fetch_offsets(&offsets, |offset| async move {
+            // Later pages finish first
+            tokio::time::sleep(std::time::Duration::from_millis((250 - offset as u64) / 25)).await;
+            Ok(json!(offset))
+        })
+        .await
+        .unwrap();
+        assert_eq!(pages, offsets.iter().map(|o| json!(o)).collect::<Vec<_>>());
+    }
+}
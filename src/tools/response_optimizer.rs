@@ -4,7 +4,8 @@
 //! from API responses to optimize token usage for LLM interactions.
 
 use anyhow::Result;
-use serde_json::Value;
+use serde::Serialize;
+use serde_json::{Value, json};
 #[cfg(test)]
 use std::sync::{Arc, Mutex};
 
@@ -62,24 +63,66 @@ pub const DEFAULT_EXCLUDE_FIELDS: &[&str] = &[
     "edituiv2",             // Confluence edit v2 URL (read-only unnecessary)
 ];
 
-/// Statistics for a single optimization operation (test-only)
-#[cfg(test)]
-#[derive(Debug, Clone, Copy, Default)]
+/// Statistics for a single optimization operation
+///
+/// Field counts are always tracked. Byte sizes are only measured when the
+/// optimizer was created with byte tracking enabled (`RESPONSE_METRICS=true`
+/// or debug logging), since measuring requires serializing the response twice.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
 pub struct OptimizationStats {
     /// Number of excluded fields removed
     pub fields_removed: usize,
     /// Number of empty string fields removed
     pub empty_strings_removed: usize,
+    /// Serialized size before optimization (0 when not measured)
+    pub bytes_before: usize,
+    /// Serialized size after optimization (0 when not measured)
+    pub bytes_after: usize,
     /// Processing time in milliseconds
     pub processing_time_ms: f64,
 }
 
+impl OptimizationStats {
+    /// Bytes removed by optimization
+    pub fn bytes_saved(&self) -> usize {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+
+    /// Size reduction as a percentage of the original payload
+    pub fn reduction_percent(&self) -> f64 {
+        if self.bytes_before == 0 {
+            return 0.0;
+        }
+        self.bytes_saved() as f64 * 100.0 / self.bytes_before as f64
+    }
+
+    /// Rough token estimate (~4 bytes per token for JSON payloads)
+    pub fn estimated_tokens_saved(&self) -> usize {
+        self.bytes_saved() / 4
+    }
+
+    /// Render as the `_meta.optimization` block attached to tool results
+    pub fn to_meta(self) -> Value {
+        json!({
+            "fields_removed": self.fields_removed,
+            "empty_strings_removed": self.empty_strings_removed,
+            "bytes_before": self.bytes_before,
+            "bytes_after": self.bytes_after,
+            "bytes_saved": self.bytes_saved(),
+            "reduction_percent": (self.reduction_percent() * 10.0).round() / 10.0,
+            "estimated_tokens_saved": self.estimated_tokens_saved(),
+            "processing_time_ms": self.processing_time_ms,
+        })
+    }
+}
+
 /// Response optimizer for removing unnecessary fields and empty strings
 ///
 /// Thread-safe and designed to be shared via `Arc` across async handlers.
 pub struct ResponseOptimizer {
     exclude_fields: Vec<String>,
     remove_empty_strings: bool,
+    track_bytes: bool,
     #[cfg(test)]
     stats: Arc<Mutex<OptimizationStats>>,
 }
@@ -88,6 +131,7 @@ impl ResponseOptimizer {
     /// Create optimizer from application configuration
     ///
     /// Uses `RESPONSE_EXCLUDE_FIELDS` env var if set, otherwise uses `DEFAULT_EXCLUDE_FIELDS`.
+    /// Byte tracking is enabled when `RESPONSE_METRICS` is set or debug logging is active.
     pub fn from_config(config: &crate::config::Config) -> Self {
         let exclude_fields = if let Some(ref fields) = config.response_exclude_fields {
             tracing::info!(
@@ -109,6 +153,7 @@ impl ResponseOptimizer {
         Self {
             exclude_fields,
             remove_empty_strings: true,
+            track_bytes: config.response_metrics || tracing::enabled!(tracing::Level::DEBUG),
             #[cfg(test)]
             stats: Arc::new(Mutex::new(OptimizationStats::default())),
         }
@@ -120,6 +165,7 @@ impl ResponseOptimizer {
         Self {
             exclude_fields,
             remove_empty_strings: true,
+            track_bytes: true,
             stats: Arc::new(Mutex::new(OptimizationStats::default())),
         }
     }
@@ -133,72 +179,34 @@ impl ResponseOptimizer {
     /// * `value` - Mutable reference to JSON value (optimized in-place)
     ///
     /// # Returns
-    /// * `Ok(())` - Optimization succeeded
-    /// * `Err` - Currently never fails, but returns Result for future extensibility
-    pub fn optimize(&self, value: &mut Value) -> Result<()> {
-        #[cfg(test)]
+    /// * `Ok(stats)` - Optimization succeeded, with removal counts and (optionally) byte sizes
+    /// * `Err` - Serialization failed while measuring payload size
+    pub fn optimize(&self, value: &mut Value) -> Result<OptimizationStats> {
         let start = std::time::Instant::now();
-        #[cfg(test)]
         let mut stats = OptimizationStats::default();
 
-        // Apply recursive optimization
-        #[cfg(test)]
-        self.optimize_recursive(value, &mut stats);
-        #[cfg(not(test))]
-        self.optimize_recursive(value);
-
-        #[cfg(test)]
-        {
-            // Record processing time
-            stats.processing_time_ms = start.elapsed().as_secs_f64() * 1000.0;
-
-            // Update shared stats
-            if let Ok(mut shared_stats) = self.stats.lock() {
-                *shared_stats = stats;
-            }
+        if self.track_bytes {
+            stats.bytes_before = serde_json::to_vec(value)?.len();
         }
 
-        Ok(())
-    }
-
-    /// Recursively optimize a JSON value (production version - no stats)
-    ///
-    /// Removes excluded fields and empty strings at all nesting levels.
-    #[cfg(not(test))]
-    fn optimize_recursive(&self, value: &mut Value) {
-        match value {
-            Value::Object(map) => {
-                // Step 1: Remove excluded fields
-                for field in &self.exclude_fields {
-                    map.remove(field);
-                }
+        self.optimize_recursive(value, &mut stats);
 
-                // Step 2: Remove empty strings (preserve nulls)
-                if self.remove_empty_strings {
-                    map.retain(|_, v| !matches!(v, Value::String(s) if s.is_empty()));
-                }
+        if self.track_bytes {
+            stats.bytes_after = serde_json::to_vec(value)?.len();
+        }
+        stats.processing_time_ms = start.elapsed().as_secs_f64() * 1000.0;
 
-                // Step 3: Recursively process nested values
-                for nested_value in map.values_mut() {
-                    self.optimize_recursive(nested_value);
-                }
-            }
-            Value::Array(arr) => {
-                // Recursively process array elements
-                for item in arr.iter_mut() {
-                    self.optimize_recursive(item);
-                }
-            }
-            _ => {
-                // Primitive types: no optimization needed
-            }
+        #[cfg(test)]
+        if let Ok(mut shared_stats) = self.stats.lock() {
+            *shared_stats = stats;
         }
+
+        Ok(stats)
     }
 
-    /// Recursively optimize a JSON value (test version - with stats)
+    /// Recursively optimize a JSON value
     ///
     /// Removes excluded fields and empty strings at all nesting levels.
-    #[cfg(test)]
     fn optimize_recursive(&self, value: &mut Value, stats: &mut OptimizationStats) {
         match value {
             Value::Object(map) => {
@@ -401,4 +409,45 @@ mod tests {
         let stats = optimizer.get_last_optimization_stats();
         assert_eq!(stats.fields_removed, 5);
     }
+
+    #[test]
+    fn test_optimize_returns_byte_savings() {
+        let optimizer = ResponseOptimizer::new_with_rules(vec!["self".to_string()]);
+        let mut input = json!({
+            "key": "PROJ-1",
+            "self": "https://test.atlassian.net/rest/api/3/issue/10001"
+        });
+
+        let stats = optimizer.optimize(&mut input).unwrap();
+        assert_eq!(stats.fields_removed, 1);
+        assert!(stats.bytes_before > stats.bytes_after);
+        assert_eq!(stats.bytes_after, serde_json::to_vec(&input).unwrap().len());
+        assert_eq!(stats.bytes_saved(), stats.bytes_before - stats.bytes_after);
+        assert!(stats.reduction_percent() > 0.0);
+    }
+
+    #[test]
+    fn test_stats_meta_block() {
+        let stats = OptimizationStats {
+            fields_removed: 3,
+            empty_strings_removed: 1,
+            bytes_before: 1000,
+            bytes_after: 600,
+            processing_time_ms: 0.5,
+        };
+
+        let meta = stats.to_meta();
+        assert_eq!(meta["fields_removed"], 3);
+        assert_eq!(meta["bytes_saved"], 400);
+        assert_eq!(meta["reduction_percent"], 40.0);
+        assert_eq!(meta["estimated_tokens_saved"], 100);
+    }
+
+    #[test]
+    fn test_stats_without_byte_tracking() {
+        let stats = OptimizationStats::default();
+        assert_eq!(stats.bytes_saved(), 0);
+        assert_eq!(stats.reduction_percent(), 0.0);
+        assert_eq!(stats.estimated_tokens_saved(), 0);
+    }
 }
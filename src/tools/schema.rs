@@ -0,0 +1,562 @@
+//! Tool descriptions and input schemas
+//!
+//! Each handler declares its own [`ToolSchema`] through
+//! `ToolHandler::schema`, next to the code that reads the arguments, and
+//! `tools/list` is built from the registered handlers. The helpers below
+//! build the common property shapes.
+//!
+//! The same schema is checked against every call ([`ToolSchema::validate`])
+//! before the handler runs, so a wrong type or enum value is answered with
+//! `invalid_params` instead of reaching the Atlassian API as a 400.
+//!
+//! Descriptions are written at the `standard` [`Verbosity`];
+//! [`ToolSchema::with_verbosity`] cuts them to their first sentence or
+//! spells out the constraints in words when `TOOL_SCHEMA_VERBOSITY` asks.
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+pub use crate::mcp::types::Property;
+
+use crate::tools::args::InvalidArgs;
+
+/// What `tools/list` shows for one tool (before server-wide properties such
+/// as `timeout_ms` are added)
+#[derive(Debug, Clone)]
+pub struct ToolSchema {
+    pub description: String,
+    pub properties: HashMap<String, Property>,
+    pub required: Vec<String>,
+}
+
+/// How much guidance `tools/list` embeds (`TOOL_SCHEMA_VERBOSITY`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verbosity {
+    /// First sentence of every description: fewest tokens
+    Minimal,
+    /// Descriptions as the handlers write them
+    #[default]
+    Standard,
+    /// Also defaults, allowed values, ranges and required arguments in
+    /// words, for clients that drop those schema keywords
+    Detailed,
+}
+
+impl Verbosity {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "standard" => Ok(Self::Standard),
+            "minimal" => Ok(Self::Minimal),
+            "detailed" => Ok(Self::Detailed),
+            other => anyhow::bail!(
+                "Invalid TOOL_SCHEMA_VERBOSITY '{}': expected minimal, standard or detailed",
+                other
+            ),
+        }
+    }
+}
+
+impl ToolSchema {
+    pub fn new(
+        description: impl Into<String>,
+        properties: HashMap<String, Property>,
+        required: Vec<String>,
+    ) -> Self {
+        Self {
+            description: description.into(),
+            properties,
+            required,
+        }
+    }
+
+    /// Checks `args` for required fields, JSON types and enum values.
+    /// `null` counts as absent; fields the schema does not declare are
+    /// left to the handler.
+    pub fn validate(&self, args: &Value) -> Result<(), InvalidArgs> {
+        let empty = serde_json::Map::new();
+        let object = match args {
+            Value::Object(object) => object,
+            Value::Null => &empty,
+            _ => {
+                return Err(InvalidArgs {
+                    missing: Vec::new(),
+                    invalid: vec![("arguments".to_string(), "expected an object".to_string())],
+                });
+            }
+        };
+
+        let mut errors = InvalidArgs::default();
+        for field in &self.required {
+            if object.get(field).is_none_or(Value::is_null) {
+                errors.missing.push(field.clone());
+            }
+        }
+
+        let mut fields: Vec<&String> = object.keys().collect();
+        fields.sort();
+        for field in fields {
+            let (Some(property), value) = (self.properties.get(field), &object[field]) else {
+                continue;
+            };
+            if value.is_null() {
+                continue;
+            }
+            if let Some(reason) = property_error(property, value) {
+                errors.invalid.push((field.clone(), reason));
+            }
+        }
+
+        if errors == InvalidArgs::default() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// The schema with its descriptions, nested ones included, at `verbosity`
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        match verbosity {
+            Verbosity::Standard => {}
+            Verbosity::Minimal => {
+                self.description = first_sentence(&self.description).to_string();
+                for property in self.properties.values_mut() {
+                    shorten(property);
+                }
+            }
+            Verbosity::Detailed => {
+                if !self.required.is_empty() {
+                    let mut required = self.required.clone();
+                    required.sort();
+                    self.description =
+                        format!("{} (required: {})", self.description, required.join(", "));
+                }
+                for property in self.properties.values_mut() {
+                    explain(property);
+                }
+            }
+        }
+        self
+    }
+}
+
+/// `text` up to the end of its first sentence or clause: a `.` or `;` and a
+/// space, or a line break, outside brackets. `e.g.` and `i.e.` do not count.
+fn first_sentence(text: &str) -> &str {
+    let mut depth = 0;
+    for (index, c) in text.char_indices() {
+        let next = text[index + c.len_utf8()..].chars().next();
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            '\n' if depth <= 0 => return text[..index].trim_end(),
+            '.' if depth <= 0
+                && next.is_some_and(char::is_whitespace)
+                && !text[..=index].ends_with("e.g.")
+                && !text[..=index].ends_with("i.e.") =>
+            {
+                return &text[..=index];
+            }
+            ';' if depth <= 0 && next.is_some_and(char::is_whitespace) => {
+                return &text[..index];
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+fn nested_properties(property: &mut Property) -> impl Iterator<Item = &mut Property> {
+    property
+        .items
+        .as_deref_mut()
+        .into_iter()
+        .chain(property.properties.iter_mut().flat_map(|p| p.values_mut()))
+        .chain(property.additional_properties.as_deref_mut())
+}
+
+fn shorten(property: &mut Property) {
+    if let Some(description) = &property.description {
+        property.description = Some(first_sentence(description).to_string());
+    }
+    nested_properties(property).for_each(shorten);
+}
+
+/// `1` rather than `1.0` for whole bounds
+fn bound(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        value.to_string()
+    }
+}
+
+fn explain(property: &mut Property) {
+    let mut notes = Vec::new();
+    if let Some(allowed) = &property.enum_values {
+        let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+        notes.push(format!("one of {}", allowed.join(", ")));
+    }
+    if let Some(default) = &property.default {
+        notes.push(format!("default {}", default));
+    }
+    match (property.minimum, property.maximum) {
+        (Some(min), Some(max)) => notes.push(format!("{} to {}", bound(min), bound(max))),
+        (Some(min), None) => notes.push(format!("at least {}", bound(min))),
+        (None, Some(max)) => notes.push(format!("at most {}", bound(max))),
+        (None, None) => {}
+    }
+    if let Some(max_length) = property.max_length {
+        notes.push(format!("at most {} characters", max_length));
+    }
+    if let Some(max_items) = property.max_items {
+        notes.push(format!("at most {} items", max_items));
+    }
+    if let Some(required) = property.required.as_ref().filter(|r| !r.is_empty()) {
+        notes.push(format!("requires {}", required.join(", ")));
+    }
+    if !notes.is_empty() {
+        let notes = format!("({})", notes.join("; "));
+        property.description = Some(match &property.description {
+            Some(description) => format!("{} {}", description, notes),
+            None => notes,
+        });
+    }
+    nested_properties(property).for_each(explain);
+}
+
+/// JSON Schema type names of a value; integers are also numbers
+fn matches_type(value: &Value, schema_type: &str) -> bool {
+    match schema_type {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+/// Why `value` does not fit `property`, if it does not. Array items and
+/// object values are checked against their own schema, reported with the
+/// path below the top-level field (`fields.labels[1]: expected string`).
+fn property_error(property: &Property, value: &Value) -> Option<String> {
+    let types = property.property_type.names();
+    if !types.iter().any(|t| matches_type(value, t)) {
+        return Some(format!("expected {}", types.join(" or ")));
+    }
+    if let Some(allowed) = &property.enum_values
+        && !allowed.contains(value)
+    {
+        let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+        return Some(format!("expected one of {}", allowed.join(", ")));
+    }
+    match value {
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or_default();
+            if let Some(minimum) = property.minimum
+                && number < minimum
+            {
+                return Some(format!("must be at least {}", minimum));
+            }
+            if let Some(maximum) = property.maximum
+                && number > maximum
+            {
+                return Some(format!("must be at most {}", maximum));
+            }
+        }
+        Value::String(text) => {
+            let length = text.chars().count();
+            if let Some(min_length) = property.min_length
+                && length < min_length
+            {
+                return Some(format!("must be at least {} characters", min_length));
+            }
+            if let Some(max_length) = property.max_length
+                && length > max_length
+            {
+                return Some(format!("must be at most {} characters", max_length));
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min_items) = property.min_items
+                && items.len() < min_items
+            {
+                return Some(format!("must have at least {} items", min_items));
+            }
+            if let Some(max_items) = property.max_items
+                && items.len() > max_items
+            {
+                return Some(format!("must have at most {} items", max_items));
+            }
+            if let Some(schema) = &property.items {
+                for (index, item) in items.iter().enumerate() {
+                    if let Some(reason) = property_error(schema, item) {
+                        return Some(nested(&format!("[{}]", index), &reason));
+                    }
+                }
+            }
+        }
+        Value::Object(object) => {
+            for key in property.required.iter().flatten() {
+                if object.get(key).is_none_or(Value::is_null) {
+                    return Some(format!("missing {}", key));
+                }
+            }
+            let mut keys: Vec<&String> = object.keys().collect();
+            keys.sort();
+            for key in keys {
+                let schema = property
+                    .properties
+                    .as_ref()
+                    .and_then(|properties| properties.get(key))
+                    .or(property.additional_properties.as_deref());
+                if let Some(reason) = schema.and_then(|schema| property_error(schema, &object[key]))
+                {
+                    return Some(nested(&format!(".{}", key), &reason));
+                }
+            }
+        }
+        _ => {}
+    }
+    None
+}
+
+/// `[1]: expected string` / `.labels[1]: expected string` for nested errors
+fn nested(path: &str, reason: &str) -> String {
+    if reason.starts_with('[') || reason.starts_with('.') {
+        format!("{}{}", path, reason)
+    } else {
+        format!("{}: {}", path, reason)
+    }
+}
+
+/// String property; `required` is documentation only, the `required` list
+/// of the schema is what clients see
+pub fn string_prop(description: &str, _required: bool) -> Property {
+    Property::new("string").with_description(description)
+}
+
+pub fn number_prop(description: &str, default: i32) -> Property {
+    Property::new("number")
+        .with_description(description)
+        .with_default(Value::Number(default.into()))
+}
+
+/// Array of strings (keys, IDs, labels, field names)
+pub fn array_prop(description: &str) -> Property {
+    Property::new("array")
+        .with_description(description)
+        .with_items(Property::new("string"))
+}
+
+pub fn union_prop(description: &str, types: Vec<&str>) -> Property {
+    Property::new(types).with_description(description)
+}
+
+/// `markdown` flag of the tools that read or write ADF bodies
+pub fn markdown_prop(description: &str) -> Property {
+    Property::new("boolean")
+        .with_description(description)
+        .with_default(json!(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_prop() {
+        let prop = string_prop("Test description", true);
+        assert_eq!(prop.property_type, "string");
+        assert_eq!(prop.description, Some("Test description".to_string()));
+        assert!(prop.default.is_none());
+        assert!(prop.enum_values.is_none());
+    }
+
+    fn schema() -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert("issue_key".to_string(), string_prop("Issue key", true));
+        props.insert("limit".to_string(), number_prop("Limit", 20));
+        props.insert(
+            "description".to_string(),
+            union_prop("Text or ADF", vec!["string", "object"]),
+        );
+        props.insert(
+            "format".to_string(),
+            Property::new("string").with_enum(vec![json!("json"), json!("mermaid")]),
+        );
+        ToolSchema::new("Example", props, vec!["issue_key".to_string()])
+    }
+
+    #[test]
+    fn test_minimal_verbosity_keeps_first_sentences() {
+        let mut props = HashMap::new();
+        props.insert(
+            "fields".to_string(),
+            array_prop("Field names to return. If not specified, returns 17 default fields: a, b"),
+        );
+        props.insert(
+            "type".to_string(),
+            string_prop(
+                "Issue type (e.g. 'Task'. Case-sensitive); needs a parent",
+                false,
+            ),
+        );
+        let schema = ToolSchema::new(
+            "Read an attachment; content is size-capped\n\nMore detail",
+            props,
+            vec![],
+        )
+        .with_verbosity(Verbosity::Minimal);
+        assert_eq!(schema.description, "Read an attachment");
+        assert_eq!(
+            schema.properties["fields"].description.as_deref(),
+            Some("Field names to return.")
+        );
+        assert_eq!(
+            schema.properties["type"].description.as_deref(),
+            Some("Issue type (e.g. 'Task'. Case-sensitive)")
+        );
+        assert_eq!(
+            first_sentence("Use e.g. 'Bug' here. Then"),
+            "Use e.g. 'Bug' here."
+        );
+    }
+
+    #[test]
+    fn test_detailed_verbosity_spells_out_constraints() {
+        let mut schema = schema();
+        schema.properties.insert(
+            "limit".to_string(),
+            number_prop("Limit", 20).with_range(Some(1.0), Some(100.0)),
+        );
+        let detailed = schema.clone().with_verbosity(Verbosity::Detailed);
+        assert_eq!(detailed.description, "Example (required: issue_key)");
+        assert_eq!(
+            detailed.properties["limit"].description.as_deref(),
+            Some("Limit (default 20; 1 to 100)")
+        );
+        assert_eq!(
+            detailed.properties["format"].description.as_deref(),
+            Some("(one of \"json\", \"mermaid\")")
+        );
+        // Validation is unchanged
+        assert_eq!(
+            detailed.validate(&json!({"issue_key": "A-1"})),
+            schema.validate(&json!({"issue_key": "A-1"}))
+        );
+    }
+
+    #[test]
+    fn test_parse_verbosity() {
+        assert_eq!(Verbosity::parse("").unwrap(), Verbosity::Standard);
+        assert_eq!(Verbosity::parse(" Minimal ").unwrap(), Verbosity::Minimal);
+        assert!(Verbosity::parse("verbose").is_err());
+    }
+
+    #[test]
+    fn test_validate_accepts_matching_args() {
+        let args = json!({"issue_key": "ENG-1", "limit": 5, "description": {"type": "doc"},
+            "format": "mermaid", "unknown": [1], "markdown": null});
+        assert!(schema().validate(&args).is_ok());
+        assert!(
+            schema()
+                .validate(&json!({"issue_key": "ENG-1", "limit": null}))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_types_enums_and_required() {
+        let args = json!({"limit": "5", "description": 3, "format": "svg"});
+        let errors = schema().validate(&args).unwrap_err();
+        assert_eq!(errors.missing, vec!["issue_key"]);
+        assert_eq!(
+            errors.invalid,
+            vec![
+                (
+                    "description".to_string(),
+                    "expected string or object".to_string()
+                ),
+                (
+                    "format".to_string(),
+                    "expected one of \"json\", \"mermaid\"".to_string()
+                ),
+                ("limit".to_string(), "expected number".to_string()),
+            ]
+        );
+        assert!(schema().validate(&json!("ENG-1")).is_err());
+    }
+
+    #[test]
+    fn test_validate_checks_nested_items_properties_and_bounds() {
+        let mut fields = HashMap::new();
+        fields.insert(
+            "summary".to_string(),
+            Property::new("string").with_length(Some(1), Some(5)),
+        );
+        fields.insert("labels".to_string(), array_prop("Labels"));
+        let mut props = HashMap::new();
+        props.insert(
+            "fields".to_string(),
+            Property::new("object").with_properties(fields),
+        );
+        props.insert(
+            "interval".to_string(),
+            number_prop("Interval", 60).with_range(Some(15.0), Some(3600.0)),
+        );
+        let schema = ToolSchema::new("Example", props, Vec::new());
+
+        let ok = json!({"fields": {"summary": "Fix", "labels": ["a"], "customfield_1": 3},
+            "interval": 15});
+        assert!(schema.validate(&ok).is_ok());
+
+        let bad = json!({"fields": {"summary": "Too long", "labels": ["a", 1]}, "interval": 5});
+        let errors = schema.validate(&bad).unwrap_err();
+        assert_eq!(
+            errors.invalid,
+            vec![
+                (
+                    "fields".to_string(),
+                    ".labels[1]: expected string".to_string()
+                ),
+                ("interval".to_string(), "must be at least 15".to_string()),
+            ]
+        );
+        let long = json!({"fields": {"summary": "Too long"}});
+        assert_eq!(
+            schema.validate(&long).unwrap_err().invalid[0].1,
+            ".summary: must be at most 5 characters"
+        );
+    }
+
+    #[test]
+    fn test_property_serializes_json_schema_keywords() {
+        let prop = array_prop("Keys").with_length(None, Some(10));
+        assert_eq!(
+            serde_json::to_value(&prop).unwrap(),
+            json!({"type": "array", "description": "Keys", "items": {"type": "string"},
+                "maxLength": 10})
+        );
+        let union = union_prop("Text or ADF", vec!["string", "object"]);
+        assert_eq!(
+            serde_json::to_value(&union).unwrap()["type"],
+            json!(["string", "object"])
+        );
+        assert_eq!(union.property_type.names(), vec!["string", "object"]);
+    }
+
+    #[test]
+    fn test_number_prop() {
+        let prop = number_prop("Test number", 42);
+        assert_eq!(prop.property_type, "number");
+        assert_eq!(prop.description, Some("Test number".to_string()));
+        assert_eq!(prop.default, Some(json!(42)));
+        assert!(prop.enum_values.is_none());
+    }
+}
@@ -0,0 +1,43 @@
+//! `atlassian_session_stats`: the counters of `mcp/stats.rs` for this session
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::mcp::stats::SessionStats;
+use crate::tools::ToolHandler;
+use crate::tools::schema::ToolSchema;
+
+pub struct SessionStatsHandler {
+    stats: Arc<SessionStats>,
+}
+
+impl SessionStatsHandler {
+    pub fn new(stats: Arc<SessionStats>) -> Self {
+        Self { stats }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for SessionStatsHandler {
+    fn name(&self) -> &'static str {
+        "atlassian_session_stats"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        ToolSchema::new(
+            "Session counters: tool calls, errors, Atlassian API time, cache hits and estimated tokens returned, per tool (heaviest first)",
+            HashMap::new(),
+            vec![],
+        )
+    }
+
+    async fn execute(&self, _args: Value, _config: &Config) -> Result<Value> {
+        let mut snapshot = self.stats.snapshot();
+        snapshot["success"] = json!(true);
+        Ok(snapshot)
+    }
+}
@@ -0,0 +1,463 @@
+//! Atlassian Teams: teams of the organization and their members
+//!
+//! The Teams REST API is served under `/gateway/api/public/teams/v1` on the
+//! site and is scoped to the organization, `ATLASSIAN_ORG_ID` or else the
+//! site's organization from the GraphQL gateway. Members come back as bare
+//! account IDs; names are read from Jira in one bulk request, and with
+//! `include_workload` each member's open issues are totalled like
+//! `jira_workload_by_assignee` does, least loaded first, so work can go "to
+//! someone on the payments team".
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+use crate::client::Jira;
+use crate::client::graphql::Gateway;
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::args::parse_args;
+use crate::tools::jira::apply_projects_filter;
+use crate::tools::jira::scope::quoted;
+use crate::tools::jira::workload::{self, WORKLOAD_FIELDS};
+use crate::tools::schema::{Property, ToolSchema, number_prop, string_prop};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+use crate::utils::time;
+
+pub struct GetTeamsHandler;
+pub struct GetTeamMembersHandler;
+
+const DEFAULT_TEAMS_LIMIT: usize = 50;
+const MAX_TEAMS_LIMIT: usize = 300;
+/// Teams read at most while filtering by name
+const MAX_TEAMS_SCANNED: usize = 2000;
+const MEMBERS_PAGE_SIZE: usize = 50;
+const MAX_MEMBERS: usize = 500;
+/// Account IDs per `/user/bulk` request
+const USERS_PER_REQUEST: usize = 100;
+const MAX_WORKLOAD_ISSUES: usize = 2000;
+
+const ORG_QUERY: &str = "query teamsOrg($cloudIds: [ID!]!) {
+  tenantContexts(cloudIds: $cloudIds) { orgId }
+}";
+
+#[derive(Deserialize)]
+struct TeamsArgs {
+    query: Option<String>,
+    limit: Option<usize>,
+    cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MembersArgs {
+    team: String,
+    #[serde(default)]
+    include_workload: bool,
+}
+
+async fn teams_request(
+    config: &Config,
+    request: reqwest::RequestBuilder,
+    what: &str,
+) -> Result<Value> {
+    let response = request
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to get {}: {}", what, response.status());
+    }
+    Ok(response.json().await?)
+}
+
+fn teams_url(config: &Config, org_id: &str, path: &str) -> String {
+    format!(
+        "{}/gateway/api/public/teams/v1/org/{}/{}",
+        config.get_atlassian_base_url(),
+        org_id,
+        path
+    )
+}
+
+/// `ATLASSIAN_ORG_ID`, else the site's organization
+async fn org_id(client: &reqwest::Client, config: &Config) -> Result<String> {
+    if let Some(org_id) = &config.atlassian_org_id {
+        return Ok(org_id.clone());
+    }
+    let gateway = Gateway::with_client(config, client.clone());
+    let cloud_id = gateway.cloud_id().await?;
+    let data = gateway
+        .query(ORG_QUERY, json!({"cloudIds": [cloud_id]}))
+        .await
+        .map_err(|e| anyhow::anyhow!("{} (set ATLASSIAN_ORG_ID to skip the lookup)", e))?;
+    data["tenantContexts"][0]["orgId"]
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| {
+            anyhow::anyhow!("Could not find the site's organization; set ATLASSIAN_ORG_ID")
+        })
+}
+
+/// The bare team ID of a team ID or ARI (`ari:cloud:identity::team/<id>`,
+/// as Compass owners are given)
+fn team_id(team: &str) -> Option<&str> {
+    let id = team.rsplit_once("team/").map_or(team, |(_, id)| id);
+    let is_uuid = id.len() == 36 && id.chars().all(|c| c.is_ascii_hexdigit() || c == '-');
+    is_uuid.then_some(id)
+}
+
+fn team_entry(team: &Value) -> Value {
+    json!({
+        "id": team["teamId"],
+        "name": team["displayName"],
+        "description": team["description"],
+        "state": team["state"]
+    })
+}
+
+/// One page of the organization's teams and the next cursor
+async fn teams_page(
+    client: &reqwest::Client,
+    config: &Config,
+    org_id: &str,
+    size: usize,
+    cursor: Option<&str>,
+) -> Result<(Vec<Value>, Option<String>)> {
+    let mut query = vec![("size", size.to_string())];
+    if let Some(cursor) = cursor {
+        query.push(("cursor", cursor.to_string()));
+    }
+    let request = client.get(teams_url(config, org_id, "teams")).query(&query);
+    let page = teams_request(config, request, "teams").await?;
+    let teams = page["entities"].as_array().cloned().unwrap_or_default();
+    let next = page["cursor"]
+        .as_str()
+        .filter(|c| !c.is_empty() && !teams.is_empty())
+        .map(String::from);
+    Ok((teams, next))
+}
+
+/// Teams whose name contains `filter` (all without one), up to `limit`
+async fn find_teams(
+    client: &reqwest::Client,
+    config: &Config,
+    org_id: &str,
+    filter: Option<&str>,
+    limit: usize,
+    mut cursor: Option<String>,
+) -> Result<(Vec<Value>, Option<String>)> {
+    let filter = filter.map(str::to_lowercase);
+    let mut found = Vec::new();
+    let mut scanned = 0;
+    loop {
+        let size = if filter.is_some() {
+            MAX_TEAMS_LIMIT
+        } else {
+            limit - found.len()
+        };
+        let (teams, next) = teams_page(client, config, org_id, size, cursor.as_deref()).await?;
+        scanned += teams.len();
+        cursor = next;
+        found.extend(teams.into_iter().filter(|team| {
+            filter.as_ref().is_none_or(|filter| {
+                team["displayName"]
+                    .as_str()
+                    .is_some_and(|name| name.to_lowercase().contains(filter))
+            })
+        }));
+        if found.len() >= limit || cursor.is_none() || scanned >= MAX_TEAMS_SCANNED {
+            // A name filter reads whole pages, so its cursor would skip
+            // unreported matches
+            let cursor = cursor.filter(|_| filter.is_none());
+            found.truncate(limit);
+            return Ok((found, cursor));
+        }
+    }
+}
+
+/// The team a name or ID refers to
+async fn resolve_team(
+    client: &reqwest::Client,
+    config: &Config,
+    org_id: &str,
+    team: &str,
+) -> Result<Value> {
+    if let Some(id) = team_id(team) {
+        let request = client.get(teams_url(config, org_id, &format!("teams/{}", id)));
+        return teams_request(config, request, "team").await;
+    }
+    let (matches, _) = find_teams(client, config, org_id, Some(team), 20, None).await?;
+    if let Some(exact) = matches.iter().find(|t| {
+        t["displayName"]
+            .as_str()
+            .is_some_and(|name| name.eq_ignore_ascii_case(team))
+    }) {
+        return Ok(exact.clone());
+    }
+    match matches.as_slice() {
+        [] => anyhow::bail!("No team named '{}'", team),
+        [only] => Ok(only.clone()),
+        several => anyhow::bail!(
+            "'{}' matches several teams: {}",
+            team,
+            several
+                .iter()
+                .filter_map(|t| t["displayName"].as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Member account IDs (at most `MAX_MEMBERS`) and whether there were more
+async fn team_members(
+    client: &reqwest::Client,
+    config: &Config,
+    org_id: &str,
+    team_id: &str,
+) -> Result<(Vec<String>, bool)> {
+    let url = teams_url(config, org_id, &format!("teams/{}/members", team_id));
+    let mut members = Vec::new();
+    let mut after: Option<String> = None;
+    loop {
+        let mut body = json!({"first": MEMBERS_PAGE_SIZE});
+        if let Some(after) = &after {
+            body["after"] = json!(after);
+        }
+        let request = client.post(&url).json(&body);
+        let page = teams_request(config, request, "team members").await?;
+        members.extend(
+            page["results"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|member| member["accountId"].as_str().map(String::from)),
+        );
+        let info = &page["pageInfo"];
+        after = info["endCursor"]
+            .as_str()
+            .filter(|_| info["hasNextPage"].as_bool() == Some(true))
+            .map(String::from);
+        if after.is_none() {
+            return Ok((members, false));
+        }
+        if members.len() >= MAX_MEMBERS {
+            members.truncate(MAX_MEMBERS);
+            return Ok((members, true));
+        }
+    }
+}
+
+/// Jira users by account ID; unknown IDs are left out
+async fn users(
+    client: &reqwest::Client,
+    config: &Config,
+    account_ids: &[String],
+) -> Result<Vec<Value>> {
+    let mut users = Vec::new();
+    for chunk in account_ids.chunks(USERS_PER_REQUEST) {
+        let mut query: Vec<(&str, String)> =
+            chunk.iter().map(|id| ("accountId", id.clone())).collect();
+        query.push(("maxResults", USERS_PER_REQUEST.to_string()));
+        let request = client
+            .get(format!(
+                "{}/rest/api/3/user/bulk",
+                config.get_atlassian_base_url()
+            ))
+            .query(&query);
+        let page = teams_request(config, request, "users").await?;
+        users.extend(page["values"].as_array().cloned().unwrap_or_default());
+    }
+    Ok(users)
+}
+
+/// JQL for the open issues assigned to `account_ids`
+fn members_jql(account_ids: &[String]) -> String {
+    let ids: Vec<String> = account_ids.iter().map(|id| quoted(id)).collect();
+    format!("assignee in ({}) AND statusCategory != Done", ids.join(","))
+}
+
+#[async_trait]
+impl ToolHandler for GetTeamsHandler {
+    fn name(&self) -> &'static str {
+        "atlassian_get_teams"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "query".to_string(),
+            string_prop("Only teams whose name contains this text", false),
+        );
+        props.insert(
+            "limit".to_string(),
+            number_prop("Teams per page (1-300)", DEFAULT_TEAMS_LIMIT as i32)
+                .with_range(Some(1.0), Some(MAX_TEAMS_LIMIT as f64)),
+        );
+        props.insert(
+            "cursor".to_string(),
+            string_prop("next_cursor from the previous page", false),
+        );
+        ToolSchema::new(
+            "List the organization's Atlassian teams (ID, name, description), optionally by name",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let TeamsArgs {
+            query,
+            limit,
+            cursor,
+        } = parse_args(&args)?;
+        let query = query.filter(|q| !q.trim().is_empty());
+        let limit = limit
+            .unwrap_or(DEFAULT_TEAMS_LIMIT)
+            .clamp(1, MAX_TEAMS_LIMIT);
+
+        let client = create_atlassian_client(config);
+        let org_id = org_id(&client, config).await?;
+        let (teams, next_cursor) = find_teams(
+            &client,
+            config,
+            &org_id,
+            query.as_deref().map(str::trim),
+            limit,
+            cursor.filter(|c| !c.is_empty()),
+        )
+        .await?;
+        Ok(json!({
+            "success": true,
+            "org_id": org_id,
+            "teams": teams.iter().map(team_entry).collect::<Vec<_>>(),
+            "has_more": next_cursor.is_some(),
+            "next_cursor": next_cursor
+        }))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for GetTeamMembersHandler {
+    fn name(&self) -> &'static str {
+        "atlassian_get_team_members"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "team".to_string(),
+            string_prop(
+                "Team name, ID or ARI (e.g., a Compass component owner)",
+                true,
+            ),
+        );
+        props.insert("include_workload".to_string(), Property::new("boolean").with_description("Add each member's open issues, in progress, overdue and remaining estimate, least loaded first (default: false)").with_default(json!(false)));
+        ToolSchema::new(
+            "Members of an Atlassian team with their Jira account IDs, optionally with each member's open workload for picking an assignee",
+            props,
+            vec!["team".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let MembersArgs {
+            team,
+            include_workload,
+        } = parse_args(&args)?;
+        let team = team.trim();
+        if team.is_empty() {
+            anyhow::bail!("Missing team");
+        }
+
+        let client = create_atlassian_client(config);
+        let org_id = org_id(&client, config).await?;
+        let team = resolve_team(&client, config, &org_id, team).await?;
+        let id = team["teamId"].as_str().unwrap_or_default().to_string();
+        let (account_ids, truncated) = team_members(&client, config, &org_id, &id).await?;
+        let known = if account_ids.is_empty() {
+            Vec::new()
+        } else {
+            users(&client, config, &account_ids).await?
+        };
+
+        let mut members: Vec<Value> = account_ids
+            .iter()
+            .map(|account_id| {
+                let user = known.iter().find(|u| u["accountId"] == account_id.as_str());
+                json!({
+                    "account_id": account_id,
+                    "name": user.and_then(|u| u["displayName"].as_str()),
+                    "active": user.and_then(|u| u["active"].as_bool())
+                })
+            })
+            .collect();
+
+        let mut result = team_entry(&team);
+        result["success"] = json!(true);
+        if include_workload && !account_ids.is_empty() {
+            let jira = Jira::with_client(config, client.clone());
+            let fields: Vec<String> = WORKLOAD_FIELDS.iter().map(|f| f.to_string()).collect();
+            let (issues, issues_truncated) = workload::collect_issues(
+                &jira,
+                &apply_projects_filter(&members_jql(&account_ids), config),
+                &fields,
+                MAX_WORKLOAD_ISSUES,
+            )
+            .await?;
+            let zone = time::user_timezone(config)?;
+            let today = time::jira_date("today", &zone)?;
+            let (_, loads) = workload::aggregate(&issues, None, &today);
+            for member in &mut members {
+                let load = loads
+                    .iter()
+                    .find(|(id, ..)| id.as_deref() == member["account_id"].as_str())
+                    .map(|(.., load)| load.clone())
+                    .unwrap_or_default();
+                member["workload"] = load.to_json(false);
+            }
+            members.sort_by_key(|member| {
+                (
+                    member["active"] == false,
+                    member["workload"]["open_issues"].as_u64().unwrap_or(0),
+                )
+            });
+            if issues_truncated {
+                result["workload_truncated"] = json!(true);
+            }
+        }
+        result["member_count"] = json!(members.len());
+        result["members"] = json!(members);
+        if truncated {
+            result["truncated"] = json!(true);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_team_id_accepts_ids_and_aris() {
+        let id = "2f1b6c47-9d7e-4a32-b8e1-0c5d3f6a9b21";
+        assert_eq!(team_id(id), Some(id));
+        assert_eq!(
+            team_id(&format!("ari:cloud:identity::team/{}", id)),
+            Some(id)
+        );
+        assert_eq!(team_id("Payments"), None);
+    }
+
+    #[test]
+    fn test_members_jql_quotes_account_ids() {
+        assert_eq!(
+            members_jql(&["a1".to_string(), "b:2".to_string()]),
+            "assignee in (\"a1\",\"b:2\") AND statusCategory != Done"
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//! Trello tools (`trello` feature)
+//!
+//! Trello authenticates with an API key + user token passed as query
+//! parameters rather than the Atlassian Basic auth header.
+
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema, string_prop};
+use crate::utils::http_utils::create_atlassian_client;
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+/// Trello REST API v1 base URL
+pub const TRELLO_API_URL: &str = "https://api.trello.com/1";
+
+const BOARD_FIELDS: &str = "name,desc,closed,url,dateLastActivity";
+const LIST_FIELDS: &str = "name,closed,pos";
+const CARD_FIELDS: &str = "name,desc,idList,labels,due,dueComplete,closed,url,dateLastActivity";
+
+// Handlers for each Trello tool
+pub struct ListBoardsHandler;
+pub struct GetListsHandler;
+pub struct GetCardsHandler;
+pub struct MoveCardHandler;
+
+/// Key/token query parameters for every request
+fn auth_params(config: &Config) -> Result<[(&'static str, String); 2]> {
+    match (&config.trello_api_key, &config.trello_token) {
+        (Some(key), Some(token)) => Ok([("key", key.clone()), ("token", token.clone())]),
+        _ => anyhow::bail!("Trello credentials not configured (TRELLO_API_KEY, TRELLO_TOKEN)"),
+    }
+}
+
+/// Trello ids are 24-char hex; short links are 8-char alphanumerics
+fn trello_id<'a>(args: &'a Value, name: &str) -> Result<&'a str> {
+    let id = args[name]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing {}", name))?;
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        anyhow::bail!("Invalid {}: '{}'", name, id);
+    }
+    Ok(id)
+}
+
+async fn get_json(url: &str, params: &[(&str, String)], config: &Config) -> Result<Value> {
+    let client = create_atlassian_client(config);
+    let response = client
+        .get(url)
+        .header("Accept", "application/json")
+        .query(&auth_params(config)?)
+        .query(params)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let error = response.text().await?;
+        anyhow::bail!("Trello request failed: {}", error);
+    }
+
+    Ok(response.json().await?)
+}
+
+#[async_trait]
+impl ToolHandler for ListBoardsHandler {
+    fn name(&self) -> &'static str {
+        "trello_list_boards"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "include_closed".to_string(),
+            Property::new("boolean")
+                .with_description("Include closed boards (default: false)")
+                .with_default(json!(false)),
+        );
+        ToolSchema::new(
+            "List Trello boards of the authenticated member",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let filter = if args["include_closed"].as_bool().unwrap_or(false) {
+            "all"
+        } else {
+            "open"
+        };
+        let url = format!("{}/members/me/boards", TRELLO_API_URL);
+        let params = [
+            ("filter", filter.to_string()),
+            ("fields", BOARD_FIELDS.to_string()),
+        ];
+
+        let boards = get_json(&url, &params, config).await?;
+
+        Ok(json!({
+            "success": true,
+            "boards": boards
+        }))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for GetListsHandler {
+    fn name(&self) -> &'static str {
+        "trello_get_lists"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "board_id".to_string(),
+            string_prop("Board ID or short link", true),
+        );
+        ToolSchema::new(
+            "Get open lists on a Trello board",
+            props,
+            vec!["board_id".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let board_id = trello_id(&args, "board_id")?;
+        let url = format!("{}/boards/{}/lists", TRELLO_API_URL, board_id);
+        let params = [
+            ("filter", "open".to_string()),
+            ("fields", LIST_FIELDS.to_string()),
+        ];
+
+        let lists = get_json(&url, &params, config).await?;
+
+        Ok(json!({
+            "success": true,
+            "lists": lists
+        }))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for GetCardsHandler {
+    fn name(&self) -> &'static str {
+        "trello_get_cards"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "list_id".to_string(),
+            string_prop("List ID (takes precedence over board_id)", false),
+        );
+        props.insert(
+            "board_id".to_string(),
+            string_prop("Board ID; returns all open cards on the board", false),
+        );
+        ToolSchema::new("Get Trello cards in a list or board", props, vec![])
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        // Cards of one list, or every open card on a board
+        let url = if args["list_id"].is_string() {
+            format!(
+                "{}/lists/{}/cards",
+                TRELLO_API_URL,
+                trello_id(&args, "list_id")?
+            )
+        } else if args["board_id"].is_string() {
+            format!(
+                "{}/boards/{}/cards",
+                TRELLO_API_URL,
+                trello_id(&args, "board_id")?
+            )
+        } else {
+            anyhow::bail!("Missing list_id or board_id");
+        };
+        let params = [("fields", CARD_FIELDS.to_string())];
+
+        let cards = get_json(&url, &params, config).await?;
+
+        Ok(json!({
+            "success": true,
+            "cards": cards
+        }))
+    }
+}
+
+#[async_trait]
+impl ToolHandler for MoveCardHandler {
+    fn name(&self) -> &'static str {
+        "trello_move_card"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "card_id".to_string(),
+            string_prop("Card ID or short link", true),
+        );
+        props.insert(
+            "list_id".to_string(),
+            string_prop("Destination list ID", true),
+        );
+        props.insert(
+            "position".to_string(),
+            Property::new("string")
+                .with_description("Position in the destination list (default: top)")
+                .with_default(json!("top"))
+                .with_enum(vec![json!("top"), json!("bottom")]),
+        );
+        ToolSchema::new(
+            "Move a Trello card to another list",
+            props,
+            vec!["card_id".to_string(), "list_id".to_string()],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let card_id = trello_id(&args, "card_id")?;
+        let list_id = trello_id(&args, "list_id")?;
+        let position = args["position"].as_str().unwrap_or("top");
+        if position != "top" && position != "bottom" {
+            anyhow::bail!(
+                "Invalid position '{}': expected 'top' or 'bottom'",
+                position
+            );
+        }
+
+        let client = create_atlassian_client(config);
+        let url = format!("{}/cards/{}", TRELLO_API_URL, card_id);
+        let params = [
+            ("idList", list_id.to_string()),
+            ("pos", position.to_string()),
+        ];
+
+        let response = client
+            .put(&url)
+            .header("Accept", "application/json")
+            .query(&auth_params(config)?)
+            .query(&params)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to move card: {}", error);
+        }
+
+        let data: Value = response.json().await?;
+
+        Ok(json!({
+            "success": true,
+            "card_id": data["id"],
+            "list_id": data["idList"],
+            "url": data["url"]
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ConfigBuilder;
+
+    fn create_test_config(with_credentials: bool) -> Config {
+        ConfigBuilder::new()
+            .with(|config| {
+                config.trello_api_key = with_credentials.then(|| "key123".to_string());
+                config.trello_token = with_credentials.then(|| "tok456".to_string());
+            })
+            .build()
+    }
+
+    #[test]
+    fn test_auth_params() {
+        let params = auth_params(&create_test_config(true)).unwrap();
+        assert_eq!(params[0], ("key", "key123".to_string()));
+        assert_eq!(params[1], ("token", "tok456".to_string()));
+
+        assert!(auth_params(&create_test_config(false)).is_err());
+    }
+
+    #[test]
+    fn test_trello_id_validation() {
+        let args = json!({"board_id": "5f1a2b3c4d5e6f7a8b9c0d1e", "bad": "abc/../x"});
+        assert!(trello_id(&args, "board_id").is_ok());
+        assert!(trello_id(&args, "bad").is_err());
+        assert!(trello_id(&args, "missing").is_err());
+    }
+
+    #[test]
+    fn test_get_cards_requires_list_or_board() {
+        let handler = GetCardsHandler;
+        let config = create_test_config(true);
+
+        let result = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(handler.execute(json!({}), &config));
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Missing list_id or board_id")
+        );
+    }
+
+    #[test]
+    fn test_move_card_rejects_invalid_position() {
+        let handler = MoveCardHandler;
+        let config = create_test_config(true);
+        let args = json!({"card_id": "abc123", "list_id": "def456", "position": "middle"});
+
+        let result = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(handler.execute(args, &config));
+        assert!(result.unwrap_err().to_string().contains("Invalid position"));
+    }
+}
@@ -0,0 +1,368 @@
+//! `atlassian_undo_last`: revert the latest journaled write (`UNDO_JOURNAL_SIZE`)
+//!
+//! Before `jira_update_issue`, `jira_transition_issue` and the Confluence
+//! page edits run, `call_tool` reads what they are about to change (the
+//! previous values of the updated fields, the current status, the page
+//! version) and keeps it once the write succeeded. The journal holds the
+//! last `UNDO_JOURNAL_SIZE` writes per process; the oldest are dropped.
+//!
+//! Undo writes the previous state back: field values are sent as read
+//! (objects reduced to their ID), statuses through a transition leading
+//! back to the old one, and pages as a new version with the old title and
+//! body. A page saved by someone else since is left alone, as is a status
+//! no transition leads back to. Created issues, pages and comments are not
+//! journaled: undoing those would mean deleting, which is left to people.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::client::{Confluence, Jira, rest};
+use crate::config::Config;
+use crate::tools::ToolHandler;
+use crate::tools::schema::{Property, ToolSchema};
+use crate::tools::write_policy;
+use crate::utils::http_utils::create_atlassian_client;
+
+pub struct UndoLastHandler;
+
+/// What a journaled write replaced
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// Field values before `jira_update_issue`
+    IssueFields {
+        issue_key: String,
+        previous: Map<String, Value>,
+    },
+    /// Status before `jira_transition_issue`
+    IssueStatus { issue_key: String, status: String },
+    /// Page version before an edit, and the version the edit saved
+    PageVersion {
+        page_id: String,
+        version: u64,
+        saved: u64,
+    },
+}
+
+struct Entry {
+    site: String,
+    tool: String,
+    change: Change,
+    at: Instant,
+}
+
+/// Per process, so entries survive config reloads
+static JOURNAL: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+/// Tools whose writes are journaled
+pub fn is_journaled(tool: &str) -> bool {
+    matches!(
+        tool,
+        "jira_update_issue"
+            | "jira_transition_issue"
+            | "confluence_update_page"
+            | "confluence_append_to_page"
+            | "confluence_replace_section"
+    )
+}
+
+/// The state a journaled call is about to change; a failed read is logged
+/// and the call runs without a journal entry
+pub async fn capture(tool: &str, args: &Value, config: &Config) -> Option<Change> {
+    if config.undo_journal_size == 0 || !is_journaled(tool) {
+        return None;
+    }
+    match read_state(tool, args, config).await {
+        Ok(change) => Some(change),
+        Err(e) => {
+            tracing::warn!("{} will not be undoable: {}", tool, e);
+            None
+        }
+    }
+}
+
+async fn read_state(tool: &str, args: &Value, config: &Config) -> Result<Change> {
+    let arg = |name: &str| {
+        args[name]
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| anyhow::anyhow!("Missing {}", name))
+    };
+    match tool {
+        "jira_update_issue" => {
+            let issue_key = arg("issue_key")?;
+            let names: Vec<&str> = args["fields"]
+                .as_object()
+                .map(|fields| fields.keys().map(String::as_str).collect())
+                .unwrap_or_default();
+            let previous = issue_fields(config, &issue_key, &names).await?;
+            Ok(Change::IssueFields {
+                issue_key,
+                previous,
+            })
+        }
+        "jira_transition_issue" => {
+            let issue_key = arg("issue_key")?;
+            let issue = Jira::new(config).get_current_issue(&issue_key).await?;
+            let status = issue
+                .fields
+                .status
+                .map(|status| status.name)
+                .ok_or_else(|| anyhow::anyhow!("Issue {} has no status", issue_key))?;
+            Ok(Change::IssueStatus { issue_key, status })
+        }
+        _ => {
+            let page_id = arg("page_id")?;
+            let page = Confluence::new(config)
+                .get_page_storage(&page_id, None)
+                .await?;
+            let version = page
+                .version_number()
+                .ok_or_else(|| anyhow::anyhow!("Page {} has no version", page_id))?;
+            Ok(Change::PageVersion {
+                page_id,
+                version,
+                saved: version + 1,
+            })
+        }
+    }
+}
+
+/// Current values of `names` (`null` for unset fields)
+async fn issue_fields(
+    config: &Config,
+    issue_key: &str,
+    names: &[&str],
+) -> Result<Map<String, Value>> {
+    let url = format!(
+        "{}/rest/api/3/issue/{}",
+        config.get_atlassian_base_url(),
+        issue_key
+    );
+    let query = [("fields", names.join(","))];
+    let client = create_atlassian_client(config);
+    let data = rest::get_current_json(&client, config, &url, &query, "issue").await?;
+    Ok(names
+        .iter()
+        .map(|name| (name.to_string(), data["fields"][*name].clone()))
+        .collect())
+}
+
+/// Journals `change` once the call succeeded; `result` is the tool result.
+/// Page edits that saved nothing (`validate_only`, conflict reports) are
+/// skipped.
+pub fn record(tool: &str, change: Change, result: &Value, config: &Config) {
+    let change = match change {
+        Change::PageVersion {
+            page_id, version, ..
+        } => match result["version"].as_u64() {
+            Some(saved) if saved > version => Change::PageVersion {
+                page_id,
+                version,
+                saved,
+            },
+            _ => return,
+        },
+        other => other,
+    };
+    let mut journal = JOURNAL.lock().unwrap_or_else(|e| e.into_inner());
+    journal.push(Entry {
+        site: config.get_atlassian_base_url().to_string(),
+        tool: tool.to_string(),
+        change,
+        at: Instant::now(),
+    });
+    let excess = journal.len().saturating_sub(config.undo_journal_size);
+    journal.drain(..excess);
+}
+
+/// A field value as read, in the shape an edit accepts: objects other than
+/// ADF documents are reduced to their identifier
+pub fn restorable(value: &Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.iter().map(restorable).collect()),
+        Value::Object(object) if object.get("type") != Some(&json!("doc")) => {
+            let id = ["accountId", "id", "key", "name", "value"]
+                .into_iter()
+                .find_map(|id| object.get(id).map(|v| (id.to_string(), v.clone())));
+            match id {
+                Some(id) => Value::Object(Map::from_iter([id])),
+                None => value.clone(),
+            }
+        }
+        _ => value.clone(),
+    }
+}
+
+fn describe(change: &Change) -> String {
+    match change {
+        Change::IssueFields {
+            issue_key,
+            previous,
+        } => {
+            let names: Vec<&str> = previous.keys().map(String::as_str).collect();
+            format!("restores {} on {}", names.join(", "), issue_key)
+        }
+        Change::IssueStatus { issue_key, status } => {
+            format!("moves {} back to {}", issue_key, status)
+        }
+        Change::PageVersion {
+            page_id, version, ..
+        } => format!("restores page {} to version {}", page_id, version),
+    }
+}
+
+/// Writes the previous state back; result fields for the undo response
+async fn revert(change: &Change, config: &Config) -> Result<Value> {
+    match change {
+        Change::IssueFields {
+            issue_key,
+            previous,
+        } => {
+            let jira = Jira::new(config);
+            write_policy::check_issue(&jira, config, issue_key).await?;
+            let fields = previous
+                .iter()
+                .map(|(name, value)| (name.clone(), restorable(value)))
+                .collect();
+            jira.update_issue(issue_key, fields).await?;
+            Ok(json!({ "issue_key": issue_key }))
+        }
+        Change::IssueStatus { issue_key, status } => {
+            let jira = Jira::new(config);
+            write_policy::check_issue(&jira, config, issue_key).await?;
+            let transitions = jira.get_transitions(issue_key).await?;
+            let back = transitions
+                .iter()
+                .find(|t| {
+                    t.to
+                        .as_ref()
+                        .is_some_and(|to| to.name.eq_ignore_ascii_case(status))
+                })
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "No transition from {}'s current status leads back to '{}'; change it by hand",
+                        issue_key,
+                        status
+                    )
+                })?;
+            jira.transition_issue(issue_key, &back.id).await?;
+            Ok(json!({ "issue_key": issue_key, "status": status }))
+        }
+        Change::PageVersion {
+            page_id,
+            version,
+            saved,
+        } => {
+            let confluence = Confluence::new(config);
+            write_policy::check_page(&confluence, config, page_id).await?;
+            let current = confluence.get_page_storage(page_id, None).await?;
+            let current_version = current
+                .version_number()
+                .ok_or_else(|| anyhow::anyhow!("Failed to get current version"))?;
+            if current_version != *saved {
+                anyhow::bail!(
+                    "Page {} was saved again since (now version {}, the edit saved {}); restoring version {} would discard that",
+                    page_id,
+                    current_version,
+                    saved,
+                    version
+                );
+            }
+            let old = confluence.get_page_storage(page_id, Some(*version)).await?;
+            let page = confluence
+                .update_page(
+                    page_id,
+                    &old.title,
+                    old.storage().unwrap_or_default(),
+                    current_version + 1,
+                    &[],
+                )
+                .await?;
+            Ok(json!({ "page_id": page_id, "version": page.version_number() }))
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for UndoLastHandler {
+    fn name(&self) -> &'static str {
+        "atlassian_undo_last"
+    }
+
+    fn schema(&self, _config: &Config) -> ToolSchema {
+        let mut props = HashMap::new();
+        props.insert(
+            "dry_run".to_string(),
+            Property::new("boolean")
+                .with_description("Only show what would be reverted")
+                .with_default(json!(false)),
+        );
+        ToolSchema::new(
+            "Revert the most recent issue update, transition or page edit made through this server",
+            props,
+            vec![],
+        )
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> Result<Value> {
+        let site = config.get_atlassian_base_url();
+        let latest = {
+            let journal = JOURNAL.lock().unwrap_or_else(|e| e.into_inner());
+            journal
+                .iter()
+                .rfind(|entry| entry.site == site)
+                .map(|entry| (entry.tool.clone(), entry.change.clone(), entry.at))
+        };
+        let Some((tool, change, at)) = latest else {
+            anyhow::bail!("Nothing to undo: no journaled writes in this session");
+        };
+        let mut result = json!({
+            "success": true,
+            "tool": tool,
+            "change": describe(&change),
+            "age_secs": at.elapsed().as_secs()
+        });
+        if args["dry_run"].as_bool().unwrap_or(false) {
+            result["dry_run"] = json!(true);
+            return Ok(result);
+        }
+
+        let reverted = revert(&change, config).await?;
+        JOURNAL
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|entry| entry.at != at || entry.change != change);
+        result["reverted"] = reverted;
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restorable_reduces_objects_to_their_id() {
+        let read = json!({
+            "self": "https://x/rest/api/3/priority/2",
+            "id": "2",
+            "name": "High",
+            "iconUrl": "https://x/high.svg"
+        });
+        assert_eq!(restorable(&read), json!({"id": "2"}));
+        let assignee = json!({"accountId": "abc", "displayName": "Sam"});
+        assert_eq!(restorable(&assignee), json!({"accountId": "abc"}));
+        assert_eq!(
+            restorable(&json!([{"id": "10", "name": "API"}])),
+            json!([{"id": "10"}])
+        );
+        let doc = json!({"type": "doc", "version": 1, "content": []});
+        assert_eq!(restorable(&doc), doc);
+        assert_eq!(restorable(&json!(["a", "b"])), json!(["a", "b"]));
+        assert_eq!(restorable(&Value::Null), Value::Null);
+    }
+}
@@ -0,0 +1,162 @@
+//! Where write tools may write (`WRITE_ALLOWED_PROJECTS`, `WRITE_ALLOWED_SPACES`)
+//!
+//! Separate from the read filters, so the assistant can read every project
+//! and space while only the listed ones accept changes. Each mutating handler
+//! checks its target before its first write: issues by the project Jira
+//! reports for them (not the key's prefix, which a moved issue keeps
+//! answering to), pages by their space, bulk tools by
+//! ANDing the projects into their JQL. Space entries are keys or v2 IDs, as
+//! in `CONFLUENCE_SPACES_FILTER`. An empty list allows everything.
+
+use anyhow::Result;
+
+use crate::client::{Confluence, Jira, rest};
+use crate::config::Config;
+use crate::tools::confluence::spaces;
+use crate::tools::jira::scope::quoted;
+use crate::tools::jira::split_order_by;
+
+fn rejected(kind: &str, target: &str, setting: &str, allowed: &[String]) -> anyhow::Error {
+    anyhow::anyhow!(
+        "{} '{}' is not in {} ({}); writes are limited to those",
+        kind,
+        target,
+        setting,
+        allowed.join(", ")
+    )
+}
+
+/// Errors unless `WRITE_ALLOWED_PROJECTS` allows project `key`
+pub fn check_project(config: &Config, key: &str) -> Result<()> {
+    let allowed = &config.write_allowed_projects;
+    if allowed.is_empty() || allowed.iter().any(|p| p.eq_ignore_ascii_case(key)) {
+        Ok(())
+    } else {
+        Err(rejected("Project", key, "WRITE_ALLOWED_PROJECTS", allowed))
+    }
+}
+
+/// Errors unless the issue's project is allowed; `issue` is a key or a
+/// numeric ID. The project is read from the issue itself, since a moved
+/// issue still answers to its old key.
+pub async fn check_issue(jira: &Jira<'_>, config: &Config, issue: &str) -> Result<()> {
+    if config.write_allowed_projects.is_empty() {
+        return Ok(());
+    }
+    let url = format!(
+        "{}/rest/api/3/issue/{}",
+        config.get_atlassian_base_url(),
+        issue
+    );
+    let query = [("fields", "project")];
+    let data = rest::get_current_json(jira.http(), config, &url, &query, "issue").await?;
+    let project = data["fields"]["project"]["key"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Issue {} has no project", issue))?;
+    check_project(config, project)
+}
+
+/// `jql` limited to `WRITE_ALLOWED_PROJECTS`, even when it names other
+/// projects itself
+pub fn scope_jql(jql: &str, config: &Config) -> String {
+    if config.write_allowed_projects.is_empty() {
+        return jql.to_string();
+    }
+    let projects: Vec<String> = config
+        .write_allowed_projects
+        .iter()
+        .map(|p| quoted(p))
+        .collect();
+    let (conditions, order_by) = split_order_by(jql);
+    let scope = format!("project IN ({})", projects.join(","));
+    let scoped = if conditions.trim().is_empty() {
+        scope
+    } else {
+        format!("{} AND ({})", scope, conditions.trim())
+    };
+    format!("{}{}", scoped, order_by.unwrap_or_default())
+}
+
+/// Errors unless `WRITE_ALLOWED_SPACES` allows the space (a key or v2 ID)
+pub async fn check_space(confluence: &Confluence<'_>, config: &Config, space: &str) -> Result<()> {
+    let allowed = &config.write_allowed_spaces;
+    if allowed.is_empty() || allowed.iter().any(|s| s.eq_ignore_ascii_case(space)) {
+        return Ok(());
+    }
+    let id = spaces::space_id(confluence, config, space).await?;
+    if spaces::space_ids(confluence, config, allowed)
+        .await?
+        .contains(&id)
+    {
+        Ok(())
+    } else {
+        Err(rejected("Space", space, "WRITE_ALLOWED_SPACES", allowed))
+    }
+}
+
+/// Errors unless the page's space is allowed
+pub async fn check_page(confluence: &Confluence<'_>, config: &Config, page_id: &str) -> Result<()> {
+    if config.write_allowed_spaces.is_empty() {
+        return Ok(());
+    }
+    let page = confluence.get_current_page(page_id, &[]).await?;
+    let space = page
+        .space_id
+        .ok_or_else(|| anyhow::anyhow!("Page {} has no spaceId", page_id))?;
+    check_space(confluence, config, &space)
+        .await
+        .map_err(|e| anyhow::anyhow!("Page {}: {}", page_id, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ConfigBuilder;
+
+    fn config(projects: &[&str]) -> Config {
+        ConfigBuilder::new()
+            .with(|config| {
+                config.write_allowed_projects = projects.iter().map(|p| p.to_string()).collect()
+            })
+            .build()
+    }
+
+    #[test]
+    fn test_check_project() {
+        assert!(check_project(&config(&[]), "ANY").is_ok());
+        let config = config(&["ENG", "OPS"]);
+        assert!(check_project(&config, "ops").is_ok());
+        let error = check_project(&config, "HR").unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "Project 'HR' is not in WRITE_ALLOWED_PROJECTS (ENG, OPS); writes are limited to those"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_issue_uses_the_issue_project() {
+        let mock = crate::mock::MockAtlassianServer::start().await.unwrap();
+        let config = ConfigBuilder::mock(&mock)
+            .with(|config| config.write_allowed_projects = vec!["DEMO".to_string()])
+            .build();
+        let jira = Jira::new(&config);
+        assert!(check_issue(&jira, &config, "DEMO-1").await.is_ok());
+        let error = check_issue(&jira, &config, "OPS-1").await.unwrap_err();
+        assert!(error.to_string().starts_with("Project 'OPS' is not in"));
+        assert!(check_issue(&jira, &config, "NOPE-1").await.is_err());
+    }
+
+    #[test]
+    fn test_scope_jql_always_adds_the_projects() {
+        assert_eq!(scope_jql("status = Open", &config(&[])), "status = Open");
+        let config = config(&["ENG"]);
+        assert_eq!(
+            scope_jql("project = HR ORDER BY key", &config),
+            "project IN (\"ENG\") AND (project = HR) ORDER BY key"
+        );
+        assert_eq!(
+            scope_jql("ORDER BY key", &config),
+            "project IN (\"ENG\") ORDER BY key"
+        );
+    }
+}
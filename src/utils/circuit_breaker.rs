@@ -0,0 +1,169 @@
+//! Per-host circuit breaker for upstream outages
+//!
+//! During an Atlassian outage every tool call waits for a timeout or a 5xx,
+//! and assistants retry, so one outage becomes a storm of slow failing calls.
+//! After `CIRCUIT_BREAKER_THRESHOLD` consecutive 5xx/timeout/connection
+//! failures from one host, its circuit opens: calls fail at once with an
+//! "appears down" error for `CIRCUIT_BREAKER_COOLDOWN_SECS`. Once the
+//! cool-down ends calls go through again; the next failure reopens the
+//! circuit right away, the next success closes it. Any answer from the host
+//! (including 4xx) counts as success.
+//!
+//! State is per process, like the cloud ID cache, so it survives reloads.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Default)]
+struct HostState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    last_error: String,
+}
+
+pub struct CircuitBreakers {
+    hosts: Mutex<Vec<(String, HostState)>>,
+}
+
+/// The process-wide breakers used by `call_tool`
+pub static BREAKERS: CircuitBreakers = CircuitBreakers::new();
+
+/// Whether an error means the host is unhealthy rather than the request bad
+pub fn is_upstream_failure(error: &anyhow::Error) -> bool {
+    let transport = error.chain().any(|cause| {
+        cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|e| e.is_timeout() || e.is_connect())
+    });
+    let message = error.to_string().to_lowercase();
+    transport
+        || [
+            "500 internal server error",
+            "502 bad gateway",
+            "503 service unavailable",
+            "504 gateway timeout",
+            "timed out after",
+        ]
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+impl Default for CircuitBreakers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CircuitBreakers {
+    pub const fn new() -> Self {
+        Self {
+            hosts: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Fails while `host`'s circuit is open
+    pub fn check(&self, host: &str, now: Instant) -> anyhow::Result<()> {
+        let hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let Some((_, state)) = hosts.iter().find(|(h, _)| h == host) else {
+            return Ok(());
+        };
+        match state.open_until {
+            Some(until) if until > now => anyhow::bail!(
+                "Atlassian appears down: {} consecutive failures from {} (last: {}). \
+                 Calls to it are paused for another {}s; retry after that.",
+                state.consecutive_failures,
+                host,
+                state.last_error,
+                (until - now).as_secs().max(1)
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Counts the outcome of a call to `host`; `failure` is the error text
+    /// of an upstream failure, `None` for any answer from the host
+    pub fn record(
+        &self,
+        host: &str,
+        failure: Option<String>,
+        threshold: u32,
+        cooldown: Duration,
+        now: Instant,
+    ) {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let index = match hosts.iter().position(|(h, _)| h == host) {
+            Some(index) => index,
+            None if failure.is_none() => return,
+            None => {
+                hosts.push((host.to_string(), HostState::default()));
+                hosts.len() - 1
+            }
+        };
+        let state = &mut hosts[index].1;
+        match failure {
+            None => *state = HostState::default(),
+            Some(error) => {
+                state.consecutive_failures += 1;
+                state.last_error = error;
+                if state.consecutive_failures >= threshold {
+                    if state.open_until.is_none_or(|until| until <= now) {
+                        tracing::warn!(
+                            host,
+                            failures = state.consecutive_failures,
+                            "Circuit opened after repeated upstream failures"
+                        );
+                    }
+                    state.open_until = Some(now + cooldown);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COOLDOWN: Duration = Duration::from_secs(30);
+
+    #[test]
+    fn test_opens_after_threshold_and_recovers() {
+        let breakers = CircuitBreakers::new();
+        let start = Instant::now();
+        let failure = || Some("Search failed: 503 Service Unavailable".to_string());
+
+        breakers.record("x.atlassian.net", failure(), 3, COOLDOWN, start);
+        breakers.record("x.atlassian.net", failure(), 3, COOLDOWN, start);
+        assert!(breakers.check("x.atlassian.net", start).is_ok());
+
+        breakers.record("x.atlassian.net", failure(), 3, COOLDOWN, start);
+        let error = breakers.check("x.atlassian.net", start).unwrap_err();
+        assert!(error.to_string().starts_with("Atlassian appears down: 3"));
+        assert!(error.to_string().contains("503 Service Unavailable"));
+        assert!(breakers.check("other.atlassian.net", start).is_ok());
+
+        // After the cool-down one failure reopens it, one success closes it
+        let later = start + Duration::from_secs(31);
+        assert!(breakers.check("x.atlassian.net", later).is_ok());
+        breakers.record("x.atlassian.net", failure(), 3, COOLDOWN, later);
+        assert!(breakers.check("x.atlassian.net", later).is_err());
+
+        let much_later = later + Duration::from_secs(31);
+        breakers.record("x.atlassian.net", None, 3, COOLDOWN, much_later);
+        breakers.record("x.atlassian.net", failure(), 3, COOLDOWN, much_later);
+        assert!(breakers.check("x.atlassian.net", much_later).is_ok());
+    }
+
+    #[test]
+    fn test_upstream_failures() {
+        assert!(is_upstream_failure(&anyhow::anyhow!(
+            "Failed to get issue: 502 Bad Gateway"
+        )));
+        assert!(is_upstream_failure(&anyhow::anyhow!(
+            "Tool jira_search timed out after 30000 ms"
+        )));
+        assert!(!is_upstream_failure(&anyhow::anyhow!(
+            "Failed to get issue: 404 Not Found"
+        )));
+    }
+}
@@ -0,0 +1,132 @@
+//! Minimal RFC 4180 CSV support
+//!
+//! Quoted fields may contain commas, doubled quotes and line breaks; CRLF and
+//! LF line endings are both accepted and blank lines are skipped. Output
+//! quotes only the fields that need it and ends every record with LF.
+
+use anyhow::Result;
+
+/// Records of `text`, the header included
+pub fn parse(text: &str) -> Result<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            '"' => anyhow::bail!("line {}: quote inside an unquoted field", line),
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                line += 1;
+                record.push(std::mem::take(&mut field));
+                if record.iter().any(|f| !f.is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                } else {
+                    record.clear();
+                }
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        anyhow::bail!("line {}: unterminated quoted field", line);
+    }
+    record.push(field);
+    if record.iter().any(|f| !f.is_empty()) {
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn write_field(out: &mut String, field: &str) {
+    if field.contains([',', '"', '\n', '\r']) {
+        out.push('"');
+        out.push_str(&field.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(field);
+    }
+}
+
+/// CSV text for `records`
+pub fn write<I, R, S>(records: I) -> String
+where
+    I: IntoIterator<Item = R>,
+    R: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut out = String::new();
+    for record in records {
+        for (i, field) in record.into_iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            write_field(&mut out, field.as_ref());
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quoted_fields() {
+        let text =
+            "Summary,Labels\r\n\"Fix \"\"login\"\" bug\",\"ui,auth\"\n\nMultiline,\"a\nb\"\n";
+        let records = parse(text).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[1], vec!["Fix \"login\" bug", "ui,auth"]);
+        assert_eq!(records[2], vec!["Multiline", "a\nb"]);
+    }
+
+    #[test]
+    fn test_parse_without_trailing_newline_and_empty_fields() {
+        let records = parse("\u{feff}a,,c\nd,e,").unwrap();
+        assert_eq!(records[0], vec!["a", "", "c"]);
+        assert_eq!(records[1], vec!["d", "e", ""]);
+    }
+
+    #[test]
+    fn test_write_round_trips() {
+        let records = vec![
+            vec!["key", "summary"],
+            vec!["PROJ-1", "Fix \"login\", again"],
+            vec!["PROJ-2", "two\nlines"],
+        ];
+        let text = write(&records);
+        assert_eq!(
+            text.lines().nth(1),
+            Some("PROJ-1,\"Fix \"\"login\"\", again\"")
+        );
+        assert_eq!(parse(&text).unwrap(), records);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("a,\"open\nb").is_err());
+        assert!(parse("a,b\"c\"").is_err());
+    }
+}
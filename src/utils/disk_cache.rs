@@ -0,0 +1,198 @@
+//! On-disk cache for responses that stay valid across restarts
+//!
+//! With `ATLASSIAN_CACHE_DIR` set, callers keep entries under
+//! `<dir>/<site>/<namespace>/<key>`: the site's cloud ID (`cloud-id`),
+//! plain-text page renderings by version (`page-text`) and, with
+//! `ATLASSIAN_CACHE_TTL_SECS`, GET responses (`responses`). Reads refresh an
+//! entry's modification time; after each write the least recently used
+//! entries (of every site) are removed until the directory is within
+//! `ATLASSIAN_CACHE_MAX_BYTES`. Failures are logged and never fail a call.
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::config::Config;
+
+pub struct DiskCache {
+    root: PathBuf,
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+/// Keys become file names: other characters (path separators included) are
+/// replaced
+fn file_name(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .trim_start_matches('.')
+        .to_string()
+}
+
+impl DiskCache {
+    /// The configured site's `namespace`, when `ATLASSIAN_CACHE_DIR` is set
+    pub fn from_config(config: &Config, namespace: &str) -> Option<Self> {
+        let site = config
+            .get_atlassian_base_url()
+            .trim_start_matches("https://");
+        Some(Self::new(
+            config.cache_dir.as_ref()?,
+            site,
+            namespace,
+            config.cache_max_bytes,
+        ))
+    }
+
+    pub fn new(root: impl Into<PathBuf>, site: &str, namespace: &str, max_bytes: u64) -> Self {
+        let root = root.into();
+        Self {
+            dir: root.join(file_name(site)).join(namespace),
+            root,
+            max_bytes,
+        }
+    }
+
+    fn path(&self, key: &str) -> Option<PathBuf> {
+        let name = file_name(key);
+        (!name.is_empty()).then(|| self.dir.join(name))
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        let path = self.path(key)?;
+        let value = std::fs::read_to_string(&path).ok()?;
+        // The modification time is the eviction order
+        if let Ok(file) = std::fs::File::options().append(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+        Some(value)
+    }
+
+    pub fn put(&self, key: &str, value: &str) {
+        let Some(path) = self.path(key) else {
+            return;
+        };
+        if let Err(e) = write_atomically(&path, value) {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to write cache entry");
+            return;
+        }
+        if let Err(e) = self.evict() {
+            tracing::warn!(dir = %self.root.display(), error = %e, "Failed to trim the cache");
+        }
+    }
+
+    /// Removes this namespace's entries whose key matches
+    pub fn remove_where(&self, matches: impl Fn(&str) -> bool) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            if matches(&entry.file_name().to_string_lossy()) {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    /// Oldest entries first until the whole cache fits `max_bytes` (0 = no limit)
+    fn evict(&self) -> Result<()> {
+        if self.max_bytes == 0 {
+            return Ok(());
+        }
+        let mut files = Vec::new();
+        collect_files(&self.root, &mut files)?;
+        let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+        if total <= self.max_bytes {
+            return Ok(());
+        }
+        files.sort_by_key(|(_, _, modified)| *modified);
+        for (path, size, _) in files {
+            if total <= self.max_bytes {
+                break;
+            }
+            if std::fs::remove_file(&path).is_ok() {
+                total -= size;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_atomically(path: &Path, value: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    // Readers never see a half-written entry
+    let partial = path.with_extension("partial");
+    std::fs::write(&partial, value)?;
+    std::fs::rename(&partial, path)?;
+    Ok(())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<(PathBuf, u64, SystemTime)>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else {
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            files.push((entry.path(), metadata.len(), modified));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn cache(name: &str, max_bytes: u64) -> DiskCache {
+        let root = std::env::temp_dir().join(format!("mcp-cache-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        DiskCache::new(root, "site", "test", max_bytes)
+    }
+
+    #[test]
+    fn test_round_trip_and_key_names() {
+        let cache = cache("keys", 0);
+        cache.put("42-3.json", "text");
+        assert_eq!(cache.get("42-3.json").as_deref(), Some("text"));
+        assert_eq!(cache.get("missing"), None);
+
+        cache.put("../escape", "x");
+        assert!(cache.dir.join("_escape").exists());
+        assert!(!cache.root.join("site").join("escape").exists());
+
+        cache.remove_where(|name| name.starts_with("42-"));
+        assert_eq!(cache.get("42-3.json"), None);
+        let _ = std::fs::remove_dir_all(&cache.root);
+    }
+
+    #[test]
+    fn test_eviction_removes_least_recently_used() {
+        let cache = cache("evict", 10);
+        let past = SystemTime::now() - Duration::from_secs(60);
+        cache.put("a", "aaaa");
+        cache.put("b", "bbbb");
+        for key in ["a", "b"] {
+            let file = std::fs::File::options()
+                .append(true)
+                .open(cache.path(key).unwrap())
+                .unwrap();
+            file.set_modified(past).unwrap();
+        }
+        // Reading `a` makes `b` the oldest
+        assert!(cache.get("a").is_some());
+        cache.put("c", "cccc");
+        assert_eq!(cache.get("b"), None);
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("c").is_some());
+        let _ = std::fs::remove_dir_all(&cache.root);
+    }
+}
@@ -1,12 +1,67 @@
 use crate::config::Config;
-use reqwest::Client;
-use std::time::Duration;
+use crate::utils::disk_cache::DiskCache;
+use anyhow::{Context, Result};
+use reqwest::{Certificate, Client, NoProxy, Proxy};
+use std::sync::Mutex;
+
+/// Parsed `ATLASSIAN_CA_CERT_PATH` bundle, keyed by path
+static CA_CERTS: Mutex<Option<(String, Vec<Certificate>)>> = Mutex::new(None);
+
+/// Loads the PEM bundle at `path` (cached after the first read)
+pub fn load_ca_certificates(path: &str) -> Result<Vec<Certificate>> {
+    let mut cache = CA_CERTS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((cached, certs)) = cache.as_ref()
+        && cached == path
+    {
+        return Ok(certs.clone());
+    }
+
+    let pem = std::fs::read(path).with_context(|| format!("Failed to read CA bundle {}", path))?;
+    let certs = Certificate::from_pem_bundle(&pem)
+        .with_context(|| format!("Invalid PEM in CA bundle {}", path))?;
+    if certs.is_empty() {
+        anyhow::bail!("CA bundle {} contains no certificates", path);
+    }
+    *cache = Some((path.to_string(), certs.clone()));
+    Ok(certs)
+}
+
+/// Explicit proxy from `HTTPS_PROXY`, bypassed for hosts in `NO_PROXY`
+pub fn build_proxy(config: &Config) -> Result<Option<Proxy>> {
+    let Some(url) = &config.https_proxy else {
+        return Ok(None);
+    };
+    let proxy = Proxy::all(url.as_str()).with_context(|| format!("Invalid HTTPS_PROXY {}", url))?;
+    Ok(Some(proxy.no_proxy(
+        config.no_proxy.as_deref().and_then(NoProxy::from_string),
+    )))
+}
 
 pub fn create_atlassian_client(config: &Config) -> Client {
-    Client::builder()
-        .timeout(Duration::from_millis(config.request_timeout_ms))
-        .build()
-        .expect("Failed to create HTTP client")
+    let mut builder = Client::builder().timeout(config.request_timeout());
+
+    // Both were checked by `Config::validate`; a failure here means the file
+    // changed since, and the client falls back to the default trust store
+    match build_proxy(config) {
+        Ok(Some(proxy)) => builder = builder.proxy(proxy),
+        Ok(None) => {}
+        Err(e) => tracing::warn!("{:#}", e),
+    }
+    if let Some(path) = &config.ca_cert_path {
+        match load_ca_certificates(path) {
+            Ok(certs) => {
+                for cert in certs {
+                    builder = builder.add_root_certificate(cert);
+                }
+            }
+            Err(e) => tracing::warn!("{:#}", e),
+        }
+    }
+    if config.insecure_skip_verify {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    builder.build().expect("Failed to create HTTP client")
 }
 
 pub fn create_auth_header(config: &Config) -> String {
@@ -15,27 +70,111 @@ pub fn create_auth_header(config: &Config) -> String {
     format!("Basic {}", STANDARD.encode(credentials))
 }
 
+/// Bitbucket Cloud auth: app password when configured, else the Atlassian API token
+pub fn create_bitbucket_auth_header(config: &Config) -> String {
+    use base64::{Engine as _, engine::general_purpose::STANDARD};
+    let credentials = match (&config.bitbucket_username, &config.bitbucket_app_password) {
+        (Some(username), Some(password)) => format!("{}:{}", username, password),
+        _ => format!("{}:{}", config.atlassian_email, config.atlassian_api_token),
+    };
+    format!("Basic {}", STANDARD.encode(credentials))
+}
+
+/// Cloud ID per site, resolved once per process (or once per
+/// `ATLASSIAN_CACHE_DIR`)
+static CLOUD_IDS: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+/// Cloud ID of the configured site, needed for `api.atlassian.com` APIs
+pub async fn fetch_cloud_id(client: &Client, config: &Config) -> Result<String> {
+    let base_url = config.get_atlassian_base_url();
+    {
+        let cache = CLOUD_IDS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((_, id)) = cache.iter().find(|(site, _)| *site == base_url) {
+            crate::mcp::stats::record_cache_hit();
+            return Ok(id.clone());
+        }
+    }
+
+    let disk = DiskCache::from_config(config, "cloud-id");
+    let id = match disk.as_ref().and_then(|disk| disk.get("cloud-id")) {
+        Some(id) => {
+            crate::mcp::stats::record_cache_hit();
+            id
+        }
+        None => {
+            let id = request_cloud_id(client, base_url).await?;
+            if let Some(disk) = &disk {
+                disk.put("cloud-id", &id);
+            }
+            id
+        }
+    };
+
+    CLOUD_IDS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push((base_url.to_string(), id.clone()));
+    Ok(id)
+}
+
+async fn request_cloud_id(client: &Client, base_url: &str) -> Result<String> {
+    let response = client
+        .get(format!("{}/_edge/tenant_info", base_url))
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to resolve cloud ID: {}", response.status());
+    }
+    let data: serde_json::Value = response.json().await?;
+    data["cloudId"]
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| anyhow::anyhow!("tenant_info returned no cloudId"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::ConfigBuilder;
 
     fn create_test_config(email: &str, token: &str, timeout_ms: u64) -> Config {
-        Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: email.to_string(),
-            atlassian_api_token: token.to_string(),
-            request_timeout_ms: timeout_ms,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        }
+        ConfigBuilder::new()
+            .with(|config| {
+                config.atlassian_email = email.to_string();
+                config.atlassian_api_token = token.to_string();
+                config.request_timeout_ms = timeout_ms;
+            })
+            .build()
     }
 
     // T019: HTTP Utils tests
 
+    #[test]
+    fn test_build_proxy() {
+        let mut config = create_test_config("test@example.com", "token123", 30000);
+        assert!(build_proxy(&config).unwrap().is_none());
+
+        config.https_proxy = Some("http://proxy.corp:3128".to_string());
+        config.no_proxy = Some("localhost,.internal".to_string());
+        assert!(build_proxy(&config).unwrap().is_some());
+        let client = create_atlassian_client(&config);
+        assert!(format!("{:?}", client).contains("proxy.corp"));
+
+        config.https_proxy = Some("::not a url".to_string());
+        assert!(build_proxy(&config).is_err());
+    }
+
+    #[test]
+    fn test_load_ca_certificates_rejects_non_pem() {
+        let path = std::env::temp_dir().join(format!("mcp-ca-{}.pem", std::process::id()));
+        std::fs::write(&path, "not a certificate").unwrap();
+        let err = load_ca_certificates(path.to_str().unwrap()).unwrap_err();
+        assert!(err.to_string().contains("no certificates"));
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(load_ca_certificates("/nonexistent/ca.pem").is_err());
+    }
+
     #[test]
     fn test_create_atlassian_client_success() {
         let config = create_test_config("test@example.com", "token123", 30000);
@@ -120,4 +259,26 @@ mod tests {
 
         assert_ne!(header1, header2);
     }
+
+    #[test]
+    fn test_create_bitbucket_auth_header_prefers_app_password() {
+        use base64::{Engine as _, engine::general_purpose::STANDARD};
+        let mut config = create_test_config("test@example.com", "token123", 30000);
+
+        let decode =
+            |header: String| String::from_utf8(STANDARD.decode(&header[6..]).unwrap()).unwrap();
+
+        // Falls back to the Atlassian credentials
+        assert_eq!(
+            decode(create_bitbucket_auth_header(&config)),
+            "test@example.com:token123"
+        );
+
+        config.bitbucket_username = Some("bbuser".to_string());
+        config.bitbucket_app_password = Some("apppass".to_string());
+        assert_eq!(
+            decode(create_bitbucket_auth_header(&config)),
+            "bbuser:apppass"
+        );
+    }
 }
@@ -0,0 +1,121 @@
+//! Jira issue key detection in free text
+//!
+//! A key is a project key (an uppercase letter followed by uppercase letters,
+//! digits or underscores) joined by `-` to an issue number, e.g. `PROJ-123`.
+//! Keys must stand alone: `xPROJ-1`, `PROJ-1a` and `PROJ-01` are not keys.
+
+/// Distinct issue keys in `text`, in order of first appearance
+pub fn find_issue_keys(text: &str) -> Vec<String> {
+    let bytes = text.as_bytes();
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut keys: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_uppercase() || (i > 0 && is_word(bytes[i - 1])) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        let mut j = i + 1;
+        while j < bytes.len()
+            && (bytes[j].is_ascii_uppercase() || bytes[j].is_ascii_digit() || bytes[j] == b'_')
+        {
+            j += 1;
+        }
+        let digits_start = j + 1;
+        let mut end = digits_start;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+
+        let is_key = j - start >= 2
+            && bytes.get(j) == Some(&b'-')
+            && end > digits_start
+            && bytes[digits_start] != b'0'
+            && !bytes.get(end).is_some_and(|&b| is_word(b));
+        if is_key {
+            let key = &text[start..end];
+            if !keys.iter().any(|k| k == key) {
+                keys.push(key.to_string());
+            }
+            i = end;
+        } else {
+            i = j;
+        }
+    }
+    keys
+}
+
+/// Canonical form of an `issue_key` argument: trimmed, uppercased, and taken
+/// out of a pasted link (`.../browse/PROJ-1`, `...?selectedIssue=PROJ-1`).
+/// Numeric issue IDs pass through, since the API accepts them as well.
+pub fn normalize_issue_key(input: &str) -> Result<String, String> {
+    let trimmed = input.trim();
+    let candidate = if let Some((_, rest)) = trimmed.split_once("/browse/") {
+        rest.split(['/', '?', '#']).next().unwrap_or_default()
+    } else if let Some((_, rest)) = trimmed.split_once("selectedIssue=") {
+        rest.split(['&', '#']).next().unwrap_or_default()
+    } else {
+        trimmed
+    };
+
+    if !candidate.is_empty() && candidate.bytes().all(|b| b.is_ascii_digit()) {
+        return Ok(candidate.to_string());
+    }
+    let key = candidate.to_ascii_uppercase();
+    if find_issue_keys(&key) == [key.as_str()] {
+        Ok(key)
+    } else {
+        Err(format!(
+            "'{}' is not an issue key; expected e.g. PROJ-123 or a .../browse/PROJ-123 link",
+            input
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_issue_keys() {
+        assert_eq!(
+            find_issue_keys("Fixed PROJ-12 and (OPS_2-7); see PROJ-12 again, AB-3."),
+            vec!["PROJ-12", "OPS_2-7", "AB-3"]
+        );
+    }
+
+    #[test]
+    fn test_find_issue_keys_rejects_lookalikes() {
+        assert!(find_issue_keys("UTF-8x xPROJ-1 PROJ-1a PROJ-01 A-1 proj-1 PROJ- -12").is_empty());
+    }
+
+    #[test]
+    fn test_normalize_issue_key() {
+        assert_eq!(
+            normalize_issue_key(" proj-12 \n"),
+            Ok("PROJ-12".to_string())
+        );
+        assert_eq!(
+            normalize_issue_key("https://x.atlassian.net/browse/OPS_2-7?focusedCommentId=1"),
+            Ok("OPS_2-7".to_string())
+        );
+        assert_eq!(
+            normalize_issue_key(
+                "https://x.atlassian.net/jira/software/projects/AB/boards/1?selectedIssue=AB-3"
+            ),
+            Ok("AB-3".to_string())
+        );
+        assert_eq!(normalize_issue_key("10042"), Ok("10042".to_string()));
+        for bad in [
+            "",
+            "PROJ",
+            "PROJ-01",
+            "PROJ-1 PROJ-2",
+            "https://x.atlassian.net/wiki/x",
+        ] {
+            assert!(normalize_issue_key(bad).is_err(), "{}", bad);
+        }
+    }
+}
@@ -1,4 +1,6 @@
 use std::env;
+use std::io::{self, Write};
+use std::sync::RwLock;
 use tracing::info;
 use tracing_subscriber::{
     EnvFilter,
@@ -7,6 +9,49 @@ use tracing_subscriber::{
     util::SubscriberInitExt,
 };
 
+/// Shorter values are too likely to match ordinary text to be masked
+const MIN_SECRET_LEN: usize = 6;
+
+static SECRETS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Masks `secret` in all further log output
+pub fn register_secret(secret: &str) {
+    let secret = secret.trim();
+    if secret.len() < MIN_SECRET_LEN {
+        return;
+    }
+    let mut secrets = SECRETS.write().unwrap_or_else(|e| e.into_inner());
+    if !secrets.iter().any(|s| s == secret) {
+        secrets.push(secret.to_string());
+    }
+}
+
+/// `text` with every registered secret replaced by `[REDACTED]`
+pub fn redact_secrets(text: &str) -> String {
+    let secrets = SECRETS.read().unwrap_or_else(|e| e.into_inner());
+    secrets.iter().fold(text.to_string(), |text, secret| {
+        text.replace(secret.as_str(), "[REDACTED]")
+    })
+}
+
+/// Stderr writer that redacts registered secrets; the fmt layer writes each
+/// event in one call, so a secret is never split across writes
+struct RedactingStderr;
+
+impl Write for RedactingStderr {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match std::str::from_utf8(buf) {
+            Ok(text) => io::stderr().write_all(redact_secrets(text).as_bytes())?,
+            Err(_) => io::stderr().write_all(buf)?,
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stderr().flush()
+    }
+}
+
 pub fn init_logging() {
     let log_level = env::var("LOG_LEVEL").unwrap_or_else(|_| "warn".to_string());
 
@@ -22,7 +67,7 @@ pub fn init_logging() {
         .with_thread_names(false)
         .with_span_events(FmtSpan::CLOSE)
         .with_ansi(false)
-        .with_writer(std::io::stderr);
+        .with_writer(|| RedactingStderr);
 
     let json_logs = env::var("JSON_LOGS")
         .unwrap_or_else(|_| "false".to_string())
@@ -85,6 +130,10 @@ pub fn log_startup(config: &crate::config::Config) {
         atlassian_domain = %config.atlassian_domain,
         "MCP Atlassian server starting (stdio mode)"
     );
+
+    if config.trello_api_key.is_some() && !config.trello_enabled() {
+        tracing::warn!("TRELLO_API_KEY is set but this build lacks the `trello` feature");
+    }
 }
 
 pub fn log_shutdown() {
@@ -95,20 +144,10 @@ pub fn log_shutdown() {
 mod tests {
     use super::*;
     use crate::config::Config;
+    use crate::test_support::ConfigBuilder;
 
     fn create_test_config() -> Config {
-        Config {
-            atlassian_domain: "test.atlassian.net".to_string(),
-            atlassian_email: "test@example.com".to_string(),
-            atlassian_api_token: "token123".to_string(),
-            request_timeout_ms: 30000,
-            jira_projects_filter: vec![],
-            confluence_spaces_filter: vec![],
-            jira_search_default_fields: None,
-            jira_search_custom_fields: vec![],
-            response_exclude_fields: None,
-            base_url: "https://test.atlassian.net".to_string(),
-        }
+        ConfigBuilder::new().build()
     }
 
     // T019: Logging tests
@@ -148,4 +187,22 @@ mod tests {
 
         // If we get here, all macros expanded successfully
     }
+
+    #[test]
+    fn test_redact_secrets() {
+        register_secret("ATATT-logging-test-token");
+        register_secret("abc");
+        assert_eq!(
+            redact_secrets("GET https://x?token=ATATT-logging-test-token failed (abc)"),
+            "GET https://x?token=[REDACTED] failed (abc)"
+        );
+    }
+
+    #[test]
+    fn test_config_debug_redacts_token() {
+        let config = create_test_config();
+        let debug = format!("{:?}", config);
+        assert!(debug.contains("test.atlassian.net"));
+        assert!(!debug.contains("test-token"));
+    }
 }
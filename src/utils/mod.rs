@@ -1,2 +1,8 @@
+pub mod circuit_breaker;
+pub mod csv;
+pub mod disk_cache;
 pub mod http_utils;
+pub mod issue_keys;
 pub mod logging;
+pub mod sha256;
+pub mod time;
@@ -0,0 +1,688 @@
+//! Dates in the user's timezone (`ATLASSIAN_TIMEZONE`)
+//!
+//! Jira and Confluence answer in UTC or the account's profile zone, which is
+//! rarely what the person asking has in mind. With a timezone configured,
+//! every timestamp string in a tool result (`2026-10-14T08:00:00.000+0000`,
+//! `...Z`) is rewritten to that zone ([`localize_timestamps`]), and date
+//! inputs such as `tomorrow`, `next friday` or `2026-10-20 17:00` are read in
+//! it ([`jira_date`]).
+//!
+//! Zones are fixed offsets (`UTC`, `+09:00`, `-0530`) or IANA names read
+//! from the system zoneinfo database (`TZDIR`, default `/usr/share/zoneinfo`),
+//! including the POSIX rule that covers dates after the last transition.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::Config;
+
+const DAY: i64 = 86_400;
+
+/// Days since 1970-01-01 of a proleptic Gregorian date
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let month = month as i64;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// `(year, month, day)` of a day count since 1970-01-01
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// 0 = Sunday
+fn weekday(days: i64) -> i64 {
+    (days + 4).rem_euclid(7)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    let next = if month == 12 {
+        days_from_civil(year + 1, 1, 1)
+    } else {
+        days_from_civil(year, month + 1, 1)
+    };
+    (next - days_from_civil(year, month, 1)) as u32
+}
+
+/// `Mm.w.d/time` of a POSIX TZ rule: weekday `d` of week `w` (5 = last)
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RuleDate {
+    month: u32,
+    week: u32,
+    weekday: i64,
+    /// Local seconds after midnight (may exceed a day or be negative)
+    time: i64,
+}
+
+impl RuleDate {
+    /// Local seconds since the epoch at which the rule fires in `year`
+    fn local_instant(&self, year: i64) -> i64 {
+        let first = days_from_civil(year, self.month, 1);
+        let mut day =
+            1 + (self.weekday - weekday(first)).rem_euclid(7) + (self.week as i64 - 1) * 7;
+        while day > days_in_month(year, self.month) as i64 {
+            day -= 7;
+        }
+        (first + day - 1) * DAY + self.time
+    }
+}
+
+/// Footer rule of a TZif file, e.g. `CET-1CEST,M3.5.0,M10.5.0/3`
+#[derive(Debug, Clone, PartialEq)]
+struct PosixRule {
+    /// Seconds east of UTC
+    std_offset: i32,
+    dst: Option<(i32, RuleDate, RuleDate)>,
+}
+
+impl PosixRule {
+    fn parse(rule: &str) -> Option<Self> {
+        let mut rest = rule;
+        skip_zone_name(&mut rest)?;
+        let std_offset = -parse_posix_offset(&mut rest)?;
+        if rest.is_empty() {
+            return Some(Self {
+                std_offset,
+                dst: None,
+            });
+        }
+        skip_zone_name(&mut rest)?;
+        let dst_offset = if rest.starts_with(',') {
+            std_offset + 3600
+        } else {
+            -parse_posix_offset(&mut rest)?
+        };
+        let (start, end) = rest.strip_prefix(',')?.split_once(',')?;
+        Some(Self {
+            std_offset,
+            dst: Some((dst_offset, parse_rule_date(start)?, parse_rule_date(end)?)),
+        })
+    }
+
+    fn offset_at(&self, utc: i64) -> i32 {
+        let Some((dst_offset, start, end)) = self.dst else {
+            return self.std_offset;
+        };
+        let (year, _, _) = civil_from_days((utc + self.std_offset as i64).div_euclid(DAY));
+        // DST starts in standard time and ends in daylight time
+        let start = start.local_instant(year) - self.std_offset as i64;
+        let end = end.local_instant(year) - dst_offset as i64;
+        let in_dst = if start < end {
+            (start..end).contains(&utc)
+        } else {
+            !(end..start).contains(&utc)
+        };
+        if in_dst { dst_offset } else { self.std_offset }
+    }
+}
+
+fn skip_zone_name(rest: &mut &str) -> Option<()> {
+    let len = if let Some(quoted) = rest.strip_prefix('<') {
+        quoted.find('>')? + 2
+    } else {
+        rest.find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len())
+    };
+    (len >= 3).then(|| *rest = &rest[len..])
+}
+
+/// `[+-]hh[:mm[:ss]]`, west-positive as POSIX writes it
+fn parse_posix_offset(rest: &mut &str) -> Option<i32> {
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, ':' | '+' | '-')))
+        .unwrap_or(rest.len());
+    let seconds = parse_hms(&rest[..end])?;
+    *rest = &rest[end..];
+    Some(seconds as i32)
+}
+
+fn parse_hms(text: &str) -> Option<i64> {
+    let (sign, text) = match text.as_bytes().first()? {
+        b'-' => (-1, &text[1..]),
+        b'+' => (1, &text[1..]),
+        _ => (1, text),
+    };
+    let mut parts = text.split(':');
+    let hours: i64 = parts.next()?.parse().ok()?;
+    let minutes: i64 = parts.next().map_or(Some(0), |m| m.parse().ok())?;
+    let seconds: i64 = parts.next().map_or(Some(0), |s| s.parse().ok())?;
+    Some(sign * (hours * 3600 + minutes * 60 + seconds))
+}
+
+fn parse_rule_date(text: &str) -> Option<RuleDate> {
+    let (date, time) = match text.split_once('/') {
+        Some((date, time)) => (date, parse_hms(time)?),
+        None => (text, 7200),
+    };
+    let mut parts = date.strip_prefix('M')?.split('.');
+    let month = parts.next()?.parse().ok()?;
+    let week = parts.next()?.parse().ok()?;
+    let weekday = parts.next()?.parse().ok()?;
+    ((1..=12).contains(&month) && (1..=5).contains(&week) && (0..=6).contains(&weekday)).then_some(
+        RuleDate {
+            month,
+            week,
+            weekday,
+            time,
+        },
+    )
+}
+
+#[derive(Debug)]
+enum Kind {
+    Fixed(i32),
+    Zone {
+        /// `(utc instant, offset from then on)`, ascending
+        transitions: Vec<(i64, i32)>,
+        /// Offset before the first transition
+        initial: i32,
+        rule: Option<PosixRule>,
+    },
+}
+
+#[derive(Debug)]
+pub struct Timezone {
+    pub name: String,
+    kind: Kind,
+}
+
+impl Timezone {
+    /// `UTC`, `+09:00`/`-0530`, or an IANA name from the zoneinfo database
+    pub fn parse(name: &str) -> Result<Self> {
+        let name = name.trim();
+        if ["UTC", "Z", "GMT", "Etc/UTC"].contains(&name) {
+            return Ok(Self {
+                name: name.to_string(),
+                kind: Kind::Fixed(0),
+            });
+        }
+        if let Some(offset) = parse_offset(name) {
+            return Ok(Self {
+                name: name.to_string(),
+                kind: Kind::Fixed(offset),
+            });
+        }
+        if name.is_empty() || name.contains("..") || name.starts_with('/') {
+            anyhow::bail!("Invalid timezone '{}'", name);
+        }
+        let dir = std::env::var("TZDIR").unwrap_or_else(|_| "/usr/share/zoneinfo".to_string());
+        let path = std::path::Path::new(&dir).join(name);
+        let data = std::fs::read(&path).with_context(|| {
+            format!(
+                "Unknown timezone '{}' (not found in {}); use an IANA name or an offset like +09:00",
+                name, dir
+            )
+        })?;
+        let kind = parse_tzif(&data)
+            .with_context(|| format!("Unreadable zoneinfo file for '{}'", name))?;
+        Ok(Self {
+            name: name.to_string(),
+            kind,
+        })
+    }
+
+    /// Seconds east of UTC at a UTC instant
+    pub fn offset_at(&self, utc: i64) -> i32 {
+        match &self.kind {
+            Kind::Fixed(offset) => *offset,
+            Kind::Zone {
+                transitions,
+                initial,
+                rule,
+            } => match transitions.partition_point(|(at, _)| *at <= utc) {
+                0 => *initial,
+                n if n == transitions.len() && rule.is_some() => {
+                    rule.as_ref().map_or(*initial, |rule| rule.offset_at(utc))
+                }
+                n => transitions[n - 1].1,
+            },
+        }
+    }
+}
+
+/// `+09:00`, `+0900`, `-05`, `UTC+9`
+fn parse_offset(text: &str) -> Option<i32> {
+    let text = text.strip_prefix("UTC").unwrap_or(text);
+    let sign = match text.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let digits: String = text[1..].chars().filter(|c| *c != ':').collect();
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = match digits.len() {
+        1 | 2 => (digits.parse::<i32>().ok()?, 0),
+        4 => (digits[..2].parse().ok()?, digits[2..].parse().ok()?),
+        _ => return None,
+    };
+    (hours <= 14 && minutes < 60).then_some(sign * (hours * 3600 + minutes * 60))
+}
+
+fn parse_tzif(data: &[u8]) -> Option<Kind> {
+    fn counts(header: &[u8]) -> Option<[usize; 6]> {
+        if header.get(..4)? != b"TZif" {
+            return None;
+        }
+        let mut counts = [0; 6];
+        for (i, count) in counts.iter_mut().enumerate() {
+            let at = 20 + i * 4;
+            *count = u32::from_be_bytes(header.get(at..at + 4)?.try_into().ok()?) as usize;
+        }
+        Some(counts)
+    }
+    fn block_len([isut, isstd, leap, time, types, chars]: [usize; 6], time_size: usize) -> usize {
+        time * time_size + time + types * 6 + chars + leap * (time_size + 4) + isstd + isut
+    }
+
+    let v1 = counts(data)?;
+    let version = *data.get(4)?;
+    let (body, time_size, counts, footer_at) = if version >= b'2' {
+        let v2_at = 44 + block_len(v1, 4);
+        let v2 = counts(data.get(v2_at..)?)?;
+        let body_at = v2_at + 44;
+        (data.get(body_at..)?, 8, v2, body_at + block_len(v2, 8))
+    } else {
+        (data.get(44..)?, 4, v1, data.len())
+    };
+    let [_, _, _, time_count, type_count, _] = counts;
+
+    let time_at = |i: usize| -> Option<i64> {
+        let bytes = body.get(i * time_size..(i + 1) * time_size)?;
+        Some(if time_size == 8 {
+            i64::from_be_bytes(bytes.try_into().ok()?)
+        } else {
+            i32::from_be_bytes(bytes.try_into().ok()?) as i64
+        })
+    };
+    let indexes_at = time_count * time_size;
+    let types_at = indexes_at + time_count;
+    let type_offset = |i: usize| -> Option<(i32, bool)> {
+        let at = types_at + i * 6;
+        let bytes = body.get(at..at + 6)?;
+        Some((
+            i32::from_be_bytes(bytes[..4].try_into().ok()?),
+            bytes[4] != 0,
+        ))
+    };
+    if type_count == 0 {
+        return None;
+    }
+
+    let mut transitions = Vec::with_capacity(time_count);
+    for i in 0..time_count {
+        let index = *body.get(indexes_at + i)? as usize;
+        transitions.push((time_at(i)?, type_offset(index)?.0));
+    }
+    let initial = (0..type_count)
+        .filter_map(type_offset)
+        .find(|(_, is_dst)| !is_dst)
+        .or_else(|| type_offset(0))?
+        .0;
+    let rule = data
+        .get(footer_at..)
+        .and_then(|footer| std::str::from_utf8(footer).ok())
+        .map(|footer| footer.trim_matches('\n'))
+        .filter(|footer| !footer.is_empty())
+        .and_then(PosixRule::parse);
+    Some(Kind::Zone {
+        transitions,
+        initial,
+        rule,
+    })
+}
+
+/// Zones loaded so far, by name
+static ZONES: Mutex<Vec<Arc<Timezone>>> = Mutex::new(Vec::new());
+
+/// The configured timezone (UTC when unset), loaded once per name
+pub fn user_timezone(config: &Config) -> Result<Arc<Timezone>> {
+    let name = config.timezone.as_deref().unwrap_or("UTC");
+    {
+        let zones = ZONES.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(zone) = zones.iter().find(|zone| zone.name == name.trim()) {
+            return Ok(zone.clone());
+        }
+    }
+    let zone = Arc::new(Timezone::parse(name)?);
+    ZONES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(zone.clone());
+    Ok(zone)
+}
+
+/// `(utc seconds, milliseconds)` of `YYYY-MM-DDTHH:MM[:SS[.fff]]` followed
+/// by `Z` or an offset; anything else (dates, local times) is `None`
+pub fn parse_timestamp(text: &str) -> Option<(i64, u32)> {
+    let bytes = text.as_bytes();
+    if bytes.len() < 17 || bytes[4] != b'-' || bytes[7] != b'-' || bytes[10] != b'T' {
+        return None;
+    }
+    let number = |range: std::ops::Range<usize>| -> Option<i64> {
+        let part = text.get(range)?;
+        part.bytes()
+            .all(|b| b.is_ascii_digit())
+            .then(|| part.parse().ok())?
+    };
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute) = (number(11..13)?, number(14..16)?);
+    if bytes[13] != b':' || !(1..=12).contains(&month) || day < 1 || hour > 23 || minute > 59 {
+        return None;
+    }
+    if day > days_in_month(year, month as u32) as i64 {
+        return None;
+    }
+
+    let mut at = 16;
+    let mut second = 0;
+    let mut millis = 0;
+    if bytes.get(at) == Some(&b':') {
+        second = number(at + 1..at + 3)?;
+        at += 3;
+        if bytes.get(at) == Some(&b'.') {
+            let digits = bytes[at + 1..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+            if digits == 0 {
+                return None;
+            }
+            let fraction = &text[at + 1..at + 1 + digits.min(3)];
+            millis = format!("{:0<3}", fraction).parse().ok()?;
+            at += 1 + digits;
+        }
+    }
+    if second > 60 {
+        return None;
+    }
+
+    let offset = match text.get(at..)? {
+        "Z" => 0,
+        zone => parse_offset(zone)?,
+    };
+    let local =
+        days_from_civil(year, month as u32, day as u32) * DAY + hour * 3600 + minute * 60 + second;
+    Some((local - offset as i64, millis))
+}
+
+/// Jira's timestamp form in a zone: `2026-10-14T17:00:00.000+0900`
+pub fn format_timestamp(utc: i64, millis: u32, zone: &Timezone) -> String {
+    let offset = zone.offset_at(utc);
+    let local = utc + offset as i64;
+    let (year, month, day) = civil_from_days(local.div_euclid(DAY));
+    let seconds = local.rem_euclid(DAY);
+    let sign = if offset < 0 { '-' } else { '+' };
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}{}{:02}{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60,
+        millis,
+        sign,
+        offset.abs() / 3600,
+        offset.abs() % 3600 / 60
+    )
+}
+
+/// Rewrites every timestamp string in `value` into `zone`
+pub fn localize_timestamps(value: &mut Value, zone: &Timezone) {
+    match value {
+        Value::String(text) => {
+            if let Some((utc, millis)) = parse_timestamp(text) {
+                *text = format_timestamp(utc, millis, zone);
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| localize_timestamps(item, zone)),
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|item| localize_timestamps(item, zone)),
+        _ => {}
+    }
+}
+
+/// Current Unix time in seconds
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+const WEEKDAYS: [&str; 7] = [
+    "sunday",
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+];
+
+/// Local day (days since the epoch) meant by a date input, read in `zone`
+/// at `now`: `today`, `tomorrow`, `yesterday`, `in 3 days`, `in 2 weeks`,
+/// `+5d`, `friday`/`next friday`, `YYYY-MM-DD[ HH:MM]` or a full timestamp
+pub fn local_day(input: &str, zone: &Timezone, now: i64) -> Option<i64> {
+    let text = input.trim().to_ascii_lowercase();
+    let today = (now + zone.offset_at(now) as i64).div_euclid(DAY);
+
+    if let Some((utc, _)) = parse_timestamp(input.trim()) {
+        return Some((utc + zone.offset_at(utc) as i64).div_euclid(DAY));
+    }
+    let date = text.split([' ', 't']).next().unwrap_or_default();
+    if date.len() == 10 && date.as_bytes()[4] == b'-' && date.as_bytes()[7] == b'-' {
+        let year = date[..4].parse().ok()?;
+        let month = date[5..7].parse().ok()?;
+        let day = date[8..10].parse().ok()?;
+        let valid = (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month);
+        return valid.then(|| days_from_civil(year, month, day));
+    }
+
+    match text.as_str() {
+        "today" | "now" => return Some(today),
+        "tomorrow" => return Some(today + 1),
+        "yesterday" => return Some(today - 1),
+        _ => {}
+    }
+    let relative = text
+        .strip_prefix("in ")
+        .or_else(|| text.strip_prefix('+'))
+        .map(|rest| rest.trim());
+    if let Some(rest) = relative {
+        let digits = rest.bytes().take_while(|b| b.is_ascii_digit()).count();
+        let count: i64 = rest[..digits].parse().ok()?;
+        let unit = match rest[digits..].trim() {
+            "d" | "day" | "days" => 1,
+            "w" | "week" | "weeks" => 7,
+            _ => return None,
+        };
+        return Some(today + count * unit);
+    }
+    let name = text.strip_prefix("next ").unwrap_or(&text);
+    let target = WEEKDAYS
+        .iter()
+        .position(|day| *day == name || &day[..3] == name)? as i64;
+    let ahead = (target - weekday(today)).rem_euclid(7);
+    Some(today + if ahead == 0 { 7 } else { ahead })
+}
+
+/// The instant (Unix seconds) a `since` filter means: a full timestamp, a
+/// window before `now` (`30m`, `12h`, `7d`, `2w`), or the start in `zone` of
+/// any day [`local_day`] understands
+pub fn since_instant(input: &str, zone: &Timezone, now: i64) -> Option<i64> {
+    let input = input.trim();
+    if let Some((utc, _)) = parse_timestamp(input) {
+        return Some(utc);
+    }
+    let (amount, unit) = input.split_at(input.len().saturating_sub(1));
+    if !amount.is_empty() && amount.bytes().all(|b| b.is_ascii_digit()) {
+        let seconds = match unit {
+            "m" => 60,
+            "h" => 3600,
+            "d" => DAY,
+            "w" => 7 * DAY,
+            _ => return None,
+        };
+        return Some(now - amount.parse::<i64>().ok()? * seconds);
+    }
+    let midnight = local_day(input, zone, now)? * DAY;
+    Some(midnight - zone.offset_at(midnight) as i64)
+}
+
+/// `YYYY-MM-DD` for a date field such as `duedate`, from any input
+/// [`local_day`] understands
+pub fn jira_date(input: &str, zone: &Timezone) -> Result<String> {
+    let day = local_day(input, zone, now()).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid date '{}': expected YYYY-MM-DD, today, tomorrow, in N days, or a weekday",
+            input
+        )
+    })?;
+    let (year, month, day) = civil_from_days(day);
+    Ok(format!("{:04}-{:02}-{:02}", year, month, day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_civil_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2024, 2, 29), 19_782);
+        for days in [-1, 0, 19_782, 20_740, 100_000] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+        assert_eq!(weekday(days_from_civil(2026, 10, 14)), 3);
+    }
+
+    #[test]
+    fn test_parse_and_format_timestamps() {
+        let (utc, millis) = parse_timestamp("2026-10-14T08:30:00.123+0000").unwrap();
+        assert_eq!(millis, 123);
+        assert_eq!(
+            parse_timestamp("2026-10-14T17:30:00.123+09:00"),
+            Some((utc, 123))
+        );
+        assert_eq!(parse_timestamp("2026-10-14T08:30:00Z"), Some((utc, 0)));
+        assert_eq!(
+            format_timestamp(utc, millis, &Timezone::parse("+09:00").unwrap()),
+            "2026-10-14T17:30:00.123+0900"
+        );
+        for not_timestamp in [
+            "2026-10-14",
+            "2026-10-14T08:30:00",
+            "2026-13-01T00:00Z",
+            "PROJ-1",
+        ] {
+            assert!(
+                parse_timestamp(not_timestamp).is_none(),
+                "{}",
+                not_timestamp
+            );
+        }
+    }
+
+    #[test]
+    fn test_posix_rule_dst() {
+        let rule = PosixRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+        let summer = parse_timestamp("2030-07-01T12:00:00Z").unwrap().0;
+        let winter = parse_timestamp("2030-12-01T12:00:00Z").unwrap().0;
+        assert_eq!(rule.offset_at(summer), 7200);
+        assert_eq!(rule.offset_at(winter), 3600);
+        // 2030-03-31 01:00 UTC is 02:00 CET, when CEST begins
+        let start = parse_timestamp("2030-03-31T01:00:00Z").unwrap().0;
+        assert_eq!(rule.offset_at(start - 1), 3600);
+        assert_eq!(rule.offset_at(start), 7200);
+
+        let south = PosixRule::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+        assert_eq!(south.offset_at(winter), 39_600);
+        assert_eq!(south.offset_at(summer), 36_000);
+        assert_eq!(
+            PosixRule::parse("<+09>-9").unwrap().offset_at(summer),
+            32_400
+        );
+    }
+
+    #[test]
+    fn test_zoneinfo_when_installed() {
+        let Ok(zone) = Timezone::parse("Europe/Berlin") else {
+            return;
+        };
+        let summer = parse_timestamp("2026-07-01T12:00:00Z").unwrap().0;
+        assert_eq!(zone.offset_at(summer), 7200);
+        assert_eq!(zone.offset_at(summer + 180 * DAY), 3600);
+        assert!(Timezone::parse("Mars/Olympus").is_err());
+    }
+
+    #[test]
+    fn test_localize_timestamps() {
+        let zone = Timezone::parse("-05:00").unwrap();
+        let mut result = json!({"issue": {"fields": {"created": "2026-10-14T03:00:00.000+0000",
+            "duedate": "2026-10-20", "summary": "2026-10-14T03:00:00Z"}}});
+        localize_timestamps(&mut result, &zone);
+        assert_eq!(
+            result["issue"]["fields"]["created"],
+            "2026-10-13T22:00:00.000-0500"
+        );
+        assert_eq!(result["issue"]["fields"]["duedate"], "2026-10-20");
+    }
+
+    #[test]
+    fn test_local_day_inputs() {
+        let zone = Timezone::parse("+09:00").unwrap();
+        // Wednesday 2026-10-14 20:00 UTC is already Thursday in +09:00
+        let now = parse_timestamp("2026-10-14T20:00:00Z").unwrap().0;
+        let day = |input: &str| local_day(input, &zone, now).map(civil_from_days);
+        assert_eq!(day("today"), Some((2026, 10, 15)));
+        assert_eq!(day("Tomorrow"), Some((2026, 10, 16)));
+        assert_eq!(day("in 2 weeks"), Some((2026, 10, 29)));
+        assert_eq!(day("+3d"), Some((2026, 10, 18)));
+        assert_eq!(day("next thursday"), Some((2026, 10, 22)));
+        assert_eq!(day("fri"), Some((2026, 10, 16)));
+        assert_eq!(day("2024-07-01 17:00"), Some((2024, 7, 1)));
+        assert_eq!(day("2026-10-14T20:00:00Z"), Some((2026, 10, 15)));
+        assert_eq!(day("2024-02-30"), None);
+        assert_eq!(day("someday"), None);
+    }
+
+    #[test]
+    fn test_since_instant_inputs() {
+        let zone = Timezone::parse("+09:00").unwrap();
+        let now = parse_timestamp("2026-10-14T20:00:00Z").unwrap().0;
+        let since = |input: &str| since_instant(input, &zone, now);
+        assert_eq!(since("2026-10-14T19:00:00Z"), Some(now - 3600));
+        assert_eq!(since("90m"), Some(now - 5400));
+        assert_eq!(since("2d"), Some(now - 2 * DAY));
+        assert_eq!(since("1w"), Some(now - 7 * DAY));
+        // Local midnight of 2026-10-15 in +09:00 is 15:00 UTC the day before
+        assert_eq!(
+            since("today"),
+            parse_timestamp("2026-10-14T15:00:00Z").map(|t| t.0)
+        );
+        assert_eq!(since("7y"), None);
+        assert_eq!(since("soon"), None);
+    }
+}
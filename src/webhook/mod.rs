@@ -0,0 +1,584 @@
+//! Jira webhook receiver
+//!
+//! Optional HTTP endpoint (enabled by `JIRA_WEBHOOK_LISTEN_ADDR`) that accepts
+//! Jira Cloud webhook deliveries and translates issue/comment events into MCP
+//! `notifications/resources/updated` messages for subscribed `jira://` resources.
+//!
+//! When `JIRA_WEBHOOK_PUBLIC_URL` is set, the webhook is registered in Jira at
+//! startup (admin permission required). `JIRA_WEBHOOK_SECRET` is appended to the
+//! registered URL as `?token=` and checked on every delivery; without it the
+//! receiver only binds loopback addresses, so unauthenticated deliveries can
+//! only come from the same machine.
+
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::mcp::notifications::Notifier;
+use crate::mcp::subscriptions::{ResourceSubscriptions, issue_uri, project_uri};
+use crate::utils::http_utils::{create_atlassian_client, create_auth_header};
+
+/// Path the receiver accepts deliveries on
+pub const WEBHOOK_PATH: &str = "/webhooks/jira";
+
+/// Events requested when registering the webhook
+pub const WEBHOOK_EVENTS: &[&str] = &[
+    "jira:issue_created",
+    "jira:issue_updated",
+    "jira:issue_deleted",
+    "comment_created",
+    "comment_updated",
+    "comment_deleted",
+];
+
+/// Maximum accepted delivery size (Jira payloads are typically < 100KB)
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+/// Maximum size of the request line plus headers
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+/// A connection that has not sent a whole request by then is answered 408
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Issue-level event extracted from a webhook payload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookEvent {
+    pub event: String,
+    pub issue_key: String,
+    pub project_key: Option<String>,
+}
+
+impl WebhookEvent {
+    /// Parses a Jira webhook payload. Returns `None` for events without an issue.
+    pub fn from_payload(payload: &Value) -> Option<Self> {
+        let issue_key = payload["issue"]["key"].as_str()?.to_string();
+        let project_key = payload["issue"]["fields"]["project"]["key"]
+            .as_str()
+            .map(String::from)
+            .or_else(|| issue_key.split('-').next().map(String::from));
+
+        Some(Self {
+            event: payload["webhookEvent"]
+                .as_str()
+                .unwrap_or("unknown")
+                .to_string(),
+            issue_key,
+            project_key,
+        })
+    }
+
+    /// Resource URIs affected by this event (issue first, then project)
+    pub fn resource_uris(&self) -> Vec<String> {
+        let mut uris = vec![issue_uri(&self.issue_key)];
+        if let Some(ref project) = self.project_key {
+            uris.push(project_uri(project));
+        }
+        uris
+    }
+}
+
+/// Minimal parsed HTTP/1.1 request
+#[derive(Debug)]
+pub(crate) struct HttpRequest {
+    pub(crate) method: String,
+    pub(crate) target: String,
+    headers: Vec<(String, String)>,
+    pub(crate) body: Vec<u8>,
+}
+
+impl HttpRequest {
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub(crate) fn path(&self) -> &str {
+        self.target.split('?').next().unwrap_or("")
+    }
+
+    pub(crate) fn query_param(&self, name: &str) -> Option<String> {
+        let url = reqwest::Url::parse(&format!("http://localhost{}", self.target)).ok()?;
+        url.query_pairs()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.into_owned())
+    }
+}
+
+pub(crate) async fn read_http_request<R: AsyncRead + Unpin>(reader: R) -> Result<HttpRequest> {
+    let mut reader = BufReader::new(reader);
+    let mut header_bytes = 0;
+    let mut line = String::new();
+
+    reader.read_line(&mut line).await?;
+    header_bytes += line.len();
+    let mut parts = line.split_whitespace();
+    let method = parts.next().context("Missing HTTP method")?.to_string();
+    let target = parts.next().context("Missing request target")?.to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        let n = reader.read_line(&mut line).await?;
+        header_bytes += n;
+        if header_bytes > MAX_HEADER_BYTES {
+            anyhow::bail!("Request headers too large");
+        }
+        let trimmed = line.trim_end();
+        if n == 0 || trimmed.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = trimmed.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let content_length = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+        .map(|(_, v)| v.parse::<usize>())
+        .transpose()
+        .context("Invalid Content-Length")?
+        .unwrap_or(0);
+
+    if content_length > MAX_BODY_BYTES {
+        anyhow::bail!("Request body too large: {} bytes", content_length);
+    }
+
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body).await?;
+
+    Ok(HttpRequest {
+        method,
+        target,
+        headers,
+        body,
+    })
+}
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+/// Webhook receiver state shared across connections
+pub struct WebhookListener {
+    subscriptions: Arc<ResourceSubscriptions>,
+    notifier: Notifier,
+    secret: Option<String>,
+}
+
+impl WebhookListener {
+    pub fn new(
+        config: &Config,
+        subscriptions: Arc<ResourceSubscriptions>,
+        notifier: Notifier,
+    ) -> Self {
+        Self {
+            subscriptions,
+            notifier,
+            secret: config.jira_webhook_secret.clone(),
+        }
+    }
+
+    /// Accepts connections until the listener fails or the task is aborted
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> Result<()> {
+        info!(
+            "Jira webhook receiver listening on {}{}",
+            listener.local_addr()?,
+            WEBHOOK_PATH
+        );
+
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let this = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = this.handle_connection(stream).await {
+                    debug!("Webhook connection from {} failed: {}", peer, e);
+                }
+            });
+        }
+    }
+
+    async fn handle_connection(&self, mut stream: TcpStream) -> Result<()> {
+        let read = tokio::time::timeout(READ_TIMEOUT, read_http_request(&mut stream)).await;
+        let (status, reason) = match read {
+            Ok(Ok(request)) => self.handle_request(&request),
+            Ok(Err(e)) => {
+                warn!("Rejected malformed webhook request: {}", e);
+                (400, "Bad Request")
+            }
+            Err(_) => {
+                debug!("Webhook request not received within {:?}", READ_TIMEOUT);
+                (408, "Request Timeout")
+            }
+        };
+
+        let response = format!(
+            "HTTP/1.1 {} {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            status, reason
+        );
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await?;
+        Ok(())
+    }
+
+    fn handle_request(&self, request: &HttpRequest) -> (u16, &'static str) {
+        if request.path() != WEBHOOK_PATH {
+            return (404, "Not Found");
+        }
+        if request.method == "GET" {
+            return (200, "OK"); // Reachability probe
+        }
+        if request.method != "POST" {
+            return (405, "Method Not Allowed");
+        }
+
+        if let Some(ref secret) = self.secret {
+            let token = request
+                .query_param("token")
+                .or_else(|| request.header("X-Webhook-Token").map(String::from));
+            if !token.is_some_and(|t| constant_time_eq(&t, secret)) {
+                warn!("Rejected webhook delivery with missing or invalid token");
+                return (401, "Unauthorized");
+            }
+        }
+
+        match serde_json::from_slice::<Value>(&request.body) {
+            Ok(payload) => {
+                self.dispatch(&payload);
+                (200, "OK")
+            }
+            Err(e) => {
+                warn!("Rejected webhook delivery with invalid JSON: {}", e);
+                (400, "Bad Request")
+            }
+        }
+    }
+
+    /// Emits `resources/updated` for every subscribed URI affected by the payload.
+    /// Returns the number of notifications sent.
+    pub fn dispatch(&self, payload: &Value) -> usize {
+        if self.subscriptions.is_empty() {
+            return 0;
+        }
+        let Some(event) = WebhookEvent::from_payload(payload) else {
+            debug!("Ignoring webhook event without issue");
+            return 0;
+        };
+
+        let subscribed = self.subscriptions.subscribed_among(&event.resource_uris());
+        debug!(
+            event = %event.event,
+            issue = %event.issue_key,
+            subscribers = subscribed.len(),
+            "Received Jira webhook event"
+        );
+
+        subscribed
+            .iter()
+            .filter(|uri| self.notifier.resource_updated(uri))
+            .count()
+    }
+}
+
+/// URL Jira should deliver to, including the shared secret when configured
+pub fn delivery_url(config: &Config) -> Option<String> {
+    let public = config.jira_webhook_public_url.as_ref()?;
+    let mut url = format!("{}{}", public.trim_end_matches('/'), WEBHOOK_PATH);
+    if let Some(ref secret) = config.jira_webhook_secret {
+        let mut parsed = reqwest::Url::parse(&url).ok()?;
+        parsed.query_pairs_mut().append_pair("token", secret);
+        url = parsed.to_string();
+    }
+    Some(url)
+}
+
+/// Registration body for `POST /rest/webhooks/1.0/webhook`
+pub fn registration_body(config: &Config, url: &str) -> Value {
+    let jql = if config.jira_projects_filter.is_empty() {
+        String::new()
+    } else {
+        let projects = config
+            .jira_projects_filter
+            .iter()
+            .map(|p| format!("\"{}\"", p))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("project IN ({})", projects)
+    };
+
+    json!({
+        "name": "mcp-atlassian",
+        "url": url,
+        "events": WEBHOOK_EVENTS,
+        "filters": { "issue-related-events-section": jql },
+        "excludeBody": false
+    })
+}
+
+/// Registers the webhook in Jira unless one with the same URL already exists.
+/// Returns the webhook's self URL.
+pub async fn register_webhook(config: &Config) -> Result<String> {
+    let url = delivery_url(config).context("JIRA_WEBHOOK_PUBLIC_URL not configured")?;
+    let client = create_atlassian_client(config);
+    let endpoint = format!(
+        "{}/rest/webhooks/1.0/webhook",
+        config.get_atlassian_base_url()
+    );
+
+    let response = client
+        .get(&endpoint)
+        .header("Authorization", create_auth_header(config))
+        .header("Accept", "application/json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to list webhooks: {}", response.status());
+    }
+
+    let existing: Value = response.json().await?;
+    if let Some(hook) = existing
+        .as_array()
+        .and_then(|hooks| hooks.iter().find(|h| h["url"].as_str() == Some(&url)))
+    {
+        return Ok(hook["self"].as_str().unwrap_or_default().to_string());
+    }
+
+    let response = client
+        .post(&endpoint)
+        .header("Authorization", create_auth_header(config))
+        .header("Content-Type", "application/json")
+        .json(&registration_body(config, &url))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let error = response.text().await?;
+        anyhow::bail!("Failed to register webhook: {}", error);
+    }
+
+    let data: Value = response.json().await?;
+    Ok(data["self"].as_str().unwrap_or_default().to_string())
+}
+
+/// Errors when the receiver would accept unauthenticated deliveries from
+/// other machines
+fn check_exposure(addr: SocketAddr, secret: Option<&str>) -> Result<()> {
+    if secret.is_none() && !addr.ip().is_loopback() {
+        anyhow::bail!(
+            "JIRA_WEBHOOK_SECRET is required to listen on {} (JIRA_WEBHOOK_LISTEN_ADDR); without it only loopback addresses are allowed",
+            addr
+        );
+    }
+    Ok(())
+}
+
+/// Binds the receiver and, if configured, registers the webhook in Jira.
+/// Returns `None` when the receiver is disabled.
+pub async fn start(
+    config: &Config,
+    subscriptions: Arc<ResourceSubscriptions>,
+    notifier: Notifier,
+) -> Result<Option<tokio::task::JoinHandle<()>>> {
+    let Some(ref addr) = config.jira_webhook_listen_addr else {
+        return Ok(None);
+    };
+
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind webhook receiver on {}", addr))?;
+    check_exposure(
+        listener.local_addr()?,
+        config.jira_webhook_secret.as_deref(),
+    )?;
+
+    if config.jira_webhook_public_url.is_some() {
+        match register_webhook(config).await {
+            Ok(id) => info!("Jira webhook registered: {}", id),
+            Err(e) => warn!("Jira webhook registration failed: {}", e),
+        }
+    }
+
+    let receiver = Arc::new(WebhookListener::new(config, subscriptions, notifier));
+    Ok(Some(tokio::spawn(async move {
+        if let Err(e) = receiver.serve(listener).await {
+            warn!("Jira webhook receiver stopped: {}", e);
+        }
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ConfigBuilder;
+
+    fn create_test_config(secret: Option<&str>) -> Config {
+        ConfigBuilder::new()
+            .jira_projects_filter(&["PROJ"])
+            .with(|config| {
+                config.jira_webhook_listen_addr = Some("127.0.0.1:0".to_string());
+                config.jira_webhook_public_url = Some("https://hooks.example.com/".to_string());
+                config.jira_webhook_secret = secret.map(String::from);
+            })
+            .build()
+    }
+
+    fn issue_updated_payload() -> Value {
+        json!({
+            "webhookEvent": "jira:issue_updated",
+            "issue": {
+                "key": "PROJ-42",
+                "fields": {"project": {"key": "PROJ"}}
+            }
+        })
+    }
+
+    #[test]
+    fn test_webhook_event_from_payload() {
+        let event = WebhookEvent::from_payload(&issue_updated_payload()).unwrap();
+        assert_eq!(event.event, "jira:issue_updated");
+        assert_eq!(event.issue_key, "PROJ-42");
+        assert_eq!(
+            event.resource_uris(),
+            vec!["jira://issue/PROJ-42", "jira://project/PROJ"]
+        );
+    }
+
+    #[test]
+    fn test_webhook_event_falls_back_to_key_prefix() {
+        let payload = json!({"webhookEvent": "comment_created", "issue": {"key": "OPS-7"}});
+        let event = WebhookEvent::from_payload(&payload).unwrap();
+        assert_eq!(event.project_key.as_deref(), Some("OPS"));
+    }
+
+    #[test]
+    fn test_webhook_event_without_issue() {
+        let payload = json!({"webhookEvent": "project_created"});
+        assert!(WebhookEvent::from_payload(&payload).is_none());
+    }
+
+    #[test]
+    fn test_dispatch_only_notifies_subscribers() {
+        let config = create_test_config(None);
+        let subs = Arc::new(ResourceSubscriptions::new());
+        let (notifier, mut rx) = Notifier::channel();
+        let listener = WebhookListener::new(&config, subs.clone(), notifier);
+
+        // No subscribers: nothing sent
+        assert_eq!(listener.dispatch(&issue_updated_payload()), 0);
+
+        subs.subscribe("jira://issue/PROJ-42");
+        assert_eq!(listener.dispatch(&issue_updated_payload()), 1);
+        let notification = rx.try_recv().unwrap();
+        assert_eq!(notification.params.unwrap()["uri"], "jira://issue/PROJ-42");
+    }
+
+    #[test]
+    fn test_delivery_url_includes_token() {
+        let config = create_test_config(Some("s3cret"));
+        assert_eq!(
+            delivery_url(&config).unwrap(),
+            "https://hooks.example.com/webhooks/jira?token=s3cret"
+        );
+
+        let config = create_test_config(None);
+        assert_eq!(
+            delivery_url(&config).unwrap(),
+            "https://hooks.example.com/webhooks/jira"
+        );
+    }
+
+    #[test]
+    fn test_registration_body_scopes_to_project_filter() {
+        let config = create_test_config(None);
+        let body = registration_body(&config, "https://hooks.example.com/webhooks/jira");
+        assert_eq!(
+            body["filters"]["issue-related-events-section"],
+            "project IN (\"PROJ\")"
+        );
+        assert_eq!(
+            body["events"].as_array().unwrap().len(),
+            WEBHOOK_EVENTS.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_http_request() {
+        let raw =
+            b"POST /webhooks/jira?token=abc HTTP/1.1\r\nHost: x\r\nContent-Length: 2\r\n\r\n{}";
+        let request = read_http_request(&raw[..]).await.unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path(), "/webhooks/jira");
+        assert_eq!(request.query_param("token").as_deref(), Some("abc"));
+        assert_eq!(request.header("host"), Some("x"));
+        assert_eq!(request.body, b"{}");
+    }
+
+    #[tokio::test]
+    async fn test_read_http_request_rejects_oversized_body() {
+        let raw = format!(
+            "POST /webhooks/jira HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        assert!(read_http_request(raw.as_bytes()).await.is_err());
+    }
+
+    #[test]
+    fn test_receiver_without_secret_stays_on_loopback() {
+        let local: SocketAddr = "127.0.0.1:8787".parse().unwrap();
+        let public: SocketAddr = "0.0.0.0:8787".parse().unwrap();
+        assert!(check_exposure(local, None).is_ok());
+        assert!(check_exposure("[::1]:8787".parse().unwrap(), None).is_ok());
+        let error = check_exposure(public, None).unwrap_err().to_string();
+        assert!(error.contains("JIRA_WEBHOOK_SECRET is required"));
+        assert!(check_exposure(public, Some("s3cret")).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_receiver_end_to_end() {
+        let config = create_test_config(Some("s3cret"));
+        let subs = Arc::new(ResourceSubscriptions::new());
+        subs.subscribe("jira://project/PROJ");
+        let (notifier, mut rx) = Notifier::channel();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let receiver = Arc::new(WebhookListener::new(&config, subs, notifier));
+        let server = tokio::spawn(receiver.serve(listener));
+
+        let client = reqwest::Client::new();
+        let url = format!("http://{}{}", addr, WEBHOOK_PATH);
+
+        // Wrong token is rejected
+        let response = client
+            .post(format!("{}?token=wrong", url))
+            .json(&issue_updated_payload())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 401);
+
+        let response = client
+            .post(format!("{}?token=s3cret", url))
+            .json(&issue_updated_payload())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let notification = rx.recv().await.unwrap();
+        assert_eq!(notification.params.unwrap()["uri"], "jira://project/PROJ");
+
+        server.abort();
+    }
+}
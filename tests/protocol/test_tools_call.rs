@@ -31,6 +31,7 @@ fn test_call_tool_request_jira_search() {
             "jql": "project = TEST AND status = Open",
             "limit": 20
         }),
+        meta: None,
     };
 
     assert_eq!(request.name, "jira_search");
@@ -46,6 +47,7 @@ fn test_call_tool_request_confluence_search() {
             "query": "type=page",
             "limit": 10
         }),
+        meta: None,
     };
 
     assert_eq!(request.name, "confluence_search");
@@ -58,6 +60,8 @@ fn test_call_tool_result_with_text_content() {
         content: vec![ToolContent::Text {
             text: "Search completed successfully".to_string(),
         }],
+        structured_content: None,
+        meta: None,
     };
 
     assert_eq!(result.content.len(), 1);
@@ -83,6 +87,8 @@ fn test_call_tool_result_with_multiple_content() {
                 text: "Result 3".to_string(),
             },
         ],
+        structured_content: None,
+        meta: None,
     };
 
     assert_eq!(result.content.len(), 3);
@@ -94,11 +100,28 @@ fn test_call_tool_result_serialization() {
         content: vec![ToolContent::Text {
             text: "Test output".to_string(),
         }],
+        structured_content: None,
+        meta: None,
     };
 
     let serialized = serde_json::to_string(&result).unwrap();
     assert!(serialized.contains("Test output"));
     assert!(serialized.contains("\"type\":\"text\""));
+    assert!(!serialized.contains("_meta"));
+}
+
+#[test]
+fn test_call_tool_result_with_optimization_meta() {
+    let result = CallToolResult {
+        content: vec![ToolContent::Text {
+            text: "{}".to_string(),
+        }],
+        structured_content: None,
+        meta: Some(json!({"optimization": {"bytes_saved": 120}})),
+    };
+
+    let serialized = serde_json::to_value(&result).unwrap();
+    assert_eq!(serialized["_meta"]["optimization"]["bytes_saved"], 120);
 }
 
 #[test]
@@ -106,6 +129,7 @@ fn test_call_tool_request_serialization() {
     let request = CallToolRequest {
         name: "jira_get_issue".to_string(),
         arguments: json!({"issue_key": "PROJ-123"}),
+        meta: None,
     };
 
     let serialized = serde_json::to_string(&request).unwrap();
@@ -163,6 +187,7 @@ fn test_call_tool_with_complex_arguments() {
                 "labels": ["urgent", "bug"]
             }
         }),
+        meta: None,
     };
 
     assert_eq!(request.name, "jira_update_issue");
@@ -170,3 +195,18 @@ fn test_call_tool_with_complex_arguments() {
     assert_eq!(request.arguments["fields"]["summary"], "Updated summary");
     assert_eq!(request.arguments["fields"]["priority"]["name"], "High");
 }
+
+#[test]
+fn test_call_tool_result_with_structured_content() {
+    let result = CallToolResult {
+        content: vec![ToolContent::Text {
+            text: "{\"success\": true}".to_string(),
+        }],
+        structured_content: Some(json!({"success": true})),
+        meta: None,
+    };
+
+    let serialized = serde_json::to_value(&result).unwrap();
+    assert_eq!(serialized["structuredContent"]["success"], true);
+    assert_eq!(serialized["content"][0]["type"], "text");
+}
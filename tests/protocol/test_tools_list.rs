@@ -37,6 +37,7 @@ fn test_list_tools_result_with_tools() {
                 properties: HashMap::new(),
                 required: vec!["jql".to_string()],
             },
+            output_schema: None,
         },
         Tool {
             name: "confluence_search".to_string(),
@@ -46,6 +47,7 @@ fn test_list_tools_result_with_tools() {
                 properties: HashMap::new(),
                 required: vec!["query".to_string()],
             },
+            output_schema: None,
         },
     ];
 
@@ -61,12 +63,7 @@ fn test_tool_input_schema_structure() {
     let mut properties = HashMap::new();
     properties.insert(
         "jql".to_string(),
-        mcp_atlassian::mcp::types::Property {
-            property_type: json!("string"),
-            description: Some("JQL query string".to_string()),
-            default: None,
-            enum_values: None,
-        },
+        mcp_atlassian::mcp::types::Property::new("string").with_description("JQL query string"),
     );
 
     let schema = ToolInputSchema {
@@ -91,6 +88,7 @@ fn test_tools_list_serialization() {
             properties: HashMap::new(),
             required: vec![],
         },
+        output_schema: None,
     }];
 
     let result = ListToolsResult { tools };
@@ -115,6 +113,7 @@ fn test_tool_with_multiple_required_params() {
                 "param3".to_string(),
             ],
         },
+        output_schema: None,
     };
 
     assert_eq!(tool.input_schema.required.len(), 3);